@@ -0,0 +1,21 @@
+-- AffilAI Database Migration 008
+-- Network Postback Receiver
+-- Description: Log inbound conversion postbacks from affiliate networks
+
+-- Raw log of every postback hit received by the local listener
+CREATE TABLE IF NOT EXISTS postback_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    query_string TEXT NOT NULL,       -- Raw query string as received (secrets stripped)
+    subid TEXT,                       -- Sub ID / click reference sent by the network
+    accepted BOOLEAN DEFAULT 0,       -- Whether a conversion was written
+    conversion_id INTEGER,            -- conversion_events row created, if any
+    error TEXT,                       -- Rejection reason (bad signature, unknown subid, ...)
+    received_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (conversion_id) REFERENCES conversion_events(id) ON DELETE SET NULL
+);
+
+-- Note: conversion_events.source and conversion_events.subid columns are added
+-- in Rust code (schema.rs) with existence checks
+
+CREATE INDEX IF NOT EXISTS idx_postback_log_received ON postback_log(received_at);
+CREATE INDEX IF NOT EXISTS idx_conversions_order_id ON conversion_events(order_id);
@@ -0,0 +1,20 @@
+-- AffilAI Database Migration 009
+-- Off-network Traffic Import
+-- Description: Store UTM-campaign-level sessions pulled from GA4 / Plausible
+
+CREATE TABLE IF NOT EXISTS traffic_sessions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    link_id INTEGER,                  -- Matched affiliate link (NULL when no link matches)
+    provider TEXT NOT NULL,           -- 'ga4', 'plausible'
+    utm_campaign TEXT NOT NULL,
+    utm_source TEXT NOT NULL DEFAULT '',
+    date DATE NOT NULL,
+    sessions INTEGER DEFAULT 0,
+    events INTEGER DEFAULT 0,
+    imported_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (link_id) REFERENCES affiliate_links(id) ON DELETE SET NULL,
+    UNIQUE(provider, utm_campaign, utm_source, date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_traffic_sessions_link ON traffic_sessions(link_id);
+CREATE INDEX IF NOT EXISTS idx_traffic_sessions_date ON traffic_sessions(date);
@@ -0,0 +1,15 @@
+-- AffilAI Database Migration 010
+-- Weekly Digests
+-- Description: Store periodic workspace status summaries
+
+CREATE TABLE IF NOT EXISTS digests (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    summary_json TEXT NOT NULL,       -- Serialized WeeklyDigest metrics
+    summary_text TEXT NOT NULL,       -- Human-readable summary used for notifications
+    notified BOOLEAN DEFAULT 0,       -- Whether an OS notification was shown
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_digests_period_end ON digests(period_end);
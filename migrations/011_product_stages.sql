@@ -0,0 +1,12 @@
+-- AffilAI Database Migration 011
+-- Product Lifecycle Stages
+-- Description: Track each product's position in the promotion pipeline
+-- (researching -> approved -> promoting -> retired)
+-- Note: ALTER TABLE ADD COLUMN is handled in Rust code (schema.rs)
+-- to gracefully handle cases where columns already exist
+
+-- The following statements are handled in schema.rs:
+-- ALTER TABLE products ADD COLUMN stage TEXT DEFAULT 'researching';
+-- ALTER TABLE products ADD COLUMN stage_changed_at DATETIME;
+-- CREATE INDEX IF NOT EXISTS idx_products_stage ON products(stage);
+-- UPDATE products SET stage = 'researching' WHERE stage IS NULL;
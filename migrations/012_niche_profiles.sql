@@ -0,0 +1,17 @@
+-- AffilAI Database Migration 012
+-- Niche / Vertical Profiles
+-- Description: Workspace-level niche settings that tune discovery scoring and ad generation
+
+CREATE TABLE IF NOT EXISTS niche_profiles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,            -- e.g. 'Home Fitness', 'Pet Tech'
+    description TEXT,
+    preferred_platforms TEXT DEFAULT '[]', -- JSON array: ["tiktok", "youtube"]
+    typical_audience TEXT,                -- Fallback audience when a product has none
+    banned_claims TEXT DEFAULT '[]',      -- JSON array of phrases never used in copy
+    active BOOLEAN DEFAULT 0,             -- At most one profile is active
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_niche_profiles_active ON niche_profiles(active);
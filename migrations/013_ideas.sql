@@ -0,0 +1,18 @@
+-- AffilAI Database Migration 013
+-- Ideas Inbox
+-- Description: Lightweight capture of product finds, kept separate from the catalog
+-- until promoted
+
+CREATE TABLE IF NOT EXISTS ideas (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    url TEXT NOT NULL,
+    note TEXT,
+    source TEXT,                      -- 'manual', 'clipboard', 'extension', 'rss', ...
+    status TEXT DEFAULT 'new' CHECK(status IN ('new', 'promoted', 'dismissed')),
+    product_id INTEGER,               -- Product created when the idea was promoted
+    captured_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ideas_status ON ideas(status);
+CREATE INDEX IF NOT EXISTS idx_ideas_url ON ideas(url);
@@ -0,0 +1,8 @@
+-- AffilAI Database Migration 014
+-- Ad Favorites
+-- Description: Let users mark ads worth keeping so bulk cleanup skips them
+-- Note: ALTER TABLE ADD COLUMN is handled in Rust code (schema.rs)
+-- to gracefully handle cases where columns already exist
+
+-- The following statements are handled in schema.rs:
+-- ALTER TABLE ad_copies ADD COLUMN is_favorite INTEGER DEFAULT 0;
@@ -0,0 +1,9 @@
+-- AffilAI Database Migration 015
+-- Product Tags
+-- Description: Free-form tags (JSON array of lowercase strings) used to scope
+-- bulk operations such as link generation
+-- Note: ALTER TABLE ADD COLUMN is handled in Rust code (schema.rs)
+-- to gracefully handle cases where columns already exist
+
+-- The following statements are handled in schema.rs:
+-- ALTER TABLE products ADD COLUMN tags TEXT DEFAULT '[]';
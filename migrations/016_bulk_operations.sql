@@ -0,0 +1,30 @@
+-- AffilAI Database Migration 016
+-- Bulk Operations
+-- Description: Persist bulk link/ad generation runs item by item so they can be
+-- observed while running and resumed after a crash or cancellation
+
+CREATE TABLE IF NOT EXISTS bulk_operations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,                     -- 'links', 'ads', 'enrich', or 'refresh'
+    params_json TEXT NOT NULL DEFAULT '{}', -- Options the run was started with (filter, ad type, ...)
+    status TEXT NOT NULL DEFAULT 'running'
+        CHECK(status IN ('running', 'completed', 'cancelled', 'interrupted')),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS bulk_operation_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    operation_id INTEGER NOT NULL,
+    product_id INTEGER NOT NULL,
+    product_name TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK(status IN ('pending', 'succeeded', 'failed', 'skipped')),
+    message TEXT,          -- Skip reason or error
+    result_id INTEGER,     -- Generated affiliate_links.id / ad_copies.id
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (operation_id) REFERENCES bulk_operations(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_bulk_operation_items_operation ON bulk_operation_items(operation_id, status);
+CREATE INDEX IF NOT EXISTS idx_bulk_operations_status ON bulk_operations(status);
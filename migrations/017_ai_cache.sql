@@ -0,0 +1,16 @@
+-- AffilAI Database Migration 017
+-- AI Response Cache
+-- Description: Reuse provider responses for identical rendered prompts so
+-- repeated discovery for unchanged products doesn't re-spend tokens
+
+CREATE TABLE IF NOT EXISTS ai_cache (
+    cache_key TEXT PRIMARY KEY,      -- SHA-256 of provider + model + rendered prompt
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    response TEXT NOT NULL,
+    hit_count INTEGER DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    expires_at DATETIME NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_cache_expires ON ai_cache(expires_at);
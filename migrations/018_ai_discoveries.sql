@@ -0,0 +1,20 @@
+-- AffilAI Database Migration 018
+-- AI Discovery Log
+-- Description: Record every provider-backed discovery with its raw output and
+-- parse/validation diagnostics so malformed model output is visible instead of
+-- failing silently
+
+CREATE TABLE IF NOT EXISTS ai_discoveries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    product_id INTEGER NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    valid BOOLEAN NOT NULL DEFAULT 0, -- Final response passed schema validation
+    attempts INTEGER NOT NULL DEFAULT 1, -- Initial call + repair re-prompts
+    raw_response TEXT,                   -- Last response received
+    diagnostics_json TEXT NOT NULL DEFAULT '[]', -- One entry per failed attempt
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_discoveries_product ON ai_discoveries(product_id, created_at);
@@ -0,0 +1,10 @@
+-- AffilAI Database Migration 019
+-- AI Provider Fallback
+-- Description: Record which provider actually served each discovery (primary,
+-- secondary, or the local mock) and why earlier providers in the chain failed
+-- Note: ALTER TABLE ADD COLUMN is handled in Rust code (schema.rs)
+-- to gracefully handle cases where columns already exist
+
+-- The following statements are handled in schema.rs:
+-- ALTER TABLE ai_discoveries ADD COLUMN served_by TEXT;
+-- ALTER TABLE ai_discoveries ADD COLUMN fallback_errors_json TEXT DEFAULT '[]';
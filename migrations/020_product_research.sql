@@ -0,0 +1,19 @@
+-- AffilAI Database Migration 020
+-- Product Research Briefs
+-- Description: Store AI research briefs per product so ad generation can draw
+-- on real pain points and benefits instead of category boilerplate
+
+CREATE TABLE IF NOT EXISTS product_research (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    product_id INTEGER NOT NULL,
+    pain_points TEXT NOT NULL DEFAULT '[]',        -- JSON array of strings
+    objections TEXT NOT NULL DEFAULT '[]',         -- JSON array of strings
+    key_benefits TEXT NOT NULL DEFAULT '[]',       -- JSON array of strings
+    comparison_targets TEXT NOT NULL DEFAULT '[]', -- JSON array of strings
+    content_angles TEXT NOT NULL DEFAULT '[]',     -- JSON array of strings
+    served_by TEXT NOT NULL,                       -- Provider that produced the brief ('mock' for heuristics)
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_product_research_product ON product_research(product_id, created_at);
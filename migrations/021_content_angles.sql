@@ -0,0 +1,18 @@
+-- AffilAI Database Migration 021
+-- Content Angles
+-- Description: Reusable creative hooks per product (problem-agitate-solve,
+-- social proof, comparison, unboxing, ...) that ad generation can take as its
+-- creative direction
+
+CREATE TABLE IF NOT EXISTS content_angles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    product_id INTEGER NOT NULL,
+    angle_type TEXT NOT NULL,  -- 'problem_agitate_solve', 'social_proof', 'comparison', 'unboxing', ...
+    hook TEXT NOT NULL,        -- Opening line, used as the ad headline
+    direction TEXT NOT NULL,   -- Creative direction passed to ad generation
+    served_by TEXT NOT NULL,   -- Provider that produced the angle ('mock' for heuristics)
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_content_angles_product ON content_angles(product_id);
@@ -0,0 +1,18 @@
+-- AffilAI Database Migration 022
+-- Review Insights
+-- Description: Quotable social-proof snippets and common objections distilled
+-- from customer reviews, for use in ad copy
+
+CREATE TABLE IF NOT EXISTS review_insights (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    product_id INTEGER NOT NULL,
+    kind TEXT NOT NULL CHECK(kind IN ('snippet', 'objection')),
+    text TEXT NOT NULL,          -- Verbatim quote, or the objection as a short statement
+    theme TEXT,                  -- What the snippet/objection is about ('price', 'quality', ...)
+    mentions INTEGER DEFAULT 1,  -- Reviews raising the objection (1 for snippets)
+    served_by TEXT NOT NULL,     -- Provider that produced the insight ('mock' for heuristics)
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_insights_product ON review_insights(product_id, kind);
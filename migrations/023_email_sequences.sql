@@ -0,0 +1,12 @@
+-- AffilAI Database Migration 023
+-- Email Sequences
+-- Description: Multi-step email nurture sequences stored as ordered child ads
+-- of a sequence ad, each with a send delay relative to the previous message
+-- Note: ALTER TABLE ADD COLUMN is handled in Rust code (schema.rs)
+-- to gracefully handle cases where columns already exist
+
+-- The following statements are handled in schema.rs:
+-- ALTER TABLE ad_copies ADD COLUMN parent_ad_id INTEGER REFERENCES ad_copies(id) ON DELETE CASCADE;
+-- ALTER TABLE ad_copies ADD COLUMN sequence_step INTEGER;
+-- ALTER TABLE ad_copies ADD COLUMN send_delay_days INTEGER;
+-- CREATE INDEX IF NOT EXISTS idx_ad_copies_parent ON ad_copies(parent_ad_id, sequence_step);
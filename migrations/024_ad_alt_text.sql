@@ -0,0 +1,8 @@
+-- AffilAI Database Migration 024
+-- Ad Alt Text
+-- Description: Accessible alt text for the product image of visual ads
+-- Note: ALTER TABLE ADD COLUMN is handled in Rust code (schema.rs)
+-- to gracefully handle cases where columns already exist
+
+-- The following statements are handled in schema.rs:
+-- ALTER TABLE ad_copies ADD COLUMN alt_text TEXT;
@@ -0,0 +1,22 @@
+-- AffilAI Database Migration 025
+-- Ad Critiques
+-- Description: Rubric scores for generated ads (1-10 per criterion) so weak
+-- copy can be filtered or regenerated
+
+CREATE TABLE IF NOT EXISTS ad_critiques (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ad_id INTEGER NOT NULL,
+    hook_strength INTEGER NOT NULL,
+    clarity INTEGER NOT NULL,
+    cta INTEGER NOT NULL,
+    specificity INTEGER NOT NULL,
+    compliance INTEGER NOT NULL,
+    overall REAL NOT NULL,                 -- Mean of the five criteria
+    suggestions TEXT NOT NULL DEFAULT '[]', -- JSON array of strings
+    served_by TEXT NOT NULL,               -- Provider that scored the ad ('mock' for heuristics)
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (ad_id) REFERENCES ad_copies(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_ad_critiques_ad ON ad_critiques(ad_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_ad_critiques_overall ON ad_critiques(overall);
@@ -0,0 +1,18 @@
+-- AffilAI Database Migration 026
+-- Headline Variants
+-- Description: Alternative headlines for an ad, ranked by heuristic CTR
+-- signals; the selected variant is copied onto the ad's headline
+
+CREATE TABLE IF NOT EXISTS headline_variants (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ad_id INTEGER NOT NULL,
+    headline TEXT NOT NULL,
+    ctr_score REAL NOT NULL,             -- 0-100 heuristic click-through signal score
+    signals TEXT NOT NULL DEFAULT '{}',  -- JSON breakdown of the score
+    is_selected INTEGER NOT NULL DEFAULT 0,
+    served_by TEXT NOT NULL,             -- Provider that wrote the headline ('current' for the ad's own headline)
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (ad_id) REFERENCES ad_copies(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_headline_variants_ad ON headline_variants(ad_id, ctr_score);
@@ -0,0 +1,17 @@
+-- AffilAI Database Migration 027
+-- Ad Templates
+-- Description: User-authored ad templates with {{merge_tags}} for instant,
+-- deterministic "quick ads" that skip the AI provider
+
+CREATE TABLE IF NOT EXISTS ad_templates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    ad_type TEXT NOT NULL DEFAULT 'social_post',
+    headline TEXT NOT NULL,   -- e.g. 'Why I love {{product_name}}'
+    body TEXT NOT NULL,
+    cta TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_ad_templates_name ON ad_templates(name);
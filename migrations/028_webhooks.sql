@@ -0,0 +1,28 @@
+-- AffilAI Database Migration 028
+-- Outgoing Webhooks
+-- Description: User-configured webhook endpoints (Zapier, Make, n8n, ...)
+-- notified on workspace events, plus a delivery log
+
+CREATE TABLE IF NOT EXISTS webhooks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    url TEXT NOT NULL,
+    secret TEXT,                      -- HMAC-SHA256 signing key for X-AffilAI-Signature
+    events TEXT NOT NULL DEFAULT '[]', -- JSON array of event names; empty = every event
+    active BOOLEAN DEFAULT 1,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    webhook_id INTEGER NOT NULL,
+    event TEXT NOT NULL,
+    status_code INTEGER,              -- HTTP status, NULL when the request failed
+    success BOOLEAN DEFAULT 0,
+    error TEXT,
+    delivered_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, delivered_at);
@@ -0,0 +1,22 @@
+-- AffilAI Database Migration 032
+-- Affiliate Program Applications
+-- Description: Signup status per affiliate program (including ones only seen
+-- in discovery results), with dates, contact notes, and follow-up reminders
+
+CREATE TABLE IF NOT EXISTS program_applications (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    program_name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    platform TEXT,
+    status TEXT NOT NULL DEFAULT 'not_applied', -- not_applied, applied, approved, rejected
+    signup_url TEXT,
+    applied_at DATETIME,
+    decided_at DATETIME,              -- When the network approved or rejected the application
+    contact_name TEXT,
+    contact_email TEXT,
+    notes TEXT,
+    remind_at DATETIME,               -- Next follow-up reminder while the application is pending
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_program_applications_reminders ON program_applications(status, remind_at);
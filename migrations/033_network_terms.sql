@@ -0,0 +1,48 @@
+-- AffilAI Database Migration 033
+-- Network Terms Reference Data
+-- Description: Payout threshold, payment schedule, cookie policy, and
+-- deep-link support per affiliate network. Shipped rows are a starting point
+-- (networks change their terms) and can be edited; INSERT OR IGNORE keeps
+-- the user's edits across upgrades.
+
+CREATE TABLE IF NOT EXISTS network_terms (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    network TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    platform TEXT,                    -- Discovery platform this network backs, e.g. 'amazon'
+    min_payout REAL,                  -- Balance needed before a payment is sent
+    payout_currency TEXT DEFAULT 'USD',
+    payment_schedule TEXT,
+    cookie_days INTEGER,              -- Typical cookie window; NULL when set per merchant
+    cookie_policy TEXT,
+    deep_link_support BOOLEAN,
+    notes TEXT,
+    builtin BOOLEAN DEFAULT 0,        -- Shipped with the app; editable but not deletable
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT OR IGNORE INTO network_terms
+    (network, platform, min_payout, payout_currency, payment_schedule, cookie_days, cookie_policy,
+     deep_link_support, notes, builtin)
+VALUES
+    ('Amazon Associates', 'amazon', 10, 'USD', 'Monthly, about 60 days after the end of the month', 1,
+     '24 hours; 90 days for items added to cart', 1,
+     'Account closes if no qualifying sale within 180 days of joining', 1),
+    ('TikTok Shop Affiliate', 'tiktok', NULL, 'USD', 'Commission settles after the order completes and the return window closes', 7,
+     'Attribution window set by TikTok Shop per campaign', 0,
+     'Links are generated per product inside TikTok; no custom deep links', 1),
+    ('YouTube Shopping', 'youtube', 100, 'USD', 'Monthly through AdSense once the threshold is reached', NULL,
+     'Set by the participating retailer', 0,
+     'Requires YouTube Partner Program membership', 1),
+    ('eBay Partner Network', NULL, 10, 'USD', 'Monthly', 1,
+     '24 hours from the click', 1, NULL, 1),
+    ('CJ Affiliate', NULL, 50, 'USD', 'Monthly, around the 20th', NULL,
+     'Set by each advertiser', 1, 'Cheque payouts need a higher balance than direct deposit', 1),
+    ('Impact', NULL, 10, 'USD', 'Per brand contract, usually monthly', NULL,
+     'Set by each brand', 1, 'Payout threshold is chosen in the partner account', 1),
+    ('Rakuten Advertising', NULL, 50, 'USD', 'Monthly, once the advertiser has paid Rakuten', NULL,
+     'Set by each advertiser', 1, NULL, 1),
+    ('Awin', NULL, 20, 'USD', 'Twice monthly (1st and 15th)', NULL,
+     'Set by each advertiser, commonly 30 days', 1, 'ShareASale merchants have moved to Awin', 1),
+    ('ClickBank', NULL, 10, 'USD', 'Weekly or every two weeks', 60,
+     '60 days from the last hoplink click', 0, 'Hoplinks point at the vendor''s sales page', 1);
@@ -0,0 +1,23 @@
+-- AffilAI Database Migration 037
+-- Scheduled Posts
+-- Description: Posting calendar. A post with a recurrence rule heads a series
+-- that the scheduler expands into concrete dated occurrences.
+
+CREATE TABLE IF NOT EXISTS scheduled_posts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    product_id INTEGER NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    ad_id INTEGER REFERENCES ad_copies(id) ON DELETE SET NULL,
+    platform TEXT NOT NULL,
+    scheduled_at DATETIME NOT NULL,             -- UTC
+    status TEXT NOT NULL DEFAULT 'scheduled',   -- scheduled, posted, skipped, cancelled
+    notes TEXT,
+    repeat_every_days INTEGER,                  -- Recurrence interval; set on series heads only
+    repeat_until DATETIME,                      -- Last time the series may run; NULL repeats indefinitely
+    rotate_ads INTEGER NOT NULL DEFAULT 1,      -- Cycle through the product's ads across occurrences
+    series_id INTEGER REFERENCES scheduled_posts(id) ON DELETE CASCADE, -- Head this occurrence was expanded from
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_posts_calendar ON scheduled_posts(platform, scheduled_at);
+CREATE INDEX IF NOT EXISTS idx_scheduled_posts_series ON scheduled_posts(series_id, scheduled_at);
@@ -0,0 +1,40 @@
+-- AffilAI Database Migration 038
+-- Retail Events
+-- Description: Holidays and shopping events that drive campaign suggestions
+-- and seasonality. An event falls on a fixed day (month/day) or on the nth
+-- weekday of a month, optionally shifted by offset_days (Black Friday is the
+-- day after the fourth Thursday of November). Shipped rows can be edited or
+-- disabled; INSERT OR IGNORE keeps the user's edits across upgrades.
+
+CREATE TABLE IF NOT EXISTS retail_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    month INTEGER NOT NULL,               -- 1-12
+    day INTEGER,                          -- Fixed day of the month
+    weekday INTEGER,                      -- 0 = Sunday; used with week_of_month when day is NULL
+    week_of_month INTEGER,                -- 1-5, or -1 for the last one in the month
+    offset_days INTEGER NOT NULL DEFAULT 0,
+    lead_days INTEGER NOT NULL DEFAULT 21, -- How far ahead promotion should start
+    categories TEXT DEFAULT '[]',         -- JSON array of keywords matched against product categories and tags
+    enabled BOOLEAN DEFAULT 1,
+    builtin BOOLEAN DEFAULT 0,            -- Shipped with the app; editable but not deletable
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT OR IGNORE INTO retail_events
+    (name, month, day, weekday, week_of_month, offset_days, lead_days, categories, builtin)
+VALUES
+    ('New Year', 1, 1, NULL, NULL, 0, 21, '["fitness", "health", "wellness", "organization", "planner"]', 1),
+    ('Valentine''s Day', 2, 14, NULL, NULL, 0, 30, '["beauty", "skincare", "jewelry", "fragrance", "gift"]', 1),
+    ('Mother''s Day', 5, NULL, 0, 2, 0, 30, '["beauty", "skincare", "jewelry", "home", "kitchen", "gift"]', 1),
+    ('Memorial Day', 5, NULL, 1, -1, 0, 14, '["outdoor", "furniture", "mattress", "grill", "garden"]', 1),
+    ('Father''s Day', 6, NULL, 0, 3, 0, 30, '["electronics", "tech", "gadget", "tools", "grill", "fitness"]', 1),
+    ('Prime Day', 7, NULL, 2, 2, 0, 14, '["electronics", "tech", "home", "kitchen", "gadget"]', 1),
+    ('Back to School', 8, 15, NULL, NULL, 0, 30, '["school", "stationery", "backpack", "laptop", "apparel", "clothing"]', 1),
+    ('Labor Day', 9, NULL, 1, 1, 0, 14, '["furniture", "mattress", "appliance", "outdoor"]', 1),
+    ('Halloween', 10, 31, NULL, NULL, 0, 30, '["costume", "decor", "party", "candy"]', 1),
+    ('Singles'' Day', 11, 11, NULL, NULL, 0, 14, '["fashion", "beauty", "electronics"]', 1),
+    ('Black Friday', 11, NULL, 4, 4, 1, 30, '["electronics", "tech", "home", "kitchen", "fashion", "beauty", "toys", "gadget"]', 1),
+    ('Cyber Monday', 11, NULL, 4, 4, 4, 21, '["electronics", "tech", "gadget", "software", "fashion"]', 1),
+    ('Christmas', 12, 25, NULL, NULL, 0, 45, '["toys", "gift", "electronics", "jewelry", "beauty", "decor"]', 1);
@@ -0,0 +1,35 @@
+-- AffilAI Database Migration 039
+-- Content Approval Workflow
+-- Description: Ads and scheduled posts move through draft -> in_review ->
+-- approved -> published. Every transition is recorded with the reviewer and
+-- their comment, for users whose VA or client signs off on copy.
+-- Note: ALTER TABLE ADD COLUMN is handled in Rust code (schema.rs)
+-- to gracefully handle cases where columns already exist
+
+-- The following statements are handled in schema.rs:
+-- ALTER TABLE ad_copies ADD COLUMN approval_state TEXT NOT NULL DEFAULT 'draft';
+-- ALTER TABLE scheduled_posts ADD COLUMN approval_state TEXT NOT NULL DEFAULT 'draft';
+
+CREATE TABLE IF NOT EXISTS approval_transitions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity_type TEXT NOT NULL,            -- 'ad' or 'scheduled_post'
+    entity_id INTEGER NOT NULL,
+    from_state TEXT NOT NULL,
+    to_state TEXT NOT NULL,
+    reviewer TEXT,                        -- Who made the change, e.g. the client's name
+    comment TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_approval_transitions_entity
+    ON approval_transitions(entity_type, entity_id, created_at);
+
+-- Transitions can't carry a foreign key (they point at two tables), so
+-- deleting an ad or post drops its history here
+CREATE TRIGGER IF NOT EXISTS approval_transitions_ad_deleted AFTER DELETE ON ad_copies BEGIN
+    DELETE FROM approval_transitions WHERE entity_type = 'ad' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS approval_transitions_post_deleted AFTER DELETE ON scheduled_posts BEGIN
+    DELETE FROM approval_transitions WHERE entity_type = 'scheduled_post' AND entity_id = old.id;
+END;
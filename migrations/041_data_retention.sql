@@ -0,0 +1,17 @@
+-- AffilAI Database Migration 041
+-- Data Retention
+-- Description: Raw clicks older than the retention window are folded into
+-- one row per link per day before they are deleted, so long-range totals
+-- survive while click_events stays bounded.
+
+CREATE TABLE IF NOT EXISTS click_rollups (
+    day TEXT NOT NULL,                    -- YYYY-MM-DD
+    link_id INTEGER NOT NULL,
+    clicks INTEGER NOT NULL DEFAULT 0,    -- Human clicks
+    bot_clicks INTEGER NOT NULL DEFAULT 0,
+    unique_visitors INTEGER NOT NULL DEFAULT 0, -- Distinct IP hashes that day
+    PRIMARY KEY (day, link_id),
+    FOREIGN KEY (link_id) REFERENCES affiliate_links(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_click_rollups_link ON click_rollups(link_id, day);
@@ -0,0 +1,21 @@
+-- AffilAI Database Migration 042
+-- Daily Metrics
+-- Description: Per-link, per-day clicks and conversions, materialized by a
+-- nightly job so dashboards and digests read a few precomputed rows instead
+-- of scanning click_events and conversion_events. Per-product figures sum
+-- the rows for the product's links.
+
+CREATE TABLE IF NOT EXISTS daily_metrics (
+    day TEXT NOT NULL,                          -- YYYY-MM-DD
+    link_id INTEGER NOT NULL,
+    product_id INTEGER,                         -- The link's product when materialized
+    clicks INTEGER NOT NULL DEFAULT 0,          -- Human clicks
+    bot_clicks INTEGER NOT NULL DEFAULT 0,
+    unique_visitors INTEGER NOT NULL DEFAULT 0, -- Distinct IP hashes
+    conversions INTEGER NOT NULL DEFAULT 0,     -- Excludes rejected conversions
+    revenue REAL NOT NULL DEFAULT 0,
+    commission REAL NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, link_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_daily_metrics_product ON daily_metrics(product_id, day);
@@ -0,0 +1,18 @@
+-- AffilAI Database Migration 043
+-- Hot Path Indexes
+-- Description: Composite indexes matching the filters and sort orders of the
+-- main list queries and the per-link click/conversion lookups, so they are
+-- answered from the index instead of a scan plus a temp B-tree sort.
+
+-- get_ads_for_product: WHERE product_id = ? ORDER BY created_at DESC
+CREATE INDEX IF NOT EXISTS idx_ad_copies_product_created ON ad_copies(product_id, created_at);
+
+-- get_links_by_product: WHERE product_id = ? ORDER BY created_at DESC
+CREATE INDEX IF NOT EXISTS idx_affiliate_links_product_created ON affiliate_links(product_id, created_at);
+
+-- get_all_products: ORDER BY trending_score DESC, name ASC
+CREATE INDEX IF NOT EXISTS idx_products_trending_name ON products(trending_score DESC, name);
+
+-- Per-link click and conversion history over a date range
+CREATE INDEX IF NOT EXISTS idx_clicks_link_date ON click_events(link_id, clicked_at);
+CREATE INDEX IF NOT EXISTS idx_conversions_link_date ON conversion_events(link_id, converted_at);
@@ -0,0 +1,28 @@
+-- AffilAI Database Migration 044
+-- Product Watchlist
+-- Description: Watched products are re-scraped on a schedule. The last seen
+-- title, price, and availability are kept per product, and every difference
+-- found is logged so promotions can react to stockouts and price changes.
+
+CREATE TABLE IF NOT EXISTS product_watches (
+    product_id INTEGER PRIMARY KEY,
+    last_title TEXT,
+    last_price REAL,
+    last_availability TEXT,               -- 'in_stock', 'out_of_stock', 'unknown'
+    last_checked_at DATETIME,             -- NULL until the first check records a baseline
+    last_error TEXT,                      -- Why the most recent check failed, if it did
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS product_changes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    product_id INTEGER NOT NULL,
+    field TEXT NOT NULL,                  -- 'title', 'price', or 'availability'
+    old_value TEXT,
+    new_value TEXT,
+    detected_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_product_changes_product ON product_changes(product_id, detected_at);
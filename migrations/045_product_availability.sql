@@ -0,0 +1,11 @@
+-- AffilAI Database Migration 045
+-- Product Availability
+-- Description: Stock status per product ('in_stock', 'out_of_stock', or
+-- 'unknown') and when it was last read from the product page. Bulk link and
+-- ad generation skip out-of-stock products; scheduling warns about them.
+-- Note: ALTER TABLE ADD COLUMN is handled in Rust code (schema.rs)
+-- to gracefully handle cases where columns already exist
+
+-- The following statements are handled in schema.rs:
+-- ALTER TABLE products ADD COLUMN availability TEXT DEFAULT 'unknown';
+-- ALTER TABLE products ADD COLUMN availability_checked_at DATETIME;
@@ -0,0 +1,30 @@
+-- AffilAI Database Migration 046
+-- Bundles
+-- Description: Named groups of products promoted together ("Home Gym Starter
+-- Kit"). Bundle ads are ordinary ad_copies rows with bundle_id set and no
+-- product; their copy links every member product.
+-- Note: ALTER TABLE ADD COLUMN is handled in Rust code (schema.rs)
+-- to gracefully handle cases where columns already exist
+
+CREATE TABLE IF NOT EXISTS bundles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    description TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS bundle_products (
+    bundle_id INTEGER NOT NULL,
+    product_id INTEGER NOT NULL,
+    position INTEGER NOT NULL DEFAULT 0,  -- Order of the product in the bundle's ads
+    PRIMARY KEY (bundle_id, product_id),
+    FOREIGN KEY (bundle_id) REFERENCES bundles(id) ON DELETE CASCADE,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_bundle_products_product ON bundle_products(product_id);
+
+-- The following statements are handled in schema.rs:
+-- ALTER TABLE ad_copies ADD COLUMN bundle_id INTEGER REFERENCES bundles(id) ON DELETE SET NULL;
+-- CREATE INDEX IF NOT EXISTS idx_ad_copies_bundle ON ad_copies(bundle_id);
@@ -0,0 +1,61 @@
+-- AffilAI Database Migration 047
+-- Notes
+-- Description: Markdown notes (research, calls with program managers) that
+-- reference products, links, ads, and campaigns. References come from the
+-- note's explicit list and from [[product:12]]-style backlinks in its body.
+-- Notes are indexed with FTS5 for search. Existing free-text product notes
+-- are copied into notes once, when this table is first created (schema.rs).
+
+CREATE TABLE IF NOT EXISTS notes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT,
+    body TEXT NOT NULL,                   -- Markdown
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS note_refs (
+    note_id INTEGER NOT NULL,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('product', 'link', 'ad', 'campaign')),
+    entity_id INTEGER NOT NULL,
+    PRIMARY KEY (note_id, entity_type, entity_id),
+    FOREIGN KEY (note_id) REFERENCES notes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_note_refs_entity ON note_refs(entity_type, entity_id);
+
+-- Full-text index over title and body, kept in step by triggers
+CREATE VIRTUAL TABLE IF NOT EXISTS notes_fts USING fts5(
+    title, body, content='notes', content_rowid='id'
+);
+
+CREATE TRIGGER IF NOT EXISTS notes_fts_insert AFTER INSERT ON notes BEGIN
+    INSERT INTO notes_fts (rowid, title, body) VALUES (new.id, new.title, new.body);
+END;
+
+CREATE TRIGGER IF NOT EXISTS notes_fts_delete AFTER DELETE ON notes BEGIN
+    INSERT INTO notes_fts (notes_fts, rowid, title, body) VALUES ('delete', old.id, old.title, old.body);
+END;
+
+CREATE TRIGGER IF NOT EXISTS notes_fts_update AFTER UPDATE ON notes BEGIN
+    INSERT INTO notes_fts (notes_fts, rowid, title, body) VALUES ('delete', old.id, old.title, old.body);
+    INSERT INTO notes_fts (rowid, title, body) VALUES (new.id, new.title, new.body);
+END;
+
+-- References can't carry foreign keys (they point at several tables), so
+-- deleting a referenced row drops its references here
+CREATE TRIGGER IF NOT EXISTS note_refs_product_deleted AFTER DELETE ON products BEGIN
+    DELETE FROM note_refs WHERE entity_type = 'product' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS note_refs_link_deleted AFTER DELETE ON affiliate_links BEGIN
+    DELETE FROM note_refs WHERE entity_type = 'link' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS note_refs_ad_deleted AFTER DELETE ON ad_copies BEGIN
+    DELETE FROM note_refs WHERE entity_type = 'ad' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS note_refs_campaign_deleted AFTER DELETE ON campaigns BEGIN
+    DELETE FROM note_refs WHERE entity_type = 'campaign' AND entity_id = old.id;
+END;
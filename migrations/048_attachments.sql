@@ -0,0 +1,33 @@
+-- AffilAI Database Migration 048
+-- Attachments
+-- Description: Files attached to products, campaigns, and affiliate
+-- credentials (rate cards, contracts, media kits). The files are copied into
+-- an attachments folder next to the database; these rows hold their metadata.
+
+CREATE TABLE IF NOT EXISTS attachments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('product', 'campaign', 'credential')),
+    entity_id INTEGER NOT NULL,
+    file_name TEXT NOT NULL,              -- Name of the file as attached
+    stored_name TEXT NOT NULL UNIQUE,     -- Name inside the attachments folder
+    mime_type TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    description TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachments_entity ON attachments(entity_type, entity_id);
+
+-- Rows of deleted entities go with them; their files are pruned from the
+-- folder the next time something is attached
+CREATE TRIGGER IF NOT EXISTS attachments_product_deleted AFTER DELETE ON products BEGIN
+    DELETE FROM attachments WHERE entity_type = 'product' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS attachments_campaign_deleted AFTER DELETE ON campaigns BEGIN
+    DELETE FROM attachments WHERE entity_type = 'campaign' AND entity_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS attachments_credential_deleted AFTER DELETE ON affiliate_credentials BEGIN
+    DELETE FROM attachments WHERE entity_type = 'credential' AND entity_id = old.id;
+END;
@@ -0,0 +1,41 @@
+-- AffilAI Database Migration 049
+-- Benchmarks
+-- Description: Reference click-through rate, conversion rate, and order value
+-- multiplier per platform, optionally narrowed to a product category. Rows
+-- with an empty category apply to every category without its own row; the
+-- 'other' platform covers platforms with no row. Shipped rows keep their
+-- original values in the default_* columns so edits can be reset.
+
+CREATE TABLE IF NOT EXISTS benchmarks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    platform TEXT NOT NULL,
+    category TEXT NOT NULL DEFAULT '',    -- '' = any category
+    ctr REAL NOT NULL,                    -- Clicks per impression, 0.01 = 1%
+    conversion_rate REAL NOT NULL,        -- Conversions per click
+    aov_multiplier REAL NOT NULL DEFAULT 1.0,  -- Applied to the product's order value
+    default_ctr REAL,                     -- Shipped values; NULL for rows the user added
+    default_conversion_rate REAL,
+    default_aov_multiplier REAL,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (platform, category)
+);
+
+INSERT OR IGNORE INTO benchmarks
+    (platform, category, ctr, conversion_rate, aov_multiplier,
+     default_ctr, default_conversion_rate, default_aov_multiplier)
+VALUES
+    ('tiktok', '', 0.008, 0.015, 1.0, 0.008, 0.015, 1.0),
+    ('instagram', '', 0.006, 0.02, 1.0, 0.006, 0.02, 1.0),
+    ('facebook', '', 0.009, 0.025, 1.0, 0.009, 0.025, 1.0),
+    ('pinterest', '', 0.005, 0.03, 1.1, 0.005, 0.03, 1.1),
+    ('youtube', '', 0.004, 0.035, 1.2, 0.004, 0.035, 1.2),
+    ('email', '', 0.025, 0.03, 1.0, 0.025, 0.03, 1.0),
+    ('other', '', 0.005, 0.02, 1.0, 0.005, 0.02, 1.0),
+    ('tiktok', 'Beauty & Skincare', 0.012, 0.02, 0.9, 0.012, 0.02, 0.9),
+    ('instagram', 'Beauty & Skincare', 0.009, 0.025, 0.9, 0.009, 0.025, 0.9),
+    ('tiktok', 'Fashion & Apparel', 0.01, 0.018, 0.9, 0.01, 0.018, 0.9),
+    ('instagram', 'Fashion & Apparel', 0.008, 0.022, 1.0, 0.008, 0.022, 1.0),
+    ('pinterest', 'Home & Kitchen', 0.007, 0.035, 1.2, 0.007, 0.035, 1.2),
+    ('youtube', 'Wearable Health Technology', 0.006, 0.04, 1.3, 0.006, 0.04, 1.3),
+    ('facebook', 'Health & Wellness', 0.01, 0.03, 1.0, 0.01, 0.03, 1.0),
+    ('youtube', 'Fitness & Recovery', 0.005, 0.035, 1.1, 0.005, 0.035, 1.1);
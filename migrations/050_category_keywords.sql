@@ -0,0 +1,95 @@
+-- AffilAI Database Migration 050
+-- Category Keywords
+-- Description: Keyword affinities that score how well a product category suits
+-- each ad type and discovery platform. A category matches a keyword when it
+-- contains it (case-insensitive); the highest matching score wins, and the
+-- target's empty-keyword row scores every category nothing else matches.
+-- Shipped rows keep their original score in default_score so edits can be reset.
+
+CREATE TABLE IF NOT EXISTS category_keywords (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,                -- 'ad_type' or 'platform'
+    target TEXT NOT NULL,              -- Ad type key ('video_script', ...) or platform ('tiktok', ...)
+    keyword TEXT NOT NULL DEFAULT '',  -- '' = any other category
+    score REAL NOT NULL,               -- 0-1
+    default_score REAL,                -- Shipped score; NULL for rows the user added
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (kind, target, keyword)
+);
+
+INSERT OR IGNORE INTO category_keywords (kind, target, keyword, score, default_score) VALUES
+    ('ad_type', 'video_script', 'electronics', 1.0, 1.0),
+    ('ad_type', 'video_script', 'tech', 1.0, 1.0),
+    ('ad_type', 'video_script', 'wearable', 1.0, 1.0),
+    ('ad_type', 'video_script', 'gadget', 1.0, 1.0),
+    ('ad_type', 'video_script', 'fitness', 0.8, 0.8),
+    ('ad_type', 'video_script', 'health', 0.8, 0.8),
+    ('ad_type', 'video_script', 'home', 0.6, 0.6),
+    ('ad_type', 'video_script', 'kitchen', 0.6, 0.6),
+    ('ad_type', 'video_script', '', 0.4, 0.4),
+    ('ad_type', 'carousel', 'fashion', 1.0, 1.0),
+    ('ad_type', 'carousel', 'apparel', 1.0, 1.0),
+    ('ad_type', 'carousel', 'clothing', 1.0, 1.0),
+    ('ad_type', 'carousel', 'beauty', 0.9, 0.9),
+    ('ad_type', 'carousel', 'skincare', 0.9, 0.9),
+    ('ad_type', 'carousel', 'cosmetic', 0.9, 0.9),
+    ('ad_type', 'carousel', 'jewelry', 0.9, 0.9),
+    ('ad_type', 'carousel', 'accessories', 0.9, 0.9),
+    ('ad_type', 'carousel', 'home', 0.85, 0.85),
+    ('ad_type', 'carousel', 'decor', 0.85, 0.85),
+    ('ad_type', 'carousel', 'furniture', 0.85, 0.85),
+    ('ad_type', 'carousel', '', 0.5, 0.5),
+    ('ad_type', 'story', 'beauty', 0.95, 0.95),
+    ('ad_type', 'story', 'skincare', 0.95, 0.95),
+    ('ad_type', 'story', 'fashion', 0.9, 0.9),
+    ('ad_type', 'story', 'apparel', 0.9, 0.9),
+    ('ad_type', 'story', 'food', 0.85, 0.85),
+    ('ad_type', 'story', 'beverage', 0.85, 0.85),
+    ('ad_type', 'story', 'fitness', 0.8, 0.8),
+    ('ad_type', 'story', 'wellness', 0.8, 0.8),
+    ('ad_type', 'story', '', 0.5, 0.5),
+    ('ad_type', 'social_post', 'trending', 0.95, 0.95),
+    ('ad_type', 'social_post', 'viral', 0.95, 0.95),
+    ('ad_type', 'social_post', 'gadget', 0.7, 0.7),
+    ('ad_type', 'social_post', 'tech', 0.7, 0.7),
+    ('ad_type', 'social_post', '', 0.6, 0.6),
+    ('ad_type', 'email', 'finance', 0.95, 0.95),
+    ('ad_type', 'email', 'insurance', 0.95, 0.95),
+    ('ad_type', 'email', 'health', 0.9, 0.9),
+    ('ad_type', 'email', 'wellness', 0.9, 0.9),
+    ('ad_type', 'email', 'supplement', 0.9, 0.9),
+    ('ad_type', 'email', 'electronics', 0.7, 0.7),
+    ('ad_type', 'email', 'appliance', 0.7, 0.7),
+    ('ad_type', 'email', '', 0.5, 0.5),
+    ('ad_type', 'sms', 'deal', 0.95, 0.95),
+    ('ad_type', 'sms', 'flash', 0.95, 0.95),
+    ('ad_type', 'sms', 'food', 0.9, 0.9),
+    ('ad_type', 'sms', 'restaurant', 0.9, 0.9),
+    ('ad_type', 'sms', 'local', 0.8, 0.8),
+    ('ad_type', 'sms', 'service', 0.8, 0.8),
+    ('ad_type', 'sms', '', 0.3, 0.3),
+    ('platform', 'tiktok', 'Beauty & Skincare', 1.0, 1.0),
+    ('platform', 'tiktok', 'Fashion & Apparel', 1.0, 1.0),
+    ('platform', 'tiktok', 'Health & Wellness', 0.9, 0.9),
+    ('platform', 'tiktok', 'Fitness & Recovery', 0.9, 0.9),
+    ('platform', 'tiktok', 'Wearable Health Technology', 0.8, 0.8),
+    ('platform', 'tiktok', 'Consumer Electronics', 0.7, 0.7),
+    ('platform', 'tiktok', '', 0.5, 0.5),
+    ('platform', 'instagram', 'Beauty & Skincare', 1.0, 1.0),
+    ('platform', 'instagram', 'Fashion & Apparel', 1.0, 1.0),
+    ('platform', 'instagram', 'Home & Kitchen', 0.9, 0.9),
+    ('platform', 'instagram', 'Health & Wellness', 0.9, 0.9),
+    ('platform', 'instagram', 'Fitness & Recovery', 0.8, 0.8),
+    ('platform', 'instagram', '', 0.6, 0.6),
+    ('platform', 'youtube', 'Consumer Electronics', 1.0, 1.0),
+    ('platform', 'youtube', 'Wearable Health Technology', 1.0, 1.0),
+    ('platform', 'youtube', 'Fitness & Recovery', 0.9, 0.9),
+    ('platform', 'youtube', 'Health & Wellness', 0.9, 0.9),
+    ('platform', 'youtube', 'Home & Kitchen', 0.8, 0.8),
+    ('platform', 'youtube', '', 0.7, 0.7),
+    ('platform', 'pinterest', 'Home & Kitchen', 1.0, 1.0),
+    ('platform', 'pinterest', 'Fashion & Apparel', 1.0, 1.0),
+    ('platform', 'pinterest', 'Beauty & Skincare', 0.9, 0.9),
+    ('platform', 'pinterest', 'Health & Wellness', 0.8, 0.8),
+    ('platform', 'pinterest', '', 0.6, 0.6),
+    ('platform', 'amazon', '', 1.0, 1.0);
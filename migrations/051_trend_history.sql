@@ -0,0 +1,42 @@
+-- AffilAI Database Migration 051
+-- Trend History
+-- Description: Every trending score a product has had, recorded by triggers
+-- whenever a product is added with a score or its score changes, so scoring
+-- can tell a rising product from a declining one. Products that already have
+-- a score get it as their first point.
+
+CREATE TABLE IF NOT EXISTS trend_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    product_id INTEGER NOT NULL,
+    trending_score INTEGER NOT NULL,      -- 0-100
+    recorded_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_trend_history_product ON trend_history(product_id, recorded_at);
+
+INSERT INTO trend_history (product_id, trending_score, recorded_at)
+SELECT id, trending_score, COALESCE(updated_at, created_at, CURRENT_TIMESTAMP)
+FROM products
+WHERE trending_score IS NOT NULL
+  AND NOT EXISTS (SELECT 1 FROM trend_history h WHERE h.product_id = products.id);
+
+CREATE TRIGGER IF NOT EXISTS trend_history_product_insert
+AFTER INSERT ON products
+WHEN NEW.trending_score IS NOT NULL
+BEGIN
+    INSERT INTO trend_history (product_id, trending_score) VALUES (NEW.id, NEW.trending_score);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trend_history_product_update
+AFTER UPDATE OF trending_score ON products
+WHEN NEW.trending_score IS NOT NULL AND NEW.trending_score IS NOT OLD.trending_score
+BEGIN
+    INSERT INTO trend_history (product_id, trending_score) VALUES (NEW.id, NEW.trending_score);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trend_history_product_delete
+AFTER DELETE ON products
+BEGIN
+    DELETE FROM trend_history WHERE product_id = OLD.id;
+END;
@@ -0,0 +1,20 @@
+-- AffilAI Database Migration 052
+-- Variation Performance
+-- Description: Impressions and clicks recorded per ad, platform, and day, as
+-- reported by the platform's own analytics. A product's ads for a platform
+-- are its variations; the bandit samples each one's click-through rate to
+-- pick the variation to post next.
+
+CREATE TABLE IF NOT EXISTS variation_performance (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    ad_id INTEGER NOT NULL,
+    platform TEXT NOT NULL,               -- Lowercase, as in benchmarks
+    day TEXT NOT NULL,                    -- YYYY-MM-DD
+    impressions INTEGER NOT NULL DEFAULT 0,
+    clicks INTEGER NOT NULL DEFAULT 0,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (ad_id) REFERENCES ad_copies(id) ON DELETE CASCADE,
+    UNIQUE (ad_id, platform, day)
+);
+
+CREATE INDEX IF NOT EXISTS idx_variation_performance_platform ON variation_performance(platform, ad_id);
@@ -0,0 +1,34 @@
+-- AffilAI Database Migration 053
+-- Experiments
+-- Description: Structured A/B tests. An experiment states a hypothesis and
+-- the metric that decides it, and runs its variants (an ad, a link, or both)
+-- from start until stopped. Results are read from the click, conversion, and
+-- variation performance data recorded while it ran; the first variant is the
+-- control the others are compared against.
+
+CREATE TABLE IF NOT EXISTS experiments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    hypothesis TEXT,
+    success_metric TEXT NOT NULL,         -- 'ctr' (clicks per impression) or 'conversion_rate' (conversions per click)
+    status TEXT NOT NULL DEFAULT 'running', -- running, stopped
+    started_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    ended_at DATETIME,                    -- Set when stopped
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS experiment_variants (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    experiment_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    ad_id INTEGER,
+    link_id INTEGER,                      -- The ad's attached link when not given
+    position INTEGER NOT NULL DEFAULT 0,  -- 0 = control
+    FOREIGN KEY (experiment_id) REFERENCES experiments(id) ON DELETE CASCADE,
+    FOREIGN KEY (ad_id) REFERENCES ad_copies(id) ON DELETE SET NULL,
+    FOREIGN KEY (link_id) REFERENCES affiliate_links(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_experiment_variants_experiment ON experiment_variants(experiment_id, position);
+CREATE INDEX IF NOT EXISTS idx_experiments_status ON experiments(status, started_at);
@@ -0,0 +1,20 @@
+-- AffilAI Database Migration 054
+-- Link Refreshes
+-- Description: Before/after snapshots of every affiliate link refresh that
+-- changed something, single or bulk, for auditing what the refresher did
+
+CREATE TABLE IF NOT EXISTS link_refreshes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    link_id INTEGER NOT NULL,
+    product_id INTEGER NOT NULL,
+    operation_id INTEGER,                 -- Bulk refresh run; NULL for a single refresh
+    before_json TEXT NOT NULL,            -- Link fields before the refresh
+    after_json TEXT NOT NULL,             -- Link fields after the refresh
+    changed_fields TEXT NOT NULL,         -- JSON array of the fields that differ
+    refreshed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (link_id) REFERENCES affiliate_links(id) ON DELETE CASCADE,
+    FOREIGN KEY (operation_id) REFERENCES bulk_operations(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_link_refreshes_refreshed ON link_refreshes(refreshed_at);
+CREATE INDEX IF NOT EXISTS idx_link_refreshes_link ON link_refreshes(link_id, refreshed_at);
@@ -0,0 +1,22 @@
+-- AffilAI Database Migration 055
+-- Store Products
+-- Description: Maps the SKUs of a connected Shopify or WooCommerce store to
+-- catalog products. Imports create the mapping; the scheduled store sync
+-- then keeps each mapped product's price and image in step with the store,
+-- remembering what it last saw so only real changes are written.
+
+CREATE TABLE IF NOT EXISTS store_products (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    store TEXT NOT NULL,                  -- 'shopify' or 'woocommerce'
+    sku TEXT NOT NULL,                    -- The store's SKU, or 'id:<external id>' for items without one
+    external_id TEXT,                     -- Store product (or variant) id; NULL until a mapped SKU is seen
+    product_id INTEGER NOT NULL,
+    last_price REAL,
+    last_image_url TEXT,
+    last_synced_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (store, sku),
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_store_products_product ON store_products(product_id);
@@ -0,0 +1,25 @@
+-- AffilAI Database Migration 056
+-- Feed Sources
+-- Description: RSS and Atom feeds (deal blogs, trend newsletters) that the
+-- scheduled feed job polls for product finds. New items go to the ideas
+-- inbox; every item read is remembered so dismissed ideas don't come back
+-- on the next poll.
+
+CREATE TABLE IF NOT EXISTS feed_sources (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    url TEXT NOT NULL UNIQUE,
+    active BOOLEAN DEFAULT 1,
+    last_polled_at DATETIME,
+    last_error TEXT,                      -- Why the most recent poll failed, if it did
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS feed_source_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    source_id INTEGER NOT NULL,
+    guid TEXT NOT NULL,                   -- The item's guid / Atom id, or its link when it has neither
+    seen_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (source_id, guid),
+    FOREIGN KEY (source_id) REFERENCES feed_sources(id) ON DELETE CASCADE
+);
@@ -0,0 +1,312 @@
+//! Command-Line Companion
+//!
+//! `affilai-cli` runs batch jobs against the desktop app's database without
+//! launching the GUI, so they can be scripted or scheduled with cron / Task
+//! Scheduler. Built only with the `cli` feature:
+//!
+//! ```text
+//! cargo run --features cli --bin affilai-cli -- generate-ads --category beauty
+//! ```
+//!
+//! Ad generation uses the same local pipeline as the app (niche profile,
+//! research, style rules, compliance). Webhooks are not fired, since they
+//! belong to the running app.
+
+use crate::commands::ad_generation::generate_ad;
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::database;
+use crate::models::ad_export::AdExportFilter;
+use crate::models::product::Product;
+use crate::services::{markdown_export, rest_api};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params_from_iter, Connection};
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "Usage: affilai-cli [--db <path>] [--json] <command> [options]
+
+Commands:
+  products        [--category <name>] [--stage <stage>]
+  generate-ads    [--category <name>] [--stage <stage>] [--product <id>]...
+                  [--ad-type <type>] [--instructions <text>] [--limit <n>]
+  export-markdown --out <dir> [--product <id>] [--ad-type <type>] [--favorites]
+                  [--since <YYYY-MM-DD>]
+  stats           [--days <n>]
+
+The database defaults to the desktop app's; override with --db or AFFILAI_DB.";
+
+/// Parsed command line: the command, its `--flag value` options, and switches
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    pub command: String,
+    pub options: Vec<(String, String)>,
+    pub switches: Vec<String>,
+}
+
+/// Options that take no value
+const SWITCHES: [&str; 3] = ["--json", "--favorites", "--help"];
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if SWITCHES.contains(&arg.as_str()) {
+                parsed.switches.push(arg);
+            } else if let Some(name) = arg.strip_prefix("--") {
+                let value = args.next().ok_or_else(|| format!("--{} needs a value", name))?;
+                parsed.options.push((name.to_string(), value));
+            } else if parsed.command.is_empty() {
+                parsed.command = arg;
+            } else {
+                return Err(format!("Unexpected argument '{}'", arg));
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.options
+            .iter()
+            .filter(move |(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn get_i64(&self, name: &str) -> Result<Option<i64>, String> {
+        self.get(name)
+            .map(|v| v.parse().map_err(|_| format!("--{} must be a number", name)))
+            .transpose()
+    }
+
+    fn has(&self, switch: &str) -> bool {
+        self.switches.iter().any(|s| s == switch)
+    }
+}
+
+/// Entry point for the `affilai-cli` binary; returns the process exit code
+pub fn run() -> i32 {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+    if args.command.is_empty() || args.command == "help" || args.has("--help") {
+        println!("{}", USAGE);
+        return 0;
+    }
+
+    match execute(&args) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+fn db_path(args: &Args) -> Result<PathBuf, String> {
+    args.get("db")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("AFFILAI_DB").map(PathBuf::from))
+        .or_else(database::default_db_path)
+        .ok_or_else(|| "Could not locate the AffilAI database; pass --db <path>".to_string())
+}
+
+fn execute(args: &Args) -> Result<(), String> {
+    let path = db_path(args)?;
+    if !path.exists() {
+        return Err(format!(
+            "No AffilAI database at {}; launch the app once or pass --db <path>",
+            path.display()
+        ));
+    }
+    let conn = database::open_at(&path).map_err(|e| e.to_string())?;
+    let json = args.has("--json");
+
+    match args.command.as_str() {
+        "products" => list_products(&conn, args, json),
+        "generate-ads" => generate_ads(&conn, args, json),
+        "export-markdown" => export_markdown(&conn, args, json),
+        "stats" => stats(&conn, args, json),
+        other => Err(format!("Unknown command '{}'\n\n{}", other, USAGE)),
+    }
+}
+
+fn print_json(value: &impl serde::Serialize) -> Result<(), String> {
+    let out = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    println!("{}", out);
+    Ok(())
+}
+
+/// Products matching `--product`, `--category`, and `--stage`
+fn select_products(conn: &Connection, args: &Args) -> Result<Vec<Product>, String> {
+    let mut conditions = vec!["1 = 1".to_string()];
+    let mut values: Vec<SqlValue> = Vec::new();
+
+    let ids = args
+        .get_all("product")
+        .map(|id| id.parse::<i64>().map_err(|_| format!("Invalid product id '{}'", id)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if !ids.is_empty() {
+        let placeholders = ids
+            .iter()
+            .map(|id| {
+                values.push(SqlValue::Integer(*id));
+                format!("?{}", values.len())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        conditions.push(format!("id IN ({})", placeholders));
+    }
+    if let Some(category) = args.get("category") {
+        values.push(SqlValue::Text(category.to_string()));
+        // Match "beauty" against "Beauty & Skincare"
+        conditions.push(format!("category LIKE '%' || ?{} || '%'", values.len()));
+    }
+    if let Some(stage) = args.get("stage") {
+        values.push(SqlValue::Text(stage.to_lowercase()));
+        conditions.push(format!("stage = ?{}", values.len()));
+    }
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM products WHERE {} ORDER BY id",
+            PRODUCT_COLUMNS,
+            conditions.join(" AND ")
+        ))
+        .map_err(|e| e.to_string())?;
+    let products = stmt
+        .query_map(params_from_iter(values), product_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(products)
+}
+
+fn list_products(conn: &Connection, args: &Args, json: bool) -> Result<(), String> {
+    let products = select_products(conn, args)?;
+    if json {
+        return print_json(&products);
+    }
+    for product in &products {
+        println!(
+            "{}\t{}\t{}\t{}",
+            product.id.unwrap_or_default(),
+            product.name,
+            product.category,
+            product.stage.as_deref().unwrap_or("")
+        );
+    }
+    Ok(())
+}
+
+fn generate_ads(conn: &Connection, args: &Args, json: bool) -> Result<(), String> {
+    let mut products = select_products(conn, args)?;
+    if let Some(limit) = args.get_i64("limit")? {
+        products.truncate(limit.max(0) as usize);
+    }
+    if products.is_empty() {
+        return Err("No products match".to_string());
+    }
+
+    let mut generated = Vec::new();
+    let mut failures = 0;
+    for product in &products {
+        let product_id = product.id.unwrap_or_default();
+        match generate_ad(conn, product_id, args.get("ad-type"), args.get("instructions"), None, false) {
+            Ok(result) => {
+                if !json {
+                    println!(
+                        "{}\t{}\t{}",
+                        result.ad_copy.id.unwrap_or_default(),
+                        product.name,
+                        result.ad_copy.headline
+                    );
+                }
+                generated.push(result.ad_copy);
+            }
+            Err(e) => {
+                failures += 1;
+                eprintln!("{} (#{}): {}", product.name, product_id, e);
+            }
+        }
+    }
+
+    if json {
+        print_json(&generated)?;
+    }
+    if failures > 0 {
+        return Err(format!("{} of {} products failed", failures, products.len()));
+    }
+    Ok(())
+}
+
+fn export_markdown(conn: &Connection, args: &Args, json: bool) -> Result<(), String> {
+    let dir = args.get("out").ok_or("export-markdown needs --out <dir>")?;
+    let filter = AdExportFilter {
+        product_id: args.get_i64("product")?,
+        ad_type: args.get("ad-type").map(str::to_string),
+        favorites_only: args.has("--favorites"),
+        created_after: args.get("since").map(str::to_string),
+        ..Default::default()
+    };
+
+    let export = markdown_export::export_to_dir(conn, &filter, Path::new(dir))?;
+    if json {
+        return print_json(&export);
+    }
+    println!("Wrote {} notes to {}", export.files.len(), export.path);
+    Ok(())
+}
+
+fn stats(conn: &Connection, args: &Args, json: bool) -> Result<(), String> {
+    let days = args.get_i64("days")?.unwrap_or(30).max(1);
+    let stats = rest_api::workspace_stats(conn, days).map_err(|e| e.to_string())?;
+    if json {
+        return print_json(&stats);
+    }
+    println!("Products:      {}", stats.products);
+    println!("Active links:  {}", stats.active_links);
+    println!("Ads:           {}", stats.ads);
+    println!("Last {} days:", stats.days);
+    println!("  Clicks:      {}", stats.clicks);
+    println!("  Conversions: {}", stats.conversions);
+    println!("  Revenue:     {:.2}", stats.revenue);
+    println!("  Commission:  {:.2}", stats.commission);
+    Ok(())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_options_and_switches() {
+        let args = parse(&["--json", "generate-ads", "--category", "beauty", "--product", "3", "--product", "4"])
+            .unwrap();
+        assert_eq!(args.command, "generate-ads");
+        assert!(args.has("--json"));
+        assert_eq!(args.get("category"), Some("beauty"));
+        assert_eq!(args.get_all("product").collect::<Vec<_>>(), vec!["3", "4"]);
+
+        assert!(parse(&["stats", "--days"]).is_err());
+        assert!(parse(&["stats", "extra"]).is_err());
+    }
+}
@@ -1,12 +1,23 @@
+use crate::commands::affiliate_links::{generate_affiliate_link, generate_link_for_platform};
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
 use crate::database::get_connection;
+use crate::models::affiliate_link::{GenerateLinkForPlatformRequest, GenerateLinkRequest};
+use crate::models::niche_profile::NicheProfile;
 use crate::models::product::Product;
-use crate::services::ai_affiliate::mock_ai_discovery_with_platforms;
-use rusqlite::params;
+use crate::models::recommendation_trace::RecommendationTrace;
+use crate::models::research::ResearchBrief;
+use crate::services::analytics_service::ScoringSignals;
+use crate::services::category_keywords::CategoryAffinities;
+use crate::services::{
+    accessibility, ad_links, content_angles, credential_checks, data_events, niche, pinterest,
+    recommendation_trace, research, reviews, short_video, sms_compliance, style_rules, webhooks,
+};
+use rusqlite::{params, Connection, Row};
 use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
 
 /// Supported ad types for generation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "snake_case")]
 pub enum AdType {
     SocialPost,
@@ -15,6 +26,7 @@ pub enum AdType {
     Carousel,
     Email,
     Sms,
+    PinterestPin,
 }
 
 impl AdType {
@@ -26,6 +38,7 @@ impl AdType {
             AdType::Carousel => "carousel".to_string(),
             AdType::Email => "email".to_string(),
             AdType::Sms => "sms".to_string(),
+            AdType::PinterestPin => "pinterest_pin".to_string(),
         }
     }
 
@@ -37,87 +50,156 @@ impl AdType {
             "carousel" => Some(AdType::Carousel),
             "email" => Some(AdType::Email),
             "sms" => Some(AdType::Sms),
+            "pinterest_pin" => Some(AdType::PinterestPin),
             _ => None,
         }
     }
 }
 
 /// Market analysis result from analyzing a product
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct MarketAnalysis {
+    #[serde(alias = "recommended_ad_type")]
     pub recommended_ad_type: String,
+    #[serde(alias = "recommended_platform")]
     pub recommended_platform: String,
+    #[serde(alias = "target_demographic")]
     pub target_demographic: String,
+    #[serde(alias = "key_selling_points")]
     pub key_selling_points: Vec<String>,
+    #[serde(alias = "suggested_tone")]
     pub suggested_tone: String,
+    #[serde(alias = "competition_level")]
     pub competition_level: String,
+    #[serde(alias = "estimated_engagement_score")]
     pub estimated_engagement_score: f64,
 }
 
 /// A generated ad copy record
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct GeneratedAdCopy {
     pub id: Option<i64>,
+    #[serde(alias = "product_id")]
     pub product_id: Option<i64>,
+    #[serde(alias = "campaign_id")]
     pub campaign_id: i64,
+    #[serde(alias = "variation_name")]
     pub variation_name: Option<String>,
     pub headline: String,
+    #[serde(alias = "body_text")]
     pub body_text: Option<String>,
     pub cta: Option<String>,
+    #[serde(alias = "ad_format")]
     pub ad_format: Option<String>,
+    #[serde(alias = "ad_type")]
     pub ad_type: Option<String>,
+    #[serde(alias = "platform_specific_data")]
     pub platform_specific_data: Option<String>,
+    #[serde(alias = "performance_score")]
     pub performance_score: Option<f64>,
+    #[serde(alias = "created_at")]
     pub created_at: Option<String>,
+    #[serde(alias = "updated_at")]
     pub updated_at: Option<String>,
+    #[serde(default, alias = "is_favorite")]
+    pub is_favorite: bool,
+    /// The sequence ad this message belongs to (email sequences only)
+    #[serde(alias = "parent_ad_id")]
+    pub parent_ad_id: Option<i64>,
+    #[serde(alias = "sequence_step")]
+    pub sequence_step: Option<i32>,
+    /// Days to wait after the previous message in the sequence
+    #[serde(alias = "send_delay_days")]
+    pub send_delay_days: Option<i32>,
+    /// Accessible description of the product image (visual ad types)
+    #[serde(alias = "alt_text")]
+    pub alt_text: Option<String>,
+    /// The affiliate link `[LINK]` resolves to on export
+    #[serde(default, alias = "link_id")]
+    pub link_id: Option<i64>,
+    /// Where the copy is in the approval workflow: draft, in_review, approved, published
+    #[serde(default = "default_approval_state", alias = "approval_state")]
+    pub approval_state: String,
+}
+
+fn default_approval_state() -> String {
+    "draft".to_string()
+}
+
+/// Column list shared by every ad copy query; keep in sync with `ad_copy_from_row`
+pub(crate) const AD_COPY_COLUMNS: &str = "id, product_id, campaign_id, variation_name, headline, body_text,
+     cta, ad_format, ad_type, platform_specific_data, performance_score,
+     created_at, updated_at, is_favorite, parent_ad_id, sequence_step, send_delay_days,
+     alt_text, link_id, approval_state";
+
+/// Maps a row selected with `AD_COPY_COLUMNS` into a `GeneratedAdCopy`
+pub(crate) fn ad_copy_from_row(row: &Row) -> rusqlite::Result<GeneratedAdCopy> {
+    Ok(GeneratedAdCopy {
+        id: Some(row.get(0)?),
+        product_id: row.get(1)?,
+        campaign_id: row.get(2)?,
+        variation_name: row.get(3)?,
+        headline: row.get(4)?,
+        body_text: row.get(5)?,
+        cta: row.get(6)?,
+        ad_format: row.get(7)?,
+        ad_type: row.get(8)?,
+        platform_specific_data: row.get(9)?,
+        performance_score: row.get(10)?,
+        created_at: row.get(11)?,
+        updated_at: row.get(12)?,
+        is_favorite: row.get::<_, Option<bool>>(13)?.unwrap_or(false),
+        parent_ad_id: row.get(14)?,
+        sequence_step: row.get(15)?,
+        send_delay_days: row.get(16)?,
+        alt_text: row.get(17)?,
+        link_id: row.get(18)?,
+        approval_state: row.get(19)?,
+    })
 }
 
 /// Result containing both the generated ad and market analysis
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct AdGenerationResult {
+    #[serde(alias = "ad_copy")]
     pub ad_copy: GeneratedAdCopy,
+    #[serde(alias = "market_analysis")]
     pub market_analysis: MarketAnalysis,
 }
 
+/// Competition level ("high", "medium", "low") based on category
+pub(crate) fn competition_level(category: &str) -> &'static str {
+    match category {
+        "Beauty & Skincare" | "Fashion & Apparel" => "high",
+        "Consumer Electronics" | "Wearable Health Technology" => "medium",
+        "Health & Wellness" | "Fitness & Recovery" => "medium",
+        _ => "low",
+    }
+}
+
 /// Analyzes market for a product and returns recommendations
-fn analyze_market_for_product(product: &Product) -> MarketAnalysis {
+fn analyze_market_for_product(
+    product: &Product,
+    niche_profile: Option<&NicheProfile>,
+    research: Option<&ResearchBrief>,
+    affinities: &CategoryAffinities,
+    signals: &ScoringSignals,
+) -> MarketAnalysis {
     let category = &product.category;
-    let target_audience = product.target_audience.as_deref().unwrap_or("Age 25-45");
-    let trending_score = product.trending_score.unwrap_or(50);
-    let price_range = product.price_range.as_deref().unwrap_or("$50-$100");
-
-    // Get platform recommendations
-    let programs = mock_ai_discovery_with_platforms(
-        &product.name,
-        category,
-        trending_score,
-        target_audience,
-        price_range,
-    );
-
-    let recommended_platform = programs
-        .first()
-        .map(|p| p.platform.to_string())
-        .unwrap_or_else(|| "instagram".to_string());
-
-    // Determine recommended ad type based on platform and category
-    let recommended_ad_type = match recommended_platform.as_str() {
-        "tiktok" => "video_script",
-        "instagram" => {
-            if category.contains("Fashion") || category.contains("Beauty") {
-                "carousel"
-            } else {
-                "story"
-            }
-        }
-        "youtube" => "video_script",
-        "pinterest" => "carousel",
-        "facebook" => "social_post",
-        _ => "social_post",
+    // Platform, ad type and engagement come from the scoring that explain mode traces
+    let trace = recommendation_trace::explain(product, niche_profile, affinities, signals);
+    let target_audience = trace.inputs.target_audience.as_str();
+
+    // Selling points come from the research brief when the product has one, otherwise
+    // from the category; claims the niche bans are dropped either way
+    let selling_points = match research {
+        Some(brief) if !brief.key_benefits.is_empty() => brief.key_benefits.clone(),
+        _ => generate_selling_points(category, &product.name),
     };
-
-    // Extract key selling points based on category
-    let key_selling_points = generate_selling_points(category, &product.name);
+    let key_selling_points = niche::filter_banned_claims(selling_points, niche_profile);
 
     // Determine tone based on target audience
     let suggested_tone = if target_audience.contains("18-25") || target_audience.contains("18-30") {
@@ -128,35 +210,21 @@ fn analyze_market_for_product(product: &Product) -> MarketAnalysis {
         "friendly and engaging".to_string()
     };
 
-    // Competition level based on category
-    let competition_level = match category.as_str() {
-        "Beauty & Skincare" | "Fashion & Apparel" => "high".to_string(),
-        "Consumer Electronics" | "Wearable Health Technology" => "medium".to_string(),
-        "Health & Wellness" | "Fitness & Recovery" => "medium".to_string(),
-        _ => "low".to_string(),
-    };
-
-    // Estimated engagement based on trending score and platform match
-    let base_engagement = (trending_score as f64) / 100.0;
-    let platform_boost = programs
-        .first()
-        .map(|p| p.audience_match_score)
-        .unwrap_or(0.5);
-    let estimated_engagement_score = (base_engagement * 0.6 + platform_boost * 0.4).min(1.0);
+    let competition_level = competition_level(category).to_string();
 
     MarketAnalysis {
-        recommended_ad_type: recommended_ad_type.to_string(),
-        recommended_platform,
+        recommended_ad_type: trace.recommended_ad_type,
+        recommended_platform: trace.recommended_platform,
         target_demographic: target_audience.to_string(),
         key_selling_points,
         suggested_tone,
         competition_level,
-        estimated_engagement_score,
+        estimated_engagement_score: trace.engagement.score,
     }
 }
 
 /// Generate key selling points based on category
-fn generate_selling_points(category: &str, product_name: &str) -> Vec<String> {
+pub(crate) fn generate_selling_points(category: &str, product_name: &str) -> Vec<String> {
     match category {
         "Beauty & Skincare" => vec![
             "Clinically proven results".to_string(),
@@ -208,6 +276,7 @@ fn generate_ad_content(
     product: &Product,
     ad_type: &str,
     analysis: &MarketAnalysis,
+    research: Option<&ResearchBrief>,
     custom_instructions: Option<&str>,
 ) -> (String, String, String) {
     let name = &product.name;
@@ -217,6 +286,11 @@ fn generate_ad_content(
     // Incorporate custom instructions into the tone if provided
     let tone_modifier = custom_instructions.unwrap_or("");
 
+    // The researched pain point beats the generic problem statement
+    let problem = research
+        .and_then(|r| r.pain_points.first().cloned())
+        .unwrap_or_else(|| format!("Struggling with your {}?", category.to_lowercase()));
+
     let (headline, body, cta) = match ad_type {
         "social_post" => {
             let headline = format!("Transform your routine with {}", name);
@@ -247,12 +321,12 @@ fn generate_ad_content(
             let headline = format!("STOP scrolling! You need to see this {}", category.to_lowercase());
             let body = format!(
                 "[HOOK] Wait, you don't know about {} yet?\n\n\
-                 [PROBLEM] Struggling with your {}?\n\n\
+                 [PROBLEM] {}\n\n\
                  [SOLUTION] {} is the game-changer you've been waiting for.\n\n\
                  [BENEFITS]\n{}\n\n\
                  [CTA] Link in bio - but hurry, it's selling fast!{}",
                 name,
-                category.to_lowercase(),
+                problem,
                 name,
                 analysis.key_selling_points.iter()
                     .take(3)
@@ -314,6 +388,27 @@ fn generate_ad_content(
             let cta = "Reply STOP to unsubscribe".to_string();
             (headline, body, cta)
         }
+        "pinterest_pin" => {
+            let benefit = analysis
+                .key_selling_points
+                .first()
+                .cloned()
+                .unwrap_or_else(|| format!("a {} favorite", category.to_lowercase()));
+            let headline = format!("{}: {}", name, benefit);
+            let details = analysis.key_selling_points.iter()
+                .skip(1)
+                .take(2)
+                .map(|p| format!(" {}.", p.trim_end_matches('.')))
+                .collect::<String>();
+            let body = format!(
+                "{}{}{}",
+                if description.is_empty() { format!("Meet {}.", name) } else { description.to_string() },
+                details,
+                if tone_modifier.is_empty() { String::new() } else { format!(" {}", tone_modifier) }
+            );
+            let cta = "Shop Now".to_string();
+            (headline, body, cta)
+        }
         _ => {
             let headline = format!("Discover {}", name);
             let body = format!("{} - {}", name, description);
@@ -326,79 +421,183 @@ fn generate_ad_content(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn generate_ad_for_product(
     app_handle: AppHandle,
     product_id: i64,
     ad_type: Option<String>,
     custom_instructions: Option<String>,
+    angle_id: Option<i64>,
+    include_review_snippet: Option<bool>,
 ) -> Result<AdGenerationResult, String> {
-    // Step 1: Fetch the product by ID
-    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let mut result = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        generate_ad(
+            &conn,
+            product_id,
+            ad_type.as_deref(),
+            custom_instructions.as_deref(),
+            angle_id,
+            include_review_snippet.unwrap_or(false),
+        )?
+    }; // Connection dropped before link discovery
+
+    // No active link yet: create one for the recommended platform (or the best program)
+    if let (Some(ad_id), None) = (result.ad_copy.id, result.ad_copy.link_id) {
+        let request = GenerateLinkForPlatformRequest {
+            product_id,
+            platform: result.market_analysis.recommended_platform.clone(),
+            allow_missing_credential: false,
+        };
+        let link = match generate_link_for_platform(app_handle.clone(), request).await {
+            Ok(link) => Ok(link),
+            Err(_) => {
+                let request = GenerateLinkRequest { product_id, allow_missing_credential: false };
+                generate_affiliate_link(app_handle.clone(), request).await
+            }
+        };
+        match link {
+            Ok(link) => {
+                let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+                if let Some(link_id) = link.id {
+                    ad_links::attach(&conn, ad_id, link_id).map_err(|e| e.to_string())?;
+                    result.ad_copy.link_id = Some(link_id);
+                }
+            }
+            Err(e) => eprintln!(
+                "Ad {} saved without an affiliate link: {}",
+                ad_id,
+                credential_checks::error_message(&e)
+            ),
+        }
+    }
+
+    webhooks::emit(&app_handle, webhooks::AD_CREATED, webhooks::ad_data(&result.ad_copy));
+    data_events::emit(
+        &app_handle,
+        data_events::ADS_CHANGED,
+        data_events::CREATED,
+        result.ad_copy.id.into_iter().collect(),
+    );
+    Ok(result)
+}
 
+/// Generates and stores one ad for a product. Shared by the command and the CLI.
+pub(crate) fn generate_ad(
+    conn: &Connection,
+    product_id: i64,
+    ad_type: Option<&str>,
+    custom_instructions: Option<&str>,
+    angle_id: Option<i64>,
+    include_review_snippet: bool,
+) -> Result<AdGenerationResult, String> {
+    // Step 1: Fetch the product by ID
     let product = conn
         .query_row(
-            "SELECT id, name, category, description, price_range, target_audience,
-             trending_score, notes, image_url, amazon_asin, tiktok_product_id,
-             instagram_product_id, youtube_video_id, pinterest_pin_id, product_url,
-             created_at, updated_at
-             FROM products WHERE id = ?1",
+            &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
             params![product_id],
-            |row| {
-                Ok(Product {
-                    id: Some(row.get(0)?),
-                    name: row.get(1)?,
-                    category: row.get(2)?,
-                    description: row.get(3)?,
-                    price_range: row.get(4)?,
-                    target_audience: row.get(5)?,
-                    trending_score: row.get(6)?,
-                    notes: row.get(7)?,
-                    image_url: row.get(8)?,
-                    amazon_asin: row.get(9)?,
-                    tiktok_product_id: row.get(10)?,
-                    instagram_product_id: row.get(11)?,
-                    youtube_video_id: row.get(12)?,
-                    pinterest_pin_id: row.get(13)?,
-                    product_url: row.get(14)?,
-                    created_at: row.get(15)?,
-                    updated_at: row.get(16)?,
-                })
-            },
+            product_from_row,
         )
         .map_err(|e| format!("Product not found: {}", e))?;
 
-    // Step 2: Analyze market for product
-    let market_analysis = analyze_market_for_product(&product);
+    // Step 2: Analyze market for product in the context of the workspace niche
+    let niche_profile = niche::load_active_profile(conn).map_err(|e| e.to_string())?;
+    let research = research::latest_for_product(conn, product_id).map_err(|e| e.to_string())?;
+    let brief = research.as_ref().map(|r| &r.brief);
+
+    // A stored content angle sets the creative direction (and the hook as headline)
+    let angle = match angle_id {
+        Some(id) => Some(
+            content_angles::get(conn, id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Content angle {} not found", id))?,
+        ),
+        None => None,
+    };
+    let affinities = CategoryAffinities::load(conn).map_err(|e| e.to_string())?;
+    let signals = ScoringSignals::load(conn, product_id).map_err(|e| e.to_string())?;
+    let market_analysis =
+        analyze_market_for_product(&product, niche_profile.as_ref(), brief, &affinities, &signals);
 
     // Step 3: Determine ad type (use provided or recommended)
-    let final_ad_type = ad_type
-        .as_deref()
-        .unwrap_or(&market_analysis.recommended_ad_type);
+    let final_ad_type = ad_type.unwrap_or(&market_analysis.recommended_ad_type);
 
-    // Step 4: Generate ad content
+    // Step 4: Generate ad content; explicit instructions take precedence over the angle's direction
+    let instructions = custom_instructions.or_else(|| angle.as_ref().map(|a| a.direction.as_str()));
     let (headline, body_text, cta) = generate_ad_content(
         &product,
         final_ad_type,
         &market_analysis,
-        custom_instructions.as_deref(),
+        brief,
+        instructions,
     );
+    let headline = angle.as_ref().map(|a| a.hook.clone()).unwrap_or(headline);
+
+    // Optional social proof: a verbatim review quote with an attribution placeholder
+    let review_snippet = if include_review_snippet {
+        reviews::top_snippet(conn, product_id).map_err(|e| e.to_string())?
+    } else {
+        None
+    };
+    let body_text = match &review_snippet {
+        Some(snippet) => format!("{}\n\n{}", body_text, reviews::attributed_quote(snippet)),
+        None => body_text,
+    };
+
+    // Per-platform formatting (emoji, capitalization, line breaks, link placement)
+    let style_platform = style_rules::platform_for(final_ad_type, &market_analysis.recommended_platform);
+    let (headline, body_text) =
+        style_rules::apply(&style_rules::load_rules(conn, &style_platform), &headline, &body_text);
+
+    // SMS bodies always carry opt-out text; record the billed segment count
+    let (body_text, sms_segments) = if final_ad_type == "sms" {
+        let (body_text, _) = sms_compliance::ensure_opt_out(&body_text);
+        let segments = sms_compliance::segment_info(&body_text);
+        (body_text, Some(segments))
+    } else {
+        (body_text, None)
+    };
+
+    // Pins carry title, keyword description, alt text, and board suggestions
+    let pin = (final_ad_type == "pinterest_pin")
+        .then(|| pinterest::build_pin(&product, &headline, &body_text));
+
+    // Video scripts get a caption, per-scene on-screen text, and a sound placeholder
+    let short_video = (final_ad_type == "video_script").then(|| {
+        short_video::build_companion(&product, &headline, &body_text, &market_analysis.key_selling_points)
+    });
 
     // Step 5: Save to ad_copies table
     // Note: campaign_id is required by schema, using 0 as placeholder for direct product ads
-    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
-
     let variation_name = format!("{} - {} Ad", product.name, final_ad_type);
     let platform_data = serde_json::json!({
         "target_platform": market_analysis.recommended_platform,
         "suggested_tone": market_analysis.suggested_tone,
         "competition_level": market_analysis.competition_level,
+        "niche": niche_profile.as_ref().map(|p| p.name.clone()),
+        "research_id": research.as_ref().map(|r| r.id),
+        "angle_id": angle.as_ref().map(|a| a.id),
+        "angle_type": angle.as_ref().map(|a| a.angle_type.clone()),
+        "review_snippet_id": review_snippet.as_ref().map(|r| r.id),
+        "sms_segments": sms_segments,
+        "pinterest": pin,
+        "short_video": short_video,
+        "style_platform": style_platform,
     })
     .to_string();
 
+    // Visual formats carry alt text for the product image
+    let alt_text = (accessibility::is_visual(Some(final_ad_type)) && product.image_url.is_some())
+        .then(|| accessibility::alt_text(&product));
+
+    // Attach the product's link for the target platform so [LINK] resolves on export
+    let link_id = ad_links::select_link(conn, product_id, &market_analysis.recommended_platform)
+        .map_err(|e| e.to_string())?;
+
     conn.execute(
         "INSERT INTO ad_copies (campaign_id, product_id, variation_name, headline, body_text,
-         cta, ad_format, ad_type, platform_specific_data, performance_score)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+         cta, ad_format, ad_type, platform_specific_data, performance_score, alt_text, link_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
         params![
             1, // default "Direct Product Ads" campaign (created in migration 007)
             product_id,
@@ -410,6 +609,8 @@ pub async fn generate_ad_for_product(
             final_ad_type,
             platform_data,
             market_analysis.estimated_engagement_score,
+            alt_text,
+            link_id,
         ],
     )
     .map_err(|e| format!("Failed to save ad copy: {}", e))?;
@@ -419,28 +620,9 @@ pub async fn generate_ad_for_product(
     // Fetch the created ad copy
     let ad_copy = conn
         .query_row(
-            "SELECT id, product_id, campaign_id, variation_name, headline, body_text,
-             cta, ad_format, ad_type, platform_specific_data, performance_score,
-             created_at, updated_at
-             FROM ad_copies WHERE id = ?1",
+            &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
             params![id],
-            |row| {
-                Ok(GeneratedAdCopy {
-                    id: Some(row.get(0)?),
-                    product_id: row.get(1)?,
-                    campaign_id: row.get(2)?,
-                    variation_name: row.get(3)?,
-                    headline: row.get(4)?,
-                    body_text: row.get(5)?,
-                    cta: row.get(6)?,
-                    ad_format: row.get(7)?,
-                    ad_type: row.get(8)?,
-                    platform_specific_data: row.get(9)?,
-                    performance_score: row.get(10)?,
-                    created_at: row.get(11)?,
-                    updated_at: row.get(12)?,
-                })
-            },
+            ad_copy_from_row,
         )
         .map_err(|e| format!("Failed to retrieve created ad copy: {}", e))?;
 
@@ -451,6 +633,7 @@ pub async fn generate_ad_for_product(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn get_ads_for_product(
     app_handle: AppHandle,
     product_id: i64,
@@ -458,35 +641,119 @@ pub async fn get_ads_for_product(
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
 
     let mut stmt = conn
-        .prepare(
-            "SELECT id, product_id, campaign_id, variation_name, headline, body_text,
-             cta, ad_format, ad_type, platform_specific_data, performance_score,
-             created_at, updated_at
-             FROM ad_copies WHERE product_id = ?1 ORDER BY created_at DESC",
-        )
+        .prepare_cached(&format!(
+            "SELECT {} FROM ad_copies WHERE product_id = ?1 ORDER BY created_at DESC",
+            AD_COPY_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
     let ads = stmt
-        .query_map(params![product_id], |row| {
-            Ok(GeneratedAdCopy {
-                id: Some(row.get(0)?),
-                product_id: row.get(1)?,
-                campaign_id: row.get(2)?,
-                variation_name: row.get(3)?,
-                headline: row.get(4)?,
-                body_text: row.get(5)?,
-                cta: row.get(6)?,
-                ad_format: row.get(7)?,
-                ad_type: row.get(8)?,
-                platform_specific_data: row.get(9)?,
-                performance_score: row.get(10)?,
-                created_at: row.get(11)?,
-                updated_at: row.get(12)?,
-            })
-        })
+        .query_map(params![product_id], ad_copy_from_row)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
 
     Ok(ads)
 }
+
+/// Marks an ad as a favorite; favorites are protected from age-based cleanup
+#[tauri::command]
+#[specta::specta]
+pub async fn set_ad_favorite(
+    app_handle: AppHandle,
+    ad_id: i64,
+    favorite: bool,
+) -> Result<GeneratedAdCopy, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let updated = conn
+        .execute(
+            "UPDATE ad_copies SET is_favorite = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+            params![favorite, ad_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err(format!("Ad {} not found", ad_id));
+    }
+
+    let ad = conn
+        .query_row(
+            &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+            params![ad_id],
+            ad_copy_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+
+    data_events::emit(&app_handle, data_events::ADS_CHANGED, data_events::UPDATED, vec![ad_id]);
+    Ok(ad)
+}
+
+/// Sets (or clears) an ad's image alt text; `None` regenerates it from the product
+#[tauri::command]
+#[specta::specta]
+pub async fn set_ad_alt_text(
+    app_handle: AppHandle,
+    ad_id: i64,
+    alt_text: Option<String>,
+) -> Result<GeneratedAdCopy, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let alt_text = match alt_text.map(|t| t.trim().to_string()) {
+        Some(text) => text,
+        None => {
+            let product = conn
+                .query_row(
+                    &format!(
+                        "SELECT {} FROM products WHERE id = (SELECT product_id FROM ad_copies WHERE id = ?1)",
+                        PRODUCT_COLUMNS
+                    ),
+                    params![ad_id],
+                    product_from_row,
+                )
+                .map_err(|_| format!("Ad {} has no product to describe", ad_id))?;
+            accessibility::alt_text(&product)
+        }
+    };
+
+    let updated = conn
+        .execute(
+            "UPDATE ad_copies SET alt_text = NULLIF(?1, ''), updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+            params![alt_text, ad_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err(format!("Ad {} not found", ad_id));
+    }
+
+    let ad = conn
+        .query_row(
+            &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+            params![ad_id],
+            ad_copy_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+
+    data_events::emit(&app_handle, data_events::ADS_CHANGED, data_events::UPDATED, vec![ad_id]);
+    Ok(ad)
+}
+
+/// Explain mode: every intermediate value behind a product's recommended platform and ad type
+#[tauri::command]
+#[specta::specta]
+pub async fn explain_recommendation(
+    app_handle: AppHandle,
+    product_id: i64,
+) -> Result<RecommendationTrace, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let product = conn
+        .query_row(
+            &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
+            params![product_id],
+            product_from_row,
+        )
+        .map_err(|e| format!("Product not found: {}", e))?;
+    let niche_profile = niche::load_active_profile(&conn).map_err(|e| e.to_string())?;
+    let affinities = CategoryAffinities::load(&conn).map_err(|e| e.to_string())?;
+    let signals = ScoringSignals::load(&conn, product_id).map_err(|e| e.to_string())?;
+    Ok(recommendation_trace::explain(&product, niche_profile.as_ref(), &affinities, &signals))
+}
@@ -0,0 +1,32 @@
+use crate::database::get_connection;
+use crate::models::ad_import::AdImportSummary;
+use crate::services::{ad_import, data_events};
+use std::path::Path;
+use tauri::AppHandle;
+
+/// Imports previously written ads from a CSV or JSON file, matching each row
+/// to a product by ID or name
+#[tauri::command]
+#[specta::specta]
+pub async fn import_ads(app_handle: AppHandle, file_path: String) -> Result<AdImportSummary, String> {
+    let path = Path::new(file_path.trim());
+    let rows = ad_import::parse_file(path)?;
+    if rows.is_empty() {
+        return Err("The file contains no ads".to_string());
+    }
+
+    let source_file = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let summary = ad_import::import_rows(&mut conn, &rows, &source_file)?;
+
+    data_events::emit(
+        &app_handle,
+        data_events::ADS_CHANGED,
+        data_events::CREATED,
+        summary.imported_ad_ids.clone(),
+    );
+    Ok(summary)
+}
@@ -0,0 +1,21 @@
+use crate::commands::ad_generation::GeneratedAdCopy;
+use crate::database::get_connection;
+use crate::models::affiliate_link::AffiliateLink;
+use crate::services::ad_links;
+use tauri::AppHandle;
+
+/// Ads that have no active affiliate link attached, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_unlinked_ads(app_handle: AppHandle) -> Result<Vec<GeneratedAdCopy>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    ad_links::unlinked_ads(&conn).map_err(|e| e.to_string())
+}
+
+/// Active affiliate links that no ad uses yet, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_unadvertised_links(app_handle: AppHandle) -> Result<Vec<AffiliateLink>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    ad_links::unadvertised_links(&conn).map_err(|e| e.to_string())
+}
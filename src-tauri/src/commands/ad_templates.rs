@@ -0,0 +1,167 @@
+use crate::commands::ad_generation::{
+    ad_copy_from_row, generate_selling_points, AdType, GeneratedAdCopy, AD_COPY_COLUMNS,
+};
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::database::get_connection;
+use crate::models::ad_template::{AdTemplate, SaveAdTemplateInput};
+use crate::services::quick_ad::{self, template_from_row, TEMPLATE_COLUMNS};
+use crate::services::{accessibility, data_events, reviews, sms_compliance, webhooks};
+use rusqlite::{params, OptionalExtension};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_ad_templates(app_handle: AppHandle) -> Result<Vec<AdTemplate>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    quick_ad::list(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_ad_template(app_handle: AppHandle, input: SaveAdTemplateInput) -> Result<AdTemplate, String> {
+    let ad_type = AdType::from_string(input.ad_type.trim()).map(|t| t.to_string());
+    quick_ad::validate(&input, ad_type.is_some())?;
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let cta = input.cta.as_deref().map(str::trim).filter(|c| !c.is_empty());
+
+    let id = match input.id {
+        Some(id) => {
+            let updated = conn
+                .execute(
+                    "UPDATE ad_templates SET name = ?1, ad_type = ?2, headline = ?3, body = ?4, cta = ?5,
+                     updated_at = CURRENT_TIMESTAMP
+                     WHERE id = ?6",
+                    params![input.name.trim(), ad_type, input.headline, input.body, cta, id],
+                )
+                .map_err(|e| e.to_string())?;
+            if updated == 0 {
+                return Err(format!("Ad template {} not found", id));
+            }
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO ad_templates (name, ad_type, headline, body, cta) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![input.name.trim(), ad_type, input.headline, input.body, cta],
+            )
+            .map_err(|e| e.to_string())?;
+            conn.last_insert_rowid()
+        }
+    };
+
+    conn.query_row(
+        &format!("SELECT {} FROM ad_templates WHERE id = ?1", TEMPLATE_COLUMNS),
+        params![id],
+        template_from_row,
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_ad_template(app_handle: AppHandle, template_id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let deleted = conn
+        .execute("DELETE FROM ad_templates WHERE id = ?1", params![template_id])
+        .map_err(|e| e.to_string())?;
+    if deleted == 0 {
+        return Err(format!("Ad template {} not found", template_id));
+    }
+    Ok(())
+}
+
+/// Fills a template's merge tags for a product and saves the result as an ad;
+/// no AI provider is involved, so output is instant and repeatable
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_quick_ad(
+    app_handle: AppHandle,
+    product_id: i64,
+    template_id: i64,
+) -> Result<GeneratedAdCopy, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let product = conn
+        .query_row(
+            &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
+            params![product_id],
+            product_from_row,
+        )
+        .map_err(|e| format!("Product not found: {}", e))?;
+    let template = quick_ad::get(&conn, template_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Ad template {} not found", template_id))?;
+
+    let link: Option<String> = conn
+        .query_row(
+            "SELECT tracking_url FROM affiliate_links
+             WHERE product_id = ?1 AND status = 'active'
+             ORDER BY created_at DESC LIMIT 1",
+            params![product_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let review_quote = reviews::top_snippet(&conn, product_id)
+        .map_err(|e| e.to_string())?
+        .map(|snippet| reviews::attributed_quote(&snippet));
+    let selling_point = generate_selling_points(&product.category, &product.name)
+        .into_iter()
+        .next();
+
+    let values = quick_ad::merge_values(
+        &product,
+        link.as_deref(),
+        selling_point.as_deref(),
+        review_quote.as_deref(),
+    );
+    let headline = quick_ad::render(&template.headline, &values);
+    let body_text = quick_ad::render(&template.body, &values);
+    let cta = template.cta.as_deref().map(|c| quick_ad::render(c, &values));
+
+    // SMS bodies always carry opt-out text
+    let body_text = if template.ad_type == "sms" {
+        sms_compliance::ensure_opt_out(&body_text).0
+    } else {
+        body_text
+    };
+    let alt_text = (accessibility::is_visual(Some(&template.ad_type)) && product.image_url.is_some())
+        .then(|| accessibility::alt_text(&product));
+
+    let platform_data = serde_json::json!({
+        "template_id": template.id,
+        "template_name": template.name,
+        "quick_ad": true,
+    })
+    .to_string();
+
+    conn.execute(
+        "INSERT INTO ad_copies (campaign_id, product_id, variation_name, headline, body_text,
+         cta, ad_format, ad_type, platform_specific_data, alt_text)
+         VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?6, ?7, ?8)",
+        params![
+            product_id,
+            format!("{} - {} (Quick Ad)", product.name, template.name),
+            headline,
+            body_text,
+            cta,
+            template.ad_type,
+            platform_data,
+            alt_text,
+        ],
+    )
+    .map_err(|e| format!("Failed to save quick ad: {}", e))?;
+
+    let ad = conn
+        .query_row(
+            &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+            params![conn.last_insert_rowid()],
+            ad_copy_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+
+    webhooks::emit(&app_handle, webhooks::AD_CREATED, webhooks::ad_data(&ad));
+    data_events::emit(&app_handle, data_events::ADS_CHANGED, data_events::CREATED, ad.id.into_iter().collect());
+    Ok(ad)
+}
@@ -1,41 +1,65 @@
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
 use crate::database::get_connection;
 use crate::models::affiliate_link::{
-    AffiliateLink, AffiliateProgramDiscovery, CreateAffiliateLinkInput, GenerateLinkRequest,
-    GenerateLinkForPlatformRequest,
+    AffiliateLink, AffiliateProgramDiscovery, BulkLinkFilter, BulkLinkGenerationResult,
+    BulkLinkSkip, CreateAffiliateLinkInput, GenerateLinkRequest, GenerateLinkForPlatformRequest,
 };
-use crate::services::ai_affiliate::{generate_tracking_url, mock_ai_discovery_with_platforms};
-use rusqlite::params;
+use crate::models::ai_discovery::AiDiscoveryRecord;
+use crate::models::bulk_operation::BulkOperation;
+use crate::models::entity_query::QuerySort;
+use crate::models::link_refresh::RefreshDiffReport;
+use crate::models::list_page::{LinkListFilter, LinkPage};
+use crate::commands::bulk_operations::run_operation;
+use crate::services::ai_affiliate::{
+    build_discovery_prompt, generate_tracking_url, match_breakdown, mock_ai_discovery_with_platforms,
+    parse_ai_response,
+};
+use crate::services::bulk_operations::{self, NewItem};
+use crate::services::category_keywords::CategoryAffinities;
+use crate::services::{
+    ai_cache, ai_client, ai_discovery, bulk_links, credential_checks, data_events, link_refresh, list_pages,
+    network_terms, niche, program_applications, webhooks,
+};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension, Row};
 use tauri::AppHandle;
 
+/// Column list shared by every link query; keep in sync with `link_from_row`
+pub(crate) const LINK_COLUMNS: &str = "id, product_id, product_name, platform, program_name,
+     commission_rate, cookie_duration, tracking_url, destination_url, status, created_at, updated_at";
+
+/// Maps a row selected with `LINK_COLUMNS` into an `AffiliateLink`
+pub(crate) fn link_from_row(row: &Row) -> rusqlite::Result<AffiliateLink> {
+    Ok(AffiliateLink {
+        id: Some(row.get(0)?),
+        product_id: row.get(1)?,
+        product_name: row.get(2)?,
+        platform: row.get(3)?,
+        program_name: row.get(4)?,
+        commission_rate: row.get(5)?,
+        cookie_duration: row.get(6)?,
+        tracking_url: row.get(7)?,
+        destination_url: row.get(8)?,
+        status: row.get(9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+    })
+}
+
 #[tauri::command]
+#[specta::specta]
 pub async fn get_all_affiliate_links(app_handle: AppHandle) -> Result<Vec<AffiliateLink>, String> {
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
 
     let mut stmt = conn
-        .prepare(
-            "SELECT id, product_id, product_name, platform, program_name, commission_rate,
-             cookie_duration, tracking_url, destination_url, status, created_at, updated_at
-             FROM affiliate_links ORDER BY created_at DESC",
-        )
+        .prepare_cached(&format!(
+            "SELECT {} FROM affiliate_links ORDER BY created_at DESC",
+            LINK_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
     let links = stmt
-        .query_map([], |row| {
-            Ok(AffiliateLink {
-                id: Some(row.get(0)?),
-                product_id: row.get(1)?,
-                product_name: row.get(2)?,
-                platform: row.get(3)?,
-                program_name: row.get(4)?,
-                commission_rate: row.get(5)?,
-                cookie_duration: row.get(6)?,
-                tracking_url: row.get(7)?,
-                destination_url: row.get(8)?,
-                status: row.get(9)?,
-                created_at: row.get(10)?,
-                updated_at: row.get(11)?,
-            })
-        })
+        .query_map([], link_from_row)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
@@ -43,7 +67,31 @@ pub async fn get_all_affiliate_links(app_handle: AppHandle) -> Result<Vec<Affili
     Ok(links)
 }
 
+/// Number of links matching `filter`, for sizing a virtualized table
+#[tauri::command]
+#[specta::specta]
+pub async fn count_links(app_handle: AppHandle, filter: Option<LinkListFilter>) -> Result<i64, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    list_pages::count_links(&conn, &filter.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+/// Rows `offset..offset + limit` (at most 500) of the filtered link list,
+/// plus its total. Newest first unless `sort` is given.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_links_range(
+    app_handle: AppHandle,
+    filter: Option<LinkListFilter>,
+    sort: Option<QuerySort>,
+    offset: i64,
+    limit: i64,
+) -> Result<LinkPage, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    list_pages::link_page(&conn, &filter.unwrap_or_default(), &sort, offset, limit)
+}
+
 #[tauri::command]
+#[specta::specta]
 pub async fn get_links_by_product(
     app_handle: AppHandle,
     product_id: i64,
@@ -51,30 +99,14 @@ pub async fn get_links_by_product(
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
 
     let mut stmt = conn
-        .prepare(
-            "SELECT id, product_id, product_name, platform, program_name, commission_rate,
-             cookie_duration, tracking_url, destination_url, status, created_at, updated_at
-             FROM affiliate_links WHERE product_id = ?1 ORDER BY created_at DESC",
-        )
+        .prepare_cached(&format!(
+            "SELECT {} FROM affiliate_links WHERE product_id = ?1 ORDER BY created_at DESC",
+            LINK_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
     let links = stmt
-        .query_map(params![product_id], |row| {
-            Ok(AffiliateLink {
-                id: Some(row.get(0)?),
-                product_id: row.get(1)?,
-                product_name: row.get(2)?,
-                platform: row.get(3)?,
-                program_name: row.get(4)?,
-                commission_rate: row.get(5)?,
-                cookie_duration: row.get(6)?,
-                tracking_url: row.get(7)?,
-                destination_url: row.get(8)?,
-                status: row.get(9)?,
-                created_at: row.get(10)?,
-                updated_at: row.get(11)?,
-            })
-        })
+        .query_map(params![product_id], link_from_row)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
@@ -83,46 +115,126 @@ pub async fn get_links_by_product(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn discover_affiliate_programs(
     app_handle: AppHandle,
     product_id: i64,
 ) -> Result<Vec<AffiliateProgramDiscovery>, String> {
-    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    // Read everything needed up front; the connection can't be held across the provider call
+    let (product, target_audience, niche_profile, affinities, chain, cached, applications, terms) = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
 
-    // Fetch ALL product metrics
-    let product = conn
-        .query_row(
-            "SELECT name, category, description, price_range, target_audience, trending_score
-             FROM products WHERE id = ?1",
-            params![product_id],
-            |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, Option<String>>(2)?.unwrap_or_default(),
-                    row.get::<_, Option<String>>(3)?.unwrap_or_default(),
-                    row.get::<_, Option<String>>(4)?.unwrap_or_default(),
-                    row.get::<_, Option<i32>>(5)?.unwrap_or(50),
-                ))
-            },
-        )
-        .map_err(|e| format!("Product not found: {}", e))?;
+        // Fetch ALL product metrics
+        let product = conn
+            .query_row(
+                "SELECT name, category, description, price_range, target_audience, trending_score
+                 FROM products WHERE id = ?1",
+                params![product_id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                        row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                        row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+                        row.get::<_, Option<i32>>(5)?.unwrap_or(50),
+                    ))
+                },
+            )
+            .map_err(|e| format!("Product not found: {}", e))?;
+
+        // Workspace niche profile fills in a missing audience and biases platforms
+        let niche_profile = niche::load_active_profile(&conn).map_err(|e| e.to_string())?;
+        let target_audience = niche::effective_audience(&product.4, niche_profile.as_ref());
+        let affinities = CategoryAffinities::load(&conn).map_err(|e| e.to_string())?;
+
+        let prompt = build_discovery_prompt(
+            &product.0,
+            &product.1,
+            &product.2,
+            &product.3,
+            &target_audience,
+            product.5,
+            &niche::prompt_context(niche_profile.as_ref()),
+        );
+        let chain = ai_client::prepare_chain(&conn, &prompt);
+        // Only the primary's cache is consulted so a recovered primary takes over again
+        let cached = match chain.calls.first() {
+            Some(call) => ai_cache::get(&conn, &call.cache_key).map_err(|e| e.to_string())?,
+            None => None,
+        };
+
+        let applications = program_applications::statuses_by_name(&conn).map_err(|e| e.to_string())?;
+        let terms = network_terms::list(&conn).map_err(|e| e.to_string())?;
+
+        (product, target_audience, niche_profile, affinities, chain, cached, applications, terms)
+    };
 
-    let (name, category, _description, price_range, target_audience, trending_score) = product;
+    let (name, category, _description, price_range, _audience, trending_score) = product;
+
+    // Platform-aware discovery with all metrics; also the last resort of the fallback chain
+    let mock = || {
+        mock_ai_discovery_with_platforms(
+            &name,
+            &category,
+            trending_score,
+            &target_audience,
+            &price_range,
+            &affinities,
+        )
+    };
 
-    // Call platform-aware discovery with all metrics
-    let programs = mock_ai_discovery_with_platforms(
-        &name,
-        &category,
-        trending_score,
-        &target_audience,
-        &price_range,
-    );
+    let programs = if chain.is_mock_only() {
+        mock()
+    } else if let Some(cached) = cached {
+        parse_ai_response(&cached)?
+    } else {
+        // Validated, repaired, and falling back along the chain; logged either way
+        ai_discovery::run(&app_handle, product_id, &chain, mock).await?
+    };
 
+    // AI results are explained with the local heuristic's factors
+    let programs = programs
+        .into_iter()
+        .map(|mut program| {
+            if program.match_breakdown.is_none() {
+                let breakdown = match_breakdown(
+                    &program.platform.to_string(),
+                    &category,
+                    trending_score,
+                    &target_audience,
+                    &price_range,
+                    &affinities,
+                );
+                program.match_breakdown = Some(breakdown);
+            }
+            program
+        })
+        .collect();
+    let mut programs = niche::apply_to_programs(programs, niche_profile.as_ref());
+    for program in programs.iter_mut() {
+        program.application_status = applications.get(&program.program_name.to_lowercase()).cloned();
+        program.network_terms =
+            network_terms::match_terms(&program.program_name, &program.platform.to_string(), &terms).cloned();
+    }
     Ok(programs)
 }
 
+/// Recent provider-backed discovery runs for a product, with parse diagnostics
+#[tauri::command]
+#[specta::specta]
+pub async fn get_discovery_diagnostics(
+    app_handle: AppHandle,
+    product_id: i64,
+    limit: Option<i64>,
+) -> Result<Vec<AiDiscoveryRecord>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    ai_discovery::list_for_product(&conn, product_id, limit.unwrap_or(20))
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
+#[specta::specta]
 pub async fn generate_affiliate_link(
     app_handle: AppHandle,
     request: GenerateLinkRequest,
@@ -145,7 +257,9 @@ pub async fn generate_affiliate_link(
         .ok_or("Failed to select best program")?;
 
     // Fetch product details
+    let platform_str = best_program.platform.to_string();
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let affiliate_id = credential_checks::require(&conn, &platform_str, request.allow_missing_credential)?;
     let product_name: String = conn
         .query_row(
             "SELECT name FROM products WHERE id = ?1",
@@ -155,12 +269,12 @@ pub async fn generate_affiliate_link(
         .map_err(|e| format!("Product not found: {}", e))?;
 
     // Generate tracking URL with platform
-    let platform_str = best_program.platform.to_string();
     let tracking_url = generate_tracking_url(
         &platform_str,
         &best_program.program_name,
         &product_name,
         &best_program.affiliate_url,
+        affiliate_id.as_deref(),
     );
 
     let input = CreateAffiliateLinkInput {
@@ -178,6 +292,7 @@ pub async fn generate_affiliate_link(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn generate_link_for_platform(
     app_handle: AppHandle,
     request: GenerateLinkForPlatformRequest,
@@ -192,7 +307,9 @@ pub async fn generate_link_for_platform(
         .ok_or_else(|| format!("Platform {} not available for this product", request.platform))?;
 
     // Fetch product details
+    let platform_str = selected_program.platform.to_string();
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let affiliate_id = credential_checks::require(&conn, &platform_str, request.allow_missing_credential)?;
     let product_name: String = conn
         .query_row(
             "SELECT name FROM products WHERE id = ?1",
@@ -202,12 +319,12 @@ pub async fn generate_link_for_platform(
         .map_err(|e| format!("Product not found: {}", e))?;
 
     // Generate tracking URL
-    let platform_str = selected_program.platform.to_string();
     let tracking_url = generate_tracking_url(
         &platform_str,
         &selected_program.program_name,
         &product_name,
         &selected_program.affiliate_url,
+        affiliate_id.as_deref(),
     );
 
     let input = CreateAffiliateLinkInput {
@@ -225,6 +342,7 @@ pub async fn generate_link_for_platform(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn create_affiliate_link(
     app_handle: AppHandle,
     input: CreateAffiliateLinkInput,
@@ -253,39 +371,48 @@ pub async fn create_affiliate_link(
     // Fetch the created link
     let link = conn
         .query_row(
-            "SELECT id, product_id, product_name, platform, program_name, commission_rate,
-             cookie_duration, tracking_url, destination_url, status, created_at, updated_at
-             FROM affiliate_links WHERE id = ?1",
+            &format!("SELECT {} FROM affiliate_links WHERE id = ?1", LINK_COLUMNS),
             params![id],
-            |row| {
-                Ok(AffiliateLink {
-                    id: Some(row.get(0)?),
-                    product_id: row.get(1)?,
-                    product_name: row.get(2)?,
-                    platform: row.get(3)?,
-                    program_name: row.get(4)?,
-                    commission_rate: row.get(5)?,
-                    cookie_duration: row.get(6)?,
-                    tracking_url: row.get(7)?,
-                    destination_url: row.get(8)?,
-                    status: row.get(9)?,
-                    created_at: row.get(10)?,
-                    updated_at: row.get(11)?,
-                })
-            },
+            link_from_row,
         )
         .map_err(|e| e.to_string())?;
 
+    data_events::emit(&app_handle, data_events::LINKS_CHANGED, data_events::CREATED, vec![id]);
+    webhooks::emit(
+        &app_handle,
+        webhooks::LINK_CREATED,
+        serde_json::json!({
+            "link_id": link.id,
+            "product_id": link.product_id,
+            "platform": link.platform,
+            "program_name": link.program_name,
+            "tracking_url": link.tracking_url,
+        }),
+    );
+
     Ok(link)
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn refresh_affiliate_link(
     app_handle: AppHandle,
     link_id: i64,
+    allow_missing_credential: Option<bool>,
+) -> Result<AffiliateLink, String> {
+    refresh_link(&app_handle, link_id, allow_missing_credential.unwrap_or(false), None).await
+}
+
+/// Moves a link to the best program discovery finds now and records what
+/// changed; `operation_id` is the bulk refresh run doing it, if any
+pub(crate) async fn refresh_link(
+    app_handle: &AppHandle,
+    link_id: i64,
+    allow_missing_credential: bool,
+    operation_id: Option<i64>,
 ) -> Result<AffiliateLink, String> {
     // Get existing link
-    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
 
     let (product_id, product_name): (i64, String) = conn
         .query_row(
@@ -312,16 +439,18 @@ pub async fn refresh_affiliate_link(
         .ok_or("Failed to select best program")?;
 
     let platform_str = best_program.platform.to_string();
+    let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    let affiliate_id = credential_checks::require(&conn, &platform_str, allow_missing_credential)?;
     let tracking_url = generate_tracking_url(
         &platform_str,
         &best_program.program_name,
         &product_name,
         &best_program.affiliate_url,
+        affiliate_id.as_deref(),
     );
 
-    // Update existing link
-    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
-
+    // Update existing link, keeping what it was for the refresh log
+    let before = link_refresh::snapshot(&conn, link_id).map_err(|e| e.to_string())?;
     conn.execute(
         "UPDATE affiliate_links SET platform = ?1, program_name = ?2, commission_rate = ?3,
          cookie_duration = ?4, tracking_url = ?5, destination_url = ?6,
@@ -337,97 +466,192 @@ pub async fn refresh_affiliate_link(
         ],
     )
     .map_err(|e| e.to_string())?;
+    let after = link_refresh::snapshot(&conn, link_id).map_err(|e| e.to_string())?;
+    link_refresh::record(&conn, link_id, product_id, operation_id, &before, &after)?;
 
     // Fetch updated link
     let link = conn
         .query_row(
-            "SELECT id, product_id, product_name, platform, program_name, commission_rate,
-             cookie_duration, tracking_url, destination_url, status, created_at, updated_at
-             FROM affiliate_links WHERE id = ?1",
+            &format!("SELECT {} FROM affiliate_links WHERE id = ?1", LINK_COLUMNS),
             params![link_id],
-            |row| {
-                Ok(AffiliateLink {
-                    id: Some(row.get(0)?),
-                    product_id: row.get(1)?,
-                    product_name: row.get(2)?,
-                    platform: row.get(3)?,
-                    program_name: row.get(4)?,
-                    commission_rate: row.get(5)?,
-                    cookie_duration: row.get(6)?,
-                    tracking_url: row.get(7)?,
-                    destination_url: row.get(8)?,
-                    status: row.get(9)?,
-                    created_at: row.get(10)?,
-                    updated_at: row.get(11)?,
-                })
-            },
+            link_from_row,
         )
         .map_err(|e| e.to_string())?;
 
+    data_events::emit(app_handle, data_events::LINKS_CHANGED, data_events::UPDATED, vec![link_id]);
     Ok(link)
 }
 
+/// Refreshes every link of the selected products as a tracked bulk
+/// operation; what each refresh changed is kept for `get_refresh_diffs`
 #[tauri::command]
+#[specta::specta]
+pub async fn refresh_links_for_products(
+    app_handle: AppHandle,
+    product_ids: Vec<i64>,
+    allow_missing_credential: Option<bool>,
+) -> Result<BulkOperation, String> {
+    let operation_id = {
+        let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+        let mut items = Vec::new();
+        for product_id in product_ids {
+            let found: Option<(String, i64)> = conn
+                .query_row(
+                    "SELECT name, (SELECT COUNT(*) FROM affiliate_links WHERE product_id = products.id)
+                     FROM products WHERE id = ?1",
+                    params![product_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?;
+            let skip_reason = match &found {
+                None => Some("Product not found".to_string()),
+                Some((_, 0)) => Some("Product has no links to refresh".to_string()),
+                Some(_) => None,
+            };
+            items.push(NewItem {
+                product_id,
+                skip_reason,
+                product_name: found.map(|(name, _)| name).unwrap_or_default(),
+            });
+        }
+
+        let params_json = serde_json::json!({
+            "allowMissingCredential": allow_missing_credential.unwrap_or(false),
+        })
+        .to_string();
+        bulk_operations::create_operation(&mut conn, "refresh", &params_json, &items).map_err(|e| e.to_string())?
+    };
+
+    run_operation(&app_handle, operation_id).await
+}
+
+/// What link refreshes changed in `period` ("24h", "7d", "30d", "90d", or
+/// "all"), newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_refresh_diffs(app_handle: AppHandle, period: String) -> Result<RefreshDiffReport, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    link_refresh::diffs(&conn, &period, Utc::now().naive_utc())
+}
+
+#[tauri::command]
+#[specta::specta]
 pub async fn delete_affiliate_link(app_handle: AppHandle, id: i64) -> Result<(), String> {
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
 
     conn.execute("DELETE FROM affiliate_links WHERE id = ?1", params![id])
         .map_err(|e| e.to_string())?;
 
+    data_events::emit(&app_handle, data_events::LINKS_CHANGED, data_events::DELETED, vec![id]);
     Ok(())
 }
 
+/// Generates links for every product that has none yet
 #[tauri::command]
+#[specta::specta]
 pub async fn generate_links_for_all_products(
     app_handle: AppHandle,
 ) -> Result<Vec<AffiliateLink>, String> {
-    // Get all products - collect IDs and drop connection before awaiting
-    let product_ids: Vec<i64> = {
-        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let filter = BulkLinkFilter {
+        missing_platform_only: true,
+        ..Default::default()
+    };
+    let result = generate_links_for_products(app_handle, filter).await?;
+    Ok(result.generated)
+}
+
+/// Generates links for the products selected by `filter`, reporting which
+/// products were skipped by the filter and which failed to generate.
+/// The run is persisted as a bulk operation and emits progress events.
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_links_for_products(
+    app_handle: AppHandle,
+    filter: BulkLinkFilter,
+) -> Result<BulkLinkGenerationResult, String> {
+    let operation_id = {
+        let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
 
         let mut stmt = conn
-            .prepare("SELECT id FROM products")
+            .prepare(&format!("SELECT {} FROM products ORDER BY id", PRODUCT_COLUMNS))
             .map_err(|e| e.to_string())?;
-
-        let ids = stmt
-            .query_map([], |row| row.get(0))
+        let products = stmt
+            .query_map([], product_from_row)
             .map_err(|e| e.to_string())?
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| e.to_string())?;
 
-        ids
-    }; // Connection dropped here
+        let mut platform_stmt = conn
+            .prepare("SELECT DISTINCT COALESCE(platform, '') FROM affiliate_links WHERE product_id = ?1")
+            .map_err(|e| e.to_string())?;
 
-    // Generate links for each product
-    let mut generated_links = Vec::new();
+        let mut items = Vec::new();
+        for product in products {
+            let linked_platforms = platform_stmt
+                .query_map(params![product.id], |row| row.get(0))
+                .map_err(|e| e.to_string())?
+                .collect::<Result<Vec<String>, _>>()
+                .map_err(|e| e.to_string())?;
+
+            items.push(NewItem {
+                product_id: product.id.unwrap_or_default(),
+                skip_reason: bulk_links::skip_reason(&product, &linked_platforms, &filter),
+                product_name: product.name,
+            });
+        }
+        drop(platform_stmt);
+        drop(stmt);
 
-    for product_id in product_ids {
-        // Check if link already exists - use scoped connection
-        let exists: bool = {
-            let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
-            conn.query_row(
-                "SELECT COUNT(*) FROM affiliate_links WHERE product_id = ?1",
-                params![product_id],
-                |row| {
-                    let count: i64 = row.get(0)?;
-                    Ok(count > 0)
-                },
-            )
-            .unwrap_or(false)
-        }; // Connection dropped here
+        let params_json = serde_json::to_string(&filter).map_err(|e| e.to_string())?;
+        bulk_operations::create_operation(&mut conn, "links", &params_json, &items)
+            .map_err(|e| e.to_string())?
+    }; // Connection dropped before awaiting
 
-        if !exists {
-            match generate_affiliate_link(
-                app_handle.clone(),
-                GenerateLinkRequest { product_id },
-            )
-            .await
-            {
-                Ok(link) => generated_links.push(link),
-                Err(e) => eprintln!("Failed to generate link for product {}: {}", product_id, e),
+    run_operation(&app_handle, operation_id).await?;
+    link_generation_result(&app_handle, operation_id)
+}
+
+/// Summarizes a links bulk operation from its persisted items
+pub(crate) fn link_generation_result(
+    app_handle: &AppHandle,
+    operation_id: i64,
+) -> Result<BulkLinkGenerationResult, String> {
+    let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    let items = bulk_operations::list_items(&conn, operation_id, None).map_err(|e| e.to_string())?;
+
+    let mut result = BulkLinkGenerationResult {
+        operation_id,
+        generated: Vec::new(),
+        skipped: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for item in items {
+        let skip = || BulkLinkSkip {
+            product_id: item.product_id,
+            product_name: item.product_name.clone(),
+            reason: item.message.clone().unwrap_or_default(),
+        };
+
+        match (item.status.as_str(), item.result_id) {
+            ("succeeded", Some(link_id)) => {
+                let link = conn
+                    .query_row(
+                        &format!("SELECT {} FROM affiliate_links WHERE id = ?1", LINK_COLUMNS),
+                        params![link_id],
+                        link_from_row,
+                    )
+                    .optional()
+                    .map_err(|e| e.to_string())?;
+                result.generated.extend(link);
             }
+            ("skipped", _) => result.skipped.push(skip()),
+            ("failed", _) => result.failed.push(skip()),
+            _ => {}
         }
     }
 
-    Ok(generated_links)
+    Ok(result)
 }
@@ -0,0 +1,82 @@
+use crate::commands::credentials::get_credential_by_platform;
+use crate::database::get_connection;
+use crate::models::traffic::{AnalyticsImportSummary, TrafficSession};
+use crate::services::analytics_import::{fetch_traffic, store_traffic_rows};
+use chrono::{Duration, Utc};
+use rusqlite::params;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn import_analytics_traffic(
+    app_handle: AppHandle,
+    provider: String,
+    days: Option<i64>,
+) -> Result<AnalyticsImportSummary, String> {
+    let provider = provider.to_lowercase();
+    let credential = get_credential_by_platform(app_handle.clone(), provider.clone())
+        .await?
+        .filter(|c| c.active)
+        .ok_or_else(|| format!("No active credentials saved for {}", provider))?;
+
+    let end = Utc::now().date_naive();
+    let start = end - Duration::days(days.unwrap_or(30).max(1) - 1);
+    let start_date = start.format("%Y-%m-%d").to_string();
+    let end_date = end.format("%Y-%m-%d").to_string();
+
+    let rows = fetch_traffic(&provider, &credential, &start_date, &end_date).await?;
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let (rows_matched, unmatched_campaigns) =
+        store_traffic_rows(&conn, &provider, &rows).map_err(|e| e.to_string())?;
+
+    Ok(AnalyticsImportSummary {
+        provider,
+        start_date,
+        end_date,
+        rows_fetched: rows.len(),
+        rows_matched,
+        unmatched_campaigns,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_traffic_sessions(
+    app_handle: AppHandle,
+    link_id: Option<i64>,
+    product_id: Option<i64>,
+) -> Result<Vec<TrafficSession>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT t.id, t.link_id, t.provider, t.utm_campaign, t.utm_source, t.date,
+             t.sessions, t.events, t.imported_at
+             FROM traffic_sessions t
+             LEFT JOIN affiliate_links l ON l.id = t.link_id
+             WHERE (?1 IS NULL OR t.link_id = ?1) AND (?2 IS NULL OR l.product_id = ?2)
+             ORDER BY t.date DESC, t.sessions DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let sessions = stmt
+        .query_map(params![link_id, product_id], |row| {
+            Ok(TrafficSession {
+                id: row.get(0)?,
+                link_id: row.get(1)?,
+                provider: row.get(2)?,
+                utm_campaign: row.get(3)?,
+                utm_source: row.get(4)?,
+                date: row.get(5)?,
+                sessions: row.get(6)?,
+                events: row.get(7)?,
+                imported_at: row.get(8)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(sessions)
+}
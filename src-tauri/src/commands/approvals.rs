@@ -0,0 +1,102 @@
+use crate::commands::ad_generation::{ad_copy_from_row, AD_COPY_COLUMNS};
+use crate::database::get_connection;
+use crate::database::settings::{get_setting_or, set_setting};
+use crate::models::approval::{ApprovalQueue, ApprovalTransition};
+use crate::services::approvals::{self, APPROVAL_STATES};
+use crate::services::post_schedule::{post_from_row, CANCELLED, POST_COLUMNS, POST_FROM};
+use crate::services::data_events;
+use rusqlite::params;
+use tauri::AppHandle;
+
+/// Moves an ad (`entity_type` "ad") or scheduled post ("scheduled_post")
+/// through the approval workflow, recording the reviewer and their comment
+#[tauri::command]
+#[specta::specta]
+pub async fn transition_approval(
+    app_handle: AppHandle,
+    entity_type: String,
+    entity_id: i64,
+    to_state: String,
+    reviewer: Option<String>,
+    comment: Option<String>,
+) -> Result<ApprovalTransition, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let transition = approvals::transition(
+        &conn,
+        &entity_type,
+        entity_id,
+        &to_state,
+        reviewer.as_deref(),
+        comment.as_deref(),
+    )?;
+
+    if entity_type == approvals::ENTITY_AD {
+        data_events::emit(&app_handle, data_events::ADS_CHANGED, data_events::UPDATED, vec![entity_id]);
+    }
+    Ok(transition)
+}
+
+/// Every transition of an ad or scheduled post with reviewer comments, oldest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_approval_history(
+    app_handle: AppHandle,
+    entity_type: String,
+    entity_id: i64,
+) -> Result<Vec<ApprovalTransition>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    approvals::history(&conn, &entity_type, entity_id)
+}
+
+/// Ads and upcoming posts in one approval state, e.g. everything awaiting review
+#[tauri::command]
+#[specta::specta]
+pub async fn get_approval_queue(app_handle: AppHandle, state: String) -> Result<ApprovalQueue, String> {
+    if !APPROVAL_STATES.contains(&state.as_str()) {
+        return Err(format!("Unknown approval state '{}'; expected one of {}", state, APPROVAL_STATES.join(", ")));
+    }
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM ad_copies WHERE approval_state = ?1 ORDER BY updated_at DESC, id DESC",
+            AD_COPY_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let ads = stmt
+        .query_map(params![state], ad_copy_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM {} WHERE s.approval_state = ?1 AND s.status != ?2
+             ORDER BY s.scheduled_at, s.id",
+            POST_COLUMNS, POST_FROM
+        ))
+        .map_err(|e| e.to_string())?;
+    let posts = stmt
+        .query_map(params![state, CANCELLED], post_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(ApprovalQueue { state, ads, posts })
+}
+
+/// Whether posts must be approved before they can be marked posted
+#[tauri::command]
+#[specta::specta]
+pub async fn get_approval_required(app_handle: AppHandle) -> Result<bool, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    Ok(get_setting_or(&conn, approvals::REQUIRED_SETTING, "false") == "true")
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_approval_required(app_handle: AppHandle, required: bool) -> Result<bool, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    set_setting(&conn, approvals::REQUIRED_SETTING, &required.to_string()).map_err(|e| e.to_string())?;
+    Ok(required)
+}
@@ -0,0 +1,67 @@
+use crate::database::{database_path, get_connection};
+use crate::models::attachment::Attachment;
+use crate::services::attachments;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+/// Copies a file into the attachments folder and attaches it to a product,
+/// campaign, or credential
+#[tauri::command]
+#[specta::specta]
+pub async fn add_attachment(
+    app_handle: AppHandle,
+    entity_type: String,
+    entity_id: i64,
+    file_path: String,
+    description: Option<String>,
+) -> Result<Attachment, String> {
+    let folder = attachments::folder(&database_path(&app_handle));
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let attachment = attachments::add(
+        &mut conn,
+        &folder,
+        &entity_type,
+        entity_id,
+        &PathBuf::from(file_path),
+        description.as_deref(),
+    )?;
+    if let Err(e) = attachments::prune(&conn, &folder) {
+        eprintln!("Failed to prune attachments: {}", e);
+    }
+    Ok(attachment)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_attachments(
+    app_handle: AppHandle,
+    entity_type: String,
+    entity_id: i64,
+) -> Result<Vec<Attachment>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    attachments::list(&conn, &entity_type, entity_id).map_err(|e| e.to_string())
+}
+
+/// Opens the attachment with the system's default app for its type
+#[tauri::command]
+#[specta::specta]
+pub async fn open_attachment(app_handle: AppHandle, attachment_id: i64) -> Result<(), String> {
+    let folder = attachments::folder(&database_path(&app_handle));
+    let path = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        attachments::path(&conn, &folder, attachment_id)?
+    };
+    app_handle
+        .opener()
+        .open_path(path.to_string_lossy().to_string(), None::<&str>)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_attachment(app_handle: AppHandle, attachment_id: i64) -> Result<(), String> {
+    let folder = attachments::folder(&database_path(&app_handle));
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    attachments::delete(&conn, &folder, attachment_id)
+}
@@ -0,0 +1,328 @@
+use crate::commands::ad_generation::generate_ad_for_product;
+use crate::commands::affiliate_links::{generate_affiliate_link, generate_link_for_platform, refresh_link};
+use crate::database::get_connection;
+use crate::models::affiliate_link::{GenerateLinkForPlatformRequest, GenerateLinkRequest};
+use crate::models::bulk_operation::{BulkOperation, BulkOperationItem, IdentifierImport};
+use crate::services::bulk_operations::{self, NewItem, PROGRESS_EVENT};
+use crate::services::scraper::scrape_product;
+use crate::services::{availability, credential_checks, data_events, identifier_import, shared_workspace};
+use futures::stream::{self, StreamExt};
+use rusqlite::params;
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter};
+
+fn load_operation(app_handle: &AppHandle, operation_id: i64) -> Result<BulkOperation, String> {
+    let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    bulk_operations::get_operation(&conn, operation_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Bulk operation {} not found", operation_id))
+}
+
+fn emit_progress(app_handle: &AppHandle, operation: &BulkOperation, product_id: Option<i64>) {
+    let payload = bulk_operations::progress(operation, product_id);
+    if let Err(e) = app_handle.emit(PROGRESS_EVENT, &payload) {
+        eprintln!("Failed to emit bulk progress: {}", e);
+    }
+}
+
+/// Processes every pending item of an operation with bounded parallelism,
+/// emitting a progress event after each one. Stops picking up new items once
+/// the operation is cancelled.
+pub(crate) async fn run_operation(app_handle: &AppHandle, operation_id: i64) -> Result<BulkOperation, String> {
+    let (operation, pending, parallelism) = {
+        let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        bulk_operations::set_status(&conn, operation_id, "running").map_err(|e| e.to_string())?;
+        let pending = bulk_operations::list_items(&conn, operation_id, Some("pending"))
+            .map_err(|e| e.to_string())?;
+        let parallelism = bulk_operations::load_parallelism(&conn);
+        (load_operation(app_handle, operation_id)?, pending, parallelism)
+    };
+    emit_progress(app_handle, &operation, None);
+
+    let options: Value = serde_json::from_str(&operation.params_json).unwrap_or(Value::Null);
+    let kind = operation.kind.as_str();
+    let cancelled = AtomicBool::new(false);
+
+    stream::iter(pending)
+        .for_each_concurrent(parallelism, |item| {
+            let (options, cancelled) = (&options, &cancelled);
+            async move {
+                if cancelled.load(Ordering::SeqCst) || is_cancelled(app_handle, operation_id) {
+                    cancelled.store(true, Ordering::SeqCst);
+                    return;
+                }
+
+                let result = process_item(app_handle, kind, options, &item).await;
+                if let Err(e) = record_item(app_handle, operation_id, &item, result) {
+                    eprintln!("Failed to record bulk item {}: {}", item.id, e);
+                }
+            }
+        })
+        .await;
+
+    if !cancelled.load(Ordering::SeqCst) {
+        let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        bulk_operations::set_status(&conn, operation_id, "completed").map_err(|e| e.to_string())?;
+    }
+    let operation = load_operation(app_handle, operation_id)?;
+    emit_progress(app_handle, &operation, None);
+    Ok(operation)
+}
+
+fn is_cancelled(app_handle: &AppHandle, operation_id: i64) -> bool {
+    get_connection(app_handle)
+        .ok()
+        .and_then(|conn| bulk_operations::current_status(&conn, operation_id).ok().flatten())
+        .map(|status| status == "cancelled")
+        .unwrap_or(false)
+}
+
+fn record_item(
+    app_handle: &AppHandle,
+    operation_id: i64,
+    item: &BulkOperationItem,
+    result: Result<i64, String>,
+) -> Result<(), String> {
+    {
+        let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        bulk_operations::finish_item(&conn, item.id, result.as_ref().map(|id| *id).map_err(|e| e.as_str()))
+            .map_err(|e| e.to_string())?;
+    }
+
+    emit_progress(app_handle, &load_operation(app_handle, operation_id)?, Some(item.product_id));
+    Ok(())
+}
+
+/// Generates the link or ad for one item and returns the created record's id;
+/// enrichment and refresh return the product's id
+async fn process_item(
+    app_handle: &AppHandle,
+    kind: &str,
+    options: &Value,
+    item: &BulkOperationItem,
+) -> Result<i64, String> {
+    let product_id = item.product_id;
+
+    match kind {
+        "links" => {
+            // Operations queued before the camelCase switch stored snake_case keys
+            let platform = options.get("targetPlatform").or_else(|| options.get("target_platform"));
+            let allow_missing_credential =
+                options.get("allowMissingCredential").and_then(|v| v.as_bool()).unwrap_or(false);
+            let link = match platform.and_then(|v| v.as_str()) {
+                Some(platform) => {
+                    generate_link_for_platform(
+                        app_handle.clone(),
+                        GenerateLinkForPlatformRequest {
+                            product_id,
+                            platform: platform.to_string(),
+                            allow_missing_credential,
+                        },
+                    )
+                    .await
+                }
+                None => {
+                    let request = GenerateLinkRequest { product_id, allow_missing_credential };
+                    generate_affiliate_link(app_handle.clone(), request).await
+                }
+            }
+            .map_err(|e| credential_checks::error_message(&e))?;
+            link.id.ok_or_else(|| "Link was not saved".to_string())
+        }
+        "ads" => {
+            let ad_type = options.get("ad_type").and_then(|v| v.as_str()).map(String::from);
+            let result = generate_ad_for_product(app_handle.clone(), product_id, ad_type, None, None, None).await?;
+            result.ad_copy.id.ok_or_else(|| "Ad was not saved".to_string())
+        }
+        "enrich" => {
+            let url: Option<String> = {
+                let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+                conn.query_row("SELECT product_url FROM products WHERE id = ?1", params![product_id], |row| {
+                    row.get(0)
+                })
+                .map_err(|e| e.to_string())?
+            };
+            let url = url.ok_or_else(|| "Product has no URL to enrich from".to_string())?;
+            let scraped = scrape_product(&url).await?;
+
+            let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+            identifier_import::apply_scrape(&conn, product_id, &item.product_name, &scraped)
+                .map_err(|e| e.to_string())?;
+            data_events::emit(app_handle, data_events::PRODUCTS_CHANGED, data_events::UPDATED, vec![product_id]);
+            Ok(product_id)
+        }
+        "refresh" => {
+            let allow_missing_credential =
+                options.get("allowMissingCredential").and_then(|v| v.as_bool()).unwrap_or(false);
+            let link_ids: Vec<i64> = {
+                let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+                let mut stmt = conn
+                    .prepare("SELECT id FROM affiliate_links WHERE product_id = ?1 ORDER BY id")
+                    .map_err(|e| e.to_string())?;
+                let ids = stmt
+                    .query_map(params![product_id], |row| row.get(0))
+                    .map_err(|e| e.to_string())?
+                    .collect::<Result<_, _>>()
+                    .map_err(|e| e.to_string())?;
+                ids
+            };
+            let mut errors = Vec::new();
+            for link_id in &link_ids {
+                let refreshed =
+                    refresh_link(app_handle, *link_id, allow_missing_credential, Some(item.operation_id)).await;
+                if let Err(e) = refreshed {
+                    errors.push(credential_checks::error_message(&e));
+                }
+            }
+            match errors.first() {
+                None => Ok(product_id),
+                Some(first) => Err(format!(
+                    "Refreshed {} of {} links: {}",
+                    link_ids.len() - errors.len(),
+                    link_ids.len(),
+                    first
+                )),
+            }
+        }
+        other => Err(format!("Unknown bulk operation kind: {}", other)),
+    }
+}
+
+/// Generates one ad per product as a tracked bulk operation. Products last
+/// seen out of stock are skipped unless `include_out_of_stock` is set.
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_ads_for_products(
+    app_handle: AppHandle,
+    product_ids: Vec<i64>,
+    ad_type: Option<String>,
+    include_out_of_stock: Option<bool>,
+) -> Result<BulkOperation, String> {
+    let operation_id = {
+        let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+        let mut items = Vec::new();
+        for product_id in product_ids {
+            let found: Option<(String, Option<String>)> = conn
+                .query_row(
+                    "SELECT name, availability FROM products WHERE id = ?1",
+                    params![product_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .ok();
+            let skip_reason = match &found {
+                None => Some("Product not found".to_string()),
+                Some((_, status)) => {
+                    availability::skip_reason(status.as_deref(), include_out_of_stock.unwrap_or(false))
+                }
+            };
+            items.push(NewItem {
+                product_id,
+                skip_reason,
+                product_name: found.map(|(name, _)| name).unwrap_or_default(),
+            });
+        }
+
+        let params_json = serde_json::json!({ "ad_type": ad_type }).to_string();
+        bulk_operations::create_operation(&mut conn, "ads", &params_json, &items).map_err(|e| e.to_string())?
+    };
+
+    run_operation(&app_handle, operation_id).await
+}
+
+/// Reads a list of ASINs and product URLs (one per line), creates a stub
+/// product for each one not already in the catalog, and queues an `enrich`
+/// operation that scrapes their pages. Returns once the stubs exist; the
+/// operation reports progress through the usual bulk events.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_identifiers(app_handle: AppHandle, file_path: String) -> Result<IdentifierImport, String> {
+    let text = std::fs::read_to_string(file_path.trim())
+        .map_err(|e| format!("Failed to read {}: {}", file_path.trim(), e))?;
+    let (identifiers, invalid) = identifier_import::parse_list(&text);
+    if identifiers.is_empty() {
+        return Err("No ASINs or product URLs found in the file".to_string());
+    }
+
+    let editor = shared_workspace::current_editor(&app_handle);
+    let (operation_id, product_ids, duplicates) = {
+        let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        let (items, duplicates) =
+            identifier_import::create_stubs(&mut conn, &identifiers, Some(&editor)).map_err(|e| e.to_string())?;
+        let product_ids: Vec<i64> = items.iter().map(|item| item.product_id).collect();
+        let operation_id = if items.is_empty() {
+            None
+        } else {
+            Some(bulk_operations::create_operation(&mut conn, "enrich", "{}", &items).map_err(|e| e.to_string())?)
+        };
+        (operation_id, product_ids, duplicates)
+    };
+    data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::CREATED, product_ids.clone());
+
+    let operation = match operation_id {
+        Some(operation_id) => {
+            let operation = load_operation(&app_handle, operation_id)?;
+            let handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = run_operation(&handle, operation_id).await {
+                    eprintln!("Enrichment operation {} failed: {}", operation_id, e);
+                }
+            });
+            Some(operation)
+        }
+        None => None,
+    };
+
+    Ok(IdentifierImport { product_ids, duplicates, invalid, operation })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_bulk_operations(app_handle: AppHandle, limit: Option<i64>) -> Result<Vec<BulkOperation>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    bulk_operations::list_operations(&conn, limit.unwrap_or(20)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_bulk_operation_items(
+    app_handle: AppHandle,
+    operation_id: i64,
+    status: Option<String>,
+) -> Result<Vec<BulkOperationItem>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    bulk_operations::list_items(&conn, operation_id, status.as_deref()).map_err(|e| e.to_string())
+}
+
+/// Requests cancellation; the running loop stops before its next item
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_bulk_operation(app_handle: AppHandle, operation_id: i64) -> Result<BulkOperation, String> {
+    {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        conn.execute(
+            "UPDATE bulk_operations SET status = 'cancelled', updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?1 AND status = 'running'",
+            params![operation_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    load_operation(&app_handle, operation_id)
+}
+
+/// Continues a cancelled or interrupted operation with its pending items
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_bulk_operation(app_handle: AppHandle, operation_id: i64) -> Result<BulkOperation, String> {
+    let operation = load_operation(&app_handle, operation_id)?;
+    if !bulk_operations::is_resumable(&operation.status) {
+        return Err(format!(
+            "Bulk operation {} is {} and cannot be resumed",
+            operation_id, operation.status
+        ));
+    }
+
+    run_operation(&app_handle, operation_id).await
+}
@@ -0,0 +1,62 @@
+use crate::commands::ad_generation::GeneratedAdCopy;
+use crate::database::get_connection;
+use crate::models::bundle::{Bundle, BundlePerformance, SaveBundleInput};
+use crate::services::{bundles, data_events};
+use chrono::Utc;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_bundles(app_handle: AppHandle) -> Result<Vec<Bundle>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    bundles::list(&conn)
+}
+
+/// Creates a bundle, or replaces an existing one's name, description, and members
+#[tauri::command]
+#[specta::specta]
+pub async fn save_bundle(app_handle: AppHandle, input: SaveBundleInput) -> Result<Bundle, String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    bundles::save(&mut conn, &input)
+}
+
+/// Deletes a bundle; ads generated for it are kept
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_bundle(app_handle: AppHandle, bundle_id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    bundles::delete(&conn, bundle_id).map_err(|e| e.to_string())
+}
+
+/// Builds a "carousel" or "email" ad linking every product in the bundle
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_bundle_ad(
+    app_handle: AppHandle,
+    bundle_id: i64,
+    ad_type: String,
+) -> Result<GeneratedAdCopy, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let ad = bundles::generate_ad(&conn, bundle_id, &ad_type)?;
+    data_events::emit(&app_handle, data_events::ADS_CHANGED, data_events::CREATED, ad.id.into_iter().collect());
+    Ok(ad)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_bundle_ads(app_handle: AppHandle, bundle_id: i64) -> Result<Vec<GeneratedAdCopy>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    bundles::ads(&conn, bundle_id).map_err(|e| e.to_string())
+}
+
+/// Clicks and commission summed over the bundle's products (default 30 days)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_bundle_performance(
+    app_handle: AppHandle,
+    bundle_id: i64,
+    days: Option<i64>,
+) -> Result<BundlePerformance, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    bundles::performance(&conn, bundle_id, days.unwrap_or(30), Utc::now().date_naive())
+}
@@ -0,0 +1,39 @@
+use crate::database::get_connection;
+use crate::models::category_keyword::{CategoryKeyword, SaveCategoryKeywordInput};
+use crate::services::category_keywords;
+use tauri::AppHandle;
+
+/// Keyword affinities that score categories against ad types and platforms
+#[tauri::command]
+#[specta::specta]
+pub async fn get_category_keywords(app_handle: AppHandle) -> Result<Vec<CategoryKeyword>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    category_keywords::list(&conn).map_err(|e| e.to_string())
+}
+
+/// Edits a keyword's score, or teaches a new keyword to an ad type or platform
+#[tauri::command]
+#[specta::specta]
+pub async fn save_category_keyword(
+    app_handle: AppHandle,
+    input: SaveCategoryKeywordInput,
+) -> Result<CategoryKeyword, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    category_keywords::save(&conn, &input)
+}
+
+/// Restores a shipped keyword's score
+#[tauri::command]
+#[specta::specta]
+pub async fn reset_category_keyword(app_handle: AppHandle, keyword_id: i64) -> Result<CategoryKeyword, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    category_keywords::reset(&conn, keyword_id)
+}
+
+/// Deletes a keyword the user added
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_category_keyword(app_handle: AppHandle, keyword_id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    category_keywords::delete(&conn, keyword_id)
+}
@@ -0,0 +1,97 @@
+use crate::database::get_connection;
+use crate::database::settings::set_setting;
+use crate::models::click_analytics::{ClickHeatmap, ClickQuality, ClicksByCountry, ClicksBySource, GeoIpStatus};
+use crate::services::click_analytics::{build_heatmap, click_quality, clicks_by_country, clicks_by_source};
+use crate::services::geoip;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_click_heatmap(
+    app_handle: AppHandle,
+    link_id: Option<i64>,
+    product_id: Option<i64>,
+    utc_offset_minutes: Option<i32>,
+) -> Result<ClickHeatmap, String> {
+    if link_id.is_none() && product_id.is_none() {
+        return Err("Either link_id or product_id is required".to_string());
+    }
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    build_heatmap(&conn, link_id, product_id, utc_offset_minutes.unwrap_or(0))
+        .map_err(|e| e.to_string())
+}
+
+/// Clicks per traffic source (utm_source, else referrer) over the last `days`
+/// days (default 30) for a link or for all of a product's links
+#[tauri::command]
+#[specta::specta]
+pub async fn get_clicks_by_source(
+    app_handle: AppHandle,
+    link_id: Option<i64>,
+    product_id: Option<i64>,
+    days: Option<i64>,
+) -> Result<ClicksBySource, String> {
+    if link_id.is_none() && product_id.is_none() {
+        return Err("Either link_id or product_id is required".to_string());
+    }
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    clicks_by_source(&conn, link_id, product_id, days.unwrap_or(30).max(1)).map_err(|e| e.to_string())
+}
+
+/// Clicks per visitor country and region over the last `days` days (default
+/// 30). Only clicks logged while a GeoIP database was configured are located.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_clicks_by_country(
+    app_handle: AppHandle,
+    link_id: Option<i64>,
+    product_id: Option<i64>,
+    days: Option<i64>,
+) -> Result<ClicksByCountry, String> {
+    if link_id.is_none() && product_id.is_none() {
+        return Err("Either link_id or product_id is required".to_string());
+    }
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    clicks_by_country(&conn, link_id, product_id, days.unwrap_or(30).max(1)).map_err(|e| e.to_string())
+}
+
+/// Raw vs. human clicks over the last `days` days (default 30), with bot
+/// traffic broken down by why it was filtered. Without a link or product the
+/// whole workspace is counted.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_click_quality(
+    app_handle: AppHandle,
+    link_id: Option<i64>,
+    product_id: Option<i64>,
+    days: Option<i64>,
+) -> Result<ClickQuality, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    click_quality(&conn, link_id, product_id, days.unwrap_or(30).max(1)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_geoip_status(app_handle: AppHandle) -> Result<GeoIpStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    geoip::status(&conn).map_err(|e| e.to_string())
+}
+
+/// Sets the MaxMind `.mmdb` file used to locate new clicks; `None` turns
+/// lookups off. The file is checked before it is saved.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_geoip_database(app_handle: AppHandle, path: Option<String>) -> Result<GeoIpStatus, String> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    geoip::clear_cache();
+    if let Some(path) = &path {
+        geoip::validate(path)?;
+    }
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    set_setting(&conn, geoip::PATH_SETTING, path.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+    geoip::status(&conn).map_err(|e| e.to_string())
+}
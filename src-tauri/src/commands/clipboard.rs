@@ -0,0 +1,39 @@
+use crate::database::get_connection;
+use crate::database::settings::{get_setting_or, set_setting};
+use crate::models::idea::ClipboardWatcherStatus;
+use crate::services::clipboard_watcher::{self, ENABLED_SETTING};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_clipboard_watcher_status(
+    app_handle: AppHandle,
+) -> Result<ClipboardWatcherStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    Ok(ClipboardWatcherStatus {
+        enabled: get_setting_or(&conn, ENABLED_SETTING, "false") == "true",
+        running: clipboard_watcher::is_running(),
+    })
+}
+
+/// Turns the clipboard watcher on or off and remembers the choice across restarts
+#[tauri::command]
+#[specta::specta]
+pub async fn set_clipboard_watcher_enabled(
+    app_handle: AppHandle,
+    enabled: bool,
+) -> Result<ClipboardWatcherStatus, String> {
+    {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        set_setting(&conn, ENABLED_SETTING, &enabled.to_string()).map_err(|e| e.to_string())?;
+    }
+
+    if enabled {
+        clipboard_watcher::start_watcher(app_handle.clone())?;
+    } else {
+        clipboard_watcher::stop_watcher();
+    }
+
+    get_clipboard_watcher_status(app_handle).await
+}
@@ -0,0 +1,84 @@
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::database::get_connection;
+use crate::models::content_angle::ContentAngle;
+use crate::services::ai_client::{self, MOCK_PROVIDER};
+use crate::services::content_angles::{
+    self, build_angles_prompt, clamp_count, distinct_angles, mock_angles, validate_angles, ANGLES_SCHEMA,
+};
+use crate::services::{ai_discovery, niche, research};
+use rusqlite::params;
+use tauri::AppHandle;
+
+/// Generates `count` distinct content angles for a product and stores them for reuse
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_content_angles(
+    app_handle: AppHandle,
+    product_id: i64,
+    count: Option<i32>,
+) -> Result<Vec<ContentAngle>, String> {
+    let count = clamp_count(count);
+
+    // The connection can't be held across the provider call
+    let (product, brief, chain) = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+        let product = conn
+            .query_row(
+                &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
+                params![product_id],
+                product_from_row,
+            )
+            .map_err(|e| format!("Product not found: {}", e))?;
+
+        let niche_profile = niche::load_active_profile(&conn).map_err(|e| e.to_string())?;
+        let brief = research::latest_for_product(&conn, product_id)
+            .map_err(|e| e.to_string())?
+            .map(|r| r.brief);
+        let target_audience = niche::effective_audience(
+            product.target_audience.as_deref().unwrap_or(""),
+            niche_profile.as_ref(),
+        );
+        let prompt = build_angles_prompt(
+            &product,
+            &target_audience,
+            brief.as_ref(),
+            &niche::prompt_context(niche_profile.as_ref()),
+            count,
+        );
+
+        (product, brief, ai_client::prepare_chain(&conn, &prompt))
+    };
+
+    let served = if chain.is_mock_only() {
+        None
+    } else {
+        ai_discovery::run_cached(&app_handle, &chain, validate_angles, ANGLES_SCHEMA).await?
+    };
+    let (drafts, served_by) =
+        served.unwrap_or_else(|| (mock_angles(&product, brief.as_ref()), MOCK_PROVIDER.to_string()));
+
+    let drafts = distinct_angles(drafts, count as usize);
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    content_angles::save_all(&conn, product_id, &drafts, &served_by).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_content_angles(app_handle: AppHandle, product_id: i64) -> Result<Vec<ContentAngle>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    content_angles::list_for_product(&conn, product_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_content_angle(app_handle: AppHandle, angle_id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let deleted = conn
+        .execute("DELETE FROM content_angles WHERE id = ?1", params![angle_id])
+        .map_err(|e| e.to_string())?;
+    if deleted == 0 {
+        return Err(format!("Content angle {} not found", angle_id));
+    }
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use crate::database::settings::set_setting;
+use crate::database::{get_connection, parse_timestamp};
+use crate::models::conversion::{AttributionConfig, ConversionEvent, LinkAttributionStats};
+use crate::services::attribution;
+use rusqlite::params;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_conversions(
+    app_handle: AppHandle,
+    link_id: Option<i64>,
+) -> Result<Vec<ConversionEvent>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, link_id, campaign_id, converted_at, order_value, commission,
+             status, order_id, source, subid, notes
+             FROM conversion_events
+             WHERE ?1 IS NULL OR link_id = ?1
+             ORDER BY converted_at DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let conversions = stmt
+        .query_map(params![link_id], |row| {
+            Ok(ConversionEvent {
+                id: Some(row.get(0)?),
+                link_id: row.get(1)?,
+                campaign_id: row.get(2)?,
+                converted_at: row.get(3)?,
+                order_value: row.get(4)?,
+                commission: row.get(5)?,
+                status: row.get::<_, Option<String>>(6)?.unwrap_or_else(|| "pending".to_string()),
+                order_id: row.get(7)?,
+                source: row.get(8)?,
+                subid: row.get(9)?,
+                notes: row.get(10)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(conversions)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_attribution_config(app_handle: AppHandle) -> Result<AttributionConfig, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    Ok(attribution::load_config(&conn))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_attribution_config(
+    app_handle: AppHandle,
+    config: AttributionConfig,
+) -> Result<AttributionConfig, String> {
+    if config.dedup_window_minutes < 0 || config.attribution_window_days < 0 {
+        return Err("Attribution windows cannot be negative".to_string());
+    }
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    set_setting(
+        &conn,
+        "click_dedup_window_minutes",
+        &config.dedup_window_minutes.to_string(),
+    )
+    .map_err(|e| e.to_string())?;
+    set_setting(
+        &conn,
+        "attribution_window_days",
+        &config.attribution_window_days.to_string(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(attribution::load_config(&conn))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_attribution_report(
+    app_handle: AppHandle,
+    link_id: Option<i64>,
+    product_id: Option<i64>,
+) -> Result<Vec<LinkAttributionStats>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let config = attribution::load_config(&conn);
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, product_name, platform FROM affiliate_links
+             WHERE (?1 IS NULL OR id = ?1) AND (?2 IS NULL OR product_id = ?2)
+             ORDER BY created_at DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let links = stmt
+        .query_map(params![link_id, product_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut click_stmt = conn
+        .prepare("SELECT clicked_at, ip_hash, user_agent, bot_reason IS NOT NULL FROM click_events WHERE link_id = ?1")
+        .map_err(|e| e.to_string())?;
+    let mut conversion_stmt = conn
+        .prepare(
+            "SELECT converted_at FROM conversion_events
+             WHERE link_id = ?1 AND status != 'rejected'",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut report = Vec::new();
+
+    for (id, product_name, platform) in links {
+        let click_rows = click_stmt
+            .query_map(params![id], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get::<_, bool>(3)?))
+            })
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        let raw_clicks = click_rows.len() as i64;
+        // Bot clicks never count as visits or earn attribution
+        let human_rows: Vec<_> = click_rows
+            .into_iter()
+            .filter(|(_, _, _, bot)| !bot)
+            .map(|(clicked_at, ip_hash, user_agent, _)| (clicked_at, ip_hash, user_agent))
+            .collect();
+        let bot_clicks = raw_clicks - human_rows.len() as i64;
+        let unique = attribution::dedup_clicks(
+            &attribution::to_click_records(human_rows),
+            config.dedup_window_minutes,
+        );
+
+        let conversions = conversion_stmt
+            .query_map(params![id], |row| row.get::<_, String>(0))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        let conversion_times: Vec<_> = conversions
+            .iter()
+            .filter_map(|ts| parse_timestamp(ts))
+            .collect();
+
+        let attributed =
+            attribution::count_attributed(&unique, &conversion_times, config.attribution_window_days);
+        let unique_clicks = unique.len() as i64;
+
+        report.push(LinkAttributionStats {
+            link_id: id,
+            product_name,
+            platform,
+            raw_clicks,
+            bot_clicks,
+            unique_clicks,
+            conversions: conversions.len() as i64,
+            attributed_conversions: attributed,
+            conversion_rate: if unique_clicks > 0 {
+                attributed as f64 / unique_clicks as f64
+            } else {
+                0.0
+            },
+        });
+    }
+
+    Ok(report)
+}
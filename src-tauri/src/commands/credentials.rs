@@ -1,9 +1,12 @@
 use tauri::AppHandle;
 use crate::database::get_connection;
 use crate::models::affiliate_credentials::*;
+use crate::services::{credential_checks, roles};
 use rusqlite::params;
 
+/// API keys and secrets are only returned to the owner
 #[tauri::command]
+#[specta::specta]
 pub async fn get_all_credentials(
     app_handle: AppHandle,
 ) -> Result<Vec<AffiliateCredential>, String> {
@@ -36,10 +39,11 @@ pub async fn get_all_credentials(
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
 
-    Ok(credentials)
+    Ok(credentials.into_iter().map(|c| mask_secrets(&conn, c)).collect())
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn get_credential_by_platform(
     app_handle: AppHandle,
     platform: String,
@@ -70,18 +74,29 @@ pub async fn get_credential_by_platform(
     );
 
     match result {
-        Ok(cred) => Ok(Some(cred)),
+        Ok(cred) => Ok(Some(mask_secrets(&conn, cred))),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
         Err(e) => Err(e.to_string()),
     }
 }
 
+/// Hides API keys and secrets from contributors
+fn mask_secrets(conn: &rusqlite::Connection, mut credential: AffiliateCredential) -> AffiliateCredential {
+    if roles::current_role(conn) != roles::OWNER {
+        credential.api_key = None;
+        credential.api_secret = None;
+    }
+    credential
+}
+
 #[tauri::command]
+#[specta::specta]
 pub async fn save_credential(
     app_handle: AppHandle,
     input: SaveCredentialInput,
 ) -> Result<AffiliateCredential, String> {
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    roles::require_owner(&conn, "save_credential")?;
 
     conn.execute(
         "INSERT INTO affiliate_credentials
@@ -112,11 +127,13 @@ pub async fn save_credential(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn delete_credential(
     app_handle: AppHandle,
     platform: String,
 ) -> Result<(), String> {
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    roles::require_owner(&conn, "delete_credential")?;
 
     conn.execute(
         "DELETE FROM affiliate_credentials WHERE platform = ?1",
@@ -126,3 +143,18 @@ pub async fn delete_credential(
 
     Ok(())
 }
+
+/// Whether each platform has an active credential to generate real links with
+#[tauri::command]
+#[specta::specta]
+pub async fn check_platform_credentials(
+    app_handle: AppHandle,
+    platforms: Vec<String>,
+) -> Result<Vec<CredentialCheck>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    platforms
+        .iter()
+        .map(|platform| credential_checks::check(&conn, platform).map_err(|e| e.to_string()))
+        .collect()
+}
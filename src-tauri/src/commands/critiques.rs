@@ -0,0 +1,100 @@
+use crate::commands::ad_generation::{ad_copy_from_row, GeneratedAdCopy, AD_COPY_COLUMNS};
+use crate::database::get_connection;
+use crate::models::critique::AdCritique;
+use crate::services::ai_client::{self, MOCK_PROVIDER};
+use crate::services::critique::{self, build_critique_prompt, heuristic_critique, validate_critique, CRITIQUE_SCHEMA};
+use crate::services::{ai_discovery, niche};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// An ad with its latest critique
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CritiquedAd {
+    pub ad: GeneratedAdCopy,
+    pub critique: AdCritique,
+}
+
+/// Scores an ad against the copy rubric and stores the critique
+#[tauri::command]
+#[specta::specta]
+pub async fn critique_ad(app_handle: AppHandle, ad_id: i64) -> Result<AdCritique, String> {
+    // The connection can't be held across the provider call
+    let (ad, product_name, niche_profile, chain) = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+        let ad = conn
+            .query_row(
+                &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+                params![ad_id],
+                ad_copy_from_row,
+            )
+            .map_err(|_| format!("Ad {} not found", ad_id))?;
+        let product_name: String = conn
+            .query_row(
+                "SELECT name FROM products WHERE id = ?1",
+                params![ad.product_id],
+                |row| row.get(0),
+            )
+            .unwrap_or_default();
+
+        let niche_profile = niche::load_active_profile(&conn).map_err(|e| e.to_string())?;
+        let prompt = build_critique_prompt(&ad, &product_name, &niche::prompt_context(niche_profile.as_ref()));
+
+        (ad, product_name, niche_profile, ai_client::prepare_chain(&conn, &prompt))
+    };
+
+    let served = if chain.is_mock_only() {
+        None
+    } else {
+        ai_discovery::run_cached(&app_handle, &chain, validate_critique, CRITIQUE_SCHEMA).await?
+    };
+    let (draft, served_by) = served.unwrap_or_else(|| {
+        (
+            heuristic_critique(&ad, &product_name, niche_profile.as_ref()),
+            MOCK_PROVIDER.to_string(),
+        )
+    });
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    critique::save(&conn, ad_id, &draft, &served_by).map_err(|e| e.to_string())
+}
+
+/// The latest critique for an ad, if it has been scored
+#[tauri::command]
+#[specta::specta]
+pub async fn get_ad_critique(app_handle: AppHandle, ad_id: i64) -> Result<Option<AdCritique>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    critique::latest_for_ad(&conn, ad_id).map_err(|e| e.to_string())
+}
+
+/// Ads whose latest critique scores below `max_overall` (default 6.0), weakest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_weak_ads(
+    app_handle: AppHandle,
+    max_overall: Option<f64>,
+    product_id: Option<i64>,
+) -> Result<Vec<CritiquedAd>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let critiques = critique::list_weak(
+        &conn,
+        max_overall.unwrap_or(critique::DEFAULT_WEAK_THRESHOLD),
+        product_id,
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut weak = Vec::with_capacity(critiques.len());
+    for critique in critiques {
+        let ad = conn
+            .query_row(
+                &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+                params![critique.ad_id],
+                ad_copy_from_row,
+            )
+            .map_err(|e| e.to_string())?;
+        weak.push(CritiquedAd { ad, critique });
+    }
+    Ok(weak)
+}
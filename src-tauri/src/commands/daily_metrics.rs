@@ -0,0 +1,55 @@
+use crate::database::get_connection;
+use crate::models::cohort::ProductCohortReport;
+use crate::models::daily_metrics::{DailyMetric, DailyMetricsStatus};
+use crate::services::{cohorts, daily_metrics};
+use chrono::{NaiveDate, Utc};
+use tauri::AppHandle;
+
+fn parse_day(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").map_err(|_| format!("Invalid date '{}'", value))
+}
+
+/// Daily clicks and conversions for `start..=end` (YYYY-MM-DD), for the
+/// whole workspace or one product or link
+#[tauri::command]
+#[specta::specta]
+pub async fn get_daily_metrics(
+    app_handle: AppHandle,
+    start: String,
+    end: String,
+    product_id: Option<i64>,
+    link_id: Option<i64>,
+) -> Result<Vec<DailyMetric>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let (start, end) = (parse_day(&start)?, parse_day(&end)?);
+    if start > end {
+        return Err("Start date must not be after end date".to_string());
+    }
+    daily_metrics::series(&conn, start, end, product_id, link_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_daily_metrics_status(app_handle: AppHandle) -> Result<DailyMetricsStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    daily_metrics::status(&conn).map_err(|e| e.to_string())
+}
+
+/// Recomputes every day from the raw events, e.g. after importing old data
+#[tauri::command]
+#[specta::specta]
+pub async fn rebuild_daily_metrics(app_handle: AppHandle) -> Result<DailyMetricsStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    daily_metrics::rebuild(&conn, Utc::now().date_naive()).map_err(|e| e.to_string())?;
+    daily_metrics::status(&conn).map_err(|e| e.to_string())
+}
+
+/// Products grouped by the month they were added, with each cohort's clicks
+/// and commission month by month since (default: cohorts of the last 12 months)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_product_cohorts(app_handle: AppHandle, months: Option<i64>) -> Result<ProductCohortReport, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    cohorts::report(&conn, months.unwrap_or(cohorts::DEFAULT_COHORT_MONTHS), Utc::now().date_naive())
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,32 @@
+use crate::database::{self, get_connection};
+use crate::models::data_privacy::{DataExportSummary, DataWipeSummary};
+use crate::services::{attachments, data_events, data_privacy, roles, workspace};
+use std::path::Path;
+use tauri::AppHandle;
+
+/// Writes every table to `path` as JSON. Owner only, since the dump
+/// includes credentials.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_all_data(app_handle: AppHandle, path: String) -> Result<DataExportSummary, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    roles::require_owner(&conn, "export_all_data")?;
+    data_privacy::export_to_file(&conn, Path::new(path.trim()))
+}
+
+/// Erases everything the app stores: all tables (settings and credentials
+/// included), attachment files, and media under the app's data directory.
+/// `confirm_phrase` must be "DELETE ALL MY DATA".
+#[tauri::command]
+#[specta::specta]
+pub async fn wipe_all_data(app_handle: AppHandle, confirm_phrase: String) -> Result<DataWipeSummary, String> {
+    data_privacy::check_phrase(&confirm_phrase)?;
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    roles::require_owner(&conn, "wipe_all_data")?;
+    let deleted = workspace::entity_rows(&conn).map_err(|e| e.to_string())?;
+    let attachment_folder = attachments::folder(&database::database_path(&app_handle));
+    let summary = data_privacy::wipe(&mut conn, &database::app_dir(&app_handle), &attachment_folder)?;
+
+    data_events::emit_deleted_rows(&app_handle, deleted);
+    Ok(summary)
+}
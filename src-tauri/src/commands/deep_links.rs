@@ -0,0 +1,79 @@
+use crate::commands::affiliate_links::create_affiliate_link;
+use crate::database::get_connection;
+use crate::models::affiliate_link::{AffiliateLink, CreateAffiliateLinkInput};
+use crate::models::deep_link::{BuildDeepLinkRequest, ProgramNetworkIds};
+use crate::services::deep_links;
+use rusqlite::{params, OptionalExtension};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_program_network_ids(app_handle: AppHandle) -> Result<Vec<ProgramNetworkIds>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    deep_links::list(&conn).map_err(|e| e.to_string())
+}
+
+/// Saves the network a program runs on and its IDs there
+#[tauri::command]
+#[specta::specta]
+pub async fn save_program_network_ids(
+    app_handle: AppHandle,
+    input: ProgramNetworkIds,
+) -> Result<ProgramNetworkIds, String> {
+    let input = ProgramNetworkIds { network: input.network.trim().to_lowercase(), ..input };
+    deep_links::validate(&input)?;
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    deep_links::save(&conn, &input).map_err(|e| e.to_string())?;
+    deep_links::load(&conn, &input.program_name)
+        .map_err(|e| e.to_string())?
+        .map(|program| program.ids)
+        .ok_or_else(|| format!("Program {} not found", input.program_name))
+}
+
+/// Wraps a merchant URL in the program's network deep-link format and saves
+/// it as an affiliate link for the product
+#[tauri::command]
+#[specta::specta]
+pub async fn build_deep_link(app_handle: AppHandle, request: BuildDeepLinkRequest) -> Result<AffiliateLink, String> {
+    let destination_url = request.destination_url.trim().to_string();
+    if !(destination_url.starts_with("https://") || destination_url.starts_with("http://")) {
+        return Err("Destination URL must start with http:// or https://".to_string());
+    }
+
+    let input = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        let program = deep_links::load(&conn, &request.program)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("No network IDs saved for program {}", request.program))?;
+        let ids = program.ids;
+
+        let publisher_id: String = conn
+            .query_row(
+                "SELECT affiliate_id FROM affiliate_credentials
+                 WHERE platform = ?1 AND active = 1 AND affiliate_id IS NOT NULL AND affiliate_id != ''",
+                params![ids.network],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Save your {} publisher ID under credentials first", ids.network))?;
+
+        let product_name: String = conn
+            .query_row("SELECT name FROM products WHERE id = ?1", params![request.product_id], |row| row.get(0))
+            .map_err(|e| format!("Product not found: {}", e))?;
+
+        CreateAffiliateLinkInput {
+            product_id: request.product_id,
+            product_name,
+            platform: ids.network.clone(),
+            program_name: ids.program_name.clone(),
+            commission_rate: program.commission_rate,
+            cookie_duration: program.cookie_duration,
+            tracking_url: deep_links::build(&ids, publisher_id.trim(), &destination_url)?,
+            destination_url,
+        }
+    };
+
+    create_affiliate_link(app_handle, input).await
+}
@@ -0,0 +1,38 @@
+use crate::database::get_connection;
+use crate::models::diagnostics::{QueryPlan, SlowQueryReport};
+use crate::services::{query_plans, query_profiler};
+use tauri::AppHandle;
+
+/// Statements slower than the threshold since the app started, slowest
+/// total time first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_slow_queries() -> Result<SlowQueryReport, String> {
+    Ok(query_profiler::report())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_slow_query_threshold(
+    app_handle: AppHandle,
+    threshold_ms: i64,
+) -> Result<SlowQueryReport, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    query_profiler::set_threshold(&conn, threshold_ms)?;
+    Ok(query_profiler::report())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_slow_queries() -> Result<SlowQueryReport, String> {
+    query_profiler::clear();
+    Ok(query_profiler::report())
+}
+
+/// `EXPLAIN QUERY PLAN` for the main list queries, to spot missing indexes
+#[tauri::command]
+#[specta::specta]
+pub async fn explain_hot_queries(app_handle: AppHandle) -> Result<Vec<QueryPlan>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    query_plans::explain_hot_queries(&conn).map_err(|e| e.to_string())
+}
@@ -0,0 +1,62 @@
+use crate::database::get_connection;
+use crate::models::digest::{DigestRecord, WeeklyDigest};
+use crate::services::digest;
+use rusqlite::params;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_weekly_digest(
+    app_handle: AppHandle,
+    notify: Option<bool>,
+) -> Result<DigestRecord, String> {
+    digest::generate_and_store(&app_handle, notify.unwrap_or(false))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_digests(
+    app_handle: AppHandle,
+    limit: Option<i64>,
+) -> Result<Vec<DigestRecord>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, period_start, period_end, summary_json, summary_text, notified, created_at
+             FROM digests ORDER BY created_at DESC, id DESC LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map(params![limit.unwrap_or(12)], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, bool>(5)?,
+                row.get::<_, Option<String>>(6)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    rows.into_iter()
+        .map(|(id, period_start, period_end, json, summary_text, notified, created_at)| {
+            let digest: WeeklyDigest = serde_json::from_str(&json)
+                .map_err(|e| format!("Corrupt digest {}: {}", id, e))?;
+            Ok(DigestRecord {
+                id,
+                period_start,
+                period_end,
+                digest,
+                summary_text,
+                notified,
+                created_at,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,168 @@
+use crate::commands::ad_generation::{ad_copy_from_row, GeneratedAdCopy, AD_COPY_COLUMNS};
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::database::get_connection;
+use crate::services::email_sequence::{draft_sequence, plan_steps};
+use crate::services::{data_events, research, reviews, style_rules, webhooks};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// A nurture sequence: the `email_sequence` ad plus its messages in send order
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailSequence {
+    pub sequence: GeneratedAdCopy,
+    pub messages: Vec<GeneratedAdCopy>,
+}
+
+/// Loads a sequence ad and its ordered messages
+pub(crate) fn load_sequence(conn: &Connection, sequence_id: i64) -> Result<EmailSequence, String> {
+    let sequence = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM ad_copies WHERE id = ?1 AND ad_type = 'email_sequence'",
+                AD_COPY_COLUMNS
+            ),
+            params![sequence_id],
+            ad_copy_from_row,
+        )
+        .map_err(|_| format!("Email sequence {} not found", sequence_id))?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM ad_copies WHERE parent_ad_id = ?1 ORDER BY sequence_step",
+            AD_COPY_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let messages = stmt
+        .query_map(params![sequence_id], ad_copy_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(EmailSequence { sequence, messages })
+}
+
+/// Generates a 3-5 message email nurture sequence (intro, value, objection
+/// handling, urgency) stored as ordered child ads with send delays
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_email_sequence(
+    app_handle: AppHandle,
+    product_id: i64,
+    length: Option<i32>,
+    custom_instructions: Option<String>,
+    include_review_snippet: Option<bool>,
+) -> Result<EmailSequence, String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let product = conn
+        .query_row(
+            &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
+            params![product_id],
+            product_from_row,
+        )
+        .map_err(|e| format!("Product not found: {}", e))?;
+
+    let research = research::latest_for_product(&conn, product_id).map_err(|e| e.to_string())?;
+    let review_snippet = if include_review_snippet.unwrap_or(false) {
+        reviews::top_snippet(&conn, product_id).map_err(|e| e.to_string())?
+    } else {
+        None
+    };
+    let review_quote = review_snippet.as_ref().map(reviews::attributed_quote);
+
+    let steps = plan_steps(length);
+    let drafts = draft_sequence(
+        &product,
+        research.as_ref().map(|r| &r.brief),
+        review_quote.as_deref(),
+        custom_instructions.as_deref(),
+        &steps,
+    );
+
+    let rules = style_rules::load_rules(&conn, "email");
+    let drafts: Vec<_> = drafts
+        .into_iter()
+        .map(|mut draft| {
+            (draft.subject, draft.body) = style_rules::apply(&rules, &draft.subject, &draft.body);
+            draft
+        })
+        .collect();
+
+    let outline = drafts
+        .iter()
+        .scan(0, |day, draft| {
+            *day += draft.step.send_delay_days;
+            Some(format!("Day {}: {} - {}", day, draft.step.label, draft.subject))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO ad_copies (campaign_id, product_id, variation_name, headline, body_text,
+         cta, ad_format, ad_type, platform_specific_data)
+         VALUES (1, ?1, ?2, ?3, ?4, ?5, 'email', 'email_sequence', ?6)",
+        params![
+            product_id,
+            format!("{} - {}-Step Email Sequence", product.name, drafts.len()),
+            format!("{}-step nurture sequence for {}", drafts.len(), product.name),
+            outline,
+            drafts.last().map(|d| d.cta.clone()),
+            serde_json::json!({
+                "research_id": research.as_ref().map(|r| r.id),
+                "review_snippet_id": review_snippet.as_ref().map(|r| r.id),
+            })
+            .to_string(),
+        ],
+    )
+    .map_err(|e| format!("Failed to save email sequence: {}", e))?;
+    let sequence_id = tx.last_insert_rowid();
+
+    for (idx, draft) in drafts.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO ad_copies (campaign_id, product_id, variation_name, headline, body_text,
+             cta, ad_format, ad_type, platform_specific_data, parent_ad_id, sequence_step, send_delay_days)
+             VALUES (1, ?1, ?2, ?3, ?4, ?5, 'email', 'email', ?6, ?7, ?8, ?9)",
+            params![
+                product_id,
+                format!("{} - Email {}: {}", product.name, idx + 1, draft.step.label),
+                draft.subject,
+                draft.body,
+                draft.cta,
+                serde_json::json!({
+                    "step": draft.step.key,
+                    "step_label": draft.step.label,
+                    "preview_text": draft.preview_text,
+                })
+                .to_string(),
+                sequence_id,
+                idx as i32 + 1,
+                draft.step.send_delay_days,
+            ],
+        )
+        .map_err(|e| format!("Failed to save sequence email: {}", e))?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    let sequence = load_sequence(&conn, sequence_id)?;
+    webhooks::emit(&app_handle, webhooks::AD_CREATED, webhooks::ad_data(&sequence.sequence));
+    data_events::emit(
+        &app_handle,
+        data_events::ADS_CHANGED,
+        data_events::CREATED,
+        std::iter::once(&sequence.sequence)
+            .chain(&sequence.messages)
+            .filter_map(|ad| ad.id)
+            .collect(),
+    );
+    Ok(sequence)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_email_sequence(app_handle: AppHandle, sequence_id: i64) -> Result<EmailSequence, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    load_sequence(&conn, sequence_id)
+}
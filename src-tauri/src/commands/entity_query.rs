@@ -0,0 +1,16 @@
+use crate::database::get_connection;
+use crate::models::entity_query::EntityQuery;
+use crate::services::entity_query;
+use tauri::AppHandle;
+
+/// Runs a structured query (entity, fields, filters, sort, relations) in one
+/// round-trip, e.g. products with their links and latest ad
+#[tauri::command]
+#[specta::specta]
+pub async fn query_entities(
+    app_handle: AppHandle,
+    spec: EntityQuery,
+) -> Result<Vec<serde_json::Value>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    entity_query::run(&conn, &spec)
+}
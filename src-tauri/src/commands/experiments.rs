@@ -0,0 +1,43 @@
+use crate::database::get_connection;
+use crate::models::experiment::{Experiment, ExperimentAnalysis, StartExperimentInput};
+use crate::services::experiments;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_experiments(app_handle: AppHandle) -> Result<Vec<Experiment>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    experiments::list(&conn)
+}
+
+/// Starts an A/B test between ads and/or links; the first variant is the control
+#[tauri::command]
+#[specta::specta]
+pub async fn start_experiment(app_handle: AppHandle, input: StartExperimentInput) -> Result<Experiment, String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    experiments::start(&mut conn, &input)
+}
+
+/// Stops a running experiment; its analysis window ends now
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_experiment(app_handle: AppHandle, experiment_id: i64) -> Result<Experiment, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    experiments::stop(&conn, experiment_id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_experiment(app_handle: AppHandle, experiment_id: i64) -> Result<(), String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    experiments::delete(&mut conn, experiment_id).map_err(|e| e.to_string())
+}
+
+/// Lift and significance of each variant over the control, from the clicks,
+/// conversions, and impressions recorded while the experiment ran
+#[tauri::command]
+#[specta::specta]
+pub async fn analyze_experiment(app_handle: AppHandle, experiment_id: i64) -> Result<ExperimentAnalysis, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    experiments::analyze(&conn, experiment_id)
+}
@@ -0,0 +1,574 @@
+use crate::commands::ad_generation::{ad_copy_from_row, AD_COPY_COLUMNS};
+use crate::commands::email_sequences::load_sequence;
+use crate::commands::sms::compliance_report;
+use crate::database::get_connection;
+use crate::database::settings::get_setting_or;
+use crate::models::ad_export::{AdExportFilter, MarkdownExport, PaidAdsExport};
+use crate::models::ad_kit::AdKitExport;
+use crate::models::catalog_feed::CatalogFeedExport;
+use crate::models::link_hub::LinkHubExport;
+use crate::models::list_page::{ProductCsvExport, ProductListFilter};
+use crate::models::pinterest::{PinMetadata, PinterestExport};
+use crate::models::pretty_link::PrettyLinksExport;
+use crate::models::sms::{SmsContact, SmsExport};
+use crate::services::accessibility;
+use crate::services::ad_kit::{self, KitFile};
+use crate::services::ad_links;
+use crate::services::catalog_feed::{self, CatalogFeedFormat, FeedTarget};
+use crate::services::email_sequence::{self, EspFormat};
+use crate::services::link_hub::{self, DEFAULT_TITLE};
+use crate::services::markdown_export;
+use crate::services::paid_ads::{self, PaidAdsFormat, DEFAULT_CAMPAIGN_NAME};
+use crate::services::pinterest::{self, BulkPinRow};
+use crate::services::pretty_links::{self, PrettyLinksFormat};
+use crate::services::product_export;
+use crate::services::sms_compliance::{self, load_quiet_hours, SmsExportFormat};
+use rusqlite::{params, OptionalExtension};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// Resolves an export destination: a path ending in `file_name`'s extension is
+/// used as-is, anything else is treated as a directory to write `file_name` into
+fn export_file(path: &str, file_name: &str) -> Result<PathBuf, String> {
+    let path = Path::new(path.trim());
+    if path.as_os_str().is_empty() {
+        return Err("Export path is required".to_string());
+    }
+
+    let extension = Path::new(file_name).extension();
+    let file = if path.extension().is_some() && path.extension() == extension {
+        path.to_path_buf()
+    } else {
+        path.join(file_name)
+    };
+
+    if let Some(parent) = file.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    Ok(file)
+}
+
+/// Writes a Linktree-style static site (index.html) for the selected products,
+/// or for every product with an active affiliate link
+#[tauri::command]
+#[specta::specta]
+pub async fn export_link_hub(
+    app_handle: AppHandle,
+    path: String,
+    product_ids: Option<Vec<i64>>,
+    title: Option<String>,
+) -> Result<LinkHubExport, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let (entries, skipped_product_ids) =
+        link_hub::load_entries(&conn, product_ids.as_deref()).map_err(|e| e.to_string())?;
+    if entries.is_empty() {
+        return Err("None of the selected products has an active affiliate link".to_string());
+    }
+
+    let title = title
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| get_setting_or(&conn, "link_hub_title", DEFAULT_TITLE));
+
+    let file = export_file(&path, "index.html")?;
+    std::fs::write(&file, link_hub::render_html(&title, &entries))
+        .map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+
+    Ok(LinkHubExport {
+        path: file.display().to_string(),
+        product_count: entries.len(),
+        link_count: entries.iter().map(|e| e.links.len()).sum(),
+        skipped_product_ids,
+    })
+}
+
+/// Writes an email sequence as CSV in an ESP's import layout
+/// (generic, mailchimp, klaviyo, convertkit) and returns the written file
+#[tauri::command]
+#[specta::specta]
+pub async fn export_email_sequence(
+    app_handle: AppHandle,
+    sequence_id: i64,
+    format: String,
+    path: String,
+) -> Result<String, String> {
+    let esp = EspFormat::from_string(&format).ok_or_else(|| format!("Unsupported ESP format: {}", format))?;
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let sequence = load_sequence(&conn, sequence_id)?;
+    let csv = email_sequence::to_csv(esp, &sequence.messages)?;
+
+    let file = export_file(&path, &format!("email-sequence-{}-{}.csv", sequence_id, format.to_lowercase()))?;
+    std::fs::write(&file, csv).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+    Ok(file.display().to_string())
+}
+
+/// Writes an SMS campaign for a contact list in Twilio or EZTexting CSV layout.
+/// Refuses to export when the ad fails compliance (unresolved link, quiet hours).
+#[tauri::command]
+#[specta::specta]
+pub async fn export_sms_campaign(
+    app_handle: AppHandle,
+    ad_id: i64,
+    contacts: Vec<SmsContact>,
+    format: String,
+    path: String,
+    send_at: Option<String>,
+) -> Result<SmsExport, String> {
+    let provider =
+        SmsExportFormat::from_string(&format).ok_or_else(|| format!("Unsupported SMS export format: {}", format))?;
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let report = compliance_report(&conn, ad_id, send_at.as_deref())?;
+    if report.within_quiet_hours || report.body.contains("[LINK]") {
+        return Err(report.issues.join("; "));
+    }
+
+    let quiet_hours = load_quiet_hours(&conn)?;
+    let send_at = send_at.as_deref().map(sms_compliance::parse_send_at).transpose()?;
+    let (csv, exported, skipped_phones) =
+        sms_compliance::to_csv(provider, &contacts, &report.body, send_at, quiet_hours.timezone)?;
+    if exported == 0 {
+        return Err("No contacts with a valid phone number to export".to_string());
+    }
+
+    let file = export_file(&path, &format!("sms-campaign-{}-{}.csv", ad_id, format.to_lowercase()))?;
+    std::fs::write(&file, csv).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+
+    Ok(SmsExport {
+        path: file.display().to_string(),
+        exported,
+        skipped_phones,
+        segments: report.segments,
+    })
+}
+
+/// Writes `pinterest_pin` ads as a Pinterest bulk pin upload CSV. Each pin uses
+/// the product image and its newest active affiliate link (or the product URL);
+/// ads without an image or link are skipped.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_pinterest_pins(
+    app_handle: AppHandle,
+    ad_ids: Vec<i64>,
+    path: String,
+    board: Option<String>,
+    publish_date: Option<String>,
+) -> Result<PinterestExport, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let mut pins: Vec<(PinMetadata, String, String)> = Vec::new();
+    let mut skipped_ad_ids = Vec::new();
+    let mut warnings = Vec::new();
+    for ad_id in ad_ids {
+        let ad = conn
+            .query_row(
+                &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+                params![ad_id],
+                ad_copy_from_row,
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        let pin = ad
+            .as_ref()
+            .filter(|ad| ad.ad_type.as_deref() == Some("pinterest_pin"))
+            .and_then(|ad| ad.platform_specific_data.as_deref())
+            .and_then(|data| serde_json::from_str::<serde_json::Value>(data).ok())
+            .and_then(|data| serde_json::from_value::<PinMetadata>(data["pinterest"].clone()).ok());
+        let (ad, pin) = match (ad, pin) {
+            (Some(ad), Some(pin)) => (ad, pin),
+            _ => {
+                skipped_ad_ids.push(ad_id);
+                continue;
+            }
+        };
+
+        let media: Option<(Option<String>, Option<String>)> = conn
+            .query_row(
+                "SELECT p.image_url, p.product_url FROM products p WHERE p.id = ?1",
+                params![ad.product_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        let tracking_url = ad_links::tracking_url(&conn, ad.product_id, ad.link_id).map_err(|e| e.to_string())?;
+        match media {
+            Some((Some(image_url), product_url))
+                if image_url.starts_with("http") && (tracking_url.is_some() || product_url.is_some()) =>
+            {
+                let link = tracking_url.or(product_url).unwrap_or_default();
+                warnings.extend(accessibility::missing_alt_text_warning(&ad));
+                pins.push((pin, image_url, link));
+            }
+            _ => skipped_ad_ids.push(ad_id),
+        }
+    }
+
+    if pins.is_empty() {
+        return Err("None of the selected ads is a Pinterest pin with a product image and link".to_string());
+    }
+
+    let board = board.filter(|b| !b.trim().is_empty());
+    let rows: Vec<BulkPinRow> = pins
+        .iter()
+        .map(|(pin, media_url, link)| BulkPinRow {
+            pin,
+            media_url,
+            board: board
+                .as_deref()
+                .or_else(|| pin.board_suggestions.first().map(String::as_str))
+                .unwrap_or("Favorite Finds"),
+            link,
+            publish_date: publish_date.as_deref(),
+        })
+        .collect();
+    let csv = pinterest::to_bulk_csv(&rows)?;
+
+    let file = export_file(&path, "pinterest-bulk-upload.csv")?;
+    std::fs::write(&file, csv).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+
+    Ok(PinterestExport {
+        path: file.display().to_string(),
+        exported: rows.len(),
+        skipped_ad_ids,
+        warnings,
+    })
+}
+
+/// Assembles everything needed to post one ad, or every ad for a product:
+/// copy as .txt and .md, the tracking link and its QR code, and the product
+/// image. Written as a folder inside `path`, or as a single .zip when `as_zip`
+#[tauri::command]
+#[specta::specta]
+pub async fn export_ad_kit(
+    app_handle: AppHandle,
+    ad_id: Option<i64>,
+    product_id: Option<i64>,
+    path: String,
+    as_zip: Option<bool>,
+) -> Result<AdKitExport, String> {
+    // The connection can't be held across the image download
+    let (product_name, image_url, link, ads, mut warnings) = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+        let (ads, product_id) = match (ad_id, product_id) {
+            (Some(ad_id), None) => {
+                let ad = conn
+                    .query_row(
+                        &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+                        params![ad_id],
+                        ad_copy_from_row,
+                    )
+                    .map_err(|_| format!("Ad {} not found", ad_id))?;
+                let product_id = ad.product_id.ok_or_else(|| format!("Ad {} has no product", ad_id))?;
+                (vec![ad], product_id)
+            }
+            (None, Some(product_id)) => {
+                let mut stmt = conn
+                    .prepare(&format!(
+                        "SELECT {} FROM ad_copies
+                         WHERE product_id = ?1 AND ad_type != 'email_sequence'
+                         ORDER BY created_at, id",
+                        AD_COPY_COLUMNS
+                    ))
+                    .map_err(|e| e.to_string())?;
+                let ads = stmt
+                    .query_map(params![product_id], ad_copy_from_row)
+                    .map_err(|e| e.to_string())?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| e.to_string())?;
+                (ads, product_id)
+            }
+            _ => return Err("Provide either an ad or a product".to_string()),
+        };
+        if ads.is_empty() {
+            return Err(format!("Product {} has no ads to export", product_id));
+        }
+
+        let (product_name, image_url, product_url): (String, Option<String>, Option<String>) = conn
+            .query_row(
+                "SELECT p.name, p.image_url, p.product_url FROM products p WHERE p.id = ?1",
+                params![product_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .map_err(|e| format!("Product not found: {}", e))?;
+
+        // A single-ad kit uses that ad's attached link; a product kit the newest one
+        let kit_link_id = if ads.len() == 1 { ads[0].link_id } else { None };
+        let tracking_url =
+            ad_links::tracking_url(&conn, Some(product_id), kit_link_id).map_err(|e| e.to_string())?;
+
+        let mut warnings = Vec::new();
+        let link = match (tracking_url, product_url) {
+            (Some(tracking_url), _) => Some(tracking_url),
+            (None, Some(product_url)) => {
+                warnings.push("No active affiliate link; the kit uses the product URL".to_string());
+                Some(product_url)
+            }
+            (None, None) => {
+                warnings.push("No affiliate link or product URL; [LINK] is left in the copy".to_string());
+                None
+            }
+        };
+
+        // Each ad's copy resolves [LINK] to the link attached to it
+        let ads = ads
+            .into_iter()
+            .map(|ad| {
+                let ad_link = match ad.link_id {
+                    Some(link_id) => ad_links::tracking_url(&conn, Some(product_id), Some(link_id))?,
+                    None => None,
+                };
+                Ok((ad_link.or_else(|| link.clone()), ad))
+            })
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(|e| e.to_string())?;
+        (product_name, image_url, link, ads, warnings)
+    };
+
+    let mut files = Vec::new();
+    for (ad_link, ad) in &ads {
+        let stem = ad_kit::ad_file_stem(ad);
+        files.push(KitFile::text(format!("{}.txt", stem), ad_kit::ad_text(ad, ad_link.as_deref())));
+        files.push(KitFile::text(
+            format!("{}.md", stem),
+            ad_kit::ad_markdown(ad, &product_name, ad_link.as_deref()),
+        ));
+        warnings.extend(accessibility::missing_alt_text_warning(ad));
+    }
+    if let Some(link) = link.as_deref() {
+        files.push(KitFile::text("link.txt", format!("{}\n", link)));
+        files.push(KitFile::text("qr-code.svg", ad_kit::qr_svg(link)?));
+    }
+    match image_url.as_deref().filter(|url| url.starts_with("http")) {
+        Some(url) => match ad_kit::download_image(url).await {
+            Ok(bytes) => files.push(KitFile {
+                name: ad_kit::image_file_name(url, 0),
+                contents: bytes,
+            }),
+            Err(e) => warnings.push(e),
+        },
+        None => warnings.push("Product has no image to include".to_string()),
+    }
+
+    let kit_name = match ad_id {
+        Some(ad_id) => format!("{}-ad-{}-kit", ad_kit::slug(&product_name), ad_id),
+        None => format!("{}-ad-kit", ad_kit::slug(&product_name)),
+    };
+    let kit_path = if as_zip.unwrap_or(false) {
+        let file = export_file(&path, &format!("{}.zip", kit_name))?;
+        ad_kit::write_zip(&file, &kit_name, &files)?;
+        file
+    } else {
+        if path.trim().is_empty() {
+            return Err("Export path is required".to_string());
+        }
+        let dir = Path::new(path.trim()).join(&kit_name);
+        ad_kit::write_folder(&dir, &files)?;
+        dir
+    };
+
+    Ok(AdKitExport {
+        path: kit_path.display().to_string(),
+        files: files.into_iter().map(|f| f.name).collect(),
+        ad_count: ads.len(),
+        warnings,
+    })
+}
+
+/// Writes the ads selected by `filter` as Markdown notes (front matter plus
+/// copy) into the `path` folder, for Notion/Obsidian content workflows
+#[tauri::command]
+#[specta::specta]
+pub async fn export_ads_markdown(
+    app_handle: AppHandle,
+    filter: Option<AdExportFilter>,
+    path: String,
+) -> Result<MarkdownExport, String> {
+    let dir = Path::new(path.trim());
+    if dir.as_os_str().is_empty() {
+        return Err("Export path is required".to_string());
+    }
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    markdown_export::export_to_dir(&conn, &filter.unwrap_or_default(), dir)
+}
+
+/// Writes the ads selected by `filter` as a Meta Ads Manager bulk import or a
+/// Google Ads Editor CSV. Each product becomes an ad set / ad group, final
+/// URLs are tracking links, and everything is imported paused; ads whose
+/// product has no active affiliate link are skipped.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_paid_ads(
+    app_handle: AppHandle,
+    format: String,
+    filter: Option<AdExportFilter>,
+    path: String,
+    campaign_name: Option<String>,
+) -> Result<PaidAdsExport, String> {
+    let sheet_format =
+        PaidAdsFormat::from_string(&format).ok_or_else(|| format!("Unsupported paid ads format: {}", format))?;
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let notes = markdown_export::load_notes(&conn, &filter.unwrap_or_default())?;
+    let campaign = campaign_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .unwrap_or(DEFAULT_CAMPAIGN_NAME);
+
+    let (csv, exported, skipped_ad_ids, warnings) = paid_ads::to_csv(sheet_format, &notes, campaign)?;
+    if exported == 0 {
+        return Err("None of the selected ads has a product with an active affiliate link".to_string());
+    }
+
+    let file_name = match sheet_format {
+        PaidAdsFormat::Meta => "meta-ads-bulk-import.csv",
+        PaidAdsFormat::Google => "google-ads-editor.csv",
+    };
+    let file = export_file(&path, file_name)?;
+    std::fs::write(&file, csv).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+
+    Ok(PaidAdsExport {
+        path: file.display().to_string(),
+        exported,
+        skipped_ad_ids,
+        warnings,
+    })
+}
+
+/// Writes active affiliate links as `/go/<slug>` redirects: an Apache
+/// `.htaccess`, a Netlify/Cloudflare `_redirects` file, or a Pretty Links or
+/// ThirstyAffiliates import CSV
+#[tauri::command]
+#[specta::specta]
+pub async fn export_pretty_links(
+    app_handle: AppHandle,
+    format: String,
+    path: String,
+    product_ids: Option<Vec<i64>>,
+    prefix: Option<String>,
+) -> Result<PrettyLinksExport, String> {
+    let links_format =
+        PrettyLinksFormat::from_string(&format).ok_or_else(|| format!("Unsupported pretty links format: {}", format))?;
+    let prefix = prefix.as_deref().map(str::trim).unwrap_or(pretty_links::DEFAULT_PREFIX);
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let links = pretty_links::load_links(&conn, product_ids.as_deref(), prefix).map_err(|e| e.to_string())?;
+    if links.is_empty() {
+        return Err("No active affiliate links to export".to_string());
+    }
+
+    let contents = pretty_links::render(links_format, &links)?;
+    let file = export_file(&path, links_format.file_name())?;
+    std::fs::write(&file, contents).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+
+    Ok(PrettyLinksExport {
+        path: file.display().to_string(),
+        format: format.to_lowercase(),
+        links: links.into_iter().map(|(link, _)| link).collect(),
+    })
+}
+
+/// Writes the products selected by `filter` as a CSV for review in a
+/// spreadsheet. `columns` picks product fields and computed columns
+/// (`link_url:<platform>`, `latest_ad_headline`, `total_clicks`,
+/// `active_links`); without it a default set is used.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_products_csv(
+    app_handle: AppHandle,
+    filter: Option<ProductListFilter>,
+    path: String,
+    columns: Option<Vec<String>>,
+) -> Result<ProductCsvExport, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let columns = match columns.filter(|c| !c.is_empty()) {
+        Some(columns) => columns.into_iter().map(|c| c.trim().to_string()).collect(),
+        None => product_export::default_columns(&conn).map_err(|e| e.to_string())?,
+    };
+    let (contents, rows) = product_export::to_csv(&conn, &filter.unwrap_or_default(), &columns)?;
+
+    let file = export_file(&path, "products.csv")?;
+    std::fs::write(&file, contents).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+
+    Ok(ProductCsvExport { path: file.display().to_string(), rows, columns })
+}
+
+/// Validates the shared feed options, then writes `target`'s feed for the
+/// products that pass its required-attribute checks
+fn write_catalog_feed(
+    app_handle: &AppHandle,
+    target: FeedTarget,
+    product_ids: &[i64],
+    format: &str,
+    path: &str,
+    brand: &str,
+    currency: Option<String>,
+) -> Result<CatalogFeedExport, String> {
+    let feed_format =
+        CatalogFeedFormat::from_string(format).ok_or_else(|| format!("Unsupported catalog feed format: {}", format))?;
+    let brand = brand.trim();
+    if brand.is_empty() {
+        return Err("A brand is required for catalog items".to_string());
+    }
+    let currency = currency
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| catalog_feed::DEFAULT_CURRENCY.to_string());
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("Currency must be a three-letter ISO 4217 code, not '{}'", currency));
+    }
+
+    let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    let (items, skipped) = catalog_feed::load_items(&conn, target, product_ids).map_err(|e| e.to_string())?;
+    if items.is_empty() {
+        return Err("None of the selected products has an affiliate link, image, and exact price".to_string());
+    }
+
+    let contents = catalog_feed::render(target, feed_format, &items, brand, &currency)?;
+    let file = export_file(path, target.file_name(feed_format))?;
+    std::fs::write(&file, contents).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+
+    Ok(CatalogFeedExport {
+        path: file.display().to_string(),
+        format: format.to_lowercase(),
+        exported: items.len(),
+        skipped,
+    })
+}
+
+/// Writes the selected products as a Meta Commerce catalog feed (CSV or XML)
+/// for Instagram and Facebook Shopping. Item links are affiliate tracking
+/// URLs; products without a link, image, or exact price are skipped.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_meta_catalog(
+    app_handle: AppHandle,
+    product_ids: Vec<i64>,
+    format: String,
+    path: String,
+    brand: String,
+    currency: Option<String>,
+) -> Result<CatalogFeedExport, String> {
+    write_catalog_feed(&app_handle, FeedTarget::Meta, &product_ids, &format, &path, &brand, currency)
+}
+
+/// Writes the selected products as a Google Merchant Center feed (CSV or XML)
+/// to use as the product source of Shopping and Performance Max campaigns.
+/// Products failing Google's required-attribute checks are skipped with
+/// every problem listed.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_google_merchant_feed(
+    app_handle: AppHandle,
+    product_ids: Vec<i64>,
+    format: String,
+    path: String,
+    brand: String,
+    currency: Option<String>,
+) -> Result<CatalogFeedExport, String> {
+    write_catalog_feed(&app_handle, FeedTarget::Google, &product_ids, &format, &path, &brand, currency)
+}
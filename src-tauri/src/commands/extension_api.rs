@@ -0,0 +1,59 @@
+use crate::database::get_connection;
+use crate::database::settings::set_setting;
+use crate::models::extension_api::ExtensionApiStatus;
+use crate::services::extension_api::{self, ENABLED_SETTING, PORT_SETTING};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_extension_api_status(app_handle: AppHandle) -> Result<ExtensionApiStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let port = extension_api::configured_port(&conn);
+
+    Ok(ExtensionApiStatus {
+        enabled: extension_api::is_enabled(&conn),
+        running: extension_api::running_port().is_some(),
+        port,
+        base_url: format!("http://127.0.0.1:{}/api", port),
+        token: extension_api::ensure_token(&conn).map_err(|e| e.to_string())?,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_extension_api_config(
+    app_handle: AppHandle,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<ExtensionApiStatus, String> {
+    let port = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        let port = port.unwrap_or_else(|| extension_api::configured_port(&conn));
+
+        set_setting(&conn, ENABLED_SETTING, &enabled.to_string()).map_err(|e| e.to_string())?;
+        set_setting(&conn, PORT_SETTING, &port.to_string()).map_err(|e| e.to_string())?;
+        port
+    };
+
+    if enabled {
+        extension_api::start_server(app_handle.clone(), port)?;
+    } else {
+        extension_api::stop_server();
+    }
+
+    get_extension_api_status(app_handle).await
+}
+
+/// Issues a new token; the extension must be reconfigured afterwards
+#[tauri::command]
+#[specta::specta]
+pub async fn regenerate_extension_api_token(
+    app_handle: AppHandle,
+) -> Result<ExtensionApiStatus, String> {
+    {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        extension_api::regenerate_token(&conn).map_err(|e| e.to_string())?;
+    }
+
+    get_extension_api_status(app_handle).await
+}
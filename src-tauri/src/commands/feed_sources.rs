@@ -0,0 +1,37 @@
+use crate::database::get_connection;
+use crate::models::feed_source::{FeedPollReport, FeedSource, SaveFeedSourceInput};
+use crate::services::feed_sources;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_feed_sources(app_handle: AppHandle) -> Result<Vec<FeedSource>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    feed_sources::list(&conn).map_err(|e| e.to_string())
+}
+
+/// Adds an RSS or Atom feed to poll, or updates one when `input.id` is set
+#[tauri::command]
+#[specta::specta]
+pub async fn save_feed_source(app_handle: AppHandle, input: SaveFeedSourceInput) -> Result<FeedSource, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    feed_sources::save(&conn, &input)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_feed_source(app_handle: AppHandle, source_id: i64) -> Result<(), String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    feed_sources::delete(&mut conn, source_id)
+}
+
+/// Polls one feed, or every active feed, now instead of waiting for the
+/// scheduled job. New items land in the ideas inbox.
+#[tauri::command]
+#[specta::specta]
+pub async fn poll_feed_sources_now(
+    app_handle: AppHandle,
+    source_id: Option<i64>,
+) -> Result<Vec<FeedPollReport>, String> {
+    feed_sources::poll_sources(&app_handle, source_id).await
+}
@@ -0,0 +1,107 @@
+use crate::commands::ad_generation::{ad_copy_from_row, GeneratedAdCopy, AD_COPY_COLUMNS};
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::database::get_connection;
+use crate::models::headline_variant::HeadlineVariant;
+use crate::services::ai_client::{self, MOCK_PROVIDER};
+use crate::services::headline_variants::{
+    self, build_variants_prompt, clamp_count, mock_headlines, rank_candidates, validate_variants, VARIANTS_SCHEMA,
+};
+use crate::services::{ai_discovery, approvals, data_events, niche};
+use rusqlite::params;
+use tauri::AppHandle;
+
+/// Generates `count` alternative headlines for an ad, ranked by heuristic CTR
+/// signals; replaces any earlier variants for the ad
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_headline_variants(
+    app_handle: AppHandle,
+    ad_id: i64,
+    count: Option<i32>,
+) -> Result<Vec<HeadlineVariant>, String> {
+    let count = clamp_count(count);
+
+    // The connection can't be held across the provider call
+    let (ad, product, niche_profile, chain) = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+        let ad = conn
+            .query_row(
+                &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+                params![ad_id],
+                ad_copy_from_row,
+            )
+            .map_err(|_| format!("Ad {} not found", ad_id))?;
+        let product = conn
+            .query_row(
+                &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
+                params![ad.product_id],
+                product_from_row,
+            )
+            .map_err(|e| format!("Product not found: {}", e))?;
+
+        let niche_profile = niche::load_active_profile(&conn).map_err(|e| e.to_string())?;
+        let prompt = build_variants_prompt(&ad, &product, &niche::prompt_context(niche_profile.as_ref()), count);
+
+        (ad, product, niche_profile, ai_client::prepare_chain(&conn, &prompt))
+    };
+
+    let served = if chain.is_mock_only() {
+        None
+    } else {
+        ai_discovery::run_cached(&app_handle, &chain, validate_variants, VARIANTS_SCHEMA).await?
+    };
+    let (candidates, served_by) =
+        served.unwrap_or_else(|| (mock_headlines(&product), MOCK_PROVIDER.to_string()));
+
+    let ranked = rank_candidates(candidates, &ad.headline, niche_profile.as_ref(), count as usize);
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    headline_variants::save_all(&mut conn, &ad, &ranked, &served_by)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_headline_variants(app_handle: AppHandle, ad_id: i64) -> Result<Vec<HeadlineVariant>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    headline_variants::list_for_ad(&conn, ad_id).map_err(|e| e.to_string())
+}
+
+/// Makes a variant the ad's headline
+#[tauri::command]
+#[specta::specta]
+pub async fn select_headline_variant(app_handle: AppHandle, variant_id: i64) -> Result<GeneratedAdCopy, String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let (ad_id, headline): (i64, String) = conn
+        .query_row(
+            "SELECT ad_id, headline FROM headline_variants WHERE id = ?1",
+            params![variant_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|_| format!("Headline variant {} not found", variant_id))?;
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        "UPDATE headline_variants SET is_selected = (id = ?1) WHERE ad_id = ?2",
+        params![variant_id, ad_id],
+    )
+    .map_err(|e| e.to_string())?;
+    tx.execute(
+        "UPDATE ad_copies SET headline = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        params![headline, ad_id],
+    )
+    .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+    approvals::reopen(&conn, approvals::ENTITY_AD, ad_id, "Headline changed after review")?;
+
+    let ad = conn
+        .query_row(
+            &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+            params![ad_id],
+            ad_copy_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+
+    data_events::emit(&app_handle, data_events::ADS_CHANGED, data_events::UPDATED, vec![ad_id]);
+    Ok(ad)
+}
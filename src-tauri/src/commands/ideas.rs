@@ -0,0 +1,201 @@
+use crate::commands::products::{create_product, get_product_by_id};
+use crate::database::get_connection;
+use crate::models::idea::{CaptureIdeaInput, Idea};
+use crate::models::product::{CreateProductInput, Product};
+use crate::services::availability;
+use crate::services::scraper::{price_to_range, scrape_product};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use tauri::AppHandle;
+
+const IDEA_COLUMNS: &str = "id, url, note, source, status, product_id, captured_at";
+
+fn idea_from_row(row: &Row) -> rusqlite::Result<Idea> {
+    Ok(Idea {
+        id: Some(row.get(0)?),
+        url: row.get(1)?,
+        note: row.get(2)?,
+        source: row.get(3)?,
+        status: row.get(4)?,
+        product_id: row.get(5)?,
+        captured_at: row.get(6)?,
+    })
+}
+
+/// Stores a new idea unless the same URL is already waiting in the inbox.
+/// Returns the id of the new or existing idea.
+pub(crate) fn insert_idea(
+    conn: &Connection,
+    url: &str,
+    note: Option<&str>,
+    source: Option<&str>,
+) -> rusqlite::Result<i64> {
+    let existing: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM ideas WHERE url = ?1 AND status = 'new'",
+            params![url],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+
+    conn.execute(
+        "INSERT INTO ideas (url, note, source) VALUES (?1, ?2, ?3)",
+        params![url, note, source.unwrap_or("manual")],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+fn get_idea(conn: &Connection, id: i64) -> Result<Idea, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM ideas WHERE id = ?1", IDEA_COLUMNS),
+        params![id],
+        idea_from_row,
+    )
+    .map_err(|e| format!("Idea not found: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn capture_idea(app_handle: AppHandle, input: CaptureIdeaInput) -> Result<Idea, String> {
+    let url = input.url.trim();
+    if url.is_empty() {
+        return Err("Idea URL is required".to_string());
+    }
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let id = insert_idea(&conn, url, input.note.as_deref(), input.source.as_deref())
+        .map_err(|e| e.to_string())?;
+    get_idea(&conn, id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_ideas(app_handle: AppHandle, status: Option<String>) -> Result<Vec<Idea>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM ideas WHERE ?1 IS NULL OR status = ?1 ORDER BY captured_at DESC, id DESC",
+            IDEA_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let ideas = stmt
+        .query_map(params![status], idea_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(ideas)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn dismiss_idea(app_handle: AppHandle, id: i64) -> Result<Idea, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE ideas SET status = 'dismissed' WHERE id = ?1 AND status = 'new'",
+        params![id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_idea(&conn, id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_idea(app_handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM ideas WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Scrapes the idea's URL, creates a product from it, and marks the idea promoted.
+/// If the page can't be scraped the product is still created from the URL so the
+/// user can fill in details by hand.
+#[tauri::command]
+#[specta::specta]
+pub async fn promote_idea_to_product(
+    app_handle: AppHandle,
+    idea_id: i64,
+    category: Option<String>,
+) -> Result<Product, String> {
+    let idea = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        get_idea(&conn, idea_id)?
+    };
+
+    if idea.status == "promoted" {
+        return Err(format!("Idea {} was already promoted", idea_id));
+    }
+
+    let (scraped, scrape_error) = match scrape_product(&idea.url).await {
+        Ok(scraped) => (Some(scraped), None),
+        Err(e) => (None, Some(e)),
+    };
+
+    let name = scraped
+        .as_ref()
+        .and_then(|s| s.title.clone())
+        .unwrap_or_else(|| name_from_url(&idea.url));
+
+    let mut notes: Vec<String> = idea.note.iter().cloned().collect();
+    if let Some(e) = scrape_error {
+        notes.push(format!("Scrape failed: {}", e));
+    }
+
+    let input = CreateProductInput {
+        name,
+        category: category.unwrap_or_else(|| "Uncategorized".to_string()),
+        description: scraped.as_ref().and_then(|s| s.description.clone()),
+        price_range: scraped.as_ref().and_then(|s| s.price).map(price_to_range),
+        target_audience: None,
+        trending_score: None,
+        notes: if notes.is_empty() { None } else { Some(notes.join("\n")) },
+        image_url: scraped.as_ref().and_then(|s| s.image_url.clone()),
+        amazon_asin: scraped.as_ref().and_then(|s| s.amazon_asin.clone()),
+        tiktok_product_id: None,
+        instagram_product_id: None,
+        youtube_video_id: None,
+        pinterest_pin_id: None,
+        product_url: Some(idea.url.clone()),
+        tags: Vec::new(),
+    };
+
+    let mut product = create_product(app_handle.clone(), input).await?;
+
+    let raw_availability = scraped.as_ref().and_then(|s| s.availability.as_deref());
+    if let (Some(id), Some(_)) = (product.id, raw_availability) {
+        {
+            let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+            availability::record_scraped(&conn, id, raw_availability).map_err(|e| e.to_string())?;
+        }
+        product = get_product_by_id(app_handle.clone(), id).await?;
+    }
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    conn.execute(
+        "UPDATE ideas SET status = 'promoted', product_id = ?1 WHERE id = ?2",
+        params![product.id, idea_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(product)
+}
+
+/// Derives a readable placeholder name from the last meaningful URL path segment
+pub(crate) fn name_from_url(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .find(|seg| seg.len() > 3 && !seg.contains('.') && seg.chars().any(|c| c.is_alphabetic()))
+        .map(|seg| seg.replace(['-', '_'], " "))
+        .unwrap_or_else(|| url.to_string())
+}
@@ -0,0 +1,51 @@
+use crate::database::get_connection;
+use crate::models::listing_import::ListingImport;
+use crate::models::scraped_product::ScrapedProduct;
+use crate::services::scraper::fetch_html;
+use crate::services::{data_events, listing_import, shared_workspace};
+use tauri::AppHandle;
+
+/// Imports the listings of an Etsy shop or an eBay seller or search as
+/// products tagged "etsy" or "ebay". Reads up to `pages` result pages
+/// (default 1, at most 5) and stops early at a page with nothing new.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_marketplace_listings(
+    app_handle: AppHandle,
+    url: String,
+    pages: Option<u32>,
+) -> Result<ListingImport, String> {
+    let source = listing_import::parse_source(&url)?;
+    let pages = pages.unwrap_or(listing_import::DEFAULT_PAGES).clamp(1, listing_import::MAX_PAGES);
+
+    let mut listings: Vec<ScrapedProduct> = Vec::new();
+    let mut pages_read = 0;
+    for page in 1..=pages {
+        let html = match fetch_html(&listing_import::page_url(&source, page)?).await {
+            Ok(html) => html,
+            // Later pages may simply not exist
+            Err(_) if page > 1 => break,
+            Err(e) => return Err(e),
+        };
+        pages_read += 1;
+        let before = listings.len();
+        for listing in listing_import::parse_listings(&source.platform, &html) {
+            if !listings.iter().any(|seen| seen.url == listing.url) {
+                listings.push(listing);
+            }
+        }
+        if listings.len() == before {
+            break;
+        }
+    }
+    if listings.is_empty() {
+        return Err(format!("No listings found at {}", source.url));
+    }
+
+    let editor = shared_workspace::current_editor(&app_handle);
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let import = listing_import::import_listings(&mut conn, source, pages_read, &listings, Some(&editor))
+        .map_err(|e| e.to_string())?;
+    data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::CREATED, import.product_ids.clone());
+    Ok(import)
+}
@@ -0,0 +1,96 @@
+use crate::database::get_connection;
+use crate::models::maintenance::{CleanupReport, CleanupRules, IntegrityReport, RetentionPolicy, RetentionReport};
+use crate::services::{ai_cache, cleanup, data_events, integrity, read_only, retention, roles};
+use tauri::AppHandle;
+
+/// Deletes rows matched by the cleanup rules. Defaults to a dry run so the UI
+/// can show the report before the user confirms.
+#[tauri::command]
+#[specta::specta]
+pub async fn cleanup(
+    app_handle: AppHandle,
+    rules: CleanupRules,
+    dry_run: Option<bool>,
+) -> Result<CleanupReport, String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    if !dry_run.unwrap_or(true) {
+        roles::require_owner(&conn, "cleanup")?;
+    }
+    let report = cleanup::run_cleanup(&mut conn, &rules, dry_run.unwrap_or(true)).map_err(|e| e.to_string())?;
+
+    if !report.dry_run {
+        data_events::emit_deleted_rows(&app_handle, report.items.iter().map(|i| (i.table.as_str(), i.id)));
+    }
+    Ok(report)
+}
+
+/// Reports orphaned and inconsistent data; with `apply_fixes` the reported
+/// problems are repaired in the same call. Repairs delete rows, so they are
+/// owner only and refused in a read-only workspace.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_data_integrity(
+    app_handle: AppHandle,
+    apply_fixes: Option<bool>,
+) -> Result<IntegrityReport, String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let issues = integrity::check(&conn).map_err(|e| e.to_string())?;
+
+    let apply = apply_fixes.unwrap_or(false);
+    let fixed_count = if apply {
+        read_only::require_writable(&conn, "check_data_integrity")?;
+        roles::require_owner(&conn, "check_data_integrity")?;
+        let fixed = integrity::apply_fixes(&mut conn, &issues).map_err(|e| e.to_string())?;
+        data_events::emit_deleted_rows(
+            &app_handle,
+            issues.iter().filter_map(|i| i.id.map(|id| (i.table.as_str(), id))),
+        );
+        fixed
+    } else {
+        0
+    };
+
+    Ok(IntegrityReport {
+        issues,
+        fixes_applied: apply,
+        fixed_count,
+    })
+}
+
+/// Drops every cached AI response so the next discovery calls the provider again
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_ai_cache(app_handle: AppHandle) -> Result<usize, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    ai_cache::clear(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_retention_policy(app_handle: AppHandle) -> Result<RetentionPolicy, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    Ok(retention::load_policy(&conn))
+}
+
+/// Days to keep raw clicks and logs; 0 keeps them forever. Applied by the
+/// daily retention job.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_retention_policy(
+    app_handle: AppHandle,
+    policy: RetentionPolicy,
+) -> Result<RetentionPolicy, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    roles::require_owner(&conn, "save_retention_policy")?;
+    retention::save_policy(&conn, &policy)
+}
+
+/// Runs the retention pass now instead of waiting for the daily job
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_retention_policy(app_handle: AppHandle) -> Result<RetentionReport, String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    roles::require_owner(&conn, "apply_retention_policy")?;
+    let policy = retention::load_policy(&conn);
+    retention::run(&mut conn, &policy, chrono::Utc::now().naive_utc()).map_err(|e| e.to_string())
+}
@@ -1,4 +1,57 @@
 pub mod products;
 pub mod affiliate_links;
 pub mod credentials;
+pub mod critiques;
+pub mod digests;
+pub mod email_sequences;
+pub mod entity_query;
+pub mod exports;
+pub mod extension_api;
+pub mod headline_variants;
+pub mod ideas;
+pub mod maintenance;
+pub mod niche_profiles;
 pub mod ad_generation;
+pub mod ad_imports;
+pub mod ad_templates;
+pub mod analytics_import;
+pub mod bulk_operations;
+pub mod click_analytics;
+pub mod clipboard;
+pub mod content_angles;
+pub mod conversions;
+pub mod postbacks;
+pub mod research;
+pub mod rest_api;
+pub mod reviews;
+pub mod short_video;
+pub mod sms;
+pub mod spreadsheet_import;
+pub mod store_sync;
+pub mod style_rules;
+pub mod webhooks;
+pub mod workspace;
+pub mod program_applications;
+pub mod network_terms;
+pub mod deep_links;
+pub mod ad_links;
+pub mod readiness;
+pub mod post_schedule;
+pub mod retail_events;
+pub mod approvals;
+pub mod shared_workspace;
+pub mod roles;
+pub mod data_privacy;
+pub mod daily_metrics;
+pub mod diagnostics;
+pub mod watchlist;
+pub mod bundles;
+pub mod notes;
+pub mod attachments;
+pub mod platform_mix;
+pub mod category_keywords;
+pub mod variations;
+pub mod experiments;
+pub mod listing_import;
+pub mod tiktok_sync;
+pub mod feed_sources;
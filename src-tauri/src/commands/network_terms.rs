@@ -0,0 +1,42 @@
+use crate::database::get_connection;
+use crate::models::network_terms::{NetworkTerms, SaveNetworkTermsInput};
+use crate::services::network_terms;
+use rusqlite::{params, OptionalExtension};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_network_terms(app_handle: AppHandle) -> Result<Vec<NetworkTerms>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    network_terms::list(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_network_terms(app_handle: AppHandle, input: SaveNetworkTermsInput) -> Result<NetworkTerms, String> {
+    network_terms::validate(&input)?;
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    network_terms::save(&conn, &input)
+}
+
+/// Deletes user-added terms; shipped networks can only be edited
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_network_terms(app_handle: AppHandle, terms_id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let builtin: Option<bool> = conn
+        .query_row("SELECT builtin FROM network_terms WHERE id = ?1", params![terms_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    match builtin {
+        None => Err(format!("Network terms {} not found", terms_id)),
+        Some(true) => Err("Terms shipped with the app can be edited but not deleted".to_string()),
+        Some(false) => {
+            conn.execute("DELETE FROM network_terms WHERE id = ?1", params![terms_id])
+                .map_err(|e| e.to_string())?;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::database::get_connection;
+use crate::models::niche_profile::{NicheProfile, SaveNicheProfileInput};
+use crate::services::niche::{self, niche_profile_from_row, NICHE_PROFILE_COLUMNS};
+use rusqlite::params;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_niche_profiles(app_handle: AppHandle) -> Result<Vec<NicheProfile>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM niche_profiles ORDER BY active DESC, name ASC",
+            NICHE_PROFILE_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let profiles = stmt
+        .query_map([], niche_profile_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(profiles)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_active_niche_profile(
+    app_handle: AppHandle,
+) -> Result<Option<NicheProfile>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    niche::load_active_profile(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_niche_profile(
+    app_handle: AppHandle,
+    input: SaveNicheProfileInput,
+) -> Result<NicheProfile, String> {
+    if input.name.trim().is_empty() {
+        return Err("Niche profile name is required".to_string());
+    }
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let preferred_platforms: Vec<String> = input
+        .preferred_platforms
+        .iter()
+        .map(|p| p.trim().to_lowercase())
+        .filter(|p| !p.is_empty())
+        .collect();
+    let banned_claims: Vec<String> = input
+        .banned_claims
+        .iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect();
+    let preferred_json = serde_json::to_string(&preferred_platforms).map_err(|e| e.to_string())?;
+    let banned_json = serde_json::to_string(&banned_claims).map_err(|e| e.to_string())?;
+
+    let id = match input.id {
+        Some(id) => {
+            let updated = conn
+                .execute(
+                    "UPDATE niche_profiles SET name = ?1, description = ?2, preferred_platforms = ?3,
+                     typical_audience = ?4, banned_claims = ?5, updated_at = CURRENT_TIMESTAMP
+                     WHERE id = ?6",
+                    params![
+                        input.name,
+                        input.description,
+                        preferred_json,
+                        input.typical_audience,
+                        banned_json,
+                        id,
+                    ],
+                )
+                .map_err(|e| e.to_string())?;
+            if updated == 0 {
+                return Err(format!("Niche profile {} not found", id));
+            }
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO niche_profiles (name, description, preferred_platforms,
+                 typical_audience, banned_claims)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    input.name,
+                    input.description,
+                    preferred_json,
+                    input.typical_audience,
+                    banned_json,
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+            conn.last_insert_rowid()
+        }
+    };
+
+    conn.query_row(
+        &format!("SELECT {} FROM niche_profiles WHERE id = ?1", NICHE_PROFILE_COLUMNS),
+        params![id],
+        niche_profile_from_row,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Makes the given profile the workspace's active niche; `None` clears it
+#[tauri::command]
+#[specta::specta]
+pub async fn activate_niche_profile(
+    app_handle: AppHandle,
+    id: Option<i64>,
+) -> Result<Option<NicheProfile>, String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    tx.execute("UPDATE niche_profiles SET active = 0 WHERE active = 1", [])
+        .map_err(|e| e.to_string())?;
+
+    if let Some(id) = id {
+        let updated = tx
+            .execute(
+                "UPDATE niche_profiles SET active = 1, updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+                params![id],
+            )
+            .map_err(|e| e.to_string())?;
+        if updated == 0 {
+            return Err(format!("Niche profile {} not found", id));
+        }
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+    niche::load_active_profile(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_niche_profile(app_handle: AppHandle, id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    conn.execute("DELETE FROM niche_profiles WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::database::get_connection;
+use crate::models::note::{Note, NoteSearchHit, SaveNoteInput};
+use crate::services::notes;
+use tauri::AppHandle;
+
+/// Most recently edited notes first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_notes(app_handle: AppHandle, limit: Option<i64>) -> Result<Vec<Note>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    notes::list(&conn, limit.unwrap_or(100))
+}
+
+/// Notes referring to a product, link, ad, or campaign
+#[tauri::command]
+#[specta::specta]
+pub async fn get_notes_for_entity(
+    app_handle: AppHandle,
+    entity_type: String,
+    entity_id: i64,
+) -> Result<Vec<Note>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    notes::for_entity(&conn, &entity_type, entity_id)
+}
+
+/// Creates a note, or replaces an existing one and its references
+#[tauri::command]
+#[specta::specta]
+pub async fn save_note(app_handle: AppHandle, input: SaveNoteInput) -> Result<Note, String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    notes::save(&mut conn, &input)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_note(app_handle: AppHandle, note_id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    notes::delete(&conn, note_id).map_err(|e| e.to_string())
+}
+
+/// Full-text search over note titles and bodies
+#[tauri::command]
+#[specta::specta]
+pub async fn search_notes(
+    app_handle: AppHandle,
+    query: String,
+    limit: Option<i64>,
+) -> Result<Vec<NoteSearchHit>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    notes::search(&conn, &query, limit.unwrap_or(50))
+}
@@ -0,0 +1,62 @@
+use crate::database::get_connection;
+use crate::models::benchmark::{Benchmark, SaveBenchmarkInput};
+use crate::models::platform_mix::{PlatformMixSimulation, SimulationAssumptions};
+use crate::services::{benchmarks, platform_mix};
+use tauri::AppHandle;
+
+/// Projects clicks, conversions, and earnings for a product under different
+/// platform allocations, using the benchmarks for its category
+#[tauri::command]
+#[specta::specta]
+pub async fn simulate_platform_mix(
+    app_handle: AppHandle,
+    product_id: i64,
+    assumptions: SimulationAssumptions,
+) -> Result<PlatformMixSimulation, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    platform_mix::simulate(&conn, product_id, &assumptions)
+}
+
+/// Expected earnings from `impressions` of a product on one platform
+#[tauri::command]
+#[specta::specta]
+pub async fn estimate_earnings(
+    app_handle: AppHandle,
+    product_id: i64,
+    platform: String,
+    impressions: i64,
+) -> Result<PlatformMixSimulation, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    platform_mix::estimate(&conn, product_id, &platform, impressions)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_benchmarks(app_handle: AppHandle) -> Result<Vec<Benchmark>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    benchmarks::list(&conn).map_err(|e| e.to_string())
+}
+
+/// Edits a benchmark, or adds one for a platform and category
+#[tauri::command]
+#[specta::specta]
+pub async fn save_benchmark(app_handle: AppHandle, input: SaveBenchmarkInput) -> Result<Benchmark, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    benchmarks::save(&conn, &input)
+}
+
+/// Restores a shipped benchmark's values
+#[tauri::command]
+#[specta::specta]
+pub async fn reset_benchmark(app_handle: AppHandle, benchmark_id: i64) -> Result<Benchmark, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    benchmarks::reset(&conn, benchmark_id)
+}
+
+/// Deletes a benchmark the user added
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_benchmark(app_handle: AppHandle, benchmark_id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    benchmarks::delete(&conn, benchmark_id)
+}
@@ -0,0 +1,199 @@
+use crate::commands::ad_generation::{ad_copy_from_row, AD_COPY_COLUMNS};
+use crate::database::get_connection;
+use crate::database::settings::get_setting_parsed;
+use crate::models::scheduled_post::{
+    PostTimeSuggestion, PostingRules, SaveScheduledPostInput, SavedScheduledPost, ScheduleConflict, ScheduledPost,
+};
+use crate::services::post_schedule::{self, POSTED, POST_STATUSES};
+use crate::services::{accessibility, approvals, availability, post_timing, posting_caps};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use tauri::AppHandle;
+
+/// Calendar entries in a date range, optionally for one platform
+#[tauri::command]
+#[specta::specta]
+pub async fn get_scheduled_posts(
+    app_handle: AppHandle,
+    from: Option<String>,
+    to: Option<String>,
+    platform: Option<String>,
+) -> Result<Vec<ScheduledPost>, String> {
+    let from = from.as_deref().map(|f| post_schedule::normalize_time(f).ok_or(format!("Invalid date '{}'", f)));
+    let to = to.as_deref().map(|t| post_schedule::normalize_time(t).ok_or(format!("Invalid date '{}'", t)));
+    let (from, to) = (from.transpose()?, to.transpose()?);
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    post_schedule::list(&conn, from.as_deref(), to.as_deref(), platform.as_deref()).map_err(|e| e.to_string())
+}
+
+/// Creates or updates a post; recurring posts are expanded onto the calendar
+/// right away. Without a `scheduled_at` the suggested best time is used.
+/// Posts that break a posting cap or double-book the product are rejected
+/// when caps are enforced (unless `allow_conflicts`), otherwise saved with
+/// the conflicts as warnings. A product last seen out of stock, or a visual
+/// ad without alt text, is always just a warning.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_scheduled_post(
+    app_handle: AppHandle,
+    mut input: SaveScheduledPostInput,
+) -> Result<SavedScheduledPost, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    if input.scheduled_at.trim().is_empty() && !input.platform.trim().is_empty() {
+        let offset = get_setting_parsed(&conn, post_timing::UTC_OFFSET_SETTING, 0);
+        input.scheduled_at = post_timing::suggest(&conn, input.product_id, &input.platform, offset)
+            .map_err(|e| e.to_string())?
+            .best
+            .scheduled_at;
+    }
+    post_schedule::validate(&input)?;
+
+    let rules = posting_caps::load_rules(&conn);
+    let scheduled_at = post_schedule::normalize_time(&input.scheduled_at).unwrap_or_default();
+    let mut conflicts =
+        posting_caps::check(&conn, &rules, input.product_id, &input.platform, &scheduled_at, input.id)
+            .map_err(|e| e.to_string())?;
+    if rules.enforce && !conflicts.is_empty() && !input.allow_conflicts.unwrap_or(false) {
+        let messages: Vec<&str> = conflicts.iter().map(|c| c.message.as_str()).collect();
+        return Err(format!("Posting cap conflict: {}", messages.join("; ")));
+    }
+    // Stock is only ever a warning; it may be back by the time the post goes out
+    conflicts.extend(
+        availability::schedule_warning(&conn, input.product_id, &input.platform).map_err(|e| e.to_string())?,
+    );
+    if let Some(ad_id) = input.ad_id {
+        let ad = conn
+            .query_row(
+                &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+                params![ad_id],
+                ad_copy_from_row,
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        conflicts.extend(ad.as_ref().and_then(accessibility::missing_alt_text_warning).map(|message| {
+            ScheduleConflict {
+                kind: accessibility::MISSING_ALT_TEXT_CONFLICT.to_string(),
+                platform: input.platform.clone(),
+                message,
+                post_ids: Vec::new(),
+            }
+        }));
+    }
+
+    let previous = match input.id {
+        Some(id) => post_schedule::get(&conn, id).map_err(|e| e.to_string())?,
+        None => None,
+    };
+    let post = post_schedule::save(&conn, &input).map_err(|e| e.to_string())?;
+    // Reviewed posts go back to draft when what was signed off on changes
+    if let Some(previous) = previous {
+        let reviewed = |p: &ScheduledPost| {
+            (p.product_id, p.ad_id, p.platform.clone(), p.scheduled_at.clone(), p.notes.clone())
+        };
+        if reviewed(&previous) != reviewed(&post) {
+            approvals::reopen(&conn, approvals::ENTITY_POST, post.id, "Post changed after review")?;
+        }
+    }
+    let post = post_schedule::get(&conn, post.id).map_err(|e| e.to_string())?.unwrap_or(post);
+    Ok(SavedScheduledPost { post, conflicts })
+}
+
+/// Cap and double-booking conflicts among the posts in a date range
+#[tauri::command]
+#[specta::specta]
+pub async fn get_schedule_conflicts(
+    app_handle: AppHandle,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<Vec<ScheduleConflict>, String> {
+    let posts = get_scheduled_posts(app_handle.clone(), from, to, None).await?;
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    Ok(posting_caps::calendar_conflicts(&posting_caps::load_rules(&conn), &posts))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_posting_rules(app_handle: AppHandle) -> Result<PostingRules, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    Ok(posting_caps::load_rules(&conn))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_posting_rules(app_handle: AppHandle, rules: PostingRules) -> Result<PostingRules, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    posting_caps::save_rules(&conn, &rules).map_err(|e| e.to_string())?;
+    Ok(posting_caps::load_rules(&conn))
+}
+
+/// Best time to post a product on a platform from its clicks, platform
+/// defaults, and audience. `utc_offset_minutes` is the audience's time zone
+/// (defaults to the workspace setting).
+#[tauri::command]
+#[specta::specta]
+pub async fn suggest_post_time(
+    app_handle: AppHandle,
+    product_id: i64,
+    platform: String,
+    utc_offset_minutes: Option<i32>,
+) -> Result<PostTimeSuggestion, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let offset =
+        utc_offset_minutes.unwrap_or_else(|| get_setting_parsed(&conn, post_timing::UTC_OFFSET_SETTING, 0));
+    post_timing::suggest(&conn, product_id, &platform, offset).map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => format!("Product {} not found", product_id),
+        e => e.to_string(),
+    })
+}
+
+/// Marking a post posted publishes its approved post and ad; when approval
+/// is required, unapproved posts can't be marked posted
+#[tauri::command]
+#[specta::specta]
+pub async fn set_scheduled_post_status(
+    app_handle: AppHandle,
+    post_id: i64,
+    status: String,
+) -> Result<ScheduledPost, String> {
+    if !POST_STATUSES.contains(&status.as_str()) {
+        return Err(format!("Unknown post status '{}'; expected one of {}", status, POST_STATUSES.join(", ")));
+    }
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    if status == POSTED {
+        let post = post_schedule::get(&conn, post_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Scheduled post {} not found", post_id))?;
+        approvals::publish_post(&conn, post_id, post.ad_id)?;
+    }
+    post_schedule::set_status(&conn, post_id, &status).map_err(|e| e.to_string())?;
+    post_schedule::get(&conn, post_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Scheduled post {} not found", post_id))
+}
+
+/// Deletes a post (or its whole series). Single occurrences of a series are
+/// cancelled rather than deleted so they aren't scheduled again.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_scheduled_post(
+    app_handle: AppHandle,
+    post_id: i64,
+    whole_series: Option<bool>,
+) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let post = post_schedule::get(&conn, post_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Scheduled post {} not found", post_id))?;
+    post_schedule::delete(&conn, &post, whole_series.unwrap_or(false)).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Fills in upcoming occurrences of every recurring series now, instead of
+/// waiting for the daily scheduler run; returns how many were added
+#[tauri::command]
+#[specta::specta]
+pub async fn expand_recurring_posts(app_handle: AppHandle) -> Result<usize, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    post_schedule::expand_all(&conn, Utc::now().naive_utc()).map_err(|e| e.to_string())
+}
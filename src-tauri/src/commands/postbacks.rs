@@ -0,0 +1,126 @@
+use crate::database::get_connection;
+use crate::database::settings::set_setting;
+use crate::models::conversion::{PostbackConfig, PostbackLogEntry, PostbackStatus};
+use crate::services::postback::{self, POSTBACK_PATH};
+use rusqlite::params;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_postback_config(app_handle: AppHandle) -> Result<PostbackConfig, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    Ok(postback::load_config(&conn))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_postback_config(
+    app_handle: AppHandle,
+    config: PostbackConfig,
+) -> Result<PostbackStatus, String> {
+    match config.signature_mode.as_str() {
+        "none" | "token" | "hmac_sha256" => {}
+        other => return Err(format!("Unknown signature mode: {}", other)),
+    }
+
+    {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+        let values = [
+            ("postback_enabled", config.enabled.to_string()),
+            ("postback_port", config.port.to_string()),
+            ("postback_signature_mode", config.signature_mode.clone()),
+            ("postback_secret", config.secret.clone().unwrap_or_default()),
+            ("postback_subid_param", config.subid_param.clone()),
+            ("postback_amount_param", config.amount_param.clone()),
+            ("postback_commission_param", config.commission_param.clone()),
+            ("postback_order_id_param", config.order_id_param.clone()),
+        ];
+        for (key, value) in values.iter() {
+            set_setting(&conn, key, value).map_err(|e| e.to_string())?;
+        }
+    }
+
+    // Apply the new configuration to the running listener
+    if config.enabled {
+        postback::start_listener(app_handle.clone(), config.port)?;
+    } else {
+        postback::stop_listener();
+    }
+
+    get_postback_status(app_handle).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn start_postback_listener(app_handle: AppHandle) -> Result<PostbackStatus, String> {
+    let port = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        postback::load_config(&conn).port
+    };
+
+    postback::start_listener(app_handle.clone(), port)?;
+    get_postback_status(app_handle).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_postback_listener(app_handle: AppHandle) -> Result<PostbackStatus, String> {
+    postback::stop_listener();
+    get_postback_status(app_handle).await
+}
+
+/// The listener only binds 127.0.0.1, so the URL template is reachable from this
+/// machine alone. Networks can't call it directly: expose the port through a
+/// tunnel (e.g. cloudflared or ngrok) and give the network the tunnel's URL with
+/// the same path and query.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_postback_status(_app_handle: AppHandle) -> Result<PostbackStatus, String> {
+    let port = postback::running_port();
+
+    Ok(PostbackStatus {
+        running: port.is_some(),
+        port,
+        postback_url: port.map(|p| {
+            format!(
+                "http://127.0.0.1:{}{}?subid={{subid}}&amount={{amount}}&commission={{commission}}&order_id={{order_id}}",
+                p, POSTBACK_PATH
+            )
+        }),
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_postback_log(
+    app_handle: AppHandle,
+    limit: Option<i64>,
+) -> Result<Vec<PostbackLogEntry>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, query_string, subid, accepted, conversion_id, error, received_at
+             FROM postback_log ORDER BY received_at DESC, id DESC LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let entries = stmt
+        .query_map(params![limit.unwrap_or(100)], |row| {
+            Ok(PostbackLogEntry {
+                id: row.get(0)?,
+                query_string: row.get(1)?,
+                subid: row.get(2)?,
+                accepted: row.get(3)?,
+                conversion_id: row.get(4)?,
+                error: row.get(5)?,
+                received_at: row.get(6)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(entries)
+}
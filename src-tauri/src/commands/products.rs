@@ -1,44 +1,88 @@
 use crate::database::get_connection;
-use crate::models::product::{CreateProductInput, Product, UpdateProductInput};
-use rusqlite::params;
+use crate::models::product::{
+    CreateProductInput, Product, ProductStage, StageCount, UpdateProductInput,
+};
+use crate::models::entity_query::QuerySort;
+use crate::models::list_page::{ProductListFilter, ProductPage};
+use crate::models::product_comparison::ProductComparison;
+use crate::models::promotion_queue::PromotionCandidate;
+use crate::models::trend::TrendHistory;
+use crate::services::{
+    availability, data_events, list_pages, notes, product_comparison, promotion_queue, roles, shared_workspace,
+    trend_history,
+};
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension, Row};
 use tauri::AppHandle;
 
+/// Column list shared by every product query; keep in sync with `product_from_row`
+pub(crate) const PRODUCT_COLUMNS: &str = "id, name, category, description, price_range, target_audience,
+     trending_score, notes, image_url, amazon_asin, tiktok_product_id,
+     instagram_product_id, youtube_video_id, pinterest_pin_id, product_url,
+     created_at, updated_at, stage, stage_changed_at, tags, updated_by,
+     availability, availability_checked_at";
+
+/// Maps a row selected with `PRODUCT_COLUMNS` into a `Product`
+pub(crate) fn product_from_row(row: &Row) -> rusqlite::Result<Product> {
+    Ok(Product {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        category: row.get(2)?,
+        description: row.get(3)?,
+        price_range: row.get(4)?,
+        target_audience: row.get(5)?,
+        trending_score: row.get(6)?,
+        notes: row.get(7)?,
+        image_url: row.get(8)?,
+        amazon_asin: row.get(9)?,
+        tiktok_product_id: row.get(10)?,
+        instagram_product_id: row.get(11)?,
+        youtube_video_id: row.get(12)?,
+        pinterest_pin_id: row.get(13)?,
+        product_url: row.get(14)?,
+        created_at: row.get(15)?,
+        updated_at: row.get(16)?,
+        stage: row.get(17)?,
+        stage_changed_at: row.get(18)?,
+        tags: parse_tags(row.get::<_, Option<String>>(19)?.as_deref()),
+        updated_by: row.get(20)?,
+        availability: row.get(21)?,
+        availability_checked_at: row.get(22)?,
+    })
+}
+
+/// Tags are stored as a JSON array of strings
+fn parse_tags(json: Option<&str>) -> Vec<String> {
+    json.and_then(|j| serde_json::from_str(j).ok())
+        .unwrap_or_default()
+}
+
+/// Trims, lowercases, and de-duplicates tags before storing them
+fn tags_to_json(tags: &[String]) -> String {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    serde_json::to_string(&normalized).unwrap_or_else(|_| "[]".to_string())
+}
+
 #[tauri::command]
+#[specta::specta]
 pub async fn get_all_products(app_handle: AppHandle) -> Result<Vec<Product>, String> {
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
 
     let mut stmt = conn
-        .prepare(
-            "SELECT id, name, category, description, price_range, target_audience,
-             trending_score, notes, image_url, amazon_asin, tiktok_product_id,
-             instagram_product_id, youtube_video_id, pinterest_pin_id, product_url,
-             created_at, updated_at
-             FROM products ORDER BY trending_score DESC, name ASC",
-        )
+        .prepare_cached(&format!(
+            "SELECT {} FROM products ORDER BY trending_score DESC, name ASC",
+            PRODUCT_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
     let products = stmt
-        .query_map([], |row| {
-            Ok(Product {
-                id: Some(row.get(0)?),
-                name: row.get(1)?,
-                category: row.get(2)?,
-                description: row.get(3)?,
-                price_range: row.get(4)?,
-                target_audience: row.get(5)?,
-                trending_score: row.get(6)?,
-                notes: row.get(7)?,
-                image_url: row.get(8)?,
-                amazon_asin: row.get(9)?,
-                tiktok_product_id: row.get(10)?,
-                instagram_product_id: row.get(11)?,
-                youtube_video_id: row.get(12)?,
-                pinterest_pin_id: row.get(13)?,
-                product_url: row.get(14)?,
-                created_at: row.get(15)?,
-                updated_at: row.get(16)?,
-            })
-        })
+        .query_map([], product_from_row)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
@@ -46,46 +90,67 @@ pub async fn get_all_products(app_handle: AppHandle) -> Result<Vec<Product>, Str
     Ok(products)
 }
 
+/// Number of products matching `filter`, for sizing a virtualized table
+#[tauri::command]
+#[specta::specta]
+pub async fn count_products(app_handle: AppHandle, filter: Option<ProductListFilter>) -> Result<i64, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    list_pages::count_products(&conn, &filter.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+/// Rows `offset..offset + limit` (at most 500) of the filtered product list,
+/// plus its total. Sorted like `get_all_products` unless `sort` is given.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_products_range(
+    app_handle: AppHandle,
+    filter: Option<ProductListFilter>,
+    sort: Option<QuerySort>,
+    offset: i64,
+    limit: i64,
+) -> Result<ProductPage, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    list_pages::product_page(&conn, &filter.unwrap_or_default(), &sort, offset, limit)
+}
+
+/// Two to six products side by side: price, commission options, click trend,
+/// and the last 30 days' performance, plus a Markdown table of the same for
+/// comparison-article ad prompts
+#[tauri::command]
+#[specta::specta]
+pub async fn compare_products(app_handle: AppHandle, product_ids: Vec<i64>) -> Result<ProductComparison, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    product_comparison::compare(&conn, &product_ids, Utc::now().date_naive())
+}
+
+/// Products ranked by opportunity score (trending, commission, competition,
+/// earnings per click, seasonality), each with the reasons behind its rank
 #[tauri::command]
+#[specta::specta]
+pub async fn get_promotion_queue(
+    app_handle: AppHandle,
+    limit: Option<i64>,
+) -> Result<Vec<PromotionCandidate>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let limit = limit.unwrap_or(20).max(1) as usize;
+    promotion_queue::queue(&conn, Utc::now().date_naive(), limit).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
 pub async fn get_product_by_id(app_handle: AppHandle, id: i64) -> Result<Product, String> {
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
 
     let product = conn
-        .query_row(
-            "SELECT id, name, category, description, price_range, target_audience,
-             trending_score, notes, image_url, amazon_asin, tiktok_product_id,
-             instagram_product_id, youtube_video_id, pinterest_pin_id, product_url,
-             created_at, updated_at
-             FROM products WHERE id = ?1",
-            params![id],
-            |row| {
-                Ok(Product {
-                    id: Some(row.get(0)?),
-                    name: row.get(1)?,
-                    category: row.get(2)?,
-                    description: row.get(3)?,
-                    price_range: row.get(4)?,
-                    target_audience: row.get(5)?,
-                    trending_score: row.get(6)?,
-                    notes: row.get(7)?,
-                    image_url: row.get(8)?,
-                    amazon_asin: row.get(9)?,
-                    tiktok_product_id: row.get(10)?,
-                    instagram_product_id: row.get(11)?,
-                    youtube_video_id: row.get(12)?,
-                    pinterest_pin_id: row.get(13)?,
-                    product_url: row.get(14)?,
-                    created_at: row.get(15)?,
-                    updated_at: row.get(16)?,
-                })
-            },
-        )
+        .prepare_cached(&format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS))
+        .and_then(|mut stmt| stmt.query_row(params![id], product_from_row))
         .map_err(|e| e.to_string())?;
 
     Ok(product)
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn create_product(
     app_handle: AppHandle,
     input: CreateProductInput,
@@ -95,8 +160,8 @@ pub async fn create_product(
     conn.execute(
         "INSERT INTO products (name, category, description, price_range, target_audience,
          trending_score, notes, image_url, amazon_asin, tiktok_product_id,
-         instagram_product_id, youtube_video_id, pinterest_pin_id, product_url)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+         instagram_product_id, youtube_video_id, pinterest_pin_id, product_url, tags, updated_by)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
         params![
             input.name,
             input.category,
@@ -112,15 +177,19 @@ pub async fn create_product(
             input.youtube_video_id,
             input.pinterest_pin_id,
             input.product_url,
+            tags_to_json(&input.tags),
+            shared_workspace::current_editor(&app_handle),
         ],
     )
     .map_err(|e| e.to_string())?;
 
     let id = conn.last_insert_rowid();
+    data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::CREATED, vec![id]);
     get_product_by_id(app_handle, id).await
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn update_product(
     app_handle: AppHandle,
     input: UpdateProductInput,
@@ -190,39 +259,79 @@ pub async fn update_product(
             updates.push("product_url = ?");
             params_vec.push(Box::new(product_url));
         }
+        if let Some(tags) = input.tags {
+            updates.push("tags = ?");
+            params_vec.push(Box::new(tags_to_json(&tags)));
+        }
+        if let Some(status) = input.availability {
+            let status = availability::parse_status(&status)?;
+            updates.push("availability = ?");
+            params_vec.push(Box::new(status));
+            updates.push("availability_checked_at = CURRENT_TIMESTAMP");
+        }
 
         if updates.is_empty() {
             return Err("No fields to update".to_string());
         }
 
+        updates.push("updated_by = ?");
+        params_vec.push(Box::new(shared_workspace::current_editor(&app_handle)));
         updates.push("updated_at = CURRENT_TIMESTAMP");
         params_vec.push(Box::new(product_id));
 
-        let query = format!(
+        // Only write over the version the edit was based on
+        let mut query = format!(
             "UPDATE products SET {} WHERE id = ?",
             updates.join(", ")
         );
+        if let Some(expected) = input.expected_updated_at.as_ref() {
+            query.push_str(" AND updated_at IS ?");
+            params_vec.push(Box::new(expected.clone()));
+        }
 
         let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|b| &**b as &dyn rusqlite::ToSql).collect();
 
-        conn.execute(&query, params_refs.as_slice())
+        let updated = conn.execute(&query, params_refs.as_slice())
             .map_err(|e| e.to_string())?;
+        if updated == 0 {
+            let current: Option<(Option<String>, Option<String>)> = conn
+                .query_row(
+                    "SELECT updated_at, updated_by FROM products WHERE id = ?1",
+                    params![product_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?;
+            return Err(match current {
+                None => format!("Product {} not found", product_id),
+                Some((updated_at, updated_by)) => format!(
+                    "This product was changed by {} at {} after you opened it; reload it and try again",
+                    updated_by.as_deref().unwrap_or("someone else"),
+                    updated_at.as_deref().unwrap_or("an unknown time")
+                ),
+            });
+        }
     }
 
+    data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::UPDATED, vec![product_id]);
     get_product_by_id(app_handle, product_id).await
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn delete_product(app_handle: AppHandle, id: i64) -> Result<(), String> {
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    roles::require_owner(&conn, "delete_product")?;
 
     conn.execute("DELETE FROM products WHERE id = ?1", params![id])
         .map_err(|e| e.to_string())?;
 
+    data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::DELETED, vec![id]);
     Ok(())
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn search_products(
     app_handle: AppHandle,
     query: String,
@@ -230,43 +339,138 @@ pub async fn search_products(
     let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
 
     let search_pattern = format!("%{}%", query);
+    // Products whose notes match are included too
+    let noted_ids = notes::matching_product_ids(&conn, &query).map_err(|e| e.to_string())?;
+    let noted_ids = serde_json::to_string(&noted_ids).map_err(|e| e.to_string())?;
     let mut stmt = conn
-        .prepare(
-            "SELECT id, name, category, description, price_range, target_audience,
-             trending_score, notes, image_url, amazon_asin, tiktok_product_id,
-             instagram_product_id, youtube_video_id, pinterest_pin_id, product_url,
-             created_at, updated_at
-             FROM products
+        .prepare(&format!(
+            "SELECT {} FROM products
              WHERE name LIKE ?1 OR category LIKE ?1 OR description LIKE ?1
+                OR id IN (SELECT value FROM json_each(?2))
              ORDER BY trending_score DESC, name ASC",
+            PRODUCT_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let products = stmt
+        .query_map(params![search_pattern, noted_ids], product_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(products)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_product_stage(
+    app_handle: AppHandle,
+    product_id: i64,
+    stage: String,
+) -> Result<Product, String> {
+    let next = ProductStage::from_string(&stage)
+        .ok_or_else(|| format!("Unknown product stage: {}", stage))?;
+
+    {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+        let current: Option<String> = conn
+            .query_row(
+                "SELECT stage FROM products WHERE id = ?1",
+                params![product_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Product not found: {}", e))?;
+
+        let current = current
+            .as_deref()
+            .and_then(ProductStage::from_string)
+            .unwrap_or(ProductStage::Researching);
+
+        if current == next {
+            return get_product_by_id(app_handle.clone(), product_id).await;
+        }
+
+        if !current.can_transition_to(next) {
+            return Err(format!(
+                "Cannot move product from {} to {}",
+                current.as_str(),
+                next.as_str()
+            ));
+        }
+
+        conn.execute(
+            "UPDATE products SET stage = ?1, stage_changed_at = CURRENT_TIMESTAMP,
+             updated_at = CURRENT_TIMESTAMP, updated_by = ?2 WHERE id = ?3",
+            params![next.as_str(), shared_workspace::current_editor(&app_handle), product_id],
         )
         .map_err(|e| e.to_string())?;
+    }
+
+    data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::UPDATED, vec![product_id]);
+    get_product_by_id(app_handle, product_id).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_products_by_stage(
+    app_handle: AppHandle,
+    stage: String,
+) -> Result<Vec<Product>, String> {
+    let stage = ProductStage::from_string(&stage)
+        .ok_or_else(|| format!("Unknown product stage: {}", stage))?;
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare_cached(&format!(
+            "SELECT {} FROM products WHERE COALESCE(stage, 'researching') = ?1
+             ORDER BY stage_changed_at DESC, trending_score DESC, name ASC",
+            PRODUCT_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
 
     let products = stmt
-        .query_map(params![search_pattern], |row| {
-            Ok(Product {
-                id: Some(row.get(0)?),
-                name: row.get(1)?,
-                category: row.get(2)?,
-                description: row.get(3)?,
-                price_range: row.get(4)?,
-                target_audience: row.get(5)?,
-                trending_score: row.get(6)?,
-                notes: row.get(7)?,
-                image_url: row.get(8)?,
-                amazon_asin: row.get(9)?,
-                tiktok_product_id: row.get(10)?,
-                instagram_product_id: row.get(11)?,
-                youtube_video_id: row.get(12)?,
-                pinterest_pin_id: row.get(13)?,
-                product_url: row.get(14)?,
-                created_at: row.get(15)?,
-                updated_at: row.get(16)?,
-            })
-        })
+        .query_map(params![stage.as_str()], product_from_row)
         .map_err(|e| e.to_string())?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| e.to_string())?;
 
     Ok(products)
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_stage_counts(app_handle: AppHandle) -> Result<Vec<StageCount>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare_cached("SELECT COUNT(*) FROM products WHERE COALESCE(stage, 'researching') = ?1")
+        .map_err(|e| e.to_string())?;
+
+    // Report every stage in pipeline order, including empty ones
+    ProductStage::all()
+        .into_iter()
+        .map(|stage| {
+            let count = stmt
+                .query_row(params![stage.as_str()], |row| row.get(0))
+                .map_err(|e| e.to_string())?;
+            Ok(StageCount {
+                stage: stage.as_str().to_string(),
+                count,
+            })
+        })
+        .collect()
+}
+
+/// Recorded trending scores for a product with the current momentum (defaults to the momentum window)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_trend_history(
+    app_handle: AppHandle,
+    product_id: i64,
+    days: Option<i64>,
+) -> Result<TrendHistory, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    trend_history::history(&conn, product_id, days.unwrap_or(trend_history::MOMENTUM_DAYS))
+        .map_err(|e| e.to_string())
+}
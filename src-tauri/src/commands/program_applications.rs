@@ -0,0 +1,49 @@
+use crate::database::get_connection;
+use crate::models::program_application::{ProgramApplication, SaveProgramApplicationInput};
+use crate::services::program_applications;
+use rusqlite::params;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_program_applications(
+    app_handle: AppHandle,
+    status: Option<String>,
+) -> Result<Vec<ProgramApplication>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    program_applications::list(&conn, status.as_deref()).map_err(|e| e.to_string())
+}
+
+/// Creates or updates the application for a program, matched by name
+#[tauri::command]
+#[specta::specta]
+pub async fn save_program_application(
+    app_handle: AppHandle,
+    input: SaveProgramApplicationInput,
+) -> Result<ProgramApplication, String> {
+    program_applications::validate(&input)?;
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    program_applications::save(&conn, &input).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_program_application(app_handle: AppHandle, application_id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let deleted = conn
+        .execute("DELETE FROM program_applications WHERE id = ?1", params![application_id])
+        .map_err(|e| e.to_string())?;
+    if deleted == 0 {
+        return Err(format!("Program application {} not found", application_id));
+    }
+    Ok(())
+}
+
+/// Pending applications that are due a follow-up
+#[tauri::command]
+#[specta::specta]
+pub async fn get_application_reminders(app_handle: AppHandle) -> Result<Vec<ProgramApplication>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    program_applications::due_reminders(&conn).map_err(|e| e.to_string())
+}
@@ -0,0 +1,56 @@
+use crate::database::get_connection;
+use crate::models::readiness::{ProductReadiness, ReadinessConfig};
+use crate::services::readiness;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_readiness_config(app_handle: AppHandle) -> Result<ReadinessConfig, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    Ok(readiness::load_config(&conn))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_readiness_config(
+    app_handle: AppHandle,
+    config: ReadinessConfig,
+) -> Result<ReadinessConfig, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    readiness::save_config(&conn, &config).map_err(|e| e.to_string())?;
+    Ok(readiness::load_config(&conn))
+}
+
+/// Evaluates the readiness checklist for one product
+#[tauri::command]
+#[specta::specta]
+pub async fn get_product_readiness(app_handle: AppHandle, product_id: i64) -> Result<ProductReadiness, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let config = readiness::load_config(&conn);
+    readiness::product_readiness(&conn, &config, product_id)
+        .map_err(|_| format!("Product {} not found", product_id))
+}
+
+/// The products among `product_ids` that pass every required check
+#[tauri::command]
+#[specta::specta]
+pub async fn filter_ready_products(app_handle: AppHandle, product_ids: Vec<i64>) -> Result<Vec<i64>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    readiness::ready_product_ids(&conn, &product_ids).map_err(|e| e.to_string())
+}
+
+/// Marks the product's price as checked just now (or clears the mark)
+#[tauri::command]
+#[specta::specta]
+pub async fn set_price_verified(
+    app_handle: AppHandle,
+    product_id: i64,
+    verified: bool,
+) -> Result<ProductReadiness, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    if readiness::set_price_verified(&conn, product_id, verified).map_err(|e| e.to_string())? == 0 {
+        return Err(format!("Product {} not found", product_id));
+    }
+    let config = readiness::load_config(&conn);
+    readiness::product_readiness(&conn, &config, product_id).map_err(|e| e.to_string())
+}
@@ -0,0 +1,61 @@
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::database::get_connection;
+use crate::models::research::ProductResearch;
+use crate::services::ai_client::{self, MOCK_PROVIDER};
+use crate::services::research::{self, build_research_prompt, mock_research, validate_brief, RESEARCH_SCHEMA};
+use crate::services::{ai_discovery, niche};
+use rusqlite::params;
+use tauri::AppHandle;
+
+/// Generates and stores a research brief for a product; later ads use it as context
+#[tauri::command]
+#[specta::specta]
+pub async fn research_product(app_handle: AppHandle, product_id: i64) -> Result<ProductResearch, String> {
+    // The connection can't be held across the provider call
+    let (product, chain) = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+        let product = conn
+            .query_row(
+                &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
+                params![product_id],
+                product_from_row,
+            )
+            .map_err(|e| format!("Product not found: {}", e))?;
+
+        let niche_profile = niche::load_active_profile(&conn).map_err(|e| e.to_string())?;
+        let target_audience = niche::effective_audience(
+            product.target_audience.as_deref().unwrap_or(""),
+            niche_profile.as_ref(),
+        );
+        let prompt = build_research_prompt(
+            &product,
+            &target_audience,
+            &niche::prompt_context(niche_profile.as_ref()),
+        );
+
+        (product, ai_client::prepare_chain(&conn, &prompt))
+    };
+
+    let served = if chain.is_mock_only() {
+        None
+    } else {
+        ai_discovery::run_cached(&app_handle, &chain, validate_brief, RESEARCH_SCHEMA).await?
+    };
+    let (brief, served_by) =
+        served.unwrap_or_else(|| (mock_research(&product), MOCK_PROVIDER.to_string()));
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    research::save(&conn, product_id, &brief, &served_by).map_err(|e| e.to_string())
+}
+
+/// The latest research brief for a product, if it has been researched
+#[tauri::command]
+#[specta::specta]
+pub async fn get_product_research(
+    app_handle: AppHandle,
+    product_id: i64,
+) -> Result<Option<ProductResearch>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    research::latest_for_product(&conn, product_id).map_err(|e| e.to_string())
+}
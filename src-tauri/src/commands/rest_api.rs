@@ -0,0 +1,66 @@
+use crate::database::get_connection;
+use crate::database::settings::set_setting;
+use crate::models::rest_api::RestApiStatus;
+use crate::services::rest_api::{self, ALLOW_LAN_SETTING, ENABLED_SETTING, PORT_SETTING};
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_rest_api_status(app_handle: AppHandle) -> Result<RestApiStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let port = rest_api::configured_port(&conn);
+    let allow_lan = rest_api::allows_lan(&conn);
+
+    Ok(RestApiStatus {
+        enabled: rest_api::is_enabled(&conn),
+        running: rest_api::running_port().is_some(),
+        port,
+        allow_lan,
+        base_url: format!("http://127.0.0.1:{}/v1", port),
+        lan_url: allow_lan
+            .then(rest_api::lan_address)
+            .flatten()
+            .map(|ip| format!("http://{}:{}/v1", ip, port)),
+        token: rest_api::ensure_token(&conn).map_err(|e| e.to_string())?,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_rest_api_config(
+    app_handle: AppHandle,
+    enabled: bool,
+    port: Option<u16>,
+    allow_lan: Option<bool>,
+) -> Result<RestApiStatus, String> {
+    let (port, allow_lan) = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        let port = port.unwrap_or_else(|| rest_api::configured_port(&conn));
+        let allow_lan = allow_lan.unwrap_or_else(|| rest_api::allows_lan(&conn));
+
+        set_setting(&conn, ENABLED_SETTING, &enabled.to_string()).map_err(|e| e.to_string())?;
+        set_setting(&conn, PORT_SETTING, &port.to_string()).map_err(|e| e.to_string())?;
+        set_setting(&conn, ALLOW_LAN_SETTING, &allow_lan.to_string()).map_err(|e| e.to_string())?;
+        (port, allow_lan)
+    };
+
+    if enabled {
+        rest_api::start_server(app_handle.clone(), port, allow_lan)?;
+    } else {
+        rest_api::stop_server();
+    }
+
+    get_rest_api_status(app_handle).await
+}
+
+/// Issues a new token; existing scripts and dashboards stop working until updated
+#[tauri::command]
+#[specta::specta]
+pub async fn regenerate_rest_api_token(app_handle: AppHandle) -> Result<RestApiStatus, String> {
+    {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        rest_api::regenerate_token(&conn).map_err(|e| e.to_string())?;
+    }
+
+    get_rest_api_status(app_handle).await
+}
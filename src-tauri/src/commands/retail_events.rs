@@ -0,0 +1,171 @@
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::database::{get_connection, parse_timestamp};
+use crate::database::settings::get_setting_parsed;
+use crate::models::retail_event::{
+    CampaignSuggestion, ProductSeasonality, RetailEvent, SaveRetailEventInput, UpcomingEvent,
+};
+use crate::models::scheduled_post::{SaveScheduledPostInput, SavedScheduledPost};
+use crate::services::{post_schedule, post_timing, posting_caps, retail_events};
+use chrono::{NaiveTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use tauri::AppHandle;
+
+/// Default look-ahead for upcoming events and campaign suggestions
+const DEFAULT_UPCOMING_DAYS: i64 = 60;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_retail_events(app_handle: AppHandle) -> Result<Vec<RetailEvent>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    retail_events::list(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_retail_event(app_handle: AppHandle, input: SaveRetailEventInput) -> Result<RetailEvent, String> {
+    retail_events::validate(&input)?;
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    retail_events::save(&conn, &input)
+}
+
+/// Deletes a user-added event; shipped events can be disabled instead
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_retail_event(app_handle: AppHandle, event_id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let builtin: Option<bool> = conn
+        .query_row("SELECT builtin FROM retail_events WHERE id = ?1", params![event_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    match builtin {
+        None => Err(format!("Retail event {} not found", event_id)),
+        Some(true) => Err("Events shipped with the app can be edited or disabled but not deleted".to_string()),
+        Some(false) => {
+            conn.execute("DELETE FROM retail_events WHERE id = ?1", params![event_id])
+                .map_err(|e| e.to_string())?;
+            Ok(())
+        }
+    }
+}
+
+/// Enabled events in the next `days` days (default 60), soonest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_upcoming_events(app_handle: AppHandle, days: Option<i64>) -> Result<Vec<UpcomingEvent>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let events = retail_events::list(&conn).map_err(|e| e.to_string())?;
+    let today = Utc::now().date_naive();
+    Ok(retail_events::upcoming(&events, today, days.unwrap_or(DEFAULT_UPCOMING_DAYS).max(0)))
+}
+
+/// Upcoming events with the products worth promoting for each
+#[tauri::command]
+#[specta::specta]
+pub async fn get_campaign_suggestions(
+    app_handle: AppHandle,
+    days: Option<i64>,
+) -> Result<Vec<CampaignSuggestion>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let today = Utc::now().date_naive();
+    retail_events::campaign_suggestions(&conn, today, days.unwrap_or(DEFAULT_UPCOMING_DAYS).max(0))
+        .map_err(|e| e.to_string())
+}
+
+/// Seasonality of a product from the retail events its category or tags match
+#[tauri::command]
+#[specta::specta]
+pub async fn get_product_seasonality(
+    app_handle: AppHandle,
+    product_id: i64,
+) -> Result<ProductSeasonality, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let product = conn
+        .query_row(
+            &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
+            params![product_id],
+            product_from_row,
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Product {} not found", product_id))?;
+    let events = retail_events::list(&conn).map_err(|e| e.to_string())?;
+
+    let seasonality = retail_events::seasonality(&events, &product, Utc::now().date_naive());
+    Ok(ProductSeasonality {
+        product_id,
+        score: seasonality.as_ref().map_or(0.0, |(score, _)| *score),
+        event: seasonality.map(|(_, event)| event),
+    })
+}
+
+/// Puts an event campaign on the posting calendar: one post per product on
+/// `platform`, spread across the event's promotion window at each product's
+/// suggested time of day. Without `product_ids` the suggested products are
+/// used. Posts that conflict with enforced posting caps are left out.
+#[tauri::command]
+#[specta::specta]
+pub async fn schedule_event_campaign(
+    app_handle: AppHandle,
+    event_id: i64,
+    platform: String,
+    product_ids: Option<Vec<i64>>,
+) -> Result<Vec<SavedScheduledPost>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let event = retail_events::get(&conn, event_id).map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => format!("Retail event {} not found", event_id),
+        e => e.to_string(),
+    })?;
+    let today = Utc::now().date_naive();
+    let upcoming = retail_events::next_occurrence(&event, today)
+        .ok_or_else(|| format!("{} has no upcoming date", event.name))?;
+
+    let product_ids = match product_ids {
+        Some(ids) => ids,
+        None => retail_events::active_products(&conn)
+            .map_err(|e| e.to_string())?
+            .iter()
+            .filter(|p| retail_events::matches(&event, p))
+            .filter_map(|p| p.id)
+            .take(retail_events::MAX_CAMPAIGN_PRODUCTS)
+            .collect(),
+    };
+    if product_ids.is_empty() {
+        return Err(format!("No products to promote for {}", event.name));
+    }
+
+    let rules = posting_caps::load_rules(&conn);
+    let offset = get_setting_parsed(&conn, post_timing::UTC_OFFSET_SETTING, 0);
+    let mut saved = Vec::new();
+    for (product_id, day) in product_ids.iter().zip(retail_events::campaign_days(&upcoming, product_ids.len())) {
+        let time = post_timing::suggest(&conn, *product_id, &platform, offset)
+            .ok()
+            .and_then(|s| parse_timestamp(&s.best.scheduled_at))
+            .map(|at| at.time())
+            .unwrap_or_else(|| NaiveTime::from_hms_opt(18, 0, 0).unwrap_or_default());
+        let input = SaveScheduledPostInput {
+            id: None,
+            product_id: *product_id,
+            ad_id: None,
+            platform: platform.clone(),
+            scheduled_at: day.and_time(time).format("%Y-%m-%d %H:%M:%S").to_string(),
+            notes: Some(format!("{} campaign", event.name)),
+            repeat_every_days: None,
+            repeat_until: None,
+            rotate_ads: None,
+            allow_conflicts: None,
+        };
+        post_schedule::validate(&input)?;
+
+        let conflicts =
+            posting_caps::check(&conn, &rules, *product_id, &platform, &input.scheduled_at, None)
+                .map_err(|e| e.to_string())?;
+        if rules.enforce && !conflicts.is_empty() {
+            continue;
+        }
+        let post = post_schedule::save(&conn, &input).map_err(|e| e.to_string())?;
+        saved.push(SavedScheduledPost { post, conflicts });
+    }
+    Ok(saved)
+}
@@ -0,0 +1,70 @@
+use crate::database::get_connection;
+use crate::models::review::ReviewSummary;
+use crate::services::ai_client::{self, MOCK_PROVIDER};
+use crate::services::reviews::{
+    self, build_reviews_prompt, clean_reviews, keep_verbatim, mock_digest, validate_digest, REVIEWS_SCHEMA,
+};
+use crate::services::scraper::scrape_reviews;
+use crate::services::ai_discovery;
+use rusqlite::params;
+use tauri::AppHandle;
+
+/// Summarizes customer reviews into social-proof snippets and common objections,
+/// replacing the product's stored insights. Without pasted reviews, the reviews
+/// published on the product's page are scraped.
+#[tauri::command]
+#[specta::specta]
+pub async fn summarize_reviews(
+    app_handle: AppHandle,
+    product_id: i64,
+    reviews: Option<Vec<String>>,
+) -> Result<ReviewSummary, String> {
+    let (product_name, product_url) = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        conn.query_row(
+            "SELECT name, product_url FROM products WHERE id = ?1",
+            params![product_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+        )
+        .map_err(|e| format!("Product not found: {}", e))?
+    };
+
+    let reviews = match reviews.filter(|r| !r.is_empty()) {
+        Some(reviews) => reviews,
+        None => match product_url.filter(|u| !u.trim().is_empty()) {
+            Some(url) => scrape_reviews(&url).await?,
+            None => return Err("No reviews provided and the product has no URL to scrape".to_string()),
+        },
+    };
+    let reviews = clean_reviews(reviews);
+    if reviews.is_empty() {
+        return Err("No reviews to summarize".to_string());
+    }
+
+    let chain = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        ai_client::prepare_chain(&conn, &build_reviews_prompt(&product_name, &reviews))
+    };
+
+    let served = if chain.is_mock_only() {
+        None
+    } else {
+        ai_discovery::run_cached(&app_handle, &chain, validate_digest, REVIEWS_SCHEMA).await?
+    };
+    let (digest, served_by) = match served {
+        Some((digest, provider)) => (keep_verbatim(digest, &reviews), provider),
+        None => (mock_digest(&reviews), MOCK_PROVIDER.to_string()),
+    };
+
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    reviews::replace_insights(&mut conn, product_id, &digest, &served_by).map_err(|e| e.to_string())?;
+    reviews::load_summary(&conn, product_id, reviews.len()).map_err(|e| e.to_string())
+}
+
+/// Stored review snippets and objections for a product
+#[tauri::command]
+#[specta::specta]
+pub async fn get_review_insights(app_handle: AppHandle, product_id: i64) -> Result<ReviewSummary, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    reviews::load_summary(&conn, product_id, 0).map_err(|e| e.to_string())
+}
@@ -0,0 +1,40 @@
+use crate::database::get_connection;
+use crate::models::role::RoleStatus;
+use crate::services::roles;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_role_status(app_handle: AppHandle) -> Result<RoleStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    Ok(roles::status(&conn))
+}
+
+/// Unlocks owner-only commands for this session (30 minutes)
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock_owner(app_handle: AppHandle, password: String) -> Result<RoleStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    roles::unlock(&conn, &password)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn lock_owner(app_handle: AppHandle) -> Result<RoleStatus, String> {
+    roles::lock();
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    Ok(roles::status(&conn))
+}
+
+/// Sets or changes the owner password; an empty `new_password` removes it and
+/// turns role gating off. Changing or removing it needs `current_password`.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_owner_password(
+    app_handle: AppHandle,
+    current_password: Option<String>,
+    new_password: Option<String>,
+) -> Result<RoleStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    roles::set_password(&conn, current_password.as_deref(), new_password.as_deref())
+}
@@ -0,0 +1,85 @@
+use crate::database::{self, get_connection};
+use crate::models::shared_workspace::{SharedWorkspaceStatus, WorkspaceLocation, WorkspaceLock};
+use crate::services::shared_workspace;
+use rusqlite::params;
+use std::path::Path;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_shared_workspace_status(app_handle: AppHandle) -> Result<SharedWorkspaceStatus, String> {
+    Ok(shared_workspace::status(&database::app_dir(&app_handle)))
+}
+
+/// Points the workspace at a database on a shared drive (`database_path`),
+/// or back at the local one (`None`). With `copy_current` a new shared
+/// database starts as a copy of the one in use; an existing one is opened
+/// as-is and migrated. Takes the edit lock on the new database if it's free.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_shared_workspace(
+    app_handle: AppHandle,
+    database_path: Option<String>,
+    editor_name: Option<String>,
+    copy_current: Option<bool>,
+) -> Result<SharedWorkspaceStatus, String> {
+    let app_dir = database::app_dir(&app_handle);
+    let previous_path = database::database_path(&app_handle);
+    let location = WorkspaceLocation {
+        database_path: database_path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()),
+        editor_name: editor_name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+    };
+    let next_path = shared_workspace::database_path(&app_dir, &location);
+
+    if next_path != previous_path {
+        if let Some(path) = location.database_path.as_deref() {
+            let folder = Path::new(path).parent().filter(|p| !p.as_os_str().is_empty());
+            if folder.is_some_and(|f| !f.is_dir()) {
+                return Err(format!("Folder for {} doesn't exist or isn't reachable", path));
+            }
+            if copy_current.unwrap_or(false) {
+                if next_path.exists() {
+                    return Err(format!("{} already exists; open it without copying", path));
+                }
+                let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+                conn.execute("VACUUM INTO ?1", params![path])
+                    .map_err(|e| format!("Failed to copy the database: {}", e))?;
+            }
+        }
+        shared_workspace::release(&previous_path)?;
+        // Don't keep the previous database open from idle pooled connections
+        database::pool::clear();
+    }
+
+    shared_workspace::save_location(&app_dir, &location)?;
+    database::init_database(&app_handle).map_err(|e| format!("Failed to open {}: {}", next_path.display(), e))?;
+    if shared_workspace::is_shared(&location) {
+        if let Err(e) = shared_workspace::acquire(&next_path, &shared_workspace::editor_name(&location), false) {
+            eprintln!("Workspace lock not taken: {}", e);
+        }
+    }
+    Ok(shared_workspace::status(&app_dir))
+}
+
+/// Takes the edit lock on the shared database; `force` takes it over from a teammate
+#[tauri::command]
+#[specta::specta]
+pub async fn acquire_workspace_lock(app_handle: AppHandle, force: Option<bool>) -> Result<WorkspaceLock, String> {
+    let app_dir = database::app_dir(&app_handle);
+    let location = shared_workspace::load_location(&app_dir);
+    if !shared_workspace::is_shared(&location) {
+        return Err("The workspace uses a local database; there is nothing to lock".to_string());
+    }
+    shared_workspace::acquire(
+        &shared_workspace::database_path(&app_dir, &location),
+        &shared_workspace::editor_name(&location),
+        force.unwrap_or(false),
+    )
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn release_workspace_lock(app_handle: AppHandle) -> Result<SharedWorkspaceStatus, String> {
+    shared_workspace::release(&database::database_path(&app_handle))?;
+    Ok(shared_workspace::status(&database::app_dir(&app_handle)))
+}
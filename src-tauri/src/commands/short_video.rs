@@ -0,0 +1,71 @@
+use crate::commands::ad_generation::{ad_copy_from_row, generate_selling_points, GeneratedAdCopy, AD_COPY_COLUMNS};
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::database::get_connection;
+use crate::services::{data_events, research, short_video};
+use rusqlite::params;
+use tauri::AppHandle;
+
+/// (Re)builds the caption, on-screen text overlays, and sound note for a
+/// video_script ad and stores them under `short_video` in its platform data
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_video_companion(app_handle: AppHandle, ad_id: i64) -> Result<GeneratedAdCopy, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+
+    let ad = conn
+        .query_row(
+            &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+            params![ad_id],
+            ad_copy_from_row,
+        )
+        .map_err(|_| format!("Ad {} not found", ad_id))?;
+    if ad.ad_type.as_deref() != Some("video_script") {
+        return Err(format!("Ad {} is not a video script", ad_id));
+    }
+    let product_id = ad.product_id.ok_or("Ad is not linked to a product")?;
+
+    let product = conn
+        .query_row(
+            &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
+            params![product_id],
+            product_from_row,
+        )
+        .map_err(|e| format!("Product not found: {}", e))?;
+    let selling_points = research::latest_for_product(&conn, product_id)
+        .map_err(|e| e.to_string())?
+        .map(|r| r.brief.key_benefits)
+        .filter(|b| !b.is_empty())
+        .unwrap_or_else(|| generate_selling_points(&product.category, &product.name));
+
+    let companion = short_video::build_companion(
+        &product,
+        &ad.headline,
+        ad.body_text.as_deref().unwrap_or(""),
+        &selling_points,
+    );
+
+    let mut data: serde_json::Value = ad
+        .platform_specific_data
+        .as_deref()
+        .and_then(|d| serde_json::from_str(d).ok())
+        .filter(|d: &serde_json::Value| d.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    data["short_video"] = serde_json::to_value(&companion).map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE ad_copies SET platform_specific_data = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        params![data.to_string(), ad_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let ad = conn
+        .query_row(
+            &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+            params![ad_id],
+            ad_copy_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+
+    data_events::emit(&app_handle, data_events::ADS_CHANGED, data_events::UPDATED, vec![ad_id]);
+    Ok(ad)
+}
@@ -0,0 +1,82 @@
+use crate::commands::ad_generation::{ad_copy_from_row, AD_COPY_COLUMNS};
+use crate::database::get_connection;
+use crate::models::sms::SmsComplianceReport;
+use crate::services::ad_links;
+use crate::services::sms_compliance::{ensure_opt_out, load_quiet_hours, parse_send_at, segment_info};
+use rusqlite::{params, Connection};
+use tauri::AppHandle;
+
+/// Messages longer than this many segments get a cost warning
+const SEGMENT_WARNING: usize = 2;
+
+/// Builds the sendable body of an SMS ad ([LINK] replaced by the ad's
+/// affiliate link, opt-out enforced) and checks it against the rules.
+/// `issues` that block export: unresolved [LINK] and sends inside quiet hours.
+pub(crate) fn compliance_report(
+    conn: &Connection,
+    ad_id: i64,
+    send_at: Option<&str>,
+) -> Result<SmsComplianceReport, String> {
+    let ad = conn
+        .query_row(
+            &format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS),
+            params![ad_id],
+            ad_copy_from_row,
+        )
+        .map_err(|_| format!("Ad {} not found", ad_id))?;
+    if ad.ad_type.as_deref() != Some("sms") {
+        return Err(format!("Ad {} is not an SMS ad", ad_id));
+    }
+
+    let mut issues = Vec::new();
+    let mut body = ad.body_text.clone().unwrap_or_default();
+    if body.contains("[LINK]") {
+        let link = ad_links::tracking_url(conn, ad.product_id, ad.link_id).map_err(|e| e.to_string())?;
+        match link {
+            Some(url) => body = body.replace("[LINK]", &url),
+            None => issues.push("No active affiliate link to replace [LINK]".to_string()),
+        }
+    }
+
+    let (body, opt_out_added) = ensure_opt_out(&body);
+    let segments = segment_info(&body);
+    if segments.segments > SEGMENT_WARNING {
+        issues.push(format!(
+            "Message spans {} segments ({} {} characters); each segment is billed",
+            segments.segments, segments.characters, segments.encoding
+        ));
+    }
+
+    let quiet_hours = load_quiet_hours(conn)?;
+    let send_at = send_at.map(parse_send_at).transpose()?;
+    let within_quiet_hours = send_at.map(|dt| quiet_hours.contains(dt)).unwrap_or(false);
+    if within_quiet_hours {
+        issues.push(format!(
+            "Send time falls in quiet hours ({:02}:00-{:02}:00 {})",
+            quiet_hours.start_hour, quiet_hours.end_hour, quiet_hours.timezone
+        ));
+    }
+
+    Ok(SmsComplianceReport {
+        ad_id,
+        body,
+        opt_out_added,
+        segments,
+        timezone: quiet_hours.timezone.to_string(),
+        send_at: send_at.map(|dt| dt.to_rfc3339()),
+        within_quiet_hours,
+        issues,
+    })
+}
+
+/// Checks an SMS ad for opt-out text, segment count, and quiet hours at `send_at` (RFC 3339)
+#[tauri::command]
+#[specta::specta]
+pub async fn check_sms_compliance(
+    app_handle: AppHandle,
+    ad_id: i64,
+    send_at: Option<String>,
+) -> Result<SmsComplianceReport, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    compliance_report(&conn, ad_id, send_at.as_deref())
+}
@@ -0,0 +1,42 @@
+use crate::database::get_connection;
+use crate::models::spreadsheet_import::{ImportAnalysis, ImportPlan, ImportResult};
+use crate::services::{data_events, shared_workspace, spreadsheet_import};
+use std::path::Path;
+use tauri::AppHandle;
+
+/// Reads a spreadsheet export and suggests how its columns map onto
+/// products, links, or earnings, with a few rows to preview against
+#[tauri::command]
+#[specta::specta]
+pub async fn analyze_import_file(path: String) -> Result<ImportAnalysis, String> {
+    let path = path.trim();
+    let sheet = spreadsheet_import::read_sheet(Path::new(path))?;
+    if sheet.rows.is_empty() {
+        return Err("The file has a header row but no data".to_string());
+    }
+    Ok(spreadsheet_import::analyze(&sheet, path))
+}
+
+/// Imports the file with a reviewed mapping. Rows that can't be imported are
+/// skipped and listed in the result; nothing is written if the plan itself
+/// is invalid.
+#[tauri::command]
+#[specta::specta]
+pub async fn execute_import(app_handle: AppHandle, plan: ImportPlan) -> Result<ImportResult, String> {
+    let sheet = spreadsheet_import::read_sheet(Path::new(plan.path.trim()))?;
+    let editor = shared_workspace::current_editor(&app_handle);
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let result = spreadsheet_import::execute(&mut conn, &sheet, &plan, Some(&editor))?;
+
+    let product_ids = match result.target.as_str() {
+        "products" => result.imported_ids.clone(),
+        _ => result.created_product_ids.clone(),
+    };
+    if !product_ids.is_empty() {
+        data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::CREATED, product_ids);
+    }
+    if result.target == "links" && result.imported > 0 {
+        data_events::emit(&app_handle, data_events::LINKS_CHANGED, data_events::CREATED, result.imported_ids.clone());
+    }
+    Ok(result)
+}
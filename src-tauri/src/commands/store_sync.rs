@@ -0,0 +1,54 @@
+use crate::database::get_connection;
+use crate::models::store_sync::{StoreSkuMapping, StoreSyncReport};
+use crate::services::{shared_workspace, store_sync};
+use tauri::AppHandle;
+
+/// Imports a connected store's products: unmapped SKUs are mapped to the
+/// product of the same name, or a product is created for them
+#[tauri::command]
+#[specta::specta]
+pub async fn import_store_products(app_handle: AppHandle, store: String) -> Result<StoreSyncReport, String> {
+    let editor = shared_workspace::current_editor(&app_handle);
+    store_sync::sync_store(&app_handle, &store.to_lowercase(), true, Some(&editor)).await
+}
+
+/// Syncs prices and images of mapped SKUs now instead of waiting for the scheduled job
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_store_now(app_handle: AppHandle, store: String) -> Result<StoreSyncReport, String> {
+    store_sync::sync_store(&app_handle, &store.to_lowercase(), false, None).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_store_sku_mappings(
+    app_handle: AppHandle,
+    store: Option<String>,
+) -> Result<Vec<StoreSkuMapping>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    store_sync::mappings(&conn, store.map(|s| s.to_lowercase()).as_deref()).map_err(|e| e.to_string())
+}
+
+/// Points a store SKU at a catalog product, e.g. when the import matched the wrong one
+#[tauri::command]
+#[specta::specta]
+pub async fn map_store_sku(
+    app_handle: AppHandle,
+    store: String,
+    sku: String,
+    product_id: i64,
+) -> Result<Vec<StoreSkuMapping>, String> {
+    let store = store.to_lowercase();
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    store_sync::map_sku(&conn, &store, &sku, product_id)?;
+    store_sync::mappings(&conn, Some(&store)).map_err(|e| e.to_string())
+}
+
+/// Stops syncing a SKU; its product stays in the catalog
+#[tauri::command]
+#[specta::specta]
+pub async fn unmap_store_sku(app_handle: AppHandle, mapping_id: i64) -> Result<Vec<StoreSkuMapping>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    store_sync::unmap_sku(&conn, mapping_id).map_err(|e| e.to_string())?;
+    store_sync::mappings(&conn, None).map_err(|e| e.to_string())
+}
@@ -0,0 +1,41 @@
+use crate::database::get_connection;
+use crate::models::style_rules::StyleRules;
+use crate::services::style_rules::{self, STYLE_PLATFORMS};
+use rusqlite::params;
+use tauri::AppHandle;
+
+/// Effective style rules for every platform (saved overrides or defaults)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_style_rules(app_handle: AppHandle) -> Result<Vec<StyleRules>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    Ok(STYLE_PLATFORMS
+        .iter()
+        .map(|platform| style_rules::load_rules(&conn, platform))
+        .collect())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_style_rules(app_handle: AppHandle, rules: StyleRules) -> Result<StyleRules, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let rules = StyleRules {
+        platform: rules.platform.trim().to_lowercase(),
+        ..rules
+    };
+    style_rules::save_rules(&conn, &rules)?;
+    Ok(style_rules::load_rules(&conn, &rules.platform))
+}
+
+/// Drops a platform's saved rules and returns its defaults
+#[tauri::command]
+#[specta::specta]
+pub async fn reset_style_rules(app_handle: AppHandle, platform: String) -> Result<StyleRules, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    conn.execute(
+        "DELETE FROM settings WHERE key = ?1",
+        params![format!("style_rules:{}", platform)],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(style_rules::default_rules(&platform))
+}
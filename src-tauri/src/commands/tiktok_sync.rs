@@ -0,0 +1,29 @@
+use crate::database::get_connection;
+use crate::database::settings::set_setting;
+use crate::models::tiktok_sync::TikTokSyncReport;
+use crate::services::{data_events, roles, shared_workspace, tiktok_sync};
+use tauri::AppHandle;
+
+/// Pulls the TikTok shop's products (or the creator's showcase when no shop
+/// is connected) and fills in `tiktok_product_id`: existing products are
+/// matched by name, anything unmatched becomes a new product
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_tiktok_products(app_handle: AppHandle) -> Result<TikTokSyncReport, String> {
+    let editor = shared_workspace::current_editor(&app_handle);
+    let report = tiktok_sync::sync(&app_handle, Some(&editor)).await?;
+    let (created, linked) = (report.created_product_ids.clone(), report.linked_product_ids.clone());
+    data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::CREATED, created);
+    data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::UPDATED, linked);
+    Ok(report)
+}
+
+/// Stores the access token from authorizing the TikTok Shop app. Owner only,
+/// like the credentials it pairs with.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_tiktok_access_token(app_handle: AppHandle, token: String) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    roles::require_owner(&conn, "save_tiktok_access_token")?;
+    set_setting(&conn, tiktok_sync::ACCESS_TOKEN_SETTING, token.trim()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,28 @@
+use crate::database::get_connection;
+use crate::models::variation::{NextVariation, RecordVariationPerformanceInput};
+use crate::services::variation_bandit;
+use tauri::AppHandle;
+
+/// Records an ad's impressions and clicks on a platform for one day
+#[tauri::command]
+#[specta::specta]
+pub async fn record_variation_performance(
+    app_handle: AppHandle,
+    input: RecordVariationPerformanceInput,
+) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    variation_bandit::record(&conn, &input)
+}
+
+/// Which of a product's ads to post next on a platform, by Thompson sampling
+/// over their recorded click-through rates
+#[tauri::command]
+#[specta::specta]
+pub async fn get_next_variation(
+    app_handle: AppHandle,
+    product_id: i64,
+    platform: String,
+) -> Result<NextVariation, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    variation_bandit::next_variation(&conn, product_id, &platform)
+}
@@ -0,0 +1,47 @@
+use crate::database::get_connection;
+use crate::models::watchlist::{ProductChange, WatchCheckReport, WatchedProduct};
+use crate::services::watchlist;
+use tauri::AppHandle;
+
+/// Adds a product to the watchlist; it needs a product URL to re-scrape
+#[tauri::command]
+#[specta::specta]
+pub async fn watch_product(app_handle: AppHandle, product_id: i64) -> Result<Vec<WatchedProduct>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    watchlist::watch(&conn, product_id)?;
+    watchlist::list(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn unwatch_product(app_handle: AppHandle, product_id: i64) -> Result<Vec<WatchedProduct>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    watchlist::unwatch(&conn, product_id).map_err(|e| e.to_string())?;
+    watchlist::list(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_watchlist(app_handle: AppHandle) -> Result<Vec<WatchedProduct>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    watchlist::list(&conn).map_err(|e| e.to_string())
+}
+
+/// Logged title, price, availability, and store image changes, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_product_changes(
+    app_handle: AppHandle,
+    product_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<ProductChange>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    watchlist::changes(&conn, product_id, 0, limit.unwrap_or(100)).map_err(|e| e.to_string())
+}
+
+/// Checks every watched product now instead of waiting for the scheduled job
+#[tauri::command]
+#[specta::specta]
+pub async fn check_watchlist_now(app_handle: AppHandle) -> Result<WatchCheckReport, String> {
+    watchlist::check_all(&app_handle).await
+}
@@ -0,0 +1,117 @@
+use crate::database::get_connection;
+use crate::models::webhook::{SaveWebhookInput, Webhook, WebhookDelivery};
+use crate::services::webhooks::{self, delivery_from_row, webhook_from_row, TEST_EVENT, WEBHOOK_COLUMNS};
+use rusqlite::params;
+use serde_json::json;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_webhooks(app_handle: AppHandle) -> Result<Vec<Webhook>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    webhooks::list(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_webhook(app_handle: AppHandle, input: SaveWebhookInput) -> Result<Webhook, String> {
+    webhooks::validate(&input)?;
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let events = serde_json::to_string(&input.events).map_err(|e| e.to_string())?;
+    let secret = input.secret.as_deref().map(str::trim).filter(|s| !s.is_empty());
+
+    let id = match input.id {
+        Some(id) => {
+            let updated = conn
+                .execute(
+                    "UPDATE webhooks SET name = ?1, url = ?2, secret = ?3, events = ?4, active = ?5,
+                     updated_at = CURRENT_TIMESTAMP
+                     WHERE id = ?6",
+                    params![input.name.trim(), input.url.trim(), secret, events, input.active, id],
+                )
+                .map_err(|e| e.to_string())?;
+            if updated == 0 {
+                return Err(format!("Webhook {} not found", id));
+            }
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO webhooks (name, url, secret, events, active) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![input.name.trim(), input.url.trim(), secret, events, input.active],
+            )
+            .map_err(|e| e.to_string())?;
+            conn.last_insert_rowid()
+        }
+    };
+
+    conn.query_row(
+        &format!("SELECT {} FROM webhooks WHERE id = ?1", WEBHOOK_COLUMNS),
+        params![id],
+        webhook_from_row,
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_webhook(app_handle: AppHandle, webhook_id: i64) -> Result<(), String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM webhook_deliveries WHERE webhook_id = ?1", params![webhook_id])
+        .map_err(|e| e.to_string())?;
+    let deleted = conn
+        .execute("DELETE FROM webhooks WHERE id = ?1", params![webhook_id])
+        .map_err(|e| e.to_string())?;
+    if deleted == 0 {
+        return Err(format!("Webhook {} not found", webhook_id));
+    }
+    Ok(())
+}
+
+/// Sends a `test` event to a webhook and waits for the result
+#[tauri::command]
+#[specta::specta]
+pub async fn test_webhook(app_handle: AppHandle, webhook_id: i64) -> Result<WebhookDelivery, String> {
+    let webhook = {
+        let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+        conn.query_row(
+            &format!("SELECT {} FROM webhooks WHERE id = ?1", WEBHOOK_COLUMNS),
+            params![webhook_id],
+            webhook_from_row,
+        )
+        .map_err(|_| format!("Webhook {} not found", webhook_id))?
+    };
+
+    let body = webhooks::payload(TEST_EVENT, &json!({ "message": "Test delivery from AffilAI" }));
+    let result = webhooks::deliver(&webhook, TEST_EVENT, &body).await;
+
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    webhooks::log_delivery(&conn, webhook_id, TEST_EVENT, &result).map_err(|e| e.to_string())
+}
+
+/// Recent delivery attempts, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_webhook_deliveries(
+    app_handle: AppHandle,
+    webhook_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<WebhookDelivery>, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, webhook_id, event, status_code, success, error, delivered_at
+             FROM webhook_deliveries
+             WHERE (?1 IS NULL OR webhook_id = ?1)
+             ORDER BY delivered_at DESC, id DESC
+             LIMIT ?2",
+        )
+        .map_err(|e| e.to_string())?;
+    let deliveries = stmt
+        .query_map(params![webhook_id, limit.unwrap_or(50)], delivery_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(deliveries)
+}
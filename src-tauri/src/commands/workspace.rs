@@ -0,0 +1,86 @@
+use crate::database::{get_connection, seed};
+use crate::models::read_only::ReadOnlyStatus;
+use crate::models::workspace::{DemoDataSummary, SeedResult, SeedStatus, WorkspaceReset};
+use crate::services::{data_events, demo_data, read_only, roles, workspace};
+use tauri::AppHandle;
+
+/// Adds the demo sandbox (50 products with links, ads, and 90 days of clicks
+/// and conversions) alongside whatever is already in the workspace
+#[tauri::command]
+#[specta::specta]
+pub async fn load_demo_data(app_handle: AppHandle) -> Result<DemoDataSummary, String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let loaded = demo_data::load(&mut conn).map_err(|e| e.to_string())?;
+
+    data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::CREATED, loaded.product_ids);
+    data_events::emit(&app_handle, data_events::LINKS_CHANGED, data_events::CREATED, loaded.link_ids);
+    data_events::emit(&app_handle, data_events::ADS_CHANGED, data_events::CREATED, loaded.ad_ids);
+    Ok(loaded.summary)
+}
+
+/// Deletes all products, links, ads, traffic, and AI output; configuration
+/// (settings, credentials, templates, webhooks) is kept
+#[tauri::command]
+#[specta::specta]
+pub async fn reset_workspace(app_handle: AppHandle) -> Result<WorkspaceReset, String> {
+    let mut conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    roles::require_owner(&conn, "reset_workspace")?;
+    let deleted = workspace::entity_rows(&conn).map_err(|e| e.to_string())?;
+    let reset = workspace::reset(&mut conn).map_err(|e| e.to_string())?;
+
+    data_events::emit_deleted_rows(&app_handle, deleted);
+    Ok(reset)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_seed_status(app_handle: AppHandle) -> Result<SeedStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    seed::status(&conn).map_err(|e| e.to_string())
+}
+
+/// Answers the first-run prompt: a blank workspace, or one with the starter products
+#[tauri::command]
+#[specta::specta]
+pub async fn choose_starting_workspace(app_handle: AppHandle, seeded: bool) -> Result<SeedResult, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let result = seed::choose_starting_workspace(&conn, seeded).map_err(|e| e.to_string())?;
+    data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::CREATED, result.product_ids.clone());
+    Ok(result)
+}
+
+/// Adds starter products missing from the workspace. Without `force` only a
+/// newer seed pack is applied; with it, deleted starter products come back.
+#[tauri::command]
+#[specta::specta]
+pub async fn reseed_products(app_handle: AppHandle, force: Option<bool>) -> Result<SeedResult, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    let result = seed::reseed_products(&conn, force.unwrap_or(false)).map_err(|e| e.to_string())?;
+    data_events::emit(&app_handle, data_events::PRODUCTS_CHANGED, data_events::CREATED, result.product_ids.clone());
+    Ok(result)
+}
+
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_read_only_status(app_handle: AppHandle) -> Result<ReadOnlyStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    Ok(read_only::status(&conn))
+}
+
+/// Turns read-only on (e.g. while presenting or after opening a backup) or
+/// off. While it's on every command that would change the workspace fails
+/// with a `read_only` error; turning it off is owner-only.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_workspace_read_only(
+    app_handle: AppHandle,
+    enabled: bool,
+    reason: Option<String>,
+) -> Result<ReadOnlyStatus, String> {
+    let conn = get_connection(&app_handle).map_err(|e| e.to_string())?;
+    if !enabled {
+        roles::require_owner(&conn, "set_workspace_read_only")?;
+    }
+    read_only::set(&conn, enabled, reason.as_deref())
+}
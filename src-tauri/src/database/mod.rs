@@ -1,19 +1,31 @@
+use crate::services::{query_profiler, shared_workspace};
+use pool::PooledConnection;
 use rusqlite::{Connection, Result};
+use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
 
+pub mod pool;
 pub mod schema;
+pub mod seed;
+pub mod settings;
 
 pub fn init_database(app_handle: &AppHandle) -> Result<Connection> {
-    let app_dir = app_handle
-        .path()
-        .app_data_dir()
-        .expect("Failed to get app data directory");
+    let app_dir = app_dir(app_handle);
 
     // Create app directory if it doesn't exist
     std::fs::create_dir_all(&app_dir).expect("Failed to create app directory");
 
-    let db_path = app_dir.join("affilai.db");
-    let conn = Connection::open(&db_path)?;
+    let location = shared_workspace::load_location(&app_dir);
+    let conn = Connection::open(shared_workspace::database_path(&app_dir, &location))?;
+
+    if shared_workspace::is_shared(&location) {
+        // WAL needs shared memory on one machine; a database on a shared
+        // drive uses a rollback journal so every client sees the same state
+        conn.execute_batch("PRAGMA journal_mode = DELETE;")?;
+    } else {
+        // WAL lets readers proceed while a bulk operation is writing
+        conn.execute_batch("PRAGMA journal_mode = WAL;")?;
+    }
 
     // Run migrations
     schema::run_migrations(&conn)?;
@@ -21,12 +33,79 @@ pub fn init_database(app_handle: &AppHandle) -> Result<Connection> {
     Ok(conn)
 }
 
-pub fn get_connection(app_handle: &AppHandle) -> Result<Connection> {
-    let app_dir = app_handle
+pub fn app_dir(app_handle: &AppHandle) -> PathBuf {
+    app_handle
         .path()
         .app_data_dir()
-        .expect("Failed to get app data directory");
+        .expect("Failed to get app data directory")
+}
+
+/// The database in use: the local one, or a shared one the user pointed the workspace at
+pub fn database_path(app_handle: &AppHandle) -> PathBuf {
+    let app_dir = app_dir(app_handle);
+    shared_workspace::database_path(&app_dir, &shared_workspace::load_location(&app_dir))
+}
+
+/// A connection to the workspace database, reused from the pool when one is idle
+pub fn get_connection(app_handle: &AppHandle) -> Result<PooledConnection> {
+    let app_dir = app_dir(app_handle);
+    let location = shared_workspace::load_location(&app_dir);
+    let path = shared_workspace::database_path(&app_dir, &location);
+    if let Some(conn) = pool::checkout(&path) {
+        return Ok(PooledConnection::new(path, conn));
+    }
+
+    let mut conn = Connection::open(&path)?;
+    conn.profile(Some(query_profiler::record));
+    conn.set_prepared_statement_cache_capacity(pool::STATEMENT_CACHE_CAPACITY);
+    // Bulk operations write from several tasks at once, and teammates may be
+    // writing to a shared database; wait for locks instead of failing
+    let timeout = if shared_workspace::is_shared(&location) { 30 } else { 5 };
+    conn.busy_timeout(std::time::Duration::from_secs(timeout))?;
+    Ok(PooledConnection::new(path, conn))
+}
+
+/// Tauri bundle identifier; the GUI keeps its database under `<data dir>/<identifier>`
+#[cfg(feature = "cli")]
+const APP_IDENTIFIER: &str = "com.ryan.affilai-temp";
+
+/// Database location used by the desktop app, resolved without an `AppHandle`
+/// (same rules as Tauri's `app_data_dir`, including a configured shared database)
+#[cfg(feature = "cli")]
+pub fn default_db_path() -> Option<PathBuf> {
+    use std::env::var_os;
+
+    let data_dir = if cfg!(target_os = "windows") {
+        var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    }?;
+    let app_dir = data_dir.join(APP_IDENTIFIER);
+    Some(shared_workspace::database_path(&app_dir, &shared_workspace::load_location(&app_dir)))
+}
+
+/// Opens an existing database outside the Tauri runtime. Migrations are left
+/// to the app, which may be running against the same file.
+#[cfg(feature = "cli")]
+pub fn open_at(path: &std::path::Path) -> Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.busy_timeout(std::time::Duration::from_secs(5))?;
+    Ok(conn)
+}
 
-    let db_path = app_dir.join("affilai.db");
-    Connection::open(&db_path)
+/// Parses a timestamp as stored by SQLite's CURRENT_TIMESTAMP ("YYYY-MM-DD HH:MM:SS"),
+/// also accepting ISO-8601/RFC 3339 variants written by the frontend.
+pub fn parse_timestamp(value: &str) -> Option<chrono::NaiveDateTime> {
+    chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f"))
+        .ok()
+        .or_else(|| {
+            chrono::DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|dt| dt.naive_utc())
+        })
 }
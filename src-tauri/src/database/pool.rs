@@ -0,0 +1,77 @@
+//! Connection reuse
+//!
+//! Commands used to open a fresh connection per call, which threw away every
+//! prepared statement. Connections are now returned to a small idle pool when
+//! the command's handle drops, so rusqlite's statement cache survives between
+//! invokes and `prepare_cached` on hot paths skips re-parsing the SQL.
+
+use rusqlite::Connection;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// Idle connections kept; more can be open at once, extras are closed on drop
+const MAX_IDLE: usize = 4;
+/// Prepared statements cached per connection
+pub const STATEMENT_CACHE_CAPACITY: usize = 64;
+
+fn idle() -> &'static Mutex<Vec<(PathBuf, Connection)>> {
+    static IDLE: OnceLock<Mutex<Vec<(PathBuf, Connection)>>> = OnceLock::new();
+    IDLE.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// An idle connection to `path`. Connections to any other database (after
+/// switching to or from a shared workspace) are closed.
+pub fn checkout(path: &Path) -> Option<Connection> {
+    let mut idle = idle().lock().ok()?;
+    idle.retain(|(p, _)| p == path);
+    idle.pop().map(|(_, conn)| conn)
+}
+
+/// Closes every idle connection, e.g. before the database file is replaced
+pub fn clear() {
+    if let Ok(mut idle) = idle().lock() {
+        idle.clear();
+    }
+}
+
+/// A connection from `get_connection`; goes back to the pool when dropped
+pub struct PooledConnection {
+    path: PathBuf,
+    conn: Option<Connection>,
+}
+
+impl PooledConnection {
+    pub fn new(path: PathBuf, conn: Connection) -> Self {
+        Self { path, conn: Some(conn) }
+    }
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection already returned")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connection already returned")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        let Some(conn) = self.conn.take() else { return };
+        // A connection left inside a transaction would leak it to the next caller
+        if !conn.is_autocommit() {
+            return;
+        }
+        if let Ok(mut idle) = idle().lock() {
+            if idle.len() < MAX_IDLE {
+                idle.push((std::mem::take(&mut self.path), conn));
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::seed;
 use rusqlite::{Connection, Result};
 
 /// Helper function to add a column if it doesn't already exist
@@ -31,15 +32,6 @@ fn add_column_if_not_exists(
 }
 
 pub fn run_migrations(conn: &Connection) -> Result<()> {
-    // Check if migrations have been run before
-    let migrations_table_exists: bool = conn
-        .query_row(
-            "SELECT name FROM sqlite_master WHERE type='table' AND name='settings'",
-            [],
-            |_| Ok(true),
-        )
-        .unwrap_or(false);
-
     // Run initial schema migration
     let schema_sql = include_str!("../../../migrations/001_initial_schema.sql");
     conn.execute_batch(schema_sql)?;
@@ -75,36 +67,290 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
     conn.execute_batch("CREATE INDEX IF NOT EXISTS idx_ad_copies_ad_type ON ad_copies(ad_type);")?;
     println!("✓ Ad copies product FK migration completed");
 
-    // Check if seed data has been run
-    if migrations_table_exists {
-        let seed_run: bool = conn
-            .query_row(
-                "SELECT value FROM settings WHERE key = 'seed_data_run'",
-                [],
-                |row| row.get(0),
-            )
-            .unwrap_or(String::from("false")) == "true";
-
-        if seed_run {
-            println!("✓ Seed data already populated");
-            // Still need to ensure default campaign exists
-            ensure_default_campaign(conn)?;
-            return Ok(());
-        }
-    }
+    // Run conversion postbacks migration (008)
+    let postbacks_sql = include_str!("../../../migrations/008_conversion_postbacks.sql");
+    conn.execute_batch(postbacks_sql)?;
+    add_column_if_not_exists(conn, "conversion_events", "source", "TEXT DEFAULT 'manual'")?;
+    add_column_if_not_exists(conn, "conversion_events", "subid", "TEXT")?;
+    println!("✓ Conversion postbacks migration completed");
+
+    // Run traffic sessions migration (009)
+    let traffic_sql = include_str!("../../../migrations/009_traffic_sessions.sql");
+    conn.execute_batch(traffic_sql)?;
+    println!("✓ Traffic sessions migration completed");
+
+    // Run digests migration (010)
+    let digests_sql = include_str!("../../../migrations/010_digests.sql");
+    conn.execute_batch(digests_sql)?;
+    println!("✓ Digests migration completed");
+
+    // Run product lifecycle stages migration (011) - add columns with existence checks
+    add_column_if_not_exists(conn, "products", "stage", "TEXT DEFAULT 'researching'")?;
+    add_column_if_not_exists(conn, "products", "stage_changed_at", "DATETIME")?;
+    conn.execute_batch("CREATE INDEX IF NOT EXISTS idx_products_stage ON products(stage);")?;
+    conn.execute_batch("UPDATE products SET stage = 'researching' WHERE stage IS NULL;")?;
+    println!("✓ Product lifecycle stages migration completed");
+
+    // Run niche profiles migration (012)
+    let niche_sql = include_str!("../../../migrations/012_niche_profiles.sql");
+    conn.execute_batch(niche_sql)?;
+    println!("✓ Niche profiles migration completed");
+
+    // Run ideas inbox migration (013)
+    let ideas_sql = include_str!("../../../migrations/013_ideas.sql");
+    conn.execute_batch(ideas_sql)?;
+    println!("✓ Ideas inbox migration completed");
+
+    // Run ad favorites migration (014) - add column with existence check
+    add_column_if_not_exists(conn, "ad_copies", "is_favorite", "INTEGER DEFAULT 0")?;
+    println!("✓ Ad favorites migration completed");
+
+    // Run product tags migration (015) - add column with existence check
+    add_column_if_not_exists(conn, "products", "tags", "TEXT DEFAULT '[]'")?;
+    println!("✓ Product tags migration completed");
+
+    // Run bulk operations migration (016)
+    let bulk_sql = include_str!("../../../migrations/016_bulk_operations.sql");
+    conn.execute_batch(bulk_sql)?;
+    println!("✓ Bulk operations migration completed");
+
+    // Run AI response cache migration (017)
+    let ai_cache_sql = include_str!("../../../migrations/017_ai_cache.sql");
+    conn.execute_batch(ai_cache_sql)?;
+    println!("✓ AI cache migration completed");
+
+    // Run AI discovery log migration (018)
+    let ai_discoveries_sql = include_str!("../../../migrations/018_ai_discoveries.sql");
+    conn.execute_batch(ai_discoveries_sql)?;
+    println!("✓ AI discovery log migration completed");
+
+    // Run AI provider fallback migration (019) - add columns with existence checks
+    add_column_if_not_exists(conn, "ai_discoveries", "served_by", "TEXT")?;
+    add_column_if_not_exists(conn, "ai_discoveries", "fallback_errors_json", "TEXT DEFAULT '[]'")?;
+    println!("✓ AI provider fallback migration completed");
+
+    // Run product research migration (020)
+    let research_sql = include_str!("../../../migrations/020_product_research.sql");
+    conn.execute_batch(research_sql)?;
+    println!("✓ Product research migration completed");
+
+    // Run content angles migration (021)
+    let angles_sql = include_str!("../../../migrations/021_content_angles.sql");
+    conn.execute_batch(angles_sql)?;
+    println!("✓ Content angles migration completed");
+
+    // Run review insights migration (022)
+    let review_insights_sql = include_str!("../../../migrations/022_review_insights.sql");
+    conn.execute_batch(review_insights_sql)?;
+    println!("✓ Review insights migration completed");
+
+    // Run email sequences migration (023) - add columns with existence check
+    add_column_if_not_exists(
+        conn,
+        "ad_copies",
+        "parent_ad_id",
+        "INTEGER REFERENCES ad_copies(id) ON DELETE CASCADE",
+    )?;
+    add_column_if_not_exists(conn, "ad_copies", "sequence_step", "INTEGER")?;
+    add_column_if_not_exists(conn, "ad_copies", "send_delay_days", "INTEGER")?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_ad_copies_parent ON ad_copies(parent_ad_id, sequence_step);",
+    )?;
+    println!("✓ Email sequences migration completed");
+
+    // Run ad alt text migration (024) - add column with existence check
+    add_column_if_not_exists(conn, "ad_copies", "alt_text", "TEXT")?;
+    println!("✓ Ad alt text migration completed");
+
+    // Run ad critiques migration (025)
+    let critiques_sql = include_str!("../../../migrations/025_ad_critiques.sql");
+    conn.execute_batch(critiques_sql)?;
+    println!("✓ Ad critiques migration completed");
+
+    // Run headline variants migration (026)
+    let headline_variants_sql = include_str!("../../../migrations/026_headline_variants.sql");
+    conn.execute_batch(headline_variants_sql)?;
+    println!("✓ Headline variants migration completed");
+
+    // Run ad templates migration (027)
+    let ad_templates_sql = include_str!("../../../migrations/027_ad_templates.sql");
+    conn.execute_batch(ad_templates_sql)?;
+    println!("✓ Ad templates migration completed");
+
+    // Run webhooks migration (028)
+    let webhooks_sql = include_str!("../../../migrations/028_webhooks.sql");
+    conn.execute_batch(webhooks_sql)?;
+    println!("✓ Webhooks migration completed");
+
+    // Run click sources migration (029) - add columns with existence check
+    add_column_if_not_exists(conn, "click_events", "utm_source", "TEXT")?;
+    add_column_if_not_exists(conn, "click_events", "source", "TEXT")?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_click_events_source ON click_events(link_id, source);",
+    )?;
+    println!("✓ Click sources migration completed");
+
+    // Run click geo migration (030) - add columns with existence check
+    add_column_if_not_exists(conn, "click_events", "country", "TEXT")?;
+    add_column_if_not_exists(conn, "click_events", "region", "TEXT")?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_click_events_country ON click_events(link_id, country);",
+    )?;
+    println!("✓ Click geo migration completed");
+
+    // Run click bot filtering migration (031) - add column with existence check
+    add_column_if_not_exists(conn, "click_events", "bot_reason", "TEXT")?;
+    println!("✓ Click bot filtering migration completed");
+
+    // Run program applications migration (032)
+    let program_applications_sql = include_str!("../../../migrations/032_program_applications.sql");
+    conn.execute_batch(program_applications_sql)?;
+    println!("✓ Program applications migration completed");
+
+    // Run network terms migration (033)
+    let network_terms_sql = include_str!("../../../migrations/033_network_terms.sql");
+    conn.execute_batch(network_terms_sql)?;
+    println!("✓ Network terms migration completed");
+
+    // Run program deep links migration (034) - add columns with existence check
+    add_column_if_not_exists(conn, "affiliate_programs", "network", "TEXT")?;
+    add_column_if_not_exists(conn, "affiliate_programs", "merchant_id", "TEXT")?;
+    add_column_if_not_exists(conn, "affiliate_programs", "ad_id", "TEXT")?;
+    add_column_if_not_exists(conn, "affiliate_programs", "tracking_domain", "TEXT")?;
+    println!("✓ Program deep links migration completed");
+
+    // Run ad links migration (035) - add column with existence check
+    add_column_if_not_exists(
+        conn,
+        "ad_copies",
+        "link_id",
+        "INTEGER REFERENCES affiliate_links(id) ON DELETE SET NULL",
+    )?;
+    conn.execute_batch("CREATE INDEX IF NOT EXISTS idx_ad_copies_link_id ON ad_copies(link_id);")?;
+    println!("✓ Ad links migration completed");
+
+    // Run product readiness migration (036) - add column with existence check
+    add_column_if_not_exists(conn, "products", "price_verified_at", "DATETIME")?;
+    println!("✓ Product readiness migration completed");
+
+    // Run scheduled posts migration (037)
+    let scheduled_posts_sql = include_str!("../../../migrations/037_scheduled_posts.sql");
+    conn.execute_batch(scheduled_posts_sql)?;
+    println!("✓ Scheduled posts migration completed");
 
-    // Run seed data migration
-    let seed_sql = include_str!("../../../migrations/002_seed_products.sql");
-    conn.execute_batch(seed_sql)?;
+    // Run retail events migration (038)
+    let retail_events_sql = include_str!("../../../migrations/038_retail_events.sql");
+    conn.execute_batch(retail_events_sql)?;
+    println!("✓ Retail events migration completed");
 
-    // Mark seed data as run
-    conn.execute(
-        "INSERT OR REPLACE INTO settings (key, value) VALUES ('seed_data_run', 'true')",
+    // Run approval workflow migration (039) - add columns with existence check
+    add_column_if_not_exists(conn, "ad_copies", "approval_state", "TEXT NOT NULL DEFAULT 'draft'")?;
+    add_column_if_not_exists(conn, "scheduled_posts", "approval_state", "TEXT NOT NULL DEFAULT 'draft'")?;
+    let approval_sql = include_str!("../../../migrations/039_approval_workflow.sql");
+    conn.execute_batch(approval_sql)?;
+    println!("✓ Approval workflow migration completed");
+
+    // Run shared workspace migration (040) - add column with existence check
+    add_column_if_not_exists(conn, "products", "updated_by", "TEXT")?;
+    println!("✓ Shared workspace migration completed");
+
+    // Run data retention migration (041)
+    let retention_sql = include_str!("../../../migrations/041_data_retention.sql");
+    conn.execute_batch(retention_sql)?;
+    println!("✓ Data retention migration completed");
+
+    // Run daily metrics migration (042)
+    let daily_metrics_sql = include_str!("../../../migrations/042_daily_metrics.sql");
+    conn.execute_batch(daily_metrics_sql)?;
+    println!("✓ Daily metrics migration completed");
+
+    // Run hot path indexes migration (043)
+    let indexes_sql = include_str!("../../../migrations/043_hot_path_indexes.sql");
+    conn.execute_batch(indexes_sql)?;
+    println!("✓ Hot path indexes migration completed");
+
+    // Run product watchlist migration (044)
+    let watchlist_sql = include_str!("../../../migrations/044_product_watchlist.sql");
+    conn.execute_batch(watchlist_sql)?;
+    println!("✓ Product watchlist migration completed");
+
+    // Run product availability migration (045) - add columns with existence checks
+    add_column_if_not_exists(conn, "products", "availability", "TEXT DEFAULT 'unknown'")?;
+    add_column_if_not_exists(conn, "products", "availability_checked_at", "DATETIME")?;
+    println!("✓ Product availability migration completed");
+
+    // Run bundles migration (046); the ad_copies column needs an existence check
+    let bundles_sql = include_str!("../../../migrations/046_bundles.sql");
+    conn.execute_batch(bundles_sql)?;
+    add_column_if_not_exists(conn, "ad_copies", "bundle_id", "INTEGER REFERENCES bundles(id) ON DELETE SET NULL")?;
+    conn.execute_batch("CREATE INDEX IF NOT EXISTS idx_ad_copies_bundle ON ad_copies(bundle_id);")?;
+    println!("✓ Bundles migration completed");
+
+    // Run notes migration (047); free-text product notes are copied over the
+    // first time the table is created
+    let had_notes: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'notes')",
         [],
+        |row| row.get(0),
     )?;
+    let notes_sql = include_str!("../../../migrations/047_notes.sql");
+    conn.execute_batch(notes_sql)?;
+    if !had_notes {
+        copy_product_notes(conn)?;
+    }
+    println!("✓ Notes migration completed");
+
+    // Run attachments migration (048)
+    let attachments_sql = include_str!("../../../migrations/048_attachments.sql");
+    conn.execute_batch(attachments_sql)?;
+    println!("✓ Attachments migration completed");
+
+    // Run benchmarks migration (049); per-platform overrides saved in settings
+    // before the table existed are moved into it
+    let benchmarks_sql = include_str!("../../../migrations/049_benchmarks.sql");
+    conn.execute_batch(benchmarks_sql)?;
+    move_benchmark_settings(conn)?;
+    println!("✓ Benchmarks migration completed");
 
-    println!("✓ Seed data migration completed");
-    println!("✓ Database initialized with 10 trending products");
+    // Run category keywords migration (050)
+    let category_keywords_sql = include_str!("../../../migrations/050_category_keywords.sql");
+    conn.execute_batch(category_keywords_sql)?;
+    println!("✓ Category keywords migration completed");
+
+    // Run trend history migration (051)
+    let trend_history_sql = include_str!("../../../migrations/051_trend_history.sql");
+    conn.execute_batch(trend_history_sql)?;
+    println!("✓ Trend history migration completed");
+
+    // Run variation performance migration (052)
+    let variation_performance_sql = include_str!("../../../migrations/052_variation_performance.sql");
+    conn.execute_batch(variation_performance_sql)?;
+    println!("✓ Variation performance migration completed");
+
+    // Run experiments migration (053)
+    let experiments_sql = include_str!("../../../migrations/053_experiments.sql");
+    conn.execute_batch(experiments_sql)?;
+    println!("✓ Experiments migration completed");
+
+    // Run link refreshes migration (054); bulk operations created before the
+    // `enrich` and `refresh` kinds are rebuilt without the old kind CHECK
+    let link_refreshes_sql = include_str!("../../../migrations/054_link_refreshes.sql");
+    conn.execute_batch(link_refreshes_sql)?;
+    drop_bulk_kind_check(conn)?;
+    println!("✓ Link refreshes migration completed");
+
+    // Run store products migration (055)
+    let store_products_sql = include_str!("../../../migrations/055_store_products.sql");
+    conn.execute_batch(store_products_sql)?;
+    println!("✓ Store products migration completed");
+
+    // Run feed sources migration (056)
+    let feed_sources_sql = include_str!("../../../migrations/056_feed_sources.sql");
+    conn.execute_batch(feed_sources_sql)?;
+    println!("✓ Feed sources migration completed");
+
+    // Affiliate programs on first run; products wait for the first-run choice
+    seed::on_startup(conn)?;
 
     // Create default campaign for direct product ads (007)
     ensure_default_campaign(conn)?;
@@ -112,8 +358,89 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Copies each product's free-text notes into a note referencing the product
+fn copy_product_notes(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, notes, created_at FROM products WHERE TRIM(COALESCE(notes, '')) != ''",
+    )?;
+    let rows: Vec<(i64, String, String, Option<String>)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+        .collect::<Result<_>>()?;
+    for (product_id, name, notes, created_at) in rows {
+        conn.execute(
+            "INSERT INTO notes (title, body, created_at) VALUES (?1, ?2, COALESCE(?3, CURRENT_TIMESTAMP))",
+            rusqlite::params![format!("{} notes", name), notes, created_at],
+        )?;
+        conn.execute(
+            "INSERT INTO note_refs (note_id, entity_type, entity_id) VALUES (?1, 'product', ?2)",
+            rusqlite::params![conn.last_insert_rowid(), product_id],
+        )?;
+    }
+    Ok(())
+}
+
+/// Copies `benchmarks:<platform>` settings onto the platform's any-category row
+fn move_benchmark_settings(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("SELECT key, value FROM settings WHERE key LIKE 'benchmarks:%'")?;
+    let saved: Vec<(String, String)> =
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect::<Result<_>>()?;
+    for (key, json) in saved {
+        let platform = key.trim_start_matches("benchmarks:");
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(&json) {
+            if let (Some(ctr), Some(conversion_rate)) = (value["ctr"].as_f64(), value["conversionRate"].as_f64()) {
+                conn.execute(
+                    "INSERT INTO benchmarks (platform, category, ctr, conversion_rate) VALUES (?1, '', ?2, ?3)
+                     ON CONFLICT (platform, category) DO UPDATE SET
+                        ctr = excluded.ctr, conversion_rate = excluded.conversion_rate",
+                    rusqlite::params![platform, ctr, conversion_rate],
+                )?;
+            }
+        }
+        conn.execute("DELETE FROM settings WHERE key = ?1", [&key])?;
+    }
+    Ok(())
+}
+
+/// Rebuilds `bulk_operations` without the `kind IN ('links', 'ads')` CHECK
+/// that migration 016 used to create, keeping ids so items stay attached.
+/// The copy is renamed into place so references to the table are untouched.
+fn drop_bulk_kind_check(conn: &Connection) -> Result<()> {
+    let sql: Option<String> = conn
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'bulk_operations'",
+            [],
+            |row| row.get(0),
+        )
+        .ok();
+    if !sql.is_some_and(|sql| sql.contains("CHECK(kind IN")) {
+        return Ok(());
+    }
+    // Dropping the table with foreign keys on would cascade to its items
+    conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
+    let rebuilt = conn.execute_batch(
+        "BEGIN;
+         CREATE TABLE bulk_operations_new (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             kind TEXT NOT NULL,
+             params_json TEXT NOT NULL DEFAULT '{}',
+             status TEXT NOT NULL DEFAULT 'running'
+                 CHECK(status IN ('running', 'completed', 'cancelled', 'interrupted')),
+             created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+             updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+         );
+         INSERT INTO bulk_operations_new (id, kind, params_json, status, created_at, updated_at)
+             SELECT id, kind, params_json, status, created_at, updated_at FROM bulk_operations;
+         DROP TABLE bulk_operations;
+         ALTER TABLE bulk_operations_new RENAME TO bulk_operations;
+         CREATE INDEX IF NOT EXISTS idx_bulk_operations_status ON bulk_operations(status);
+         COMMIT;",
+    );
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    rebuilt
+}
+
 /// Creates a default campaign for direct product ads if it doesn't exist
-fn ensure_default_campaign(conn: &Connection) -> Result<()> {
+pub(crate) fn ensure_default_campaign(conn: &Connection) -> Result<()> {
     // Check if default campaign already exists
     let exists: bool = conn
         .query_row(
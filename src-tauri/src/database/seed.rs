@@ -0,0 +1,158 @@
+//! Seed Packs
+//!
+//! Starter products and affiliate programs. Affiliate programs are always
+//! seeded; products only when the user picks a seeded workspace on first run,
+//! or later through `reseed_products`. Each pack is applied into temporary
+//! tables that shadow `products` and `affiliate_programs`, and only rows whose
+//! name is not already in the workspace are copied over, so re-seeding never
+//! duplicates rows or brings back edits the user has made.
+
+use super::settings::{get_setting, get_setting_parsed, set_setting};
+use crate::models::workspace::{SeedResult, SeedStatus};
+use rusqlite::{Connection, Result};
+
+/// Seed packs in release order; append new packs, never edit shipped ones
+const SEED_PACKS: [&str; 1] = [include_str!("../../../migrations/002_seed_products.sql")];
+
+pub const SEED_VERSION: i64 = SEED_PACKS.len() as i64;
+
+/// Set once first-run seeding (affiliate programs) has happened
+const SEED_RUN_SETTING: &str = "seed_data_run";
+/// "seeded" or "blank"; missing until the user answers the first-run prompt
+pub const PREFERENCE_SETTING: &str = "seed_preference";
+/// Latest pack whose products were added
+const VERSION_SETTING: &str = "seed_version";
+
+pub const SEEDED: &str = "seeded";
+pub const BLANK: &str = "blank";
+
+/// Applies the packs; products are copied only with `include_products`.
+/// Returns the IDs of inserted seed products and how many already existed.
+fn apply_packs(conn: &Connection, include_products: bool) -> Result<(Vec<i64>, usize)> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(
+        "CREATE TEMP TABLE products AS SELECT * FROM main.products WHERE 0;
+         CREATE TEMP TABLE affiliate_programs AS SELECT * FROM main.affiliate_programs WHERE 0;",
+    )?;
+    for pack in SEED_PACKS {
+        tx.execute_batch(pack)?;
+    }
+
+    tx.execute(
+        "INSERT INTO main.affiliate_programs (name, base_url, default_commission_rate, cookie_duration, active)
+         SELECT name, base_url, default_commission_rate, cookie_duration, active FROM temp.affiliate_programs s
+         WHERE NOT EXISTS (SELECT 1 FROM main.affiliate_programs p WHERE p.name = s.name COLLATE NOCASE)",
+        [],
+    )?;
+
+    let mut inserted = Vec::new();
+    let mut skipped = 0;
+    if include_products {
+        let total: usize = tx.query_row("SELECT COUNT(*) FROM temp.products", [], |row| row.get(0))?;
+        let last_id: i64 = tx.query_row("SELECT COALESCE(MAX(id), 0) FROM main.products", [], |row| row.get(0))?;
+        let count = tx.execute(
+            "INSERT INTO main.products (name, category, description, price_range, target_audience,
+             trending_score, notes)
+             SELECT name, category, description, price_range, target_audience, trending_score, notes
+             FROM temp.products s
+             WHERE NOT EXISTS (SELECT 1 FROM main.products p WHERE p.name = s.name COLLATE NOCASE)",
+            [],
+        )?;
+        skipped = total - count;
+        let mut stmt = tx.prepare("SELECT id FROM main.products WHERE id > ?1 ORDER BY id")?;
+        inserted = stmt.query_map([last_id], |row| row.get(0))?.collect::<Result<Vec<i64>>>()?;
+    }
+
+    tx.execute_batch("DROP TABLE temp.products; DROP TABLE temp.affiliate_programs;")?;
+    tx.commit()?;
+    Ok((inserted, skipped))
+}
+
+/// Startup seeding. Fresh workspaces get the affiliate programs and wait for
+/// the first-run choice; workspaces seeded before the choice existed are
+/// recorded as seeded with the first pack.
+pub fn on_startup(conn: &Connection) -> Result<()> {
+    if get_setting(conn, SEED_RUN_SETTING)?.as_deref() == Some("true") {
+        if get_setting(conn, VERSION_SETTING)?.is_none() {
+            set_setting(conn, PREFERENCE_SETTING, SEEDED)?;
+            set_setting(conn, VERSION_SETTING, "1")?;
+        }
+        println!("✓ Seed data already populated");
+        return Ok(());
+    }
+
+    apply_packs(conn, false)?;
+    set_setting(conn, SEED_RUN_SETTING, "true")?;
+    println!("✓ Affiliate programs seeded; awaiting first-run workspace choice");
+    Ok(())
+}
+
+pub fn status(conn: &Connection) -> Result<SeedStatus> {
+    let preference = get_setting(conn, PREFERENCE_SETTING)?;
+    let applied_version = get_setting_parsed(conn, VERSION_SETTING, 0i64);
+    Ok(SeedStatus {
+        update_available: preference.as_deref() == Some(SEEDED) && applied_version < SEED_VERSION,
+        preference,
+        applied_version,
+        latest_version: SEED_VERSION,
+    })
+}
+
+/// Adds seed products missing from the workspace. Without `force` this only
+/// runs when a newer pack is available, so repeated calls are no-ops.
+pub fn reseed_products(conn: &Connection, force: bool) -> Result<SeedResult> {
+    let applied_version = get_setting_parsed(conn, VERSION_SETTING, 0i64);
+    if !force && applied_version >= SEED_VERSION {
+        return Ok(SeedResult { product_ids: Vec::new(), skipped: 0, seed_version: applied_version });
+    }
+
+    let (product_ids, skipped) = apply_packs(conn, true)?;
+    set_setting(conn, VERSION_SETTING, &SEED_VERSION.to_string())?;
+    super::schema::ensure_default_campaign(conn)?;
+    Ok(SeedResult { product_ids, skipped, seed_version: SEED_VERSION })
+}
+
+/// Records the first-run choice; a seeded workspace gets the seed products now
+pub fn choose_starting_workspace(conn: &Connection, seeded: bool) -> Result<SeedResult> {
+    set_setting(conn, PREFERENCE_SETTING, if seeded { SEEDED } else { BLANK })?;
+    if seeded {
+        reseed_products(conn, true)
+    } else {
+        Ok(SeedResult { product_ids: Vec::new(), skipped: 0, seed_version: 0 })
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../../migrations/001_initial_schema.sql")).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_reseed_skips_existing_products() {
+        let conn = workspace();
+        on_startup(&conn).unwrap();
+        let programs: i64 = conn.query_row("SELECT COUNT(*) FROM affiliate_programs", [], |r| r.get(0)).unwrap();
+        let products: i64 = conn.query_row("SELECT COUNT(*) FROM products", [], |r| r.get(0)).unwrap();
+        assert!(programs > 0);
+        assert_eq!(products, 0);
+        assert!(status(&conn).unwrap().preference.is_none());
+
+        let first = choose_starting_workspace(&conn, true).unwrap();
+        assert_eq!((first.product_ids.len(), first.skipped), (10, 0));
+        assert!(!status(&conn).unwrap().update_available);
+
+        conn.execute("DELETE FROM products WHERE id = 1", []).unwrap();
+        assert!(reseed_products(&conn, false).unwrap().product_ids.is_empty());
+        let forced = reseed_products(&conn, true).unwrap();
+        assert_eq!((forced.product_ids.len(), forced.skipped), (1, 9));
+    }
+}
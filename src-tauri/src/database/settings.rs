@@ -0,0 +1,35 @@
+use rusqlite::{params, Connection, OptionalExtension, Result};
+
+/// Reads a raw value from the settings key/value table
+pub fn get_setting(conn: &Connection, key: &str) -> Result<Option<String>> {
+    conn.prepare_cached("SELECT value FROM settings WHERE key = ?1")?
+        .query_row(params![key], |row| row.get(0))
+        .optional()
+}
+
+/// Reads a setting, falling back to `default` when missing
+pub fn get_setting_or(conn: &Connection, key: &str, default: &str) -> String {
+    get_setting(conn, key)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Reads a setting and parses it, falling back to `default` when missing or invalid
+pub fn get_setting_parsed<T: std::str::FromStr>(conn: &Connection, key: &str, default: T) -> T {
+    get_setting(conn, key)
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse::<T>().ok())
+        .unwrap_or(default)
+}
+
+/// Inserts or replaces a setting value
+pub fn set_setting(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, CURRENT_TIMESTAMP)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = CURRENT_TIMESTAMP",
+        params![key, value],
+    )?;
+    Ok(())
+}
@@ -1,47 +1,386 @@
 // AffilAI - Affiliate Campaign Management Desktop App
+#[cfg(feature = "cli")]
+pub mod cli;
 mod commands;
 mod database;
 mod models;
 mod services;
 
-use commands::{ad_generation, affiliate_links, credentials, products};
+use commands::{
+    ad_generation, ad_imports, ad_links, ad_templates, affiliate_links, analytics_import, approvals,
+    attachments, bulk_operations, bundles, category_keywords, click_analytics, clipboard,
+    content_angles, conversions, credentials, critiques, daily_metrics, data_privacy, deep_links,
+    diagnostics, digests, email_sequences, entity_query, experiments, exports, extension_api, feed_sources,
+    headline_variants, ideas, listing_import, maintenance, network_terms, niche_profiles, notes,
+    platform_mix, post_schedule, postbacks, products, program_applications, readiness, research, rest_api,
+    retail_events, reviews, roles, shared_workspace, short_video, sms, spreadsheet_import, store_sync,
+    style_rules, tiktok_sync, variations, watchlist, webhooks, workspace,
+};
+use tauri::Manager;
+
+/// Every command exposed to the frontend. Their signatures, and the types they
+/// take and return, are exported to `src/bindings.ts`.
+fn specta_builder() -> tauri_specta::Builder<tauri::Wry> {
+    tauri_specta::Builder::<tauri::Wry>::new().commands(tauri_specta::collect_commands![
+        products::get_all_products,
+        products::get_product_by_id,
+        products::create_product,
+        products::update_product,
+        products::delete_product,
+        products::search_products,
+        products::set_product_stage,
+        products::get_products_by_stage,
+        products::get_stage_counts,
+        products::get_trend_history,
+        affiliate_links::get_all_affiliate_links,
+        affiliate_links::get_links_by_product,
+        affiliate_links::discover_affiliate_programs,
+        affiliate_links::generate_affiliate_link,
+        affiliate_links::generate_link_for_platform,
+        affiliate_links::create_affiliate_link,
+        affiliate_links::refresh_affiliate_link,
+        affiliate_links::refresh_links_for_products,
+        affiliate_links::get_refresh_diffs,
+        affiliate_links::delete_affiliate_link,
+        affiliate_links::generate_links_for_all_products,
+        credentials::get_all_credentials,
+        credentials::get_credential_by_platform,
+        credentials::save_credential,
+        credentials::delete_credential,
+        ad_generation::generate_ad_for_product,
+        ad_generation::get_ads_for_product,
+        conversions::get_conversions,
+        conversions::get_attribution_config,
+        conversions::save_attribution_config,
+        conversions::get_attribution_report,
+        analytics_import::import_analytics_traffic,
+        analytics_import::get_traffic_sessions,
+        digests::generate_weekly_digest,
+        digests::get_digests,
+        click_analytics::get_click_heatmap,
+        click_analytics::get_clicks_by_source,
+        click_analytics::get_clicks_by_country,
+        click_analytics::get_click_quality,
+        click_analytics::get_geoip_status,
+        click_analytics::set_geoip_database,
+        niche_profiles::get_niche_profiles,
+        niche_profiles::get_active_niche_profile,
+        niche_profiles::save_niche_profile,
+        niche_profiles::activate_niche_profile,
+        niche_profiles::delete_niche_profile,
+        ideas::capture_idea,
+        ideas::get_ideas,
+        ideas::dismiss_idea,
+        ideas::delete_idea,
+        ideas::promote_idea_to_product,
+        feed_sources::get_feed_sources,
+        feed_sources::save_feed_source,
+        feed_sources::delete_feed_source,
+        feed_sources::poll_feed_sources_now,
+        clipboard::get_clipboard_watcher_status,
+        clipboard::set_clipboard_watcher_enabled,
+        extension_api::get_extension_api_status,
+        extension_api::save_extension_api_config,
+        extension_api::regenerate_extension_api_token,
+        ad_generation::set_ad_favorite,
+        ad_generation::set_ad_alt_text,
+        ad_generation::explain_recommendation,
+        maintenance::cleanup,
+        maintenance::check_data_integrity,
+        maintenance::clear_ai_cache,
+        affiliate_links::generate_links_for_products,
+        affiliate_links::get_discovery_diagnostics,
+        bulk_operations::generate_ads_for_products,
+        bulk_operations::import_identifiers,
+        listing_import::import_marketplace_listings,
+        bulk_operations::get_bulk_operations,
+        bulk_operations::get_bulk_operation_items,
+        bulk_operations::cancel_bulk_operation,
+        bulk_operations::resume_bulk_operation,
+        postbacks::get_postback_config,
+        postbacks::save_postback_config,
+        postbacks::start_postback_listener,
+        postbacks::stop_postback_listener,
+        postbacks::get_postback_status,
+        postbacks::get_postback_log,
+        research::research_product,
+        research::get_product_research,
+        content_angles::generate_content_angles,
+        content_angles::get_content_angles,
+        content_angles::delete_content_angle,
+        reviews::summarize_reviews,
+        reviews::get_review_insights,
+        exports::export_link_hub,
+        email_sequences::generate_email_sequence,
+        email_sequences::get_email_sequence,
+        exports::export_email_sequence,
+        sms::check_sms_compliance,
+        exports::export_sms_campaign,
+        exports::export_pinterest_pins,
+        short_video::generate_video_companion,
+        style_rules::get_style_rules,
+        style_rules::save_style_rules,
+        style_rules::reset_style_rules,
+        critiques::critique_ad,
+        critiques::get_ad_critique,
+        critiques::get_weak_ads,
+        headline_variants::generate_headline_variants,
+        headline_variants::get_headline_variants,
+        headline_variants::select_headline_variant,
+        ad_templates::get_ad_templates,
+        ad_templates::save_ad_template,
+        ad_templates::delete_ad_template,
+        ad_templates::generate_quick_ad,
+        exports::export_ad_kit,
+        exports::export_ads_markdown,
+        exports::export_paid_ads,
+        ad_imports::import_ads,
+        spreadsheet_import::analyze_import_file,
+        spreadsheet_import::execute_import,
+        webhooks::get_webhooks,
+        webhooks::save_webhook,
+        webhooks::delete_webhook,
+        webhooks::test_webhook,
+        webhooks::get_webhook_deliveries,
+        rest_api::get_rest_api_status,
+        rest_api::save_rest_api_config,
+        rest_api::regenerate_rest_api_token,
+        entity_query::query_entities,
+        workspace::load_demo_data,
+        workspace::reset_workspace,
+        workspace::get_seed_status,
+        workspace::choose_starting_workspace,
+        workspace::reseed_products,
+        workspace::get_read_only_status,
+        workspace::set_workspace_read_only,
+        program_applications::get_program_applications,
+        program_applications::save_program_application,
+        program_applications::delete_program_application,
+        program_applications::get_application_reminders,
+        network_terms::get_network_terms,
+        network_terms::save_network_terms,
+        network_terms::delete_network_terms,
+        deep_links::get_program_network_ids,
+        deep_links::save_program_network_ids,
+        deep_links::build_deep_link,
+        exports::export_pretty_links,
+        exports::export_products_csv,
+        exports::export_meta_catalog,
+        exports::export_google_merchant_feed,
+        ad_links::get_unlinked_ads,
+        ad_links::get_unadvertised_links,
+        credentials::check_platform_credentials,
+        readiness::get_readiness_config,
+        readiness::save_readiness_config,
+        readiness::get_product_readiness,
+        readiness::filter_ready_products,
+        readiness::set_price_verified,
+        post_schedule::get_scheduled_posts,
+        post_schedule::save_scheduled_post,
+        post_schedule::set_scheduled_post_status,
+        post_schedule::delete_scheduled_post,
+        post_schedule::expand_recurring_posts,
+        post_schedule::suggest_post_time,
+        post_schedule::get_schedule_conflicts,
+        post_schedule::get_posting_rules,
+        post_schedule::save_posting_rules,
+        retail_events::get_retail_events,
+        retail_events::save_retail_event,
+        retail_events::delete_retail_event,
+        retail_events::get_upcoming_events,
+        retail_events::get_campaign_suggestions,
+        retail_events::get_product_seasonality,
+        retail_events::schedule_event_campaign,
+        approvals::transition_approval,
+        approvals::get_approval_history,
+        approvals::get_approval_queue,
+        approvals::get_approval_required,
+        approvals::set_approval_required,
+        shared_workspace::get_shared_workspace_status,
+        shared_workspace::set_shared_workspace,
+        shared_workspace::acquire_workspace_lock,
+        shared_workspace::release_workspace_lock,
+        roles::get_role_status,
+        roles::unlock_owner,
+        roles::lock_owner,
+        roles::set_owner_password,
+        data_privacy::export_all_data,
+        data_privacy::wipe_all_data,
+        maintenance::get_retention_policy,
+        maintenance::save_retention_policy,
+        maintenance::apply_retention_policy,
+        daily_metrics::get_daily_metrics,
+        daily_metrics::get_daily_metrics_status,
+        daily_metrics::rebuild_daily_metrics,
+        daily_metrics::get_product_cohorts,
+        diagnostics::get_slow_queries,
+        diagnostics::set_slow_query_threshold,
+        diagnostics::clear_slow_queries,
+        diagnostics::explain_hot_queries,
+        products::count_products,
+        products::get_products_range,
+        products::compare_products,
+        products::get_promotion_queue,
+        affiliate_links::count_links,
+        affiliate_links::get_links_range,
+        watchlist::watch_product,
+        watchlist::unwatch_product,
+        watchlist::get_watchlist,
+        watchlist::get_product_changes,
+        watchlist::check_watchlist_now,
+        store_sync::import_store_products,
+        store_sync::sync_store_now,
+        store_sync::get_store_sku_mappings,
+        store_sync::map_store_sku,
+        store_sync::unmap_store_sku,
+        tiktok_sync::sync_tiktok_products,
+        tiktok_sync::save_tiktok_access_token,
+        bundles::get_bundles,
+        bundles::save_bundle,
+        bundles::delete_bundle,
+        bundles::generate_bundle_ad,
+        bundles::get_bundle_ads,
+        bundles::get_bundle_performance,
+        notes::get_notes,
+        notes::get_notes_for_entity,
+        notes::save_note,
+        notes::delete_note,
+        notes::search_notes,
+        attachments::add_attachment,
+        attachments::get_attachments,
+        attachments::open_attachment,
+        attachments::delete_attachment,
+        platform_mix::simulate_platform_mix,
+        platform_mix::estimate_earnings,
+        platform_mix::get_benchmarks,
+        platform_mix::save_benchmark,
+        platform_mix::reset_benchmark,
+        platform_mix::delete_benchmark,
+        category_keywords::get_category_keywords,
+        category_keywords::save_category_keyword,
+        category_keywords::reset_category_keyword,
+        category_keywords::delete_category_keyword,
+        variations::record_variation_performance,
+        variations::get_next_variation,
+        experiments::get_experiments,
+        experiments::start_experiment,
+        experiments::stop_experiment,
+        experiments::delete_experiment,
+        experiments::analyze_experiment,
+    ])
+}
+
+/// Regenerates `src/bindings.ts`; i64 IDs and counts are exported as `number`
+fn export_bindings(builder: &tauri_specta::Builder<tauri::Wry>) -> Result<(), String> {
+    let typescript = specta_typescript::Typescript::default()
+        .bigint(specta_typescript::BigIntExportBehavior::Number)
+        .header("// @ts-nocheck");
+    builder
+        .export(typescript, concat!(env!("CARGO_MANIFEST_DIR"), "/../src/bindings.ts"))
+        .map_err(|e| e.to_string())
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let builder = specta_builder();
+    let invoke_handler = builder.invoke_handler();
+
+    // Debug builds keep the TypeScript bindings in sync with the command layer
+    #[cfg(debug_assertions)]
+    if let Err(e) = export_bindings(&builder) {
+        eprintln!("Failed to export TypeScript bindings: {}", e);
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .setup(|app| {
             // Initialize database
             let app_handle = app.handle().clone();
             match database::init_database(&app_handle) {
-                Ok(_) => println!("Database initialized successfully"),
+                Ok(conn) => {
+                    println!("Database initialized successfully");
+                    services::query_profiler::load_threshold(&conn);
+
+                    // Runs left 'running' by a previous session can be resumed
+                    if let Err(e) = services::bulk_operations::mark_interrupted(&conn) {
+                        eprintln!("Failed to mark interrupted bulk operations: {}", e);
+                    }
+
+                    // Resume the postback listener if the user enabled it
+                    let postback_config = services::postback::load_config(&conn);
+                    if postback_config.enabled {
+                        if let Err(e) = services::postback::start_listener(
+                            app_handle.clone(),
+                            postback_config.port,
+                        ) {
+                            eprintln!("Failed to start postback listener: {}", e);
+                        }
+                    }
+
+                    // Resume the clipboard watcher if the user opted in
+                    let clipboard_enabled = database::settings::get_setting_or(
+                        &conn,
+                        services::clipboard_watcher::ENABLED_SETTING,
+                        "false",
+                    ) == "true";
+                    if clipboard_enabled {
+                        if let Err(e) =
+                            services::clipboard_watcher::start_watcher(app_handle.clone())
+                        {
+                            eprintln!("Failed to start clipboard watcher: {}", e);
+                        }
+                    }
+
+                    // Resume the browser extension API if enabled
+                    if services::extension_api::is_enabled(&conn) {
+                        let port = services::extension_api::configured_port(&conn);
+                        if let Err(e) =
+                            services::extension_api::start_server(app_handle.clone(), port)
+                        {
+                            eprintln!("Failed to start extension API: {}", e);
+                        }
+                    }
+
+                    // Resume the local REST API if enabled
+                    if services::rest_api::is_enabled(&conn) {
+                        let port = services::rest_api::configured_port(&conn);
+                        let allow_lan = services::rest_api::allows_lan(&conn);
+                        if let Err(e) =
+                            services::rest_api::start_server(app_handle.clone(), port, allow_lan)
+                        {
+                            eprintln!("Failed to start REST API: {}", e);
+                        }
+                    }
+                }
                 Err(e) => eprintln!("Failed to initialize database: {}", e),
             }
+
+            // Take the edit lock on a shared database and keep it alive
+            services::shared_workspace::start(app_handle.clone());
+
+            // Start periodic background jobs (weekly digest, ...)
+            services::scheduler::start(app_handle);
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            products::get_all_products,
-            products::get_product_by_id,
-            products::create_product,
-            products::update_product,
-            products::delete_product,
-            products::search_products,
-            affiliate_links::get_all_affiliate_links,
-            affiliate_links::get_links_by_product,
-            affiliate_links::discover_affiliate_programs,
-            affiliate_links::generate_affiliate_link,
-            affiliate_links::generate_link_for_platform,
-            affiliate_links::create_affiliate_link,
-            affiliate_links::refresh_affiliate_link,
-            affiliate_links::delete_affiliate_link,
-            affiliate_links::generate_links_for_all_products,
-            credentials::get_all_credentials,
-            credentials::get_credential_by_platform,
-            credentials::save_credential,
-            credentials::delete_credential,
-            ad_generation::generate_ad_for_product,
-            ad_generation::get_ads_for_product,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(move |invoke| {
+            // A read-only workspace refuses every command that could change it
+            let command = invoke.message.command();
+            if let Err(error) = services::read_only::guard(invoke.message.webview_ref().app_handle(), command) {
+                invoke.resolver.reject(error);
+                return true;
+            }
+            invoke_handler(invoke)
+        })
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            // Let teammates in as soon as this instance closes
+            if let tauri::RunEvent::Exit = event {
+                if let Err(e) = services::shared_workspace::release(&database::database_path(app_handle)) {
+                    eprintln!("Failed to release workspace lock: {}", e);
+                }
+            }
+        });
 }
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Selects ads for export; an empty filter selects every ad
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AdExportFilter {
+    #[serde(default, alias = "ad_ids")]
+    pub ad_ids: Vec<i64>, // Only these ads, when non-empty
+    #[serde(alias = "product_id")]
+    pub product_id: Option<i64>,
+    #[serde(alias = "ad_type")]
+    pub ad_type: Option<String>,
+    #[serde(default, alias = "favorites_only")]
+    pub favorites_only: bool,
+    #[serde(alias = "created_after")]
+    pub created_after: Option<String>, // YYYY-MM-DD
+}
+
+/// Result of a Markdown export: one note per ad
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownExport {
+    pub path: String, // Folder the notes were written to
+    pub files: Vec<String>,
+}
+
+/// Result of a Meta Ads Manager or Google Ads Editor bulk sheet export
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PaidAdsExport {
+    pub path: String,
+    pub exported: usize,
+    #[serde(alias = "skipped_ad_ids")]
+    pub skipped_ad_ids: Vec<i64>, // No active affiliate link for the final URL
+    pub warnings: Vec<String>,    // e.g. copy shortened to fit field limits
+}
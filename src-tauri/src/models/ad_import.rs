@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// One ad from an imported CSV or JSON library. CSV headers are matched
+/// case-insensitively, with spaces treated as underscores.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct ImportedAdRow {
+    #[serde(default)]
+    pub product_id: Option<i64>,
+    #[serde(default, alias = "product")]
+    pub product_name: Option<String>,
+    #[serde(default, alias = "title")]
+    pub headline: String,
+    #[serde(default, alias = "body", alias = "copy")]
+    pub body_text: Option<String>,
+    #[serde(default, alias = "call_to_action")]
+    pub cta: Option<String>,
+    #[serde(default, alias = "type")]
+    pub ad_type: Option<String>,
+    #[serde(default)]
+    pub platform: Option<String>,
+    #[serde(default, alias = "date")]
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AdImportSkip {
+    pub row: usize, // 1-based, excluding the CSV header
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AdImportSummary {
+    pub imported: usize,
+    #[serde(alias = "imported_ad_ids")]
+    pub imported_ad_ids: Vec<i64>,
+    pub skipped: Vec<AdImportSkip>, // unmatched product, missing headline, duplicate
+}
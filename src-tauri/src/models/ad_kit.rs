@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Result of assembling an ad kit folder or zip
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AdKitExport {
+    pub path: String,       // Kit folder, or the .zip file
+    pub files: Vec<String>, // Paths inside the kit
+    #[serde(alias = "ad_count")]
+    pub ad_count: usize,
+    pub warnings: Vec<String>, // e.g. image download failures, no tracking link
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// A user-authored ad template; text fields may contain `{{merge_tags}}`
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AdTemplate {
+    pub id: i64,
+    pub name: String,
+    #[serde(alias = "ad_type")]
+    pub ad_type: String, // social_post, story, video_script, carousel, email, sms, pinterest_pin
+    pub headline: String,
+    pub body: String,
+    pub cta: Option<String>,
+    #[serde(alias = "created_at")]
+    pub created_at: Option<String>,
+    #[serde(alias = "updated_at")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveAdTemplateInput {
+    pub id: Option<i64>, // Update when set, create otherwise
+    pub name: String,
+    #[serde(alias = "ad_type")]
+    pub ad_type: String,
+    pub headline: String,
+    pub body: String,
+    pub cta: Option<String>,
+}
@@ -1,28 +1,61 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct AffiliateCredential {
     pub id: Option<i64>,
     pub platform: String,           // "amazon", "tiktok", "instagram", "youtube", "pinterest"
+    #[serde(alias = "affiliate_id")]
     pub affiliate_id: Option<String>, // Amazon Associate Tag, Creator ID, etc.
+    #[serde(alias = "shop_id")]
     pub shop_id: Option<String>,    // For TikTok/Instagram shops
+    #[serde(alias = "account_name")]
     pub account_name: Option<String>, // Display name
+    #[serde(alias = "api_key")]
     pub api_key: Option<String>,    // For future API integration
+    #[serde(alias = "api_secret")]
     pub api_secret: Option<String>,
     pub active: bool,
     pub verified: bool,             // Whether credentials have been tested
     pub notes: Option<String>,
+    #[serde(alias = "created_at")]
     pub created_at: Option<String>,
+    #[serde(alias = "updated_at")]
     pub updated_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct SaveCredentialInput {
     pub platform: String,
+    #[serde(alias = "affiliate_id")]
     pub affiliate_id: Option<String>,
+    #[serde(alias = "shop_id")]
     pub shop_id: Option<String>,
+    #[serde(alias = "account_name")]
     pub account_name: Option<String>,
+    #[serde(alias = "api_key")]
     pub api_key: Option<String>,
+    #[serde(alias = "api_secret")]
     pub api_secret: Option<String>,
     pub notes: Option<String>,
 }
+
+/// Whether links can be created for a platform with a real affiliate ID
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialCheck {
+    pub platform: String,
+    pub ready: bool,
+    pub message: Option<String>, // Why not, when not ready
+}
+
+/// Link generation error (returned as a JSON string) for a platform without
+/// an active credential. Retry with `allowMissingCredential` to proceed anyway.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingCredentialError {
+    pub code: String, // "missing_credential"
+    pub platform: String,
+    pub message: String,
+}
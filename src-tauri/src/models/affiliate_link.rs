@@ -1,12 +1,20 @@
+use crate::models::network_terms::NetworkTerms;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
 pub enum AffiliatePlatform {
+    // Aliases accept the lowercase names AI providers are asked to return
+    #[serde(alias = "tiktok")]
     TikTokShop,
+    #[serde(alias = "instagram")]
     InstagramShopping,
+    #[serde(alias = "amazon")]
     AmazonAssociates,
+    #[serde(alias = "youtube")]
     YouTubeShopping,
+    #[serde(alias = "pinterest")]
     PinterestBuyable,
+    #[serde(alias = "facebook")]
     FacebookShops,
 }
 
@@ -35,54 +43,162 @@ impl AffiliatePlatform {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct AffiliateLink {
     pub id: Option<i64>,
+    #[serde(alias = "product_id")]
     pub product_id: i64,
+    #[serde(alias = "product_name")]
     pub product_name: String,
     pub platform: String, // "tiktok", "instagram", "amazon", etc.
+    #[serde(alias = "program_name")]
     pub program_name: String,
+    #[serde(alias = "commission_rate")]
     pub commission_rate: Option<f64>,
+    #[serde(alias = "cookie_duration")]
     pub cookie_duration: Option<i32>,
+    #[serde(alias = "tracking_url")]
     pub tracking_url: String,
+    #[serde(alias = "destination_url")]
     pub destination_url: String,
     pub status: String, // 'active', 'expired', 'invalid'
+    #[serde(alias = "created_at")]
     pub created_at: Option<String>,
+    #[serde(alias = "updated_at")]
     pub updated_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateAffiliateLinkInput {
+    #[serde(alias = "product_id")]
     pub product_id: i64,
+    #[serde(alias = "product_name")]
     pub product_name: String,
     pub platform: String,
+    #[serde(alias = "program_name")]
     pub program_name: String,
+    #[serde(alias = "commission_rate")]
     pub commission_rate: Option<f64>,
+    #[serde(alias = "cookie_duration")]
     pub cookie_duration: Option<i32>,
+    #[serde(alias = "tracking_url")]
     pub tracking_url: String,
+    #[serde(alias = "destination_url")]
     pub destination_url: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// How well a platform fits a product's audience, factor by factor (each 0-1).
+/// `score` is the weighted sum (age 50%, category 25%, trending 15%, price
+/// 10%) plus the audience's gender and interest adjustment and any boost for a
+/// platform the niche profile prefers; zero when the platform doesn't serve
+/// the audience's country.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchBreakdown {
+    pub age_alignment: f64,
+    pub category_fit: f64,
+    pub trending_fit: f64,
+    pub price_fit: f64,
+    #[serde(default)]
+    pub audience_adjustment: f64, // Gender skew and interest overlap, may be negative
+    #[serde(default)]
+    pub locale_unsupported: bool,
+    #[serde(default)]
+    pub niche_boost: f64,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct AffiliateProgramDiscovery {
+    #[serde(alias = "program_name")]
     pub program_name: String,
     pub platform: AffiliatePlatform,
+    #[serde(alias = "commission_rate")]
     pub commission_rate: f64,
+    #[serde(alias = "cookie_duration")]
     pub cookie_duration: i32,
+    #[serde(alias = "affiliate_url")]
     pub affiliate_url: String,
+    #[serde(alias = "is_official")]
     pub is_official: bool,
+    #[serde(alias = "confidence_score")]
     pub confidence_score: f64,
+    #[serde(alias = "audience_match_score")]
     pub audience_match_score: f64,
+    #[serde(alias = "recommendation_reason")]
     pub recommendation_reason: String,
+    /// Factors behind the audience match; AI results get the local heuristic's
+    #[serde(default)]
+    pub match_breakdown: Option<MatchBreakdown>,
+    #[serde(default)]
+    pub application_status: Option<String>, // From program applications; None when not tracked
+    #[serde(default)]
+    pub network_terms: Option<NetworkTerms>, // Payout and cookie constraints of the matched network
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct GenerateLinkRequest {
+    #[serde(alias = "product_id")]
     pub product_id: i64,
+    /// Create the link even without an active credential for its platform
+    #[serde(default, alias = "allow_missing_credential")]
+    pub allow_missing_credential: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct GenerateLinkForPlatformRequest {
+    #[serde(alias = "product_id")]
     pub product_id: i64,
     pub platform: String,
+    #[serde(default, alias = "allow_missing_credential")]
+    pub allow_missing_credential: bool,
+}
+
+/// Scopes bulk link generation; an empty filter selects every product
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkLinkFilter {
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>, // Product must carry at least one of these
+    #[serde(alias = "min_trending_score")]
+    pub min_trending_score: Option<i32>,
+    /// Only generate for products that have no link yet (on `target_platform`
+    /// when one is set, on any platform otherwise)
+    #[serde(default, alias = "missing_platform_only")]
+    pub missing_platform_only: bool,
+    /// Generate for this platform instead of the best-matching program
+    #[serde(alias = "target_platform")]
+    pub target_platform: Option<String>,
+    /// Generate for platforms without an active credential too (placeholder IDs)
+    #[serde(default, alias = "allow_missing_credential")]
+    pub allow_missing_credential: bool,
+    /// Generate for products last seen out of stock too
+    #[serde(default, alias = "include_out_of_stock")]
+    pub include_out_of_stock: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkLinkSkip {
+    #[serde(alias = "product_id")]
+    pub product_id: i64,
+    #[serde(alias = "product_name")]
+    pub product_name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkLinkGenerationResult {
+    #[serde(alias = "operation_id")]
+    pub operation_id: i64, // Bulk operation record for progress/resume
+    pub generated: Vec<AffiliateLink>,
+    pub skipped: Vec<BulkLinkSkip>, // Filtered out before generation
+    pub failed: Vec<BulkLinkSkip>,  // Generation attempted but errored
 }
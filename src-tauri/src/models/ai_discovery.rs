@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Why one provider response was rejected
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseDiagnostic {
+    #[serde(default)]
+    pub provider: String,
+    pub attempt: i32,
+    pub stage: String, // 'extract', 'json', 'schema'
+    pub errors: Vec<String>,
+}
+
+/// A logged provider-backed discovery run
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AiDiscoveryRecord {
+    pub id: i64,
+    #[serde(alias = "product_id")]
+    pub product_id: i64,
+    pub provider: String, // Primary provider requested
+    pub model: String,
+    #[serde(alias = "served_by")]
+    pub served_by: Option<String>, // Provider whose output was used ('mock' after full fallback)
+    #[serde(alias = "fallback_errors")]
+    pub fallback_errors: Vec<String>, // "provider: error" for each provider that failed
+    pub valid: bool,
+    pub attempts: i32,
+    #[serde(alias = "raw_response")]
+    pub raw_response: Option<String>,
+    pub diagnostics: Vec<ParseDiagnostic>,
+    #[serde(alias = "created_at")]
+    pub created_at: Option<String>,
+}
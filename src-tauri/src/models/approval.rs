@@ -0,0 +1,26 @@
+use crate::commands::ad_generation::GeneratedAdCopy;
+use crate::models::scheduled_post::ScheduledPost;
+use serde::{Deserialize, Serialize};
+
+/// One step an ad or scheduled post took through the approval workflow
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalTransition {
+    pub id: i64,
+    pub entity_type: String, // 'ad' or 'scheduled_post'
+    pub entity_id: i64,
+    pub from_state: String,
+    pub to_state: String, // 'draft', 'in_review', 'approved', 'published'
+    pub reviewer: Option<String>,
+    pub comment: Option<String>,
+    pub created_at: Option<String>,
+}
+
+/// Ads and scheduled posts in one approval state
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalQueue {
+    pub state: String,
+    pub ads: Vec<GeneratedAdCopy>,
+    pub posts: Vec<ScheduledPost>,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// A file attached to a product, campaign, or affiliate credential
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    pub id: i64,
+    pub entity_type: String, // "product", "campaign", "credential"
+    pub entity_id: i64,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub description: Option<String>,
+    pub created_at: Option<String>,
+}
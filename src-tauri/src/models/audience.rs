@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// What a free-text target audience ("Ages 30-50, 70% women, home decor, UK") says
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AudienceProfile {
+    pub age_min: i32,
+    pub age_max: i32,
+    pub gender: String,             // "female", "male", "mixed", or "unspecified"
+    pub female_share: Option<f64>,  // 0-1; None when the text doesn't say
+    pub interests: Vec<String>,     // Interest keys ("home_decor", "gaming", ...)
+    pub locale: Option<String>,     // ISO country code ("US", "GB", ...)
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Reference rates for a platform, optionally narrowed to one product category
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Benchmark {
+    pub id: i64,
+    pub platform: String, // "other" covers platforms without a row
+    pub category: String, // Empty = any category
+    pub ctr: f64,             // Clicks per impression, 0.01 = 1%
+    pub conversion_rate: f64, // Conversions per click
+    pub aov_multiplier: f64,  // Applied to the product's order value
+    pub builtin: bool,        // Shipped with the app; can be reset but not deleted
+    pub edited: bool,         // A shipped row whose values differ from the defaults
+}
+
+/// Updates the benchmark when `id` is set, adds one otherwise
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveBenchmarkInput {
+    pub id: Option<i64>,
+    pub platform: String,
+    pub category: Option<String>,
+    pub ctr: f64,
+    pub conversion_rate: f64,
+    pub aov_multiplier: Option<f64>, // Default 1.0
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// A persisted bulk link/ad generation, product enrichment, or link refresh run with item counts
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkOperation {
+    pub id: i64,
+    pub kind: String,   // "links", "ads", "enrich" or "refresh"
+    pub status: String, // "running", "completed", "cancelled", "interrupted"
+    #[serde(alias = "params_json")]
+    pub params_json: String,
+    pub total: i64,
+    pub pending: i64,
+    pub succeeded: i64,
+    pub failed: i64,
+    pub skipped: i64,
+    #[serde(alias = "created_at")]
+    pub created_at: Option<String>,
+    #[serde(alias = "updated_at")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkOperationItem {
+    pub id: i64,
+    #[serde(alias = "operation_id")]
+    pub operation_id: i64,
+    #[serde(alias = "product_id")]
+    pub product_id: i64,
+    #[serde(alias = "product_name")]
+    pub product_name: String,
+    pub status: String, // "pending", "succeeded", "failed", "skipped"
+    pub message: Option<String>,
+    #[serde(alias = "result_id")]
+    pub result_id: Option<i64>,
+}
+
+/// Payload of the `bulk-operation-progress` event, emitted after every item
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkProgress {
+    #[serde(alias = "operation_id")]
+    pub operation_id: i64,
+    pub kind: String,
+    pub status: String,
+    #[serde(alias = "product_id")]
+    pub product_id: Option<i64>,
+    pub processed: i64,
+    pub total: i64,
+    pub succeeded: i64,
+    pub failed: i64,
+    pub skipped: i64,
+}
+
+/// Result of `import_identifiers`: the stub products created and the
+/// enrichment operation queued for them
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentifierImport {
+    #[serde(alias = "product_ids")]
+    pub product_ids: Vec<i64>,
+    pub duplicates: Vec<String>, // Already in the catalog or repeated in the file
+    pub invalid: Vec<String>,    // Lines that are neither an ASIN nor a URL
+    pub operation: Option<BulkOperation>, // None when nothing new was imported
+}
@@ -0,0 +1,50 @@
+use crate::models::daily_metrics::ProductMetrics;
+use serde::{Deserialize, Serialize};
+
+/// A product in a bundle, in the order its ads list them
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleMember {
+    pub product_id: i64,
+    pub product_name: String,
+    pub price_range: Option<String>,
+    pub image_url: Option<String>,
+    pub position: i64,
+}
+
+/// Products promoted together under one name ("Home Gym Starter Kit")
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Bundle {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub members: Vec<BundleMember>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// Creates a bundle when `id` is unset, otherwise replaces its name,
+/// description, and members; `product_ids` are in display order
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveBundleInput {
+    pub id: Option<i64>,
+    pub name: String,
+    pub description: Option<String>,
+    pub product_ids: Vec<i64>,
+}
+
+/// Clicks and conversions summed over every member product
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BundlePerformance {
+    pub bundle_id: i64,
+    pub start: String, // YYYY-MM-DD
+    pub end: String,
+    pub clicks: i64,
+    pub conversions: i64,
+    pub revenue: f64,
+    pub commission: f64,
+    pub members: Vec<ProductMetrics>, // In bundle order
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// A selected product left out of a catalog feed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedSkip {
+    pub product_id: i64,
+    pub name: String,
+    pub reason: String, // Every failed required attribute, e.g. "link: no active affiliate link"
+}
+
+/// Result of writing a product catalog feed
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogFeedExport {
+    pub path: String,
+    pub format: String, // "csv" or "xml"
+    pub exported: usize,
+    pub skipped: Vec<FeedSkip>,
+}
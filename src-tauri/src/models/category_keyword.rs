@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// How strongly categories containing a keyword suit an ad type or platform
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryKeyword {
+    pub id: i64,
+    pub kind: String,    // "ad_type" or "platform"
+    pub target: String,  // Ad type key ("video_script", ...) or platform ("tiktok", ...)
+    pub keyword: String, // Empty = any category no other keyword matches
+    pub score: f64,      // 0-1
+    pub builtin: bool,   // Shipped with the app; can be reset but not deleted
+    pub edited: bool,    // A shipped row whose score differs from the default
+}
+
+/// Updates the keyword when `id` is set, adds one otherwise
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveCategoryKeywordInput {
+    pub id: Option<i64>,
+    pub kind: String,
+    pub target: String,
+    pub keyword: Option<String>,
+    pub score: f64,
+}
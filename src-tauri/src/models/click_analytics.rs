@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+/// 7x24 matrix of click counts. Rows are weekdays (0 = Sunday .. 6 = Saturday),
+/// columns are hours of the day (0-23) in the requested UTC offset.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ClickHeatmap {
+    #[serde(alias = "link_id")]
+    pub link_id: Option<i64>,
+    #[serde(alias = "product_id")]
+    pub product_id: Option<i64>,
+    #[serde(alias = "utc_offset_minutes")]
+    pub utc_offset_minutes: i32,
+    pub matrix: Vec<Vec<i64>>,
+    #[serde(alias = "total_clicks")]
+    pub total_clicks: i64,
+    #[serde(alias = "peak_slots")]
+    pub peak_slots: Vec<HeatmapSlot>, // Busiest weekday/hour combinations, best first
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapSlot {
+    pub weekday: u32, // 0 = Sunday
+    pub hour: u32,
+    pub clicks: i64,
+}
+
+/// Clicks per normalized traffic source, busiest first
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ClicksBySource {
+    pub link_id: Option<i64>,
+    pub product_id: Option<i64>,
+    pub days: i64,
+    pub total_clicks: i64, // Human clicks only
+    pub bot_clicks: i64,   // Filtered out of `sources`
+    pub sources: Vec<SourceClicks>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceClicks {
+    pub source: String, // e.g. "tiktok", "pinterest", "google", "direct", "other"
+    pub clicks: i64,
+    pub share: f64,     // Fraction of total clicks, 0-1
+}
+
+/// Clicks per visitor country, busiest first
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ClicksByCountry {
+    pub link_id: Option<i64>,
+    pub product_id: Option<i64>,
+    pub days: i64,
+    pub total_clicks: i64,   // Human clicks only
+    pub unknown_clicks: i64, // Logged without a GeoIP database or not found in it
+    pub bot_clicks: i64,     // Filtered out of `countries`
+    pub countries: Vec<CountryClicks>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CountryClicks {
+    pub country: String, // ISO 3166-1 alpha-2
+    pub clicks: i64,
+    pub share: f64,      // Fraction of located clicks, 0-1
+    pub regions: Vec<RegionClicks>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionClicks {
+    pub region: String, // Subdivision ISO code; only City databases provide one
+    pub clicks: i64,
+}
+
+/// The user-supplied MaxMind database used to locate clicks
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoIpStatus {
+    pub database_path: Option<String>,
+    pub database_type: Option<String>, // e.g. "GeoLite2-City"
+    pub built_at: Option<String>,      // YYYY-MM-DD
+    pub error: Option<String>,         // Set when the configured file can't be read
+}
+
+/// Raw clicks split into human traffic and filtered bot traffic
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ClickQuality {
+    pub link_id: Option<i64>,
+    pub product_id: Option<i64>,
+    pub days: i64,
+    pub raw_clicks: i64,
+    pub human_clicks: i64,
+    pub bot_clicks: i64,
+    pub bot_share: f64, // bot_clicks / raw_clicks, 0-1
+    pub reasons: Vec<BotReasonClicks>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BotReasonClicks {
+    pub reason: String, // "bot_user_agent", "missing_user_agent", or "rapid_repeat"
+    pub clicks: i64,
+}
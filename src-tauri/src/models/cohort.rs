@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// What a cohort's products earned in one month of their life
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CohortMonth {
+    pub offset: i64,   // Months since the cohort month; 0 = the month the products were added
+    pub month: String, // YYYY-MM
+    pub clicks: i64,
+    pub conversions: i64,
+    pub commission: f64,
+    pub active_products: i64, // Products with at least one click
+    pub cumulative_commission_per_product: f64,
+}
+
+/// Products added in the same calendar month
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductCohort {
+    pub cohort: String, // YYYY-MM the products were added
+    pub product_count: i64,
+    pub clicks: i64,
+    pub commission: f64,
+    pub months: Vec<CohortMonth>, // Every month from the cohort month to now
+}
+
+/// Cohorts oldest first; compare the same offset across cohorts to see
+/// whether newer research finds products that mature faster
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductCohortReport {
+    pub cohorts: Vec<ProductCohort>,
+    pub through: String, // YYYY-MM-DD, today
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// An angle as proposed by the AI provider (or the mock), before it is stored
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AngleDraft {
+    #[serde(alias = "angle_type")]
+    pub angle_type: String, // 'problem_agitate_solve', 'social_proof', 'comparison', 'unboxing', ...
+    pub hook: String,
+    pub direction: String,
+}
+
+/// A stored, reusable content angle
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentAngle {
+    pub id: i64,
+    #[serde(alias = "product_id")]
+    pub product_id: i64,
+    #[serde(alias = "angle_type")]
+    pub angle_type: String,
+    pub hook: String,
+    pub direction: String,
+    #[serde(alias = "served_by")]
+    pub served_by: String,
+    #[serde(alias = "created_at")]
+    pub created_at: Option<String>,
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionEvent {
+    pub id: Option<i64>,
+    #[serde(alias = "link_id")]
+    pub link_id: i64,
+    #[serde(alias = "campaign_id")]
+    pub campaign_id: Option<i64>,
+    #[serde(alias = "converted_at")]
+    pub converted_at: Option<String>,
+    #[serde(alias = "order_value")]
+    pub order_value: Option<f64>,
+    pub commission: Option<f64>,
+    pub status: String,          // 'pending', 'approved', 'rejected'
+    #[serde(alias = "order_id")]
+    pub order_id: Option<String>,
+    pub source: Option<String>,  // 'manual', 'postback'
+    pub subid: Option<String>,   // Sub ID / click reference reported by the network
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PostbackConfig {
+    pub enabled: bool,
+    pub port: u16,
+    #[serde(alias = "signature_mode")]
+    pub signature_mode: String,  // "none", "token", "hmac_sha256"
+    pub secret: Option<String>,
+    #[serde(alias = "subid_param")]
+    pub subid_param: String,     // Query parameter carrying the sub ID (e.g. "subid")
+    #[serde(alias = "amount_param")]
+    pub amount_param: String,    // Query parameter carrying the order amount
+    #[serde(alias = "commission_param")]
+    pub commission_param: String,
+    #[serde(alias = "order_id_param")]
+    pub order_id_param: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PostbackStatus {
+    pub running: bool,
+    pub port: Option<u16>,
+    #[serde(alias = "postback_url")]
+    pub postback_url: Option<String>, // Local template; networks need it behind a public tunnel
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PostbackLogEntry {
+    pub id: i64,
+    #[serde(alias = "query_string")]
+    pub query_string: String,
+    pub subid: Option<String>,
+    pub accepted: bool,
+    #[serde(alias = "conversion_id")]
+    pub conversion_id: Option<i64>,
+    pub error: Option<String>,
+    #[serde(alias = "received_at")]
+    pub received_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributionConfig {
+    #[serde(alias = "dedup_window_minutes")]
+    pub dedup_window_minutes: i64,    // Same IP + user agent within this window counts once
+    #[serde(alias = "attribution_window_days")]
+    pub attribution_window_days: i64, // Max days between click and conversion
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkAttributionStats {
+    #[serde(alias = "link_id")]
+    pub link_id: i64,
+    #[serde(alias = "product_name")]
+    pub product_name: String,
+    pub platform: String,
+    #[serde(alias = "raw_clicks")]
+    pub raw_clicks: i64,
+    #[serde(alias = "bot_clicks")]
+    pub bot_clicks: i64, // Part of raw_clicks; excluded from unique clicks and attribution
+    #[serde(alias = "unique_clicks")]
+    pub unique_clicks: i64,
+    pub conversions: i64,
+    #[serde(alias = "attributed_conversions")]
+    pub attributed_conversions: i64, // Conversions with a qualifying click inside the window
+    #[serde(alias = "conversion_rate")]
+    pub conversion_rate: f64,        // attributed_conversions / unique_clicks
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Rubric scores, 1 (weak) to 10 (strong) per criterion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RubricScores {
+    #[serde(alias = "hook_strength")]
+    pub hook_strength: i32,
+    pub clarity: i32,
+    pub cta: i32,
+    pub specificity: i32,
+    pub compliance: i32, // banned claims, risky promises, missing disclosure
+}
+
+/// A critique as returned by the AI provider or the heuristic scorer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CritiqueDraft {
+    #[serde(flatten)]
+    pub scores: RubricScores,
+    pub suggestions: Vec<String>,
+}
+
+/// A stored critique
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AdCritique {
+    pub id: i64,
+    #[serde(alias = "ad_id")]
+    pub ad_id: i64,
+    #[serde(flatten)]
+    pub scores: RubricScores,
+    pub overall: f64,
+    pub suggestions: Vec<String>,
+    #[serde(alias = "served_by")]
+    pub served_by: String,
+    #[serde(alias = "created_at")]
+    pub created_at: Option<String>,
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// Clicks and conversions for one day, summed over the links in scope
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyMetric {
+    pub day: String, // YYYY-MM-DD
+    pub clicks: i64, // Human clicks
+    pub bot_clicks: i64,
+    pub unique_visitors: i64,
+    pub conversions: i64, // Rejected conversions are left out
+    pub revenue: f64,
+    pub commission: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductMetrics {
+    pub product_id: i64,
+    pub product_name: String,
+    pub clicks: i64,
+    pub commission: f64,
+}
+
+/// How far the nightly rollup has materialized
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyMetricsStatus {
+    pub through: Option<String>, // Last materialized day; later days are read from raw events
+    pub rows: i64,
+}
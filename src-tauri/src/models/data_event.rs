@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+/// Payload of the `products://changed`, `links://changed`, and `ads://changed` events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DataChanged {
+    pub action: String, // "created", "updated", "deleted"
+    pub ids: Vec<i64>,
+}
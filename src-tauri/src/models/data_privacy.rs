@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Result of `export_all_data`
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DataExportSummary {
+    pub path: String,
+    pub tables: i64,
+    pub rows: i64,
+}
+
+/// Result of `wipe_all_data`
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DataWipeSummary {
+    pub tables_cleared: Vec<String>,
+    pub rows_deleted: i64,
+    pub files_deleted: i64, // Attachments, and media files the app kept under its data directory
+}
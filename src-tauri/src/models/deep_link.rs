@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// Network identifiers of an affiliate program, used to build deep links.
+/// The publisher's own ID comes from the network's saved credentials.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramNetworkIds {
+    pub program_name: String,
+    pub network: String,                 // "impact", "cj", "shareasale", or "awin"
+    pub merchant_id: Option<String>,     // Impact campaign ID, ShareASale merchant ID, Awin advertiser ID
+    pub ad_id: Option<String>,           // Impact ad ID, ShareASale banner ID
+    pub tracking_domain: Option<String>, // Impact brand tracking domain, e.g. "goto.example.com"
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildDeepLinkRequest {
+    pub product_id: i64,
+    pub program: String,         // Affiliate program name
+    pub destination_url: String, // Any page on the merchant's site
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// A statement that ran slower than the threshold, grouped by its SQL text
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowQuery {
+    pub sql: String, // String literals redacted; bound parameters are never recorded
+    pub count: i64,
+    pub total_ms: f64,
+    pub max_ms: f64,
+    pub last_ms: f64,
+    pub last_seen: String, // RFC 3339
+}
+
+/// Slow statements seen since the app started
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowQueryReport {
+    pub threshold_ms: i64,
+    pub queries: Vec<SlowQuery>, // Slowest total time first
+}
+
+/// `EXPLAIN QUERY PLAN` for one of the main list queries
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlan {
+    pub name: String, // e.g. "ads_for_product"
+    pub sql: String,
+    pub plan: Vec<String>, // One line per plan step, indented by depth
+    pub full_scan: bool,   // Some table is read without an index
+    pub temp_sort: bool,   // Sorting needs a temporary B-tree
+}
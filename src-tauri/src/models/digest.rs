@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// Metrics summarized for one digest period
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyDigest {
+    #[serde(alias = "period_start")]
+    pub period_start: String,
+    #[serde(alias = "period_end")]
+    pub period_end: String,
+    #[serde(alias = "new_products")]
+    pub new_products: i64,
+    #[serde(alias = "new_ads")]
+    pub new_ads: i64,
+    #[serde(alias = "new_links")]
+    pub new_links: i64,
+    pub clicks: i64,
+    pub conversions: i64,
+    pub earnings: f64,
+    #[serde(alias = "broken_links")]
+    pub broken_links: Vec<DigestLink>,  // Links currently marked 'invalid'
+    #[serde(alias = "expired_links")]
+    pub expired_links: Vec<DigestLink>, // Links whose program has expired
+    #[serde(alias = "top_products")]
+    pub top_products: Vec<DigestProduct>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestLink {
+    #[serde(alias = "link_id")]
+    pub link_id: i64,
+    #[serde(alias = "product_name")]
+    pub product_name: String,
+    pub platform: String,
+    #[serde(alias = "program_name")]
+    pub program_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestProduct {
+    #[serde(alias = "product_id")]
+    pub product_id: i64,
+    #[serde(alias = "product_name")]
+    pub product_name: String,
+    pub clicks: i64,
+    pub earnings: f64,
+}
+
+/// A stored digest record
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestRecord {
+    pub id: i64,
+    #[serde(alias = "period_start")]
+    pub period_start: String,
+    #[serde(alias = "period_end")]
+    pub period_end: String,
+    pub digest: WeeklyDigest,
+    #[serde(alias = "summary_text")]
+    pub summary_text: String,
+    pub notified: bool,
+    #[serde(alias = "created_at")]
+    pub created_at: Option<String>,
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// A structured read query for `query_entities`, e.g. products with their
+/// links and latest ad in one call
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityQuery {
+    pub entity: String, // "products", "links", "ads"
+    #[serde(default)]
+    pub fields: Vec<String>, // Columns to return; empty = all ("id" is always included)
+    #[serde(default)]
+    pub filters: Vec<QueryFilter>,
+    #[serde(default)]
+    pub sort: Vec<QuerySort>,
+    #[serde(default)]
+    pub include: Vec<String>, // Relations to embed, e.g. "links", "latest_ad", "product"
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// `field <op> value`; ops: eq, ne, lt, lte, gt, gte, like, in, is_null, not_null
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryFilter {
+    pub field: String,
+    pub op: String,
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct QuerySort {
+    pub field: String,
+    #[serde(default)]
+    pub desc: bool,
+}
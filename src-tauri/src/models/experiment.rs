@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+/// One arm of an experiment: an ad, a link, or an ad posted with a link
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExperimentVariant {
+    pub id: i64,
+    pub name: String,
+    pub ad_id: Option<i64>,
+    pub ad_headline: Option<String>,
+    pub link_id: Option<i64>,
+    pub link_name: Option<String>,
+    pub position: i64, // 0 = control
+}
+
+/// A structured A/B test
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Experiment {
+    pub id: i64,
+    pub name: String,
+    pub hypothesis: Option<String>,
+    pub success_metric: String, // "ctr" or "conversion_rate"
+    pub status: String,         // "running" or "stopped"
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub variants: Vec<ExperimentVariant>, // Control first
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExperimentVariantInput {
+    pub name: String,
+    pub ad_id: Option<i64>,
+    pub link_id: Option<i64>, // Default: the ad's attached link
+}
+
+/// Starts an experiment now; the first variant is the control
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StartExperimentInput {
+    pub name: String,
+    pub hypothesis: Option<String>,
+    pub success_metric: String,
+    pub variants: Vec<ExperimentVariantInput>,
+}
+
+/// How one variant did on the success metric, compared with the control
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantResult {
+    pub variant_id: i64,
+    pub name: String,
+    pub is_control: bool,
+    pub trials: i64,            // Impressions for CTR, clicks for conversion rate
+    pub successes: i64,         // Clicks for CTR, conversions for conversion rate
+    pub rate: Option<f64>,      // None without trials
+    pub lift: Option<f64>,      // Relative to the control, 0.25 = +25%; None for the control
+    pub z_score: Option<f64>,   // Two-proportion z-test against the control
+    pub p_value: Option<f64>,   // Two-sided
+    pub significant: bool,      // p below 1 - confidence level
+}
+
+/// Lift and significance of every variant over the experiment's run
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExperimentAnalysis {
+    pub experiment: Experiment,
+    pub start: String,
+    pub end: String,                    // Stop time, or now while running
+    pub confidence_level: f64,          // 0.95
+    pub variants: Vec<VariantResult>,   // Control first
+    pub winner_variant_id: Option<i64>, // Best significant improvement over the control
+}
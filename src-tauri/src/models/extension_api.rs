@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionApiStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+    #[serde(alias = "base_url")]
+    pub base_url: String,
+    pub token: String, // Pasted into the extension's options page
+}
+
+/// Affiliate link as returned to the browser extension
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ExtensionLink {
+    pub id: i64,
+    pub product_id: Option<i64>,
+    pub product_name: String,
+    pub platform: String,
+    pub program_name: String,
+    pub tracking_url: String,
+}
+
+/// Ad copy snippet as returned to the browser extension
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct ExtensionAdSnippet {
+    pub id: i64,
+    pub product_id: Option<i64>,
+    pub headline: String,
+    pub body_text: Option<String>,
+    pub cta: Option<String>,
+    pub ad_type: Option<String>,
+}
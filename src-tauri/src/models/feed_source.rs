@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// An RSS or Atom feed polled for product finds
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedSource {
+    pub id: i64,
+    pub name: String,
+    pub url: String,
+    pub active: bool,
+    pub last_polled_at: Option<String>,
+    pub last_error: Option<String>, // Why the most recent poll failed, if it did
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveFeedSourceInput {
+    pub id: Option<i64>, // Update when set, create otherwise
+    pub name: String,
+    pub url: String,
+    pub active: bool,
+}
+
+/// Outcome of polling one feed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedPollReport {
+    pub source_id: i64,
+    pub name: String,
+    pub items_read: i64,
+    pub new_items: i64,         // Items not seen on an earlier poll
+    pub idea_ids: Vec<i64>,     // Inbox ideas for their product links
+    pub error: Option<String>,
+}
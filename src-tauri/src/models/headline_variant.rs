@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Breakdown of a headline's heuristic CTR score
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CtrSignals {
+    #[serde(alias = "length_points")]
+    pub length_points: i32,
+    #[serde(alias = "has_number")]
+    pub has_number: bool,
+    #[serde(alias = "power_words")]
+    pub power_words: Vec<String>,
+    pub curiosity: bool,      // question, open loop, or "why/how" framing
+    #[serde(alias = "direct_address")]
+    pub direct_address: bool, // speaks to "you"
+    pub penalties: Vec<String>,
+}
+
+/// A stored alternative headline for an ad
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadlineVariant {
+    pub id: i64,
+    #[serde(alias = "ad_id")]
+    pub ad_id: i64,
+    pub headline: String,
+    #[serde(alias = "ctr_score")]
+    pub ctr_score: f64, // 0-100
+    pub signals: CtrSignals,
+    #[serde(alias = "is_selected")]
+    pub is_selected: bool,
+    #[serde(alias = "served_by")]
+    pub served_by: String,
+    #[serde(alias = "created_at")]
+    pub created_at: Option<String>,
+}
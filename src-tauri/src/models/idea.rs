@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Idea {
+    pub id: Option<i64>,
+    pub url: String,
+    pub note: Option<String>,
+    pub source: Option<String>, // "manual", "clipboard", "extension", "rss", ...
+    pub status: String,         // "new", "promoted", "dismissed"
+    #[serde(alias = "product_id")]
+    pub product_id: Option<i64>,
+    #[serde(alias = "captured_at")]
+    pub captured_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureIdeaInput {
+    pub url: String,
+    pub note: Option<String>,
+    pub source: Option<String>,
+}
+
+/// Payload of the `clipboard-product-url` event sent to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardProductUrl {
+    pub url: String,
+    pub platform: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardWatcherStatus {
+    pub enabled: bool,
+    pub running: bool,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// One product card on the link hub page
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkHubEntry {
+    #[serde(alias = "product_id")]
+    pub product_id: i64,
+    pub name: String,
+    #[serde(alias = "image_url")]
+    pub image_url: Option<String>,
+    pub blurb: String,
+    pub links: Vec<LinkHubLink>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkHubLink {
+    pub label: String, // "Shop on Amazon", ...
+    pub url: String,
+}
+
+/// Result of writing the static site
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkHubExport {
+    pub path: String, // index.html that was written
+    #[serde(alias = "product_count")]
+    pub product_count: usize,
+    #[serde(alias = "link_count")]
+    pub link_count: usize,
+    #[serde(alias = "skipped_product_ids")]
+    pub skipped_product_ids: Vec<i64>, // Selected products without an active link
+}
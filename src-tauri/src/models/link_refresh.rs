@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// The fields of an affiliate link a refresh can change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkSnapshot {
+    pub platform: String,
+    pub program_name: String,
+    pub commission_rate: Option<f64>,
+    pub cookie_duration: Option<i32>,
+    pub tracking_url: String,
+    pub destination_url: String,
+    pub status: String,
+}
+
+/// One refresh that changed a link, with the link before and after it
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkRefreshDiff {
+    pub id: i64,
+    pub link_id: i64,
+    pub product_id: i64,
+    pub product_name: String,
+    pub operation_id: Option<i64>, // Bulk refresh run; None for a single refresh
+    pub before: LinkSnapshot,
+    pub after: LinkSnapshot,
+    pub changed_fields: Vec<String>, // camelCase names of the snapshot fields that differ
+    pub refreshed_at: Option<String>,
+}
+
+/// How many refreshes changed a field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshFieldCount {
+    pub field: String,
+    pub count: i64,
+}
+
+/// Every refresh change in a period, newest first
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshDiffReport {
+    pub period: String,        // "24h", "7d", "30d", "90d", or "all"
+    pub since: Option<String>, // None for "all"
+    pub links_changed: i64,
+    pub field_counts: Vec<RefreshFieldCount>, // Most changed first
+    pub diffs: Vec<LinkRefreshDiff>,
+}
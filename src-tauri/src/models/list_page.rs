@@ -0,0 +1,51 @@
+use crate::models::affiliate_link::AffiliateLink;
+use crate::models::product::Product;
+use serde::{Deserialize, Serialize};
+
+/// Filters for the product table; all optional and combined with AND
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductListFilter {
+    pub search: Option<String>, // Name, category, or description contains this
+    pub category: Option<String>,
+    pub stage: Option<String>,
+    pub tag: Option<String>,
+}
+
+/// Filters for the link table; all optional and combined with AND
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkListFilter {
+    pub search: Option<String>, // Product or program name contains this
+    pub product_id: Option<i64>,
+    pub status: Option<String>,
+    pub platform: Option<String>,
+    pub program_name: Option<String>,
+}
+
+/// One window of a filtered list plus the total it was cut from, for
+/// virtualized tables
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductPage {
+    pub items: Vec<Product>,
+    pub total: i64,
+    pub offset: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkPage {
+    pub items: Vec<AffiliateLink>,
+    pub total: i64,
+    pub offset: i64,
+}
+
+/// Result of `export_products_csv`
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductCsvExport {
+    pub path: String,
+    pub rows: usize,
+    pub columns: Vec<String>, // In file order, e.g. "name", "link_url:amazon", "total_clicks"
+}
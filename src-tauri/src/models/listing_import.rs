@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// A marketplace page listings can be imported from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingSource {
+    pub platform: String, // "etsy" or "ebay"
+    pub kind: String,     // "shop", "seller", or "search"
+    pub name: String,     // Shop or seller name, or the search terms
+    pub url: String,
+}
+
+/// Outcome of importing a shop's, seller's, or search's listings
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingImport {
+    pub source: ListingSource,
+    pub pages_read: i64,
+    pub found: i64, // Distinct listings on the pages read
+    pub product_ids: Vec<i64>,
+    pub duplicates: Vec<String>, // Listing URLs already in the catalog
+}
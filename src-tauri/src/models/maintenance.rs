@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+/// Which cleanup rules to apply; every rule is off unless set
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupRules {
+    /// Delete non-favorite ads created more than this many days ago
+    #[serde(default, alias = "delete_ads_older_than_days")]
+    pub delete_ads_older_than_days: Option<i64>,
+    /// Remove links marked 'invalid'
+    #[serde(default, alias = "remove_invalid_links")]
+    pub remove_invalid_links: bool,
+    /// Remove links marked 'expired'
+    #[serde(default, alias = "remove_expired_links")]
+    pub remove_expired_links: bool,
+    /// Purge placeholder-campaign ads (campaign_id = 1) with no product
+    #[serde(default, alias = "purge_orphaned_ads")]
+    pub purge_orphaned_ads: bool,
+}
+
+/// A row matched by a cleanup rule
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupItem {
+    pub table: String, // "ad_copies" or "affiliate_links"
+    pub id: i64,
+    pub label: String,
+    pub rule: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupReport {
+    #[serde(alias = "dry_run")]
+    pub dry_run: bool,
+    pub items: Vec<CleanupItem>,
+    #[serde(alias = "ads_removed")]
+    pub ads_removed: i64, // Would-be counts when dry_run is set
+    #[serde(alias = "links_removed")]
+    pub links_removed: i64,
+}
+
+/// A data integrity problem and the fix `apply_fixes` would make
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityIssue {
+    pub kind: String, // e.g. "ad_missing_product", "unknown_credential_platform"
+    pub table: String,
+    pub id: Option<i64>,
+    pub description: String,
+    pub fix: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    pub issues: Vec<IntegrityIssue>,
+    #[serde(alias = "fixes_applied")]
+    pub fixes_applied: bool,
+    #[serde(alias = "fixed_count")]
+    pub fixed_count: i64,
+}
+
+/// How long raw data is kept; 0 keeps it forever
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPolicy {
+    pub click_days: i64, // Older clicks are folded into daily rollups, then deleted
+    pub log_days: i64,   // Postback log and webhook deliveries
+}
+
+/// What one retention pass removed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionReport {
+    pub clicks_rolled_up: i64,
+    pub rollup_days: i64, // (day, link) rows written or topped up
+    pub postbacks_deleted: i64,
+    pub webhook_deliveries_deleted: i64,
+    pub ai_cache_expired: i64,
+}
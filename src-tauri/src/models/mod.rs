@@ -1,3 +1,70 @@
 pub mod product;
 pub mod affiliate_link;
 pub mod affiliate_credentials;
+pub mod conversion;
+pub mod traffic;
+pub mod digest;
+pub mod click_analytics;
+pub mod niche_profile;
+pub mod idea;
+pub mod scraped_product;
+pub mod extension_api;
+pub mod maintenance;
+pub mod bulk_operation;
+pub mod ai_discovery;
+pub mod research;
+pub mod content_angle;
+pub mod review;
+pub mod link_hub;
+pub mod sms;
+pub mod pinterest;
+pub mod short_video;
+pub mod spreadsheet_import;
+pub mod store_sync;
+pub mod style_rules;
+pub mod critique;
+pub mod headline_variant;
+pub mod ad_template;
+pub mod ad_kit;
+pub mod ad_export;
+pub mod ad_import;
+pub mod webhook;
+pub mod rest_api;
+pub mod entity_query;
+pub mod data_event;
+pub mod workspace;
+pub mod program_application;
+pub mod network_terms;
+pub mod deep_link;
+pub mod pretty_link;
+pub mod readiness;
+pub mod scheduled_post;
+pub mod retail_event;
+pub mod approval;
+pub mod shared_workspace;
+pub mod role;
+pub mod data_privacy;
+pub mod daily_metrics;
+pub mod diagnostics;
+pub mod list_page;
+pub mod watchlist;
+pub mod product_comparison;
+pub mod bundle;
+pub mod note;
+pub mod attachment;
+pub mod promotion_queue;
+pub mod platform_mix;
+pub mod benchmark;
+pub mod recommendation_trace;
+pub mod category_keyword;
+pub mod audience;
+pub mod trend;
+pub mod variation;
+pub mod experiment;
+pub mod cohort;
+pub mod link_refresh;
+pub mod read_only;
+pub mod listing_import;
+pub mod tiktok_sync;
+pub mod catalog_feed;
+pub mod feed_source;
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Operational terms of an affiliate network
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkTerms {
+    pub id: i64,
+    pub network: String,
+    pub platform: Option<String>, // Discovery platform this network backs, e.g. "amazon"
+    pub min_payout: Option<f64>,
+    pub payout_currency: Option<String>,
+    pub payment_schedule: Option<String>,
+    pub cookie_days: Option<i32>, // None when each merchant sets its own
+    pub cookie_policy: Option<String>,
+    pub deep_link_support: Option<bool>,
+    pub notes: Option<String>,
+    pub builtin: bool, // Shipped with the app; can be edited but not deleted
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveNetworkTermsInput {
+    pub id: Option<i64>, // Update when set, create otherwise
+    pub network: String,
+    pub platform: Option<String>,
+    pub min_payout: Option<f64>,
+    pub payout_currency: Option<String>,
+    pub payment_schedule: Option<String>,
+    pub cookie_days: Option<i32>,
+    pub cookie_policy: Option<String>,
+    pub deep_link_support: Option<bool>,
+    pub notes: Option<String>,
+}
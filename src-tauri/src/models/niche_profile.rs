@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NicheProfile {
+    pub id: Option<i64>,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(alias = "preferred_platforms")]
+    pub preferred_platforms: Vec<String>, // "tiktok", "instagram", "amazon", ...
+    #[serde(alias = "typical_audience")]
+    pub typical_audience: Option<String>, // e.g. "Age 30-50, home gym owners"
+    #[serde(alias = "banned_claims")]
+    pub banned_claims: Vec<String>,       // e.g. "clinically proven", "cure"
+    pub active: bool,
+    #[serde(alias = "created_at")]
+    pub created_at: Option<String>,
+    #[serde(alias = "updated_at")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveNicheProfileInput {
+    pub id: Option<i64>, // Update when set, create otherwise
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(alias = "preferred_platforms")]
+    pub preferred_platforms: Vec<String>,
+    #[serde(alias = "typical_audience")]
+    pub typical_audience: Option<String>,
+    #[serde(alias = "banned_claims")]
+    pub banned_claims: Vec<String>,
+}
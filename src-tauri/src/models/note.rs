@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// An entity a note refers to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteRef {
+    pub entity_type: String, // "product", "link", "ad", "campaign"
+    pub entity_id: i64,
+    pub label: Option<String>, // Product or campaign name, link URL, ad headline
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Note {
+    pub id: i64,
+    pub title: Option<String>,
+    pub body: String, // Markdown
+    pub refs: Vec<NoteRef>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// Creates a note when `id` is unset, otherwise replaces it. References are
+/// `refs` plus any `[[product:12]]`-style backlinks in the body.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveNoteInput {
+    pub id: Option<i64>,
+    pub title: Option<String>,
+    pub body: String,
+    #[serde(default)]
+    pub refs: Vec<NoteRef>, // `label` is ignored
+}
+
+/// A search match with the matching text highlighted
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteSearchHit {
+    pub note: Note,
+    pub snippet: String, // Matches wrapped in ** for Markdown bold
+}
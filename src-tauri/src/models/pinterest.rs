@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Pin fields stored under `pinterest` in a pin ad's `platform_specific_data`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PinMetadata {
+    pub title: String,       // max 100 characters
+    pub description: String, // max 500 characters, keywords woven in
+    #[serde(alias = "alt_text")]
+    pub alt_text: String,    // max 500 characters
+    pub keywords: Vec<String>,
+    #[serde(alias = "board_suggestions")]
+    pub board_suggestions: Vec<String>,
+}
+
+/// Result of a Pinterest bulk upload CSV export
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PinterestExport {
+    pub path: String,
+    pub exported: usize,
+    #[serde(alias = "skipped_ad_ids")]
+    pub skipped_ad_ids: Vec<i64>, // not pin ads, or the product has no image
+    pub warnings: Vec<String>,     // e.g. pins exported without alt text
+}
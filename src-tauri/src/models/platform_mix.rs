@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+/// Share of the effort going to one platform
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformAllocation {
+    pub platform: String,
+    pub share: f64, // Relative weight; a scenario's shares are normalized to sum to 1
+}
+
+/// A named way of splitting impressions across platforms
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MixScenario {
+    pub name: String,
+    pub allocations: Vec<PlatformAllocation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationAssumptions {
+    pub impressions: i64, // Total reach to split across platforms
+    #[serde(default)]
+    pub scenarios: Vec<MixScenario>, // Empty: an even split plus all-in on each platform
+    pub average_order_value: Option<f64>, // Default: middle of the product's price range
+    pub commission_rate: Option<f64>,     // Default: best rate available for the product
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformProjection {
+    pub platform: String,
+    pub share: f64, // Normalized
+    pub impressions: i64,
+    pub ctr: f64,
+    pub conversion_rate: f64,
+    pub order_value: f64, // Average order value after the benchmark's multiplier
+    pub clicks: f64,
+    pub conversions: f64,
+    pub revenue: f64,
+    pub earnings: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioProjection {
+    pub name: String,
+    pub platforms: Vec<PlatformProjection>,
+    pub clicks: f64,
+    pub conversions: f64,
+    pub earnings: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformMixSimulation {
+    pub product_id: i64,
+    pub product_name: String,
+    pub impressions: i64,
+    pub average_order_value: f64, // Before each benchmark's multiplier
+    pub commission_rate: f64,
+    pub scenarios: Vec<ScenarioProjection>, // Highest earnings first
+    pub warnings: Vec<String>,              // Assumptions that fell back to a guess
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// A `/go/<slug>` path on the user's site and the tracking URL it redirects to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PrettyLink {
+    pub link_id: i64,
+    pub product_id: i64,
+    pub name: String,
+    pub slug: String,
+    pub path: String, // e.g. "/go/bamboo-cutting-board"
+    pub tracking_url: String,
+}
+
+/// Result of writing a redirects file or link-plugin import CSV
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PrettyLinksExport {
+    pub path: String,
+    pub format: String,
+    pub links: Vec<PrettyLink>,
+}
@@ -1,66 +1,199 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct Product {
     pub id: Option<i64>,
     pub name: String,
     pub category: String,
     pub description: Option<String>,
+    #[serde(alias = "price_range")]
     pub price_range: Option<String>,
+    #[serde(alias = "target_audience")]
     pub target_audience: Option<String>,
+    #[serde(alias = "trending_score")]
     pub trending_score: Option<i32>,
     pub notes: Option<String>,
+    #[serde(alias = "image_url")]
     pub image_url: Option<String>,
 
     // Affiliate platform identifiers
+    #[serde(alias = "amazon_asin")]
     pub amazon_asin: Option<String>,
+    #[serde(alias = "tiktok_product_id")]
     pub tiktok_product_id: Option<String>,
+    #[serde(alias = "instagram_product_id")]
     pub instagram_product_id: Option<String>,
+    #[serde(alias = "youtube_video_id")]
     pub youtube_video_id: Option<String>,
+    #[serde(alias = "pinterest_pin_id")]
     pub pinterest_pin_id: Option<String>,
+    #[serde(alias = "product_url")]
     pub product_url: Option<String>,
 
+    #[serde(alias = "created_at")]
     pub created_at: Option<String>,
+    #[serde(alias = "updated_at")]
     pub updated_at: Option<String>,
+
+    // Lifecycle pipeline
+    pub stage: Option<String>, // "researching", "approved", "promoting", "retired"
+    #[serde(alias = "stage_changed_at")]
+    pub stage_changed_at: Option<String>,
+
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Who last changed the product (shared workspaces)
+    #[serde(default, alias = "updated_by")]
+    pub updated_by: Option<String>,
+
+    // Stock status read from the product page
+    #[serde(default)]
+    pub availability: Option<String>, // "in_stock", "out_of_stock", "unknown"
+    #[serde(default, alias = "availability_checked_at")]
+    pub availability_checked_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateProductInput {
     pub name: String,
     pub category: String,
     pub description: Option<String>,
+    #[serde(alias = "price_range")]
     pub price_range: Option<String>,
+    #[serde(alias = "target_audience")]
     pub target_audience: Option<String>,
+    #[serde(alias = "trending_score")]
     pub trending_score: Option<i32>,
     pub notes: Option<String>,
+    #[serde(alias = "image_url")]
     pub image_url: Option<String>,
 
     // Affiliate platform identifiers
+    #[serde(alias = "amazon_asin")]
     pub amazon_asin: Option<String>,
+    #[serde(alias = "tiktok_product_id")]
     pub tiktok_product_id: Option<String>,
+    #[serde(alias = "instagram_product_id")]
     pub instagram_product_id: Option<String>,
+    #[serde(alias = "youtube_video_id")]
     pub youtube_video_id: Option<String>,
+    #[serde(alias = "pinterest_pin_id")]
     pub pinterest_pin_id: Option<String>,
+    #[serde(alias = "product_url")]
     pub product_url: Option<String>,
+
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdateProductInput {
     pub id: i64,
     pub name: Option<String>,
     pub category: Option<String>,
     pub description: Option<String>,
+    #[serde(alias = "price_range")]
     pub price_range: Option<String>,
+    #[serde(alias = "target_audience")]
     pub target_audience: Option<String>,
+    #[serde(alias = "trending_score")]
     pub trending_score: Option<i32>,
     pub notes: Option<String>,
+    #[serde(alias = "image_url")]
     pub image_url: Option<String>,
 
     // Affiliate platform identifiers
+    #[serde(alias = "amazon_asin")]
     pub amazon_asin: Option<String>,
+    #[serde(alias = "tiktok_product_id")]
     pub tiktok_product_id: Option<String>,
+    #[serde(alias = "instagram_product_id")]
     pub instagram_product_id: Option<String>,
+    #[serde(alias = "youtube_video_id")]
     pub youtube_video_id: Option<String>,
+    #[serde(alias = "pinterest_pin_id")]
     pub pinterest_pin_id: Option<String>,
+    #[serde(alias = "product_url")]
     pub product_url: Option<String>,
+
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+
+    /// Set stock status by hand: "in_stock", "out_of_stock", or "unknown"
+    #[serde(default)]
+    pub availability: Option<String>,
+
+    /// The `updated_at` the edit was based on; the update is rejected if the
+    /// product has changed since (e.g. a teammate saved it first)
+    #[serde(default, alias = "expected_updated_at")]
+    pub expected_updated_at: Option<String>,
+}
+
+/// Lifecycle stage of a product in the promotion pipeline
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, specta::Type)]
+pub enum ProductStage {
+    Researching,
+    Approved,
+    Promoting,
+    Retired,
+}
+
+impl ProductStage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProductStage::Researching => "researching",
+            ProductStage::Approved => "approved",
+            ProductStage::Promoting => "promoting",
+            ProductStage::Retired => "retired",
+        }
+    }
+
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "researching" => Some(ProductStage::Researching),
+            "approved" => Some(ProductStage::Approved),
+            "promoting" => Some(ProductStage::Promoting),
+            "retired" => Some(ProductStage::Retired),
+            _ => None,
+        }
+    }
+
+    /// All stages in pipeline order
+    pub fn all() -> Vec<ProductStage> {
+        vec![
+            ProductStage::Researching,
+            ProductStage::Approved,
+            ProductStage::Promoting,
+            ProductStage::Retired,
+        ]
+    }
+
+    /// Whether a product may move from this stage to `next`.
+    /// Products advance one step at a time, can step back one stage,
+    /// can be retired from anywhere, and retired products restart research.
+    pub fn can_transition_to(self, next: ProductStage) -> bool {
+        use ProductStage::*;
+        matches!(
+            (self, next),
+            (Researching, Approved)
+                | (Researching, Retired)
+                | (Approved, Promoting)
+                | (Approved, Researching)
+                | (Approved, Retired)
+                | (Promoting, Approved)
+                | (Promoting, Retired)
+                | (Retired, Researching)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StageCount {
+    pub stage: String,
+    pub count: i64,
 }
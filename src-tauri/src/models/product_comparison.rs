@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// A program a product can be promoted through
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionOption {
+    pub program_name: String,
+    pub platform: Option<String>,
+    pub commission_rate: Option<f64>, // Fraction, 0.08 = 8%
+    pub cookie_days: Option<i64>,
+    pub has_link: bool, // An active affiliate link exists for it
+}
+
+/// One product's column of a comparison
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparedProduct {
+    pub product_id: i64,
+    pub name: String,
+    pub category: String,
+    pub stage: Option<String>,
+    pub availability: Option<String>,
+    pub price_range: Option<String>,
+    pub price_low: Option<f64>,
+    pub price_high: Option<f64>,
+    pub trending_score: i32,
+    pub weekly_clicks: Vec<i64>, // Oldest week first
+    pub trend: String,           // "rising", "falling", "flat"
+    pub commission_options: Vec<CommissionOption>,
+    pub best_commission_rate: Option<f64>,
+    // Performance over the comparison period
+    pub clicks: i64,
+    pub conversions: i64,
+    pub revenue: f64,
+    pub commission: f64,
+    pub conversion_rate: Option<f64>,    // Conversions per click; None without clicks
+    pub earnings_per_click: Option<f64>, // Commission per click
+}
+
+/// One attribute across every compared product
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparisonRow {
+    pub key: String, // e.g. "price", "best_commission", "clicks"
+    pub label: String,
+    pub values: Vec<Option<String>>, // Formatted, in `products` order
+    pub best: Option<usize>,         // Index of the leading product, for rows with a clear winner
+}
+
+/// Result of `compare_products`
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductComparison {
+    pub products: Vec<ComparedProduct>,
+    pub rows: Vec<ComparisonRow>,
+    pub period_days: i64,
+    pub context: String, // Markdown table for comparison-article prompts
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a signup with an affiliate program stands
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramApplication {
+    pub id: i64,
+    pub program_name: String,
+    pub platform: Option<String>,
+    pub status: String, // 'not_applied', 'applied', 'approved', 'rejected'
+    pub signup_url: Option<String>,
+    pub applied_at: Option<String>,
+    pub decided_at: Option<String>, // Set once approved or rejected
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub notes: Option<String>,
+    pub remind_at: Option<String>, // Next follow-up while the application is pending
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// Creates or updates the application for `program_name`. Dates left empty
+/// are filled in from the status change (applied now, decided now, reminder
+/// two weeks after applying).
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveProgramApplicationInput {
+    pub program_name: String,
+    pub platform: Option<String>,
+    pub status: String,
+    pub signup_url: Option<String>,
+    pub applied_at: Option<String>,
+    pub decided_at: Option<String>,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub notes: Option<String>,
+    pub remind_at: Option<String>,
+}
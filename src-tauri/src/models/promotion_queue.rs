@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// One input to a product's opportunity score
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreFactor {
+    pub key: String, // "trending", "commission", "competition", "epc", "seasonality"
+    pub label: String,
+    pub value: f64,  // 0-1, higher is better
+    pub weight: f64, // Share of the total score; weights sum to 1
+    pub detail: String,
+}
+
+/// A product in the "promote next" ranking
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PromotionCandidate {
+    pub product_id: i64,
+    pub product_name: String,
+    pub category: String,
+    pub score: f64, // 0-100
+    pub factors: Vec<ScoreFactor>,
+    pub reasons: Vec<String>, // Strongest factors, in plain words
+}
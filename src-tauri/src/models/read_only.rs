@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether the workspace refuses changes, and why
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadOnlyStatus {
+    pub enabled: bool,
+    pub reason: Option<String>, // Shown in the error, e.g. "Presenting" or "Opened from a backup"
+    pub since: Option<String>,  // YYYY-MM-DD HH:MM:SS, UTC
+}
+
+/// Error returned (JSON-encoded) by any command that would change a read-only
+/// workspace. Turn read-only off and retry.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadOnlyError {
+    pub code: String,    // "read_only"
+    pub command: String, // The command that was refused, e.g. "update_product"
+    pub message: String,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Which checks a product must pass before it is promoted. Stored in settings.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessConfig {
+    pub require_image: bool,
+    pub require_link: bool,
+    pub min_ads: i64, // 0 disables the ad count check
+    pub require_disclosure: bool,
+    pub require_price_verified: bool,
+    pub price_max_age_days: i64, // A verification older than this no longer counts
+    pub disclosure_text: Option<String>, // Workspace affiliate disclosure, e.g. "#ad - I earn from qualifying purchases"
+}
+
+/// One item of the checklist
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessCheck {
+    pub key: String, // "image", "link", "ads", "disclosure", "price_verified"
+    pub label: String,
+    pub required: bool, // Disabled checks are reported but don't block
+    pub passed: bool,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductReadiness {
+    pub product_id: i64,
+    pub product_name: String,
+    pub ready: bool, // Every required check passed
+    pub checks: Vec<ReadinessCheck>,
+}
@@ -0,0 +1,67 @@
+use crate::models::affiliate_link::MatchBreakdown;
+use crate::models::audience::AudienceProfile;
+use serde::{Deserialize, Serialize};
+
+/// What a product's recommendations were scored on, after defaults and niche fill-ins
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceInputs {
+    pub category: String,
+    pub target_audience: String, // Product audience, else the niche's, else "Age 25-45"
+    pub audience: AudienceProfile,
+    pub price_range: String,
+    pub price_tier: String, // "low", "medium", "high", "premium"
+    pub trending_score: i32,
+    pub trend_momentum: Option<f64>, // Trending change per week, None without history
+    pub best_commission_rate: Option<f64>, // Fraction, 0.08 = 8%
+    pub cookie_days: Option<i64>,           // Of the best-paying program
+    pub niche: Option<String>,
+    pub preferred_platforms: Vec<String>,
+}
+
+/// One candidate platform in discovery scoring
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformTrace {
+    pub platform: String,
+    pub breakdown: MatchBreakdown, // Includes the niche boost when one applied
+    pub included: bool,            // False when at or below the discovery cut-off
+    pub rank: Option<i32>,         // 1-based position among included platforms
+}
+
+/// One ad type's factor scores from the analytics service
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AdTypeFactors {
+    pub ad_type: String,
+    pub category_score: f64,
+    pub audience_score: f64,
+    pub trending_score: f64,
+    pub platform_score: f64,
+    #[serde(default)]
+    pub commission_adjustment: f64, // Nurture vs volume shift from commission and price
+    pub total_score: f64, // Category 30%, audience 35%, trending 20%, platform 15%, plus adjustment
+}
+
+/// How the estimated engagement score was put together
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct EngagementTrace {
+    pub base_engagement: f64, // Trending score / 100, weighted 60%
+    pub platform_boost: f64,  // Top platform's audience match, weighted 40%
+    pub score: f64,
+}
+
+/// Every intermediate value behind a product's platform and ad type recommendation
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendationTrace {
+    pub inputs: TraceInputs,
+    pub platforms: Vec<PlatformTrace>, // Ranked platforms first, then excluded ones
+    pub recommended_platform: String,
+    pub recommended_ad_type: String,
+    pub ad_type_rule: String, // Why the platform maps to that ad type
+    pub engagement: EngagementTrace,
+    pub ad_type_factors: Vec<AdTypeFactors>, // Best first
+    pub fingerprint: String, // Hash of everything above; equal traces have equal fingerprints
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Structured research brief for a product, as returned by the AI provider
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ResearchBrief {
+    #[serde(alias = "pain_points")]
+    pub pain_points: Vec<String>,
+    pub objections: Vec<String>,
+    #[serde(alias = "key_benefits")]
+    pub key_benefits: Vec<String>,
+    #[serde(alias = "comparison_targets")]
+    pub comparison_targets: Vec<String>, // Competing products or alternatives
+    #[serde(alias = "content_angles")]
+    pub content_angles: Vec<String>,
+}
+
+/// A stored research brief
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductResearch {
+    pub id: i64,
+    #[serde(alias = "product_id")]
+    pub product_id: i64,
+    #[serde(flatten)]
+    pub brief: ResearchBrief,
+    pub served_by: String,
+    #[serde(alias = "created_at")]
+    pub created_at: Option<String>,
+}
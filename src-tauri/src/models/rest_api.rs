@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RestApiStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+    #[serde(alias = "allow_lan")]
+    pub allow_lan: bool, // Listen on all interfaces instead of 127.0.0.1 only
+    #[serde(alias = "base_url")]
+    pub base_url: String,
+    #[serde(alias = "lan_url")]
+    pub lan_url: Option<String>, // Set when LAN access is on and an address was found
+    pub token: String,
+}
+
+/// Workspace totals returned by `GET /v1/stats`
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RestApiStats {
+    pub days: i64,
+    pub products: i64,
+    #[serde(alias = "active_links")]
+    pub active_links: i64,
+    pub ads: i64,
+    pub clicks: i64,
+    pub conversions: i64,
+    pub revenue: f64,
+    pub commission: f64,
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+/// A holiday or shopping event, falling on a fixed day or the nth weekday of a month
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RetailEvent {
+    pub id: i64,
+    pub name: String,
+    pub month: u32,                 // 1-12
+    pub day: Option<u32>,           // Fixed day of the month
+    pub weekday: Option<u32>,       // 0 = Sunday; used with week_of_month when day is None
+    pub week_of_month: Option<i32>, // 1-5, or -1 for the last one
+    pub offset_days: i64,           // Shift from the rule, e.g. Black Friday is Thanksgiving + 1
+    pub lead_days: i64,             // How far ahead promotion should start
+    pub categories: Vec<String>,    // Keywords matched against product categories and tags
+    pub enabled: bool,
+    pub builtin: bool, // Shipped with the app; can be edited but not deleted
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveRetailEventInput {
+    pub id: Option<i64>, // Update when set, create otherwise
+    pub name: String,
+    pub month: u32,
+    pub day: Option<u32>,
+    pub weekday: Option<u32>,
+    pub week_of_month: Option<i32>,
+    pub offset_days: Option<i64>,
+    pub lead_days: Option<i64>,
+    pub categories: Vec<String>,
+    pub enabled: Option<bool>,
+}
+
+/// The next date an event falls on
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingEvent {
+    pub event: RetailEvent,
+    pub date: String, // YYYY-MM-DD
+    pub days_until: i64,
+    pub promote_from: String, // Date promotion should start (lead_days before, not before today)
+}
+
+/// Products worth promoting ahead of an upcoming event
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CampaignSuggestion {
+    pub event: UpcomingEvent,
+    pub categories: Vec<String>, // Product categories that matched
+    pub product_ids: Vec<i64>,   // Highest trending first
+    pub product_names: Vec<String>,
+    pub message: String, // e.g. "Valentine's Day in 30 days — Beauty & Skincare products to promote"
+}
+
+/// How seasonal a product is right now and the event driving it
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductSeasonality {
+    pub product_id: i64,
+    pub score: f64, // 0-1; 1 inside the event's promotion window
+    pub event: Option<UpcomingEvent>,
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// A quotable line lifted verbatim from a review
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetDraft {
+    pub quote: String,
+    pub theme: Option<String>,
+}
+
+/// An objection raised across reviews
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectionDraft {
+    pub objection: String,
+    pub theme: Option<String>,
+    pub mentions: i32,
+}
+
+/// Review summary as returned by the AI provider (or the heuristics)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewDigest {
+    pub snippets: Vec<SnippetDraft>,
+    pub objections: Vec<ObjectionDraft>,
+}
+
+/// A stored snippet or objection
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewInsight {
+    pub id: i64,
+    #[serde(alias = "product_id")]
+    pub product_id: i64,
+    pub kind: String, // 'snippet', 'objection'
+    pub text: String,
+    pub theme: Option<String>,
+    pub mentions: i32,
+    #[serde(alias = "served_by")]
+    pub served_by: String,
+    #[serde(alias = "created_at")]
+    pub created_at: Option<String>,
+}
+
+/// Stored insights for a product, split by kind
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewSummary {
+    #[serde(alias = "product_id")]
+    pub product_id: i64,
+    #[serde(alias = "review_count")]
+    pub review_count: usize, // Reviews summarized (0 when only loading stored insights)
+    pub snippets: Vec<ReviewInsight>,
+    pub objections: Vec<ReviewInsight>,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// The role this app session has. Without an owner password everyone is owner.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleStatus {
+    pub password_set: bool,
+    pub role: String,                   // "owner" or "contributor"
+    pub unlocked_until: Option<String>, // RFC 3339; when an unlocked owner session drops back to contributor
+}
+
+/// Error returned (JSON-encoded) by commands restricted to the owner.
+/// Unlock with the owner password and retry.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerRequiredError {
+    pub code: String,   // "owner_required"
+    pub action: String, // The command that was refused, e.g. "delete_product"
+    pub message: String,
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+/// A calendar entry: one post of a product's ad on a platform
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledPost {
+    pub id: i64,
+    pub product_id: i64,
+    pub product_name: String,
+    pub ad_id: Option<i64>,
+    pub platform: String,
+    pub scheduled_at: String, // UTC, "YYYY-MM-DD HH:MM:SS"
+    pub status: String,       // 'scheduled', 'posted', 'skipped', 'cancelled'
+    pub notes: Option<String>,
+    pub repeat_every_days: Option<i64>, // Set on the head of a recurring series
+    pub repeat_until: Option<String>,
+    pub rotate_ads: bool,
+    pub series_id: Option<i64>, // The series head an occurrence was expanded from
+    pub approval_state: String, // 'draft', 'in_review', 'approved', 'published'
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// Creates (no `id`) or updates a scheduled post. Setting `repeat_every_days`
+/// makes it the head of a recurring series that is expanded onto the calendar.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveScheduledPostInput {
+    pub id: Option<i64>,
+    pub product_id: i64,
+    pub ad_id: Option<i64>,
+    pub platform: String,
+    pub scheduled_at: String,
+    pub notes: Option<String>,
+    pub repeat_every_days: Option<i64>,
+    pub repeat_until: Option<String>,
+    pub rotate_ads: Option<bool>, // Defaults to true
+    pub allow_conflicts: Option<bool>, // Save despite posting cap conflicts when caps are enforced
+}
+
+/// A weekday/hour in the audience's local time and how well it scores
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestedSlot {
+    pub weekday: u32, // 0 = Sunday
+    pub hour: u32,
+    pub score: f64, // 0-1
+    pub scheduled_at: String, // Next occurrence of the slot, UTC
+}
+
+/// Recommended time to post a product on a platform
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PostTimeSuggestion {
+    pub product_id: i64,
+    pub platform: String,
+    pub utc_offset_minutes: i32,
+    pub best: SuggestedSlot,
+    pub alternatives: Vec<SuggestedSlot>,
+    pub clicks_considered: i64, // Human clicks behind the heatmap component
+    pub reasons: Vec<String>,
+}
+
+/// Posting limits for one platform; `None` means no cap
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformPostingCap {
+    pub platform: String,
+    pub daily_cap: Option<i64>,
+    pub weekly_cap: Option<i64>, // Per calendar week, Monday to Sunday
+}
+
+/// Frequency caps and double-booking rules for the posting calendar. Stored in settings.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PostingRules {
+    pub caps: Vec<PlatformPostingCap>,
+    pub duplicate_window_hours: i64, // Same product on the same platform closer than this is a double booking
+    pub enforce: bool,               // Reject conflicting posts instead of warning
+}
+
+/// A cap or double booking a post runs into
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleConflict {
+    pub kind: String, // "daily_cap", "weekly_cap", "duplicate", "out_of_stock", "missing_alt_text"
+    pub platform: String,
+    pub message: String,
+    pub post_ids: Vec<i64>, // Posts already on the calendar that it conflicts with
+}
+
+/// A saved post and the conflicts it was saved with (warn mode)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedScheduledPost {
+    pub post: ScheduledPost,
+    pub conflicts: Vec<ScheduleConflict>,
+}
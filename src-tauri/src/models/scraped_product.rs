@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Product details extracted from a product page
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrapedProduct {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    #[serde(alias = "image_url")]
+    pub image_url: Option<String>,
+    pub price: Option<f64>,
+    pub currency: Option<String>,
+    pub availability: Option<String>, // Raw schema.org / og availability value
+    #[serde(alias = "amazon_asin")]
+    pub amazon_asin: Option<String>,
+    #[serde(alias = "source_platform")]
+    pub source_platform: Option<String>, // "amazon", "tiktok", "etsy", ...
+}
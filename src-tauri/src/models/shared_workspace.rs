@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Where this install keeps its database and who is editing. Stored in
+/// `workspace.json` in the app data directory, outside the database itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceLocation {
+    pub database_path: Option<String>, // A database on a shared drive; None uses the local one
+    pub editor_name: Option<String>,   // Recorded on changes and shown to teammates
+}
+
+/// The cooperative lock held by whoever is editing a shared database
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceLock {
+    pub editor: String,
+    pub host: String,
+    pub pid: u32,
+    pub acquired_at: String,  // RFC 3339
+    pub heartbeat_at: String, // Refreshed every minute while the app runs
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedWorkspaceStatus {
+    pub database_path: String,
+    pub shared: bool,
+    pub editor_name: String,
+    pub lock: Option<WorkspaceLock>,
+    pub held_by_me: bool,
+    pub lock_stale: bool, // The holder stopped refreshing it (crashed or lost the drive)
+}
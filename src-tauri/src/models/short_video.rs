@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Companion output for a `video_script` ad, stored under `short_video` in its
+/// `platform_specific_data`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortVideoCompanion {
+    pub caption: String,
+    pub hashtags: Vec<String>,
+    pub overlays: Vec<SceneOverlay>,
+    #[serde(alias = "sound_note")]
+    pub sound_note: String, // placeholder; trending sounds change daily
+}
+
+/// On-screen text for one scene of the script
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneOverlay {
+    pub scene: String, // script section: HOOK, PROBLEM, SOLUTION, BENEFIT, CTA
+    pub text: String,
+    #[serde(alias = "start_seconds")]
+    pub start_seconds: u32,
+    #[serde(alias = "duration_seconds")]
+    pub duration_seconds: u32,
+}
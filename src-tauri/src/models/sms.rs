@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// Character encoding and segment count an SMS body will be billed as
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SmsSegments {
+    pub encoding: String, // "gsm7" or "ucs2"
+    pub characters: usize, // GSM-7 extension characters count twice
+    pub segments: usize,
+}
+
+/// Compliance check for an SMS ad
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SmsComplianceReport {
+    #[serde(alias = "ad_id")]
+    pub ad_id: i64,
+    pub body: String, // the body as it will be sent, opt-out text included
+    #[serde(alias = "opt_out_added")]
+    pub opt_out_added: bool,
+    pub segments: SmsSegments,
+    pub timezone: String,
+    #[serde(alias = "send_at")]
+    pub send_at: Option<String>,
+    #[serde(alias = "within_quiet_hours")]
+    pub within_quiet_hours: bool,
+    pub issues: Vec<String>,
+}
+
+/// A recipient in a contact list export
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SmsContact {
+    pub phone: String,
+    #[serde(alias = "first_name")]
+    pub first_name: Option<String>,
+}
+
+/// Result of exporting an SMS campaign for a contact list
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SmsExport {
+    pub path: String,
+    pub exported: usize,
+    #[serde(alias = "skipped_phones")]
+    pub skipped_phones: Vec<String>, // not valid E.164 numbers
+    pub segments: SmsSegments,
+}
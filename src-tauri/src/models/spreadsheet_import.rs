@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// A field a spreadsheet column can fill in one kind of import
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportField {
+    pub name: String, // e.g. "tracking_url"
+    pub label: String,
+    pub required: bool, // Alternatives such as product name or ID are listed in `missing_required` instead
+}
+
+/// A spreadsheet column assigned to a field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnMapping {
+    pub column: usize, // 0-based index into `ImportAnalysis::columns`
+    pub field: String,
+}
+
+/// How a file would import as one kind of data
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportTargetSuggestion {
+    pub target: String, // "products", "links", or "earnings"
+    pub fields: Vec<ImportField>,
+    pub mappings: Vec<ColumnMapping>,    // Columns recognized from their headers
+    pub missing_required: Vec<String>,   // e.g. "Date", "Product or Product ID"
+}
+
+/// What `analyze_import_file` found in a spreadsheet
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportAnalysis {
+    pub path: String,
+    pub delimiter: String, // ",", ";", or "\t"
+    pub columns: Vec<String>,
+    pub row_count: usize,
+    pub sample_rows: Vec<Vec<String>>,    // The first rows, to preview the mappings against
+    pub suggested_target: Option<String>, // None when no kind of import has its required columns
+    pub targets: Vec<ImportTargetSuggestion>, // Every kind, in `TARGETS` order
+}
+
+/// A reviewed mapping to run; start from an `ImportTargetSuggestion`
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPlan {
+    pub path: String,
+    pub target: String,
+    pub mappings: Vec<ColumnMapping>,
+    #[serde(default)]
+    pub create_missing_products: bool, // Links: create products not yet in the catalog instead of skipping the row
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRowSkip {
+    pub row: usize, // 1-based, excluding the header
+    pub reason: String,
+}
+
+/// Outcome of `execute_import`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResult {
+    pub target: String,
+    pub imported: usize,
+    pub imported_ids: Vec<i64>,        // Product, link, or conversion ids, by target
+    pub created_product_ids: Vec<i64>, // Products created for link rows
+    pub skipped: Vec<ImportRowSkip>,   // Missing or unparseable values, unmatched products or links, duplicates
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// A store SKU and the catalog product it keeps in sync
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreSkuMapping {
+    pub id: i64,
+    pub store: String, // "shopify" or "woocommerce"
+    pub sku: String,   // "id:<external id>" for store items without a SKU
+    pub external_id: Option<String>,
+    pub product_id: i64,
+    pub product_name: String,
+    pub last_price: Option<f64>,
+    pub last_image_url: Option<String>,
+    pub last_synced_at: Option<String>, // None until the SKU is seen in the store
+}
+
+/// Outcome of importing from or syncing with a store
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreSyncReport {
+    pub store: String,
+    pub fetched: i64,                  // Store items read, one per variant
+    pub created_product_ids: Vec<i64>, // Imports only
+    pub matched_product_ids: Vec<i64>, // Imports only: existing products mapped by name
+    pub updated_product_ids: Vec<i64>, // Mapped products whose price or image changed
+    pub unmapped: i64,                 // Syncs only: store items no product is mapped to
+}
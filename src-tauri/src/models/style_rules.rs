@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Formatting rules applied to generated copy for one platform
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StyleRules {
+    pub platform: String,
+    #[serde(alias = "emoji_density")]
+    pub emoji_density: String,  // "none", "light" (one per post), "heavy" (one per paragraph)
+    pub capitalization: String, // headline: "as_is", "sentence", "title", "upper"
+    #[serde(alias = "line_breaks")]
+    pub line_breaks: String,    // "as_is", "airy" (blank line between paragraphs), "compact", "single_line"
+    #[serde(alias = "link_placement")]
+    pub link_placement: String, // "as_is", "start", "end", "bio" ("link in bio" instead of a URL)
+}
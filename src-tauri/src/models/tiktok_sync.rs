@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Outcome of `sync_tiktok_products`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TikTokSyncReport {
+    pub source: String, // "shop" when a shop cipher is configured, else "showcase"
+    pub fetched: i64,
+    pub linked_product_ids: Vec<i64>,  // Existing products matched by name and given their TikTok id
+    pub created_product_ids: Vec<i64>, // New products for TikTok items no product matched
+    pub already_linked: i64,           // Items some product already carries the TikTok id of
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// One day of UTM-campaign traffic reported by an external analytics provider
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficRow {
+    #[serde(alias = "utm_campaign")]
+    pub utm_campaign: String,
+    #[serde(alias = "utm_source")]
+    pub utm_source: String,
+    pub date: String, // YYYY-MM-DD
+    pub sessions: i64,
+    pub events: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficSession {
+    pub id: i64,
+    #[serde(alias = "link_id")]
+    pub link_id: Option<i64>,
+    pub provider: String, // "ga4", "plausible"
+    #[serde(alias = "utm_campaign")]
+    pub utm_campaign: String,
+    #[serde(alias = "utm_source")]
+    pub utm_source: String,
+    pub date: String,
+    pub sessions: i64,
+    pub events: i64,
+    #[serde(alias = "imported_at")]
+    pub imported_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsImportSummary {
+    pub provider: String,
+    #[serde(alias = "start_date")]
+    pub start_date: String,
+    #[serde(alias = "end_date")]
+    pub end_date: String,
+    #[serde(alias = "rows_fetched")]
+    pub rows_fetched: usize,
+    #[serde(alias = "rows_matched")]
+    pub rows_matched: usize,
+    #[serde(alias = "unmatched_campaigns")]
+    pub unmatched_campaigns: Vec<String>,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// A product's trending score at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendPoint {
+    pub trending_score: i32,
+    pub recorded_at: String,
+}
+
+/// Which way a product's trending score is heading
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendMomentum {
+    pub direction: String,     // "rising", "declining", or "steady"
+    pub change_per_week: f64,  // Least-squares slope, in score points per week
+    pub first_score: i32,
+    pub latest_score: i32,
+    pub points: i64,
+    pub days: i64,             // Span from the first to the latest point
+}
+
+/// A product's recent trending scores and their momentum
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendHistory {
+    pub product_id: i64,
+    pub points: Vec<TrendPoint>,           // Oldest first
+    pub momentum: Option<TrendMomentum>,   // None with fewer than two points a day apart
+}
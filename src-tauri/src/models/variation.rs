@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Impressions and clicks for one ad on one platform and day, as reported by
+/// the platform; recording the same day again replaces its figures
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordVariationPerformanceInput {
+    pub ad_id: i64,
+    pub platform: String,
+    pub day: Option<String>, // YYYY-MM-DD; default today
+    pub impressions: i64,
+    pub clicks: i64,
+}
+
+/// One of a product's ads competing for the next post on a platform
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct VariationArm {
+    pub ad_id: i64,
+    pub variation_name: Option<String>,
+    pub headline: String,
+    pub impressions: i64,
+    pub clicks: i64,
+    pub observed_ctr: Option<f64>, // None before any impressions
+    pub sampled_ctr: f64,          // This draw from the arm's posterior
+}
+
+/// The ad to post next on a platform and the draw that chose it
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NextVariation {
+    pub product_id: i64,
+    pub platform: String,
+    pub ad_id: i64,
+    pub prior_ctr: f64,          // Platform benchmark for the product's category
+    pub exploring: bool,         // The chosen ad isn't the one with the best observed CTR
+    pub arms: Vec<VariationArm>, // Highest sampled CTR first
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// A watched product with the state seen on its last check
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchedProduct {
+    pub product_id: i64,
+    pub product_name: String,
+    pub product_url: Option<String>,
+    pub last_title: Option<String>,
+    pub last_price: Option<f64>,
+    pub last_availability: Option<String>, // "in_stock", "out_of_stock", "unknown"
+    pub last_checked_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
+/// One field that differed between two checks of a watched product
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductChange {
+    pub id: i64,
+    pub product_id: i64,
+    pub product_name: String,
+    pub field: String, // "title", "price", or "availability"; store syncs also log "image"
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub detected_at: Option<String>,
+}
+
+/// Outcome of checking the watchlist
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchCheckReport {
+    pub checked: i64,
+    pub failed: i64,
+    pub changes: Vec<ProductChange>,
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// An outgoing webhook endpoint
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub id: i64,
+    pub name: String,
+    pub url: String,
+    pub secret: Option<String>, // Signs payloads as X-AffilAI-Signature when set
+    pub events: Vec<String>,    // "ad_created", "link_created", "conversion_recorded"; empty = all
+    pub active: bool,
+    #[serde(alias = "created_at")]
+    pub created_at: Option<String>,
+    #[serde(alias = "updated_at")]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveWebhookInput {
+    pub id: Option<i64>, // Update when set, create otherwise
+    pub name: String,
+    pub url: String,
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub events: Vec<String>,
+    pub active: bool,
+}
+
+/// One attempt to deliver an event to a webhook
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    pub id: i64,
+    #[serde(alias = "webhook_id")]
+    pub webhook_id: i64,
+    pub event: String,
+    #[serde(alias = "status_code")]
+    pub status_code: Option<u16>,
+    pub success: bool,
+    pub error: Option<String>,
+    #[serde(alias = "delivered_at")]
+    pub delivered_at: Option<String>,
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Rows created by `load_demo_data`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DemoDataSummary {
+    pub products: usize,
+    pub links: usize,
+    pub ads: usize,
+    pub clicks: usize,
+    pub conversions: usize,
+    pub days: i64, // Click and conversion history spans this many days back
+}
+
+/// Result of `reset_workspace`
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceReset {
+    pub tables_cleared: Vec<String>,
+    pub rows_deleted: i64,
+}
+
+/// First-run choice and which seed pack the workspace has
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SeedStatus {
+    pub preference: Option<String>, // "seeded" or "blank"; None until the user chooses
+    pub applied_version: i64,       // 0 when no seed products were ever added
+    pub latest_version: i64,
+    pub update_available: bool,     // Newer pack for a seeded workspace
+}
+
+/// Result of seeding products
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SeedResult {
+    pub product_ids: Vec<i64>, // Starter products added by this call
+    pub skipped: usize,        // Seed products already in the workspace (matched by name)
+    pub seed_version: i64,
+}
@@ -0,0 +1,102 @@
+//! Accessible Alt Text
+//!
+//! Visual ad formats get alt text for the product image stored with the ad
+//! (`ad_copies.alt_text`). The text names the product and what it is without
+//! "image of" filler, and stays under the length screen readers read in one go.
+//! Exports and schedules call `missing_alt_text_warning` so a visual ad never
+//! leaves the app without it unnoticed.
+
+use crate::commands::ad_generation::GeneratedAdCopy;
+use crate::models::product::Product;
+
+/// Screen readers commonly stop announcing alt text around this length
+pub const ALT_TEXT_MAX: usize = 125;
+
+/// `ScheduleConflict::kind` for a post of a visual ad without alt text
+pub const MISSING_ALT_TEXT_CONFLICT: &str = "missing_alt_text";
+
+/// Ad types that are published with the product image
+pub const VISUAL_AD_TYPES: [&str; 5] = ["social_post", "story", "carousel", "video_script", "pinterest_pin"];
+
+pub fn is_visual(ad_type: Option<&str>) -> bool {
+    ad_type.map(|t| VISUAL_AD_TYPES.contains(&t)).unwrap_or(false)
+}
+
+/// Alt text for a product's image: name, category, and the gist of the description
+pub fn alt_text(product: &Product) -> String {
+    let detail = product
+        .description
+        .as_deref()
+        .and_then(|d| d.split_inclusive(['.', '!', '?']).next())
+        .map(|d| d.trim().trim_end_matches(['.', '!', '?']))
+        .filter(|d| !d.is_empty())
+        .map(|d| format!(" - {}", d))
+        .unwrap_or_default();
+    let text = format!("{}, {}{}", product.name, product.category.to_lowercase(), detail);
+
+    if text.chars().count() <= ALT_TEXT_MAX {
+        return text;
+    }
+    let cut: String = text.chars().take(ALT_TEXT_MAX).collect();
+    match cut.rfind(' ') {
+        Some(idx) => cut[..idx].trim_end_matches([',', '-', ' ']).to_string(),
+        None => cut,
+    }
+}
+
+/// Warning for a visual ad that would be published without alt text
+pub fn missing_alt_text_warning(ad: &GeneratedAdCopy) -> Option<String> {
+    let missing = ad.alt_text.as_deref().map(str::trim).unwrap_or("").is_empty();
+    (is_visual(ad.ad_type.as_deref()) && missing).then(|| {
+        format!(
+            "Ad {} ({}) has no alt text for its image",
+            ad.id.unwrap_or_default(),
+            ad.variation_name.as_deref().unwrap_or(&ad.headline)
+        )
+    })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alt_text_uses_first_sentence_within_limit() {
+        let mut product = Product {
+            id: Some(1),
+            name: "Glow Serum".to_string(),
+            category: "Beauty & Skincare".to_string(),
+            description: Some("A vitamin C serum in a frosted glass dropper bottle. Brightens skin.".to_string()),
+            price_range: None,
+            target_audience: None,
+            trending_score: None,
+            notes: None,
+            image_url: None,
+            amazon_asin: None,
+            tiktok_product_id: None,
+            instagram_product_id: None,
+            youtube_video_id: None,
+            pinterest_pin_id: None,
+            product_url: None,
+            created_at: None,
+            updated_at: None,
+            stage: None,
+            stage_changed_at: None,
+            tags: Vec::new(),
+            updated_by: None,
+            availability: None,
+            availability_checked_at: None,
+        };
+        assert_eq!(
+            alt_text(&product),
+            "Glow Serum, beauty & skincare - A vitamin C serum in a frosted glass dropper bottle"
+        );
+
+        product.description = Some("word ".repeat(60));
+        assert!(alt_text(&product).chars().count() <= ALT_TEXT_MAX);
+    }
+}
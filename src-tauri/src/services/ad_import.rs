@@ -0,0 +1,206 @@
+//! Ad Copy Library Import
+//!
+//! Brings previously written ads (CSV or JSON, e.g. from a spreadsheet) into
+//! `ad_copies` so historical copy lives alongside generated ads. Rows are
+//! matched to products by ID, then by exact name (case-insensitive); rows
+//! without a product, without a headline, or identical to an existing ad are
+//! skipped and reported.
+
+use crate::commands::ad_generation::AdType;
+use crate::database::parse_timestamp;
+use crate::models::ad_import::{AdImportSkip, AdImportSummary, ImportedAdRow};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+/// Parses a CSV library; headers are lowercased with spaces as underscores
+pub fn parse_csv(data: &str) -> Result<Vec<ImportedAdRow>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(data.as_bytes());
+
+    let headers: csv::StringRecord = reader
+        .headers()
+        .map_err(|e| format!("Invalid CSV header: {}", e))?
+        .iter()
+        .map(|h| h.trim_start_matches('\u{FEFF}').to_lowercase().replace([' ', '-'], "_"))
+        .collect();
+    reader.set_headers(headers);
+
+    reader
+        .deserialize()
+        .enumerate()
+        .map(|(idx, row)| row.map_err(|e| format!("Row {}: {}", idx + 1, e)))
+        .collect()
+}
+
+/// Parses a JSON library: an array of ads, or an object with an `ads` array
+pub fn parse_json(data: &str) -> Result<Vec<ImportedAdRow>, String> {
+    let value: serde_json::Value = serde_json::from_str(data).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let ads = match value {
+        serde_json::Value::Object(mut object) => object.remove("ads").unwrap_or_default(),
+        other => other,
+    };
+    serde_json::from_value(ads).map_err(|e| format!("Expected an array of ads: {}", e))
+}
+
+/// Reads and parses a library file by extension (.csv or .json)
+pub fn parse_file(path: &Path) -> Result<Vec<ImportedAdRow>, String> {
+    let data = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    match path.extension().and_then(|e| e.to_str()).map(str::to_lowercase).as_deref() {
+        Some("csv") => parse_csv(&data),
+        Some("json") => parse_json(&data),
+        _ => Err("Unsupported file type; expected .csv or .json".to_string()),
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Product for a row: by ID when it exists, otherwise by exact name
+fn resolve_product(conn: &Connection, row: &ImportedAdRow) -> rusqlite::Result<Option<(i64, String)>> {
+    if let Some(product_id) = row.product_id {
+        let found = conn
+            .query_row(
+                "SELECT id, name FROM products WHERE id = ?1",
+                params![product_id],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .optional()?;
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+    match non_empty(row.product_name.as_deref()) {
+        Some(name) => conn
+            .query_row(
+                "SELECT id, name FROM products WHERE name = ?1 COLLATE NOCASE ORDER BY id LIMIT 1",
+                params![name],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .optional(),
+        None => Ok(None),
+    }
+}
+
+/// Stores the rows as ads in one transaction
+pub fn import_rows(
+    conn: &mut Connection,
+    rows: &[ImportedAdRow],
+    source_file: &str,
+) -> Result<AdImportSummary, String> {
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let mut summary = AdImportSummary {
+        imported: 0,
+        imported_ad_ids: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    for (idx, row) in rows.iter().enumerate() {
+        let skip = |reason: String| AdImportSkip { row: idx + 1, reason };
+        let headline = row.headline.trim();
+        if headline.is_empty() {
+            summary.skipped.push(skip("Missing headline".to_string()));
+            continue;
+        }
+        let (product_id, product_name) = match resolve_product(&tx, row).map_err(|e| e.to_string())? {
+            Some(product) => product,
+            None => {
+                let wanted = non_empty(row.product_name.as_deref())
+                    .map(str::to_string)
+                    .or_else(|| row.product_id.map(|id| format!("#{}", id)))
+                    .unwrap_or_else(|| "(none)".to_string());
+                summary.skipped.push(skip(format!("No product matches {}", wanted)));
+                continue;
+            }
+        };
+
+        let body_text = non_empty(row.body_text.as_deref());
+        let duplicate: bool = tx
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM ad_copies
+                 WHERE product_id = ?1 AND headline = ?2 AND COALESCE(body_text, '') = ?3)",
+                params![product_id, headline, body_text.unwrap_or("")],
+                |r| r.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        if duplicate {
+            summary.skipped.push(skip(format!("Duplicate of an existing ad for {}", product_name)));
+            continue;
+        }
+
+        // Unknown types are kept as social posts; the original label stays in platform data
+        let original_type = non_empty(row.ad_type.as_deref());
+        let ad_type = original_type
+            .and_then(|t| AdType::from_string(&t.replace(' ', "_")))
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| "social_post".to_string());
+        let created_at = non_empty(row.created_at.as_deref())
+            .and_then(parse_timestamp)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string());
+        let platform_data = serde_json::json!({
+            "imported": true,
+            "source_file": source_file,
+            "original_ad_type": original_type,
+            "target_platform": non_empty(row.platform.as_deref()).map(str::to_lowercase),
+        })
+        .to_string();
+
+        tx.execute(
+            "INSERT INTO ad_copies (campaign_id, product_id, variation_name, headline, body_text,
+             cta, ad_format, ad_type, platform_specific_data, created_at)
+             VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?6, ?7, COALESCE(?8, CURRENT_TIMESTAMP))",
+            params![
+                product_id,
+                format!("{} - Imported {}", product_name, ad_type),
+                headline,
+                body_text,
+                non_empty(row.cta.as_deref()),
+                ad_type,
+                platform_data,
+                created_at,
+            ],
+        )
+        .map_err(|e| format!("Failed to import row {}: {}", idx + 1, e))?;
+        summary.imported += 1;
+        summary.imported_ad_ids.push(tx.last_insert_rowid());
+    }
+
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(summary)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_normalizes_headers() {
+        let csv = "Product Name,Title,Body,Call To Action,Type,Extra\n\
+                   Glow SPF,Meet Glow,\"Light, clear SPF\",Shop Now,Story,x\n";
+        let rows = parse_csv(csv).unwrap();
+        assert_eq!(
+            rows,
+            vec![ImportedAdRow {
+                product_name: Some("Glow SPF".to_string()),
+                headline: "Meet Glow".to_string(),
+                body_text: Some("Light, clear SPF".to_string()),
+                cta: Some("Shop Now".to_string()),
+                ad_type: Some("Story".to_string()),
+                ..Default::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_json_accepts_wrapped_array() {
+        let rows = parse_json(r#"{"ads": [{"product_id": 4, "headline": "Hi"}]}"#).unwrap();
+        assert_eq!(rows[0].product_id, Some(4));
+        assert!(parse_json(r#"{"items": []}"#).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Ad Kit Assembly
+//!
+//! Bundles everything needed to post an ad in one place: the copy as plain
+//! text and Markdown (with `[LINK]` resolved to the tracking link), the link
+//! itself, a QR code for it, and the product image. The kit is written as a
+//! folder or a single zip file.
+
+use crate::commands::ad_generation::GeneratedAdCopy;
+use qrcode::render::svg;
+use qrcode::QrCode;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+const LINK_PLACEHOLDER: &str = "[LINK]";
+const IMAGE_TIMEOUT: Duration = Duration::from_secs(20);
+const IMAGE_EXTENSIONS: [&str; 5] = ["jpg", "jpeg", "png", "webp", "gif"];
+
+/// A file to place in the kit, relative to its root
+#[derive(Debug, Clone)]
+pub struct KitFile {
+    pub name: String,
+    pub contents: Vec<u8>,
+}
+
+impl KitFile {
+    pub fn text(name: impl Into<String>, contents: impl Into<String>) -> Self {
+        KitFile {
+            name: name.into(),
+            contents: contents.into().into_bytes(),
+        }
+    }
+}
+
+/// Lowercase, hyphen-separated name safe for file systems
+pub fn slug(text: &str) -> String {
+    let slug = text
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "ad-kit".to_string()
+    } else {
+        slug
+    }
+}
+
+fn with_link(text: &str, link: Option<&str>) -> String {
+    match link {
+        Some(link) => text.replace(LINK_PLACEHOLDER, link),
+        None => text.to_string(),
+    }
+}
+
+/// File name stem for an ad's copy files
+pub fn ad_file_stem(ad: &GeneratedAdCopy) -> String {
+    format!("ad-{}-{}", ad.id.unwrap_or_default(), slug(ad.ad_type.as_deref().unwrap_or("ad")))
+}
+
+/// Plain-text copy, ready to paste into a post
+pub fn ad_text(ad: &GeneratedAdCopy, link: Option<&str>) -> String {
+    let mut sections = vec![with_link(&ad.headline, link)];
+    if let Some(body) = ad.body_text.as_deref().filter(|b| !b.trim().is_empty()) {
+        sections.push(with_link(body, link));
+    }
+    if let Some(cta) = ad.cta.as_deref().filter(|c| !c.trim().is_empty()) {
+        sections.push(format!("CTA: {}", cta));
+    }
+    if let Some(link) = link {
+        sections.push(format!("Link: {}", link));
+    }
+    if let Some(alt_text) = ad.alt_text.as_deref() {
+        sections.push(format!("Image alt text: {}", alt_text));
+    }
+    sections.join("\n\n") + "\n"
+}
+
+/// The same copy as Markdown, with the link and alt text called out
+pub fn ad_markdown(ad: &GeneratedAdCopy, product_name: &str, link: Option<&str>) -> String {
+    let mut md = format!("# {}\n\n", with_link(&ad.headline, link));
+    md.push_str(&format!(
+        "*{} - {}*\n\n",
+        product_name,
+        ad.ad_type.as_deref().unwrap_or("ad").replace('_', " ")
+    ));
+    if let Some(body) = ad.body_text.as_deref().filter(|b| !b.trim().is_empty()) {
+        md.push_str(&with_link(body, link));
+        md.push_str("\n\n");
+    }
+    if let Some(cta) = ad.cta.as_deref().filter(|c| !c.trim().is_empty()) {
+        match link {
+            Some(link) => md.push_str(&format!("**[{}]({})**\n\n", cta, link)),
+            None => md.push_str(&format!("**{}**\n\n", cta)),
+        }
+    }
+    if let Some(alt_text) = ad.alt_text.as_deref() {
+        md.push_str(&format!("> Image alt text: {}\n", alt_text));
+    }
+    md
+}
+
+/// QR code for the tracking link as an SVG document
+pub fn qr_svg(url: &str) -> Result<String, String> {
+    let code = QrCode::new(url.as_bytes()).map_err(|e| format!("Failed to encode QR code: {}", e))?;
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(256, 256)
+        .quiet_zone(true)
+        .build())
+}
+
+/// Kit file name for a downloaded image, keeping a known extension from the URL
+pub fn image_file_name(url: &str, index: usize) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .filter(|e| IMAGE_EXTENSIONS.contains(&e.as_str()))
+        .unwrap_or_else(|| "jpg".to_string());
+    format!("images/product-{}.{}", index + 1, extension)
+}
+
+/// Downloads an image for the kit
+pub async fn download_image(url: &str) -> Result<Vec<u8>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(IMAGE_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let bytes = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?
+        .error_for_status()
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", url, e))?;
+
+    Ok(bytes.to_vec())
+}
+
+/// Writes the kit as a folder at `dir`
+pub fn write_folder(dir: &Path, files: &[KitFile]) -> Result<(), String> {
+    for file in files {
+        let target = dir.join(&file.name);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&target, &file.contents)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Writes the kit as a zip archive at `path`, with every file under `root/`
+pub fn write_zip(path: &Path, root: &str, files: &[KitFile]) -> Result<(), String> {
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for file in files {
+        zip.start_file(format!("{}/{}", root, file.name), options)
+            .map_err(|e| e.to_string())?;
+        zip.write_all(&file.contents).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ad_text_resolves_link_placeholder() {
+        let ad = GeneratedAdCopy {
+            id: Some(7),
+            product_id: Some(1),
+            campaign_id: 1,
+            variation_name: None,
+            headline: "Meet Glow".to_string(),
+            body_text: Some("Shop here: [LINK]".to_string()),
+            cta: Some("Shop Now".to_string()),
+            ad_format: None,
+            ad_type: Some("social_post".to_string()),
+            platform_specific_data: None,
+            performance_score: None,
+            created_at: None,
+            updated_at: None,
+            is_favorite: false,
+            parent_ad_id: None,
+            sequence_step: None,
+            send_delay_days: None,
+            alt_text: None,
+            link_id: None,
+            approval_state: "draft".to_string(),
+        };
+
+        let text = ad_text(&ad, Some("https://go.example/glow"));
+        assert_eq!(
+            text,
+            "Meet Glow\n\nShop here: https://go.example/glow\n\nCTA: Shop Now\n\nLink: https://go.example/glow\n"
+        );
+        assert_eq!(ad_file_stem(&ad), "ad-7-social-post");
+    }
+
+    #[test]
+    fn test_image_file_name_keeps_known_extensions() {
+        assert_eq!(image_file_name("https://cdn.example/a/b.PNG?w=800", 0), "images/product-1.png");
+        assert_eq!(image_file_name("https://cdn.example/image", 1), "images/product-2.jpg");
+    }
+}
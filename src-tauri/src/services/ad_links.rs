@@ -0,0 +1,100 @@
+//! Ad Link Attachment
+//!
+//! Generated copy says `[LINK]` (or "Link in bio"); this module decides which
+//! affiliate link an ad stands for. New ads get an active link for their
+//! recommended platform when there is one, else the product's newest active
+//! link. Exports then resolve `[LINK]` to the attached link's tracking URL,
+//! falling back to the newest active link for ads without one.
+
+use crate::commands::ad_generation::{ad_copy_from_row, GeneratedAdCopy, AD_COPY_COLUMNS};
+use crate::commands::affiliate_links::{link_from_row, LINK_COLUMNS};
+use crate::models::affiliate_link::AffiliateLink;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// The link to attach given the product's active links as `(id, platform)`,
+/// newest first: the newest on `platform`, otherwise the newest overall
+pub fn preferred_link(candidates: &[(i64, String)], platform: &str) -> Option<i64> {
+    candidates
+        .iter()
+        .find(|(_, p)| p.eq_ignore_ascii_case(platform))
+        .or_else(|| candidates.first())
+        .map(|(id, _)| *id)
+}
+
+/// Picks an active link of `product_id` for an ad targeting `platform`
+pub fn select_link(conn: &Connection, product_id: i64, platform: &str) -> rusqlite::Result<Option<i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, COALESCE(platform, '') FROM affiliate_links
+         WHERE product_id = ?1 AND status = 'active'
+         ORDER BY created_at DESC, id DESC",
+    )?;
+    let candidates = stmt
+        .query_map(params![product_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(preferred_link(&candidates, platform))
+}
+
+pub fn attach(conn: &Connection, ad_id: i64, link_id: i64) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE ad_copies SET link_id = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        params![link_id, ad_id],
+    )?;
+    Ok(())
+}
+
+/// Tracking URL that `[LINK]` stands for in an ad: its attached link while
+/// that is active, else the product's newest active link
+pub fn tracking_url(conn: &Connection, product_id: Option<i64>, link_id: Option<i64>) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT tracking_url FROM affiliate_links
+         WHERE status = 'active' AND (id = ?2 OR product_id = ?1)
+         ORDER BY id = ?2 DESC, created_at DESC LIMIT 1",
+        params![product_id, link_id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Ads with no attached link, or whose attached link is no longer active.
+/// Email sequence containers are skipped; their messages are listed.
+pub fn unlinked_ads(conn: &Connection) -> rusqlite::Result<Vec<GeneratedAdCopy>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM ad_copies
+         WHERE COALESCE(ad_type, '') != 'email_sequence'
+           AND (link_id IS NULL OR link_id NOT IN (SELECT id FROM affiliate_links WHERE status = 'active'))
+         ORDER BY created_at DESC, id DESC",
+        AD_COPY_COLUMNS
+    ))?;
+    let ads = stmt.query_map([], ad_copy_from_row)?.collect();
+    ads
+}
+
+/// Active links that no ad is attached to
+pub fn unadvertised_links(conn: &Connection) -> rusqlite::Result<Vec<AffiliateLink>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM affiliate_links
+         WHERE status = 'active'
+           AND id NOT IN (SELECT link_id FROM ad_copies WHERE link_id IS NOT NULL)
+         ORDER BY created_at DESC, id DESC",
+        LINK_COLUMNS
+    ))?;
+    let links = stmt.query_map([], link_from_row)?.collect();
+    links
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preferred_link_matches_platform() {
+        let candidates = vec![(7, "amazon".to_string()), (5, "tiktok".to_string()), (3, "TikTok".to_string())];
+        assert_eq!(preferred_link(&candidates, "tiktok"), Some(5));
+        assert_eq!(preferred_link(&candidates, "instagram"), Some(7));
+        assert_eq!(preferred_link(&[], "tiktok"), None);
+    }
+}
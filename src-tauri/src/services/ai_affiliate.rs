@@ -1,4 +1,8 @@
-use crate::models::affiliate_link::{AffiliatePlatform, AffiliateProgramDiscovery};
+use crate::models::affiliate_link::{AffiliatePlatform, AffiliateProgramDiscovery, MatchBreakdown};
+use crate::services::ai_validation::validate_discovery;
+use crate::models::audience::AudienceProfile;
+use crate::services::audience;
+use crate::services::category_keywords::CategoryAffinities;
 use serde::{Deserialize, Serialize};
 
 // AI Prompt Template for Affiliate Program Discovery (with platform awareness)
@@ -12,6 +16,9 @@ Product Information:
 - Target Audience: {target_audience}
 - Trending Score: {trending_score}
 
+Niche Profile:
+{niche_context}
+
 Your task:
 1. Analyze the product metrics (target audience age, category, trending score, price)
 2. Identify the TOP 3 PLATFORMS where this product will perform best
@@ -43,6 +50,7 @@ Rules:
 - Return maximum 5 platforms, sorted by audience_match_score
 - audience_match_score weighted by age alignment (50%), category fit (25%), trending (15%), price (10%)
 - Only include legitimate platforms
+- Favor the niche's preferred platforms when audience fit is comparable
 - NO explanatory text, ONLY the JSON array"#;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +70,7 @@ pub fn build_discovery_prompt(
     price_range: &str,
     target_audience: &str,
     trending_score: i32,
+    niche_context: &str,
 ) -> String {
     AFFILIATE_DISCOVERY_PROMPT
         .replace("{product_name}", product_name)
@@ -70,26 +79,31 @@ pub fn build_discovery_prompt(
         .replace("{price_range}", price_range)
         .replace("{target_audience}", target_audience)
         .replace("{trending_score}", &trending_score.to_string())
+        .replace("{niche_context}", niche_context)
 }
 
+/// Parses and schema-validates a discovery response
 pub fn parse_ai_response(response: &str) -> Result<Vec<AffiliateProgramDiscovery>, String> {
-    let json_str = extract_json_array(response)?;
-    serde_json::from_str(&json_str)
-        .map_err(|e| format!("Failed to parse AI response: {}", e))
+    validate_discovery(response).map_err(|d| {
+        format!("Failed to parse AI response ({}): {}", d.stage, d.errors.join("; "))
+    })
 }
 
-fn extract_json_array(text: &str) -> Result<String, String> {
-    let start = text.find('[')
-        .ok_or_else(|| "No JSON array found in response".to_string())?;
-    let end = text.rfind(']')
-        .ok_or_else(|| "No JSON array found in response".to_string())?;
+/// Platforms discovery scores, in candidate order
+pub const DISCOVERY_PLATFORMS: [(&str, AffiliatePlatform); 5] = [
+    ("tiktok", AffiliatePlatform::TikTokShop),
+    ("instagram", AffiliatePlatform::InstagramShopping),
+    ("amazon", AffiliatePlatform::AmazonAssociates),
+    ("youtube", AffiliatePlatform::YouTubeShopping),
+    ("pinterest", AffiliatePlatform::PinterestBuyable),
+];
 
-    if start >= end {
-        return Err("Invalid JSON array structure".to_string());
-    }
+/// Score added per audience interest a platform is known for, up to the maximum
+const INTEREST_MATCH_BONUS: f64 = 0.05;
+const MAX_INTEREST_BONUS: f64 = 0.1;
 
-    Ok(text[start..=end].to_string())
-}
+/// Platforms scoring at or below this are left out of discovery results
+pub const MIN_PLATFORM_SCORE: f64 = 0.3;
 
 // Platform-aware mock AI discovery
 pub fn mock_ai_discovery_with_platforms(
@@ -98,68 +112,151 @@ pub fn mock_ai_discovery_with_platforms(
     trending_score: i32,
     target_audience: &str,
     price_range: &str,
+    affinities: &CategoryAffinities,
 ) -> Vec<AffiliateProgramDiscovery> {
-    let age_range = extract_age_range(target_audience);
-    let price_tier = parse_price_tier(price_range);
-
+    let profile = audience::parse(target_audience);
+    let age_range = (profile.age_min, profile.age_max);
     let mut programs = Vec::new();
 
-    // Score each platform
-    let platforms = vec![
-        ("tiktok", AffiliatePlatform::TikTokShop),
-        ("instagram", AffiliatePlatform::InstagramShopping),
-        ("amazon", AffiliatePlatform::AmazonAssociates),
-        ("youtube", AffiliatePlatform::YouTubeShopping),
-        ("pinterest", AffiliatePlatform::PinterestBuyable),
-    ];
-
-    for (platform_str, platform_enum) in platforms {
-        let score = calculate_platform_score(
-            platform_str,
-            category,
-            trending_score,
-            age_range,
-            price_tier,
-        );
-
+    for (platform_str, platform_enum, breakdown) in
+        score_platforms(category, trending_score, &profile, price_range, affinities)
+    {
         // Only include platforms with decent scores (> 0.3)
-        if score > 0.3 {
+        if breakdown.score > MIN_PLATFORM_SCORE {
             programs.push(create_program_for_platform(
                 product_name,
                 category,
                 platform_str,
                 platform_enum,
-                score,
+                breakdown,
                 age_range,
             ));
         }
     }
 
-    // Sort by audience match score (descending)
-    programs.sort_by(|a, b| {
-        b.audience_match_score
-            .partial_cmp(&a.audience_match_score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    // Sort by audience match score (descending); the sort is stable, so ties keep
+    // candidate order and identical inputs always rank identically
+    programs.sort_by(|a, b| b.audience_match_score.total_cmp(&a.audience_match_score));
 
     // Return top 5 platforms
     programs.into_iter().take(5).collect()
 }
 
+/// Scores every candidate platform, in candidate order, including those below
+/// the discovery cut-off
+pub fn score_platforms(
+    category: &str,
+    trending_score: i32,
+    profile: &AudienceProfile,
+    price_range: &str,
+    affinities: &CategoryAffinities,
+) -> Vec<(&'static str, AffiliatePlatform, MatchBreakdown)> {
+    let price_tier = parse_price_tier(price_range);
+
+    DISCOVERY_PLATFORMS
+        .into_iter()
+        .map(|(platform_str, platform_enum)| {
+            let breakdown = calculate_platform_score(
+                platform_str,
+                category,
+                trending_score,
+                profile,
+                price_tier,
+                affinities,
+            );
+            (platform_str, platform_enum, breakdown)
+        })
+        .collect()
+}
+
 fn calculate_platform_score(
     platform: &str,
     category: &str,
     trending_score: i32,
-    age_range: (i32, i32),
+    profile: &AudienceProfile,
     price_tier: PriceTier,
-) -> f64 {
-    // WEIGHTED SCORING (50% age, 25% category, 15% trending, 10% price)
-    let age_score = calculate_age_alignment(platform, age_range) * 0.50;
-    let category_score = calculate_category_fit(platform, category) * 0.25;
-    let trending_fit = calculate_trending_fit(platform, trending_score) * 0.15;
-    let price_score = calculate_price_fit(platform, price_tier) * 0.10;
-
-    age_score + category_score + trending_fit + price_score
+    affinities: &CategoryAffinities,
+) -> MatchBreakdown {
+    let mut breakdown = MatchBreakdown {
+        age_alignment: calculate_age_alignment(platform, (profile.age_min, profile.age_max)),
+        category_fit: affinities.platform_score(platform, category),
+        trending_fit: calculate_trending_fit(platform, trending_score),
+        price_fit: calculate_price_fit(platform, price_tier),
+        audience_adjustment: calculate_audience_adjustment(platform, profile),
+        locale_unsupported: profile.locale.as_deref().is_some_and(|l| !platform_serves_locale(platform, l)),
+        ..Default::default()
+    };
+    // WEIGHTED SCORING (50% age, 25% category, 15% trending, 10% price),
+    // then gender and interest skew; a platform unavailable in the locale scores zero
+    let weighted = breakdown.age_alignment * 0.50
+        + breakdown.category_fit * 0.25
+        + breakdown.trending_fit * 0.15
+        + breakdown.price_fit * 0.10;
+    breakdown.score = if breakdown.locale_unsupported {
+        0.0
+    } else {
+        (weighted + breakdown.audience_adjustment).clamp(0.0, 1.0)
+    };
+    breakdown
+}
+
+/// Gender and interest skew of a platform's users, as a score adjustment.
+/// An evenly split or unstated gender mix and no matching interests give 0.
+fn calculate_audience_adjustment(platform: &str, profile: &AudienceProfile) -> f64 {
+    // Score gained at a fully female audience (lost at a fully male one)
+    let gender_skew = match platform {
+        "pinterest" => 0.1,
+        "instagram" => 0.05,
+        "tiktok" => 0.02,
+        "youtube" => -0.05,
+        _ => 0.0,
+    };
+    let gender = profile.female_share.map_or(0.0, |share| (share - 0.5) * 2.0 * gender_skew);
+
+    let platform_interests: &[&str] = match platform {
+        "pinterest" => &["home_decor", "diy", "cooking", "gardening", "fashion", "parenting", "travel"],
+        "youtube" => &["tech", "gaming", "diy", "fitness", "music", "finance", "outdoors"],
+        "tiktok" => &["beauty", "fashion", "gaming", "music", "fitness", "cooking"],
+        "instagram" => &["fashion", "beauty", "travel", "fitness", "cooking", "home_decor"],
+        "amazon" => &["deals", "tech", "pets", "parenting"],
+        _ => &[],
+    };
+    let matches = profile.interests.iter().filter(|i| platform_interests.contains(&i.as_str())).count();
+    let interests = (matches as f64 * INTEREST_MATCH_BONUS).min(MAX_INTEREST_BONUS);
+
+    gender + interests
+}
+
+/// Whether a platform's affiliate/shopping program runs in a country
+fn platform_serves_locale(platform: &str, locale: &str) -> bool {
+    let countries: &[&str] = match platform {
+        "tiktok" => &["US", "GB", "DE", "FR", "ES", "MX", "BR", "JP"],
+        "pinterest" => &["US", "GB", "CA", "AU", "DE", "FR", "ES", "MX", "BR", "JP"],
+        "amazon" => &["US", "GB", "CA", "AU", "DE", "FR", "ES", "MX", "BR", "IN", "JP"],
+        "instagram" | "youtube" => return locale != "CN",
+        _ => return true,
+    };
+    countries.contains(&locale)
+}
+
+/// The local heuristic's breakdown for a platform, used to explain programs
+/// an AI provider scored
+pub fn match_breakdown(
+    platform: &str,
+    category: &str,
+    trending_score: i32,
+    target_audience: &str,
+    price_range: &str,
+    affinities: &CategoryAffinities,
+) -> MatchBreakdown {
+    calculate_platform_score(
+        platform,
+        category,
+        trending_score,
+        &audience::parse(target_audience),
+        parse_price_tier(price_range),
+        affinities,
+    )
 }
 
 fn calculate_age_alignment(platform: &str, age_range: (i32, i32)) -> f64 {
@@ -222,38 +319,6 @@ fn calculate_age_alignment(platform: &str, age_range: (i32, i32)) -> f64 {
     score
 }
 
-fn calculate_category_fit(platform: &str, category: &str) -> f64 {
-    match platform {
-        "tiktok" => match category {
-            "Beauty & Skincare" | "Fashion & Apparel" => 1.0,
-            "Health & Wellness" | "Fitness & Recovery" => 0.9,
-            "Consumer Electronics" => 0.7,
-            "Wearable Health Technology" => 0.8,
-            _ => 0.5,
-        },
-        "instagram" => match category {
-            "Beauty & Skincare" | "Fashion & Apparel" => 1.0,
-            "Home & Kitchen" | "Health & Wellness" => 0.9,
-            "Fitness & Recovery" => 0.8,
-            _ => 0.6,
-        },
-        "youtube" => match category {
-            "Consumer Electronics" | "Wearable Health Technology" => 1.0,
-            "Fitness & Recovery" | "Health & Wellness" => 0.9,
-            "Home & Kitchen" => 0.8,
-            _ => 0.7,
-        },
-        "pinterest" => match category {
-            "Home & Kitchen" | "Fashion & Apparel" => 1.0,
-            "Beauty & Skincare" => 0.9,
-            "Health & Wellness" => 0.8,
-            _ => 0.6,
-        },
-        "amazon" => 1.0, // Universal
-        _ => 0.5,
-    }
-}
-
 fn calculate_trending_fit(platform: &str, trending_score: i32) -> f64 {
     match platform {
         "tiktok" => {
@@ -321,9 +386,10 @@ fn create_program_for_platform(
     category: &str,
     platform: &str,
     platform_enum: AffiliatePlatform,
-    audience_match_score: f64,
+    breakdown: MatchBreakdown,
     age_range: (i32, i32),
 ) -> AffiliateProgramDiscovery {
+    let audience_match_score = breakdown.score;
     let (commission_rate, cookie_duration, program_name, affiliate_url, is_official) = match platform {
         "tiktok" => (
             0.12,
@@ -385,6 +451,9 @@ fn create_program_for_platform(
         confidence_score: 0.85 + (audience_match_score * 0.15), // Scale 0.85-1.0
         audience_match_score,
         recommendation_reason,
+        match_breakdown: Some(breakdown),
+        application_status: None,
+        network_terms: None,
     }
 }
 
@@ -400,25 +469,6 @@ fn generate_recommendation_reason(platform: &str, age_range: (i32, i32), categor
     }
 }
 
-// Helper to extract age range from target_audience string
-fn extract_age_range(target_audience: &str) -> (i32, i32) {
-    // Parse strings like "Age 18-35" or "Ages 30-50, female"
-    let age_pattern = regex::Regex::new(r"(?i)age[s]?\s+(\d+)[-–]\s*(\d+)").ok();
-
-    if let Some(re) = age_pattern {
-        if let Some(caps) = re.captures(target_audience) {
-            if let (Some(min), Some(max)) = (caps.get(1), caps.get(2)) {
-                let min_age = min.as_str().parse::<i32>().unwrap_or(25);
-                let max_age = max.as_str().parse::<i32>().unwrap_or(45);
-                return (min_age, max_age);
-            }
-        }
-    }
-
-    // Default fallback
-    (25, 45)
-}
-
 #[derive(Debug, Clone, Copy)]
 enum PriceTier {
     Low,      // < $50
@@ -427,6 +477,16 @@ enum PriceTier {
     Premium,  // > $500
 }
 
+/// The price tier a price range string scores as ("low", "medium", "high", "premium")
+pub(crate) fn price_tier_label(price_range: &str) -> &'static str {
+    match parse_price_tier(price_range) {
+        PriceTier::Low => "low",
+        PriceTier::Medium => "medium",
+        PriceTier::High => "high",
+        PriceTier::Premium => "premium",
+    }
+}
+
 fn parse_price_tier(price_range: &str) -> PriceTier {
     // Parse strings like "$30-$40" or "$300-400"
     let price_pattern = regex::Regex::new(r"\$?(\d+)").ok();
@@ -452,12 +512,14 @@ fn parse_price_tier(price_range: &str) -> PriceTier {
     PriceTier::Medium // Default
 }
 
-// Generate platform-specific tracking URL
+// Generate platform-specific tracking URL; `affiliate_id` is the user's ID on
+// the platform (placeholder Amazon tag when generating without a credential)
 pub fn generate_tracking_url(
     platform: &str,
     program_name: &str,
     product_name: &str,
     destination_url: &str,
+    affiliate_id: Option<&str>,
 ) -> String {
     let tracking_id = generate_tracking_id();
     let campaign = product_name.to_lowercase().replace(" ", "_");
@@ -472,7 +534,8 @@ pub fn generate_tracking_url(
             destination_url, campaign, tracking_id
         ),
         "amazon" => format!(
-            "https://www.amazon.com/dp/XXXXX?tag=affilai-20&linkCode=as2&ref={}",
+            "https://www.amazon.com/dp/XXXXX?tag={}&linkCode=as2&ref={}",
+            affiliate_id.unwrap_or("affilai-20"),
             tracking_id
         ),
         "youtube" => format!(
@@ -498,6 +561,58 @@ fn generate_tracking_id() -> String {
 
 // Legacy function for backward compatibility
 pub fn mock_ai_discovery(product_name: &str, category: &str) -> Vec<AffiliateProgramDiscovery> {
-    // Call new function with defaults
-    mock_ai_discovery_with_platforms(product_name, category, 70, "Age 25-45", "$50-$100")
+    // Call new function with defaults; no keywords are loaded, so category fit is neutral
+    let affinities = CategoryAffinities::default();
+    mock_ai_discovery_with_platforms(product_name, category, 70, "Age 25-45", "$50-$100", &affinities)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakdown_factors_weigh_into_audience_match() {
+        let affinities = CategoryAffinities::defaults();
+        let breakdown =
+            match_breakdown("tiktok", "Beauty & Skincare", 90, "Ages 18-25", "$20-$30", &affinities);
+        assert_eq!(
+            (breakdown.age_alignment, breakdown.category_fit, breakdown.trending_fit, breakdown.price_fit),
+            (1.0, 1.0, 1.0, 1.0)
+        );
+        assert!((breakdown.score - 1.0).abs() < 1e-9);
+
+        let programs =
+            mock_ai_discovery_with_platforms("Serum", "Beauty & Skincare", 60, "Ages 45-60", "$600", &affinities);
+        for program in &programs {
+            let b = program.match_breakdown.as_ref().unwrap();
+            let weighted =
+                b.age_alignment * 0.5 + b.category_fit * 0.25 + b.trending_fit * 0.15 + b.price_fit * 0.1;
+            assert!((weighted - program.audience_match_score).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_gender_interests_and_locale_shift_platforms() {
+        let affinities = CategoryAffinities::defaults();
+        let score = |platform: &str, audience: &str| {
+            match_breakdown(platform, "Pet Supplies", 60, audience, "$50-$100", &affinities).score
+        };
+
+        // Pinterest gains on a female, home decor audience and loses on a male one
+        let neutral = score("pinterest", "Ages 30-50");
+        assert!(score("pinterest", "Ages 30-50, women into home decor") > neutral);
+        assert!(score("pinterest", "Ages 30-50, men") < neutral);
+        assert_eq!(score("amazon", "Ages 30-50, women"), score("amazon", "Ages 30-50"));
+
+        // A platform without a program in the audience's country drops out of discovery
+        let india = "Ages 18-25, India";
+        let breakdown = match_breakdown("tiktok", "Home & Kitchen", 60, india, "$20", &affinities);
+        assert!(breakdown.locale_unsupported);
+        let programs = mock_ai_discovery_with_platforms("Lamp", "Home & Kitchen", 60, india, "$20", &affinities);
+        assert!(programs.iter().all(|p| p.platform.to_string() != "tiktok"));
+    }
 }
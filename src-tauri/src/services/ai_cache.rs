@@ -0,0 +1,85 @@
+//! AI Response Cache
+//!
+//! Provider responses are cached by a hash of provider, model, and the fully
+//! rendered prompt. Any change to the product (or niche profile) changes the
+//! prompt and therefore the key, so stale answers are never served for edited
+//! products. Entries expire after `ai_cache_ttl_hours` (default one week).
+
+use crate::database::settings::get_setting_parsed;
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+
+pub const DEFAULT_TTL_HOURS: i64 = 168;
+
+/// Cache key for a rendered prompt sent to `provider`/`model`
+pub fn cache_key(provider: &str, model: &str, prompt: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [provider, model, prompt] {
+        hasher.update(part.as_bytes());
+        hasher.update([0u8]); // separator so ("ab","c") != ("a","bc")
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Returns the cached response for `key` if present and not expired
+pub fn get(conn: &Connection, key: &str) -> rusqlite::Result<Option<String>> {
+    let response: Option<String> = conn
+        .query_row(
+            "SELECT response FROM ai_cache WHERE cache_key = ?1 AND expires_at > CURRENT_TIMESTAMP",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    if response.is_some() {
+        conn.execute(
+            "UPDATE ai_cache SET hit_count = hit_count + 1 WHERE cache_key = ?1",
+            params![key],
+        )?;
+    }
+
+    Ok(response)
+}
+
+/// Stores a response with the configured TTL
+pub fn put(conn: &Connection, key: &str, provider: &str, model: &str, response: &str) -> rusqlite::Result<()> {
+    let ttl_hours = get_setting_parsed(conn, "ai_cache_ttl_hours", DEFAULT_TTL_HOURS).max(0);
+    if ttl_hours == 0 {
+        return Ok(()); // caching disabled
+    }
+
+    // Drop expired entries while we're writing anyway
+    conn.execute("DELETE FROM ai_cache WHERE expires_at <= CURRENT_TIMESTAMP", [])?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO ai_cache (cache_key, provider, model, response, expires_at)
+         VALUES (?1, ?2, ?3, ?4, datetime('now', ?5))",
+        params![key, provider, model, response, format!("+{} hours", ttl_hours)],
+    )?;
+    Ok(())
+}
+
+/// Removes every cached response. Returns the number of entries removed.
+pub fn clear(conn: &Connection) -> rusqlite::Result<usize> {
+    conn.execute("DELETE FROM ai_cache", [])
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_depends_on_every_part() {
+        let key = cache_key("openai", "gpt-4o-mini", "prompt");
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, cache_key("openai", "gpt-4o-mini", "prompt"));
+        assert_ne!(key, cache_key("anthropic", "gpt-4o-mini", "prompt"));
+        assert_ne!(key, cache_key("openai", "gpt-4o", "prompt"));
+        assert_ne!(key, cache_key("openai", "gpt-4o-mini", "prompt "));
+        assert_ne!(cache_key("ab", "c", ""), cache_key("a", "bc", ""));
+    }
+}
@@ -0,0 +1,253 @@
+//! AI Provider Client
+//!
+//! Sends rendered prompts to a hosted LLM provider. The provider is chosen
+//! with the `ai_provider` setting ("mock" by default, which keeps everything
+//! local). API keys are stored in `affiliate_credentials` under the provider
+//! name (`api_key`, with `affiliate_id` optionally overriding the base URL);
+//! the model comes from the `ai_model:<provider>` setting. Every call goes
+//! through the provider's rate limiter.
+//!
+//! When `ai_fallback_provider` is set, discovery tries it after the primary
+//! provider fails, and the local mock heuristics serve as the last resort.
+
+use crate::database::settings::{get_setting, get_setting_or};
+use crate::services::ai_cache;
+use crate::services::rate_limiter::{self, parse_retry_after, CallError, RateLimits};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+pub const MOCK_PROVIDER: &str = "mock";
+/// Hosted providers whose credentials live in `affiliate_credentials`
+pub const PROVIDERS: [&str; 2] = ["openai", "anthropic"];
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_TOKENS: u32 = 2048;
+
+#[derive(Debug, Clone)]
+pub struct AiProviderConfig {
+    pub provider: String,
+    pub model: String,
+    pub api_key: String,
+    pub base_url: String,
+}
+
+/// The provider selected in settings ("mock" when none is configured)
+pub fn active_provider(conn: &Connection) -> String {
+    get_setting_or(conn, "ai_provider", MOCK_PROVIDER).to_lowercase()
+}
+
+fn default_model(provider: &str) -> &'static str {
+    match provider {
+        "anthropic" => "claude-3-5-haiku-latest",
+        _ => "gpt-4o-mini",
+    }
+}
+
+fn default_base_url(provider: &str) -> &'static str {
+    match provider {
+        "anthropic" => "https://api.anthropic.com",
+        _ => "https://api.openai.com",
+    }
+}
+
+/// Loads credentials and model for a hosted provider
+pub fn load_provider_config(conn: &Connection, provider: &str) -> Result<AiProviderConfig, String> {
+    if !PROVIDERS.contains(&provider) {
+        return Err(format!("Unsupported AI provider: {}", provider));
+    }
+
+    let credential: Option<(Option<String>, Option<String>)> = conn
+        .query_row(
+            "SELECT api_key, affiliate_id FROM affiliate_credentials WHERE platform = ?1 AND active = 1",
+            params![provider],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    let (api_key, base_url) = credential.ok_or_else(|| format!("No active credentials saved for {}", provider))?;
+    let api_key = api_key
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| format!("{} API key is not configured", provider))?;
+
+    let model = get_setting(conn, &format!("ai_model:{}", provider))
+        .ok()
+        .flatten()
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| default_model(provider).to_string());
+
+    Ok(AiProviderConfig {
+        provider: provider.to_string(),
+        model,
+        api_key,
+        base_url: base_url
+            .filter(|u| u.starts_with("http"))
+            .unwrap_or_else(|| default_base_url(provider).to_string())
+            .trim_end_matches('/')
+            .to_string(),
+    })
+}
+
+/// A rendered prompt ready to send, with everything needed to rate-limit and cache it
+#[derive(Debug, Clone)]
+pub struct AiCall {
+    pub config: AiProviderConfig,
+    pub limits: RateLimits,
+    pub prompt: String,
+    pub cache_key: String,
+}
+
+/// Hosted providers to try in order, before falling back to the mock
+#[derive(Debug, Clone, Default)]
+pub struct AiChain {
+    pub calls: Vec<AiCall>,
+    /// Providers in the chain that couldn't be prepared, as "provider: error"
+    pub skipped: Vec<String>,
+}
+
+impl AiChain {
+    /// True when no hosted provider is selected at all (mock only)
+    pub fn is_mock_only(&self) -> bool {
+        self.calls.is_empty() && self.skipped.is_empty()
+    }
+}
+
+/// Provider order: primary, then secondary, skipping mock and duplicates.
+/// A mock primary means everything stays local, so the secondary is ignored.
+pub fn fallback_order(primary: &str, secondary: Option<&str>) -> Vec<String> {
+    if primary.trim().eq_ignore_ascii_case(MOCK_PROVIDER) {
+        return Vec::new();
+    }
+
+    let mut order: Vec<String> = Vec::new();
+    for provider in std::iter::once(primary).chain(secondary) {
+        let provider = provider.trim().to_lowercase();
+        if !provider.is_empty() && provider != MOCK_PROVIDER && !order.contains(&provider) {
+            order.push(provider);
+        }
+    }
+    order
+}
+
+/// Prepares `prompt` for every provider in the fallback chain. An empty chain
+/// means the mock provider is selected and the caller should use its local
+/// heuristics.
+pub fn prepare_chain(conn: &Connection, prompt: &str) -> AiChain {
+    let secondary = get_setting(conn, "ai_fallback_provider").ok().flatten();
+    let mut chain = AiChain::default();
+
+    for provider in fallback_order(&active_provider(conn), secondary.as_deref()) {
+        match load_provider_config(conn, &provider) {
+            Ok(config) => chain.calls.push(AiCall {
+                limits: rate_limiter::load_limits(conn, &provider),
+                cache_key: ai_cache::cache_key(&config.provider, &config.model, prompt),
+                config,
+                prompt: prompt.to_string(),
+            }),
+            Err(e) => chain.skipped.push(format!("{}: {}", provider, e)),
+        }
+    }
+
+    chain
+}
+
+/// Sends the prompt to the provider under its rate limit and returns the text reply
+pub async fn complete(call: &AiCall) -> Result<String, String> {
+    rate_limiter::call_with_rate_limit(&call.config.provider, call.limits, || {
+        send_request(&call.config, &call.prompt)
+    })
+    .await
+}
+
+async fn send_request(config: &AiProviderConfig, prompt: &str) -> Result<String, CallError> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| CallError::Failed(e.to_string()))?;
+
+    let request = match config.provider.as_str() {
+        "anthropic" => client
+            .post(format!("{}/v1/messages", config.base_url))
+            .header("x-api-key", &config.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .json(&json!({
+                "model": config.model,
+                "max_tokens": MAX_TOKENS,
+                "messages": [{ "role": "user", "content": prompt }],
+            })),
+        _ => client
+            .post(format!("{}/v1/chat/completions", config.base_url))
+            .bearer_auth(&config.api_key)
+            .json(&json!({
+                "model": config.model,
+                "max_tokens": MAX_TOKENS,
+                "messages": [{ "role": "user", "content": prompt }],
+            })),
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| CallError::Failed(format!("{} request failed: {}", config.provider, e)))?;
+
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = response
+            .headers()
+            .get("retry-after")
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_retry_after);
+        return Err(CallError::RateLimited { retry_after });
+    }
+
+    let body: Value = response
+        .error_for_status()
+        .map_err(|e| CallError::Failed(format!("{} request failed: {}", config.provider, e)))?
+        .json()
+        .await
+        .map_err(|e| CallError::Failed(format!("Invalid {} response: {}", config.provider, e)))?;
+
+    extract_text(&config.provider, &body)
+        .ok_or_else(|| CallError::Failed(format!("{} response contained no text", config.provider)))
+}
+
+/// Pulls the reply text out of a provider's response body
+pub fn extract_text(provider: &str, body: &Value) -> Option<String> {
+    match provider {
+        "anthropic" => body["content"]
+            .as_array()?
+            .iter()
+            .filter(|block| block["type"] == "text")
+            .find_map(|block| block["text"].as_str())
+            .map(String::from),
+        _ => body["choices"][0]["message"]["content"].as_str().map(String::from),
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_text() {
+        let openai = json!({ "choices": [{ "message": { "role": "assistant", "content": "[1]" } }] });
+        assert_eq!(extract_text("openai", &openai).as_deref(), Some("[1]"));
+
+        let anthropic = json!({ "content": [{ "type": "text", "text": "[2]" }] });
+        assert_eq!(extract_text("anthropic", &anthropic).as_deref(), Some("[2]"));
+
+        assert_eq!(extract_text("openai", &json!({})), None);
+    }
+
+    #[test]
+    fn test_fallback_order() {
+        assert_eq!(fallback_order("openai", Some("anthropic")), vec!["openai", "anthropic"]);
+        assert_eq!(fallback_order("openai", Some("OpenAI")), vec!["openai"]);
+        assert_eq!(fallback_order("anthropic", Some("mock")), vec!["anthropic"]);
+        assert!(fallback_order("mock", Some("anthropic")).is_empty());
+    }
+}
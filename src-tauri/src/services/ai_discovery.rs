@@ -0,0 +1,269 @@
+//! Provider-Backed Discovery Runs
+//!
+//! Walks the provider fallback chain (primary, then `ai_fallback_provider`).
+//! Each provider's answer is validated and re-prompted with the validation
+//! errors up to `ai_repair_attempts` times; a provider that errors, times out,
+//! or never produces valid output hands over to the next one, and the local
+//! mock heuristics serve when the whole chain fails. Every run is logged in
+//! `ai_discoveries` with the provider that served it, the failures along the
+//! way, and per-attempt parse diagnostics.
+//!
+//! `run_chain` and `run_cached` are shared by every structured AI request
+//! (research briefs, content angles, ...); `run` is the discovery flow built
+//! on top of them.
+
+use crate::database::get_connection;
+use crate::database::settings::get_setting_parsed;
+use crate::models::affiliate_link::AffiliateProgramDiscovery;
+use crate::models::ai_discovery::{AiDiscoveryRecord, ParseDiagnostic};
+use crate::services::ai_cache;
+use crate::services::ai_client::{self, AiCall, AiChain, MOCK_PROVIDER};
+use crate::services::ai_validation::{
+    repair_prompt, validate_discovery, DEFAULT_REPAIR_ATTEMPTS, DISCOVERY_SCHEMA, MAX_REPAIR_ATTEMPTS,
+};
+use rusqlite::{params, Connection, Row};
+use tauri::AppHandle;
+
+pub const AI_DISCOVERY_COLUMNS: &str = "id, product_id, provider, model, served_by,
+     fallback_errors_json, valid, attempts, raw_response, diagnostics_json, created_at";
+
+/// Maps a row selected with `AI_DISCOVERY_COLUMNS` into an `AiDiscoveryRecord`
+pub fn ai_discovery_from_row(row: &Row) -> rusqlite::Result<AiDiscoveryRecord> {
+    let fallback_errors: Option<String> = row.get(5)?;
+    let diagnostics: String = row.get(9)?;
+    Ok(AiDiscoveryRecord {
+        id: row.get(0)?,
+        product_id: row.get(1)?,
+        provider: row.get(2)?,
+        model: row.get(3)?,
+        served_by: row.get(4)?,
+        fallback_errors: fallback_errors
+            .and_then(|j| serde_json::from_str(&j).ok())
+            .unwrap_or_default(),
+        valid: row.get(6)?,
+        attempts: row.get(7)?,
+        raw_response: row.get(8)?,
+        diagnostics: serde_json::from_str(&diagnostics).unwrap_or_default(),
+        created_at: row.get(10)?,
+    })
+}
+
+/// What one run went through, accumulated across the chain
+#[derive(Debug, Default)]
+pub struct RunLog {
+    pub attempts: i32,
+    pub raw_response: Option<String>,
+    pub diagnostics: Vec<ParseDiagnostic>,
+    pub fallback_errors: Vec<String>,
+}
+
+/// A validated answer and the provider call that produced it
+pub struct Served<'a, T> {
+    pub call: &'a AiCall,
+    pub value: T,
+    pub response: String,
+}
+
+/// Tries each provider in `chain` until one returns output that passes
+/// `validate`, re-prompting with the errors (and `schema`) on invalid output.
+/// `None` means every provider failed and the caller should fall back to the mock.
+pub async fn run_chain<'a, T>(
+    app_handle: &AppHandle,
+    chain: &'a AiChain,
+    validate: fn(&str) -> Result<T, ParseDiagnostic>,
+    schema: &str,
+) -> Result<(Option<Served<'a, T>>, RunLog), String> {
+    let repair_attempts = {
+        let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        load_repair_attempts(&conn)
+    };
+
+    let mut log = RunLog {
+        fallback_errors: chain.skipped.clone(),
+        ..RunLog::default()
+    };
+
+    for call in &chain.calls {
+        match run_provider(call, repair_attempts, validate, schema, &mut log).await {
+            Ok((value, response)) => return Ok((Some(Served { call, value, response }), log)),
+            Err(e) => {
+                eprintln!("AI provider {} failed, falling back: {}", call.config.provider, e);
+                log.fallback_errors.push(format!("{}: {}", call.config.provider, e));
+            }
+        }
+    }
+
+    Ok((None, log))
+}
+
+/// Structured request with caching: serves the primary provider's cached
+/// answer when it still validates, otherwise walks the chain and caches the
+/// answer that passes. Returns the value and the provider that served it, or
+/// `None` when the caller should fall back to its mock heuristics.
+pub async fn run_cached<T>(
+    app_handle: &AppHandle,
+    chain: &AiChain,
+    validate: fn(&str) -> Result<T, ParseDiagnostic>,
+    schema: &str,
+) -> Result<Option<(T, String)>, String> {
+    if let Some(call) = chain.calls.first() {
+        let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        let cached = ai_cache::get(&conn, &call.cache_key)
+            .map_err(|e| e.to_string())?
+            .and_then(|response| validate(&response).ok());
+        if let Some(value) = cached {
+            return Ok(Some((value, call.config.provider.clone())));
+        }
+    }
+
+    let (served, log) = run_chain(app_handle, chain, validate, schema).await?;
+    match served {
+        Some(served) => {
+            let call = served.call;
+            let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+            ai_cache::put(&conn, &call.cache_key, &call.config.provider, &call.config.model, &served.response)
+                .map_err(|e| e.to_string())?;
+            Ok(Some((served.value, call.config.provider.clone())))
+        }
+        None => {
+            if !log.fallback_errors.is_empty() {
+                eprintln!("AI request fell back to heuristics: {}", log.fallback_errors.join("; "));
+            }
+            Ok(None)
+        }
+    }
+}
+
+/// Runs discovery over `chain`, falling back to `mock` when every provider
+/// fails, and logs the outcome. Valid responses are cached under the serving
+/// provider's key.
+pub async fn run(
+    app_handle: &AppHandle,
+    product_id: i64,
+    chain: &AiChain,
+    mock: impl FnOnce() -> Vec<AffiliateProgramDiscovery>,
+) -> Result<Vec<AffiliateProgramDiscovery>, String> {
+    let (served, log) = run_chain(app_handle, chain, validate_discovery, DISCOVERY_SCHEMA).await?;
+
+    let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    let (primary, model) = primary_of(chain);
+
+    match served {
+        Some(served) => {
+            let call = served.call;
+            record(&conn, product_id, &primary, &model, &call.config.provider, true, &log)
+                .map_err(|e| e.to_string())?;
+            ai_cache::put(&conn, &call.cache_key, &call.config.provider, &call.config.model, &served.response)
+                .map_err(|e| e.to_string())?;
+            Ok(served.value)
+        }
+        None => {
+            record(&conn, product_id, &primary, &model, MOCK_PROVIDER, false, &log)
+                .map_err(|e| e.to_string())?;
+            Ok(mock())
+        }
+    }
+}
+
+/// Primary provider and model of a chain, even when the primary couldn't be configured
+fn primary_of(chain: &AiChain) -> (String, String) {
+    chain
+        .calls
+        .first()
+        .map(|c| (c.config.provider.clone(), c.config.model.clone()))
+        .or_else(|| {
+            // Primary couldn't even be configured; its name leads the skip message
+            chain
+                .skipped
+                .first()
+                .and_then(|s| s.split(':').next())
+                .map(|p| (p.to_string(), String::new()))
+        })
+        .unwrap_or_else(|| (MOCK_PROVIDER.to_string(), String::new()))
+}
+
+/// One provider's turn: call, validate, and re-prompt with the errors until
+/// the output is valid or the repair budget is spent
+async fn run_provider<T>(
+    call: &AiCall,
+    repair_attempts: i32,
+    validate: fn(&str) -> Result<T, ParseDiagnostic>,
+    schema: &str,
+    log: &mut RunLog,
+) -> Result<(T, String), String> {
+    let mut attempt_call = call.clone();
+    let mut attempt = 1;
+
+    loop {
+        log.attempts += 1;
+        let response = ai_client::complete(&attempt_call).await?;
+        log.raw_response = Some(response.clone());
+
+        match validate(&response) {
+            Ok(value) => return Ok((value, response)),
+            Err(mut diagnostic) => {
+                diagnostic.provider = call.config.provider.clone();
+                diagnostic.attempt = attempt;
+                let summary = diagnostic.errors.join("; ");
+
+                if attempt > repair_attempts {
+                    log.diagnostics.push(diagnostic);
+                    return Err(format!(
+                        "response failed validation after {} attempt(s): {}",
+                        attempt, summary
+                    ));
+                }
+
+                attempt_call.prompt = repair_prompt(&call.prompt, &response, &diagnostic, schema);
+                log.diagnostics.push(diagnostic);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Repair re-prompts allowed after the first call
+pub fn load_repair_attempts(conn: &Connection) -> i32 {
+    get_setting_parsed(conn, "ai_repair_attempts", DEFAULT_REPAIR_ATTEMPTS).clamp(0, MAX_REPAIR_ATTEMPTS)
+}
+
+fn record(
+    conn: &Connection,
+    product_id: i64,
+    provider: &str,
+    model: &str,
+    served_by: &str,
+    valid: bool,
+    log: &RunLog,
+) -> rusqlite::Result<i64> {
+    conn.execute(
+        "INSERT INTO ai_discoveries
+         (product_id, provider, model, served_by, fallback_errors_json, valid, attempts,
+          raw_response, diagnostics_json)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            product_id,
+            provider,
+            model,
+            served_by,
+            serde_json::to_string(&log.fallback_errors).unwrap_or_else(|_| "[]".to_string()),
+            valid,
+            log.attempts,
+            log.raw_response,
+            serde_json::to_string(&log.diagnostics).unwrap_or_else(|_| "[]".to_string()),
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Logged discovery runs for a product, newest first
+pub fn list_for_product(conn: &Connection, product_id: i64, limit: i64) -> rusqlite::Result<Vec<AiDiscoveryRecord>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM ai_discoveries WHERE product_id = ?1 ORDER BY created_at DESC, id DESC LIMIT ?2",
+        AI_DISCOVERY_COLUMNS
+    ))?;
+    let records = stmt
+        .query_map(params![product_id, limit], ai_discovery_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(records)
+}
@@ -0,0 +1,204 @@
+//! AI Output Validation
+//!
+//! Provider output is untrusted text. Responses (discovery arrays, research
+//! briefs, ...) are extracted, parsed, and checked against a JSON Schema before
+//! they are deserialized, and every failure is reported as a `ParseDiagnostic`
+//! listing each violation. Those diagnostics double as the feedback in a
+//! repair prompt asking the model to correct its own output.
+
+use crate::models::affiliate_link::AffiliateProgramDiscovery;
+use crate::models::ai_discovery::ParseDiagnostic;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Repair re-prompts after the initial call when `ai_repair_attempts` is unset
+pub const DEFAULT_REPAIR_ATTEMPTS: i32 = 2;
+
+/// Upper bound so a persistently broken model can't burn the rate limit
+pub const MAX_REPAIR_ATTEMPTS: i32 = 5;
+
+/// Schema for the array requested by `AFFILIATE_DISCOVERY_PROMPT`
+pub const DISCOVERY_SCHEMA: &str = r#"{
+  "type": "array",
+  "maxItems": 5,
+  "items": {
+    "type": "object",
+    "required": [
+      "program_name", "platform", "commission_rate", "cookie_duration", "affiliate_url",
+      "is_official", "confidence_score", "audience_match_score", "recommendation_reason"
+    ],
+    "properties": {
+      "program_name": { "type": "string", "minLength": 1 },
+      "platform": { "enum": ["tiktok", "instagram", "amazon", "youtube", "pinterest", "facebook"] },
+      "commission_rate": { "type": "number", "minimum": 0 },
+      "cookie_duration": { "type": "integer", "minimum": 0 },
+      "affiliate_url": { "type": "string", "minLength": 1 },
+      "is_official": { "type": "boolean" },
+      "confidence_score": { "type": "number", "minimum": 0, "maximum": 1 },
+      "audience_match_score": { "type": "number", "minimum": 0, "maximum": 1 },
+      "recommendation_reason": { "type": "string" }
+    }
+  }
+}"#;
+
+/// Extracts, parses, and validates a discovery response
+pub fn validate_discovery(response: &str) -> Result<Vec<AffiliateProgramDiscovery>, ParseDiagnostic> {
+    validate_array(response, DISCOVERY_SCHEMA)
+}
+
+/// Extracts, parses, and validates a response whose payload is a JSON array
+pub fn validate_array<T: DeserializeOwned>(response: &str, schema: &str) -> Result<Vec<T>, ParseDiagnostic> {
+    validate_extracted(extract_json_array(response), schema)
+}
+
+/// Extracts, parses, and validates a response whose payload is a JSON object
+pub fn validate_object<T: DeserializeOwned>(response: &str, schema: &str) -> Result<T, ParseDiagnostic> {
+    validate_extracted(extract_json_object(response), schema)
+}
+
+fn validate_extracted<T: DeserializeOwned>(
+    extracted: Result<&str, String>,
+    schema: &str,
+) -> Result<T, ParseDiagnostic> {
+    let json_str = extracted.map_err(|e| diagnostic("extract", vec![e]))?;
+
+    let value: Value = serde_json::from_str(json_str)
+        .map_err(|e| diagnostic("json", vec![format!("Invalid JSON: {}", e)]))?;
+
+    let errors = schema_errors(schema, &value);
+    if !errors.is_empty() {
+        return Err(diagnostic("schema", errors));
+    }
+
+    // Anything the schema admits should deserialize; report it the same way if not
+    serde_json::from_value(value).map_err(|e| diagnostic("schema", vec![e.to_string()]))
+}
+
+/// Lists every schema violation in `value` as "path: message"
+pub fn schema_errors(schema: &str, value: &Value) -> Vec<String> {
+    let schema: Value = match serde_json::from_str(schema) {
+        Ok(schema) => schema,
+        Err(e) => return vec![format!("Invalid schema: {}", e)],
+    };
+    let validator = match jsonschema::validator_for(&schema) {
+        Ok(validator) => validator,
+        Err(e) => return vec![format!("Invalid schema: {}", e)],
+    };
+
+    validator
+        .iter_errors(value)
+        .map(|e| {
+            let path = e.instance_path.to_string();
+            let path = if path.is_empty() { "/".to_string() } else { path };
+            format!("{}: {}", path, e)
+        })
+        .collect()
+}
+
+/// Returns the outermost `[...]` span, tolerating prose and code fences around it
+pub fn extract_json_array(text: &str) -> Result<&str, String> {
+    let start = text.find('[')
+        .ok_or_else(|| "No JSON array found in response".to_string())?;
+    let end = text.rfind(']')
+        .ok_or_else(|| "No JSON array found in response".to_string())?;
+
+    if start >= end {
+        return Err("Invalid JSON array structure".to_string());
+    }
+
+    Ok(&text[start..=end])
+}
+
+/// Returns the outermost `{...}` span, tolerating prose and code fences around it
+pub fn extract_json_object(text: &str) -> Result<&str, String> {
+    let start = text.find('{')
+        .ok_or_else(|| "No JSON object found in response".to_string())?;
+    let end = text.rfind('}')
+        .ok_or_else(|| "No JSON object found in response".to_string())?;
+
+    if start >= end {
+        return Err("Invalid JSON object structure".to_string());
+    }
+
+    Ok(&text[start..=end])
+}
+
+/// Follow-up prompt asking the model to fix a rejected response
+pub fn repair_prompt(
+    original_prompt: &str,
+    bad_response: &str,
+    diagnostic: &ParseDiagnostic,
+    schema: &str,
+) -> String {
+    let errors = diagnostic
+        .errors
+        .iter()
+        .map(|e| format!("- {}", e))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "{}\n\nYour previous response could not be used:\n{}\n\nValidation errors:\n{}\n\n\
+         Return ONLY the corrected JSON, matching this JSON Schema:\n{}",
+        original_prompt, bad_response, errors, schema
+    )
+}
+
+fn diagnostic(stage: &str, errors: Vec<String>) -> ParseDiagnostic {
+    ParseDiagnostic {
+        provider: String::new(),
+        attempt: 0,
+        stage: stage.to_string(),
+        errors,
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::affiliate_link::AffiliatePlatform;
+
+    const VALID: &str = r#"Here you go:
+```json
+[{"program_name": "Amazon Associates", "platform": "amazon", "commission_rate": 4.0,
+  "cookie_duration": 1, "affiliate_url": "https://amazon.com", "is_official": true,
+  "confidence_score": 0.9, "audience_match_score": 0.8, "recommendation_reason": "Broad reach"}]
+```"#;
+
+    #[test]
+    fn test_valid_response_parses() {
+        let programs = validate_discovery(VALID).unwrap();
+        assert_eq!(programs.len(), 1);
+        assert_eq!(programs[0].platform, AffiliatePlatform::AmazonAssociates);
+    }
+
+    #[test]
+    fn test_schema_violations_are_listed() {
+        let response = VALID
+            .replace("\"amazon\"", "\"myspace\"")
+            .replace("0.8", "1.8");
+        let diagnostic = validate_discovery(&response).unwrap_err();
+        assert_eq!(diagnostic.stage, "schema");
+        assert_eq!(diagnostic.errors.len(), 2);
+        assert!(diagnostic.errors.iter().any(|e| e.starts_with("/0/platform")));
+        assert!(diagnostic.errors.iter().any(|e| e.starts_with("/0/audience_match_score")));
+    }
+
+    #[test]
+    fn test_unparseable_responses() {
+        assert_eq!(validate_discovery("Sorry, I can't help").unwrap_err().stage, "extract");
+        assert_eq!(validate_discovery("[{\"program_name\": }]").unwrap_err().stage, "json");
+    }
+
+    #[test]
+    fn test_repair_prompt_includes_errors() {
+        let diagnostic = validate_discovery("[{}]").unwrap_err();
+        let prompt = repair_prompt("Find programs", "[{}]", &diagnostic, DISCOVERY_SCHEMA);
+        assert!(prompt.starts_with("Find programs"));
+        assert!(prompt.contains("\"platform\" is a required property"));
+    }
+}
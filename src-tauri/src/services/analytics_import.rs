@@ -0,0 +1,318 @@
+//! Analytics Import Service (GA4 / Plausible)
+//!
+//! Pulls UTM-campaign-level sessions and events from Google Analytics 4 or
+//! Plausible and maps each `utm_campaign`/`utm_source` pair back to the
+//! affiliate link that generated it, so off-network click data can enrich
+//! the performance reports.
+//!
+//! Credentials are stored in `affiliate_credentials` under the platform names
+//! `ga4` (shop_id = property ID, api_key = OAuth access token) and
+//! `plausible` (shop_id = site ID, api_key = API key, affiliate_id = optional
+//! self-hosted base URL).
+
+use crate::models::affiliate_credentials::AffiliateCredential;
+use crate::models::traffic::TrafficRow;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::{json, Value};
+
+pub const PLAUSIBLE_DEFAULT_BASE_URL: &str = "https://plausible.io";
+pub const GA4_API_BASE_URL: &str = "https://analyticsdata.googleapis.com/v1beta";
+
+/// Providers whose credentials live in `affiliate_credentials` alongside affiliate platforms
+pub const PROVIDERS: [&str; 2] = ["plausible", "ga4"];
+
+// =============================================================================
+// PROVIDER FETCHING
+// =============================================================================
+
+/// Fetches daily UTM traffic from the given provider for an inclusive date range
+pub async fn fetch_traffic(
+    provider: &str,
+    credential: &AffiliateCredential,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<TrafficRow>, String> {
+    match provider {
+        "plausible" => fetch_plausible(credential, start_date, end_date).await,
+        "ga4" => fetch_ga4(credential, start_date, end_date).await,
+        other => Err(format!("Unsupported analytics provider: {}", other)),
+    }
+}
+
+async fn fetch_plausible(
+    credential: &AffiliateCredential,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<TrafficRow>, String> {
+    let site_id = credential
+        .shop_id
+        .as_deref()
+        .ok_or("Plausible site ID is not configured")?;
+    let api_key = credential
+        .api_key
+        .as_deref()
+        .ok_or("Plausible API key is not configured")?;
+    let base_url = credential
+        .affiliate_id
+        .as_deref()
+        .filter(|u| u.starts_with("http"))
+        .unwrap_or(PLAUSIBLE_DEFAULT_BASE_URL)
+        .trim_end_matches('/');
+
+    let body = json!({
+        "site_id": site_id,
+        "metrics": ["visits", "events"],
+        "date_range": [start_date, end_date],
+        "dimensions": ["visit:utm_campaign", "visit:utm_source", "time:day"],
+        "filters": [["is_not", "visit:utm_campaign", [""]]],
+    });
+
+    let response: Value = reqwest::Client::new()
+        .post(format!("{}/api/v2/query", base_url))
+        .bearer_auth(api_key)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Plausible request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("Plausible request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid Plausible response: {}", e))?;
+
+    parse_plausible_response(&response)
+}
+
+async fn fetch_ga4(
+    credential: &AffiliateCredential,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<TrafficRow>, String> {
+    let property_id = credential
+        .shop_id
+        .as_deref()
+        .ok_or("GA4 property ID is not configured")?;
+    let access_token = credential
+        .api_key
+        .as_deref()
+        .ok_or("GA4 access token is not configured")?;
+
+    let body = json!({
+        "dateRanges": [{ "startDate": start_date, "endDate": end_date }],
+        "dimensions": [
+            { "name": "sessionCampaignName" },
+            { "name": "sessionSource" },
+            { "name": "date" }
+        ],
+        "metrics": [{ "name": "sessions" }, { "name": "eventCount" }],
+    });
+
+    let response: Value = reqwest::Client::new()
+        .post(format!(
+            "{}/properties/{}:runReport",
+            GA4_API_BASE_URL, property_id
+        ))
+        .bearer_auth(access_token)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("GA4 request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("GA4 request failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid GA4 response: {}", e))?;
+
+    parse_ga4_response(&response)
+}
+
+// =============================================================================
+// RESPONSE PARSING
+// =============================================================================
+
+/// Parses a Plausible Stats API v2 query response.
+/// Rows look like `{"dimensions": [campaign, source, "YYYY-MM-DD"], "metrics": [visits, events]}`.
+pub fn parse_plausible_response(response: &Value) -> Result<Vec<TrafficRow>, String> {
+    let results = response["results"]
+        .as_array()
+        .ok_or("Plausible response has no results")?;
+
+    Ok(results
+        .iter()
+        .filter_map(|row| {
+            let dims = row["dimensions"].as_array()?;
+            let metrics = row["metrics"].as_array()?;
+            Some(TrafficRow {
+                utm_campaign: dims.first()?.as_str()?.to_string(),
+                utm_source: dims.get(1).and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                date: dims.get(2)?.as_str()?.chars().take(10).collect(),
+                sessions: metrics.first().and_then(|v| v.as_i64()).unwrap_or(0),
+                events: metrics.get(1).and_then(|v| v.as_i64()).unwrap_or(0),
+            })
+        })
+        .filter(|row| !row.utm_campaign.is_empty() && row.utm_campaign != "(none)")
+        .collect())
+}
+
+/// Parses a GA4 Data API runReport response.
+/// Dimension values are strings; GA4 reports dates as `YYYYMMDD` and metrics as strings.
+pub fn parse_ga4_response(response: &Value) -> Result<Vec<TrafficRow>, String> {
+    // GA4 omits "rows" entirely when the report is empty
+    let rows = match response.get("rows") {
+        Some(rows) => rows.as_array().ok_or("GA4 response rows are malformed")?,
+        None => return Ok(Vec::new()),
+    };
+
+    let metric = |values: &Vec<Value>, idx: usize| -> i64 {
+        values
+            .get(idx)
+            .and_then(|v| v["value"].as_str())
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0)
+    };
+
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let dims = row["dimensionValues"].as_array()?;
+            let metrics = row["metricValues"].as_array()?;
+            let raw_date = dims.get(2)?["value"].as_str()?;
+            if raw_date.len() != 8 {
+                return None;
+            }
+            Some(TrafficRow {
+                utm_campaign: dims.first()?["value"].as_str()?.to_string(),
+                utm_source: dims.get(1).and_then(|v| v["value"].as_str()).unwrap_or("").to_string(),
+                date: format!("{}-{}-{}", &raw_date[0..4], &raw_date[4..6], &raw_date[6..8]),
+                sessions: metric(metrics, 0),
+                events: metric(metrics, 1),
+            })
+        })
+        .filter(|row| !row.utm_campaign.is_empty() && row.utm_campaign != "(not set)")
+        .collect())
+}
+
+// =============================================================================
+// LINK MATCHING & STORAGE
+// =============================================================================
+
+/// Maps a utm_campaign/utm_source pair back to the affiliate link that carries it.
+/// Tracking URLs embed `utm_source=<platform>` and `utm_campaign=<product_slug>`,
+/// so the source is used to disambiguate products promoted on several platforms.
+pub fn match_link(conn: &Connection, utm_campaign: &str, utm_source: &str) -> rusqlite::Result<Option<i64>> {
+    let campaign_pattern = format!("%utm_campaign={}%", utm_campaign.to_lowercase());
+
+    let exact: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM affiliate_links
+             WHERE LOWER(tracking_url) LIKE ?1 AND platform = ?2
+             ORDER BY created_at DESC LIMIT 1",
+            params![campaign_pattern, utm_source.to_lowercase()],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    if exact.is_some() {
+        return Ok(exact);
+    }
+
+    conn.query_row(
+        "SELECT id FROM affiliate_links WHERE LOWER(tracking_url) LIKE ?1
+         ORDER BY created_at DESC LIMIT 1",
+        params![campaign_pattern],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Upserts imported rows and returns (matched rows, unmatched campaign names)
+pub fn store_traffic_rows(
+    conn: &Connection,
+    provider: &str,
+    rows: &[TrafficRow],
+) -> rusqlite::Result<(usize, Vec<String>)> {
+    let mut matched = 0;
+    let mut unmatched: Vec<String> = Vec::new();
+
+    for row in rows {
+        let link_id = match_link(conn, &row.utm_campaign, &row.utm_source)?;
+        match link_id {
+            Some(_) => matched += 1,
+            None => {
+                if !unmatched.contains(&row.utm_campaign) {
+                    unmatched.push(row.utm_campaign.clone());
+                }
+            }
+        }
+
+        conn.execute(
+            "INSERT INTO traffic_sessions (link_id, provider, utm_campaign, utm_source, date, sessions, events)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(provider, utm_campaign, utm_source, date) DO UPDATE SET
+             link_id = excluded.link_id,
+             sessions = excluded.sessions,
+             events = excluded.events,
+             imported_at = CURRENT_TIMESTAMP",
+            params![
+                link_id,
+                provider,
+                row.utm_campaign,
+                row.utm_source,
+                row.date,
+                row.sessions,
+                row.events,
+            ],
+        )?;
+    }
+
+    Ok((matched, unmatched))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plausible_response() {
+        let response = json!({
+            "results": [
+                { "dimensions": ["smart_ring", "tiktok", "2025-03-01"], "metrics": [42, 90] },
+                { "dimensions": ["", "direct", "2025-03-01"], "metrics": [5, 5] }
+            ]
+        });
+        let rows = parse_plausible_response(&response).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].utm_campaign, "smart_ring");
+        assert_eq!(rows[0].utm_source, "tiktok");
+        assert_eq!(rows[0].date, "2025-03-01");
+        assert_eq!(rows[0].sessions, 42);
+        assert_eq!(rows[0].events, 90);
+    }
+
+    #[test]
+    fn test_parse_ga4_response() {
+        let response = json!({
+            "rows": [{
+                "dimensionValues": [{ "value": "smart_ring" }, { "value": "pinterest" }, { "value": "20250302" }],
+                "metricValues": [{ "value": "17" }, { "value": "31" }]
+            }, {
+                "dimensionValues": [{ "value": "(not set)" }, { "value": "google" }, { "value": "20250302" }],
+                "metricValues": [{ "value": "3" }, { "value": "3" }]
+            }]
+        });
+        let rows = parse_ga4_response(&response).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].date, "2025-03-02");
+        assert_eq!(rows[0].sessions, 17);
+        assert_eq!(rows[0].events, 31);
+    }
+
+    #[test]
+    fn test_parse_ga4_empty_report() {
+        assert!(parse_ga4_response(&json!({ "rowCount": 0 })).unwrap().is_empty());
+    }
+}
@@ -6,6 +6,14 @@
 //! and conversion potential across different advertising formats.
 
 use crate::models::product::Product;
+use crate::models::recommendation_trace::AdTypeFactors;
+use crate::models::audience::AudienceProfile;
+use crate::services::ai_affiliate::price_tier_label;
+use crate::services::audience;
+use crate::services::category_keywords::CategoryAffinities;
+use crate::services::trend_history::{self, SIGNIFICANT_CHANGE_PER_WEEK};
+use crate::services::{network_terms, product_comparison};
+use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 
 // =============================================================================
@@ -72,6 +80,18 @@ impl AdType {
         }
     }
 
+    /// Returns the snake_case key used for ad types elsewhere in the app
+    pub fn key(&self) -> &'static str {
+        match self {
+            AdType::SocialPost => "social_post",
+            AdType::Story => "story",
+            AdType::VideoScript => "video_script",
+            AdType::Carousel => "carousel",
+            AdType::Email => "email",
+            AdType::Sms => "sms",
+        }
+    }
+
     /// Returns all available ad types as a vector
     pub fn all() -> Vec<AdType> {
         vec![
@@ -92,18 +112,22 @@ impl AdType {
 /// Comprehensive market analysis result containing the recommended ad type
 /// along with supporting data for the recommendation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct MarketAnalysis {
     /// The primary recommended ad type based on product analysis
+    #[serde(alias = "recommended_ad_type")]
     pub recommended_ad_type: AdType,
 
     /// Confidence score for the recommendation (0.0 to 1.0)
     /// Higher scores indicate stronger alignment between product and ad type
+    #[serde(alias = "confidence_score")]
     pub confidence_score: f64,
 
     /// Human-readable explanation of why this ad type was selected
     pub reasoning: String,
 
     /// Alternative ad types that could also work well, ordered by suitability
+    #[serde(alias = "alternative_types")]
     pub alternative_types: Vec<AdType>,
 }
 
@@ -118,6 +142,46 @@ impl Default for MarketAnalysis {
     }
 }
 
+/// Signals beyond the product row that scoring can use; the default has none
+#[derive(Debug, Clone, Default)]
+pub struct ScoringSignals {
+    /// Trending score change per week (see `trend_history`); None without history
+    pub momentum_per_week: Option<f64>,
+    /// Best commission rate among the product's links and programs, as a fraction
+    pub best_commission_rate: Option<f64>,
+    /// Cookie window of the best-paying program, from its link or network terms
+    pub cookie_days: Option<i64>,
+}
+
+impl ScoringSignals {
+    /// Loads a product's trend momentum and best available commission terms
+    pub fn load(conn: &Connection, product_id: i64) -> rusqlite::Result<Self> {
+        let momentum = trend_history::product_momentum(conn, product_id)?;
+        let options = product_comparison::commission_options(conn, product_id)?;
+        let best = options
+            .into_iter()
+            .filter(|o| o.commission_rate.is_some())
+            .max_by(|a, b| a.commission_rate.unwrap_or(0.0).total_cmp(&b.commission_rate.unwrap_or(0.0)));
+
+        let cookie_days = match &best {
+            Some(option) if option.cookie_days.is_some() => option.cookie_days,
+            Some(option) => {
+                let terms = network_terms::list(conn)?;
+                network_terms::match_terms(&option.program_name, option.platform.as_deref().unwrap_or(""), &terms)
+                    .and_then(|t| t.cookie_days)
+                    .map(i64::from)
+            }
+            None => None,
+        };
+
+        Ok(ScoringSignals {
+            momentum_per_week: momentum.map(|m| m.change_per_week),
+            best_commission_rate: best.and_then(|o| o.commission_rate),
+            cookie_days,
+        })
+    }
+}
+
 // =============================================================================
 // INTERNAL SCORING STRUCTURES
 // =============================================================================
@@ -130,6 +194,7 @@ struct AdTypeScore {
     audience_score: f64,
     trending_score: f64,
     platform_score: f64,
+    commission_adjustment: f64,
     total_score: f64,
 }
 
@@ -141,17 +206,20 @@ impl AdTypeScore {
             audience_score: 0.0,
             trending_score: 0.0,
             platform_score: 0.0,
+            commission_adjustment: 0.0,
             total_score: 0.0,
         }
     }
 
     /// Calculate total score with weighted factors
-    /// Weights: Category 30%, Audience 35%, Trending 20%, Platform 15%
+    /// Weights: Category 30%, Audience 35%, Trending 20%, Platform 15%,
+    /// then the commission adjustment on top
     fn calculate_total(&mut self) {
         self.total_score = (self.category_score * 0.30)
             + (self.audience_score * 0.35)
             + (self.trending_score * 0.20)
-            + (self.platform_score * 0.15);
+            + (self.platform_score * 0.15)
+            + self.commission_adjustment;
     }
 }
 
@@ -170,6 +238,8 @@ impl AdTypeScore {
 ///
 /// # Arguments
 /// * `product` - Reference to the Product being analyzed
+/// * `affinities` - Category keyword affinities
+/// * `signals` - Trend momentum and other signals beyond the product row
 ///
 /// # Returns
 /// The optimal `AdType` for the product based on the analysis
@@ -177,11 +247,15 @@ impl AdTypeScore {
 /// # Example
 /// ```ignore
 /// let product = Product { category: "Consumer Electronics".to_string(), ... };
-/// let ad_type = select_optimal_ad_type(&product);
+/// let ad_type = select_optimal_ad_type(&product, &CategoryAffinities::load(&conn)?, &ScoringSignals::default());
 /// assert_eq!(ad_type, AdType::VideoScript);
 /// ```
-pub fn select_optimal_ad_type(product: &Product) -> AdType {
-    let analysis = analyze_market_for_product(product);
+pub fn select_optimal_ad_type(
+    product: &Product,
+    affinities: &CategoryAffinities,
+    signals: &ScoringSignals,
+) -> AdType {
+    let analysis = analyze_market_for_product(product, affinities, signals);
     analysis.recommended_ad_type
 }
 
@@ -195,6 +269,7 @@ pub fn select_optimal_ad_type(product: &Product) -> AdType {
 /// # Analysis Factors
 ///
 /// ## Category Analysis (30% weight)
+/// Scored from the editable category keyword table; the shipped keywords map:
 /// - Tech/Electronics -> VideoScript (detailed demos needed)
 /// - Fashion/Beauty -> Carousel/Story (visual appeal)
 /// - Home/Kitchen -> Carousel (product showcase)
@@ -219,34 +294,17 @@ pub fn select_optimal_ad_type(product: &Product) -> AdType {
 ///
 /// # Arguments
 /// * `product` - Reference to the Product being analyzed
+/// * `affinities` - Category keyword affinities
+/// * `signals` - Trend momentum and other signals beyond the product row
 ///
 /// # Returns
 /// A `MarketAnalysis` struct with complete recommendation details
-pub fn analyze_market_for_product(product: &Product) -> MarketAnalysis {
-    // Initialize scores for all ad types
-    let mut scores: Vec<AdTypeScore> = AdType::all()
-        .into_iter()
-        .map(AdTypeScore::new)
-        .collect();
-
-    // Calculate individual factor scores for each ad type
-    for score in &mut scores {
-        score.category_score = calculate_category_score(&product.category, score.ad_type);
-        score.audience_score = calculate_audience_score(
-            product.target_audience.as_deref(),
-            score.ad_type,
-        );
-        score.trending_score = calculate_trending_score(product.trending_score, score.ad_type);
-        score.platform_score = calculate_platform_score(product, score.ad_type);
-        score.calculate_total();
-    }
-
-    // Sort by total score (descending)
-    scores.sort_by(|a, b| {
-        b.total_score
-            .partial_cmp(&a.total_score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+pub fn analyze_market_for_product(
+    product: &Product,
+    affinities: &CategoryAffinities,
+    signals: &ScoringSignals,
+) -> MarketAnalysis {
+    let scores = score_ad_types(product, affinities, signals);
 
     // Extract the best match and alternatives
     let best = &scores[0];
@@ -266,152 +324,76 @@ pub fn analyze_market_for_product(product: &Product) -> MarketAnalysis {
     }
 }
 
-// =============================================================================
-// CATEGORY SCORING
-// =============================================================================
-
-/// Calculates how well an ad type matches a product category.
-///
-/// Categories are mapped to ad types based on typical content consumption
-/// patterns and purchase decision processes for each product type.
-fn calculate_category_score(category: &str, ad_type: AdType) -> f64 {
-    let category_lower = category.to_lowercase();
-
-    match ad_type {
-        AdType::VideoScript => {
-            // Video scripts excel for products that need demonstration
-            if category_lower.contains("electronics")
-                || category_lower.contains("tech")
-                || category_lower.contains("wearable")
-                || category_lower.contains("gadget")
-            {
-                1.0
-            } else if category_lower.contains("fitness")
-                || category_lower.contains("health")
-            {
-                0.8
-            } else if category_lower.contains("home")
-                || category_lower.contains("kitchen")
-            {
-                0.6
-            } else {
-                0.4
-            }
-        }
-
-        AdType::Carousel => {
-            // Carousels work best for visual, multi-angle products
-            if category_lower.contains("fashion")
-                || category_lower.contains("apparel")
-                || category_lower.contains("clothing")
-            {
-                1.0
-            } else if category_lower.contains("beauty")
-                || category_lower.contains("skincare")
-                || category_lower.contains("cosmetic")
-            {
-                0.9
-            } else if category_lower.contains("home")
-                || category_lower.contains("decor")
-                || category_lower.contains("furniture")
-            {
-                0.85
-            } else if category_lower.contains("jewelry")
-                || category_lower.contains("accessories")
-            {
-                0.9
-            } else {
-                0.5
-            }
-        }
-
-        AdType::Story => {
-            // Stories are great for lifestyle and trending products
-            if category_lower.contains("beauty")
-                || category_lower.contains("skincare")
-            {
-                0.95
-            } else if category_lower.contains("fashion")
-                || category_lower.contains("apparel")
-            {
-                0.9
-            } else if category_lower.contains("food")
-                || category_lower.contains("beverage")
-            {
-                0.85
-            } else if category_lower.contains("fitness")
-                || category_lower.contains("wellness")
-            {
-                0.8
-            } else {
-                0.5
-            }
-        }
-
-        AdType::SocialPost => {
-            // Social posts have broad appeal but excel for viral-friendly products
-            if category_lower.contains("trending")
-                || category_lower.contains("viral")
-            {
-                0.95
-            } else if category_lower.contains("gadget")
-                || category_lower.contains("tech")
-            {
-                0.7
-            } else {
-                0.6 // Baseline for all categories
-            }
-        }
+/// Every ad type's factor scores for a product, best first. Identical products
+/// always produce identical scores in the same order.
+pub fn ad_type_factors(
+    product: &Product,
+    affinities: &CategoryAffinities,
+    signals: &ScoringSignals,
+) -> Vec<AdTypeFactors> {
+    score_ad_types(product, affinities, signals)
+        .into_iter()
+        .map(|s| AdTypeFactors {
+            ad_type: s.ad_type.key().to_string(),
+            category_score: s.category_score,
+            audience_score: s.audience_score,
+            trending_score: s.trending_score,
+            platform_score: s.platform_score,
+            commission_adjustment: s.commission_adjustment,
+            total_score: s.total_score,
+        })
+        .collect()
+}
 
-        AdType::Email => {
-            // Email works for products requiring consideration
-            if category_lower.contains("health")
-                || category_lower.contains("wellness")
-                || category_lower.contains("supplement")
-            {
-                0.9
-            } else if category_lower.contains("finance")
-                || category_lower.contains("insurance")
-            {
-                0.95
-            } else if category_lower.contains("electronics")
-                || category_lower.contains("appliance")
-            {
-                0.7
-            } else {
-                0.5
-            }
-        }
+/// Scores all ad types and sorts them by total score (descending)
+fn score_ad_types(
+    product: &Product,
+    affinities: &CategoryAffinities,
+    signals: &ScoringSignals,
+) -> Vec<AdTypeScore> {
+    // Initialize scores for all ad types
+    let mut scores: Vec<AdTypeScore> = AdType::all()
+        .into_iter()
+        .map(AdTypeScore::new)
+        .collect();
 
-        AdType::Sms => {
-            // SMS best for time-sensitive, impulse-friendly products
-            if category_lower.contains("food")
-                || category_lower.contains("restaurant")
-            {
-                0.9
-            } else if category_lower.contains("deal")
-                || category_lower.contains("flash")
-            {
-                0.95
-            } else if category_lower.contains("local")
-                || category_lower.contains("service")
-            {
-                0.8
-            } else {
-                0.3
-            }
-        }
+    // Calculate individual factor scores for each ad type
+    for score in &mut scores {
+        score.category_score = affinities.ad_type_score(score.ad_type.key(), &product.category);
+        score.audience_score = calculate_audience_score(
+            product.target_audience.as_deref(),
+            score.ad_type,
+        );
+        score.trending_score = calculate_trending_score(
+            product.trending_score,
+            signals.momentum_per_week,
+            score.ad_type,
+        );
+        score.platform_score = calculate_platform_score(product, score.ad_type);
+        score.commission_adjustment = calculate_commission_adjustment(product, signals, score.ad_type);
+        score.calculate_total();
     }
+
+    // Sort by total score (descending); stable, so ties keep AdType::all() order
+    scores.sort_by(|a, b| b.total_score.total_cmp(&a.total_score));
+    scores
 }
 
 // =============================================================================
 // AUDIENCE SCORING
 // =============================================================================
 
+/// Added when an audience interest favors the ad type
+const INTEREST_BONUS: f64 = 0.1;
+/// Weekly trending change that earns the full momentum shift
+const FULL_MOMENTUM_PER_WEEK: f64 = 15.0;
+const MAX_MOMENTUM_SHIFT: f64 = 0.15;
+
 /// Calculates how well an ad type matches the target audience demographics.
 ///
-/// Audience age ranges are extracted from the target_audience string and
-/// mapped to preferred content consumption patterns.
+/// The target_audience string is parsed into an audience profile; its age
+/// range maps to preferred content consumption patterns, and its gender mix
+/// and interests nudge the formats they favor.
 fn calculate_audience_score(target_audience: Option<&str>, ad_type: AdType) -> f64 {
     let audience = match target_audience {
         Some(a) => a,
@@ -419,10 +401,10 @@ fn calculate_audience_score(target_audience: Option<&str>, ad_type: AdType) -> f
     };
 
     let audience_lower = audience.to_lowercase();
+    let profile = audience::parse(audience);
 
     // Extract age indicators
-    let age_range = extract_age_range(&audience_lower);
-    let avg_age = (age_range.0 + age_range.1) / 2;
+    let avg_age = (profile.age_min + profile.age_max) / 2;
 
     // Check for generation keywords
     let is_gen_z = audience_lower.contains("gen z")
@@ -441,6 +423,45 @@ fn calculate_audience_score(target_audience: Option<&str>, ad_type: AdType) -> f
         || audience_lower.contains("senior")
         || avg_age > 55;
 
+    let generation_score = generation_audience_score(ad_type, is_gen_z, is_millennial, is_gen_x, is_boomer);
+    (generation_score + audience_profile_bonus(&profile, ad_type)).min(1.0)
+}
+
+/// Gender and interest bonus for an ad type; zero when the audience mentions neither
+fn audience_profile_bonus(profile: &AudienceProfile, ad_type: AdType) -> f64 {
+    // Visual, swipeable formats skew female; long-form video skews male
+    let gender = match (profile.gender.as_str(), ad_type) {
+        ("female", AdType::Story | AdType::Carousel) => 0.05,
+        ("male", AdType::VideoScript) => 0.05,
+        _ => 0.0,
+    };
+
+    // One bonus when any interest is known to favor the format
+    let favored = profile.interests.iter().any(|interest| {
+        let formats: &[AdType] = match interest.as_str() {
+            "home_decor" | "diy" | "cooking" | "gardening" => &[AdType::Carousel, AdType::VideoScript],
+            "gaming" | "tech" => &[AdType::VideoScript],
+            "beauty" | "fashion" | "travel" => &[AdType::Story, AdType::Carousel],
+            "fitness" => &[AdType::VideoScript, AdType::Story],
+            "finance" | "parenting" => &[AdType::Email],
+            "deals" => &[AdType::Sms, AdType::Email],
+            _ => &[],
+        };
+        formats.contains(&ad_type)
+    });
+    let interests = if favored { INTEREST_BONUS } else { 0.0 };
+
+    gender + interests
+}
+
+/// Audience score for an ad type from the audience's generation
+fn generation_audience_score(
+    ad_type: AdType,
+    is_gen_z: bool,
+    is_millennial: bool,
+    is_gen_x: bool,
+    is_boomer: bool,
+) -> f64 {
     match ad_type {
         AdType::Story => {
             // Stories are Gen Z's native format
@@ -522,37 +543,6 @@ fn calculate_audience_score(target_audience: Option<&str>, ad_type: AdType) -> f
     }
 }
 
-/// Extracts age range from audience description string.
-/// Returns (min_age, max_age) tuple.
-fn extract_age_range(audience: &str) -> (i32, i32) {
-    // Try to find age pattern like "18-35" or "ages 25-45"
-    let age_pattern = regex::Regex::new(r"(\d{2})[-–]\s*(\d{2})").ok();
-
-    if let Some(re) = age_pattern {
-        if let Some(caps) = re.captures(audience) {
-            if let (Some(min), Some(max)) = (caps.get(1), caps.get(2)) {
-                let min_age = min.as_str().parse::<i32>().unwrap_or(25);
-                let max_age = max.as_str().parse::<i32>().unwrap_or(45);
-                return (min_age, max_age);
-            }
-        }
-    }
-
-    // Fallback: check for generation keywords
-    if audience.contains("gen z") || audience.contains("genz") {
-        return (18, 25);
-    } else if audience.contains("millennial") {
-        return (26, 40);
-    } else if audience.contains("gen x") || audience.contains("genx") {
-        return (41, 55);
-    } else if audience.contains("boomer") || audience.contains("senior") {
-        return (56, 70);
-    }
-
-    // Default middle-range audience
-    (25, 45)
-}
-
 // =============================================================================
 // TRENDING SCORE ANALYSIS
 // =============================================================================
@@ -561,7 +551,32 @@ fn extract_age_range(audience: &str) -> (i32, i32) {
 ///
 /// High-trending products should use formats that maximize viral spread,
 /// while low-trending products benefit from educational, trust-building formats.
-fn calculate_trending_score(trending_score: Option<i32>, ad_type: AdType) -> f64 {
+/// Momentum shifts this further: accelerating products lean viral, decaying
+/// ones lean evergreen.
+fn calculate_trending_score(trending_score: Option<i32>, momentum_per_week: Option<f64>, ad_type: AdType) -> f64 {
+    let level_score = trending_level_score(trending_score, ad_type);
+    (level_score + momentum_adjustment(momentum_per_week, ad_type)).clamp(0.0, 1.0)
+}
+
+/// Shift toward viral formats for rising products and evergreen formats for
+/// declining ones, growing with the rate of change up to `MAX_MOMENTUM_SHIFT`
+fn momentum_adjustment(momentum_per_week: Option<f64>, ad_type: AdType) -> f64 {
+    let momentum = match momentum_per_week {
+        Some(m) if m.abs() >= SIGNIFICANT_CHANGE_PER_WEEK => m,
+        _ => return 0.0, // No history, or steady
+    };
+    let shift = (momentum.abs() / FULL_MOMENTUM_PER_WEEK).min(1.0) * MAX_MOMENTUM_SHIFT;
+    let viral = matches!(ad_type, AdType::SocialPost | AdType::Story);
+    let evergreen = matches!(ad_type, AdType::Email | AdType::VideoScript);
+    match (momentum > 0.0, viral, evergreen) {
+        (true, true, _) | (false, _, true) => shift,
+        (true, _, true) | (false, true, _) => -shift,
+        _ => 0.0,
+    }
+}
+
+/// Trending fit from the current score alone
+fn trending_level_score(trending_score: Option<i32>, ad_type: AdType) -> f64 {
     let score = trending_score.unwrap_or(50); // Default to medium trending
 
     match ad_type {
@@ -711,6 +726,50 @@ fn calculate_platform_score(product: &Product, ad_type: AdType) -> f64 {
     }
 }
 
+// =============================================================================
+// COMMISSION SCORING
+// =============================================================================
+
+/// At or above this rate a considered purchase is worth nurturing
+const HIGH_COMMISSION_RATE: f64 = 0.08;
+/// At or below this rate an impulse product has to make it up in volume
+const LOW_COMMISSION_RATE: f64 = 0.04;
+const COMMISSION_SHIFT: f64 = 0.08;
+/// Added when the cookie window backs the bias: long cookies give nurture
+/// sequences time to convert, same-day cookies reward immediate clicks
+const COOKIE_SHIFT: f64 = 0.04;
+const LONG_COOKIE_DAYS: i64 = 30;
+const SHORT_COOKIE_DAYS: i64 = 1;
+
+/// Shifts the total toward nurture formats (email, video) for high-commission,
+/// high-price products and toward volume formats (posts, stories) for
+/// low-commission, low-price ones. Zero without a known commission or price.
+fn calculate_commission_adjustment(product: &Product, signals: &ScoringSignals, ad_type: AdType) -> f64 {
+    let (rate, price_range) = match (signals.best_commission_rate, product.price_range.as_deref()) {
+        (Some(rate), Some(price_range)) => (rate, price_range),
+        _ => return 0.0,
+    };
+    let tier = price_tier_label(price_range);
+    let nurture = matches!(ad_type, AdType::Email | AdType::VideoScript);
+    let volume = matches!(ad_type, AdType::SocialPost | AdType::Story);
+
+    let considered = matches!(tier, "high" | "premium");
+    let (favor_nurture, cookie_backs_bias) = if rate >= HIGH_COMMISSION_RATE && considered {
+        (true, signals.cookie_days.is_some_and(|d| d >= LONG_COOKIE_DAYS))
+    } else if rate <= LOW_COMMISSION_RATE && tier == "low" {
+        (false, signals.cookie_days.is_some_and(|d| d <= SHORT_COOKIE_DAYS))
+    } else {
+        return 0.0;
+    };
+
+    let shift = COMMISSION_SHIFT + if cookie_backs_bias { COOKIE_SHIFT } else { 0.0 };
+    match (favor_nurture, nurture, volume) {
+        (true, true, _) | (false, _, true) => shift,
+        (true, _, true) | (false, true, _) => -shift,
+        _ => 0.0,
+    }
+}
+
 // =============================================================================
 // REASONING GENERATION
 // =============================================================================
@@ -747,6 +806,15 @@ fn generate_reasoning(product: &Product, score: &AdTypeScore) -> String {
         }
     }
 
+    // Add commission-based reasoning
+    if score.commission_adjustment > 0.0 {
+        reasons.push(if matches!(score.ad_type, AdType::Email | AdType::VideoScript) {
+            "A high commission on a considered purchase rewards nurturing the buyer".to_string()
+        } else {
+            "A low commission on an impulse purchase calls for reach and volume".to_string()
+        });
+    }
+
     // Add platform-based reasoning
     if score.platform_score >= 0.85 {
         let platforms: Vec<&str> = [
@@ -788,6 +856,14 @@ fn generate_reasoning(product: &Product, score: &AdTypeScore) -> String {
 mod tests {
     use super::*;
 
+    fn analyze(product: &Product) -> MarketAnalysis {
+        analyze_market_for_product(product, &CategoryAffinities::defaults(), &ScoringSignals::default())
+    }
+
+    fn select(product: &Product) -> AdType {
+        analyze(product).recommended_ad_type
+    }
+
     fn create_test_product(
         category: &str,
         target_audience: Option<&str>,
@@ -811,41 +887,47 @@ mod tests {
             product_url: None,
             created_at: None,
             updated_at: None,
+            stage: None,
+            stage_changed_at: None,
+            tags: Vec::new(),
+            updated_by: None,
+            availability: None,
+            availability_checked_at: None,
         }
     }
 
     #[test]
     fn test_tech_products_favor_video_scripts() {
         let product = create_test_product("Consumer Electronics", Some("Age 30-45"), Some(60));
-        let ad_type = select_optimal_ad_type(&product);
+        let ad_type = select(&product);
         assert_eq!(ad_type, AdType::VideoScript);
     }
 
     #[test]
     fn test_fashion_products_favor_carousels() {
         let product = create_test_product("Fashion & Apparel", Some("Age 25-40"), Some(65));
-        let ad_type = select_optimal_ad_type(&product);
+        let ad_type = select(&product);
         assert!(ad_type == AdType::Carousel || ad_type == AdType::Story);
     }
 
     #[test]
     fn test_gen_z_audience_favors_stories() {
         let product = create_test_product("Beauty & Skincare", Some("Gen Z, Age 18-24"), Some(75));
-        let ad_type = select_optimal_ad_type(&product);
+        let ad_type = select(&product);
         assert_eq!(ad_type, AdType::Story);
     }
 
     #[test]
     fn test_older_audience_favors_email() {
         let product = create_test_product("Health & Wellness", Some("Age 55-70, Boomers"), Some(40));
-        let ad_type = select_optimal_ad_type(&product);
+        let ad_type = select(&product);
         assert_eq!(ad_type, AdType::Email);
     }
 
     #[test]
     fn test_high_trending_favors_social_post() {
         let product = create_test_product("Gadgets", Some("Age 25-35"), Some(92));
-        let analysis = analyze_market_for_product(&product);
+        let analysis = analyze(&product);
         // High trending should boost social post score
         assert!(analysis.confidence_score > 0.6);
     }
@@ -854,7 +936,7 @@ mod tests {
     fn test_tiktok_platform_boosts_story() {
         let mut product = create_test_product("Beauty & Skincare", Some("Age 18-30"), Some(70));
         product.tiktok_product_id = Some("tiktok123".to_string());
-        let analysis = analyze_market_for_product(&product);
+        let analysis = analyze(&product);
         assert_eq!(analysis.recommended_ad_type, AdType::Story);
     }
 
@@ -862,7 +944,7 @@ mod tests {
     fn test_youtube_platform_boosts_video_script() {
         let mut product = create_test_product("Consumer Electronics", Some("Age 30-50"), Some(55));
         product.youtube_video_id = Some("youtube456".to_string());
-        let analysis = analyze_market_for_product(&product);
+        let analysis = analyze(&product);
         assert_eq!(analysis.recommended_ad_type, AdType::VideoScript);
     }
 
@@ -871,14 +953,14 @@ mod tests {
         let mut product = create_test_product("Home & Decor", Some("Age 30-45"), Some(60));
         product.instagram_product_id = Some("insta789".to_string());
         product.pinterest_pin_id = Some("pin101".to_string());
-        let analysis = analyze_market_for_product(&product);
+        let analysis = analyze(&product);
         assert_eq!(analysis.recommended_ad_type, AdType::Carousel);
     }
 
     #[test]
     fn test_market_analysis_has_alternatives() {
         let product = create_test_product("Fashion & Apparel", Some("Age 25-35"), Some(70));
-        let analysis = analyze_market_for_product(&product);
+        let analysis = analyze(&product);
         assert!(!analysis.alternative_types.is_empty());
         assert!(analysis.alternative_types.len() <= 3);
     }
@@ -886,7 +968,7 @@ mod tests {
     #[test]
     fn test_confidence_score_in_valid_range() {
         let product = create_test_product("Consumer Electronics", Some("Age 25-45"), Some(65));
-        let analysis = analyze_market_for_product(&product);
+        let analysis = analyze(&product);
         assert!(analysis.confidence_score >= 0.0 && analysis.confidence_score <= 1.0);
     }
 
@@ -911,4 +993,51 @@ mod tests {
         assert_eq!(analysis.recommended_ad_type, AdType::SocialPost);
         assert_eq!(analysis.confidence_score, 0.5);
     }
+
+    #[test]
+    fn test_momentum_shifts_viral_and_evergreen_formats() {
+        let product = create_test_product("Pet Supplies", Some("Age 30-45"), Some(60));
+        let affinities = CategoryAffinities::defaults();
+        let trending = |momentum: Option<f64>| {
+            let signals = ScoringSignals { momentum_per_week: momentum, ..Default::default() };
+            let factors = ad_type_factors(&product, &affinities, &signals);
+            let score = |key: &str| factors.iter().find(|f| f.ad_type == key).unwrap().trending_score;
+            (score("social_post"), score("email"), score("carousel"))
+        };
+
+        let (social, email, carousel) = trending(None);
+        assert_eq!(trending(Some(1.0)), (social, email, carousel));
+        let (rising_social, rising_email, rising_carousel) = trending(Some(15.0));
+        assert!(rising_social > social && rising_email < email && rising_carousel == carousel);
+        let (declining_social, declining_email, _) = trending(Some(-8.0));
+        assert!(declining_social < social && declining_email > email);
+    }
+
+    #[test]
+    fn test_commission_biases_nurture_or_volume_formats() {
+        let affinities = CategoryAffinities::defaults();
+        let adjustments = |price_range: &str, rate: f64, cookie_days: Option<i64>| {
+            let mut product = create_test_product("Pet Supplies", Some("Age 30-45"), Some(60));
+            product.price_range = Some(price_range.to_string());
+            let signals = ScoringSignals { best_commission_rate: Some(rate), cookie_days, ..Default::default() };
+            let factors = ad_type_factors(&product, &affinities, &signals);
+            let adjustment = |key: &str| factors.iter().find(|f| f.ad_type == key).unwrap().commission_adjustment;
+            (adjustment("email"), adjustment("story"), adjustment("carousel"))
+        };
+
+        // High commission on a considered purchase nurtures; a long cookie strengthens it
+        let (email, story, carousel) = adjustments("$300-$400", 0.10, None);
+        assert!(email > 0.0 && story < 0.0 && carousel == 0.0);
+        assert!(adjustments("$300-$400", 0.10, Some(90)).0 > email);
+
+        // Low commission on an impulse buy goes for volume
+        let (email, story, _) = adjustments("$15-$25", 0.03, Some(1));
+        assert!(email < 0.0 && story > 0.0);
+
+        // Middling terms, or no commission data, leave scoring alone
+        assert_eq!(adjustments("$60-$90", 0.10, None), (0.0, 0.0, 0.0));
+        let product = create_test_product("Pet Supplies", Some("Age 30-45"), Some(60));
+        let factors = ad_type_factors(&product, &affinities, &ScoringSignals::default());
+        assert!(factors.iter().all(|f| f.commission_adjustment == 0.0));
+    }
 }
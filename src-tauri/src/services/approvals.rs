@@ -0,0 +1,240 @@
+//! Approvals
+//!
+//! A sign-off workflow for ads and scheduled posts, for users who work with a
+//! VA or a client who must approve copy: draft -> in_review -> approved ->
+//! published. A reviewer can send content back to draft with a comment, and
+//! changing approved copy reopens it. Every transition is kept, with the
+//! reviewer's comment, as the item's review history.
+
+use crate::database::settings::get_setting_or;
+use crate::models::approval::ApprovalTransition;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+pub const DRAFT: &str = "draft";
+pub const IN_REVIEW: &str = "in_review";
+pub const APPROVED: &str = "approved";
+pub const PUBLISHED: &str = "published";
+
+pub const APPROVAL_STATES: [&str; 4] = [DRAFT, IN_REVIEW, APPROVED, PUBLISHED];
+
+pub const ENTITY_AD: &str = "ad";
+pub const ENTITY_POST: &str = "scheduled_post";
+
+/// When on, posts can only be marked posted once they (and their ad) are approved
+pub const REQUIRED_SETTING: &str = "approval_required";
+
+const TRANSITION_COLUMNS: &str = "id, entity_type, entity_id, from_state, to_state, reviewer, comment, created_at";
+
+fn transition_from_row(row: &Row) -> rusqlite::Result<ApprovalTransition> {
+    Ok(ApprovalTransition {
+        id: row.get(0)?,
+        entity_type: row.get(1)?,
+        entity_id: row.get(2)?,
+        from_state: row.get(3)?,
+        to_state: row.get(4)?,
+        reviewer: row.get(5)?,
+        comment: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+/// States reachable from `state`
+pub fn next_states(state: &str) -> &'static [&'static str] {
+    match state {
+        DRAFT => &[IN_REVIEW],
+        IN_REVIEW => &[APPROVED, DRAFT],
+        APPROVED => &[PUBLISHED, DRAFT],
+        _ => &[],
+    }
+}
+
+/// Checks a transition; sending content back from review needs a comment
+/// saying what to change
+pub fn validate_transition(from: &str, to: &str, comment: Option<&str>) -> Result<(), String> {
+    if !APPROVAL_STATES.contains(&to) {
+        return Err(format!("Unknown approval state '{}'; expected one of {}", to, APPROVAL_STATES.join(", ")));
+    }
+    if !next_states(from).contains(&to) {
+        return Err(format!("Cannot move from {} to {}", from, to));
+    }
+    if from == IN_REVIEW && to == DRAFT && comment.is_none_or(|c| c.trim().is_empty()) {
+        return Err("Add a comment saying what needs to change".to_string());
+    }
+    Ok(())
+}
+
+fn table(entity_type: &str) -> Result<&'static str, String> {
+    match entity_type {
+        ENTITY_AD => Ok("ad_copies"),
+        ENTITY_POST => Ok("scheduled_posts"),
+        _ => Err(format!("Unknown entity '{}'; expected {} or {}", entity_type, ENTITY_AD, ENTITY_POST)),
+    }
+}
+
+pub fn current_state(conn: &Connection, entity_type: &str, entity_id: i64) -> Result<Option<String>, String> {
+    conn.query_row(
+        &format!("SELECT approval_state FROM {} WHERE id = ?1", table(entity_type)?),
+        params![entity_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// Records a transition without checking it against the workflow
+fn record(
+    conn: &Connection,
+    entity_type: &str,
+    entity_id: i64,
+    from: &str,
+    to: &str,
+    reviewer: Option<&str>,
+    comment: Option<&str>,
+) -> Result<ApprovalTransition, String> {
+    let clean = |value: Option<&str>| value.map(str::trim).filter(|v| !v.is_empty()).map(String::from);
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        &format!(
+            "UPDATE {} SET approval_state = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+            table(entity_type)?
+        ),
+        params![to, entity_id],
+    )
+    .map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO approval_transitions (entity_type, entity_id, from_state, to_state, reviewer, comment)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![entity_type, entity_id, from, to, clean(reviewer), clean(comment)],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let transition = tx
+        .query_row(
+            &format!("SELECT {} FROM approval_transitions WHERE id = ?1", TRANSITION_COLUMNS),
+            params![tx.last_insert_rowid()],
+            transition_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(transition)
+}
+
+/// Moves an ad or scheduled post to `to`, recording who did it and why
+pub fn transition(
+    conn: &Connection,
+    entity_type: &str,
+    entity_id: i64,
+    to: &str,
+    reviewer: Option<&str>,
+    comment: Option<&str>,
+) -> Result<ApprovalTransition, String> {
+    let from = current_state(conn, entity_type, entity_id)?
+        .ok_or_else(|| format!("{} {} not found", entity_type, entity_id))?;
+    validate_transition(&from, to, comment)?;
+    record(conn, entity_type, entity_id, &from, to, reviewer, comment)
+}
+
+/// Sends content that changed while in review or approved back to draft
+pub fn reopen(conn: &Connection, entity_type: &str, entity_id: i64, reason: &str) -> Result<(), String> {
+    if let Some(from @ (IN_REVIEW | APPROVED)) = current_state(conn, entity_type, entity_id)?.as_deref() {
+        record(conn, entity_type, entity_id, from, DRAFT, None, Some(reason))?;
+    }
+    Ok(())
+}
+
+/// Called when a post is marked posted: approved content becomes published.
+/// With approval required, the post and its ad must have been approved.
+pub fn publish_post(conn: &Connection, post_id: i64, ad_id: Option<i64>) -> Result<(), String> {
+    let required = get_setting_or(conn, REQUIRED_SETTING, "false") == "true";
+    let post_state = current_state(conn, ENTITY_POST, post_id)?.unwrap_or_default();
+    let ad_state = match ad_id {
+        Some(ad_id) => current_state(conn, ENTITY_AD, ad_id)?,
+        None => None,
+    };
+
+    if required {
+        if !matches!(post_state.as_str(), APPROVED | PUBLISHED) {
+            return Err(format!("Post {} needs approval before it can be marked posted", post_id));
+        }
+        if ad_state.as_deref().is_some_and(|s| !matches!(s, APPROVED | PUBLISHED)) {
+            return Err("The post's ad copy needs approval before it can be marked posted".to_string());
+        }
+    }
+
+    let comment = Some("Marked as posted");
+    if post_state == APPROVED {
+        record(conn, ENTITY_POST, post_id, APPROVED, PUBLISHED, None, comment)?;
+    }
+    if let (Some(ad_id), Some(APPROVED)) = (ad_id, ad_state.as_deref()) {
+        record(conn, ENTITY_AD, ad_id, APPROVED, PUBLISHED, None, comment)?;
+    }
+    Ok(())
+}
+
+/// An item's transitions, oldest first
+pub fn history(conn: &Connection, entity_type: &str, entity_id: i64) -> Result<Vec<ApprovalTransition>, String> {
+    table(entity_type)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM approval_transitions WHERE entity_type = ?1 AND entity_id = ?2
+             ORDER BY created_at, id",
+            TRANSITION_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let transitions = stmt
+        .query_map(params![entity_type, entity_id], transition_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string());
+    transitions
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workflow_transitions() {
+        assert!(validate_transition(DRAFT, IN_REVIEW, None).is_ok());
+        assert!(validate_transition(IN_REVIEW, APPROVED, Some("Looks good")).is_ok());
+        assert!(validate_transition(APPROVED, PUBLISHED, None).is_ok());
+
+        assert!(validate_transition(DRAFT, APPROVED, None).is_err());
+        assert!(validate_transition(PUBLISHED, DRAFT, Some("Typo")).is_err());
+        assert!(validate_transition(IN_REVIEW, DRAFT, Some("  ")).is_err());
+        assert!(validate_transition(IN_REVIEW, DRAFT, Some("Tone down the claims")).is_ok());
+        assert!(validate_transition(DRAFT, "rejected", None).is_err());
+    }
+
+    #[test]
+    fn test_history_goes_with_the_deleted_item() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE ad_copies (id INTEGER PRIMARY KEY AUTOINCREMENT,
+                approval_state TEXT NOT NULL DEFAULT 'draft', updated_at DATETIME);
+             CREATE TABLE scheduled_posts (id INTEGER PRIMARY KEY AUTOINCREMENT,
+                approval_state TEXT NOT NULL DEFAULT 'draft', updated_at DATETIME);
+             INSERT INTO ad_copies DEFAULT VALUES;
+             INSERT INTO scheduled_posts DEFAULT VALUES;",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../../migrations/039_approval_workflow.sql")).unwrap();
+
+        transition(&conn, ENTITY_AD, 1, IN_REVIEW, Some("Sam"), None).unwrap();
+        transition(&conn, ENTITY_POST, 1, IN_REVIEW, None, None).unwrap();
+        assert_eq!(current_state(&conn, ENTITY_AD, 1).unwrap().as_deref(), Some(IN_REVIEW));
+        assert_eq!(history(&conn, ENTITY_AD, 1).unwrap().len(), 1);
+
+        conn.execute_batch("DELETE FROM ad_copies; DELETE FROM sqlite_sequence WHERE name = 'ad_copies';").unwrap();
+        conn.execute("INSERT INTO ad_copies DEFAULT VALUES", []).unwrap();
+        assert!(history(&conn, ENTITY_AD, 1).unwrap().is_empty());
+        assert_eq!(history(&conn, ENTITY_POST, 1).unwrap().len(), 1);
+
+        conn.execute("DELETE FROM scheduled_posts", []).unwrap();
+        assert!(history(&conn, ENTITY_POST, 1).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,260 @@
+//! Attachments
+//!
+//! Rate cards, contracts, and media kits attached to products, campaigns, and
+//! affiliate credentials. Each file is copied into an `attachments` folder
+//! beside the database (so a shared workspace shares its files too) under a
+//! name prefixed with its row ID; the row keeps the original name, type, and
+//! size. Files larger than `MAX_FILE_BYTES` are refused.
+
+use crate::models::attachment::Attachment;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const MAX_FILE_BYTES: u64 = 25 * 1024 * 1024;
+const FOLDER: &str = "attachments";
+
+const ATTACHMENT_COLUMNS: &str =
+    "id, entity_type, entity_id, file_name, mime_type, size_bytes, description, created_at";
+
+fn attachment_from_row(row: &Row) -> rusqlite::Result<Attachment> {
+    Ok(Attachment {
+        id: row.get(0)?,
+        entity_type: row.get(1)?,
+        entity_id: row.get(2)?,
+        file_name: row.get(3)?,
+        mime_type: row.get(4)?,
+        size_bytes: row.get(5)?,
+        description: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+/// The folder attachments are stored in, beside the database file
+pub fn folder(database_path: &Path) -> PathBuf {
+    database_path.parent().unwrap_or_else(|| Path::new(".")).join(FOLDER)
+}
+
+/// MIME type from the file extension
+pub fn mime_type(file_name: &str) -> &'static str {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    match extension.as_str() {
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "txt" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "zip" => "application/zip",
+        "mp4" => "video/mp4",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Name inside the folder: the row ID, then the original name with anything
+/// but letters, digits, dots, dashes, and underscores replaced
+pub fn stored_name(id: i64, file_name: &str) -> String {
+    let safe: String = file_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect();
+    format!("{}-{}", id, safe.trim_start_matches('.'))
+}
+
+fn entity_exists(conn: &Connection, entity_type: &str, entity_id: i64) -> Result<bool, String> {
+    let table = match entity_type {
+        "product" => "products",
+        "campaign" => "campaigns",
+        "credential" => "affiliate_credentials",
+        other => {
+            return Err(format!("Files can't be attached to '{}' (use product, campaign, or credential)", other))
+        }
+    };
+    let sql = format!("SELECT EXISTS(SELECT 1 FROM {} WHERE id = ?1)", table);
+    conn.query_row(&sql, params![entity_id], |row| row.get(0)).map_err(|e| e.to_string())
+}
+
+/// Copies `source` into the folder and records it
+pub fn add(
+    conn: &mut Connection,
+    folder: &Path,
+    entity_type: &str,
+    entity_id: i64,
+    source: &Path,
+    description: Option<&str>,
+) -> Result<Attachment, String> {
+    if !entity_exists(conn, entity_type, entity_id)? {
+        return Err(format!("No {} with ID {}", entity_type, entity_id));
+    }
+    let metadata = fs::metadata(source).map_err(|e| format!("Can't read {}: {}", source.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("{} is not a file", source.display()));
+    }
+    if metadata.len() > MAX_FILE_BYTES {
+        return Err(format!(
+            "{} is {:.1} MB; attachments are limited to {} MB",
+            source.display(),
+            metadata.len() as f64 / (1024.0 * 1024.0),
+            MAX_FILE_BYTES / (1024 * 1024)
+        ));
+    }
+    let file_name = source
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("{} has no usable file name", source.display()))?
+        .to_string();
+    let description = description.map(str::trim).filter(|d| !d.is_empty());
+
+    // The row is written first for its ID; the copy failing rolls it back
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO attachments
+            (entity_type, entity_id, file_name, stored_name, mime_type, size_bytes, description)
+         VALUES (?1, ?2, ?3, '', ?4, ?5, ?6)",
+        params![entity_type, entity_id, file_name, mime_type(&file_name), metadata.len() as i64, description],
+    )
+    .map_err(|e| e.to_string())?;
+    let id = tx.last_insert_rowid();
+    let stored = stored_name(id, &file_name);
+    tx.execute("UPDATE attachments SET stored_name = ?2 WHERE id = ?1", params![id, stored])
+        .map_err(|e| e.to_string())?;
+    fs::create_dir_all(folder).map_err(|e| format!("Can't create {}: {}", folder.display(), e))?;
+    fs::copy(source, folder.join(&stored)).map_err(|e| format!("Can't copy {}: {}", source.display(), e))?;
+    tx.commit().map_err(|e| e.to_string())?;
+    get(conn, id)
+}
+
+pub fn get(conn: &Connection, id: i64) -> Result<Attachment, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM attachments WHERE id = ?1", ATTACHMENT_COLUMNS),
+        params![id],
+        attachment_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Attachment {} not found", id))
+}
+
+/// An entity's attachments, newest first
+pub fn list(conn: &Connection, entity_type: &str, entity_id: i64) -> rusqlite::Result<Vec<Attachment>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM attachments WHERE entity_type = ?1 AND entity_id = ?2 ORDER BY created_at DESC, id DESC",
+        ATTACHMENT_COLUMNS
+    ))?;
+    let attachments = stmt.query_map(params![entity_type, entity_id], attachment_from_row)?.collect();
+    attachments
+}
+
+/// Where an attachment's file is stored
+pub fn path(conn: &Connection, folder: &Path, id: i64) -> Result<PathBuf, String> {
+    let stored: String = conn
+        .query_row("SELECT stored_name FROM attachments WHERE id = ?1", params![id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Attachment {} not found", id))?;
+    let path = folder.join(stored);
+    if !path.is_file() {
+        return Err(format!("The file for attachment {} is missing from {}", id, folder.display()));
+    }
+    Ok(path)
+}
+
+/// Deletes the row and its file
+pub fn delete(conn: &Connection, folder: &Path, id: i64) -> Result<(), String> {
+    let stored: Option<String> = conn
+        .query_row("SELECT stored_name FROM attachments WHERE id = ?1", params![id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM attachments WHERE id = ?1", params![id]).map_err(|e| e.to_string())?;
+    if let Some(stored) = stored {
+        match fs::remove_file(folder.join(stored)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.to_string()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Removes files no row refers to (left by deleted entities or a workspace
+/// reset); returns how many were removed
+pub fn prune(conn: &Connection, folder: &Path) -> Result<usize, String> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return Ok(0);
+    };
+    let known: HashSet<String> = conn
+        .prepare("SELECT stored_name FROM attachments")
+        .and_then(|mut stmt| stmt.query_map([], |row| row.get(0))?.collect())
+        .map_err(|e| e.to_string())?;
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.path().is_file() && !known.contains(&name) && fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_files_are_copied_listed_and_removed() {
+        assert_eq!(stored_name(4, "Rate card (2025).pdf"), "4-Rate_card__2025_.pdf");
+        assert_eq!(stored_name(5, "../.env"), "5-_.env");
+        assert_eq!(mime_type("kit.PDF"), "application/pdf");
+        assert_eq!(mime_type("notes"), "application/octet-stream");
+
+        let dir = std::env::temp_dir().join(format!("affilai-attachments-{}", std::process::id()));
+        let folder = dir.join(FOLDER);
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("media kit.txt");
+        fs::write(&source, "rates").unwrap();
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY);
+             CREATE TABLE campaigns (id INTEGER PRIMARY KEY);
+             CREATE TABLE affiliate_credentials (id INTEGER PRIMARY KEY);
+             INSERT INTO products VALUES (1);
+             {}",
+            include_str!("../../../migrations/048_attachments.sql")
+        ))
+        .unwrap();
+
+        assert!(add(&mut conn, &folder, "product", 2, &source, None).is_err());
+        assert!(add(&mut conn, &folder, "bundle", 1, &source, None).is_err());
+        let attachment = add(&mut conn, &folder, "product", 1, &source, Some(" Q3 ")).unwrap();
+        assert_eq!(attachment.file_name, "media kit.txt");
+        assert_eq!(attachment.size_bytes, 5);
+        assert_eq!(attachment.description.as_deref(), Some("Q3"));
+        let stored = path(&conn, &folder, attachment.id).unwrap();
+        assert_eq!(fs::read_to_string(&stored).unwrap(), "rates");
+        assert_eq!(list(&conn, "product", 1).unwrap().len(), 1);
+
+        // Deleting the product drops the row; pruning then removes the file
+        conn.execute("DELETE FROM products WHERE id = 1", []).unwrap();
+        assert!(list(&conn, "product", 1).unwrap().is_empty());
+        assert_eq!(prune(&conn, &folder).unwrap(), 1);
+        assert!(!stored.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,152 @@
+//! Click Deduplication and Conversion Attribution
+//!
+//! Raw click logs over-count: bots and impatient humans click the same link
+//! repeatedly. This module collapses repeat clicks from the same visitor
+//! (IP hash + user agent) inside a configurable window, and only credits a
+//! conversion to a link when a qualifying click happened within the
+//! attribution window before it.
+
+use crate::database::parse_timestamp;
+use crate::database::settings::get_setting_parsed;
+use crate::models::conversion::AttributionConfig;
+use chrono::{Duration, NaiveDateTime};
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+pub const DEFAULT_DEDUP_WINDOW_MINUTES: i64 = 30;
+pub const DEFAULT_ATTRIBUTION_WINDOW_DAYS: i64 = 30;
+
+/// A click as needed for deduplication
+#[derive(Debug, Clone)]
+pub struct ClickRecord {
+    pub clicked_at: NaiveDateTime,
+    pub ip_hash: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+/// Loads the attribution configuration from the settings table
+pub fn load_config(conn: &Connection) -> AttributionConfig {
+    AttributionConfig {
+        dedup_window_minutes: get_setting_parsed(
+            conn,
+            "click_dedup_window_minutes",
+            DEFAULT_DEDUP_WINDOW_MINUTES,
+        ),
+        attribution_window_days: get_setting_parsed(
+            conn,
+            "attribution_window_days",
+            DEFAULT_ATTRIBUTION_WINDOW_DAYS,
+        ),
+    }
+}
+
+/// Returns the clicks that count as unique.
+/// A click is a duplicate when the same visitor (IP hash + user agent) already
+/// produced a counted click less than `window_minutes` earlier. Clicks without
+/// an IP hash cannot be attributed to a visitor and always count.
+pub fn dedup_clicks(clicks: &[ClickRecord], window_minutes: i64) -> Vec<ClickRecord> {
+    let mut sorted: Vec<&ClickRecord> = clicks.iter().collect();
+    sorted.sort_by_key(|c| c.clicked_at);
+
+    let window = Duration::minutes(window_minutes.max(0));
+    let mut unique: Vec<ClickRecord> = Vec::new();
+    // Clicks come in time order, so a visitor's last counted click is the only one the window can reach
+    let mut last_counted: HashMap<(&str, Option<&str>), NaiveDateTime> = HashMap::new();
+
+    for click in sorted {
+        if let Some(ip_hash) = click.ip_hash.as_deref() {
+            let visitor = (ip_hash, click.user_agent.as_deref());
+            if last_counted.get(&visitor).is_some_and(|&kept| click.clicked_at - kept < window) {
+                continue;
+            }
+            last_counted.insert(visitor, click.clicked_at);
+        }
+        unique.push(click.clone());
+    }
+
+    unique
+}
+
+/// Counts conversions that have at least one click in the attribution window
+/// `[converted_at - window_days, converted_at]`.
+pub fn count_attributed(
+    clicks: &[ClickRecord],
+    conversions: &[NaiveDateTime],
+    window_days: i64,
+) -> i64 {
+    let window = Duration::days(window_days.max(0));
+
+    conversions
+        .iter()
+        .filter(|&&converted_at| {
+            clicks.iter().any(|c| {
+                c.clicked_at <= converted_at && converted_at - c.clicked_at <= window
+            })
+        })
+        .count() as i64
+}
+
+/// Parses click rows (clicked_at, ip_hash, user_agent), skipping unparseable timestamps
+pub fn to_click_records(rows: Vec<(String, Option<String>, Option<String>)>) -> Vec<ClickRecord> {
+    rows.into_iter()
+        .filter_map(|(clicked_at, ip_hash, user_agent)| {
+            Some(ClickRecord {
+                clicked_at: parse_timestamp(&clicked_at)?,
+                ip_hash,
+                user_agent,
+            })
+        })
+        .collect()
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(ts: &str, ip: Option<&str>, ua: &str) -> ClickRecord {
+        ClickRecord {
+            clicked_at: parse_timestamp(ts).unwrap(),
+            ip_hash: ip.map(String::from),
+            user_agent: Some(ua.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_repeat_clicks_within_window_count_once() {
+        let clicks = vec![
+            click("2025-03-01 10:00:00", Some("a"), "ua"),
+            click("2025-03-01 10:05:00", Some("a"), "ua"),
+            click("2025-03-01 10:20:00", Some("a"), "ua"),
+            click("2025-03-01 11:00:00", Some("a"), "ua"),
+        ];
+        // 10:00 counts, 10:05 and 10:20 are repeats, 11:00 is outside the 30 min window
+        assert_eq!(dedup_clicks(&clicks, 30).len(), 2);
+    }
+
+    #[test]
+    fn test_different_visitors_are_not_merged() {
+        let clicks = vec![
+            click("2025-03-01 10:00:00", Some("a"), "ua"),
+            click("2025-03-01 10:01:00", Some("b"), "ua"),
+            click("2025-03-01 10:02:00", Some("a"), "other-ua"),
+            click("2025-03-01 10:03:00", None, "ua"),
+            click("2025-03-01 10:04:00", None, "ua"),
+        ];
+        assert_eq!(dedup_clicks(&clicks, 30).len(), 5);
+    }
+
+    #[test]
+    fn test_attribution_window() {
+        let clicks = vec![click("2025-03-01 10:00:00", Some("a"), "ua")];
+        let conversions = vec![
+            parse_timestamp("2025-03-05 10:00:00").unwrap(), // inside 7 days
+            parse_timestamp("2025-03-20 10:00:00").unwrap(), // outside 7 days
+            parse_timestamp("2025-02-28 10:00:00").unwrap(), // before the click
+        ];
+        assert_eq!(count_attributed(&clicks, &conversions, 7), 1);
+    }
+}
@@ -0,0 +1,181 @@
+//! Audience Parsing
+//!
+//! Turns a free-text target audience into an `AudienceProfile`: an age range
+//! (explicit "18-35", "55+", or a generation name), the gender mix (a
+//! percentage, or which genders are named), interests from a fixed
+//! vocabulary, and a country. Platform and ad type scoring read the profile;
+//! anything the text doesn't mention is left neutral.
+
+use crate::models::audience::AudienceProfile;
+
+/// Age range assumed when the text has none
+pub const DEFAULT_AGE_RANGE: (i32, i32) = (25, 45);
+
+/// Female share at or above which an audience counts as female (and at or below
+/// one minus it, male)
+const GENDER_MAJORITY: f64 = 0.6;
+
+const GENERATIONS: [(&[&str], (i32, i32)); 4] = [
+    (&["gen z", "genz", "zoomer", "zoomers"], (18, 25)),
+    (&["millennial", "millennials"], (26, 40)),
+    (&["gen x", "genx"], (41, 55)),
+    (&["boomer", "boomers", "senior", "seniors", "retirees"], (56, 70)),
+];
+
+const FEMALE_WORDS: [&str; 10] =
+    ["female", "females", "women", "woman", "moms", "mums", "mothers", "ladies", "girls", "brides"];
+const MALE_WORDS: [&str; 10] =
+    ["male", "males", "men", "man", "dads", "fathers", "guys", "boys", "grooms", "gents"];
+
+/// Interest keys and the phrases that name them
+pub const INTERESTS: [(&str, &[&str]); 16] = [
+    ("home_decor", &["home decor", "interior design", "decor"]),
+    ("diy", &["diy", "crafts", "crafting", "makers"]),
+    ("cooking", &["cooking", "baking", "recipes", "foodies", "foodie"]),
+    ("gardening", &["gardening", "gardeners", "plants"]),
+    ("fitness", &["fitness", "gym", "workout", "athletes", "runners", "yoga"]),
+    ("gaming", &["gaming", "gamers", "esports"]),
+    ("tech", &["tech", "gadgets", "techies", "early adopters"]),
+    ("beauty", &["beauty", "skincare", "makeup"]),
+    ("fashion", &["fashion", "streetwear", "style"]),
+    ("travel", &["travel", "travelers", "travellers"]),
+    ("parenting", &["parents", "parenting", "new moms", "new dads"]),
+    ("pets", &["pets", "pet owners", "dog owners", "cat owners"]),
+    ("outdoors", &["outdoors", "hiking", "camping"]),
+    ("finance", &["finance", "investing", "investors"]),
+    ("deals", &["deals", "deal hunters", "bargain hunters", "coupons", "budget"]),
+    ("music", &["music", "musicians"]),
+];
+
+/// Country codes and the names that identify them (codes themselves must be uppercase)
+const LOCALES: [(&str, &[&str], &[&str]); 12] = [
+    ("US", &["US", "USA"], &["united states", "american", "americans"]),
+    ("GB", &["UK", "GB"], &["united kingdom", "britain", "british", "england"]),
+    ("CA", &["CA"], &["canada", "canadian", "canadians"]),
+    ("AU", &["AU"], &["australia", "australian", "australians"]),
+    ("DE", &["DE"], &["germany", "german", "germans"]),
+    ("FR", &["FR"], &["france", "french"]),
+    ("ES", &["ES"], &["spain"]),
+    ("MX", &["MX"], &["mexico", "mexican", "mexicans"]),
+    ("BR", &["BR"], &["brazil", "brazilian", "brazilians"]),
+    ("IN", &[], &["india", "indian", "indians"]),
+    ("JP", &["JP"], &["japan", "japanese"]),
+    ("CN", &["CN"], &["china", "chinese"]),
+];
+
+/// Parses a target audience description
+pub fn parse(text: &str) -> AudienceProfile {
+    let lower = text.to_lowercase();
+    // Lowercase words padded with spaces, so phrases only match whole words
+    let words = format!(
+        " {} ",
+        lower.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect::<Vec<_>>().join(" ")
+    );
+    let has = |phrase: &str| words.contains(&format!(" {} ", phrase));
+
+    let (age_min, age_max) = age_range(&lower, &has);
+    let female_share = female_share(&lower, &has);
+    let gender = match female_share {
+        None => "unspecified",
+        Some(share) if share >= GENDER_MAJORITY => "female",
+        Some(share) if share <= 1.0 - GENDER_MAJORITY => "male",
+        Some(_) => "mixed",
+    };
+    let interests = INTERESTS
+        .iter()
+        .filter(|(_, phrases)| phrases.iter().any(|p| has(p)))
+        .map(|(key, _)| key.to_string())
+        .collect();
+
+    let codes: Vec<&str> = text.split(|c: char| !c.is_alphanumeric()).collect();
+    let locale = LOCALES
+        .iter()
+        .find(|(_, upper, names)| upper.iter().any(|u| codes.contains(u)) || names.iter().any(|n| has(n)))
+        .map(|(code, _, _)| code.to_string());
+
+    AudienceProfile {
+        age_min,
+        age_max,
+        gender: gender.to_string(),
+        female_share,
+        interests,
+        locale,
+    }
+}
+
+fn age_range(lower: &str, has: &impl Fn(&str) -> bool) -> (i32, i32) {
+    // "Age 18-35", "ages 30–50", "25-34"
+    if let Ok(re) = regex::Regex::new(r"(\d{1,2})\s*[-–]\s*(\d{1,2})") {
+        if let Some(caps) = re.captures(lower) {
+            let min = caps[1].parse::<i32>().unwrap_or(DEFAULT_AGE_RANGE.0);
+            let max = caps[2].parse::<i32>().unwrap_or(DEFAULT_AGE_RANGE.1);
+            return (min, max);
+        }
+    }
+    // "55+"
+    if let Ok(re) = regex::Regex::new(r"(\d{2})\s*\+") {
+        if let Some(caps) = re.captures(lower) {
+            let min = caps[1].parse::<i32>().unwrap_or(DEFAULT_AGE_RANGE.0);
+            return (min, min + 20);
+        }
+    }
+    GENERATIONS
+        .iter()
+        .find(|(names, _)| names.iter().any(|n| has(n)))
+        .map(|(_, range)| *range)
+        .unwrap_or(DEFAULT_AGE_RANGE)
+}
+
+fn female_share(lower: &str, has: &impl Fn(&str) -> bool) -> Option<f64> {
+    // "70% women", "60 % male"
+    if let Ok(re) = regex::Regex::new(r"(\d{1,3})\s*%\s*([a-z]+)") {
+        for caps in re.captures_iter(lower) {
+            let share = (caps[1].parse::<f64>().unwrap_or(50.0) / 100.0).clamp(0.0, 1.0);
+            if FEMALE_WORDS.contains(&&caps[2]) {
+                return Some(share);
+            }
+            if MALE_WORDS.contains(&&caps[2]) {
+                return Some(1.0 - share);
+            }
+        }
+    }
+    match (FEMALE_WORDS.iter().any(|w| has(w)), MALE_WORDS.iter().any(|w| has(w))) {
+        (true, false) => Some(1.0),
+        (false, true) => Some(0.0),
+        (true, true) => Some(0.5),
+        (false, false) => None,
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_age_gender_interests_and_locale() {
+        let audience = parse("Ages 30-50, 70% women into home decor and DIY, UK");
+        assert_eq!((audience.age_min, audience.age_max), (30, 50));
+        assert_eq!((audience.gender.as_str(), audience.female_share), ("female", Some(0.7)));
+        assert_eq!(audience.interests, vec!["home_decor", "diy"]);
+        assert_eq!(audience.locale.as_deref(), Some("GB"));
+
+        let gamers = parse("Gen Z guys, gaming and tech, United States");
+        assert_eq!((gamers.age_min, gamers.age_max), (18, 25));
+        assert_eq!(gamers.gender, "male");
+        assert_eq!(gamers.interests, vec!["gaming", "tech"]);
+        assert_eq!(gamers.locale.as_deref(), Some("US"));
+
+        // "us" as a word and "female" containing "male" don't mislead the parser
+        let plain = parse("People like us, 55+, female");
+        assert_eq!((plain.age_min, plain.age_max, plain.gender.as_str()), (55, 75, "female"));
+        assert_eq!(plain.locale, None);
+
+        let empty = parse("");
+        assert_eq!((empty.age_min, empty.age_max), DEFAULT_AGE_RANGE);
+        assert_eq!((empty.gender.as_str(), empty.interests.len(), empty.locale), ("unspecified", 0, None));
+    }
+}
@@ -0,0 +1,122 @@
+//! Product Availability
+//!
+//! Stock status per product, read from the product page whenever it is
+//! scraped (identifier enrichment, the watchlist, promoting an idea) or set by
+//! hand. Bulk link and ad generation skip out-of-stock products unless asked
+//! not to, and scheduling a post for one saves it with a warning.
+
+use crate::models::scheduled_post::ScheduleConflict;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub const IN_STOCK: &str = "in_stock";
+pub const OUT_OF_STOCK: &str = "out_of_stock";
+pub const UNKNOWN: &str = "unknown";
+
+/// `ScheduleConflict::kind` for a post of an out-of-stock product
+pub const OUT_OF_STOCK_CONFLICT: &str = "out_of_stock";
+
+/// Maps schema.org / OpenGraph availability values ("https://schema.org/InStock",
+/// "instock", "out of stock") to a status; `None` when the page published nothing
+pub fn normalize(raw: &str) -> Option<String> {
+    let compact: String = raw
+        .rsplit('/')
+        .next()
+        .unwrap_or(raw)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    let status = match compact.as_str() {
+        "" => return None,
+        "instock" | "limitedavailability" | "onlineonly" | "instoreonly" => IN_STOCK,
+        "outofstock" | "soldout" | "discontinued" => OUT_OF_STOCK,
+        // Preorder, backorder, and anything unrecognised can't be promised
+        _ => UNKNOWN,
+    };
+    Some(status.to_string())
+}
+
+/// Validates a status set by hand
+pub fn parse_status(value: &str) -> Result<String, String> {
+    let value = value.trim().to_lowercase();
+    match value.as_str() {
+        IN_STOCK | OUT_OF_STOCK | UNKNOWN => Ok(value),
+        _ => Err(format!("Invalid availability '{}' (use in_stock, out_of_stock, or unknown)", value)),
+    }
+}
+
+/// Stores a status read from the product page. Pages that publish no
+/// availability leave the last known status alone.
+pub fn record_scraped(conn: &Connection, product_id: i64, raw: Option<&str>) -> rusqlite::Result<()> {
+    let Some(status) = raw.and_then(normalize) else {
+        return Ok(());
+    };
+    conn.execute(
+        "UPDATE products SET availability = ?2, availability_checked_at = CURRENT_TIMESTAMP WHERE id = ?1",
+        params![product_id, status],
+    )?;
+    Ok(())
+}
+
+pub fn is_out_of_stock(conn: &Connection, product_id: i64) -> rusqlite::Result<bool> {
+    let status: Option<Option<String>> = conn
+        .query_row("SELECT availability FROM products WHERE id = ?1", params![product_id], |row| row.get(0))
+        .optional()?;
+    Ok(status.flatten().as_deref() == Some(OUT_OF_STOCK))
+}
+
+/// Skip reason for bulk generation
+pub fn skip_reason(availability: Option<&str>, include_out_of_stock: bool) -> Option<String> {
+    (!include_out_of_stock && availability == Some(OUT_OF_STOCK)).then(|| "Out of stock".to_string())
+}
+
+/// Warning attached to a post scheduled for an out-of-stock product
+pub fn schedule_warning(
+    conn: &Connection,
+    product_id: i64,
+    platform: &str,
+) -> rusqlite::Result<Option<ScheduleConflict>> {
+    if !is_out_of_stock(conn, product_id)? {
+        return Ok(None);
+    }
+    Ok(Some(ScheduleConflict {
+        kind: OUT_OF_STOCK_CONFLICT.to_string(),
+        platform: platform.to_string(),
+        message: "This product was out of stock when last checked".to_string(),
+        post_ids: Vec::new(),
+    }))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statuses_normalize_and_out_of_stock_is_flagged() {
+        assert_eq!(normalize("https://schema.org/InStock").as_deref(), Some(IN_STOCK));
+        assert_eq!(normalize("out of stock").as_deref(), Some(OUT_OF_STOCK));
+        assert_eq!(normalize("PreOrder").as_deref(), Some(UNKNOWN));
+        assert_eq!(normalize(" "), None);
+        assert!(parse_status("Out_Of_Stock").is_ok());
+        assert!(parse_status("gone").is_err());
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, availability TEXT DEFAULT 'unknown',
+                availability_checked_at DATETIME);
+             INSERT INTO products (id) VALUES (1);",
+        )
+        .unwrap();
+        record_scraped(&conn, 1, Some("http://schema.org/SoldOut")).unwrap();
+        record_scraped(&conn, 1, None).unwrap();
+        assert!(is_out_of_stock(&conn, 1).unwrap());
+        assert_eq!(schedule_warning(&conn, 1, "tiktok").unwrap().unwrap().kind, OUT_OF_STOCK_CONFLICT);
+
+        assert_eq!(skip_reason(Some(OUT_OF_STOCK), false).as_deref(), Some("Out of stock"));
+        assert_eq!(skip_reason(Some(OUT_OF_STOCK), true), None);
+    }
+}
@@ -0,0 +1,211 @@
+//! Benchmarks
+//!
+//! Reference click-through rate, conversion rate, and order value multiplier
+//! per platform, used to project results before any content exists. Shipped
+//! rows (migration 049) cover each platform for any category, plus a few
+//! categories that behave differently; users can edit them, reset them, and
+//! add their own. A lookup prefers the platform's row for the product's
+//! category, then its any-category row, then the `other` platform.
+
+use crate::models::benchmark::{Benchmark, SaveBenchmarkInput};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+/// Platform whose rows apply to platforms without their own
+pub const FALLBACK_PLATFORM: &str = "other";
+
+const BENCHMARK_COLUMNS: &str = "id, platform, category, ctr, conversion_rate, aov_multiplier,
+     default_ctr IS NOT NULL,
+     default_ctr IS NOT NULL AND (ctr != default_ctr OR conversion_rate != default_conversion_rate
+        OR aov_multiplier != default_aov_multiplier)";
+
+fn benchmark_from_row(row: &Row) -> rusqlite::Result<Benchmark> {
+    Ok(Benchmark {
+        id: row.get(0)?,
+        platform: row.get(1)?,
+        category: row.get(2)?,
+        ctr: row.get(3)?,
+        conversion_rate: row.get(4)?,
+        aov_multiplier: row.get(5)?,
+        builtin: row.get(6)?,
+        edited: row.get(7)?,
+    })
+}
+
+pub fn list(conn: &Connection) -> rusqlite::Result<Vec<Benchmark>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM benchmarks ORDER BY platform = ?1, platform, category",
+        BENCHMARK_COLUMNS
+    ))?;
+    let benchmarks = stmt.query_map(params![FALLBACK_PLATFORM], benchmark_from_row)?.collect();
+    benchmarks
+}
+
+pub fn get(conn: &Connection, id: i64) -> Result<Benchmark, String> {
+    let sql = format!("SELECT {} FROM benchmarks WHERE id = ?1", BENCHMARK_COLUMNS);
+    conn.query_row(&sql, params![id], benchmark_from_row)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Benchmark {} not found", id))
+}
+
+/// Platforms with their own benchmarks
+pub fn platforms(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+    let mut stmt =
+        conn.prepare("SELECT DISTINCT platform FROM benchmarks WHERE platform != ?1 ORDER BY platform")?;
+    let platforms = stmt.query_map(params![FALLBACK_PLATFORM], |row| row.get(0))?.collect();
+    platforms
+}
+
+/// The benchmark that applies to a platform and product category
+pub fn lookup(conn: &Connection, platform: &str, category: &str) -> Result<Benchmark, String> {
+    // NOCASE goes on the column: with it on the parameter, the OR is answered from
+    // the (platform, category) index, which compares categories case-sensitively
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM benchmarks
+             WHERE platform IN (?1, ?3) AND (category = '' OR category COLLATE NOCASE = ?2)
+             ORDER BY platform = ?1 DESC, category != '' DESC LIMIT 1",
+            BENCHMARK_COLUMNS
+        ),
+        params![platform.trim().to_lowercase(), category.trim(), FALLBACK_PLATFORM],
+        benchmark_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("No benchmark for {} (and no '{}' fallback)", platform, FALLBACK_PLATFORM))
+}
+
+/// Validates and saves a benchmark
+pub fn save(conn: &Connection, input: &SaveBenchmarkInput) -> Result<Benchmark, String> {
+    let platform = input.platform.trim().to_lowercase();
+    if platform.is_empty() {
+        return Err("Platform is required".to_string());
+    }
+    let category = input.category.as_deref().unwrap_or("").trim().to_string();
+    for (field, value) in [("CTR", input.ctr), ("Conversion rate", input.conversion_rate)] {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(format!("{} must be between 0 and 1 (0.01 = 1%)", field));
+        }
+    }
+    let aov_multiplier = input.aov_multiplier.unwrap_or(1.0);
+    if aov_multiplier <= 0.0 {
+        return Err("Order value multiplier must be greater than zero".to_string());
+    }
+
+    let taken: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM benchmarks WHERE platform = ?1 AND category = ?2 COLLATE NOCASE",
+            params![platform, category],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if taken.is_some() && taken != input.id {
+        let scope = if category.is_empty() { "any category".to_string() } else { category.clone() };
+        return Err(format!("There is already a {} benchmark for {}", platform, scope));
+    }
+
+    let id = match input.id {
+        Some(id) => {
+            let builtin = get(conn, id)?.builtin;
+            // Shipped rows keep their platform and category so they can be reset
+            let updated = if builtin {
+                conn.execute(
+                    "UPDATE benchmarks SET ctr = ?2, conversion_rate = ?3, aov_multiplier = ?4,
+                        updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+                    params![id, input.ctr, input.conversion_rate, aov_multiplier],
+                )
+            } else {
+                conn.execute(
+                    "UPDATE benchmarks SET platform = ?2, category = ?3, ctr = ?4, conversion_rate = ?5,
+                        aov_multiplier = ?6, updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+                    params![id, platform, category, input.ctr, input.conversion_rate, aov_multiplier],
+                )
+            };
+            updated.map_err(|e| e.to_string())?;
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO benchmarks (platform, category, ctr, conversion_rate, aov_multiplier)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![platform, category, input.ctr, input.conversion_rate, aov_multiplier],
+            )
+            .map_err(|e| e.to_string())?;
+            conn.last_insert_rowid()
+        }
+    };
+    get(conn, id)
+}
+
+/// Restores a shipped benchmark's values
+pub fn reset(conn: &Connection, id: i64) -> Result<Benchmark, String> {
+    if !get(conn, id)?.builtin {
+        return Err("Only shipped benchmarks can be reset; delete this one instead".to_string());
+    }
+    conn.execute(
+        "UPDATE benchmarks SET ctr = default_ctr, conversion_rate = default_conversion_rate,
+            aov_multiplier = default_aov_multiplier, updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+        params![id],
+    )
+    .map_err(|e| e.to_string())?;
+    get(conn, id)
+}
+
+/// Deletes a benchmark the user added
+pub fn delete(conn: &Connection, id: i64) -> Result<(), String> {
+    if get(conn, id)?.builtin {
+        return Err("Shipped benchmarks can't be deleted; reset it instead".to_string());
+    }
+    conn.execute("DELETE FROM benchmarks WHERE id = ?1", params![id]).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_prefers_category_then_platform_then_other() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../../migrations/049_benchmarks.sql")).unwrap();
+
+        let beauty = lookup(&conn, "TikTok", "beauty & skincare").unwrap();
+        assert_eq!((beauty.category.as_str(), beauty.ctr), ("Beauty & Skincare", 0.012));
+        assert_eq!(lookup(&conn, "tiktok", "Pets").unwrap().category, "");
+        assert_eq!(lookup(&conn, "snapchat", "Pets").unwrap().platform, FALLBACK_PLATFORM);
+        assert!(!platforms(&conn).unwrap().contains(&FALLBACK_PLATFORM.to_string()));
+
+        // Shipped rows can be edited and reset, not deleted or duplicated
+        let input = SaveBenchmarkInput {
+            id: Some(beauty.id),
+            platform: "tiktok".to_string(),
+            category: Some("Beauty & Skincare".to_string()),
+            ctr: 0.02,
+            conversion_rate: 0.03,
+            aov_multiplier: None,
+        };
+        assert!(save(&conn, &input).unwrap().edited);
+        assert!(save(&conn, &SaveBenchmarkInput { id: None, ..input.clone() }).is_err());
+        assert!(save(&conn, &SaveBenchmarkInput { ctr: 2.0, ..input }).is_err());
+        assert!(delete(&conn, beauty.id).is_err());
+        assert_eq!(reset(&conn, beauty.id).unwrap(), beauty);
+
+        let pets = SaveBenchmarkInput {
+            id: None,
+            platform: "TikTok".to_string(),
+            category: Some("Pets".to_string()),
+            ctr: 0.011,
+            conversion_rate: 0.02,
+            aov_multiplier: Some(0.8),
+        };
+        let pets = save(&conn, &pets).unwrap();
+        assert_eq!(lookup(&conn, "tiktok", "Pets").unwrap().id, pets.id);
+        assert!(reset(&conn, pets.id).is_err());
+        delete(&conn, pets.id).unwrap();
+    }
+}
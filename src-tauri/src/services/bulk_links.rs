@@ -0,0 +1,119 @@
+//! Bulk Link Generation Scoping
+//!
+//! Decides which products a bulk link generation run should touch. Products
+//! that fall outside the filter are reported as skipped with a reason instead
+//! of silently disappearing from the result.
+
+use crate::models::affiliate_link::BulkLinkFilter;
+use crate::models::product::Product;
+use crate::services::availability;
+
+/// Returns why `product` is excluded by `filter`, or `None` when it should get a link.
+/// `linked_platforms` are the platforms the product already has links on.
+pub fn skip_reason(product: &Product, linked_platforms: &[String], filter: &BulkLinkFilter) -> Option<String> {
+    if let Some(reason) = availability::skip_reason(product.availability.as_deref(), filter.include_out_of_stock) {
+        return Some(reason);
+    }
+
+    if let Some(category) = filter.category.as_deref().filter(|c| !c.is_empty()) {
+        if !product.category.eq_ignore_ascii_case(category) {
+            return Some(format!("Category is {}", product.category));
+        }
+    }
+
+    if !filter.tags.is_empty()
+        && !filter
+            .tags
+            .iter()
+            .any(|t| product.tags.iter().any(|pt| pt.eq_ignore_ascii_case(t.trim())))
+    {
+        return Some("No matching tag".to_string());
+    }
+
+    if let Some(min) = filter.min_trending_score {
+        let score = product.trending_score.unwrap_or(0);
+        if score < min {
+            return Some(format!("Trending score {} is below {}", score, min));
+        }
+    }
+
+    if filter.missing_platform_only {
+        match filter.target_platform.as_deref() {
+            Some(platform) if linked_platforms.iter().any(|p| p.eq_ignore_ascii_case(platform)) => {
+                return Some(format!("Already has a {} link", platform.to_lowercase()));
+            }
+            None if !linked_platforms.is_empty() => {
+                return Some("Already has a link".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product(category: &str, score: i32, tags: &[&str]) -> Product {
+        Product {
+            id: Some(1),
+            name: "Test Product".to_string(),
+            category: category.to_string(),
+            description: None,
+            price_range: None,
+            target_audience: None,
+            trending_score: Some(score),
+            notes: None,
+            image_url: None,
+            amazon_asin: None,
+            tiktok_product_id: None,
+            instagram_product_id: None,
+            youtube_video_id: None,
+            pinterest_pin_id: None,
+            product_url: None,
+            created_at: None,
+            updated_at: None,
+            stage: None,
+            stage_changed_at: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            updated_by: None,
+            availability: None,
+            availability_checked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_filters_by_category_tags_and_score() {
+        let p = product("Beauty & Skincare", 70, &["k-beauty"]);
+        let filter = |f: BulkLinkFilter| skip_reason(&p, &[], &f);
+
+        assert!(filter(BulkLinkFilter::default()).is_none());
+        assert!(filter(BulkLinkFilter { category: Some("beauty & skincare".into()), ..Default::default() }).is_none());
+        assert!(filter(BulkLinkFilter { category: Some("Fitness".into()), ..Default::default() }).is_some());
+        assert!(filter(BulkLinkFilter { tags: vec!["K-Beauty".into()], ..Default::default() }).is_none());
+        assert!(filter(BulkLinkFilter { tags: vec!["gift".into()], ..Default::default() }).is_some());
+        assert!(filter(BulkLinkFilter { min_trending_score: Some(80), ..Default::default() }).is_some());
+    }
+
+    #[test]
+    fn test_missing_platform_only() {
+        let p = product("Fitness", 50, &[]);
+        let linked = vec!["amazon".to_string()];
+        let missing = |target: Option<&str>| BulkLinkFilter {
+            missing_platform_only: true,
+            target_platform: target.map(String::from),
+            ..Default::default()
+        };
+
+        assert!(skip_reason(&p, &linked, &missing(None)).is_some());
+        assert!(skip_reason(&p, &linked, &missing(Some("Amazon"))).is_some());
+        assert!(skip_reason(&p, &linked, &missing(Some("tiktok"))).is_none());
+        assert!(skip_reason(&p, &[], &missing(None)).is_none());
+    }
+}
@@ -0,0 +1,242 @@
+//! Bulk Operation Tracking
+//!
+//! Bulk link and ad generation runs are persisted as an operation plus one
+//! item per product. Items move from `pending` to `succeeded`/`failed`/
+//! `skipped` as the run progresses, so a run interrupted by a crash or
+//! cancelled by the user can be resumed by processing its pending items.
+
+use crate::database::settings::get_setting_parsed;
+use crate::models::bulk_operation::{BulkOperation, BulkOperationItem, BulkProgress};
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub const PROGRESS_EVENT: &str = "bulk-operation-progress";
+
+pub const DEFAULT_PARALLELISM: usize = 4;
+const MAX_PARALLELISM: usize = 16;
+
+/// How many items a bulk run processes concurrently (`bulk_parallelism` setting)
+pub fn load_parallelism(conn: &Connection) -> usize {
+    clamp_parallelism(get_setting_parsed(conn, "bulk_parallelism", DEFAULT_PARALLELISM))
+}
+
+pub fn clamp_parallelism(value: usize) -> usize {
+    value.clamp(1, MAX_PARALLELISM)
+}
+
+const OPERATION_SELECT: &str = "SELECT o.id, o.kind, o.status, o.params_json,
+     COUNT(i.id),
+     COALESCE(SUM(i.status = 'pending'), 0),
+     COALESCE(SUM(i.status = 'succeeded'), 0),
+     COALESCE(SUM(i.status = 'failed'), 0),
+     COALESCE(SUM(i.status = 'skipped'), 0),
+     o.created_at, o.updated_at
+     FROM bulk_operations o
+     LEFT JOIN bulk_operation_items i ON i.operation_id = o.id";
+
+fn operation_from_row(row: &rusqlite::Row) -> rusqlite::Result<BulkOperation> {
+    Ok(BulkOperation {
+        id: row.get(0)?,
+        kind: row.get(1)?,
+        status: row.get(2)?,
+        params_json: row.get(3)?,
+        total: row.get(4)?,
+        pending: row.get(5)?,
+        succeeded: row.get(6)?,
+        failed: row.get(7)?,
+        skipped: row.get(8)?,
+        created_at: row.get(9)?,
+        updated_at: row.get(10)?,
+    })
+}
+
+/// Product queued in a new operation, with the reason when it is skipped up front
+pub struct NewItem {
+    pub product_id: i64,
+    pub product_name: String,
+    pub skip_reason: Option<String>,
+}
+
+/// Creates an operation with its items in one transaction and returns its id
+pub fn create_operation(
+    conn: &mut Connection,
+    kind: &str,
+    params_json: &str,
+    items: &[NewItem],
+) -> rusqlite::Result<i64> {
+    let tx = conn.transaction()?;
+
+    tx.execute(
+        "INSERT INTO bulk_operations (kind, params_json) VALUES (?1, ?2)",
+        params![kind, params_json],
+    )?;
+    let operation_id = tx.last_insert_rowid();
+
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO bulk_operation_items (operation_id, product_id, product_name, status, message)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        for item in items {
+            let status = if item.skip_reason.is_some() { "skipped" } else { "pending" };
+            stmt.execute(params![
+                operation_id,
+                item.product_id,
+                item.product_name,
+                status,
+                item.skip_reason
+            ])?;
+        }
+    }
+
+    tx.commit()?;
+    Ok(operation_id)
+}
+
+pub fn get_operation(conn: &Connection, operation_id: i64) -> rusqlite::Result<Option<BulkOperation>> {
+    conn.query_row(
+        &format!("{} WHERE o.id = ?1 GROUP BY o.id", OPERATION_SELECT),
+        params![operation_id],
+        operation_from_row,
+    )
+    .optional()
+}
+
+pub fn list_operations(conn: &Connection, limit: i64) -> rusqlite::Result<Vec<BulkOperation>> {
+    let mut stmt = conn.prepare(&format!(
+        "{} GROUP BY o.id ORDER BY o.created_at DESC, o.id DESC LIMIT ?1",
+        OPERATION_SELECT
+    ))?;
+    let operations = stmt
+        .query_map(params![limit], operation_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(operations)
+}
+
+pub fn list_items(conn: &Connection, operation_id: i64, status: Option<&str>) -> rusqlite::Result<Vec<BulkOperationItem>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, operation_id, product_id, product_name, status, message, result_id
+         FROM bulk_operation_items
+         WHERE operation_id = ?1 AND (?2 IS NULL OR status = ?2)
+         ORDER BY id",
+    )?;
+    let items = stmt
+        .query_map(params![operation_id, status], |row| {
+            Ok(BulkOperationItem {
+                id: row.get(0)?,
+                operation_id: row.get(1)?,
+                product_id: row.get(2)?,
+                product_name: row.get(3)?,
+                status: row.get(4)?,
+                message: row.get(5)?,
+                result_id: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(items)
+}
+
+/// Records the outcome of one item
+pub fn finish_item(conn: &Connection, item_id: i64, result: Result<i64, &str>) -> rusqlite::Result<()> {
+    let (status, message, result_id) = match result {
+        Ok(id) => ("succeeded", None, Some(id)),
+        Err(e) => ("failed", Some(e), None),
+    };
+    conn.execute(
+        "UPDATE bulk_operation_items
+         SET status = ?1, message = ?2, result_id = ?3, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?4",
+        params![status, message, result_id, item_id],
+    )?;
+    Ok(())
+}
+
+pub fn set_status(conn: &Connection, operation_id: i64, status: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE bulk_operations SET status = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        params![status, operation_id],
+    )?;
+    Ok(())
+}
+
+pub fn current_status(conn: &Connection, operation_id: i64) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT status FROM bulk_operations WHERE id = ?1",
+        params![operation_id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Marks runs left `running` by a previous session as resumable
+pub fn mark_interrupted(conn: &Connection) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE bulk_operations SET status = 'interrupted', updated_at = CURRENT_TIMESTAMP
+         WHERE status = 'running'",
+        [],
+    )
+}
+
+/// Whether an operation in `status` may be resumed
+pub fn is_resumable(status: &str) -> bool {
+    matches!(status, "cancelled" | "interrupted")
+}
+
+/// Builds the progress event payload from the operation's current counts
+pub fn progress(operation: &BulkOperation, product_id: Option<i64>) -> BulkProgress {
+    BulkProgress {
+        operation_id: operation.id,
+        kind: operation.kind.clone(),
+        status: operation.status.clone(),
+        product_id,
+        processed: operation.total - operation.pending,
+        total: operation.total,
+        succeeded: operation.succeeded,
+        failed: operation.failed,
+        skipped: operation.skipped,
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resumable_statuses() {
+        assert!(is_resumable("cancelled"));
+        assert!(is_resumable("interrupted"));
+        assert!(!is_resumable("running"));
+        assert!(!is_resumable("completed"));
+    }
+
+    #[test]
+    fn test_parallelism_is_bounded() {
+        assert_eq!(clamp_parallelism(0), 1);
+        assert_eq!(clamp_parallelism(4), 4);
+        assert_eq!(clamp_parallelism(500), MAX_PARALLELISM);
+    }
+
+    #[test]
+    fn test_progress_counts_processed_items() {
+        let operation = BulkOperation {
+            id: 7,
+            kind: "links".to_string(),
+            status: "running".to_string(),
+            params_json: "{}".to_string(),
+            total: 10,
+            pending: 4,
+            succeeded: 3,
+            failed: 1,
+            skipped: 2,
+            created_at: None,
+            updated_at: None,
+        };
+        let p = progress(&operation, Some(42));
+        assert_eq!(p.processed, 6);
+        assert_eq!(p.total, 10);
+        assert_eq!(p.product_id, Some(42));
+    }
+}
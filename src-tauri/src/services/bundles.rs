@@ -0,0 +1,361 @@
+//! Product Bundles
+//!
+//! A bundle groups products that are promoted together ("Home Gym Starter
+//! Kit"). Bundle ads are built from the members rather than generated per
+//! product: a carousel with one card per member, or an email listing each
+//! member with its own tracking link. They are stored as ordinary ad copies
+//! with `bundle_id` set and no product. Performance is rolled up from the
+//! members' daily metrics.
+
+use crate::commands::ad_generation::{ad_copy_from_row, GeneratedAdCopy, AD_COPY_COLUMNS};
+use crate::models::bundle::{Bundle, BundleMember, BundlePerformance, SaveBundleInput};
+use crate::models::daily_metrics::ProductMetrics;
+use crate::services::{ad_links, daily_metrics};
+use chrono::{Duration, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::json;
+
+pub const MIN_PRODUCTS: usize = 2;
+pub const MAX_PRODUCTS: usize = 10;
+
+/// A member's link as used in a bundle ad
+#[derive(Debug, Clone)]
+pub struct MemberLink {
+    pub member: BundleMember,
+    pub link_id: Option<i64>,
+    pub tracking_url: Option<String>,
+}
+
+/// Headline, body, CTA, and platform data of a bundle ad
+#[derive(Debug, Clone)]
+pub struct BundleAd {
+    pub headline: String,
+    pub body_text: String,
+    pub cta: String,
+    pub platform_data: serde_json::Value,
+}
+
+// =============================================================================
+// BUNDLES
+// =============================================================================
+
+fn members(conn: &Connection, bundle_id: i64) -> rusqlite::Result<Vec<BundleMember>> {
+    let mut stmt = conn.prepare(
+        "SELECT bp.product_id, p.name, p.price_range, p.image_url, bp.position
+         FROM bundle_products bp JOIN products p ON p.id = bp.product_id
+         WHERE bp.bundle_id = ?1 ORDER BY bp.position, p.name",
+    )?;
+    let members = stmt
+        .query_map(params![bundle_id], |row| {
+            Ok(BundleMember {
+                product_id: row.get(0)?,
+                product_name: row.get(1)?,
+                price_range: row.get(2)?,
+                image_url: row.get(3)?,
+                position: row.get(4)?,
+            })
+        })?
+        .collect();
+    members
+}
+
+pub fn get(conn: &Connection, bundle_id: i64) -> Result<Bundle, String> {
+    let bundle = conn
+        .query_row(
+            "SELECT id, name, description, created_at, updated_at FROM bundles WHERE id = ?1",
+            params![bundle_id],
+            |row| {
+                Ok(Bundle {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    description: row.get(2)?,
+                    members: Vec::new(),
+                    created_at: row.get(3)?,
+                    updated_at: row.get(4)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Bundle {} not found", bundle_id))?;
+    let members = members(conn, bundle_id).map_err(|e| e.to_string())?;
+    Ok(Bundle { members, ..bundle })
+}
+
+pub fn list(conn: &Connection) -> Result<Vec<Bundle>, String> {
+    let ids: Vec<i64> = conn
+        .prepare("SELECT id FROM bundles ORDER BY name")
+        .and_then(|mut stmt| stmt.query_map([], |row| row.get(0))?.collect())
+        .map_err(|e| e.to_string())?;
+    ids.into_iter().map(|id| get(conn, id)).collect()
+}
+
+/// Creates or replaces a bundle; members keep the order of `product_ids`
+pub fn save(conn: &mut Connection, input: &SaveBundleInput) -> Result<Bundle, String> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err("Bundle name is required".to_string());
+    }
+    let mut product_ids: Vec<i64> = Vec::new();
+    for id in &input.product_ids {
+        if !product_ids.contains(id) {
+            product_ids.push(*id);
+        }
+    }
+    if product_ids.len() < MIN_PRODUCTS {
+        return Err(format!("A bundle needs at least {} products", MIN_PRODUCTS));
+    }
+    if product_ids.len() > MAX_PRODUCTS {
+        return Err(format!("A bundle can hold at most {} products", MAX_PRODUCTS));
+    }
+    let description = input.description.as_deref().map(str::trim).filter(|d| !d.is_empty());
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    for id in &product_ids {
+        let exists: bool = tx
+            .query_row("SELECT EXISTS(SELECT 1 FROM products WHERE id = ?1)", params![id], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+        if !exists {
+            return Err(format!("Product {} not found", id));
+        }
+    }
+    let bundle_id = match input.id {
+        Some(id) => {
+            let updated = tx
+                .execute(
+                    "UPDATE bundles SET name = ?2, description = ?3, updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+                    params![id, name, description],
+                )
+                .map_err(|e| e.to_string())?;
+            if updated == 0 {
+                return Err(format!("Bundle {} not found", id));
+            }
+            tx.execute("DELETE FROM bundle_products WHERE bundle_id = ?1", params![id])
+                .map_err(|e| e.to_string())?;
+            id
+        }
+        None => {
+            tx.execute("INSERT INTO bundles (name, description) VALUES (?1, ?2)", params![name, description])
+                .map_err(|e| e.to_string())?;
+            tx.last_insert_rowid()
+        }
+    };
+    for (position, product_id) in product_ids.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO bundle_products (bundle_id, product_id, position) VALUES (?1, ?2, ?3)",
+            params![bundle_id, product_id, position as i64],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    get(conn, bundle_id)
+}
+
+/// Deletes a bundle; its ads are kept and lose the bundle reference
+pub fn delete(conn: &Connection, bundle_id: i64) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM bundle_products WHERE bundle_id = ?1", params![bundle_id])?;
+    conn.execute("UPDATE ad_copies SET bundle_id = NULL WHERE bundle_id = ?1", params![bundle_id])?;
+    conn.execute("DELETE FROM bundles WHERE id = ?1", params![bundle_id])?;
+    Ok(())
+}
+
+// =============================================================================
+// BUNDLE ADS
+// =============================================================================
+
+/// Each member's link for an ad targeting `platform`
+pub fn member_links(conn: &Connection, bundle: &Bundle, platform: &str) -> rusqlite::Result<Vec<MemberLink>> {
+    bundle
+        .members
+        .iter()
+        .map(|member| {
+            let link_id = ad_links::select_link(conn, member.product_id, platform)?;
+            let tracking_url = match link_id {
+                Some(_) => ad_links::tracking_url(conn, Some(member.product_id), link_id)?,
+                None => None,
+            };
+            Ok(MemberLink { member: member.clone(), link_id, tracking_url })
+        })
+        .collect()
+}
+
+/// Builds a carousel (one card per member) or an email listing every member.
+/// Members without an active link are still listed, without a URL.
+pub fn build_ad(bundle: &Bundle, links: &[MemberLink], ad_type: &str) -> Result<BundleAd, String> {
+    let cards: Vec<serde_json::Value> = links
+        .iter()
+        .map(|l| {
+            json!({
+                "productId": l.member.product_id,
+                "productName": l.member.product_name,
+                "priceRange": l.member.price_range,
+                "imageUrl": l.member.image_url,
+                "linkId": l.link_id,
+                "trackingUrl": l.tracking_url,
+            })
+        })
+        .collect();
+    let missing_links: Vec<&str> =
+        links.iter().filter(|l| l.tracking_url.is_none()).map(|l| l.member.product_name.as_str()).collect();
+    let platform_data = json!({ "bundleId": bundle.id, "cards": cards, "missingLinks": missing_links });
+
+    let line = |l: &MemberLink| {
+        let price = l.member.price_range.as_deref().map(|p| format!(" ({})", p)).unwrap_or_default();
+        match &l.tracking_url {
+            Some(url) => format!("{}{}: {}", l.member.product_name, price, url),
+            None => format!("{}{}", l.member.product_name, price),
+        }
+    };
+    let intro = bundle
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("Everything in the {}, picked to work together.", bundle.name));
+
+    match ad_type {
+        "carousel" => {
+            let cards = links.iter().enumerate().map(|(i, l)| format!("{}. {}", i + 1, line(l)));
+            Ok(BundleAd {
+                headline: bundle.name.clone(),
+                body_text: std::iter::once(intro).chain(cards).collect::<Vec<_>>().join("\n"),
+                cta: "Swipe through the set".to_string(),
+                platform_data,
+            })
+        }
+        "email" => {
+            let items = links.iter().map(|l| format!("- {}", line(l))).collect::<Vec<_>>().join("\n");
+            Ok(BundleAd {
+                headline: format!("Build your {}", bundle.name),
+                body_text: format!(
+                    "{}\n\nWhat's inside:\n{}\n\nGrab them together or one at a time.",
+                    intro, items
+                ),
+                cta: "Shop the bundle".to_string(),
+                platform_data,
+            })
+        }
+        other => Err(format!("Unsupported bundle ad type '{}' (use carousel or email)", other)),
+    }
+}
+
+/// Builds and stores a bundle ad. It is attached to the first member's link
+/// so exports resolve a link; every member's link is kept in the platform data.
+pub fn generate_ad(conn: &Connection, bundle_id: i64, ad_type: &str) -> Result<GeneratedAdCopy, String> {
+    let bundle = get(conn, bundle_id)?;
+    let platform = if ad_type == "email" { "email" } else { "instagram" };
+    let links = member_links(conn, &bundle, platform).map_err(|e| e.to_string())?;
+    let ad = build_ad(&bundle, &links, ad_type)?;
+    let link_id = links.iter().find_map(|l| l.link_id);
+    conn.execute(
+        "INSERT INTO ad_copies (campaign_id, product_id, bundle_id, variation_name, headline, body_text,
+         cta, ad_format, ad_type, platform_specific_data, link_id)
+         VALUES (1, NULL, ?1, ?2, ?3, ?4, ?5, ?6, ?6, ?7, ?8)",
+        params![
+            bundle_id,
+            format!("{} ({})", bundle.name, ad_type),
+            ad.headline,
+            ad.body_text,
+            ad.cta,
+            ad_type,
+            ad.platform_data.to_string(),
+            link_id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    let id = conn.last_insert_rowid();
+    let sql = format!("SELECT {} FROM ad_copies WHERE id = ?1", AD_COPY_COLUMNS);
+    conn.query_row(&sql, params![id], ad_copy_from_row).map_err(|e| e.to_string())
+}
+
+pub fn ads(conn: &Connection, bundle_id: i64) -> rusqlite::Result<Vec<GeneratedAdCopy>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM ad_copies WHERE bundle_id = ?1 ORDER BY created_at DESC, id DESC",
+        AD_COPY_COLUMNS
+    ))?;
+    let ads = stmt.query_map(params![bundle_id], ad_copy_from_row)?.collect();
+    ads
+}
+
+// =============================================================================
+// PERFORMANCE
+// =============================================================================
+
+/// Members' clicks and conversions over the `days` ending `today`
+pub fn performance(
+    conn: &Connection,
+    bundle_id: i64,
+    days: i64,
+    today: NaiveDate,
+) -> Result<BundlePerformance, String> {
+    let bundle = get(conn, bundle_id)?;
+    let start = today - Duration::days(days.max(1) - 1);
+    let mut performance = BundlePerformance {
+        bundle_id,
+        start: start.format("%Y-%m-%d").to_string(),
+        end: today.format("%Y-%m-%d").to_string(),
+        ..Default::default()
+    };
+    for member in bundle.members {
+        let series = daily_metrics::series(conn, start, today, Some(member.product_id), None)
+            .map_err(|e| e.to_string())?;
+        let mut metrics = ProductMetrics {
+            product_id: member.product_id,
+            product_name: member.product_name,
+            clicks: 0,
+            commission: 0.0,
+        };
+        for day in series {
+            metrics.clicks += day.clicks;
+            metrics.commission += day.commission;
+            performance.conversions += day.conversions;
+            performance.revenue += day.revenue;
+        }
+        performance.clicks += metrics.clicks;
+        performance.commission += metrics.commission;
+        performance.members.push(metrics);
+    }
+    Ok(performance)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_ads_link_every_member() {
+        let member = |id: i64, name: &str, url: Option<&str>| MemberLink {
+            member: BundleMember {
+                product_id: id,
+                product_name: name.to_string(),
+                price_range: Some("$20".to_string()),
+                image_url: None,
+                position: id,
+            },
+            link_id: url.map(|_| id * 10),
+            tracking_url: url.map(String::from),
+        };
+        let links = vec![member(1, "Kettlebell", Some("https://go.example/k")), member(2, "Mat", None)];
+        let bundle = Bundle {
+            id: 7,
+            name: "Home Gym Starter Kit".to_string(),
+            description: None,
+            members: links.iter().map(|l| l.member.clone()).collect(),
+            created_at: None,
+            updated_at: None,
+        };
+
+        let carousel = build_ad(&bundle, &links, "carousel").unwrap();
+        assert_eq!(carousel.headline, "Home Gym Starter Kit");
+        assert!(carousel.body_text.contains("1. Kettlebell ($20): https://go.example/k"));
+        assert!(carousel.body_text.contains("2. Mat ($20)"));
+        assert_eq!(carousel.platform_data["cards"].as_array().unwrap().len(), 2);
+        assert_eq!(carousel.platform_data["missingLinks"][0], "Mat");
+
+        let email = build_ad(&bundle, &links, "email").unwrap();
+        assert!(email.body_text.contains("- Kettlebell ($20): https://go.example/k"));
+        assert!(build_ad(&bundle, &links, "tiktok").is_err());
+    }
+}
@@ -0,0 +1,410 @@
+//! Product Catalog Feeds
+//!
+//! Writes selected products as a shopping catalog feed so the catalog can be
+//! reused outside the app: a Meta Commerce catalog (Instagram and Facebook
+//! Shopping) or a Google Merchant Center feed (Shopping ads and Performance
+//! Max). Both come as CSV or as RSS 2.0 XML with the `g:` attribute
+//! namespace. Each item links to the product's newest active affiliate link.
+//!
+//! Products are validated against the attributes the target requires before
+//! anything is written. A catalog needs an exact price rather than a range,
+//! so prices come from the watchlist or a store sync; products missing a
+//! required attribute are skipped with every problem listed.
+
+use crate::models::catalog_feed::FeedSkip;
+use crate::services::link_hub::escape_html;
+use crate::services::scraper::parse_price;
+use rusqlite::{params_from_iter, Connection};
+
+pub const DEFAULT_CURRENCY: &str = "USD";
+
+const META_HEADERS: [&str; 10] =
+    ["id", "title", "description", "availability", "condition", "price", "link", "image_link", "brand", "product_type"];
+const GOOGLE_HEADERS: [&str; 11] = [
+    "id",
+    "title",
+    "description",
+    "link",
+    "image_link",
+    "availability",
+    "price",
+    "condition",
+    "brand",
+    "identifier_exists",
+    "product_type",
+];
+
+/// File layouts supported by `render`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CatalogFeedFormat {
+    Csv,
+    Xml,
+}
+
+impl CatalogFeedFormat {
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Some(CatalogFeedFormat::Csv),
+            "xml" | "rss" => Some(CatalogFeedFormat::Xml),
+            _ => None,
+        }
+    }
+}
+
+/// Where the feed will be uploaded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FeedTarget {
+    Meta,
+    Google,
+}
+
+impl FeedTarget {
+    pub fn file_name(&self, format: CatalogFeedFormat) -> &'static str {
+        match (self, format) {
+            (FeedTarget::Meta, CatalogFeedFormat::Csv) => "meta-catalog.csv",
+            (FeedTarget::Meta, CatalogFeedFormat::Xml) => "meta-catalog.xml",
+            (FeedTarget::Google, CatalogFeedFormat::Csv) => "google-merchant-feed.csv",
+            (FeedTarget::Google, CatalogFeedFormat::Xml) => "google-merchant-feed.xml",
+        }
+    }
+
+    /// Longest title and description the target accepts
+    fn limits(&self) -> (usize, usize) {
+        match self {
+            FeedTarget::Meta => (200, 9999),
+            FeedTarget::Google => (150, 5000),
+        }
+    }
+}
+
+/// A product as a feed lists it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedItem {
+    pub product_id: i64,
+    pub title: String,
+    pub description: String,
+    pub in_stock: bool,
+    pub price: f64,
+    pub link: String,
+    pub image_link: String,
+    pub product_type: String,
+}
+
+/// A product row before its required attributes are checked
+#[derive(Debug, Clone, Default)]
+pub struct ProductRow {
+    pub id: i64,
+    pub name: String,
+    pub category: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub availability: Option<String>,
+    pub price: Option<f64>,
+    pub price_range: Option<String>,
+    pub link: Option<String>,
+}
+
+fn truncate(text: &str, limit: usize) -> String {
+    text.chars().take(limit).collect()
+}
+
+fn is_web_url(url: &str) -> bool {
+    let lower = url.trim().to_lowercase();
+    ["http://", "https://"].iter().any(|scheme| lower.len() > scheme.len() && lower.starts_with(scheme))
+        && !url.trim().contains(char::is_whitespace)
+}
+
+/// An exact price for the product: the latest watched or store-synced price,
+/// else a `price_range` that names one price rather than a range
+fn exact_price(price: Option<f64>, price_range: Option<&str>) -> Option<f64> {
+    price
+        .or_else(|| price_range.filter(|range| !range.contains('-')).and_then(parse_price))
+        .filter(|price| *price > 0.0)
+}
+
+/// Checks a product against the attributes `target` requires, returning the
+/// feed item or every problem found
+pub fn validate(target: FeedTarget, row: ProductRow) -> Result<FeedItem, Vec<String>> {
+    let mut problems = Vec::new();
+    let title = row.name.trim();
+    if title.is_empty() {
+        problems.push("title is empty".to_string());
+    }
+    let link = row.link.map(|url| url.trim().to_string());
+    match &link {
+        None => problems.push("link: no active affiliate link".to_string()),
+        Some(url) if !is_web_url(url) => problems.push(format!("link is not a web URL: {}", url)),
+        Some(_) => {}
+    }
+    let image_link = row.image_url.map(|url| url.trim().to_string()).filter(|url| !url.is_empty());
+    match &image_link {
+        None => problems.push("image_link: no product image".to_string()),
+        Some(url) if !is_web_url(url) => problems.push(format!("image_link is not a web URL: {}", url)),
+        Some(_) => {}
+    }
+    let price = exact_price(row.price, row.price_range.as_deref());
+    if price.is_none() {
+        problems.push("price: no exact price; watch the product or sync it from a store".to_string());
+    }
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+
+    let (title_limit, description_limit) = target.limits();
+    let description = row.description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+    Ok(FeedItem {
+        product_id: row.id,
+        title: truncate(title, title_limit),
+        description: truncate(description.as_deref().unwrap_or(title), description_limit),
+        // Unchecked products are listed as in stock, like the storefront shows them
+        in_stock: row.availability.as_deref() != Some("out_of_stock"),
+        price: price.unwrap_or_default(),
+        link: link.unwrap_or_default(),
+        image_link: image_link.unwrap_or_default(),
+        product_type: row.category,
+    })
+}
+
+/// The selected products as feed items for `target`, plus the ones left out and why
+pub fn load_items(
+    conn: &Connection,
+    target: FeedTarget,
+    product_ids: &[i64],
+) -> rusqlite::Result<(Vec<FeedItem>, Vec<FeedSkip>)> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT p.id, p.name, p.category, p.description, p.image_url, p.availability,
+            COALESCE(
+                (SELECT w.last_price FROM product_watches w WHERE w.product_id = p.id),
+                (SELECT s.last_price FROM store_products s WHERE s.product_id = p.id AND s.last_price IS NOT NULL
+                 ORDER BY s.last_synced_at DESC LIMIT 1)),
+            p.price_range,
+            (SELECT l.tracking_url FROM affiliate_links l WHERE l.product_id = p.id AND l.status = 'active'
+             ORDER BY l.created_at DESC, l.id DESC LIMIT 1)
+         FROM products p WHERE p.id IN ({})
+         ORDER BY p.name COLLATE NOCASE, p.id",
+        vec!["?"; product_ids.len().max(1)].join(", ")
+    ))?;
+    let rows = stmt
+        .query_map(params_from_iter(product_ids.iter()), |row| {
+            Ok(ProductRow {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                category: row.get(2)?,
+                description: row.get(3)?,
+                image_url: row.get(4)?,
+                availability: row.get(5)?,
+                price: row.get(6)?,
+                price_range: row.get(7)?,
+                link: row.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut items = Vec::new();
+    let mut skipped = Vec::new();
+    for row in rows {
+        let (product_id, name) = (row.id, row.name.clone());
+        match validate(target, row) {
+            Ok(item) => items.push(item),
+            Err(problems) => skipped.push(FeedSkip { product_id, name, reason: problems.join("; ") }),
+        }
+    }
+
+    // Products that no longer exist are reported too
+    for id in product_ids {
+        if !items.iter().any(|i| i.product_id == *id) && !skipped.iter().any(|s| s.product_id == *id) {
+            skipped.push(FeedSkip { product_id: *id, name: String::new(), reason: "product not found".to_string() });
+        }
+    }
+    Ok((items, skipped))
+}
+
+/// Both targets' price format, e.g. "24.99 USD"
+fn format_price(price: f64, currency: &str) -> String {
+    format!("{:.2} {}", price, currency)
+}
+
+/// The target's attribute names and an item's values in the same order
+fn attributes(target: FeedTarget, item: &FeedItem, brand: &str, currency: &str) -> Vec<(&'static str, String)> {
+    match target {
+        FeedTarget::Meta => META_HEADERS
+            .iter()
+            .zip([
+                item.product_id.to_string(),
+                item.title.clone(),
+                item.description.clone(),
+                if item.in_stock { "in stock" } else { "out of stock" }.to_string(),
+                "new".to_string(),
+                format_price(item.price, currency),
+                item.link.clone(),
+                item.image_link.clone(),
+                brand.to_string(),
+                item.product_type.clone(),
+            ])
+            .map(|(name, value)| (*name, value))
+            .collect(),
+        // Affiliate products carry no GTIN or MPN, so identifier_exists is "no"
+        FeedTarget::Google => GOOGLE_HEADERS
+            .iter()
+            .zip([
+                item.product_id.to_string(),
+                item.title.clone(),
+                item.description.clone(),
+                item.link.clone(),
+                item.image_link.clone(),
+                if item.in_stock { "in_stock" } else { "out_of_stock" }.to_string(),
+                format_price(item.price, currency),
+                "new".to_string(),
+                brand.to_string(),
+                "no".to_string(),
+                item.product_type.clone(),
+            ])
+            .map(|(name, value)| (*name, value))
+            .collect(),
+    }
+}
+
+/// Renders the feed file
+pub fn render(
+    target: FeedTarget,
+    format: CatalogFeedFormat,
+    items: &[FeedItem],
+    brand: &str,
+    currency: &str,
+) -> Result<String, String> {
+    match format {
+        CatalogFeedFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            let headers = match target {
+                FeedTarget::Meta => &META_HEADERS[..],
+                FeedTarget::Google => &GOOGLE_HEADERS[..],
+            };
+            writer.write_record(headers).map_err(|e| e.to_string())?;
+            for item in items {
+                let values = attributes(target, item, brand, currency).into_iter().map(|(_, value)| value);
+                writer.write_record(values).map_err(|e| e.to_string())?;
+            }
+            String::from_utf8(writer.into_inner().map_err(|e| e.to_string())?).map_err(|e| e.to_string())
+        }
+        CatalogFeedFormat::Xml => {
+            let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+            xml.push_str("<rss xmlns:g=\"http://base.google.com/ns/1.0\" version=\"2.0\">\n<channel>\n");
+            // The channel is named after the brand and links to the first item
+            xml.push_str(&format!("<title>{}</title>\n", escape_html(brand)));
+            xml.push_str(&format!("<link>{}</link>\n", escape_html(items.first().map_or("", |i| i.link.as_str()))));
+            xml.push_str(&format!("<description>{} product catalog</description>\n", escape_html(brand)));
+            for item in items {
+                xml.push_str("<item>\n");
+                for (tag, value) in attributes(target, item, brand, currency) {
+                    xml.push_str(&format!("  <g:{tag}>{}</g:{tag}>\n", escape_html(&value), tag = tag));
+                }
+                xml.push_str("</item>\n");
+            }
+            xml.push_str("</channel>\n</rss>\n");
+            Ok(xml)
+        }
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_products_without_feed_fields_are_skipped() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT NOT NULL, category TEXT NOT NULL,
+                description TEXT, image_url TEXT, availability TEXT, price_range TEXT);
+             CREATE TABLE product_watches (product_id INTEGER PRIMARY KEY, last_price REAL);
+             CREATE TABLE store_products (id INTEGER PRIMARY KEY, product_id INTEGER, last_price REAL,
+                last_synced_at DATETIME);
+             CREATE TABLE affiliate_links (id INTEGER PRIMARY KEY, product_id INTEGER, tracking_url TEXT,
+                status TEXT, created_at DATETIME);
+             INSERT INTO products VALUES
+                (1, 'Lip Oil', 'Beauty', NULL, 'https://img.example.com/lip.jpg', 'out_of_stock', '$10-$20'),
+                (2, 'Brow Gel', 'Beauty', 'Tinted gel', 'https://img.example.com/brow.jpg', 'unknown', '$9.99'),
+                (3, 'Serum', 'Beauty', NULL, 'https://img.example.com/serum.jpg', 'in_stock', '$20-$30'),
+                (4, 'Mug', 'Home', NULL, NULL, 'in_stock', '$12');
+             INSERT INTO store_products VALUES (1, 1, 14.5, '2026-10-01');
+             INSERT INTO affiliate_links VALUES
+                (1, 1, 'https://go.example.com/lip', 'active', '2026-10-01'),
+                (2, 2, 'https://go.example.com/brow', 'active', '2026-10-01'),
+                (3, 3, 'https://go.example.com/serum', 'active', '2026-10-01'),
+                (4, 4, 'https://go.example.com/mug', 'active', '2026-10-01');",
+        )
+        .unwrap();
+
+        let (items, skipped) = load_items(&conn, FeedTarget::Meta, &[1, 2, 3, 4, 9]).unwrap();
+        assert_eq!(items.iter().map(|i| i.product_id).collect::<Vec<_>>(), [2, 1]);
+        assert_eq!((items[1].price, items[1].in_stock), (14.5, false));
+        assert_eq!((items[0].price, items[0].description.as_str()), (9.99, "Tinted gel"));
+        let reasons: Vec<(i64, &str)> = skipped.iter().map(|s| (s.product_id, s.reason.as_str())).collect();
+        assert_eq!(reasons[0], (4, "image_link: no product image"));
+        assert!(reasons[1].0 == 3 && reasons[1].1.starts_with("price: no exact price"));
+        assert_eq!(reasons[2], (9, "product not found"));
+    }
+
+    #[test]
+    fn test_render_meta_feed() {
+        let item = FeedItem {
+            product_id: 7,
+            title: "Salt & Pepper Mill".to_string(),
+            description: "Set of 2".to_string(),
+            in_stock: true,
+            price: 24.0,
+            link: "https://go.example.com/mill?a=1&b=2".to_string(),
+            image_link: "https://img.example.com/mill.jpg".to_string(),
+            product_type: "Kitchen".to_string(),
+        };
+
+        let csv = render(FeedTarget::Meta, CatalogFeedFormat::Csv, std::slice::from_ref(&item), "Acme", "USD").unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(META_HEADERS.join(",").as_str()));
+        assert_eq!(
+            lines.next(),
+            Some(
+                "7,Salt & Pepper Mill,Set of 2,in stock,new,24.00 USD,https://go.example.com/mill?a=1&b=2,\
+                 https://img.example.com/mill.jpg,Acme,Kitchen"
+            )
+        );
+
+        let xml = render(FeedTarget::Meta, CatalogFeedFormat::Xml, std::slice::from_ref(&item), "Acme", "EUR").unwrap();
+        assert!(xml.contains("<g:title>Salt &amp; Pepper Mill</g:title>"));
+        assert!(xml.contains("<g:price>24.00 EUR</g:price>"));
+        assert!(xml.contains("<link>https://go.example.com/mill?a=1&amp;b=2</link>"));
+
+        let google = render(FeedTarget::Google, CatalogFeedFormat::Xml, &[item], "Acme", "USD").unwrap();
+        assert!(google.contains("<g:availability>in_stock</g:availability>"));
+        assert!(google.contains("<g:identifier_exists>no</g:identifier_exists>"));
+    }
+
+    #[test]
+    fn test_google_validation_lists_every_missing_attribute() {
+        let row = ProductRow {
+            id: 5,
+            name: "A".repeat(180),
+            category: "Home".to_string(),
+            image_url: Some("img/local.png".to_string()),
+            price_range: Some("$20-$30".to_string()),
+            ..Default::default()
+        };
+        let problems = validate(FeedTarget::Google, row.clone()).unwrap_err();
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].starts_with("link:") && problems[1].starts_with("image_link is not a web URL"));
+
+        let valid = ProductRow {
+            link: Some("https://go.example.com/a".to_string()),
+            image_url: Some("https://img.example.com/a.jpg".to_string()),
+            price: Some(25.0),
+            ..row
+        };
+        let item = validate(FeedTarget::Google, valid.clone()).unwrap();
+        assert_eq!((item.title.chars().count(), item.description.chars().count()), (150, 180));
+        assert_eq!(validate(FeedTarget::Meta, valid).unwrap().title.chars().count(), 180);
+    }
+}
@@ -0,0 +1,241 @@
+//! Category Keywords
+//!
+//! Keyword affinities behind the category factor of ad type scoring and the
+//! category fit of platform discovery. A category matches a keyword when it
+//! contains it, ignoring case; the highest matching score wins, and a
+//! target's empty-keyword row scores any category nothing else matches.
+//! Shipped rows (migration 050) can be edited and reset; users add keywords
+//! for their own categories (e.g. "guitar" for video scripts).
+//!
+//! Scoring reads a loaded `CategoryAffinities` rather than the database, so
+//! recommendations stay pure functions of their inputs.
+
+use crate::models::category_keyword::{CategoryKeyword, SaveCategoryKeywordInput};
+use crate::services::ai_affiliate::DISCOVERY_PLATFORMS;
+use crate::services::analytics_service::AdType;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+pub const KIND_AD_TYPE: &str = "ad_type";
+pub const KIND_PLATFORM: &str = "platform";
+
+/// Score used when a target has no matching keyword and no fallback row
+const NEUTRAL_SCORE: f64 = 0.5;
+
+#[cfg(test)]
+const SHIPPED_SQL: &str = include_str!("../../../migrations/050_category_keywords.sql");
+
+const KEYWORD_COLUMNS: &str = "id, kind, target, keyword, score,
+     default_score IS NOT NULL, default_score IS NOT NULL AND score != default_score";
+
+fn keyword_from_row(row: &Row) -> rusqlite::Result<CategoryKeyword> {
+    Ok(CategoryKeyword {
+        id: row.get(0)?,
+        kind: row.get(1)?,
+        target: row.get(2)?,
+        keyword: row.get(3)?,
+        score: row.get(4)?,
+        builtin: row.get(5)?,
+        edited: row.get(6)?,
+    })
+}
+
+/// Keyword affinities loaded for scoring
+#[derive(Debug, Clone, Default)]
+pub struct CategoryAffinities {
+    keywords: Vec<CategoryKeyword>,
+}
+
+impl CategoryAffinities {
+    pub fn load(conn: &Connection) -> rusqlite::Result<Self> {
+        Ok(CategoryAffinities { keywords: list(conn)? })
+    }
+
+    /// The shipped keywords, without a database
+    #[cfg(test)]
+    pub fn defaults() -> Self {
+        Connection::open_in_memory()
+            .and_then(|conn| {
+                conn.execute_batch(SHIPPED_SQL)?;
+                Self::load(&conn)
+            })
+            .unwrap_or_default()
+    }
+
+    /// How well a category suits an ad type ("video_script", ...)
+    pub fn ad_type_score(&self, ad_type: &str, category: &str) -> f64 {
+        self.score(KIND_AD_TYPE, ad_type, category)
+    }
+
+    /// How well a category suits a discovery platform ("tiktok", ...)
+    pub fn platform_score(&self, platform: &str, category: &str) -> f64 {
+        self.score(KIND_PLATFORM, platform, category)
+    }
+
+    fn score(&self, kind: &str, target: &str, category: &str) -> f64 {
+        let category = category.to_lowercase();
+        let rows = self.keywords.iter().filter(|k| k.kind == kind && k.target == target);
+        let mut matched: Option<f64> = None;
+        let mut fallback: Option<f64> = None;
+        for row in rows {
+            if row.keyword.is_empty() {
+                fallback = Some(row.score);
+            } else if category.contains(&row.keyword.to_lowercase()) {
+                matched = Some(matched.map_or(row.score, |best| best.max(row.score)));
+            }
+        }
+        matched.or(fallback).unwrap_or(NEUTRAL_SCORE)
+    }
+}
+
+pub fn list(conn: &Connection) -> rusqlite::Result<Vec<CategoryKeyword>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM category_keywords ORDER BY kind, target, keyword = '', score DESC, keyword",
+        KEYWORD_COLUMNS
+    ))?;
+    let keywords = stmt.query_map([], keyword_from_row)?.collect();
+    keywords
+}
+
+pub fn get(conn: &Connection, id: i64) -> Result<CategoryKeyword, String> {
+    let sql = format!("SELECT {} FROM category_keywords WHERE id = ?1", KEYWORD_COLUMNS);
+    conn.query_row(&sql, params![id], keyword_from_row)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Category keyword {} not found", id))
+}
+
+/// Validates and saves a keyword
+pub fn save(conn: &Connection, input: &SaveCategoryKeywordInput) -> Result<CategoryKeyword, String> {
+    let kind = input.kind.trim().to_lowercase();
+    let target = input.target.trim().to_lowercase();
+    let known = match kind.as_str() {
+        KIND_AD_TYPE => AdType::all().iter().any(|t| t.key() == target),
+        KIND_PLATFORM => DISCOVERY_PLATFORMS.iter().any(|(p, _)| *p == target),
+        _ => return Err(format!("Kind must be '{}' or '{}'", KIND_AD_TYPE, KIND_PLATFORM)),
+    };
+    if !known {
+        return Err(format!("Unknown {} '{}'", kind.replace('_', " "), target));
+    }
+    let keyword = input.keyword.as_deref().unwrap_or("").trim().to_string();
+    if !(0.0..=1.0).contains(&input.score) {
+        return Err("Score must be between 0 and 1".to_string());
+    }
+
+    let taken: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM category_keywords WHERE kind = ?1 AND target = ?2 AND keyword = ?3 COLLATE NOCASE",
+            params![kind, target, keyword],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if taken.is_some() && taken != input.id {
+        let scope = if keyword.is_empty() { "other categories".to_string() } else { format!("'{}'", keyword) };
+        return Err(format!("{} already has a score for {}", target, scope));
+    }
+
+    let id = match input.id {
+        Some(id) => {
+            let builtin = get(conn, id)?.builtin;
+            // Shipped rows keep their kind, target and keyword so they can be reset
+            let updated = if builtin {
+                conn.execute(
+                    "UPDATE category_keywords SET score = ?2, updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+                    params![id, input.score],
+                )
+            } else {
+                conn.execute(
+                    "UPDATE category_keywords SET kind = ?2, target = ?3, keyword = ?4, score = ?5,
+                        updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+                    params![id, kind, target, keyword, input.score],
+                )
+            };
+            updated.map_err(|e| e.to_string())?;
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO category_keywords (kind, target, keyword, score) VALUES (?1, ?2, ?3, ?4)",
+                params![kind, target, keyword, input.score],
+            )
+            .map_err(|e| e.to_string())?;
+            conn.last_insert_rowid()
+        }
+    };
+    get(conn, id)
+}
+
+/// Restores a shipped keyword's score
+pub fn reset(conn: &Connection, id: i64) -> Result<CategoryKeyword, String> {
+    if !get(conn, id)?.builtin {
+        return Err("Only shipped keywords can be reset; delete this one instead".to_string());
+    }
+    conn.execute(
+        "UPDATE category_keywords SET score = default_score, updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+        params![id],
+    )
+    .map_err(|e| e.to_string())?;
+    get(conn, id)
+}
+
+/// Deletes a keyword the user added
+pub fn delete(conn: &Connection, id: i64) -> Result<(), String> {
+    if get(conn, id)?.builtin {
+        return Err("Shipped keywords can't be deleted; reset it instead".to_string());
+    }
+    conn.execute("DELETE FROM category_keywords WHERE id = ?1", params![id]).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_keyword_teaches_a_category() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(SHIPPED_SQL).unwrap();
+
+        // Highest matching keyword wins; unmatched categories get the fallback row
+        let shipped = CategoryAffinities::load(&conn).unwrap();
+        assert_eq!(shipped.ad_type_score("email", "Health Insurance"), 0.95);
+        assert_eq!(shipped.ad_type_score("video_script", "Musical Instruments"), 0.4);
+        assert_eq!(shipped.platform_score("tiktok", "beauty & skincare"), 1.0);
+        assert_eq!(shipped.platform_score("amazon", "Anything"), 1.0);
+
+        let input = SaveCategoryKeywordInput {
+            id: None,
+            kind: "ad_type".to_string(),
+            target: "Video_Script".to_string(),
+            keyword: Some("Instrument".to_string()),
+            score: 0.9,
+        };
+        let added = save(&conn, &input).unwrap();
+        assert!(!added.builtin);
+        assert!(save(&conn, &input).is_err());
+        assert!(save(&conn, &SaveCategoryKeywordInput { target: "fax".to_string(), ..input.clone() }).is_err());
+        assert!(save(&conn, &SaveCategoryKeywordInput { score: 1.5, ..input }).is_err());
+        let taught = CategoryAffinities::load(&conn).unwrap();
+        assert_eq!(taught.ad_type_score("video_script", "Musical Instruments"), 0.9);
+
+        // Shipped rows can be edited and reset, not deleted
+        let keywords = list(&conn).unwrap();
+        let fallback = keywords.into_iter().find(|k| k.target == "sms" && k.keyword.is_empty()).unwrap();
+        let edit = SaveCategoryKeywordInput {
+            id: Some(fallback.id),
+            kind: "ad_type".to_string(),
+            target: "sms".to_string(),
+            keyword: None,
+            score: 0.7,
+        };
+        assert!(save(&conn, &edit).unwrap().edited);
+        assert!(delete(&conn, fallback.id).is_err());
+        assert_eq!(reset(&conn, fallback.id).unwrap(), fallback);
+        delete(&conn, added.id).unwrap();
+        assert_eq!(CategoryAffinities::defaults().keywords, list(&conn).unwrap());
+    }
+}
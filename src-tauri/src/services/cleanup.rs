@@ -0,0 +1,111 @@
+//! Bulk Cleanup
+//!
+//! Rule-based deletion of stale ads and dead links. Every run first collects
+//! the matching rows so a dry run can show exactly what would be removed; a
+//! real run deletes the same rows inside one transaction.
+
+use crate::models::maintenance::{CleanupItem, CleanupReport, CleanupRules};
+use rusqlite::{params, Connection};
+
+/// Placeholder campaign that direct product ads are attached to
+const PLACEHOLDER_CAMPAIGN_ID: i64 = 1;
+
+/// Collects every row matched by the enabled rules. A row matched by several
+/// rules is only reported once, under the first rule.
+pub fn collect_candidates(conn: &Connection, rules: &CleanupRules) -> rusqlite::Result<Vec<CleanupItem>> {
+    let mut items: Vec<CleanupItem> = Vec::new();
+
+    if let Some(days) = rules.delete_ads_older_than_days {
+        let rule = format!("ads older than {} days (not favorites)", days);
+        items.extend(query_items(
+            conn,
+            "ad_copies",
+            &rule,
+            "SELECT id, headline FROM ad_copies
+             WHERE COALESCE(is_favorite, 0) = 0
+             AND created_at < datetime('now', ?1)",
+            params![format!("-{} days", days.max(0))],
+        )?);
+    }
+
+    if rules.purge_orphaned_ads {
+        items.extend(query_items(
+            conn,
+            "ad_copies",
+            "orphaned placeholder-campaign ads",
+            "SELECT id, headline FROM ad_copies
+             WHERE campaign_id = ?1 AND bundle_id IS NULL
+             AND (product_id IS NULL OR product_id NOT IN (SELECT id FROM products))",
+            params![PLACEHOLDER_CAMPAIGN_ID],
+        )?);
+    }
+
+    for (enabled, status) in [
+        (rules.remove_invalid_links, "invalid"),
+        (rules.remove_expired_links, "expired"),
+    ] {
+        if enabled {
+            items.extend(query_items(
+                conn,
+                "affiliate_links",
+                &format!("{} links", status),
+                "SELECT id, product_name || ' (' || program_name || ')'
+                 FROM affiliate_links WHERE status = ?1",
+                params![status],
+            )?);
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    items.retain(|item| seen.insert((item.table.clone(), item.id)));
+
+    Ok(items)
+}
+
+fn query_items(
+    conn: &Connection,
+    table: &str,
+    rule: &str,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> rusqlite::Result<Vec<CleanupItem>> {
+    let mut stmt = conn.prepare(sql)?;
+    let items = stmt
+        .query_map(params, |row| {
+            Ok(CleanupItem {
+                table: table.to_string(),
+                id: row.get(0)?,
+                label: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                rule: rule.to_string(),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(items)
+}
+
+/// Applies the rules. With `dry_run` nothing is deleted and the report lists
+/// what would have been removed.
+pub fn run_cleanup(conn: &mut Connection, rules: &CleanupRules, dry_run: bool) -> rusqlite::Result<CleanupReport> {
+    let items = collect_candidates(conn, rules)?;
+
+    let count = |table: &str| items.iter().filter(|i| i.table == table).count() as i64;
+    let report = CleanupReport {
+        dry_run,
+        ads_removed: count("ad_copies"),
+        links_removed: count("affiliate_links"),
+        items,
+    };
+
+    if dry_run {
+        return Ok(report);
+    }
+
+    let tx = conn.transaction()?;
+    for item in &report.items {
+        // Table names come from the fixed set used in collect_candidates
+        tx.execute(&format!("DELETE FROM {} WHERE id = ?1", item.table), params![item.id])?;
+    }
+    tx.commit()?;
+
+    Ok(report)
+}
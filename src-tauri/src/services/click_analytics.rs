@@ -0,0 +1,447 @@
+//! Click Analytics
+//!
+//! Aggregations over the `click_events` log used to understand when and where
+//! an audience clicks, e.g. the weekday/hour heatmap that informs posting times.
+//! Also owns the write path for clicks, which tags each click with a
+//! normalized traffic source (tiktok, pinterest, google, direct, ...) taken
+//! from its `utm_source` or, failing that, its referrer. Crawler user agents
+//! and sub-second repeat clicks are kept in the log with a `bot_reason` but
+//! left out of every report, so raw and human counts stay separate.
+
+use crate::models::click_analytics::{
+    BotReasonClicks, ClickHeatmap, ClickQuality, ClicksByCountry, ClicksBySource, CountryClicks, HeatmapSlot,
+    RegionClicks, SourceClicks,
+};
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+
+/// Number of peak slots reported with a heatmap
+const PEAK_SLOT_COUNT: usize = 3;
+
+/// Builds the weekday x hour click heatmap for a link or for all of a product's links.
+/// Click timestamps are stored in UTC; `utc_offset_minutes` shifts them into the
+/// audience's local time before bucketing.
+pub fn build_heatmap(
+    conn: &Connection,
+    link_id: Option<i64>,
+    product_id: Option<i64>,
+    utc_offset_minutes: i32,
+) -> rusqlite::Result<ClickHeatmap> {
+    let modifier = format!("{:+} minutes", utc_offset_minutes);
+
+    let mut stmt = conn.prepare(
+        "SELECT CAST(strftime('%w', datetime(c.clicked_at, ?1)) AS INTEGER) AS weekday,
+         CAST(strftime('%H', datetime(c.clicked_at, ?1)) AS INTEGER) AS hour,
+         COUNT(*)
+         FROM click_events c
+         JOIN affiliate_links l ON l.id = c.link_id
+         WHERE (?2 IS NULL OR c.link_id = ?2) AND (?3 IS NULL OR l.product_id = ?3)
+           AND c.bot_reason IS NULL
+         GROUP BY weekday, hour",
+    )?;
+
+    let mut matrix = vec![vec![0i64; 24]; 7];
+    let buckets = stmt
+        .query_map(params![modifier, link_id, product_id], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (weekday, hour, clicks) in buckets {
+        if (0..7).contains(&weekday) && (0..24).contains(&hour) {
+            matrix[weekday as usize][hour as usize] += clicks;
+        }
+    }
+
+    Ok(ClickHeatmap {
+        link_id,
+        product_id,
+        utc_offset_minutes,
+        total_clicks: matrix.iter().flatten().sum(),
+        peak_slots: peak_slots(&matrix, PEAK_SLOT_COUNT),
+        matrix,
+    })
+}
+
+/// Known sources as (source, utm_source aliases, referrer domains)
+const KNOWN_SOURCES: [(&str, &[&str], &[&str]); 10] = [
+    ("tiktok", &["tiktok", "tt"], &["tiktok.com"]),
+    ("instagram", &["instagram", "ig"], &["instagram.com"]),
+    ("facebook", &["facebook", "fb", "meta"], &["facebook.com", "fb.com", "messenger.com"]),
+    ("pinterest", &["pinterest", "pin"], &["pinterest.com", "pin.it"]),
+    ("youtube", &["youtube", "yt"], &["youtube.com", "youtu.be"]),
+    ("x", &["x", "twitter"], &["twitter.com", "x.com", "t.co"]),
+    ("reddit", &["reddit"], &["reddit.com"]),
+    ("google", &["google"], &["google.com", "google.co.uk", "google.ca", "google.de"]),
+    ("bing", &["bing"], &["bing.com"]),
+    ("email", &["email", "newsletter", "mail"], &["mail.google.com", "outlook.live.com", "mail.yahoo.com"]),
+];
+
+/// Maps a click's `utm_source` and referrer onto one source name. A UTM
+/// source wins over the referrer; unknown UTM sources are kept as given
+/// (lowercased), unknown referrers become "other", and no data is "direct".
+pub fn normalize_source(utm_source: Option<&str>, referrer: Option<&str>) -> String {
+    if let Some(utm) = utm_source.map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()) {
+        return KNOWN_SOURCES
+            .iter()
+            .find(|(_, aliases, _)| aliases.contains(&utm.as_str()))
+            .map(|(source, _, _)| source.to_string())
+            .unwrap_or(utm);
+    }
+
+    let host = match referrer.map(referrer_host).filter(|h| !h.is_empty()) {
+        Some(host) => host,
+        None => return "direct".to_string(),
+    };
+    // Most specific domain first so mail.google.com is email, not google
+    let mut matches: Vec<(&str, &str)> = KNOWN_SOURCES
+        .iter()
+        .flat_map(|(source, _, domains)| domains.iter().map(move |d| (*source, *d)))
+        .filter(|(_, domain)| host == *domain || host.ends_with(&format!(".{}", domain)))
+        .collect();
+    matches.sort_by_key(|(_, domain)| std::cmp::Reverse(domain.len()));
+    matches.first().map(|(source, _)| source.to_string()).unwrap_or_else(|| "other".to_string())
+}
+
+/// Lowercased host of a referrer URL, without scheme, port, or path
+fn referrer_host(referrer: &str) -> String {
+    let rest = referrer.trim().split("://").last().unwrap_or("");
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    host.rsplit('@').next().unwrap_or("").split(':').next().unwrap_or("").to_lowercase()
+}
+
+/// User agent fragments (lowercase) of crawlers, link previewers, uptime
+/// monitors, and HTTP libraries
+const BOT_USER_AGENT_MARKERS: [&str; 32] = [
+    "bot/", "bot;", "googlebot", "bingbot", "crawler", "spider", "slurp", "facebookexternalhit",
+    "facebookcatalog", "meta-externalagent", "whatsapp", "telegrambot", "discordbot", "slackbot",
+    "twitterbot", "linkedinbot", "pinterestbot", "embedly", "headlesschrome", "phantomjs", "lighthouse",
+    "pingdom", "uptimerobot", "python-requests", "python-urllib", "curl/", "wget/", "go-http-client",
+    "axios/", "node-fetch", "scrapy", "gptbot",
+];
+
+/// Clicks from the same visitor on the same link closer together than this
+/// are automated (double-fired redirects, prefetchers, click bots)
+const REPEAT_CLICK_WINDOW_MS: i64 = 1000;
+
+pub const BOT_USER_AGENT: &str = "bot_user_agent";
+pub const MISSING_USER_AGENT: &str = "missing_user_agent";
+pub const RAPID_REPEAT: &str = "rapid_repeat";
+
+/// Classifies a user agent; `None` means it looks like a person's browser
+pub fn user_agent_bot_reason(user_agent: Option<&str>) -> Option<&'static str> {
+    let user_agent = match user_agent.map(str::trim).filter(|ua| !ua.is_empty()) {
+        Some(ua) => ua.to_lowercase(),
+        None => return Some(MISSING_USER_AGENT),
+    };
+    BOT_USER_AGENT_MARKERS
+        .iter()
+        .any(|marker| user_agent.contains(marker))
+        .then_some(BOT_USER_AGENT)
+}
+
+/// Why a click being recorded should not count as human traffic, if it shouldn't
+fn bot_reason(conn: &Connection, click: &ClickRecord) -> rusqlite::Result<Option<&'static str>> {
+    if let Some(reason) = user_agent_bot_reason(click.user_agent) {
+        return Ok(Some(reason));
+    }
+    let Some(ip_hash) = click.ip_hash else {
+        return Ok(None);
+    };
+    let repeat: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM click_events
+         WHERE link_id = ?1 AND ip_hash = ?2
+           AND (julianday('now') - julianday(clicked_at)) * 86400000 < ?3)",
+        params![click.link_id, ip_hash, REPEAT_CLICK_WINDOW_MS],
+        |row| row.get(0),
+    )?;
+    Ok(repeat.then_some(RAPID_REPEAT))
+}
+
+/// A click to log against an affiliate link
+pub struct ClickRecord<'a> {
+    pub link_id: i64,
+    pub campaign_id: Option<i64>,
+    pub ip_hash: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+    pub referrer: Option<&'a str>,
+    pub utm_source: Option<&'a str>,
+    pub country: Option<&'a str>,
+    pub region: Option<&'a str>,
+}
+
+/// Logs a click with its normalized source and bot classification and
+/// returns the new row ID. Times are kept to the millisecond so rapid repeats
+/// can be told apart.
+pub fn record_click(conn: &Connection, click: &ClickRecord) -> rusqlite::Result<i64> {
+    let bot_reason = bot_reason(conn, click)?;
+    conn.execute(
+        "INSERT INTO click_events (link_id, campaign_id, ip_hash, user_agent, referrer, utm_source, source,
+         country, region, bot_reason, clicked_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, strftime('%Y-%m-%d %H:%M:%f', 'now'))",
+        params![
+            click.link_id,
+            click.campaign_id,
+            click.ip_hash,
+            click.user_agent,
+            click.referrer,
+            click.utm_source,
+            normalize_source(click.utm_source, click.referrer),
+            click.country,
+            click.region,
+            bot_reason,
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Filtered-out clicks per bot reason over the last `days` days, most first
+fn bot_reason_counts(
+    conn: &Connection,
+    link_id: Option<i64>,
+    product_id: Option<i64>,
+    days: i64,
+) -> rusqlite::Result<Vec<BotReasonClicks>> {
+    let mut stmt = conn.prepare(
+        "SELECT c.bot_reason, COUNT(*) AS clicks
+         FROM click_events c
+         JOIN affiliate_links l ON l.id = c.link_id
+         WHERE (?1 IS NULL OR c.link_id = ?1) AND (?2 IS NULL OR l.product_id = ?2)
+           AND c.clicked_at >= datetime('now', ?3) AND c.bot_reason IS NOT NULL
+         GROUP BY c.bot_reason
+         ORDER BY clicks DESC, c.bot_reason",
+    )?;
+    let reasons = stmt
+        .query_map(params![link_id, product_id, format!("-{} days", days)], |row| {
+            Ok(BotReasonClicks { reason: row.get(0)?, clicks: row.get(1)? })
+        })?
+        .collect();
+    reasons
+}
+
+/// Raw vs. human click counts over the last `days` days for a link, a
+/// product's links, or (with neither) the whole workspace
+pub fn click_quality(
+    conn: &Connection,
+    link_id: Option<i64>,
+    product_id: Option<i64>,
+    days: i64,
+) -> rusqlite::Result<ClickQuality> {
+    let raw_clicks: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM click_events c
+         JOIN affiliate_links l ON l.id = c.link_id
+         WHERE (?1 IS NULL OR c.link_id = ?1) AND (?2 IS NULL OR l.product_id = ?2)
+           AND c.clicked_at >= datetime('now', ?3)",
+        params![link_id, product_id, format!("-{} days", days)],
+        |row| row.get(0),
+    )?;
+    let reasons = bot_reason_counts(conn, link_id, product_id, days)?;
+    let bot_clicks: i64 = reasons.iter().map(|r| r.clicks).sum();
+
+    Ok(ClickQuality {
+        link_id,
+        product_id,
+        days,
+        raw_clicks,
+        human_clicks: raw_clicks - bot_clicks,
+        bot_clicks,
+        bot_share: if raw_clicks > 0 { bot_clicks as f64 / raw_clicks as f64 } else { 0.0 },
+        reasons,
+    })
+}
+
+/// Counts clicks per traffic source over the last `days` days for a link or
+/// for all of a product's links. Clicks logged before sources were stored are
+/// normalized from their referrer on the fly.
+pub fn clicks_by_source(
+    conn: &Connection,
+    link_id: Option<i64>,
+    product_id: Option<i64>,
+    days: i64,
+) -> rusqlite::Result<ClicksBySource> {
+    let mut stmt = conn.prepare(
+        "SELECT c.source, c.utm_source, c.referrer, COUNT(*)
+         FROM click_events c
+         JOIN affiliate_links l ON l.id = c.link_id
+         WHERE (?1 IS NULL OR c.link_id = ?1) AND (?2 IS NULL OR l.product_id = ?2)
+           AND c.clicked_at >= datetime('now', ?3) AND c.bot_reason IS NULL
+         GROUP BY c.source, c.utm_source, c.referrer",
+    )?;
+    let groups = stmt
+        .query_map(params![link_id, product_id, format!("-{} days", days)], |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut counts: HashMap<String, i64> = HashMap::new();
+    for (source, utm_source, referrer, clicks) in groups {
+        let source = source.unwrap_or_else(|| normalize_source(utm_source.as_deref(), referrer.as_deref()));
+        *counts.entry(source).or_default() += clicks;
+    }
+
+    Ok(ClicksBySource {
+        link_id,
+        product_id,
+        days,
+        total_clicks: counts.values().sum(),
+        bot_clicks: bot_reason_counts(conn, link_id, product_id, days)?.iter().map(|r| r.clicks).sum(),
+        sources: rank_sources(counts),
+    })
+}
+
+/// Orders sources by clicks (then name) and works out each one's share
+fn rank_sources(counts: HashMap<String, i64>) -> Vec<SourceClicks> {
+    let total: i64 = counts.values().sum();
+    let mut sources: Vec<SourceClicks> = counts
+        .into_iter()
+        .map(|(source, clicks)| SourceClicks {
+            share: if total > 0 { clicks as f64 / total as f64 } else { 0.0 },
+            source,
+            clicks,
+        })
+        .collect();
+    sources.sort_by(|a, b| b.clicks.cmp(&a.clicks).then_with(|| a.source.cmp(&b.source)));
+    sources
+}
+
+/// Counts clicks per country (and region within it) over the last `days` days
+/// for a link or for all of a product's links
+pub fn clicks_by_country(
+    conn: &Connection,
+    link_id: Option<i64>,
+    product_id: Option<i64>,
+    days: i64,
+) -> rusqlite::Result<ClicksByCountry> {
+    let mut stmt = conn.prepare(
+        "SELECT c.country, c.region, COUNT(*)
+         FROM click_events c
+         JOIN affiliate_links l ON l.id = c.link_id
+         WHERE (?1 IS NULL OR c.link_id = ?1) AND (?2 IS NULL OR l.product_id = ?2)
+           AND c.clicked_at >= datetime('now', ?3) AND c.bot_reason IS NULL
+         GROUP BY c.country, c.region",
+    )?;
+    let groups = stmt
+        .query_map(params![link_id, product_id, format!("-{} days", days)], |row| {
+            Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?, row.get::<_, i64>(2)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let unknown_clicks = groups.iter().filter(|(country, _, _)| country.is_none()).map(|g| g.2).sum();
+    let countries = rank_countries(
+        groups.into_iter().filter_map(|(country, region, clicks)| Some((country?, region, clicks))),
+    );
+
+    Ok(ClicksByCountry {
+        link_id,
+        product_id,
+        days,
+        total_clicks: unknown_clicks + countries.iter().map(|c| c.clicks).sum::<i64>(),
+        unknown_clicks,
+        bot_clicks: bot_reason_counts(conn, link_id, product_id, days)?.iter().map(|r| r.clicks).sum(),
+        countries,
+    })
+}
+
+/// Rolls (country, region, clicks) groups up into countries, busiest first
+fn rank_countries(groups: impl Iterator<Item = (String, Option<String>, i64)>) -> Vec<CountryClicks> {
+    let mut by_country: HashMap<String, CountryClicks> = HashMap::new();
+    for (country, region, clicks) in groups {
+        let entry = by_country.entry(country.clone()).or_insert_with(|| CountryClicks {
+            country,
+            clicks: 0,
+            share: 0.0,
+            regions: Vec::new(),
+        });
+        entry.clicks += clicks;
+        if let Some(region) = region {
+            entry.regions.push(RegionClicks { region, clicks });
+        }
+    }
+
+    let located: i64 = by_country.values().map(|c| c.clicks).sum();
+    let mut countries: Vec<CountryClicks> = by_country.into_values().collect();
+    for country in &mut countries {
+        country.share = if located > 0 { country.clicks as f64 / located as f64 } else { 0.0 };
+        country.regions.sort_by(|a, b| b.clicks.cmp(&a.clicks).then_with(|| a.region.cmp(&b.region)));
+    }
+    countries.sort_by(|a, b| b.clicks.cmp(&a.clicks).then_with(|| a.country.cmp(&b.country)));
+    countries
+}
+
+/// Returns the `count` busiest non-empty weekday/hour slots, busiest first
+pub fn peak_slots(matrix: &[Vec<i64>], count: usize) -> Vec<HeatmapSlot> {
+    let mut slots: Vec<HeatmapSlot> = matrix
+        .iter()
+        .enumerate()
+        .flat_map(|(weekday, hours)| {
+            hours.iter().enumerate().map(move |(hour, &clicks)| HeatmapSlot {
+                weekday: weekday as u32,
+                hour: hour as u32,
+                clicks,
+            })
+        })
+        .filter(|slot| slot.clicks > 0)
+        .collect();
+
+    // Stable sort keeps earlier weekday/hour first on ties
+    slots.sort_by_key(|slot| std::cmp::Reverse(slot.clicks));
+    slots.truncate(count);
+    slots
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peak_slots_ordering() {
+        let mut matrix = vec![vec![0i64; 24]; 7];
+        matrix[1][9] = 5;
+        matrix[5][20] = 12;
+        matrix[3][12] = 5;
+        let peaks = peak_slots(&matrix, 3);
+        assert_eq!(peaks[0], HeatmapSlot { weekday: 5, hour: 20, clicks: 12 });
+        assert_eq!(peaks[1], HeatmapSlot { weekday: 1, hour: 9, clicks: 5 });
+        assert_eq!(peaks[2], HeatmapSlot { weekday: 3, hour: 12, clicks: 5 });
+    }
+
+    #[test]
+    fn test_peak_slots_skips_empty() {
+        let matrix = vec![vec![0i64; 24]; 7];
+        assert!(peak_slots(&matrix, 3).is_empty());
+    }
+
+    #[test]
+    fn test_normalize_source() {
+        assert_eq!(normalize_source(Some("IG"), Some("https://www.google.com/")), "instagram");
+        assert_eq!(normalize_source(Some("Spring_Newsletter"), None), "spring_newsletter");
+        assert_eq!(normalize_source(None, Some("https://vm.tiktok.com/ZM123/")), "tiktok");
+        assert_eq!(normalize_source(None, Some("https://mail.google.com/mail/u/0/")), "email");
+        assert_eq!(normalize_source(None, Some("https://pin.it/abc")), "pinterest");
+        assert_eq!(normalize_source(None, Some("https://blog.example.com/post")), "other");
+        assert_eq!(normalize_source(Some(" "), None), "direct");
+    }
+
+    #[test]
+    fn test_user_agent_bot_reason() {
+        let iphone = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_4 like Mac OS X) AppleWebKit/605.1.15 Mobile/15E148";
+        assert_eq!(user_agent_bot_reason(Some(iphone)), None);
+        assert_eq!(user_agent_bot_reason(Some("Mozilla/5.0 (Linux; Android 13; CUBOT X30)")), None);
+        assert_eq!(
+            user_agent_bot_reason(Some("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)")),
+            Some(BOT_USER_AGENT)
+        );
+        assert_eq!(user_agent_bot_reason(Some("facebookexternalhit/1.1")), Some(BOT_USER_AGENT));
+        assert_eq!(user_agent_bot_reason(Some("python-requests/2.31.0")), Some(BOT_USER_AGENT));
+        assert_eq!(user_agent_bot_reason(Some("  ")), Some(MISSING_USER_AGENT));
+    }
+}
@@ -0,0 +1,133 @@
+//! Clipboard Watcher
+//!
+//! Opt-in background watcher that polls the system clipboard for product URLs
+//! (Amazon, TikTok Shop, Etsy, ...) during research sessions. When a new one
+//! is copied it emits a `clipboard-product-url` event so the frontend can offer
+//! to capture it into the ideas inbox. Nothing is stored without the user's
+//! confirmation.
+
+use crate::database::get_connection;
+use crate::models::idea::ClipboardProductUrl;
+use crate::services::scraper::detect_platform;
+use rusqlite::{params, Connection};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+pub const CLIPBOARD_EVENT: &str = "clipboard-product-url";
+pub const ENABLED_SETTING: &str = "clipboard_watcher_enabled";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1500);
+
+fn stop_slot() -> &'static Mutex<Option<Arc<AtomicBool>>> {
+    static SLOT: OnceLock<Mutex<Option<Arc<AtomicBool>>>> = OnceLock::new();
+    SLOT.get_or_init(|| Mutex::new(None))
+}
+
+/// Whether the watcher thread is running
+pub fn is_running() -> bool {
+    stop_slot().lock().map(|s| s.is_some()).unwrap_or(false)
+}
+
+/// Starts polling the clipboard. Does nothing if already running.
+pub fn start_watcher(app_handle: AppHandle) -> Result<(), String> {
+    let mut slot = stop_slot().lock().map_err(|e| e.to_string())?;
+    if slot.is_some() {
+        return Ok(());
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = stop.clone();
+
+    std::thread::spawn(move || {
+        // Whatever is on the clipboard at startup was not copied in this session
+        let mut last_seen = app_handle.clipboard().read_text().ok();
+
+        while !stop_flag.load(Ordering::SeqCst) {
+            std::thread::sleep(POLL_INTERVAL);
+
+            let text = match app_handle.clipboard().read_text() {
+                Ok(text) => text,
+                Err(_) => continue, // empty clipboard or non-text content
+            };
+            if last_seen.as_deref() == Some(text.as_str()) {
+                continue;
+            }
+            last_seen = Some(text.clone());
+
+            if let Some(detected) = extract_product_url(&text) {
+                let known = get_connection(&app_handle)
+                    .map(|conn| is_known_url(&conn, &detected.url))
+                    .unwrap_or(false);
+                if known {
+                    continue;
+                }
+                if let Err(e) = app_handle.emit(CLIPBOARD_EVENT, &detected) {
+                    eprintln!("Failed to emit clipboard event: {}", e);
+                }
+            }
+        }
+    });
+
+    *slot = Some(stop);
+    println!("✓ Clipboard watcher started");
+    Ok(())
+}
+
+/// Stops the watcher if it is running
+pub fn stop_watcher() {
+    if let Ok(mut slot) = stop_slot().lock() {
+        if let Some(stop) = slot.take() {
+            stop.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Returns the copied text as a product URL when it is a single link to a
+/// recognized storefront
+pub fn extract_product_url(text: &str) -> Option<ClipboardProductUrl> {
+    let url = text.trim();
+    if url.contains(char::is_whitespace)
+        || !(url.starts_with("http://") || url.starts_with("https://"))
+    {
+        return None;
+    }
+
+    detect_platform(url).map(|platform| ClipboardProductUrl {
+        url: url.to_string(),
+        platform: platform.to_string(),
+    })
+}
+
+/// Whether the URL is already in the ideas inbox or the product catalog
+fn is_known_url(conn: &Connection, url: &str) -> bool {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM ideas WHERE url = ?1)
+             OR EXISTS(SELECT 1 FROM products WHERE product_url = ?1)",
+        params![url],
+        |row| row.get(0),
+    )
+    .unwrap_or(false)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_product_url() {
+        let detected = extract_product_url("  https://www.amazon.com/dp/B00PBX3L7K \n").unwrap();
+        assert_eq!(detected.url, "https://www.amazon.com/dp/B00PBX3L7K");
+        assert_eq!(detected.platform, "amazon");
+
+        assert!(extract_product_url("https://www.etsy.com/listing/123/mug").is_some());
+        assert!(extract_product_url("https://example.com/blog").is_none());
+        assert!(extract_product_url("check this https://www.amazon.com/dp/B00PBX3L7K").is_none());
+    }
+}
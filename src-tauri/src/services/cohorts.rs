@@ -0,0 +1,153 @@
+//! Product Cohorts
+//!
+//! Groups products by the month they were added and follows each group's
+//! clicks and commission through the months after, read from daily metrics.
+//! Month offsets line cohorts up by age, so "commission per product after
+//! three months" can be compared between January's research and June's.
+
+use crate::models::cohort::{CohortMonth, ProductCohort, ProductCohortReport};
+use crate::services::daily_metrics::{self, ProductMonth};
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
+
+/// Cohorts reported when no count is given: the last year
+pub const DEFAULT_COHORT_MONTHS: i64 = 12;
+pub const MAX_COHORT_MONTHS: i64 = 60;
+
+/// Months since year 0 of a "YYYY-MM" string
+fn month_index(month: &str) -> Option<i64> {
+    let (year, month) = month.get(..7)?.split_once('-')?;
+    let (year, month): (i64, i64) = (year.parse().ok()?, month.parse().ok()?);
+    (1..=12).contains(&month).then_some(year * 12 + month - 1)
+}
+
+fn month_label(index: i64) -> String {
+    format!("{:04}-{:02}", index.div_euclid(12), index.rem_euclid(12) + 1)
+}
+
+/// Cohorts from `products` as `(product_id, month added)` and their monthly
+/// activity, each followed from its own month through `current_month`
+pub fn build(products: &[(i64, String)], activity: &[ProductMonth], current_month: &str) -> Vec<ProductCohort> {
+    let Some(current) = month_index(current_month) else {
+        return Vec::new();
+    };
+    let mut members: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (product_id, added) in products {
+        if let Some(index) = month_index(added) {
+            members.entry(index).or_default().push(*product_id);
+        }
+    }
+    let activity: HashMap<(i64, i64), &ProductMonth> = activity
+        .iter()
+        .filter_map(|m| Some(((m.product_id, month_index(&m.month)?), m)))
+        .collect();
+
+    let mut cohort_months: Vec<i64> = members.keys().copied().filter(|&m| m <= current).collect();
+    cohort_months.sort_unstable();
+    cohort_months
+        .into_iter()
+        .map(|cohort| {
+            let product_ids: HashSet<i64> = members[&cohort].iter().copied().collect();
+            let product_count = product_ids.len() as i64;
+            let mut cumulative = 0.0;
+            let months: Vec<CohortMonth> = (cohort..=current)
+                .map(|index| {
+                    let rows: Vec<&ProductMonth> =
+                        product_ids.iter().filter_map(|id| activity.get(&(*id, index)).copied()).collect();
+                    let commission = rows.iter().map(|m| m.commission).sum::<f64>();
+                    cumulative += commission;
+                    CohortMonth {
+                        offset: index - cohort,
+                        month: month_label(index),
+                        clicks: rows.iter().map(|m| m.clicks).sum(),
+                        conversions: rows.iter().map(|m| m.conversions).sum(),
+                        commission: (commission * 100.0).round() / 100.0,
+                        active_products: rows.iter().filter(|m| m.clicks > 0).count() as i64,
+                        cumulative_commission_per_product: (cumulative / product_count as f64 * 100.0).round() / 100.0,
+                    }
+                })
+                .collect();
+            ProductCohort {
+                cohort: month_label(cohort),
+                product_count,
+                clicks: months.iter().map(|m| m.clicks).sum(),
+                commission: (cumulative * 100.0).round() / 100.0,
+                months,
+            }
+        })
+        .collect()
+}
+
+/// The cohorts of the last `months` months, including the current one
+pub fn report(conn: &Connection, months: i64, today: NaiveDate) -> rusqlite::Result<ProductCohortReport> {
+    let months = months.clamp(1, MAX_COHORT_MONTHS);
+    let current = today.year() as i64 * 12 + today.month0() as i64;
+    let first_month = month_label(current - months + 1);
+    let start = NaiveDate::parse_from_str(&format!("{}-01", first_month), "%Y-%m-%d").unwrap_or(today);
+
+    let mut stmt = conn.prepare(
+        "SELECT id, substr(created_at, 1, 7) FROM products
+         WHERE created_at IS NOT NULL AND created_at >= ?1 ORDER BY id",
+    )?;
+    let products = stmt
+        .query_map(params![first_month], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    let activity = daily_metrics::product_months(conn, start, today)?;
+
+    Ok(ProductCohortReport {
+        cohorts: build(&products, &activity, &month_label(current)),
+        through: today.format("%Y-%m-%d").to_string(),
+    })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(product_id: i64, month: &str, clicks: i64, commission: f64) -> ProductMonth {
+        let conversions = (commission > 0.0) as i64;
+        ProductMonth { product_id, month: month.to_string(), clicks, conversions, commission }
+    }
+
+    #[test]
+    fn test_cohorts_follow_products_from_the_month_they_were_added() {
+        let products = [
+            (1, "2026-01".to_string()),
+            (2, "2026-01".to_string()),
+            (3, "2026-02".to_string()),
+            (4, "2025-12".to_string()),
+        ];
+        let months = [
+            activity(1, "2026-01", 10, 0.0),
+            activity(1, "2026-02", 30, 12.0),
+            activity(2, "2026-02", 5, 4.0),
+            activity(3, "2026-02", 40, 20.0),
+            activity(3, "2026-03", 20, 10.0),
+        ];
+        let cohorts = build(&products, &months, "2026-03");
+        let labels: Vec<&str> = cohorts.iter().map(|c| c.cohort.as_str()).collect();
+        assert_eq!(labels, ["2025-12", "2026-01", "2026-02"]);
+
+        let january = &cohorts[1];
+        assert_eq!((january.product_count, january.clicks, january.commission), (2, 45, 16.0));
+        let offsets: Vec<(i64, &str, i64, i64)> =
+            january.months.iter().map(|m| (m.offset, m.month.as_str(), m.clicks, m.active_products)).collect();
+        assert_eq!(offsets, [(0, "2026-01", 10, 1), (1, "2026-02", 35, 2), (2, "2026-03", 0, 0)]);
+        assert_eq!(january.months[1].cumulative_commission_per_product, 8.0);
+
+        // February's product earned more by month 1 than January's did
+        let february = &cohorts[2];
+        assert_eq!(february.months[1].cumulative_commission_per_product, 30.0);
+
+        // A cohort with no activity still reports every month
+        assert_eq!(cohorts[0].months.len(), 4);
+        assert!(cohorts[0].months.iter().all(|m| m.clicks == 0));
+        assert_eq!(month_label(month_index("2025-12").unwrap() + 1), "2026-01");
+        assert!(build(&products, &months, "soon").is_empty());
+    }
+}
@@ -0,0 +1,296 @@
+//! Content Angle Generator
+//!
+//! Produces distinct creative angles for a product: a hook plus a short
+//! creative direction, tagged with the angle type (problem-agitate-solve,
+//! social proof, comparison, unboxing, ...). Angles are stored so they can be
+//! reused as the creative direction for `generate_ad_for_product`. The
+//! product's research brief, when present, grounds both the prompt and the
+//! mock heuristics.
+
+use crate::models::ai_discovery::ParseDiagnostic;
+use crate::models::content_angle::{AngleDraft, ContentAngle};
+use crate::models::product::Product;
+use crate::models::research::ResearchBrief;
+use crate::services::ai_validation::validate_array;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+pub const DEFAULT_ANGLE_COUNT: i32 = 4;
+pub const MAX_ANGLE_COUNT: i32 = 8;
+
+pub const ANGLES_PROMPT: &str = r#"You are a creative director for short-form affiliate ads. Propose {count} distinct content angles for the following product.
+
+Product Information:
+- Name: {product_name}
+- Category: {category}
+- Description: {description}
+- Target Audience: {target_audience}
+
+Research Brief:
+{research_context}
+
+Niche Profile:
+{niche_context}
+
+Return ONLY a valid JSON array with this structure:
+[
+  {
+    "angle_type": "problem_agitate_solve|social_proof|comparison|unboxing|before_after|myth_busting",
+    "hook": "Scroll-stopping opening line",
+    "direction": "One or two sentences of creative direction for the ad"
+  }
+]
+
+Rules:
+- Exactly {count} angles, each with a different hook
+- Use as many different angle types as possible
+- Never make claims the niche profile bans
+- NO explanatory text, ONLY the JSON array"#;
+
+/// Schema for the array requested by `ANGLES_PROMPT`
+pub const ANGLES_SCHEMA: &str = r#"{
+  "type": "array",
+  "minItems": 1,
+  "items": {
+    "type": "object",
+    "required": ["angle_type", "hook", "direction"],
+    "properties": {
+      "angle_type": {
+        "enum": ["problem_agitate_solve", "social_proof", "comparison", "unboxing", "before_after", "myth_busting"]
+      },
+      "hook": { "type": "string", "minLength": 1 },
+      "direction": { "type": "string", "minLength": 1 }
+    }
+  }
+}"#;
+
+pub const ANGLE_COLUMNS: &str = "id, product_id, angle_type, hook, direction, served_by, created_at";
+
+/// Maps a row selected with `ANGLE_COLUMNS` into a `ContentAngle`
+pub fn angle_from_row(row: &Row) -> rusqlite::Result<ContentAngle> {
+    Ok(ContentAngle {
+        id: row.get(0)?,
+        product_id: row.get(1)?,
+        angle_type: row.get(2)?,
+        hook: row.get(3)?,
+        direction: row.get(4)?,
+        served_by: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
+
+/// Clamps a requested angle count into the supported range
+pub fn clamp_count(count: Option<i32>) -> i32 {
+    count.unwrap_or(DEFAULT_ANGLE_COUNT).clamp(1, MAX_ANGLE_COUNT)
+}
+
+pub fn build_angles_prompt(
+    product: &Product,
+    target_audience: &str,
+    research: Option<&ResearchBrief>,
+    niche_context: &str,
+    count: i32,
+) -> String {
+    ANGLES_PROMPT
+        .replace("{count}", &count.to_string())
+        .replace("{product_name}", &product.name)
+        .replace("{category}", &product.category)
+        .replace("{description}", product.description.as_deref().unwrap_or(""))
+        .replace("{target_audience}", target_audience)
+        .replace("{research_context}", &research_context(research))
+        .replace("{niche_context}", niche_context)
+}
+
+fn research_context(research: Option<&ResearchBrief>) -> String {
+    let brief = match research {
+        Some(brief) => brief,
+        None => return "- Not researched yet".to_string(),
+    };
+
+    [
+        ("Pain Points", &brief.pain_points),
+        ("Objections", &brief.objections),
+        ("Key Benefits", &brief.key_benefits),
+        ("Compared Against", &brief.comparison_targets),
+    ]
+    .iter()
+    .filter(|(_, list)| !list.is_empty())
+    .map(|(label, list)| format!("- {}: {}", label, list.join("; ")))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Extracts and schema-validates an angles response
+pub fn validate_angles(response: &str) -> Result<Vec<AngleDraft>, ParseDiagnostic> {
+    validate_array(response, ANGLES_SCHEMA)
+}
+
+/// Picks up to `count` angles with distinct hooks, covering as many angle
+/// types as possible before repeating one
+pub fn distinct_angles(drafts: Vec<AngleDraft>, count: usize) -> Vec<AngleDraft> {
+    let mut unique: Vec<AngleDraft> = Vec::new();
+    for draft in drafts {
+        let hook = draft.hook.trim().to_lowercase();
+        if !hook.is_empty() && !unique.iter().any(|u| u.hook.trim().to_lowercase() == hook) {
+            unique.push(draft);
+        }
+    }
+
+    let (mut picked, mut repeats): (Vec<AngleDraft>, Vec<AngleDraft>) = (Vec::new(), Vec::new());
+    for draft in unique {
+        if picked.iter().any(|p| p.angle_type == draft.angle_type) {
+            repeats.push(draft);
+        } else {
+            picked.push(draft);
+        }
+    }
+    picked.extend(repeats);
+    picked.truncate(count);
+    picked
+}
+
+/// Heuristic angles used with the mock provider and as the last fallback
+pub fn mock_angles(product: &Product, research: Option<&ResearchBrief>) -> Vec<AngleDraft> {
+    let name = &product.name;
+    let category = product.category.to_lowercase();
+    let pain_point = research
+        .and_then(|r| r.pain_points.first().cloned())
+        .unwrap_or_else(|| format!("your {} routine isn't working", category));
+    let rival = research
+        .and_then(|r| r.comparison_targets.first().cloned())
+        .unwrap_or_else(|| format!("other {}", category));
+    let objection = research
+        .and_then(|r| r.objections.first().cloned())
+        .unwrap_or_else(|| "Is it worth the hype?".to_string());
+
+    let draft = |angle_type: &str, hook: String, direction: String| AngleDraft {
+        angle_type: angle_type.to_string(),
+        hook,
+        direction,
+    };
+
+    vec![
+        draft(
+            "problem_agitate_solve",
+            format!("Still dealing with this? {}", pain_point),
+            format!(
+                "Open on the problem, show how much it costs day to day, then reveal {} as the fix.",
+                name
+            ),
+        ),
+        draft(
+            "social_proof",
+            format!("Why everyone is switching to {}", name),
+            "Lead with reviews, ratings, and real customer reactions before showing the product.".to_string(),
+        ),
+        draft(
+            "comparison",
+            format!("{} vs. {}: the honest comparison", name, rival),
+            format!("Side-by-side on price, results, and ease of use against {}.", rival),
+        ),
+        draft(
+            "unboxing",
+            format!("Unboxing {} - first impressions", name),
+            "Unscripted unboxing with genuine first reactions; end on the first use.".to_string(),
+        ),
+        draft(
+            "before_after",
+            format!("Before and after one week with {}", name),
+            "Show the same moment before and after, letting the difference speak for itself.".to_string(),
+        ),
+        draft(
+            "myth_busting",
+            objection.clone(),
+            format!("Address \"{}\" head on, then answer it with a demonstration.", objection),
+        ),
+    ]
+}
+
+/// Stores angles for a product and returns the saved records
+pub fn save_all(
+    conn: &Connection,
+    product_id: i64,
+    drafts: &[AngleDraft],
+    served_by: &str,
+) -> rusqlite::Result<Vec<ContentAngle>> {
+    let mut ids = Vec::new();
+    for draft in drafts {
+        conn.execute(
+            "INSERT INTO content_angles (product_id, angle_type, hook, direction, served_by)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![product_id, draft.angle_type, draft.hook, draft.direction, served_by],
+        )?;
+        ids.push(conn.last_insert_rowid());
+    }
+
+    ids.into_iter().map(|id| fetch(conn, id)).collect()
+}
+
+fn fetch(conn: &Connection, id: i64) -> rusqlite::Result<ContentAngle> {
+    conn.query_row(
+        &format!("SELECT {} FROM content_angles WHERE id = ?1", ANGLE_COLUMNS),
+        params![id],
+        angle_from_row,
+    )
+}
+
+pub fn get(conn: &Connection, id: i64) -> rusqlite::Result<Option<ContentAngle>> {
+    fetch(conn, id).optional()
+}
+
+/// Stored angles for a product, newest first
+pub fn list_for_product(conn: &Connection, product_id: i64) -> rusqlite::Result<Vec<ContentAngle>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM content_angles WHERE product_id = ?1 ORDER BY created_at DESC, id DESC",
+        ANGLE_COLUMNS
+    ))?;
+    let angles = stmt
+        .query_map(params![product_id], angle_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(angles)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(angle_type: &str, hook: &str) -> AngleDraft {
+        AngleDraft {
+            angle_type: angle_type.to_string(),
+            hook: hook.to_string(),
+            direction: "Direction".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_distinct_angles_prefers_new_types() {
+        let drafts = vec![
+            draft("social_proof", "Everyone loves it"),
+            draft("social_proof", "5,000 five-star reviews"),
+            draft("comparison", "Everyone Loves It "), // duplicate hook
+            draft("unboxing", "What's in the box"),
+        ];
+        let picked = distinct_angles(drafts, 3);
+        let hooks: Vec<&str> = picked.iter().map(|a| a.hook.as_str()).collect();
+        assert_eq!(hooks, vec!["Everyone loves it", "What's in the box", "5,000 five-star reviews"]);
+    }
+
+    #[test]
+    fn test_validate_angles() {
+        let response = r#"[{"angle_type": "unboxing", "hook": "Open it with me", "direction": "Film it raw"}]"#;
+        assert_eq!(validate_angles(response).unwrap().len(), 1);
+
+        let invalid = r#"[{"angle_type": "meme", "hook": "", "direction": "x"}]"#;
+        assert_eq!(validate_angles(invalid).unwrap_err().errors.len(), 2);
+    }
+
+    #[test]
+    fn test_clamp_count() {
+        assert_eq!(clamp_count(None), DEFAULT_ANGLE_COUNT);
+        assert_eq!(clamp_count(Some(0)), 1);
+        assert_eq!(clamp_count(Some(50)), MAX_ANGLE_COUNT);
+    }
+}
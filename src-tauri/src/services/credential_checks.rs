@@ -0,0 +1,88 @@
+//! Credential Checks
+//!
+//! Links are only worth creating for platforms the user has an account with:
+//! without one the tracking URL carries a placeholder ID (`tag=affilai-20`)
+//! that earns nothing. Link generation checks for an active credential first
+//! and fails with a structured `missing_credential` error the UI can act on,
+//! unless the caller explicitly proceeds anyway.
+
+use crate::models::affiliate_credentials::{CredentialCheck, MissingCredentialError};
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub const MISSING_CREDENTIAL: &str = "missing_credential";
+
+/// The affiliate/creator ID of the platform's active credential. A credential
+/// counts when it is active and has an affiliate or shop ID filled in.
+pub fn active_credential(conn: &Connection, platform: &str) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT COALESCE(NULLIF(TRIM(affiliate_id), ''), TRIM(shop_id)) FROM affiliate_credentials
+         WHERE platform = ?1 COLLATE NOCASE AND active = 1
+           AND (TRIM(COALESCE(affiliate_id, '')) != '' OR TRIM(COALESCE(shop_id, '')) != '')",
+        params![platform],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+pub fn check(conn: &Connection, platform: &str) -> rusqlite::Result<CredentialCheck> {
+    let affiliate_id = active_credential(conn, platform)?;
+    Ok(CredentialCheck {
+        platform: platform.to_lowercase(),
+        ready: affiliate_id.is_some(),
+        message: affiliate_id.is_none().then(|| missing_message(platform)),
+    })
+}
+
+fn missing_message(platform: &str) -> String {
+    format!(
+        "Missing credential for {}. Add your {} affiliate ID under Settings, or generate anyway with a placeholder ID.",
+        platform.to_lowercase(),
+        platform.to_lowercase()
+    )
+}
+
+/// The `missing_credential` error as the JSON string commands return
+pub fn missing_credential_error(platform: &str) -> String {
+    let error = MissingCredentialError {
+        code: MISSING_CREDENTIAL.to_string(),
+        platform: platform.to_lowercase(),
+        message: missing_message(platform),
+    };
+    serde_json::to_string(&error).unwrap_or_else(|_| error.message.clone())
+}
+
+/// Human-readable form of a command error, unwrapping a `missing_credential` error
+pub fn error_message(error: &str) -> String {
+    match serde_json::from_str::<MissingCredentialError>(error) {
+        Ok(parsed) if parsed.code == MISSING_CREDENTIAL => parsed.message,
+        _ => error.to_string(),
+    }
+}
+
+/// Gate for link creation: the platform's affiliate ID, `None` when missing
+/// but `allow_missing`, or a `missing_credential` error
+pub fn require(conn: &Connection, platform: &str, allow_missing: bool) -> Result<Option<String>, String> {
+    match active_credential(conn, platform).map_err(|e| e.to_string())? {
+        Some(affiliate_id) => Ok(Some(affiliate_id)),
+        None if allow_missing => Ok(None),
+        None => Err(missing_credential_error(platform)),
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_credential_error_round_trips() {
+        let error = missing_credential_error("TikTok");
+        let parsed: MissingCredentialError = serde_json::from_str(&error).unwrap();
+        assert_eq!((parsed.code.as_str(), parsed.platform.as_str()), (MISSING_CREDENTIAL, "tiktok"));
+        assert!(error_message(&error).starts_with("Missing credential for tiktok."));
+        assert_eq!(error_message("Product not found"), "Product not found");
+    }
+}
@@ -0,0 +1,380 @@
+//! Ad Copy Critique
+//!
+//! Scores a generated ad against a five-point rubric (hook strength, clarity,
+//! CTA, specificity, compliance), each 1-10, with concrete suggestions for
+//! the weak criteria. The AI provider scores when one is configured; the
+//! heuristic scorer below is used with the mock provider and as the last
+//! fallback. Critiques are stored in `ad_critiques` so weak copy can be
+//! filtered out or regenerated.
+
+use crate::commands::ad_generation::GeneratedAdCopy;
+use crate::models::ai_discovery::ParseDiagnostic;
+use crate::models::critique::{AdCritique, CritiqueDraft, RubricScores};
+use crate::models::niche_profile::NicheProfile;
+use crate::services::ai_validation::validate_object;
+use crate::services::niche;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+/// Ads whose overall score is below this are considered weak by default
+pub const DEFAULT_WEAK_THRESHOLD: f64 = 6.0;
+
+pub const CRITIQUE_PROMPT: &str = r#"You are a direct-response copy chief reviewing an affiliate ad. Score it from 1 (weak) to 10 (strong) on each criterion.
+
+Ad:
+- Type: {ad_type}
+- Product: {product_name}
+- Headline: {headline}
+- Body: {body}
+- CTA: {cta}
+
+Niche Profile:
+{niche_context}
+
+Criteria:
+- hook_strength: would the headline stop the scroll?
+- clarity: is the message easy to follow in one read?
+- cta: is there a clear, specific next step?
+- specificity: concrete details, numbers, and benefits instead of hype
+- compliance: no banned or unverifiable claims, affiliate disclosure where needed
+
+Return ONLY a valid JSON object with this structure:
+{
+  "hook_strength": 1-10,
+  "clarity": 1-10,
+  "cta": 1-10,
+  "specificity": 1-10,
+  "compliance": 1-10,
+  "suggestions": ["One concrete fix per weak criterion"]
+}
+
+NO explanatory text, ONLY the JSON object"#;
+
+/// Schema for the object requested by `CRITIQUE_PROMPT`
+pub const CRITIQUE_SCHEMA: &str = r##"{
+  "type": "object",
+  "required": ["hook_strength", "clarity", "cta", "specificity", "compliance", "suggestions"],
+  "properties": {
+    "hook_strength": { "$ref": "#/$defs/score" },
+    "clarity": { "$ref": "#/$defs/score" },
+    "cta": { "$ref": "#/$defs/score" },
+    "specificity": { "$ref": "#/$defs/score" },
+    "compliance": { "$ref": "#/$defs/score" },
+    "suggestions": { "type": "array", "items": { "type": "string" } }
+  },
+  "$defs": {
+    "score": { "type": "integer", "minimum": 1, "maximum": 10 }
+  }
+}"##;
+
+pub const CRITIQUE_COLUMNS: &str = "id, ad_id, hook_strength, clarity, cta, specificity, compliance,
+     overall, suggestions, served_by, created_at";
+
+/// Ad types published on social feeds, where an affiliate disclosure is expected
+const DISCLOSED_AD_TYPES: [&str; 5] = ["social_post", "story", "video_script", "carousel", "pinterest_pin"];
+const DISCLOSURE_MARKERS: [&str; 6] = ["#ad", "#affiliate", "affiliate", "commission", "sponsored", "paid partnership"];
+const RISKY_CLAIMS: [&str; 7] = [
+    "guaranteed",
+    "cure",
+    "miracle",
+    "risk-free",
+    "100%",
+    "clinically proven",
+    "no side effects",
+];
+const VAGUE_WORDS: [&str; 7] = ["amazing", "best", "game-changer", "everyone", "incredible", "must-have", "breaking the internet"];
+const ACTION_WORDS: [&str; 11] = ["shop", "get", "buy", "try", "grab", "swipe", "tap", "click", "order", "link in bio", "[link]"];
+
+/// Maps a row selected with `CRITIQUE_COLUMNS` into an `AdCritique`
+pub fn critique_from_row(row: &Row) -> rusqlite::Result<AdCritique> {
+    let suggestions: Option<String> = row.get(8)?;
+    Ok(AdCritique {
+        id: row.get(0)?,
+        ad_id: row.get(1)?,
+        scores: RubricScores {
+            hook_strength: row.get(2)?,
+            clarity: row.get(3)?,
+            cta: row.get(4)?,
+            specificity: row.get(5)?,
+            compliance: row.get(6)?,
+        },
+        overall: row.get(7)?,
+        suggestions: suggestions
+            .and_then(|j| serde_json::from_str(&j).ok())
+            .unwrap_or_default(),
+        served_by: row.get(9)?,
+        created_at: row.get(10)?,
+    })
+}
+
+pub fn build_critique_prompt(ad: &GeneratedAdCopy, product_name: &str, niche_context: &str) -> String {
+    CRITIQUE_PROMPT
+        .replace("{ad_type}", ad.ad_type.as_deref().unwrap_or("social_post"))
+        .replace("{product_name}", product_name)
+        .replace("{headline}", &ad.headline)
+        .replace("{body}", ad.body_text.as_deref().unwrap_or(""))
+        .replace("{cta}", ad.cta.as_deref().unwrap_or(""))
+        .replace("{niche_context}", niche_context)
+}
+
+/// Extracts and schema-validates a critique response
+pub fn validate_critique(response: &str) -> Result<CritiqueDraft, ParseDiagnostic> {
+    validate_object(response, CRITIQUE_SCHEMA)
+}
+
+/// Mean of the five criteria, rounded to one decimal
+pub fn overall(scores: &RubricScores) -> f64 {
+    let total = scores.hook_strength + scores.clarity + scores.cta + scores.specificity + scores.compliance;
+    (total as f64 / 5.0 * 10.0).round() / 10.0
+}
+
+/// Rule-based critique used with the mock provider and as the last fallback
+pub fn heuristic_critique(
+    ad: &GeneratedAdCopy,
+    product_name: &str,
+    profile: Option<&NicheProfile>,
+) -> CritiqueDraft {
+    let headline = ad.headline.trim();
+    let body = ad.body_text.as_deref().unwrap_or("").trim();
+    let cta = ad.cta.as_deref().unwrap_or("").trim();
+    let ad_type = ad.ad_type.as_deref().unwrap_or("");
+    let all_text = format!("{}\n{}\n{}", headline, body, cta);
+    let lower = all_text.to_lowercase();
+    let mut suggestions = Vec::new();
+
+    // Hook: punctuation, numbers, direct address, and a scannable length
+    let headline_lower = headline.to_lowercase();
+    let headline_len = headline.chars().count();
+    let mut hook = 4;
+    if headline.contains('?') || headline.contains('!') {
+        hook += 2;
+    }
+    if headline.chars().any(|c| c.is_ascii_digit()) {
+        hook += 1;
+    }
+    if headline_lower.split_whitespace().any(|w| w.starts_with("you")) {
+        hook += 1;
+    }
+    if (20..=70).contains(&headline_len) {
+        hook += 1;
+    } else if !(10..=100).contains(&headline_len) {
+        hook -= 2;
+    }
+    if ["stop", "secret", "finally", "why", "how", "pov", "new"]
+        .iter()
+        .any(|w| headline_lower.split(|c: char| !c.is_alphanumeric()).any(|word| word == *w))
+    {
+        hook += 1;
+    }
+    if hook < 6 {
+        suggestions.push("Open with a question, a number, or a direct \"you\" to stop the scroll".to_string());
+    }
+
+    // Clarity: average sentence length
+    let sentences: Vec<&str> = body
+        .split(['.', '!', '?', '\n'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    let words = body.split_whitespace().count();
+    let avg_words = if sentences.is_empty() { 0 } else { words / sentences.len() };
+    let mut clarity = match avg_words {
+        0 => 3,
+        1..=15 => 9,
+        16..=20 => 7,
+        21..=28 => 5,
+        _ => 3,
+    };
+    if body.chars().count() > 1200 && ad_type != "email" {
+        clarity -= 1;
+    }
+    if clarity < 6 {
+        suggestions.push("Break long sentences up; aim for under 20 words each".to_string());
+    }
+
+    // CTA: a stated next step with an action verb
+    let mut cta_score = if cta.is_empty() { 2 } else { 5 };
+    if ACTION_WORDS.iter().any(|w| lower.contains(w)) {
+        cta_score += 3;
+    }
+    if ["today", "now", "before", "limited", "last chance"].iter().any(|w| lower.contains(w)) {
+        cta_score += 1;
+    }
+    if cta_score < 6 {
+        suggestions.push("End with one clear action (e.g. \"Shop now\" with the link)".to_string());
+    }
+
+    // Specificity: numbers and the product name beat hype words
+    let mut specificity = 4;
+    if body.chars().any(|c| c.is_ascii_digit()) {
+        specificity += 2;
+    }
+    if body.to_lowercase().contains(&product_name.to_lowercase()) {
+        specificity += 2;
+    }
+    if body.contains('%') || words > 40 {
+        specificity += 1;
+    }
+    let vague = VAGUE_WORDS.iter().filter(|w| lower.contains(*w)).count() as i32;
+    specificity -= vague.min(3);
+    if specificity < 6 {
+        suggestions.push("Swap hype words for concrete details: results, numbers, ingredients, sizes".to_string());
+    }
+
+    // Compliance: banned and risky claims, disclosure, SMS opt-out
+    let mut compliance = 10;
+    if niche::contains_banned_claim(&all_text, profile) {
+        compliance -= 5;
+        suggestions.push("Remove claims the niche profile bans".to_string());
+    }
+    let risky: Vec<&str> = RISKY_CLAIMS.iter().copied().filter(|c| lower.contains(c)).collect();
+    if !risky.is_empty() {
+        compliance -= 2 * risky.len().min(3) as i32;
+        suggestions.push(format!("Avoid unverifiable claims: {}", risky.join(", ")));
+    }
+    if DISCLOSED_AD_TYPES.contains(&ad_type) && !DISCLOSURE_MARKERS.iter().any(|m| lower.contains(m)) {
+        compliance -= 2;
+        suggestions.push("Add an affiliate disclosure such as #ad".to_string());
+    }
+    if ad_type == "sms" && !lower.contains("stop") {
+        compliance -= 3;
+        suggestions.push("SMS must tell recipients how to opt out (Reply STOP)".to_string());
+    }
+
+    CritiqueDraft {
+        scores: RubricScores {
+            hook_strength: hook.clamp(1, 10),
+            clarity: clarity.clamp(1, 10),
+            cta: cta_score.clamp(1, 10),
+            specificity: specificity.clamp(1, 10),
+            compliance: compliance.clamp(1, 10),
+        },
+        suggestions,
+    }
+}
+
+/// Stores a critique for an ad and returns the saved record
+pub fn save(conn: &Connection, ad_id: i64, draft: &CritiqueDraft, served_by: &str) -> rusqlite::Result<AdCritique> {
+    let scores = &draft.scores;
+    conn.execute(
+        "INSERT INTO ad_critiques
+         (ad_id, hook_strength, clarity, cta, specificity, compliance, overall, suggestions, served_by)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            ad_id,
+            scores.hook_strength,
+            scores.clarity,
+            scores.cta,
+            scores.specificity,
+            scores.compliance,
+            overall(scores),
+            serde_json::to_string(&draft.suggestions).unwrap_or_else(|_| "[]".to_string()),
+            served_by,
+        ],
+    )?;
+
+    conn.query_row(
+        &format!("SELECT {} FROM ad_critiques WHERE id = ?1", CRITIQUE_COLUMNS),
+        params![conn.last_insert_rowid()],
+        critique_from_row,
+    )
+}
+
+/// The most recent critique for an ad, if it has been scored
+pub fn latest_for_ad(conn: &Connection, ad_id: i64) -> rusqlite::Result<Option<AdCritique>> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM ad_critiques WHERE ad_id = ?1 ORDER BY created_at DESC, id DESC LIMIT 1",
+            CRITIQUE_COLUMNS
+        ),
+        params![ad_id],
+        critique_from_row,
+    )
+    .optional()
+}
+
+/// Latest critiques scoring below `threshold`, weakest first
+pub fn list_weak(
+    conn: &Connection,
+    threshold: f64,
+    product_id: Option<i64>,
+) -> rusqlite::Result<Vec<AdCritique>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM ad_critiques c
+         WHERE c.id = (SELECT MAX(id) FROM ad_critiques WHERE ad_id = c.ad_id)
+           AND c.overall < ?1
+           AND c.ad_id IN (SELECT id FROM ad_copies WHERE ?2 IS NULL OR product_id = ?2)
+         ORDER BY c.overall ASC",
+        CRITIQUE_COLUMNS
+    ))?;
+    let critiques = stmt
+        .query_map(params![threshold, product_id], critique_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(critiques)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ad(ad_type: &str, headline: &str, body: &str, cta: &str) -> GeneratedAdCopy {
+        GeneratedAdCopy {
+            id: Some(1),
+            product_id: Some(1),
+            campaign_id: 1,
+            variation_name: None,
+            headline: headline.to_string(),
+            body_text: Some(body.to_string()),
+            cta: Some(cta.to_string()),
+            ad_format: Some(ad_type.to_string()),
+            ad_type: Some(ad_type.to_string()),
+            platform_specific_data: None,
+            performance_score: None,
+            created_at: None,
+            updated_at: None,
+            is_favorite: false,
+            parent_ad_id: None,
+            sequence_step: None,
+            send_delay_days: None,
+            alt_text: None,
+            link_id: None,
+            approval_state: "draft".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_heuristic_prefers_specific_disclosed_copy() {
+        let strong = heuristic_critique(
+            &ad(
+                "social_post",
+                "Why do 9 of 10 of you skip sunscreen?",
+                "Glow SPF 50 blends clear in 10 seconds. No white cast. Shop Glow SPF today. #ad",
+                "Shop Now",
+            ),
+            "Glow SPF",
+            None,
+        );
+        let weak = heuristic_critique(
+            &ad("social_post", "Wow", "The best, most amazing miracle cream. Guaranteed results", ""),
+            "Glow SPF",
+            None,
+        );
+
+        assert!(overall(&strong.scores) > overall(&weak.scores));
+        assert_eq!(strong.scores.compliance, 10);
+        assert!(weak.scores.compliance <= 4);
+        assert!(weak.suggestions.iter().any(|s| s.contains("disclosure")));
+    }
+
+    #[test]
+    fn test_validate_critique_rejects_out_of_range_scores() {
+        let response = r#"{"hook_strength": 7, "clarity": 8, "cta": 6, "specificity": 5, "compliance": 10, "suggestions": []}"#;
+        assert_eq!(validate_critique(response).unwrap().scores.cta, 6);
+
+        let invalid = r#"{"hook_strength": 0, "clarity": 8, "cta": 6, "specificity": 5, "compliance": 11, "suggestions": []}"#;
+        assert_eq!(validate_critique(invalid).unwrap_err().errors.len(), 2);
+    }
+}
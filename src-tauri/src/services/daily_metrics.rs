@@ -0,0 +1,369 @@
+//! Daily Metrics
+//!
+//! Materializes per-link, per-day clicks and conversions into
+//! `daily_metrics` so dashboards and digests don't scan the raw event tables.
+//! A nightly job fills every complete day up to yesterday and records how far
+//! it got; readers take materialized rows up to that day and aggregate the
+//! raw tables only for the days after it (normally just today). Clicks that
+//! retention already folded into `click_rollups` are read from there.
+
+use crate::database::get_connection;
+use crate::database::settings::{get_setting, set_setting};
+use crate::models::daily_metrics::{DailyMetric, DailyMetricsStatus, ProductMetrics};
+use chrono::{Duration, NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use tauri::AppHandle;
+
+/// Last day (YYYY-MM-DD) whose rows are in `daily_metrics`
+const THROUGH_SETTING: &str = "daily_metrics_through";
+/// Days before the last materialized one that each run recomputes, for
+/// conversions that arrive a little late
+const RECOMPUTE_DAYS: i64 = 2;
+
+/// Per (day, link) aggregates of the raw tables for days ?1..=?2
+const RAW_METRICS: &str = "
+    SELECT m.day, m.link_id, l.product_id,
+           SUM(m.clicks) AS clicks, SUM(m.bot_clicks) AS bot_clicks, SUM(m.unique_visitors) AS unique_visitors,
+           SUM(m.conversions) AS conversions, SUM(m.revenue) AS revenue, SUM(m.commission) AS commission
+    FROM (
+        SELECT date(clicked_at) AS day, link_id,
+               SUM(bot_reason IS NULL) AS clicks, SUM(bot_reason IS NOT NULL) AS bot_clicks,
+               COUNT(DISTINCT ip_hash) AS unique_visitors,
+               0 AS conversions, 0.0 AS revenue, 0.0 AS commission
+        FROM click_events
+        WHERE clicked_at >= ?1 AND clicked_at < date(?2, '+1 day')
+        GROUP BY date(clicked_at), link_id
+        UNION ALL
+        SELECT day, link_id, clicks, bot_clicks, unique_visitors, 0, 0.0, 0.0
+        FROM click_rollups WHERE day BETWEEN ?1 AND ?2
+        UNION ALL
+        SELECT date(converted_at), link_id, 0, 0, 0,
+               COUNT(*), COALESCE(SUM(order_value), 0), COALESCE(SUM(commission), 0)
+        FROM conversion_events
+        WHERE converted_at >= ?1 AND converted_at < date(?2, '+1 day') AND status != 'rejected'
+        GROUP BY date(converted_at), link_id
+    ) m
+    LEFT JOIN affiliate_links l ON l.id = m.link_id
+    GROUP BY m.day, m.link_id";
+
+fn day_string(day: NaiveDate) -> String {
+    day.format("%Y-%m-%d").to_string()
+}
+
+pub fn materialized_through(conn: &Connection) -> Option<NaiveDate> {
+    get_setting(conn, THROUGH_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok())
+}
+
+/// Recomputes the rows for `from..=to`
+pub fn materialize(conn: &Connection, from: NaiveDate, to: NaiveDate) -> rusqlite::Result<usize> {
+    let (from, to) = (day_string(from), day_string(to));
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM daily_metrics WHERE day BETWEEN ?1 AND ?2", params![from, to])?;
+    let rows = tx.execute(
+        &format!(
+            "INSERT INTO daily_metrics (day, link_id, product_id, clicks, bot_clicks, unique_visitors,
+             conversions, revenue, commission) {}",
+            RAW_METRICS
+        ),
+        params![from, to],
+    )?;
+    tx.commit()?;
+    Ok(rows)
+}
+
+fn earliest_event_day(conn: &Connection) -> rusqlite::Result<Option<NaiveDate>> {
+    let day: Option<String> = conn.query_row(
+        "SELECT MIN(day) FROM (
+            SELECT MIN(date(clicked_at)) AS day FROM click_events
+            UNION ALL SELECT MIN(day) FROM click_rollups
+            UNION ALL SELECT MIN(date(converted_at)) FROM conversion_events
+         )",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(day.and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()))
+}
+
+/// Brings the table up to yesterday. The first run backfills all history.
+pub fn refresh(conn: &Connection, today: NaiveDate) -> rusqlite::Result<usize> {
+    let yesterday = today - Duration::days(1);
+    let from = match materialized_through(conn) {
+        Some(through) => through - Duration::days(RECOMPUTE_DAYS),
+        None => match earliest_event_day(conn)? {
+            Some(day) => day,
+            None => yesterday,
+        },
+    };
+    let rows = if from <= yesterday { materialize(conn, from, yesterday)? } else { 0 };
+    set_setting(conn, THROUGH_SETTING, &day_string(yesterday))?;
+    Ok(rows)
+}
+
+/// Drops everything and backfills from scratch
+pub fn rebuild(conn: &Connection, today: NaiveDate) -> rusqlite::Result<usize> {
+    conn.execute("DELETE FROM daily_metrics", [])?;
+    set_setting(conn, THROUGH_SETTING, "")?;
+    refresh(conn, today)
+}
+
+/// Call after back-dated events are written so the next refresh recomputes
+/// from `day` on; until then readers aggregate those days from the raw tables.
+pub fn mark_stale(conn: &Connection, day: NaiveDate) -> rusqlite::Result<()> {
+    if let Some(through) = materialized_through(conn) {
+        if day <= through {
+            set_setting(conn, THROUGH_SETTING, &day_string(day - Duration::days(1)))?;
+        }
+    }
+    Ok(())
+}
+
+/// Inclusive `(first, last)` days
+type DayRange = (NaiveDate, NaiveDate);
+
+/// Splits `start..=end` into the materialized part and the part read live
+fn split_range(
+    through: Option<NaiveDate>,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> (Option<DayRange>, Option<DayRange>) {
+    let Some(through) = through else {
+        return (None, Some((start, end)));
+    };
+    let stored = (start <= through).then(|| (start, end.min(through)));
+    let live_start = start.max(through + Duration::days(1));
+    let live = (live_start <= end).then_some((live_start, end));
+    (stored, live)
+}
+
+/// Rows for `from..=to` (?1, ?2) from the table or, for live days, the raw events
+fn metrics_source(stored: bool) -> String {
+    if stored {
+        "(SELECT * FROM daily_metrics WHERE day BETWEEN ?1 AND ?2)".to_string()
+    } else {
+        format!("({})", RAW_METRICS)
+    }
+}
+
+fn metric_from_row(row: &rusqlite::Row) -> rusqlite::Result<DailyMetric> {
+    Ok(DailyMetric {
+        day: row.get(0)?,
+        clicks: row.get(1)?,
+        bot_clicks: row.get(2)?,
+        unique_visitors: row.get(3)?,
+        conversions: row.get(4)?,
+        revenue: row.get(5)?,
+        commission: row.get(6)?,
+    })
+}
+
+/// One row per day with activity, optionally for a single product or link
+pub fn series(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    product_id: Option<i64>,
+    link_id: Option<i64>,
+) -> rusqlite::Result<Vec<DailyMetric>> {
+    let (stored, live) = split_range(materialized_through(conn), start, end);
+    let mut days = Vec::new();
+    for (is_stored, range) in [(true, stored), (false, live)] {
+        let Some((from, to)) = range else { continue };
+        let mut stmt = conn.prepare(&format!(
+            "SELECT day, SUM(clicks), SUM(bot_clicks), SUM(unique_visitors),
+                    SUM(conversions), SUM(revenue), SUM(commission)
+             FROM {} m
+             WHERE (?3 IS NULL OR m.product_id = ?3) AND (?4 IS NULL OR m.link_id = ?4)
+             GROUP BY day ORDER BY day",
+            metrics_source(is_stored)
+        ))?;
+        let rows = stmt
+            .query_map(params![day_string(from), day_string(to), product_id, link_id], metric_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        days.extend(rows);
+    }
+    Ok(days)
+}
+
+/// Sum of `series` over the range, with `day` set to the range's end
+pub fn totals(conn: &Connection, start: NaiveDate, end: NaiveDate) -> rusqlite::Result<DailyMetric> {
+    let mut total = DailyMetric { day: day_string(end), ..Default::default() };
+    for day in series(conn, start, end, None, None)? {
+        total.clicks += day.clicks;
+        total.bot_clicks += day.bot_clicks;
+        total.unique_visitors += day.unique_visitors;
+        total.conversions += day.conversions;
+        total.revenue += day.revenue;
+        total.commission += day.commission;
+    }
+    Ok(total)
+}
+
+/// Products ranked by commission, then clicks, over the range
+pub fn top_products(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    limit: usize,
+) -> rusqlite::Result<Vec<ProductMetrics>> {
+    let (stored, live) = split_range(materialized_through(conn), start, end);
+    let mut by_product: std::collections::HashMap<i64, (i64, f64)> = std::collections::HashMap::new();
+    for (is_stored, range) in [(true, stored), (false, live)] {
+        let Some((from, to)) = range else { continue };
+        let mut stmt = conn.prepare(&format!(
+            "SELECT product_id, SUM(clicks), SUM(commission) FROM {} m
+             WHERE product_id IS NOT NULL GROUP BY product_id",
+            metrics_source(is_stored)
+        ))?;
+        let rows = stmt.query_map(params![day_string(from), day_string(to)], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, f64>(2)?))
+        })?;
+        for row in rows {
+            let (product_id, clicks, commission) = row?;
+            let entry = by_product.entry(product_id).or_default();
+            entry.0 += clicks;
+            entry.1 += commission;
+        }
+    }
+
+    let mut products = Vec::new();
+    for (product_id, (clicks, commission)) in by_product {
+        if clicks == 0 && commission == 0.0 {
+            continue;
+        }
+        let name: Option<String> = conn
+            .query_row("SELECT name FROM products WHERE id = ?1", params![product_id], |row| row.get(0))
+            .optional()?;
+        if let Some(product_name) = name {
+            products.push(ProductMetrics { product_id, product_name, clicks, commission });
+        }
+    }
+    products.sort_by(|a, b| b.commission.total_cmp(&a.commission).then(b.clicks.cmp(&a.clicks)));
+    products.truncate(limit);
+    Ok(products)
+}
+
+/// Clicks, conversions, and commission of one product in one calendar month
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProductMonth {
+    pub product_id: i64,
+    pub month: String, // YYYY-MM
+    pub clicks: i64,
+    pub conversions: i64,
+    pub commission: f64,
+}
+
+/// Per product and month totals over the range, for products with activity
+pub fn product_months(conn: &Connection, start: NaiveDate, end: NaiveDate) -> rusqlite::Result<Vec<ProductMonth>> {
+    let (stored, live) = split_range(materialized_through(conn), start, end);
+    let mut by_month: std::collections::BTreeMap<(i64, String), ProductMonth> = std::collections::BTreeMap::new();
+    for (is_stored, range) in [(true, stored), (false, live)] {
+        let Some((from, to)) = range else { continue };
+        let mut stmt = conn.prepare(&format!(
+            "SELECT product_id, substr(day, 1, 7), SUM(clicks), SUM(conversions), SUM(commission) FROM {} m
+             WHERE product_id IS NOT NULL GROUP BY product_id, substr(day, 1, 7)",
+            metrics_source(is_stored)
+        ))?;
+        let rows = stmt.query_map(params![day_string(from), day_string(to)], |row| {
+            Ok(ProductMonth {
+                product_id: row.get(0)?,
+                month: row.get(1)?,
+                clicks: row.get(2)?,
+                conversions: row.get(3)?,
+                commission: row.get(4)?,
+            })
+        })?;
+        for row in rows {
+            let row = row?;
+            let entry = by_month.entry((row.product_id, row.month.clone())).or_insert_with(|| ProductMonth {
+                product_id: row.product_id,
+                month: row.month.clone(),
+                ..Default::default()
+            });
+            entry.clicks += row.clicks;
+            entry.conversions += row.conversions;
+            entry.commission += row.commission;
+        }
+    }
+    Ok(by_month.into_values().collect())
+}
+
+pub fn status(conn: &Connection) -> rusqlite::Result<DailyMetricsStatus> {
+    let rows = conn.query_row("SELECT COUNT(*) FROM daily_metrics", [], |row| row.get(0))?;
+    Ok(DailyMetricsStatus { through: materialized_through(conn).map(day_string), rows })
+}
+
+/// Scheduler entry point
+pub fn run_rollup_job(app_handle: &AppHandle) -> Result<String, String> {
+    let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    let rows = refresh(&conn, Utc::now().date_naive()).map_err(|e| e.to_string())?;
+    Ok(format!("{} daily metric row(s) materialized", rows))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_combine_materialized_and_live_days() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE settings (key TEXT PRIMARY KEY, value TEXT, updated_at DATETIME);
+             CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE affiliate_links (id INTEGER PRIMARY KEY, product_id INTEGER);
+             CREATE TABLE click_events (id INTEGER PRIMARY KEY, link_id INTEGER, clicked_at TEXT,
+                ip_hash TEXT, bot_reason TEXT);
+             CREATE TABLE conversion_events (id INTEGER PRIMARY KEY, link_id INTEGER, converted_at TEXT,
+                order_value REAL, commission REAL, status TEXT);
+             INSERT INTO products VALUES (1, 'Lamp'), (2, 'Mug');
+             INSERT INTO affiliate_links VALUES (10, 1), (20, 2);
+             INSERT INTO click_events (link_id, clicked_at, ip_hash, bot_reason) VALUES
+                (10, '2026-03-01 09:00:00', 'a', NULL),
+                (10, '2026-03-01 10:00:00', 'b', 'crawler'),
+                (20, '2026-03-02 11:00:00', 'c', NULL),
+                (20, '2026-03-03 12:00:00', 'd', NULL);
+             INSERT INTO conversion_events (link_id, converted_at, order_value, commission, status) VALUES
+                (10, '2026-03-02 13:00:00', 40.0, 4.0, 'approved'),
+                (20, '2026-03-02 14:00:00', 10.0, 1.0, 'rejected');",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../../migrations/041_data_retention.sql")).unwrap();
+        conn.execute_batch(include_str!("../../../migrations/042_daily_metrics.sql")).unwrap();
+        conn.execute("INSERT INTO click_rollups VALUES ('2026-02-01', 10, 6, 0, 3)", []).unwrap();
+
+        let day = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        // Today is Mar 3: Feb 1 (from rollups) through Mar 2 are materialized
+        refresh(&conn, day("2026-03-03")).unwrap();
+        assert_eq!(materialized_through(&conn), Some(day("2026-03-02")));
+        let stored: i64 = conn.query_row("SELECT COUNT(*) FROM daily_metrics", [], |r| r.get(0)).unwrap();
+        assert_eq!(stored, 4);
+
+        let total = totals(&conn, day("2026-02-01"), day("2026-03-03")).unwrap();
+        assert_eq!((total.clicks, total.bot_clicks, total.conversions), (9, 1, 1));
+        assert_eq!(total.commission, 4.0);
+
+        let lamp = series(&conn, day("2026-03-01"), day("2026-03-03"), Some(1), None).unwrap();
+        assert_eq!(lamp.iter().map(|d| d.day.as_str()).collect::<Vec<_>>(), ["2026-03-01", "2026-03-02"]);
+
+        let top = top_products(&conn, day("2026-03-01"), day("2026-03-03"), 5).unwrap();
+        assert_eq!(top[0].product_name, "Lamp");
+        assert_eq!((top[1].product_name.as_str(), top[1].clicks), ("Mug", 2));
+
+        let months = product_months(&conn, day("2026-02-01"), day("2026-03-03")).unwrap();
+        let lamp: Vec<(&str, i64, f64)> =
+            months.iter().filter(|m| m.product_id == 1).map(|m| (m.month.as_str(), m.clicks, m.commission)).collect();
+        assert_eq!(lamp, [("2026-02", 6, 0.0), ("2026-03", 1, 4.0)]);
+        // Mar 2 is materialized and Mar 3 live; the month sums both
+        assert_eq!(months.iter().find(|m| m.product_id == 2).unwrap().clicks, 2);
+
+        // Back-dated events push the boundary back so they're read live
+        mark_stale(&conn, day("2026-03-01")).unwrap();
+        assert_eq!(materialized_through(&conn), Some(day("2026-02-28")));
+    }
+}
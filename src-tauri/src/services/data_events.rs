@@ -0,0 +1,55 @@
+//! Data Change Events
+//!
+//! Mutating commands announce which rows they touched so every open view can
+//! refresh itself instead of polling list commands. Events are emitted after
+//! the write succeeds; a failed emit is logged and never fails the command.
+
+use crate::models::data_event::DataChanged;
+use tauri::{AppHandle, Emitter};
+
+pub const PRODUCTS_CHANGED: &str = "products://changed";
+pub const LINKS_CHANGED: &str = "links://changed";
+pub const ADS_CHANGED: &str = "ads://changed";
+
+pub const CREATED: &str = "created";
+pub const UPDATED: &str = "updated";
+pub const DELETED: &str = "deleted";
+
+/// The change event for rows of `table`, if views listen for it
+pub fn event_for_table(table: &str) -> Option<&'static str> {
+    match table {
+        "products" => Some(PRODUCTS_CHANGED),
+        "affiliate_links" => Some(LINKS_CHANGED),
+        "ad_copies" => Some(ADS_CHANGED),
+        _ => None,
+    }
+}
+
+pub fn emit(app_handle: &AppHandle, event: &str, action: &str, ids: Vec<i64>) {
+    if ids.is_empty() {
+        return;
+    }
+    let payload = DataChanged {
+        action: action.to_string(),
+        ids,
+    };
+    if let Err(e) = app_handle.emit(event, &payload) {
+        eprintln!("Failed to emit {}: {}", event, e);
+    }
+}
+
+/// Emits `deleted` events for `(table, id)` pairs, one event per table
+pub fn emit_deleted_rows<'a>(app_handle: &AppHandle, rows: impl IntoIterator<Item = (&'a str, i64)>) {
+    let mut by_event: Vec<(&'static str, Vec<i64>)> = Vec::new();
+    for (table, id) in rows {
+        if let Some(event) = event_for_table(table) {
+            match by_event.iter_mut().find(|(e, _)| *e == event) {
+                Some((_, ids)) => ids.push(id),
+                None => by_event.push((event, vec![id])),
+            }
+        }
+    }
+    for (event, ids) in by_event {
+        emit(app_handle, event, DELETED, ids);
+    }
+}
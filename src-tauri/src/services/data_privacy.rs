@@ -0,0 +1,208 @@
+//! Data Export and Wipe
+//!
+//! Lets a user take out or erase everything the app stores about them. The
+//! export is a JSON dump of every table; the wipe empties every table,
+//! deletes attachments and media files the app keeps, and compacts
+//! the database so deleted rows don't linger in free pages. Credentials live
+//! in `affiliate_credentials` rather than the OS keychain, so both cover them.
+
+use crate::database::schema;
+use crate::models::data_privacy::{DataExportSummary, DataWipeSummary};
+use crate::services::attachments;
+use rusqlite::types::ValueRef;
+use rusqlite::Connection;
+use serde_json::{json, Map, Value};
+use std::path::Path;
+
+/// Typed exactly to confirm `wipe_all_data`
+pub const WIPE_CONFIRM_PHRASE: &str = "DELETE ALL MY DATA";
+
+/// Every table the app created, in name order. The notes search index and its
+/// FTS5 shadow tables are left out: they're derived from `notes`, and writing
+/// to the shadow tables directly corrupts the index.
+pub fn user_tables(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM sqlite_master
+         WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name NOT LIKE 'notes_fts%'
+         ORDER BY name",
+    )?;
+    let tables = stmt.query_map([], |row| row.get::<_, String>(0))?.collect();
+    tables
+}
+
+/// A column value as JSON; blobs become `{"hex": "..."}`
+fn value_to_json(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => json!(i),
+        ValueRef::Real(f) => json!(f),
+        ValueRef::Text(t) => Value::String(String::from_utf8_lossy(t).into_owned()),
+        ValueRef::Blob(b) => json!({ "hex": hex::encode(b) }),
+    }
+}
+
+fn table_rows(conn: &Connection, table: &str) -> rusqlite::Result<Vec<Value>> {
+    let mut stmt = conn.prepare(&format!("SELECT * FROM \"{}\"", table))?;
+    let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    let rows = stmt
+        .query_map([], |row| {
+            let mut object = Map::new();
+            for (i, column) in columns.iter().enumerate() {
+                object.insert(column.clone(), value_to_json(row.get_ref(i)?));
+            }
+            Ok(Value::Object(object))
+        })?
+        .collect();
+    rows
+}
+
+/// `{ exportedAt, tables: { name: [row, ...] } }` for every table
+pub fn export(conn: &Connection) -> rusqlite::Result<(Value, i64)> {
+    let mut tables = Map::new();
+    let mut row_count = 0;
+    for table in user_tables(conn)? {
+        let rows = table_rows(conn, &table)?;
+        row_count += rows.len() as i64;
+        tables.insert(table, Value::Array(rows));
+    }
+    let dump = json!({
+        "exportedAt": chrono::Utc::now().to_rfc3339(),
+        "tables": Value::Object(tables),
+    });
+    Ok((dump, row_count))
+}
+
+pub fn export_to_file(conn: &Connection, path: &Path) -> Result<DataExportSummary, String> {
+    let (dump, rows) = export(conn).map_err(|e| e.to_string())?;
+    let tables = dump["tables"].as_object().map_or(0, |t| t.len() as i64);
+    let json = serde_json::to_string_pretty(&dump).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())?;
+    Ok(DataExportSummary { path: path.to_string_lossy().into_owned(), tables, rows })
+}
+
+pub fn check_phrase(confirm_phrase: &str) -> Result<(), String> {
+    if confirm_phrase.trim() != WIPE_CONFIRM_PHRASE {
+        return Err(format!("Type \"{}\" to confirm erasing all data", WIPE_CONFIRM_PHRASE));
+    }
+    Ok(())
+}
+
+/// Media paths recorded on creative assets that sit under the app's data
+/// directory; files elsewhere belong to the user and are left alone
+fn owned_media(conn: &Connection, app_dir: &Path) -> rusqlite::Result<Vec<std::path::PathBuf>> {
+    let mut stmt = conn.prepare(
+        "SELECT file_path FROM creative_assets WHERE file_path IS NOT NULL
+         UNION SELECT thumbnail_path FROM creative_assets WHERE thumbnail_path IS NOT NULL",
+    )?;
+    let paths = stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<Result<Vec<_>, _>>()?;
+    Ok(paths
+        .into_iter()
+        .map(std::path::PathBuf::from)
+        .filter(|p| p.starts_with(app_dir))
+        .collect())
+}
+
+/// Deletes every row of every table in one transaction, returning the tables
+/// and the number of rows deleted
+fn clear_tables(conn: &mut Connection) -> Result<(Vec<String>, i64), String> {
+    let tables = user_tables(conn).map_err(|e| e.to_string())?;
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute_batch("PRAGMA defer_foreign_keys = ON;").map_err(|e| e.to_string())?;
+    let mut rows_deleted = 0;
+    for table in &tables {
+        rows_deleted += tx.execute(&format!("DELETE FROM \"{}\"", table), []).map_err(|e| e.to_string())? as i64;
+    }
+    // The notes search index is reset through FTS5 itself
+    if tables.iter().any(|t| t == "notes") {
+        tx.execute("INSERT INTO notes_fts (notes_fts) VALUES ('delete-all')", []).map_err(|e| e.to_string())?;
+    }
+    // sqlite_sequence only exists once an AUTOINCREMENT table has had a row
+    let _ = tx.execute("DELETE FROM sqlite_sequence", []);
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok((tables, rows_deleted))
+}
+
+/// Empties every table and restores the state of a fresh install: migrations
+/// re-seed reference data (programs, network terms, retail events, the system
+/// campaign) and the first-run prompt shows again. Files in
+/// `attachment_folder` go with their rows.
+pub fn wipe(conn: &mut Connection, app_dir: &Path, attachment_folder: &Path) -> Result<DataWipeSummary, String> {
+    let media = owned_media(conn, app_dir).map_err(|e| e.to_string())?;
+    let (tables, rows_deleted) = clear_tables(conn)?;
+
+    // With the attachments table empty, every stored file is an orphan
+    let mut files_deleted = attachments::prune(conn, attachment_folder)? as i64;
+    for path in media {
+        if std::fs::remove_file(&path).is_ok() {
+            files_deleted += 1;
+        }
+    }
+
+    // Rewrite the file so deleted rows can't be recovered from free pages
+    conn.execute_batch("VACUUM;").map_err(|e| e.to_string())?;
+    let _ = conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);");
+
+    schema::run_migrations(conn).map_err(|e| e.to_string())?;
+
+    Ok(DataWipeSummary { tables_cleared: tables, rows_deleted, files_deleted })
+}
+
+// ============================================================================
+// UNIT TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::note::SaveNoteInput;
+    use crate::services::notes;
+
+    #[test]
+    fn test_export_dumps_every_table_and_phrase_must_match() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT, score REAL, raw BLOB);
+             INSERT INTO notes (body, score, raw) VALUES ('hi', 1.5, x'00ff');
+             CREATE TABLE empty (id INTEGER PRIMARY KEY);",
+        )
+        .unwrap();
+
+        let (dump, rows) = export(&conn).unwrap();
+        assert_eq!(rows, 1);
+        assert_eq!(dump["tables"]["empty"], json!([]));
+        let note = &dump["tables"]["notes"][0];
+        assert_eq!(note["body"], "hi");
+        assert_eq!(note["score"], 1.5);
+        assert_eq!(note["raw"]["hex"], "00ff");
+
+        assert!(check_phrase("DELETE ALL MY DATA").is_ok());
+        assert!(check_phrase("delete all my data").is_err());
+        assert!(check_phrase("").is_err());
+    }
+
+    #[test]
+    fn test_wipe_leaves_notes_searchable() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY);
+             CREATE TABLE affiliate_links (id INTEGER PRIMARY KEY);
+             CREATE TABLE ad_copies (id INTEGER PRIMARY KEY);
+             CREATE TABLE campaigns (id INTEGER PRIMARY KEY);",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../../migrations/047_notes.sql")).unwrap();
+        let note = |body: &str| SaveNoteInput { id: None, title: None, body: body.to_string(), refs: Vec::new() };
+        notes::save(&mut conn, &note("Call with the lamp program manager")).unwrap();
+
+        let (tables, rows) = clear_tables(&mut conn).unwrap();
+        assert!(!tables.iter().any(|t| t.starts_with("notes_fts")));
+        assert_eq!(rows, 1);
+        assert!(notes::search(&conn, "lamp", 10).unwrap().is_empty());
+
+        notes::save(&mut conn, &note("Lamp rates went up")).unwrap();
+        let hits = notes::search(&conn, "lamp", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].note.body, "Lamp rates went up");
+    }
+}
@@ -0,0 +1,176 @@
+//! Deep Links
+//!
+//! Wraps any merchant URL in an affiliate network's deep-link format so a
+//! link can point at a specific page rather than the merchant's home page.
+//! Covers networks outside the built-in platforms; the program's network IDs
+//! are stored on `affiliate_programs` and the publisher ID is the
+//! `affiliate_id` of the network's saved credentials.
+
+use crate::models::deep_link::ProgramNetworkIds;
+use crate::services::postback::percent_encode;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub const DEEP_LINK_NETWORKS: [&str; 4] = ["impact", "cj", "shareasale", "awin"];
+
+pub fn validate(ids: &ProgramNetworkIds) -> Result<(), String> {
+    if ids.program_name.trim().is_empty() {
+        return Err("Program name is required".to_string());
+    }
+    if !DEEP_LINK_NETWORKS.contains(&ids.network.as_str()) {
+        return Err(format!(
+            "Unsupported network '{}'; expected one of {}",
+            ids.network,
+            DEEP_LINK_NETWORKS.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Builds the deep link for `destination_url` through `ids.network`
+pub fn build(ids: &ProgramNetworkIds, publisher_id: &str, destination_url: &str) -> Result<String, String> {
+    let required = |value: &Option<String>, field: &str| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+            .ok_or_else(|| format!("{} needs a {} for {} deep links", ids.program_name, field, ids.network))
+    };
+    let destination = destination_url.trim();
+
+    match ids.network.as_str() {
+        "impact" => Ok(format!(
+            "https://{}/c/{}/{}/{}?u={}",
+            required(&ids.tracking_domain, "tracking domain")?.trim_end_matches('/'),
+            publisher_id,
+            required(&ids.ad_id, "ad ID")?,
+            required(&ids.merchant_id, "campaign ID")?,
+            percent_encode(destination)
+        )),
+        // CJ's deep link generator takes the destination unencoded after the PID
+        "cj" => Ok(format!("https://www.anrdoezrs.net/links/{}/type/dlg/{}", publisher_id, destination)),
+        "shareasale" => {
+            let without_scheme = destination.split_once("://").map_or(destination, |(_, rest)| rest);
+            Ok(format!(
+                "https://shareasale.com/r.cfm?b={}&u={}&m={}&urllink={}&afftrack=",
+                required(&ids.ad_id, "banner ID")?,
+                publisher_id,
+                required(&ids.merchant_id, "merchant ID")?,
+                percent_encode(without_scheme)
+            ))
+        }
+        "awin" => Ok(format!(
+            "https://www.awin1.com/cread.php?awinmid={}&awinaffid={}&ued={}",
+            required(&ids.merchant_id, "advertiser ID")?,
+            publisher_id,
+            percent_encode(destination)
+        )),
+        other => Err(format!("Unsupported network '{}'", other)),
+    }
+}
+
+/// Programs with a deep-link network configured
+pub fn list(conn: &Connection) -> rusqlite::Result<Vec<ProgramNetworkIds>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, network, merchant_id, ad_id, tracking_domain FROM affiliate_programs
+         WHERE network IS NOT NULL ORDER BY name",
+    )?;
+    let programs = stmt
+        .query_map([], |row| {
+            Ok(ProgramNetworkIds {
+                program_name: row.get(0)?,
+                network: row.get(1)?,
+                merchant_id: row.get(2)?,
+                ad_id: row.get(3)?,
+                tracking_domain: row.get(4)?,
+            })
+        })?
+        .collect();
+    programs
+}
+
+/// Stores a program's network IDs, adding the program when it isn't known yet
+pub fn save(conn: &Connection, ids: &ProgramNetworkIds) -> rusqlite::Result<()> {
+    let clean = |value: &Option<String>| value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(String::from);
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT name FROM affiliate_programs WHERE name = ?1 COLLATE NOCASE",
+            params![ids.program_name.trim()],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let name = existing.unwrap_or_else(|| ids.program_name.trim().to_string());
+
+    conn.execute(
+        "INSERT INTO affiliate_programs (name, network, merchant_id, ad_id, tracking_domain)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(name) DO UPDATE SET network = excluded.network, merchant_id = excluded.merchant_id,
+            ad_id = excluded.ad_id, tracking_domain = excluded.tracking_domain,
+            updated_at = CURRENT_TIMESTAMP",
+        params![name, ids.network, clean(&ids.merchant_id), clean(&ids.ad_id), clean(&ids.tracking_domain)],
+    )?;
+    Ok(())
+}
+
+/// A deep-link program with the defaults copied onto links built for it
+pub struct DeepLinkProgram {
+    pub ids: ProgramNetworkIds,
+    pub commission_rate: Option<f64>,
+    pub cookie_duration: Option<i32>,
+}
+
+pub fn load(conn: &Connection, program: &str) -> rusqlite::Result<Option<DeepLinkProgram>> {
+    conn.query_row(
+        "SELECT name, network, merchant_id, ad_id, tracking_domain, default_commission_rate, cookie_duration
+         FROM affiliate_programs WHERE name = ?1 COLLATE NOCASE AND network IS NOT NULL",
+        params![program.trim()],
+        |row| {
+            Ok(DeepLinkProgram {
+                ids: ProgramNetworkIds {
+                    program_name: row.get(0)?,
+                    network: row.get(1)?,
+                    merchant_id: row.get(2)?,
+                    ad_id: row.get(3)?,
+                    tracking_domain: row.get(4)?,
+                },
+                commission_rate: row.get(5)?,
+                cookie_duration: row.get(6)?,
+            })
+        },
+    )
+    .optional()
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(network: &str, merchant_id: Option<&str>) -> ProgramNetworkIds {
+        ProgramNetworkIds {
+            program_name: "Acme Outdoors".to_string(),
+            network: network.to_string(),
+            merchant_id: merchant_id.map(String::from),
+            ad_id: None,
+            tracking_domain: None,
+        }
+    }
+
+    #[test]
+    fn test_build_network_formats() {
+        let url = "https://acme.example/tents?color=green";
+        assert_eq!(
+            build(&ids("awin", Some("1234")), "98765", url).unwrap(),
+            "https://www.awin1.com/cread.php?awinmid=1234&awinaffid=98765&ued=https%3A%2F%2Facme.example%2Ftents%3Fcolor%3Dgreen"
+        );
+        assert_eq!(
+            build(&ids("cj", None), "555", url).unwrap(),
+            "https://www.anrdoezrs.net/links/555/type/dlg/https://acme.example/tents?color=green"
+        );
+        let missing = build(&ids("shareasale", Some("42")), "7", url).unwrap_err();
+        assert!(missing.contains("banner ID"));
+    }
+}
@@ -0,0 +1,461 @@
+//! Demo Workspace
+//!
+//! Generates a realistic sandbox (50 products with links, ads, and 90 days of
+//! clicks and conversions) so analytics and reports can be explored before
+//! any real data is entered. Generation is deterministic: every load produces
+//! the same catalog and traffic shape, dated relative to today. Demo products
+//! carry the `demo` tag so they are easy to filter out or delete later.
+
+use crate::database::schema::ensure_default_campaign;
+use crate::models::workspace::DemoDataSummary;
+use crate::services::click_analytics::{normalize_source, user_agent_bot_reason};
+use crate::services::daily_metrics;
+use chrono::{Duration, NaiveDateTime, Utc};
+use rusqlite::{params, Connection};
+
+pub const DEMO_TAG: &str = "demo";
+
+/// Days of click and conversion history
+const HISTORY_DAYS: i64 = 90;
+
+/// (category, target audience, products as (name, price in dollars))
+type CatalogCategory = (&'static str, &'static str, [(&'static str, f64); 5]);
+
+const CATALOG: [CatalogCategory; 10] = [
+    (
+        "Wearable Health Technology",
+        "Age 25-45, health-conscious professionals and biohackers",
+        [
+            ("Lumen Sleep Ring", 299.0),
+            ("PulseBand HRV Tracker", 179.0),
+            ("Oxa Breathing Wearable", 149.0),
+            ("CoreTemp Smart Patch", 89.0),
+            ("Stride Smart Insoles", 129.0),
+        ],
+    ),
+    (
+        "Beauty & Skincare",
+        "Age 18-35, skincare routine enthusiasts, K-beauty fans",
+        [
+            ("Glow Snail Mucin Essence", 24.0),
+            ("Dewdrop Rice Toner", 19.0),
+            ("Barrier Repair Ceramide Cream", 32.0),
+            ("SunVeil Invisible SPF 50", 28.0),
+            ("Bakuchiol Night Serum", 36.0),
+        ],
+    ),
+    (
+        "Fitness & Recovery",
+        "Age 25-50, gym-goers, runners, desk workers with tension",
+        [
+            ("Percussion Mini Massage Gun", 149.0),
+            ("Heated Foam Roller", 79.0),
+            ("Compression Recovery Boots", 399.0),
+            ("Adjustable Kettlebell 40lb", 129.0),
+            ("Smart Jump Rope", 49.0),
+        ],
+    ),
+    (
+        "Home & Kitchen",
+        "Age 25-45, home cooks, small-apartment renters",
+        [
+            ("Dual-Zone Air Fryer", 139.0),
+            ("Cordless Handheld Vacuum", 99.0),
+            ("Glass Meal Prep Set", 39.0),
+            ("Smart Sous Vide Stick", 119.0),
+            ("Countertop Ice Maker", 129.0),
+        ],
+    ),
+    (
+        "Supplements & Wellness",
+        "Age 25-55, wellness-focused adults, athletes",
+        [
+            ("Magnesium Glycinate Sleep Blend", 29.0),
+            ("Daily Greens Powder", 59.0),
+            ("Creatine Monohydrate Gummies", 34.0),
+            ("Lion's Mane Focus Capsules", 32.0),
+            ("Collagen Peptides Unflavored", 38.0),
+        ],
+    ),
+    (
+        "Pet Supplies",
+        "Age 25-45, dog and cat owners, pet wellness shoppers",
+        [
+            ("Self-Cleaning Litter Box", 449.0),
+            ("GPS Pet Collar Tracker", 99.0),
+            ("Orthopedic Dog Bed", 89.0),
+            ("Automatic Pet Feeder", 79.0),
+            ("Lick Mat Enrichment Set", 19.0),
+        ],
+    ),
+    (
+        "Home Office",
+        "Age 22-45, remote workers, students, creators",
+        [
+            ("Under-Desk Walking Pad", 299.0),
+            ("Ergonomic Mesh Chair", 249.0),
+            ("Monitor Light Bar", 59.0),
+            ("Split Mechanical Keyboard", 159.0),
+            ("Standing Desk Converter", 189.0),
+        ],
+    ),
+    (
+        "Outdoor & Travel",
+        "Age 22-50, weekend hikers, frequent travelers",
+        [
+            ("Packable Down Jacket", 129.0),
+            ("Portable Power Station 300W", 279.0),
+            ("Water Filter Bottle", 39.0),
+            ("Carry-On Compression Backpack", 149.0),
+            ("Ultralight Camping Hammock", 69.0),
+        ],
+    ),
+    (
+        "Baby & Parenting",
+        "Age 25-40, new and expecting parents",
+        [
+            ("Smart Baby Monitor", 199.0),
+            ("Portable Bottle Warmer", 49.0),
+            ("White Noise Sound Machine", 35.0),
+            ("Convertible Travel Stroller", 299.0),
+            ("Silicone Feeding Set", 29.0),
+        ],
+    ),
+    (
+        "Tech Accessories",
+        "Age 18-40, smartphone power users, creators",
+        [
+            ("3-in-1 Wireless Charging Stand", 79.0),
+            ("Magnetic Power Bank 10000mAh", 49.0),
+            ("Noise-Cancelling Earbuds", 129.0),
+            ("Clip-On Wireless Microphone", 69.0),
+            ("Ring Light with Phone Mount", 45.0),
+        ],
+    ),
+];
+
+/// (platform, program, commission rate, cookie days, click referrer)
+const PROGRAMS: [(&str, &str, f64, i64, &str); 4] = [
+    ("amazon", "Amazon Associates", 0.04, 1, "https://www.google.com/"),
+    ("tiktok", "TikTok Shop Affiliate", 0.15, 7, "https://www.tiktok.com/"),
+    ("instagram", "Instagram Shopping", 0.10, 7, "https://www.instagram.com/"),
+    ("pinterest", "Pinterest Shopping", 0.08, 30, "https://www.pinterest.com/"),
+];
+
+const AD_TYPES: [&str; 5] = ["social_post", "story", "video_script", "carousel", "email"];
+
+/// Crawler traffic mixed into the clicks, filtered out of reports
+const BOT_USER_AGENT: &str = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+const BOT_CLICK_RATE: f64 = 0.03;
+
+/// Visitor countries, weighted by repetition
+const COUNTRIES: [&str; 10] = ["US", "US", "US", "US", "US", "GB", "GB", "CA", "AU", "DE"];
+
+const USER_AGENTS: [&str; 3] = [
+    "Mozilla/5.0 (iPhone; CPU iPhone OS 17_4 like Mac OS X) AppleWebKit/605.1.15 Mobile/15E148",
+    "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 Chrome/124.0 Mobile Safari/537.36",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 Chrome/124.0 Safari/537.36",
+];
+
+/// Relative click volume per hour of day: quiet overnight, peaking in the evening
+const HOUR_WEIGHTS: [u32; 24] = [2, 1, 1, 1, 1, 1, 2, 4, 5, 5, 5, 6, 7, 6, 5, 5, 6, 7, 9, 11, 12, 11, 8, 4];
+
+/// Small xorshift generator; the demo needs repeatable data, not randomness
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform in [0, 1)
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in [low, high]
+    fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.next() % (high - low + 1) as u64) as i64
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        self.unit() < probability
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.next() as usize % items.len()]
+    }
+
+    fn hour(&mut self) -> u32 {
+        let total: u32 = HOUR_WEIGHTS.iter().sum();
+        let mut roll = (self.next() % total as u64) as u32;
+        for (hour, weight) in HOUR_WEIGHTS.iter().enumerate() {
+            if roll < *weight {
+                return hour as u32;
+            }
+            roll -= weight;
+        }
+        23
+    }
+}
+
+fn timestamp(at: NaiveDateTime) -> String {
+    at.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+fn price_range(price: f64) -> String {
+    format!("${:.0}-{:.0}", price * 0.85, price * 1.15)
+}
+
+fn ad_copy(ad_type: &str, name: &str, category: &str) -> (String, String, &'static str) {
+    let category = category.to_lowercase();
+    match ad_type {
+        "story" => (
+            format!("POV: You just discovered {}", name),
+            format!("The {} pick everyone keeps asking about. Swipe up before it sells out!", category),
+            "Swipe Up",
+        ),
+        "video_script" => (
+            format!("STOP scrolling! You need to see this {}", category),
+            format!(
+                "[HOOK] Wait, you don't know about {} yet?\n\n[SOLUTION] It fixes the one thing \
+                 everyone hates about {}.\n\n[CTA] Link in bio!",
+                name, category
+            ),
+            "Link in Bio",
+        ),
+        "carousel" => (
+            format!("5 Reasons {} is a Must-Have", name),
+            format!("Slide 1: Meet your new favorite {}\nSlide 5: Ready to upgrade?", category),
+            "Save for Later",
+        ),
+        "email" => (
+            format!("You're going to love {} - Here's why", name),
+            format!("Hi there,\n\nSearching for the perfect {}? Meet {}.\n\nBest,\nThe Team", category, name),
+            "Shop Now",
+        ),
+        _ => (
+            format!("Transform your routine with {}", name),
+            format!("Discover why everyone is talking about {}. #trending #musthave", name),
+            "Shop Now",
+        ),
+    }
+}
+
+/// What `load` created, with the IDs needed for change events
+pub struct LoadedDemo {
+    pub summary: DemoDataSummary,
+    pub product_ids: Vec<i64>,
+    pub link_ids: Vec<i64>,
+    pub ad_ids: Vec<i64>,
+}
+
+/// Inserts the demo catalog with its links, ads, clicks, and conversions in
+/// one transaction
+pub fn load(conn: &mut Connection) -> rusqlite::Result<LoadedDemo> {
+    let tx = conn.transaction()?;
+    let mut rng = Rng(0x5EED_AFF1_1A7E_D3A0);
+    let now = Utc::now().naive_utc();
+    let mut summary = DemoDataSummary { days: HISTORY_DAYS, ..Default::default() };
+    let (mut product_ids, mut link_ids, mut ad_ids) = (Vec::new(), Vec::new(), Vec::new());
+    let tags = serde_json::json!([DEMO_TAG]).to_string();
+
+    {
+        let mut insert_click = tx.prepare(
+            "INSERT INTO click_events (link_id, clicked_at, ip_hash, user_agent, referrer, source, country,
+             bot_reason)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        let mut insert_conversion = tx.prepare(
+            "INSERT INTO conversion_events (link_id, converted_at, order_value, commission, status,
+             order_id, source) VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'demo')",
+        )?;
+
+        for (category, audience, products) in CATALOG.iter() {
+            for (name, price) in products.iter() {
+                let trending_score = rng.range(40, 100);
+                let stage = match rng.range(0, 19) {
+                    0..=3 => "researching",
+                    4..=8 => "approved",
+                    9..=17 => "promoting",
+                    _ => "retired",
+                };
+                let created_at = now - Duration::days(HISTORY_DAYS + rng.range(0, 14));
+                let slug = name.to_lowercase().replace(|c: char| !c.is_ascii_alphanumeric(), "-");
+                let product_url = format!("https://shop.example.com/{}", slug);
+                tx.execute(
+                    "INSERT INTO products (name, category, description, price_range, target_audience,
+                     trending_score, notes, product_url, stage, stage_changed_at, tags,
+                     created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'Demo product', ?7, ?8, ?9, ?10, ?9, ?9)",
+                    params![
+                        name,
+                        category,
+                        format!("{} from the demo catalog ({}).", name, category.to_lowercase()),
+                        price_range(*price),
+                        audience,
+                        trending_score,
+                        product_url,
+                        stage,
+                        timestamp(created_at),
+                        tags,
+                    ],
+                )?;
+                let product_id = tx.last_insert_rowid();
+                product_ids.push(product_id);
+                if stage == "researching" {
+                    continue;
+                }
+
+                // Every promoted product has an Amazon link; some also sell on a social shop
+                let mut programs = vec![&PROGRAMS[0]];
+                if rng.chance(0.6) {
+                    programs.push(rng.pick(&PROGRAMS[1..]));
+                }
+                let conversion_rate = 0.01 + rng.unit() * 0.04;
+                let popularity = trending_score as f64 / 100.0 * (1.0 + rng.unit() * 9.0);
+
+                for (platform, program, rate, cookie_days, referrer) in programs {
+                    let status = if rng.chance(0.06) { "invalid" } else { "active" };
+                    tx.execute(
+                        "INSERT INTO affiliate_links (product_id, product_name, platform, program_name,
+                         commission_rate, cookie_duration, tracking_url, destination_url, status,
+                         created_at, updated_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?10)",
+                        params![
+                            product_id,
+                            name,
+                            platform,
+                            program,
+                            rate,
+                            cookie_days,
+                            format!("{}?aff=affilai-demo&pid={}", product_url, product_id),
+                            product_url,
+                            status,
+                            timestamp(created_at),
+                        ],
+                    )?;
+                    let link_id = tx.last_insert_rowid();
+                    link_ids.push(link_id);
+                    summary.links += 1;
+
+                    // Traffic grows over the window, with busier weekends
+                    for day in (0..HISTORY_DAYS).rev() {
+                        let date = (now - Duration::days(day)).date();
+                        let growth = 0.5 + (HISTORY_DAYS - day) as f64 / HISTORY_DAYS as f64;
+                        let weekend = matches!(date.format("%u").to_string().as_str(), "6" | "7");
+                        let expected = popularity * growth * if weekend { 1.4 } else { 1.0 };
+                        let clicks = (expected * (0.5 + rng.unit())).round() as i64;
+
+                        for _ in 0..clicks {
+                            let clicked_at = date.and_hms_opt(rng.hour(), rng.range(0, 59) as u32, 0).unwrap_or(now);
+                            if clicked_at > now {
+                                continue;
+                            }
+                            let referrer = if rng.chance(0.8) { Some(*referrer) } else { None };
+                            let user_agent =
+                                if rng.chance(BOT_CLICK_RATE) { BOT_USER_AGENT } else { rng.pick(&USER_AGENTS) };
+                            insert_click.execute(params![
+                                link_id,
+                                timestamp(clicked_at),
+                                format!("{:016x}", rng.next()),
+                                user_agent,
+                                referrer,
+                                normalize_source(None, referrer),
+                                rng.pick(&COUNTRIES),
+                                user_agent_bot_reason(Some(user_agent)),
+                            ])?;
+                            summary.clicks += 1;
+                            if user_agent == BOT_USER_AGENT {
+                                continue;
+                            }
+
+                            if rng.chance(conversion_rate) {
+                                let order_value = (price * (0.8 + rng.unit() * 0.6) * 100.0).round() / 100.0;
+                                let converted_at = clicked_at + Duration::minutes(rng.range(2, 180));
+                                // Networks confirm commissions a month or so after the sale
+                                let status = if day > 30 { "approved" } else { "pending" };
+                                insert_conversion.execute(params![
+                                    link_id,
+                                    timestamp(converted_at.min(now)),
+                                    order_value,
+                                    (order_value * rate * 100.0).round() / 100.0,
+                                    status,
+                                    format!("DEMO-{}-{:06}", link_id, rng.range(0, 999_999)),
+                                ])?;
+                                summary.conversions += 1;
+                            }
+                        }
+                    }
+                }
+
+                for _ in 0..rng.range(1, 3) {
+                    let ad_type = *rng.pick(&AD_TYPES);
+                    let (headline, body, cta) = ad_copy(ad_type, name, category);
+                    let ad_created_at = now - Duration::days(rng.range(0, HISTORY_DAYS));
+                    tx.execute(
+                        "INSERT INTO ad_copies (campaign_id, product_id, variation_name, headline,
+                         body_text, cta, ad_format, ad_type, platform_specific_data, performance_score,
+                         is_favorite, created_at, updated_at)
+                         VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?6, ?7, ?8, ?9, ?10, ?10)",
+                        params![
+                            product_id,
+                            format!("{} - {}", name, ad_type),
+                            headline,
+                            body,
+                            cta,
+                            ad_type,
+                            serde_json::json!({ "demo": true }).to_string(),
+                            ((0.4 + rng.unit() * 0.55) * 100.0).round() / 100.0,
+                            rng.chance(0.15),
+                            timestamp(ad_created_at),
+                        ],
+                    )?;
+                    ad_ids.push(tx.last_insert_rowid());
+                    summary.ads += 1;
+                }
+            }
+        }
+    }
+
+    summary.products = product_ids.len();
+    ensure_default_campaign(&tx)?;
+    // The history is back-dated, so already materialized days need recomputing
+    daily_metrics::mark_stale(&tx, (now - Duration::days(HISTORY_DAYS)).date())?;
+    tx.commit()?;
+    Ok(LoadedDemo { summary, product_ids, link_ids, ad_ids })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_has_fifty_unique_products() {
+        let mut names: Vec<&str> = CATALOG.iter().flat_map(|(_, _, products)| products.map(|(n, _)| n)).collect();
+        assert_eq!(names.len(), 50);
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), 50);
+        assert_eq!(price_range(100.0), "$85-115");
+    }
+
+    #[test]
+    fn test_rng_is_repeatable_and_bounded() {
+        let (mut a, mut b) = (Rng(42), Rng(42));
+        for _ in 0..1000 {
+            let value = a.range(3, 7);
+            assert_eq!(value, b.range(3, 7));
+            assert!((3..=7).contains(&value));
+            assert!(a.hour() < 24);
+            b.hour();
+        }
+    }
+}
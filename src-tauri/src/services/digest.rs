@@ -0,0 +1,225 @@
+//! Weekly Digest Generation
+//!
+//! Summarizes a week of workspace activity (new ads, clicks, earnings, broken
+//! and expired links) into a stored digest record, optionally surfaced as an
+//! OS notification so users get a proactive status update.
+
+use crate::database::get_connection;
+use crate::database::settings::get_setting_or;
+use crate::models::digest::{DigestLink, DigestProduct, DigestRecord, WeeklyDigest};
+use crate::services::daily_metrics;
+use chrono::{Duration, NaiveDate, Utc};
+use rusqlite::{params, Connection};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+/// Builds the digest for the 7 days ending on `period_end` (inclusive)
+pub fn build_digest(conn: &Connection, period_end: NaiveDate) -> rusqlite::Result<WeeklyDigest> {
+    let first_day = period_end - Duration::days(6);
+    let start = first_day.format("%Y-%m-%d").to_string();
+    let end = period_end.format("%Y-%m-%d").to_string();
+
+    let count = |sql: &str| -> rusqlite::Result<i64> {
+        conn.query_row(sql, params![start, end], |row| row.get(0))
+    };
+
+    let new_products = count("SELECT COUNT(*) FROM products WHERE date(created_at) BETWEEN ?1 AND ?2")?;
+    let new_ads = count("SELECT COUNT(*) FROM ad_copies WHERE date(created_at) BETWEEN ?1 AND ?2")?;
+    let new_links = count("SELECT COUNT(*) FROM affiliate_links WHERE date(created_at) BETWEEN ?1 AND ?2")?;
+    let totals = daily_metrics::totals(conn, first_day, period_end)?;
+    let (clicks, conversions, earnings) = (totals.clicks, totals.conversions, totals.commission);
+
+    let broken_links = links_with_status(conn, "invalid")?;
+    let expired_links = links_with_status(conn, "expired")?;
+
+    let top_products = daily_metrics::top_products(conn, first_day, period_end, 3)?
+        .into_iter()
+        .map(|p| DigestProduct {
+            product_id: p.product_id,
+            product_name: p.product_name,
+            clicks: p.clicks,
+            earnings: p.commission,
+        })
+        .collect();
+
+    Ok(WeeklyDigest {
+        period_start: start,
+        period_end: end,
+        new_products,
+        new_ads,
+        new_links,
+        clicks,
+        conversions,
+        earnings,
+        broken_links,
+        expired_links,
+        top_products,
+    })
+}
+
+fn links_with_status(conn: &Connection, status: &str) -> rusqlite::Result<Vec<DigestLink>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, product_name, platform, program_name FROM affiliate_links
+         WHERE status = ?1 ORDER BY updated_at DESC",
+    )?;
+    let links = stmt
+        .query_map(params![status], |row| {
+            Ok(DigestLink {
+                link_id: row.get(0)?,
+                product_name: row.get(1)?,
+                platform: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                program_name: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(links)
+}
+
+/// Renders a short plain-text summary suitable for a notification body
+pub fn summarize(digest: &WeeklyDigest) -> String {
+    let mut lines = vec![format!(
+        "{} clicks, {} conversions, ${:.2} earned",
+        digest.clicks, digest.conversions, digest.earnings
+    )];
+
+    lines.push(format!(
+        "{} new ads, {} new links, {} new products",
+        digest.new_ads, digest.new_links, digest.new_products
+    ));
+
+    if let Some(top) = digest.top_products.first() {
+        lines.push(format!("Top product: {} (${:.2})", top.product_name, top.earnings));
+    }
+
+    let attention = digest.broken_links.len() + digest.expired_links.len();
+    if attention > 0 {
+        lines.push(format!(
+            "{} link(s) need attention ({} broken, {} expired)",
+            attention,
+            digest.broken_links.len(),
+            digest.expired_links.len()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Builds, stores, and optionally announces the digest for the week ending today
+pub fn generate_and_store(app_handle: &AppHandle, notify: bool) -> Result<DigestRecord, String> {
+    let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+
+    let digest = build_digest(&conn, Utc::now().date_naive()).map_err(|e| e.to_string())?;
+    let summary_text = summarize(&digest);
+
+    let notified = notify && show_notification(app_handle, &summary_text);
+
+    conn.execute(
+        "INSERT INTO digests (period_start, period_end, summary_json, summary_text, notified)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            digest.period_start,
+            digest.period_end,
+            serde_json::to_string(&digest).map_err(|e| e.to_string())?,
+            summary_text,
+            notified,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(DigestRecord {
+        id: conn.last_insert_rowid(),
+        period_start: digest.period_start.clone(),
+        period_end: digest.period_end.clone(),
+        digest,
+        summary_text,
+        notified,
+        created_at: None,
+    })
+}
+
+fn show_notification(app_handle: &AppHandle, body: &str) -> bool {
+    match app_handle
+        .notification()
+        .builder()
+        .title("AffilAI weekly digest")
+        .body(body)
+        .show()
+    {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("Failed to show digest notification: {}", e);
+            false
+        }
+    }
+}
+
+/// Scheduled job entry point: generates the digest when enabled in settings
+pub fn run_weekly_digest_job(app_handle: &AppHandle) -> Result<String, String> {
+    let notify = {
+        let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        get_setting_or(&conn, "digest_notifications_enabled", "true") == "true"
+    };
+
+    let record = generate_and_store(app_handle, notify)?;
+    Ok(format!("Digest {} generated", record.id))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_digest() -> WeeklyDigest {
+        WeeklyDigest {
+            period_start: "2025-03-01".to_string(),
+            period_end: "2025-03-07".to_string(),
+            new_products: 0,
+            new_ads: 2,
+            new_links: 1,
+            clicks: 30,
+            conversions: 2,
+            earnings: 12.5,
+            broken_links: Vec::new(),
+            expired_links: Vec::new(),
+            top_products: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_summary_includes_totals() {
+        let summary = summarize(&empty_digest());
+        assert!(summary.contains("30 clicks, 2 conversions, $12.50 earned"));
+        assert!(!summary.contains("need attention"));
+    }
+
+    #[test]
+    fn test_summary_flags_broken_links() {
+        let mut digest = empty_digest();
+        digest.broken_links.push(DigestLink {
+            link_id: 1,
+            product_name: "Smart Ring".to_string(),
+            platform: "amazon".to_string(),
+            program_name: "Amazon Associates".to_string(),
+        });
+        assert!(summarize(&digest).contains("1 link(s) need attention (1 broken, 0 expired)"));
+    }
+
+    #[test]
+    fn test_stored_digest_json_round_trips() {
+        let json = serde_json::to_value(empty_digest()).unwrap();
+        assert_eq!(json["periodStart"], "2025-03-01");
+        assert_eq!(json["brokenLinks"], serde_json::json!([]));
+
+        // Digests stored before the camelCase switch still load
+        let stored = r#"{"period_start": "2025-03-01", "period_end": "2025-03-07", "new_products": 1,
+            "new_ads": 0, "new_links": 0, "clicks": 4, "conversions": 0, "earnings": 0.0,
+            "broken_links": [{"link_id": 3, "product_name": "Smart Ring", "platform": "amazon",
+            "program_name": "Amazon Associates"}], "expired_links": [], "top_products": []}"#;
+        let digest: WeeklyDigest = serde_json::from_str(stored).unwrap();
+        assert_eq!(digest.new_products, 1);
+        assert_eq!(digest.broken_links[0].link_id, 3);
+    }
+}
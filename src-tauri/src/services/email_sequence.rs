@@ -0,0 +1,330 @@
+//! Email Nurture Sequences
+//!
+//! Extends the single email ad into a 3-5 message nurture sequence (intro,
+//! value, objection handling, urgency, final reminder). A sequence is stored
+//! as an `email_sequence` ad whose messages are ordered `email` child ads
+//! carrying `sequence_step` and `send_delay_days` (days after the previous
+//! message). Sequences export to the CSV layouts ESPs accept for automations.
+
+use crate::commands::ad_generation::{generate_selling_points, GeneratedAdCopy};
+use crate::models::product::Product;
+use crate::models::research::ResearchBrief;
+
+pub const MIN_SEQUENCE_LENGTH: i32 = 3;
+pub const MAX_SEQUENCE_LENGTH: i32 = 5;
+pub const DEFAULT_SEQUENCE_LENGTH: i32 = 4;
+
+/// One message slot in a nurture sequence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SequenceStep {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub send_delay_days: i32,
+}
+
+const INTRO: SequenceStep = SequenceStep { key: "intro", label: "Intro", send_delay_days: 0 };
+const VALUE: SequenceStep = SequenceStep { key: "value", label: "Value", send_delay_days: 2 };
+const OBJECTION: SequenceStep = SequenceStep {
+    key: "objection",
+    label: "Objection Handling",
+    send_delay_days: 2,
+};
+const URGENCY: SequenceStep = SequenceStep { key: "urgency", label: "Urgency", send_delay_days: 2 };
+const FINAL: SequenceStep = SequenceStep {
+    key: "final_reminder",
+    label: "Final Reminder",
+    send_delay_days: 1,
+};
+
+/// Steps for a sequence of `length` messages; short sequences drop objection
+/// handling first, and only five-message sequences get the final reminder
+pub fn plan_steps(length: Option<i32>) -> Vec<SequenceStep> {
+    match length
+        .unwrap_or(DEFAULT_SEQUENCE_LENGTH)
+        .clamp(MIN_SEQUENCE_LENGTH, MAX_SEQUENCE_LENGTH)
+    {
+        3 => vec![INTRO, VALUE, URGENCY],
+        4 => vec![INTRO, VALUE, OBJECTION, URGENCY],
+        _ => vec![INTRO, VALUE, OBJECTION, URGENCY, FINAL],
+    }
+}
+
+/// A drafted message before it is stored as a child ad
+#[derive(Debug, Clone)]
+pub struct EmailDraft {
+    pub step: SequenceStep,
+    pub subject: String,
+    pub preview_text: String,
+    pub body: String,
+    pub cta: String,
+}
+
+/// Drafts every message of the sequence from the product, its research brief,
+/// and an optional attributed review quote for the objection step
+pub fn draft_sequence(
+    product: &Product,
+    research: Option<&ResearchBrief>,
+    review_quote: Option<&str>,
+    custom_instructions: Option<&str>,
+    steps: &[SequenceStep],
+) -> Vec<EmailDraft> {
+    let name = &product.name;
+    let category = product.category.to_lowercase();
+    let description = product.description.as_deref().unwrap_or("").trim();
+
+    let benefits = research
+        .map(|r| r.key_benefits.clone())
+        .filter(|b| !b.is_empty())
+        .unwrap_or_else(|| generate_selling_points(&product.category, name));
+    let pain_point = research
+        .and_then(|r| r.pain_points.first().cloned())
+        .unwrap_or_else(|| format!("finding a {} that actually works", category));
+    let objections = research
+        .map(|r| r.objections.clone())
+        .filter(|o| !o.is_empty())
+        .unwrap_or_else(|| vec!["Is it worth the price?".to_string(), "Will it work for me?".to_string()]);
+    let ps = custom_instructions
+        .map(str::trim)
+        .filter(|i| !i.is_empty())
+        .map(|i| format!("\n\nP.S. {}", i))
+        .unwrap_or_default();
+
+    steps
+        .iter()
+        .map(|step| {
+            let (subject, preview_text, body, cta) = match step.key {
+                "intro" => (
+                    format!("Meet {}", name),
+                    format!("The {} I keep recommending", category),
+                    format!(
+                        "Hi there,\n\n\
+                         If {} sounds familiar, this one's for you.\n\n\
+                         {} {}\n\n\
+                         Over the next few days I'll share why it works, answer the questions \
+                         people ask most, and make sure you don't miss out.\n\n\
+                         Take a look: [LINK]",
+                        pain_point.trim_end_matches(['.', '?', '!']).to_lowercase(),
+                        name,
+                        if description.is_empty() { format!("is my go-to {}.", category) } else { format!("- {}", description) }
+                    ),
+                    "Take a look".to_string(),
+                ),
+                "value" => (
+                    format!("{} ways {} makes life easier", benefits.len().min(3), name),
+                    format!("What sets {} apart", name),
+                    format!(
+                        "Hi again,\n\n\
+                         Here's what makes {} worth it:\n\n{}\n\n\
+                         See it for yourself: [LINK]",
+                        name,
+                        benefits
+                            .iter()
+                            .take(3)
+                            .map(|b| format!("  - {}", b))
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    ),
+                    "See how it works".to_string(),
+                ),
+                "objection" => (
+                    format!("\"{}\"", objections[0].trim_matches('"')),
+                    "Honest answers to the questions I get most".to_string(),
+                    format!(
+                        "Hi,\n\n\
+                         A few questions come up every time I mention {}:\n\n{}\n\n\
+                         The short answer: {}{}\n\n\
+                         Decide for yourself: [LINK]",
+                        name,
+                        objections
+                            .iter()
+                            .take(3)
+                            .map(|o| format!("  - {}", o))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                        benefits.first().cloned().unwrap_or_default(),
+                        review_quote.map(|q| format!("\n\n{}", q)).unwrap_or_default()
+                    ),
+                    "Get the details".to_string(),
+                ),
+                "urgency" => (
+                    format!("Don't wait on {}", name),
+                    "Popular picks don't stay in stock for long".to_string(),
+                    format!(
+                        "Hi,\n\n\
+                         Quick reminder: {} is one of the most popular {} picks right now, \
+                         and stock and pricing can change without notice.\n\n\
+                         If it's been on your list, now's the time: [LINK]",
+                        name, category
+                    ),
+                    "Shop Now".to_string(),
+                ),
+                _ => (
+                    format!("Last note about {}", name),
+                    "After this, I'll stop bringing it up".to_string(),
+                    format!(
+                        "Hi,\n\n\
+                         This is my last email about {}. If {} is still on your mind, \
+                         it's worth a look before you move on.\n\n[LINK]",
+                        name,
+                        pain_point.trim_end_matches(['.', '?', '!']).to_lowercase()
+                    ),
+                    "Shop Now".to_string(),
+                ),
+            };
+
+            EmailDraft {
+                step: *step,
+                subject,
+                preview_text,
+                body: format!("{}{}", body, ps),
+                cta,
+            }
+        })
+        .collect()
+}
+
+/// ESP import layouts supported by `to_csv`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EspFormat {
+    Generic,
+    Mailchimp,
+    Klaviyo,
+    ConvertKit,
+}
+
+impl EspFormat {
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "generic" | "csv" => Some(EspFormat::Generic),
+            "mailchimp" => Some(EspFormat::Mailchimp),
+            "klaviyo" => Some(EspFormat::Klaviyo),
+            "convertkit" | "kit" => Some(EspFormat::ConvertKit),
+            _ => None,
+        }
+    }
+
+    fn headers(&self) -> &'static [&'static str] {
+        match self {
+            EspFormat::Generic => &["step", "send_delay_days", "subject", "preview_text", "body", "cta"],
+            EspFormat::Mailchimp => &["Email Name", "Delay (days)", "Subject Line", "Preview Text", "Content"],
+            EspFormat::Klaviyo => &["Flow Message", "Time Delay (days)", "Subject", "Preview Text", "Body"],
+            EspFormat::ConvertKit => &["Position", "Send Day", "Subject", "Content"],
+        }
+    }
+}
+
+/// Renders sequence messages (ordered by step) in an ESP's CSV layout.
+/// ConvertKit schedules by day offset from the start, the others by delay
+/// after the previous message.
+pub fn to_csv(format: EspFormat, messages: &[GeneratedAdCopy]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(format.headers()).map_err(|e| e.to_string())?;
+
+    let mut send_day = 0;
+    for message in messages {
+        let step = message.sequence_step.unwrap_or_default();
+        let delay = message.send_delay_days.unwrap_or_default();
+        send_day += delay;
+
+        let data: serde_json::Value = message
+            .platform_specific_data
+            .as_deref()
+            .and_then(|d| serde_json::from_str(d).ok())
+            .unwrap_or_default();
+        let preview = data["preview_text"].as_str().unwrap_or("");
+        let name = format!(
+            "{}. {}",
+            step,
+            data["step_label"].as_str().unwrap_or(&message.headline)
+        );
+        let body = message.body_text.as_deref().unwrap_or("");
+        let cta = message.cta.as_deref().unwrap_or("");
+
+        let record: Vec<String> = match format {
+            EspFormat::Generic => vec![
+                step.to_string(),
+                delay.to_string(),
+                message.headline.clone(),
+                preview.to_string(),
+                body.to_string(),
+                cta.to_string(),
+            ],
+            EspFormat::Mailchimp | EspFormat::Klaviyo => vec![
+                name,
+                delay.to_string(),
+                message.headline.clone(),
+                preview.to_string(),
+                body.to_string(),
+            ],
+            EspFormat::ConvertKit => vec![
+                step.to_string(),
+                send_day.to_string(),
+                message.headline.clone(),
+                body.to_string(),
+            ],
+        };
+        writer.write_record(&record).map_err(|e| e.to_string())?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(step: i32, delay: i32, subject: &str, body: &str) -> GeneratedAdCopy {
+        GeneratedAdCopy {
+            id: Some(step as i64),
+            product_id: Some(1),
+            campaign_id: 1,
+            variation_name: None,
+            headline: subject.to_string(),
+            body_text: Some(body.to_string()),
+            cta: Some("Shop Now".to_string()),
+            ad_format: Some("email".to_string()),
+            ad_type: Some("email".to_string()),
+            platform_specific_data: Some(r#"{"step_label": "Intro", "preview_text": "Hello"}"#.to_string()),
+            performance_score: None,
+            created_at: None,
+            updated_at: None,
+            is_favorite: false,
+            parent_ad_id: Some(10),
+            sequence_step: Some(step),
+            send_delay_days: Some(delay),
+            alt_text: None,
+            link_id: None,
+            approval_state: "draft".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_plan_steps_clamps_length() {
+        let keys = |length| plan_steps(length).iter().map(|s| s.key).collect::<Vec<_>>();
+        assert_eq!(keys(Some(1)), vec!["intro", "value", "urgency"]);
+        assert_eq!(keys(None), vec!["intro", "value", "objection", "urgency"]);
+        assert_eq!(keys(Some(9)).len(), 5);
+    }
+
+    #[test]
+    fn test_convertkit_csv_uses_cumulative_days() {
+        let messages = vec![
+            message(1, 0, "Meet it", "Hi, \"friend\""),
+            message(2, 2, "Why it works", "Line one\nLine two"),
+            message(3, 3, "Last call", "Bye"),
+        ];
+        let csv = to_csv(EspFormat::ConvertKit, &messages).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "Position,Send Day,Subject,Content");
+        assert_eq!(lines[1], r#"1,0,Meet it,"Hi, ""friend""""#);
+        assert!(csv.contains("3,5,Last call,Bye"));
+
+        let mailchimp = to_csv(EspFormat::Mailchimp, &messages[..1]).unwrap();
+        assert!(mailchimp.contains("1. Intro,0,Meet it,Hello,"));
+    }
+}
@@ -0,0 +1,341 @@
+//! Flexible Entity Queries
+//!
+//! Backs `query_entities`: one structured query (entity, fields, filters,
+//! sort, relations) instead of a list call plus one call per row. Field and
+//! relation names are checked against each entity's column list before any
+//! SQL is built, and every filter value is bound as a parameter. Relations
+//! are loaded with one extra query each, keyed by the parent IDs.
+
+use crate::commands::ad_generation::{ad_copy_from_row, AD_COPY_COLUMNS};
+use crate::commands::affiliate_links::{link_from_row, LINK_COLUMNS};
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::models::entity_query::{EntityQuery, QueryFilter};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params_from_iter, Connection, Row};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 1000;
+
+/// A queryable table and how its rows serialize
+struct EntityDef {
+    table: &'static str,
+    columns: &'static str,
+    relations: &'static [&'static str],
+    from_row: fn(&Row) -> rusqlite::Result<Value>,
+}
+
+const PRODUCTS: EntityDef = EntityDef {
+    table: "products",
+    columns: PRODUCT_COLUMNS,
+    relations: &["links", "active_links", "ads", "latest_ad"],
+    from_row: |row| product_from_row(row).map(|p| serde_json::to_value(p).unwrap_or_default()),
+};
+
+const LINKS: EntityDef = EntityDef {
+    table: "affiliate_links",
+    columns: LINK_COLUMNS,
+    relations: &["product"],
+    from_row: |row| link_from_row(row).map(|l| serde_json::to_value(l).unwrap_or_default()),
+};
+
+const ADS: EntityDef = EntityDef {
+    table: "ad_copies",
+    columns: AD_COPY_COLUMNS,
+    relations: &["product"],
+    from_row: |row| ad_copy_from_row(row).map(|a| serde_json::to_value(a).unwrap_or_default()),
+};
+
+fn entity(name: &str) -> Result<&'static EntityDef, String> {
+    match name {
+        "products" => Ok(&PRODUCTS),
+        "links" => Ok(&LINKS),
+        "ads" => Ok(&ADS),
+        _ => Err(format!("Unknown entity '{}'; expected products, links, or ads", name)),
+    }
+}
+
+fn column_names(def: &EntityDef) -> Vec<&'static str> {
+    def.columns.split(',').map(str::trim).collect()
+}
+
+fn check_field<'a>(def: &EntityDef, field: &'a str) -> Result<&'a str, String> {
+    if column_names(def).contains(&field) {
+        Ok(field)
+    } else {
+        Err(format!("Unknown field '{}' on {}", field, def.table))
+    }
+}
+
+/// Binds a JSON filter value; objects and nested arrays are rejected
+fn sql_value(value: &Value) -> Result<SqlValue, String> {
+    match value {
+        Value::Null => Ok(SqlValue::Null),
+        Value::Bool(b) => Ok(SqlValue::Integer(*b as i64)),
+        Value::Number(n) => Ok(match n.as_i64() {
+            Some(i) => SqlValue::Integer(i),
+            None => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        }),
+        Value::String(s) => Ok(SqlValue::Text(s.clone())),
+        other => Err(format!("Unsupported filter value {}", other)),
+    }
+}
+
+fn filter_sql(def: &EntityDef, filter: &QueryFilter, values: &mut Vec<SqlValue>) -> Result<String, String> {
+    let field = check_field(def, &filter.field)?;
+    let mut bind = |value: &Value| -> Result<String, String> {
+        values.push(sql_value(value)?);
+        Ok(format!("?{}", values.len()))
+    };
+
+    let op = match filter.op.as_str() {
+        "eq" => "=",
+        "ne" => "!=",
+        "lt" => "<",
+        "lte" => "<=",
+        "gt" => ">",
+        "gte" => ">=",
+        "like" => "LIKE",
+        "is_null" => return Ok(format!("{} IS NULL", field)),
+        "not_null" => return Ok(format!("{} IS NOT NULL", field)),
+        "in" => {
+            let items = filter
+                .value
+                .as_array()
+                .filter(|items| !items.is_empty())
+                .ok_or_else(|| format!("'in' filter on {} needs a non-empty array", field))?;
+            let placeholders = items.iter().map(&mut bind).collect::<Result<Vec<_>, _>>()?;
+            return Ok(format!("{} IN ({})", field, placeholders.join(", ")));
+        }
+        other => return Err(format!("Unknown filter op '{}'", other)),
+    };
+    Ok(format!("{} {} {}", field, op, bind(&filter.value)?))
+}
+
+/// Builds the main SELECT; all columns are selected so rows map through the
+/// entity's usual row mapper, and `fields` is applied afterwards
+fn build_select(def: &EntityDef, query: &EntityQuery) -> Result<(String, Vec<SqlValue>), String> {
+    let mut values = Vec::new();
+    let conditions = query
+        .filters
+        .iter()
+        .map(|f| filter_sql(def, f, &mut values))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut order = query
+        .sort
+        .iter()
+        .map(|s| check_field(def, &s.field).map(|f| format!("{} {}", f, if s.desc { "DESC" } else { "ASC" })))
+        .collect::<Result<Vec<_>, _>>()?;
+    order.push("id ASC".to_string());
+
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let sql = format!(
+        "SELECT {} FROM {}{} ORDER BY {} LIMIT {} OFFSET {}",
+        def.columns,
+        def.table,
+        if conditions.is_empty() { String::new() } else { format!(" WHERE {}", conditions.join(" AND ")) },
+        order.join(", "),
+        limit,
+        offset
+    );
+    Ok((sql, values))
+}
+
+/// The serialized (camelCase) key of a column
+fn json_key(column: &str) -> String {
+    let mut key = String::with_capacity(column.len());
+    let mut upper = false;
+    for c in column.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            key.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            key.push(c);
+        }
+    }
+    key
+}
+
+/// Keeps only `fields` (plus `id`) of a serialized row
+fn project(value: Value, fields: &[String]) -> Value {
+    if fields.is_empty() {
+        return value;
+    }
+    let keys: Vec<String> = fields.iter().map(|f| json_key(f)).collect();
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .filter(|(key, _)| key == "id" || keys.contains(key))
+                .collect::<Map<_, _>>(),
+        ),
+        other => other,
+    }
+}
+
+/// Rows of `def` whose `key` is one of `ids`, grouped by that key
+fn load_by(
+    conn: &Connection,
+    def: &EntityDef,
+    key: &str,
+    ids: &[i64],
+    extra: &str,
+) -> Result<HashMap<i64, Vec<Value>>, String> {
+    let mut grouped: HashMap<i64, Vec<Value>> = HashMap::new();
+    if ids.is_empty() {
+        return Ok(grouped);
+    }
+    let placeholders = (1..=ids.len()).map(|i| format!("?{}", i)).collect::<Vec<_>>().join(", ");
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM {} WHERE {} IN ({}){} ORDER BY created_at DESC, id DESC",
+            def.columns, def.table, key, placeholders, extra
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params_from_iter(ids), def.from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    let key = json_key(key);
+    for row in rows {
+        if let Some(id) = row[&key].as_i64() {
+            grouped.entry(id).or_default().push(row);
+        }
+    }
+    Ok(grouped)
+}
+
+/// Related rows loaded for one `include` entry
+struct Embedded<'a> {
+    name: &'a str,
+    key: &'static str,                 // Parent JSON key matched against the grouped rows
+    grouped: HashMap<i64, Vec<Value>>, // Newest first
+    single: bool,                      // Embed the first row instead of an array
+}
+
+fn ids_of(rows: &[Value], key: &str) -> Vec<i64> {
+    let mut ids: Vec<i64> = rows.iter().filter_map(|r| r[key].as_i64()).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
+/// Runs a query and returns one JSON object per row with relations embedded
+pub fn run(conn: &Connection, query: &EntityQuery) -> Result<Vec<Value>, String> {
+    let def = entity(&query.entity)?;
+    for field in &query.fields {
+        check_field(def, field)?;
+    }
+    if let Some(unknown) = query.include.iter().find(|r| !def.relations.contains(&r.as_str())) {
+        return Err(format!(
+            "Unknown relation '{}' on {}; expected one of {}",
+            unknown,
+            query.entity,
+            def.relations.join(", ")
+        ));
+    }
+
+    let (sql, values) = build_select(def, query)?;
+    let mut stmt = conn.prepare(&sql).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params_from_iter(values), def.from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut embedded = Vec::with_capacity(query.include.len());
+    for relation in &query.include {
+        let product_ids = || ids_of(&rows, "id");
+        let (key, grouped, single) = match relation.as_str() {
+            "links" => ("id", load_by(conn, &LINKS, "product_id", &product_ids(), "")?, false),
+            "active_links" => (
+                "id",
+                load_by(conn, &LINKS, "product_id", &product_ids(), " AND status = 'active'")?,
+                false,
+            ),
+            "ads" => ("id", load_by(conn, &ADS, "product_id", &product_ids(), "")?, false),
+            "latest_ad" => ("id", load_by(conn, &ADS, "product_id", &product_ids(), "")?, true),
+            "product" => (
+                "productId",
+                load_by(conn, &PRODUCTS, "id", &ids_of(&rows, "productId"), "")?,
+                true,
+            ),
+            _ => unreachable!("relations are validated above"),
+        };
+        embedded.push(Embedded { name: relation, key, grouped, single });
+    }
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let keys: Vec<Option<i64>> = embedded.iter().map(|e| row[e.key].as_i64()).collect();
+            let mut value = project(row, &query.fields);
+            if let Value::Object(object) = &mut value {
+                for (relation, key) in embedded.iter().zip(keys) {
+                    let related = key.and_then(|k| relation.grouped.get(&k));
+                    let related = if relation.single {
+                        related.and_then(|r| r.first().cloned()).unwrap_or(Value::Null)
+                    } else {
+                        Value::Array(related.cloned().unwrap_or_default())
+                    };
+                    object.insert(relation.name.to_string(), related);
+                }
+            }
+            value
+        })
+        .collect())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entity_query::QuerySort;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_select_binds_filters() {
+        let query = EntityQuery {
+            entity: "products".to_string(),
+            filters: vec![
+                QueryFilter { field: "category".to_string(), op: "eq".to_string(), value: json!("Beauty") },
+                QueryFilter { field: "id".to_string(), op: "in".to_string(), value: json!([1, 2]) },
+            ],
+            sort: vec![QuerySort { field: "trending_score".to_string(), desc: true }],
+            limit: Some(5000),
+            ..Default::default()
+        };
+        let (sql, values) = build_select(&PRODUCTS, &query).unwrap();
+        assert!(sql.ends_with(
+            "FROM products WHERE category = ?1 AND id IN (?2, ?3) \
+             ORDER BY trending_score DESC, id ASC LIMIT 1000 OFFSET 0"
+        ));
+        assert_eq!(values.len(), 3);
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        let query = EntityQuery {
+            entity: "links".to_string(),
+            sort: vec![QuerySort { field: "1; DROP TABLE products".to_string(), desc: false }],
+            ..Default::default()
+        };
+        assert!(build_select(&LINKS, &query).unwrap_err().contains("Unknown field"));
+        assert_eq!(
+            project(
+                json!({ "id": 1, "name": "Glow", "trendingScore": 80.0, "notes": "x" }),
+                &["name".to_string(), "trending_score".to_string()]
+            ),
+            json!({ "id": 1, "name": "Glow", "trendingScore": 80.0 })
+        );
+    }
+}
@@ -0,0 +1,435 @@
+//! Experiments
+//!
+//! Structured A/B tests over ads and links. An experiment names a hypothesis
+//! and a success metric and runs from the moment it starts until it is
+//! stopped. Analysis reads what was recorded in that window:
+//!
+//! - `ctr`: impressions and clicks from the platform figures recorded for
+//!   each variant's ad (see `variation_bandit`)
+//! - `conversion_rate`: human clicks and non-rejected conversions on each
+//!   variant's link
+//!
+//! Every variant is compared with the control (the first one) by a
+//! two-proportion z-test.
+
+use crate::models::experiment::{
+    Experiment, ExperimentAnalysis, ExperimentVariant, StartExperimentInput, VariantResult,
+};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub const RUNNING: &str = "running";
+pub const STOPPED: &str = "stopped";
+
+pub const CTR: &str = "ctr";
+pub const CONVERSION_RATE: &str = "conversion_rate";
+pub const SUCCESS_METRICS: [&str; 2] = [CTR, CONVERSION_RATE];
+
+pub const MIN_VARIANTS: usize = 2;
+pub const MAX_VARIANTS: usize = 6;
+
+/// Confidence a difference must reach to count as significant
+pub const CONFIDENCE_LEVEL: f64 = 0.95;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// =============================================================================
+// EXPERIMENTS
+// =============================================================================
+
+fn variants(conn: &Connection, experiment_id: i64) -> rusqlite::Result<Vec<ExperimentVariant>> {
+    let mut stmt = conn.prepare(
+        "SELECT v.id, v.name, v.ad_id, a.headline, v.link_id, l.short_name, v.position
+         FROM experiment_variants v
+         LEFT JOIN ad_copies a ON a.id = v.ad_id
+         LEFT JOIN affiliate_links l ON l.id = v.link_id
+         WHERE v.experiment_id = ?1 ORDER BY v.position, v.id",
+    )?;
+    let variants = stmt
+        .query_map(params![experiment_id], |row| {
+            Ok(ExperimentVariant {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                ad_id: row.get(2)?,
+                ad_headline: row.get(3)?,
+                link_id: row.get(4)?,
+                link_name: row.get(5)?,
+                position: row.get(6)?,
+            })
+        })?
+        .collect();
+    variants
+}
+
+pub fn get(conn: &Connection, experiment_id: i64) -> Result<Experiment, String> {
+    let experiment = conn
+        .query_row(
+            "SELECT id, name, hypothesis, success_metric, status, started_at, ended_at, created_at
+             FROM experiments WHERE id = ?1",
+            params![experiment_id],
+            |row| {
+                Ok(Experiment {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    hypothesis: row.get(2)?,
+                    success_metric: row.get(3)?,
+                    status: row.get(4)?,
+                    started_at: row.get(5)?,
+                    ended_at: row.get(6)?,
+                    variants: Vec::new(),
+                    created_at: row.get(7)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Experiment {} not found", experiment_id))?;
+    let variants = variants(conn, experiment_id).map_err(|e| e.to_string())?;
+    Ok(Experiment { variants, ..experiment })
+}
+
+/// Running experiments first, then the most recently started
+pub fn list(conn: &Connection) -> Result<Vec<Experiment>, String> {
+    let ids: Vec<i64> = conn
+        .prepare("SELECT id FROM experiments ORDER BY status = ?1 DESC, started_at DESC, id DESC")
+        .and_then(|mut stmt| stmt.query_map(params![RUNNING], |row| row.get(0))?.collect())
+        .map_err(|e| e.to_string())?;
+    ids.into_iter().map(|id| get(conn, id)).collect()
+}
+
+/// Validates the variants and starts the experiment now
+pub fn start(conn: &mut Connection, input: &StartExperimentInput) -> Result<Experiment, String> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err("Experiment name is required".to_string());
+    }
+    let metric = input.success_metric.trim().to_lowercase();
+    if !SUCCESS_METRICS.contains(&metric.as_str()) {
+        return Err(format!("Success metric must be one of: {}", SUCCESS_METRICS.join(", ")));
+    }
+    if input.variants.len() < MIN_VARIANTS {
+        return Err(format!("An experiment needs at least {} variants", MIN_VARIANTS));
+    }
+    if input.variants.len() > MAX_VARIANTS {
+        return Err(format!("An experiment can have at most {} variants", MAX_VARIANTS));
+    }
+    let hypothesis = input.hypothesis.as_deref().map(str::trim).filter(|h| !h.is_empty());
+
+    // Each variant as (name, ad, link), with the ad's attached link filled in
+    let mut resolved: Vec<(String, Option<i64>, Option<i64>)> = Vec::new();
+    for (i, variant) in input.variants.iter().enumerate() {
+        let variant_name = match variant.name.trim() {
+            "" => format!("Variant {}", (b'A' + i as u8) as char),
+            name => name.to_string(),
+        };
+        let mut link_id = variant.link_id;
+        if let Some(ad_id) = variant.ad_id {
+            let attached: Option<i64> = conn
+                .query_row("SELECT link_id FROM ad_copies WHERE id = ?1", params![ad_id], |row| row.get(0))
+                .optional()
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Ad {} not found", ad_id))?;
+            link_id = link_id.or(attached);
+        }
+        if let Some(link_id) = variant.link_id {
+            let exists: bool = conn
+                .query_row("SELECT EXISTS(SELECT 1 FROM affiliate_links WHERE id = ?1)", params![link_id], |row| {
+                    row.get(0)
+                })
+                .map_err(|e| e.to_string())?;
+            if !exists {
+                return Err(format!("Link {} not found", link_id));
+            }
+        }
+
+        if metric == CTR && variant.ad_id.is_none() {
+            return Err(format!("{} needs an ad: CTR is measured on ads", variant_name));
+        }
+        if metric == CONVERSION_RATE {
+            let Some(link_id) = link_id else {
+                return Err(format!("{} needs a link: conversion rate is measured on links", variant_name));
+            };
+            if resolved.iter().any(|(_, _, other)| *other == Some(link_id)) {
+                return Err("Each variant needs its own link to tell their conversions apart".to_string());
+            }
+        }
+        resolved.push((variant_name, variant.ad_id, link_id));
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO experiments (name, hypothesis, success_metric, status) VALUES (?1, ?2, ?3, ?4)",
+        params![name, hypothesis, metric, RUNNING],
+    )
+    .map_err(|e| e.to_string())?;
+    let experiment_id = tx.last_insert_rowid();
+    for (position, (variant_name, ad_id, link_id)) in resolved.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO experiment_variants (experiment_id, name, ad_id, link_id, position)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![experiment_id, variant_name, ad_id, link_id, position as i64],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    get(conn, experiment_id)
+}
+
+/// Ends a running experiment; analysis then covers start to stop
+pub fn stop(conn: &Connection, experiment_id: i64) -> Result<Experiment, String> {
+    let experiment = get(conn, experiment_id)?;
+    if experiment.status != RUNNING {
+        return Err(format!("Experiment '{}' is already stopped", experiment.name));
+    }
+    conn.execute(
+        "UPDATE experiments SET status = ?1, ended_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?2",
+        params![STOPPED, experiment_id],
+    )
+    .map_err(|e| e.to_string())?;
+    get(conn, experiment_id)
+}
+
+pub fn delete(conn: &mut Connection, experiment_id: i64) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM experiment_variants WHERE experiment_id = ?1", params![experiment_id])?;
+    tx.execute("DELETE FROM experiments WHERE id = ?1", params![experiment_id])?;
+    tx.commit()
+}
+
+// =============================================================================
+// ANALYSIS
+// =============================================================================
+
+/// Trials and successes of a variant between `start` and `end`
+fn measure(
+    conn: &Connection,
+    metric: &str,
+    variant: &ExperimentVariant,
+    start: &str,
+    end: &str,
+) -> rusqlite::Result<(i64, i64)> {
+    if metric == CTR {
+        return conn.query_row(
+            "SELECT COALESCE(SUM(impressions), 0), COALESCE(SUM(clicks), 0) FROM variation_performance
+             WHERE ad_id = ?1 AND day BETWEEN date(?2) AND date(?3)",
+            params![variant.ad_id, start, end],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        );
+    }
+
+    // Clicks that retention has rolled up only have a day; whole days count
+    let clicks: i64 = conn.query_row(
+        "SELECT (SELECT COUNT(*) FROM click_events
+                 WHERE link_id = ?1 AND bot_reason IS NULL AND clicked_at >= ?2 AND clicked_at < ?3)
+              + (SELECT COALESCE(SUM(clicks), 0) FROM click_rollups
+                 WHERE link_id = ?1 AND day BETWEEN date(?2) AND date(?3))",
+        params![variant.link_id, start, end],
+        |row| row.get(0),
+    )?;
+    let conversions: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM conversion_events
+         WHERE link_id = ?1 AND status != 'rejected' AND converted_at >= ?2 AND converted_at < ?3",
+        params![variant.link_id, start, end],
+        |row| row.get(0),
+    )?;
+    Ok((clicks, conversions))
+}
+
+/// Standard normal CDF (Abramowitz and Stegun 7.1.26, error below 1.5e-7)
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+/// Lift, z-score, and two-sided p-value of `variant` over `control`, each as
+/// `(trials, successes)`; None when either has no trials
+pub fn compare(control: (i64, i64), variant: (i64, i64)) -> Option<(Option<f64>, f64, f64)> {
+    let ((n1, x1), (n2, x2)) = (control, variant);
+    if n1 <= 0 || n2 <= 0 {
+        return None;
+    }
+    let (p1, p2) = (x1 as f64 / n1 as f64, x2 as f64 / n2 as f64);
+    let pooled = (x1 + x2) as f64 / (n1 + n2) as f64;
+    let se = (pooled * (1.0 - pooled) * (1.0 / n1 as f64 + 1.0 / n2 as f64)).sqrt();
+    let z = if se > 0.0 { (p2 - p1) / se } else { 0.0 };
+    let lift = (p1 > 0.0).then(|| (p2 - p1) / p1);
+    Some((lift, z, 2.0 * (1.0 - normal_cdf(z.abs()))))
+}
+
+/// Results for variants measured as `(trials, successes)`, control first,
+/// and the winning variant if any
+pub fn results(variants: &[ExperimentVariant], measured: &[(i64, i64)]) -> (Vec<VariantResult>, Option<i64>) {
+    let control = measured.first().copied().unwrap_or((0, 0));
+    let results: Vec<VariantResult> = variants
+        .iter()
+        .zip(measured)
+        .enumerate()
+        .map(|(i, (variant, &(trials, successes)))| {
+            let comparison = if i == 0 { None } else { compare(control, (trials, successes)) };
+            let round = |v: f64| (v * 10_000.0).round() / 10_000.0;
+            VariantResult {
+                variant_id: variant.id,
+                name: variant.name.clone(),
+                is_control: i == 0,
+                trials,
+                successes,
+                rate: (trials > 0).then(|| round(successes as f64 / trials as f64)),
+                lift: comparison.and_then(|(lift, _, _)| lift).map(round),
+                z_score: comparison.map(|(_, z, _)| round(z)),
+                p_value: comparison.map(|(_, _, p)| round(p)),
+                significant: comparison.is_some_and(|(_, _, p)| p < 1.0 - CONFIDENCE_LEVEL),
+            }
+        })
+        .collect();
+
+    // The best variant that significantly beats the control; the control
+    // when every other variant is significantly worse
+    let challengers = &results[results.len().min(1)..];
+    let winner = challengers
+        .iter()
+        .filter(|r| r.significant && r.z_score.is_some_and(|z| z > 0.0))
+        .max_by(|a, b| a.rate.unwrap_or(0.0).total_cmp(&b.rate.unwrap_or(0.0)))
+        .or_else(|| {
+            let all_worse = !challengers.is_empty()
+                && challengers.iter().all(|r| r.significant && r.z_score.is_some_and(|z| z < 0.0));
+            all_worse.then(|| &results[0])
+        })
+        .map(|r| r.variant_id);
+    (results, winner)
+}
+
+/// Lift and significance of each variant over the experiment's run so far
+pub fn analyze(conn: &Connection, experiment_id: i64) -> Result<ExperimentAnalysis, String> {
+    let experiment = get(conn, experiment_id)?;
+    let start = experiment.started_at.clone();
+    let end = experiment
+        .ended_at
+        .clone()
+        .unwrap_or_else(|| Utc::now().naive_utc().format(TIMESTAMP_FORMAT).to_string());
+
+    let measured = experiment
+        .variants
+        .iter()
+        .map(|v| measure(conn, &experiment.success_metric, v, &start, &end))
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+    let (variants, winner_variant_id) = results(&experiment.variants, &measured);
+
+    Ok(ExperimentAnalysis {
+        experiment,
+        start,
+        end,
+        confidence_level: CONFIDENCE_LEVEL,
+        variants,
+        winner_variant_id,
+    })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::experiment::ExperimentVariantInput;
+
+    fn variant(id: i64, name: &str) -> ExperimentVariant {
+        ExperimentVariant {
+            id,
+            name: name.to_string(),
+            ad_id: None,
+            ad_headline: None,
+            link_id: None,
+            link_name: None,
+            position: id - 1,
+        }
+    }
+
+    #[test]
+    fn test_compare_reports_lift_and_significance() {
+        // 5% vs 6.5% over 4000 trials each: z ≈ 2.89
+        let (lift, z, p) = compare((4000, 200), (4000, 260)).unwrap();
+        assert!((lift.unwrap() - 0.3).abs() < 1e-9);
+        assert!((z - 2.89).abs() < 0.01, "z {}", z);
+        assert!((p - 0.0038).abs() < 0.0005, "p {}", p);
+
+        // Same difference on a tenth of the traffic isn't significant
+        let (_, _, p) = compare((400, 20), (400, 26)).unwrap();
+        assert!(p > 0.05);
+        assert_eq!(compare((0, 0), (100, 5)), None);
+        assert_eq!(compare((100, 0), (100, 3)).unwrap().0, None);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_results_pick_a_significant_winner() {
+        let variants = [variant(1, "Control"), variant(2, "Question hook"), variant(3, "Discount hook")];
+        let (results, winner) = results(&variants, &[(4000, 200), (4000, 260), (4000, 210)]);
+        assert!(results[0].is_control && results[0].lift.is_none() && !results[0].significant);
+        assert_eq!(results[1].rate, Some(0.065));
+        assert!(results[1].significant && !results[2].significant);
+        assert_eq!(winner, Some(2));
+
+        // No clear difference: no winner; every challenger worse: the control
+        assert_eq!(super::results(&variants, &[(4000, 200), (4000, 205), (4000, 195)]).1, None);
+        assert_eq!(super::results(&variants, &[(4000, 260), (4000, 200), (4000, 190)]).1, Some(1));
+        let (empty, winner) = super::results(&variants, &[(0, 0), (0, 0), (0, 0)]);
+        assert!(empty.iter().all(|r| r.rate.is_none()) && winner.is_none());
+    }
+
+    #[test]
+    fn test_start_validates_variants_for_the_metric() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE ad_copies (id INTEGER PRIMARY KEY, headline TEXT NOT NULL, link_id INTEGER);
+             CREATE TABLE affiliate_links (id INTEGER PRIMARY KEY, short_name TEXT NOT NULL);
+             INSERT INTO affiliate_links VALUES (10, 'tiktok-bio'), (11, 'tiktok-bio-b');
+             INSERT INTO ad_copies VALUES (1, 'Headline A', 10), (2, 'Headline B', 10), (3, 'Headline C', NULL);",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../../migrations/053_experiments.sql")).unwrap();
+
+        let arm = |ad_id: Option<i64>, link_id: Option<i64>| ExperimentVariantInput {
+            name: String::new(),
+            ad_id,
+            link_id,
+        };
+        let input = |metric: &str, variants: Vec<ExperimentVariantInput>| StartExperimentInput {
+            name: "Hook test".to_string(),
+            hypothesis: Some("A question hook lifts CTR".to_string()),
+            success_metric: metric.to_string(),
+            variants,
+        };
+
+        // Ads sharing a link can't be told apart by conversions, but can by CTR
+        assert!(start(&mut conn, &input("conversion_rate", vec![arm(Some(1), None), arm(Some(2), None)])).is_err());
+        assert!(start(&mut conn, &input("ctr", vec![arm(Some(1), None), arm(None, Some(11))])).is_err());
+        assert!(start(&mut conn, &input("ctr", vec![arm(Some(1), None)])).is_err());
+        assert!(start(&mut conn, &input("revenue", vec![arm(Some(1), None), arm(Some(2), None)])).is_err());
+
+        let experiment =
+            start(&mut conn, &input("conversion_rate", vec![arm(Some(1), None), arm(Some(3), Some(11))])).unwrap();
+        assert_eq!(experiment.status, RUNNING);
+        let arms: Vec<(&str, Option<i64>, i64)> =
+            experiment.variants.iter().map(|v| (v.name.as_str(), v.link_id, v.position)).collect();
+        assert_eq!(arms, vec![("Variant A", Some(10), 0), ("Variant B", Some(11), 1)]);
+        assert_eq!(experiment.variants[0].link_name.as_deref(), Some("tiktok-bio"));
+
+        let stopped = stop(&conn, experiment.id).unwrap();
+        assert!(stopped.status == STOPPED && stopped.ended_at.is_some());
+        assert!(stop(&conn, experiment.id).is_err());
+
+        delete(&mut conn, experiment.id).unwrap();
+        let left: i64 = conn.query_row("SELECT COUNT(*) FROM experiment_variants", [], |r| r.get(0)).unwrap();
+        assert_eq!(left, 0);
+    }
+}
@@ -0,0 +1,386 @@
+//! Browser Extension Companion API
+//!
+//! A small localhost HTTP API for the companion browser extension. The
+//! extension can push the product page the user is viewing into the ideas
+//! inbox and pull affiliate links and ad snippets for products already in the
+//! catalog. Every request must carry the token stored in settings, either as
+//! `Authorization: Bearer <token>` or an `X-AffilAI-Token` header.
+//!
+//! Endpoints:
+//! * `GET  /api/ping` - token check
+//! * `POST /api/ideas` - JSON `{ "url": ..., "note": ... }`; refused while the workspace is read-only
+//! * `GET  /api/links?url=...|product_id=...` - affiliate links for a product
+//! * `GET  /api/ads?url=...|product_id=...` - ad snippets for a product
+
+use crate::commands::ideas::insert_idea;
+use crate::database::get_connection;
+use crate::database::settings::{get_setting, get_setting_or, get_setting_parsed, set_setting};
+use crate::models::extension_api::{ExtensionAdSnippet, ExtensionLink};
+use crate::services::postback::parse_query;
+use crate::services::read_only;
+use crate::services::scraper::extract_asin;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tauri::AppHandle;
+
+pub const DEFAULT_EXTENSION_API_PORT: u16 = 8788;
+pub const ENABLED_SETTING: &str = "extension_api_enabled";
+pub const PORT_SETTING: &str = "extension_api_port";
+pub const TOKEN_SETTING: &str = "extension_api_token";
+
+/// Largest request body accepted (product page URL + note)
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+// =============================================================================
+// CONFIGURATION
+// =============================================================================
+
+pub fn is_enabled(conn: &Connection) -> bool {
+    get_setting_or(conn, ENABLED_SETTING, "false") == "true"
+}
+
+pub fn configured_port(conn: &Connection) -> u16 {
+    get_setting_parsed(conn, PORT_SETTING, DEFAULT_EXTENSION_API_PORT)
+}
+
+/// Returns the API token, generating and storing one on first use
+pub fn ensure_token(conn: &Connection) -> rusqlite::Result<String> {
+    match get_setting(conn, TOKEN_SETTING)? {
+        Some(token) if !token.is_empty() => Ok(token),
+        _ => regenerate_token(conn),
+    }
+}
+
+/// Replaces the API token, invalidating any extension configured with the old one
+pub fn regenerate_token(conn: &Connection) -> rusqlite::Result<String> {
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    set_setting(conn, TOKEN_SETTING, &token)?;
+    Ok(token)
+}
+
+// =============================================================================
+// REQUEST HANDLING
+// =============================================================================
+
+/// A parsed HTTP request
+#[derive(Debug, Default)]
+pub struct ApiRequest {
+    pub method: String,
+    pub path: String,
+    pub query: String,
+    pub headers: HashMap<String, String>, // lowercased names
+    pub body: String,
+}
+
+/// Extracts the token from `Authorization: Bearer` or `X-AffilAI-Token`
+pub fn request_token(request: &ApiRequest) -> Option<&str> {
+    request
+        .headers
+        .get("authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .or_else(|| request.headers.get("x-affilai-token").map(|v| v.as_str()))
+        .map(|t| t.trim())
+}
+
+/// Routes an authenticated request. Returns the HTTP status and JSON body.
+pub fn handle_request(conn: &Connection, request: &ApiRequest) -> (&'static str, Value) {
+    let expected = match ensure_token(conn) {
+        Ok(token) => token,
+        Err(e) => return ("500 Internal Server Error", json!({ "error": e.to_string() })),
+    };
+    if request_token(request) != Some(expected.as_str()) {
+        return ("401 Unauthorized", json!({ "error": "Invalid or missing token" }));
+    }
+
+    let params: HashMap<String, String> = parse_query(&request.query).into_iter().collect();
+
+    let result = match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/api/ping") => Ok(json!({ "ok": true, "app": "AffilAI" })),
+        ("POST", "/api/ideas") if read_only::is_enabled(conn) => {
+            return ("403 Forbidden", json!({ "error": "The workspace is read-only" }));
+        }
+        ("POST", "/api/ideas") => push_idea(conn, &request.body),
+        ("GET", "/api/links") => {
+            resolve_product(conn, &params).and_then(|product_id| links_for(conn, product_id))
+        }
+        ("GET", "/api/ads") => {
+            resolve_product(conn, &params).and_then(|product_id| ads_for(conn, product_id))
+        }
+        _ => return ("404 Not Found", json!({ "error": "Not found" })),
+    };
+
+    match result {
+        Ok(body) => ("200 OK", body),
+        Err(e) => ("400 Bad Request", json!({ "error": e })),
+    }
+}
+
+fn push_idea(conn: &Connection, body: &str) -> Result<Value, String> {
+    let payload: Value = serde_json::from_str(body).map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    let url = payload
+        .get("url")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .ok_or_else(|| "Missing url".to_string())?;
+    let note = payload.get("note").and_then(|v| v.as_str());
+
+    let id = insert_idea(conn, url, note, Some("extension")).map_err(|e| e.to_string())?;
+    Ok(json!({ "idea_id": id }))
+}
+
+/// Finds the catalog product for `product_id=` or a product page `url=`
+/// (matched on product URL, then Amazon ASIN)
+fn resolve_product(conn: &Connection, params: &HashMap<String, String>) -> Result<i64, String> {
+    if let Some(id) = params.get("product_id") {
+        return id.parse().map_err(|_| format!("Invalid product_id: {}", id));
+    }
+
+    let url = params
+        .get("url")
+        .filter(|u| !u.is_empty())
+        .ok_or_else(|| "Pass product_id or url".to_string())?;
+
+    let found: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM products
+             WHERE product_url = ?1 OR (?2 IS NOT NULL AND amazon_asin = ?2)
+             ORDER BY product_url = ?1 DESC LIMIT 1",
+            params![url, extract_asin(url)],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    found.ok_or_else(|| "Product is not in the catalog".to_string())
+}
+
+fn links_for(conn: &Connection, product_id: i64) -> Result<Value, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, product_id, product_name, platform, program_name, tracking_url
+             FROM affiliate_links
+             WHERE product_id = ?1 AND status = 'active'
+             ORDER BY commission_rate DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let links = stmt
+        .query_map(params![product_id], |row| {
+            Ok(ExtensionLink {
+                id: row.get(0)?,
+                product_id: row.get(1)?,
+                product_name: row.get(2)?,
+                platform: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                program_name: row.get(4)?,
+                tracking_url: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(json!({ "product_id": product_id, "links": links }))
+}
+
+fn ads_for(conn: &Connection, product_id: i64) -> Result<Value, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, product_id, headline, body_text, cta, ad_type
+             FROM ad_copies WHERE product_id = ?1
+             ORDER BY created_at DESC LIMIT 20",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let ads = stmt
+        .query_map(params![product_id], |row| {
+            Ok(ExtensionAdSnippet {
+                id: row.get(0)?,
+                product_id: row.get(1)?,
+                headline: row.get(2)?,
+                body_text: row.get(3)?,
+                cta: row.get(4)?,
+                ad_type: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(json!({ "product_id": product_id, "ads": ads }))
+}
+
+// =============================================================================
+// LOCAL HTTP LISTENER
+// =============================================================================
+
+struct ServerHandle {
+    port: u16,
+    stop: Arc<AtomicBool>,
+}
+
+fn server_slot() -> &'static Mutex<Option<ServerHandle>> {
+    static SLOT: OnceLock<Mutex<Option<ServerHandle>>> = OnceLock::new();
+    SLOT.get_or_init(|| Mutex::new(None))
+}
+
+/// Returns the port of the running API server, if any
+pub fn running_port() -> Option<u16> {
+    server_slot().lock().ok()?.as_ref().map(|h| h.port)
+}
+
+/// Starts the API server on 127.0.0.1:`port`. Restarts it if already running
+/// on a different port.
+pub fn start_server(app_handle: AppHandle, port: u16) -> Result<(), String> {
+    let mut slot = server_slot().lock().map_err(|e| e.to_string())?;
+
+    if let Some(handle) = slot.as_ref() {
+        if handle.port == port {
+            return Ok(());
+        }
+        handle.stop.store(true, Ordering::SeqCst);
+        *slot = None;
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .map_err(|e| format!("Failed to bind extension API on port {}: {}", port, e))?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = stop.clone();
+
+    std::thread::spawn(move || {
+        while !stop_flag.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    if let Err(e) = serve_connection(&app_handle, stream) {
+                        eprintln!("Extension API request failed: {}", e);
+                    }
+                }
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+                Err(e) => eprintln!("Extension API listener error: {}", e),
+            }
+        }
+    });
+
+    *slot = Some(ServerHandle { port, stop });
+    println!("✓ Extension API started on port {}", port);
+    Ok(())
+}
+
+/// Stops the API server if it is running
+pub fn stop_server() {
+    if let Ok(mut slot) = server_slot().lock() {
+        if let Some(handle) = slot.take() {
+            handle.stop.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+pub fn read_request(stream: &TcpStream) -> std::io::Result<ApiRequest> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let target = parts.next().unwrap_or("");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length = headers
+        .get("content-length")
+        .and_then(|l| l.parse::<usize>().ok())
+        .unwrap_or(0)
+        .min(MAX_BODY_BYTES);
+    let mut body = vec![0u8; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(ApiRequest {
+        method,
+        path: path.to_string(),
+        query: query.to_string(),
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn serve_connection(app_handle: &AppHandle, stream: TcpStream) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    let request = read_request(&stream)?;
+
+    let (status, body) = if request.method == "OPTIONS" {
+        // CORS preflight from the extension
+        ("204 No Content", String::new())
+    } else {
+        let (status, value) = match get_connection(app_handle) {
+            Ok(conn) => handle_request(&conn, &request),
+            Err(e) => ("500 Internal Server Error", json!({ "error": e.to_string() })),
+        };
+        (status, value.to_string())
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Headers: Authorization, Content-Type, X-AffilAI-Token\r\n\
+         Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(headers: &[(&str, &str)]) -> ApiRequest {
+        ApiRequest {
+            method: "GET".to_string(),
+            path: "/api/ping".to_string(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_request_token_sources() {
+        assert_eq!(request_token(&request(&[("authorization", "Bearer abc")])), Some("abc"));
+        assert_eq!(request_token(&request(&[("x-affilai-token", "xyz")])), Some("xyz"));
+        assert_eq!(request_token(&request(&[("authorization", "Basic abc")])), None);
+        assert_eq!(request_token(&request(&[])), None);
+    }
+}
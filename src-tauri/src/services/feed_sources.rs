@@ -0,0 +1,426 @@
+//! RSS / Atom Feed Sources
+//!
+//! Keeps the research pipeline fed from deal blogs and trend newsletters.
+//! Configured feeds are polled by the scheduled `feed_sources` job (or on
+//! demand); each item not seen before becomes ideas in the inbox, one per
+//! product link found in the item, or a single idea for the item itself when
+//! it links to no product. Every item read is remembered per feed, so items
+//! only ever arrive once and a new feed's backlog is capped.
+
+use crate::commands::ideas::insert_idea;
+use crate::database::get_connection;
+use crate::models::feed_source::{FeedPollReport, FeedSource, SaveFeedSourceInput};
+use crate::services::scraper::{decode_entities, detect_platform, fetch_html};
+use regex::Regex;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use tauri::AppHandle;
+
+pub const IDEA_SOURCE: &str = "rss";
+
+/// New items turned into ideas per poll; the rest are only marked seen
+const MAX_NEW_ITEMS: usize = 20;
+const MAX_LINKS_PER_ITEM: usize = 5;
+
+/// URL path fragments of product pages on stores `detect_platform` doesn't know
+const PRODUCT_PATH_MARKERS: [&str; 7] =
+    ["/dp/", "/gp/product/", "/products/", "/product/", "/listing/", "/itm/", "/ip/"];
+
+const SOURCE_COLUMNS: &str = "id, name, url, active, last_polled_at, last_error, created_at";
+
+fn source_from_row(row: &Row) -> rusqlite::Result<FeedSource> {
+    Ok(FeedSource {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        url: row.get(2)?,
+        active: row.get(3)?,
+        last_polled_at: row.get(4)?,
+        last_error: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
+
+/// One item of a feed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedEntry {
+    pub guid: String,
+    pub title: String,
+    pub link: Option<String>,
+    pub content: String, // description / summary / content HTML
+}
+
+// =============================================================================
+// SOURCES
+// =============================================================================
+
+pub fn list(conn: &Connection) -> rusqlite::Result<Vec<FeedSource>> {
+    let mut stmt =
+        conn.prepare(&format!("SELECT {} FROM feed_sources ORDER BY name COLLATE NOCASE, id", SOURCE_COLUMNS))?;
+    let sources = stmt.query_map([], source_from_row)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(sources)
+}
+
+pub fn get(conn: &Connection, id: i64) -> Result<FeedSource, String> {
+    conn.query_row(&format!("SELECT {} FROM feed_sources WHERE id = ?1", SOURCE_COLUMNS), params![id], source_from_row)
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Feed source {} not found", id))
+}
+
+/// Creates or updates a source; a changed URL starts its seen items over
+pub fn save(conn: &Connection, input: &SaveFeedSourceInput) -> Result<FeedSource, String> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err("Feed name is required".to_string());
+    }
+    let url = input.url.trim();
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err("Feed URL must start with http:// or https://".to_string());
+    }
+    let duplicate: Option<String> = conn
+        .query_row(
+            "SELECT name FROM feed_sources WHERE url = ?1 AND id IS NOT ?2",
+            params![url, input.id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if let Some(existing) = duplicate {
+        return Err(format!("This feed is already added as '{}'", existing));
+    }
+
+    let id = match input.id {
+        Some(id) => {
+            let previous = get(conn, id)?;
+            conn.execute(
+                "UPDATE feed_sources SET name = ?1, url = ?2, active = ?3 WHERE id = ?4",
+                params![name, url, input.active, id],
+            )
+            .map_err(|e| e.to_string())?;
+            if previous.url != url {
+                conn.execute("DELETE FROM feed_source_items WHERE source_id = ?1", params![id])
+                    .map_err(|e| e.to_string())?;
+            }
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO feed_sources (name, url, active) VALUES (?1, ?2, ?3)",
+                params![name, url, input.active],
+            )
+            .map_err(|e| e.to_string())?;
+            conn.last_insert_rowid()
+        }
+    };
+    get(conn, id)
+}
+
+pub fn delete(conn: &mut Connection, id: i64) -> Result<(), String> {
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM feed_source_items WHERE source_id = ?1", params![id]).map_err(|e| e.to_string())?;
+    let deleted = tx.execute("DELETE FROM feed_sources WHERE id = ?1", params![id]).map_err(|e| e.to_string())?;
+    if deleted == 0 {
+        return Err(format!("Feed source {} not found", id));
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+// =============================================================================
+// FEED PARSING
+// =============================================================================
+
+/// Text of the first `<tag>` in `block`, with CDATA unwrapped or entities decoded
+fn tag_text(block: &str, tag: &str) -> Option<String> {
+    let re = Regex::new(&format!(r"(?is)<{tag}(?:\s[^>]*)?>(.*?)</{tag}>", tag = regex::escape(tag))).ok()?;
+    let raw = re.captures(block)?.get(1)?.as_str().trim();
+    let text = match raw.strip_prefix("<![CDATA[").and_then(|r| r.strip_suffix("]]>")) {
+        Some(cdata) => cdata.to_string(),
+        None => decode_entities(raw),
+    };
+    Some(text.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+/// An Atom entry's `<link href>`: the alternate link, or one without a `rel`
+fn atom_link(block: &str) -> Option<String> {
+    let link_re = Regex::new(r"(?is)<link\s[^>]*>").ok()?;
+    let attr_re = Regex::new(r#"(?is)(href|rel)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).ok()?;
+    let link = link_re.find_iter(block).find_map(|tag| {
+        let (mut href, mut rel) = (None, None);
+        for caps in attr_re.captures_iter(tag.as_str()) {
+            let value = caps.get(2).or_else(|| caps.get(3)).map(|m| decode_entities(m.as_str()));
+            match caps[1].to_lowercase().as_str() {
+                "href" => href = value,
+                _ => rel = value,
+            }
+        }
+        href.filter(|_| rel.as_deref().is_none_or(|r| r.eq_ignore_ascii_case("alternate")))
+    });
+    link
+}
+
+/// The items of an RSS 2.0 / RSS 1.0 or Atom document, in feed order
+pub fn parse_feed(xml: &str) -> Vec<FeedEntry> {
+    let entry_re = match Regex::new(r"(?is)<(item|entry)(?:\s[^>]*)?>(.*?)</(?:item|entry)>") {
+        Ok(re) => re,
+        Err(_) => return Vec::new(),
+    };
+    entry_re
+        .captures_iter(xml)
+        .filter_map(|caps| {
+            let block = &caps[2];
+            let link = if caps[1].eq_ignore_ascii_case("entry") {
+                atom_link(block)
+            } else {
+                tag_text(block, "link")
+            }
+            .filter(|l| l.starts_with("http"));
+            let guid = tag_text(block, "guid").or_else(|| tag_text(block, "id")).or_else(|| link.clone())?;
+            let content = ["content:encoded", "content", "description", "summary"]
+                .iter()
+                .filter_map(|tag| tag_text(block, tag))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let title = tag_text(block, "title").unwrap_or_else(|| link.clone().unwrap_or_default());
+            Some(FeedEntry { guid, title, link, content })
+        })
+        .collect()
+}
+
+fn is_product_link(url: &str) -> bool {
+    let lower = url.to_lowercase();
+    lower.starts_with("http")
+        && (detect_platform(url).is_some() || PRODUCT_PATH_MARKERS.iter().any(|marker| lower.contains(marker)))
+}
+
+/// Product links in an item: its own link when it is one, then the links in
+/// its content, without duplicates
+pub fn product_links(entry: &FeedEntry) -> Vec<String> {
+    let href_re = match Regex::new(r#"(?is)href\s*=\s*(?:"([^"]*)"|'([^']*)')"#) {
+        Ok(re) => re,
+        Err(_) => return Vec::new(),
+    };
+    let hrefs = href_re
+        .captures_iter(&entry.content)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .map(|m| decode_entities(m.as_str().trim()));
+
+    let mut links: Vec<String> = Vec::new();
+    for url in entry.link.iter().cloned().chain(hrefs) {
+        if is_product_link(&url) && !links.contains(&url) {
+            links.push(url);
+        }
+    }
+    links.truncate(MAX_LINKS_PER_ITEM);
+    links
+}
+
+// =============================================================================
+// POLLING
+// =============================================================================
+
+/// Marks the entries seen and sends the unseen ones to the ideas inbox.
+/// URLs already in the catalog are left out.
+pub fn record_entries(
+    conn: &mut Connection,
+    source: &FeedSource,
+    entries: &[FeedEntry],
+) -> rusqlite::Result<FeedPollReport> {
+    let tx = conn.transaction()?;
+    let mut report = FeedPollReport {
+        source_id: source.id,
+        name: source.name.clone(),
+        items_read: entries.len() as i64,
+        ..Default::default()
+    };
+    for entry in entries {
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO feed_source_items (source_id, guid) VALUES (?1, ?2)",
+            params![source.id, entry.guid],
+        )?;
+        if inserted == 0 {
+            continue;
+        }
+        report.new_items += 1;
+        if report.new_items as usize > MAX_NEW_ITEMS {
+            continue;
+        }
+
+        let links = product_links(entry);
+        let ideas: Vec<(String, String)> = if links.is_empty() {
+            entry
+                .link
+                .iter()
+                .map(|link| (link.clone(), format!("{} ({}; no product links found)", entry.title, source.name)))
+                .collect()
+        } else {
+            let from = entry.link.as_deref().map(|link| format!("\nFrom: {}", link)).unwrap_or_default();
+            links.into_iter().map(|url| (url, format!("{} ({}){}", entry.title, source.name, from))).collect()
+        };
+        for (url, note) in ideas {
+            let in_catalog: bool =
+                tx.query_row("SELECT EXISTS(SELECT 1 FROM products WHERE product_url = ?1)", params![url], |row| {
+                    row.get(0)
+                })?;
+            if !in_catalog {
+                report.idea_ids.push(insert_idea(&tx, &url, Some(&note), Some(IDEA_SOURCE))?);
+            }
+        }
+    }
+    tx.execute(
+        "UPDATE feed_sources SET last_polled_at = CURRENT_TIMESTAMP, last_error = NULL WHERE id = ?1",
+        params![source.id],
+    )?;
+    tx.commit()?;
+    Ok(report)
+}
+
+/// Fetches and records one feed; failures are stored on the source and reported
+pub async fn poll(app_handle: &AppHandle, source: &FeedSource) -> FeedPollReport {
+    let fetched = fetch_html(&source.url).await.and_then(|xml| {
+        let entries = parse_feed(&xml);
+        if entries.is_empty() && !xml.contains("<rss") && !xml.contains("<feed") && !xml.contains("<rdf:RDF") {
+            return Err(format!("{} is not an RSS or Atom feed", source.url));
+        }
+        Ok(entries)
+    });
+    let recorded = fetched.and_then(|entries| {
+        let mut conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        record_entries(&mut conn, source, &entries).map_err(|e| e.to_string())
+    });
+
+    recorded.unwrap_or_else(|error| {
+        if let Ok(conn) = get_connection(app_handle) {
+            if let Err(e) = conn.execute(
+                "UPDATE feed_sources SET last_polled_at = CURRENT_TIMESTAMP, last_error = ?1 WHERE id = ?2",
+                params![error, source.id],
+            ) {
+                eprintln!("Failed to record feed error for {}: {}", source.name, e);
+            }
+        }
+        FeedPollReport { source_id: source.id, name: source.name.clone(), error: Some(error), ..Default::default() }
+    })
+}
+
+/// Polls one source, or every active one
+pub async fn poll_sources(app_handle: &AppHandle, source_id: Option<i64>) -> Result<Vec<FeedPollReport>, String> {
+    let sources = {
+        let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        match source_id {
+            Some(id) => vec![get(&conn, id)?],
+            None => list(&conn).map_err(|e| e.to_string())?.into_iter().filter(|s| s.active).collect(),
+        }
+    };
+    let mut reports = Vec::new();
+    for source in &sources {
+        reports.push(poll(app_handle, source).await);
+    }
+    Ok(reports)
+}
+
+/// Scheduled job: polls every active feed
+pub fn run_feed_job(app_handle: &AppHandle) -> Result<String, String> {
+    let reports = tauri::async_runtime::block_on(poll_sources(app_handle, None))?;
+    if reports.is_empty() {
+        return Ok("No feed sources configured".to_string());
+    }
+    let failed: Vec<String> = reports
+        .iter()
+        .filter_map(|r| r.error.as_ref().map(|e| format!("{}: {}", r.name, e)))
+        .collect();
+    if failed.len() == reports.len() {
+        return Err(failed.join("; "));
+    }
+    let mut summary = vec![format!(
+        "{} feed(s), {} new item(s), {} idea(s)",
+        reports.len() - failed.len(),
+        reports.iter().map(|r| r.new_items).sum::<i64>(),
+        reports.iter().map(|r| r.idea_ids.len()).sum::<usize>()
+    )];
+    summary.extend(failed);
+    Ok(summary.join("; "))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Deals</title>
+        <item><title>Air fryer &amp; mug deals</title><link>https://deals.example.com/air-fryer</link>
+          <guid>deal-1</guid>
+          <description><![CDATA[<p>See <a href="https://www.amazon.com/dp/B0TEST1234?tag=x">the fryer</a>,
+            <a href='https://shop.example.com/products/mug'>the mug</a> and
+            <a href="https://deals.example.com/about">us</a>.</p>]]></description></item>
+        <item><title>Weekly trends</title><link>https://deals.example.com/trends</link></item>
+        </channel></rss>"#;
+
+    const ATOM: &str = r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>Finds</title>
+        <entry><title>Lamp</title><id>tag:finds,1</id>
+          <link rel="self" href="https://finds.example.com/self/1"/>
+          <link rel="alternate" href="https://www.etsy.com/listing/42/lamp"/>
+          <summary type="html">&lt;a href="https://www.ebay.com/itm/77"&gt;also here&lt;/a&gt;</summary></entry>
+        </feed>"#;
+
+    #[test]
+    fn test_parse_feed_and_extract_product_links() {
+        let entries = parse_feed(RSS);
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].guid.as_str(), entries[0].title.as_str()), ("deal-1", "Air fryer & mug deals"));
+        assert_eq!(
+            product_links(&entries[0]),
+            ["https://www.amazon.com/dp/B0TEST1234?tag=x", "https://shop.example.com/products/mug"]
+        );
+        // No guid: the link identifies the item
+        assert_eq!(entries[1].guid, "https://deals.example.com/trends");
+        assert!(product_links(&entries[1]).is_empty());
+
+        let atom = parse_feed(ATOM);
+        assert_eq!(atom[0].link.as_deref(), Some("https://www.etsy.com/listing/42/lamp"));
+        assert_eq!(product_links(&atom[0]), ["https://www.etsy.com/listing/42/lamp", "https://www.ebay.com/itm/77"]);
+    }
+
+    #[test]
+    fn test_new_items_become_ideas_once() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, product_url TEXT);
+             CREATE TABLE ideas (id INTEGER PRIMARY KEY, url TEXT NOT NULL, note TEXT, source TEXT,
+                status TEXT DEFAULT 'new', product_id INTEGER, captured_at DATETIME);
+             INSERT INTO products (product_url) VALUES ('https://shop.example.com/products/mug');",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../../migrations/056_feed_sources.sql")).unwrap();
+        let input = SaveFeedSourceInput {
+            id: None,
+            name: "Deals".to_string(),
+            url: "https://deals.example.com/feed".to_string(),
+            active: true,
+        };
+        let source = save(&conn, &input).unwrap();
+
+        let report = record_entries(&mut conn, &source, &parse_feed(RSS)).unwrap();
+        assert_eq!((report.items_read, report.new_items), (2, 2));
+        // The fryer, plus the trends post itself; the mug is already a product
+        let ideas: Vec<(String, String)> = conn
+            .prepare("SELECT url, note FROM ideas ORDER BY id")
+            .unwrap()
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(report.idea_ids.len(), 2);
+        assert_eq!(ideas[0].0, "https://www.amazon.com/dp/B0TEST1234?tag=x");
+        assert!(ideas[0].1.ends_with("From: https://deals.example.com/air-fryer"));
+        assert!(ideas[1].1.contains("no product links found"));
+
+        let again = record_entries(&mut conn, &source, &parse_feed(RSS)).unwrap();
+        assert_eq!((again.new_items, again.idea_ids.len()), (0, 0));
+
+        delete(&mut conn, source.id).unwrap();
+        let items: i64 = conn.query_row("SELECT COUNT(*) FROM feed_source_items", [], |r| r.get(0)).unwrap();
+        assert_eq!(items, 0);
+        assert!(delete(&mut conn, source.id).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! GeoIP Lookup
+//!
+//! Optional, fully offline country/region lookup for logged clicks. The user
+//! supplies a MaxMind database (GeoLite2 or GeoIP2 Country/City `.mmdb`); its
+//! path is kept in settings and the reader is cached after the first lookup.
+//! Without a database clicks are simply logged without a location.
+
+use crate::database::settings::get_setting;
+use crate::models::click_analytics::GeoIpStatus;
+use chrono::DateTime;
+use maxminddb::{geoip2, Reader};
+use rusqlite::Connection;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, OnceLock};
+
+pub const PATH_SETTING: &str = "geoip_database_path";
+
+type CachedReader = Option<(String, Arc<Reader<Vec<u8>>>)>;
+
+/// Country and first-level subdivision of an IP address
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoLocation {
+    pub country: String,        // ISO 3166-1 alpha-2, e.g. "US"
+    pub region: Option<String>, // Subdivision ISO code, e.g. "CA"; City databases only
+}
+
+fn reader_slot() -> &'static Mutex<CachedReader> {
+    static SLOT: OnceLock<Mutex<CachedReader>> = OnceLock::new();
+    SLOT.get_or_init(|| Mutex::new(None))
+}
+
+/// Opens (or reuses) the reader for `path`
+fn reader(path: &str) -> Result<Arc<Reader<Vec<u8>>>, String> {
+    let mut slot = reader_slot().lock().map_err(|e| e.to_string())?;
+    if let Some((cached_path, reader)) = slot.as_ref() {
+        if cached_path == path {
+            return Ok(reader.clone());
+        }
+    }
+
+    let reader = Arc::new(
+        Reader::open_readfile(path).map_err(|e| format!("Failed to open GeoIP database {}: {}", path, e))?,
+    );
+    *slot = Some((path.to_string(), reader.clone()));
+    Ok(reader)
+}
+
+/// Drops the cached reader so a replaced or removed database file is reloaded
+pub fn clear_cache() {
+    if let Ok(mut slot) = reader_slot().lock() {
+        *slot = None;
+    }
+}
+
+/// Describes the configured database, including why it can't be used
+pub fn status(conn: &Connection) -> rusqlite::Result<GeoIpStatus> {
+    let database_path = get_setting(conn, PATH_SETTING)?.filter(|p| !p.is_empty());
+    let mut status = GeoIpStatus { database_path: database_path.clone(), ..Default::default() };
+
+    if let Some(path) = database_path {
+        match reader(&path) {
+            Ok(reader) => {
+                status.database_type = Some(reader.metadata.database_type.clone());
+                status.built_at = DateTime::from_timestamp(reader.metadata.build_epoch as i64, 0)
+                    .map(|built| built.format("%Y-%m-%d").to_string());
+            }
+            Err(e) => status.error = Some(e),
+        }
+    }
+    Ok(status)
+}
+
+/// Checks that `path` is a readable MaxMind database before it is saved
+pub fn validate(path: &str) -> Result<(), String> {
+    let reader = reader(path)?;
+    if reader.lookup::<geoip2::Country>(IpAddr::from([8, 8, 8, 8])).is_err()
+        && reader.lookup::<geoip2::Country>(IpAddr::from([1, 1, 1, 1])).is_err()
+    {
+        return Err(format!(
+            "{} is a {} database without country data",
+            path, reader.metadata.database_type
+        ));
+    }
+    Ok(())
+}
+
+/// Looks up where `ip` is. Returns `None` when no database is configured, it
+/// can't be opened, or the address isn't in it (private and loopback ranges).
+pub fn lookup(conn: &Connection, ip: IpAddr) -> Option<GeoLocation> {
+    let path = get_setting(conn, PATH_SETTING).ok().flatten().filter(|p| !p.is_empty())?;
+    let reader = reader(&path).map_err(|e| eprintln!("GeoIP lookup skipped: {}", e)).ok()?;
+    let city: geoip2::City = reader.lookup(ip).ok()?;
+    location_from(&city)
+}
+
+/// Extracts the country and region codes from a City (or Country) record
+fn location_from(city: &geoip2::City) -> Option<GeoLocation> {
+    let country = [&city.country, &city.registered_country]
+        .into_iter()
+        .find_map(|c| c.as_ref().and_then(|c| c.iso_code))?;
+    let region = city
+        .subdivisions
+        .as_ref()
+        .and_then(|subdivisions| subdivisions.first())
+        .and_then(|s| s.iso_code)
+        .map(str::to_string);
+    Some(GeoLocation { country: country.to_string(), region })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn country(iso_code: Option<&str>) -> Option<geoip2::city::Country<'_>> {
+        Some(geoip2::city::Country { iso_code, geoname_id: None, is_in_european_union: None, names: None })
+    }
+
+    #[test]
+    fn test_location_prefers_country_over_registered() {
+        let record = |iso: Option<&'static str>, registered: Option<&'static str>| geoip2::City {
+            city: None,
+            continent: None,
+            country: country(iso),
+            location: None,
+            postal: None,
+            registered_country: country(registered),
+            represented_country: None,
+            subdivisions: Some(vec![geoip2::city::Subdivision { iso_code: Some("ON"), geoname_id: None, names: None }]),
+            traits: None,
+        };
+
+        let located = location_from(&record(Some("CA"), Some("US"))).unwrap();
+        assert_eq!(located, GeoLocation { country: "CA".to_string(), region: Some("ON".to_string()) });
+        assert_eq!(location_from(&record(None, Some("US"))).unwrap().country, "US");
+        assert!(location_from(&record(None, None)).is_none());
+    }
+}
@@ -0,0 +1,285 @@
+//! Headline Variant Generator
+//!
+//! Writes alternative headlines for an existing ad and ranks them by
+//! heuristic click-through signals: length, numbers, power words, curiosity,
+//! and direct address. The ad's current headline is kept as the selected
+//! variant so switching back is always possible; selecting another variant copies it
+//! onto the ad.
+
+use crate::commands::ad_generation::GeneratedAdCopy;
+use crate::models::ai_discovery::ParseDiagnostic;
+use crate::models::headline_variant::{CtrSignals, HeadlineVariant};
+use crate::models::niche_profile::NicheProfile;
+use crate::models::product::Product;
+use crate::services::ai_validation::validate_array;
+use crate::services::niche;
+use rusqlite::{params, Connection, Row};
+
+pub const DEFAULT_VARIANT_COUNT: i32 = 5;
+pub const MAX_VARIANT_COUNT: i32 = 10;
+
+/// `served_by` of the variant holding the ad's headline at generation time
+pub const CURRENT_SOURCE: &str = "current";
+
+pub const VARIANTS_PROMPT: &str = r#"You are a direct-response copywriter. Write {count} alternative headlines for this affiliate ad.
+
+Ad:
+- Type: {ad_type}
+- Product: {product_name} ({category})
+- Current Headline: {headline}
+- Body: {body}
+
+Niche Profile:
+{niche_context}
+
+Return ONLY a valid JSON array of headline strings:
+["Headline one", "Headline two"]
+
+Rules:
+- Exactly {count} headlines, each meaningfully different from the current one
+- Mix formats: questions, numbers, how/why, bold statements
+- Keep each under 80 characters
+- Never make claims the niche profile bans
+- NO explanatory text, ONLY the JSON array"#;
+
+/// Schema for the array requested by `VARIANTS_PROMPT`
+pub const VARIANTS_SCHEMA: &str = r#"{
+  "type": "array",
+  "minItems": 1,
+  "items": { "type": "string", "minLength": 1, "maxLength": 150 }
+}"#;
+
+pub const VARIANT_COLUMNS: &str =
+    "id, ad_id, headline, ctr_score, signals, is_selected, served_by, created_at";
+
+const POWER_WORDS: [&str; 14] = [
+    "secret", "finally", "instantly", "easy", "simple", "new", "stop", "hack", "mistake",
+    "mistakes", "effortless", "free", "proof", "honest",
+];
+const CURIOSITY_OPENERS: [&str; 4] = ["why", "how", "what", "this"];
+
+/// Maps a row selected with `VARIANT_COLUMNS` into a `HeadlineVariant`
+pub fn variant_from_row(row: &Row) -> rusqlite::Result<HeadlineVariant> {
+    let signals: Option<String> = row.get(4)?;
+    Ok(HeadlineVariant {
+        id: row.get(0)?,
+        ad_id: row.get(1)?,
+        headline: row.get(2)?,
+        ctr_score: row.get(3)?,
+        signals: signals
+            .and_then(|j| serde_json::from_str(&j).ok())
+            .unwrap_or_default(),
+        is_selected: row.get::<_, i32>(5)? != 0,
+        served_by: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+/// Clamps a requested variant count into the supported range
+pub fn clamp_count(count: Option<i32>) -> i32 {
+    count.unwrap_or(DEFAULT_VARIANT_COUNT).clamp(1, MAX_VARIANT_COUNT)
+}
+
+pub fn build_variants_prompt(ad: &GeneratedAdCopy, product: &Product, niche_context: &str, count: i32) -> String {
+    VARIANTS_PROMPT
+        .replace("{count}", &count.to_string())
+        .replace("{ad_type}", ad.ad_type.as_deref().unwrap_or("social_post"))
+        .replace("{product_name}", &product.name)
+        .replace("{category}", &product.category)
+        .replace("{headline}", &ad.headline)
+        .replace("{body}", ad.body_text.as_deref().unwrap_or(""))
+        .replace("{niche_context}", niche_context)
+}
+
+/// Extracts and schema-validates a variants response
+pub fn validate_variants(response: &str) -> Result<Vec<String>, ParseDiagnostic> {
+    validate_array(response, VARIANTS_SCHEMA)
+}
+
+/// Template headlines used with the mock provider and as the last fallback
+pub fn mock_headlines(product: &Product) -> Vec<String> {
+    let name = &product.name;
+    let category = product.category.to_lowercase();
+    vec![
+        format!("Why everyone is switching to {}", name),
+        format!("3 reasons {} is worth it", name),
+        format!("The {} secret nobody talks about", category),
+        format!("I tried {} for 7 days. Here's what happened", name),
+        format!("Is {} worth the hype?", name),
+        format!("{}: the {} upgrade you didn't know you needed", name, category),
+        format!("3 {} mistakes you're probably making", category),
+        format!("Finally, a {} pick that actually delivers", category),
+        format!("How {} makes your {} routine effortless", name, category),
+        format!("Stop scrolling if you need a better {}", category),
+    ]
+}
+
+/// Scores a headline 0-100 on heuristic click-through signals
+pub fn score_headline(headline: &str) -> (f64, CtrSignals) {
+    let lower = headline.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mut signals = CtrSignals::default();
+    let mut score = 0;
+
+    // Headlines around 40-60 characters read fully in feeds and search
+    signals.length_points = match headline.chars().count() {
+        40..=60 => 25,
+        25..=39 | 61..=80 => 15,
+        _ => 5,
+    };
+    score += signals.length_points;
+
+    signals.has_number = headline.chars().any(|c| c.is_ascii_digit());
+    if signals.has_number {
+        score += 20;
+    }
+
+    signals.power_words = POWER_WORDS
+        .iter()
+        .filter(|p| words.contains(p))
+        .map(|p| p.to_string())
+        .collect();
+    score += 8 * signals.power_words.len().min(3) as i32;
+
+    signals.curiosity = headline.trim_end().ends_with('?')
+        || headline.contains("...")
+        || lower.starts_with("the reason")
+        || words.first().is_some_and(|w| CURIOSITY_OPENERS.contains(w))
+        || words.contains(&"nobody");
+    if signals.curiosity {
+        score += 15;
+    }
+
+    signals.direct_address = words.iter().any(|w| w.starts_with("you"));
+    if signals.direct_address {
+        score += 10;
+    }
+
+    let shouted = headline
+        .split_whitespace()
+        .filter(|w| w.chars().filter(|c| c.is_alphabetic()).count() > 3 && !w.chars().any(|c| c.is_lowercase()))
+        .count();
+    if shouted > 2 {
+        score -= 10;
+        signals.penalties.push("Too many all-caps words".to_string());
+    }
+    if headline.matches('!').count() > 1 {
+        score -= 5;
+        signals.penalties.push("Multiple exclamation marks".to_string());
+    }
+
+    (score.clamp(0, 100) as f64, signals)
+}
+
+/// Up to `count` distinct, compliant candidates (excluding the current
+/// headline), ranked best first
+pub fn rank_candidates(
+    candidates: Vec<String>,
+    current: &str,
+    profile: Option<&NicheProfile>,
+    count: usize,
+) -> Vec<(String, f64, CtrSignals)> {
+    let current = current.trim().to_lowercase();
+    let mut seen = vec![current];
+    let mut ranked = Vec::new();
+    for candidate in candidates {
+        let headline = candidate.trim().to_string();
+        let key = headline.to_lowercase();
+        if headline.is_empty() || seen.contains(&key) || niche::contains_banned_claim(&headline, profile) {
+            continue;
+        }
+        seen.push(key);
+        let (score, signals) = score_headline(&headline);
+        ranked.push((headline, score, signals));
+    }
+
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    ranked.truncate(count);
+    ranked
+}
+
+/// Replaces an ad's variants with the ad's own headline (selected) plus the
+/// ranked candidates
+pub fn save_all(
+    conn: &mut Connection,
+    ad: &GeneratedAdCopy,
+    ranked: &[(String, f64, CtrSignals)],
+    served_by: &str,
+) -> Result<Vec<HeadlineVariant>, String> {
+    let ad_id = ad.id.ok_or("Ad has no id")?;
+    let (current_score, current_signals) = score_headline(&ad.headline);
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM headline_variants WHERE ad_id = ?1", params![ad_id])
+        .map_err(|e| e.to_string())?;
+
+    let current = std::iter::once((&ad.headline, current_score, &current_signals, true, CURRENT_SOURCE));
+    let generated = ranked.iter().map(|(h, score, signals)| (h, *score, signals, false, served_by));
+    for (headline, score, signals, selected, source) in current.chain(generated) {
+        tx.execute(
+            "INSERT INTO headline_variants (ad_id, headline, ctr_score, signals, is_selected, served_by)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                ad_id,
+                headline,
+                score,
+                serde_json::to_string(signals).map_err(|e| e.to_string())?,
+                selected,
+                source,
+            ],
+        )
+        .map_err(|e| format!("Failed to save headline variant: {}", e))?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    list_for_ad(conn, ad_id).map_err(|e| e.to_string())
+}
+
+/// An ad's variants, best CTR score first
+pub fn list_for_ad(conn: &Connection, ad_id: i64) -> rusqlite::Result<Vec<HeadlineVariant>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM headline_variants WHERE ad_id = ?1 ORDER BY ctr_score DESC, id",
+        VARIANT_COLUMNS
+    ))?;
+    let variants = stmt
+        .query_map(params![ad_id], variant_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(variants)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_rewards_ctr_signals() {
+        let (plain, _) = score_headline("Serum");
+        let (strong, signals) = score_headline("Why 9 of 10 of you are finally ditching retinol?");
+        assert!(strong > plain);
+        assert!(signals.has_number && signals.curiosity && signals.direct_address);
+        assert_eq!(signals.power_words, vec!["finally"]);
+
+        let (_, shouty) = score_headline("BEST DEAL EVER TODAY!!");
+        assert_eq!(shouty.penalties.len(), 2);
+    }
+
+    #[test]
+    fn test_rank_candidates_dedupes_and_skips_current() {
+        let candidates = vec![
+            "Meet Glow".to_string(),
+            "meet glow".to_string(),
+            "Is Glow worth the hype?".to_string(),
+            "Shop Glow now".to_string(),
+        ];
+        let ranked = rank_candidates(candidates, "Shop Glow Now", None, 5);
+        let headlines: Vec<&str> = ranked.iter().map(|(h, _, _)| h.as_str()).collect();
+        assert_eq!(headlines, vec!["Is Glow worth the hype?", "Meet Glow"]);
+    }
+}
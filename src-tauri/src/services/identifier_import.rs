@@ -0,0 +1,213 @@
+//! Identifier Import
+//!
+//! Turns a plain list of Amazon ASINs and product URLs (a bookmark dump, a
+//! research spreadsheet column) into catalog entries. Each new identifier
+//! becomes a stub product named from its URL; an `enrich` bulk operation then
+//! scrapes every stub's page and fills in title, description, image, and price.
+//! Identifiers already in the catalog are reported instead of duplicated.
+
+use crate::commands::ideas::name_from_url;
+use crate::models::scraped_product::ScrapedProduct;
+use crate::services::availability;
+use crate::services::bulk_operations::NewItem;
+use crate::services::scraper::{extract_asin, price_to_range};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashSet;
+
+pub const IMPORT_TAG: &str = "imported";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Identifier {
+    Asin(String),
+    Url(String),
+}
+
+impl Identifier {
+    /// Parses one list entry; extra columns after a tab, comma, or space are ignored
+    pub fn parse(line: &str) -> Option<Identifier> {
+        let token = line.trim().split(['\t', ' ']).next()?.trim_end_matches(',');
+        let lower = token.to_lowercase();
+        if lower.starts_with("http://") || lower.starts_with("https://") {
+            return Some(Identifier::Url(token.to_string()));
+        }
+        let token = token.split(',').next()?;
+        let is_asin = token.len() == 10
+            && token.chars().all(|c| c.is_ascii_alphanumeric())
+            && token.chars().any(|c| c.is_ascii_digit());
+        is_asin.then(|| Identifier::Asin(token.to_uppercase()))
+    }
+
+    pub fn url(&self) -> String {
+        match self {
+            Identifier::Asin(asin) => format!("https://www.amazon.com/dp/{}", asin),
+            Identifier::Url(url) => url.clone(),
+        }
+    }
+
+    pub fn asin(&self) -> Option<String> {
+        match self {
+            Identifier::Asin(asin) => Some(asin.clone()),
+            Identifier::Url(url) => extract_asin(url),
+        }
+    }
+
+    /// Placeholder name until enrichment finds the real title
+    pub fn stub_name(&self) -> String {
+        match self {
+            Identifier::Asin(asin) => format!("Amazon {}", asin),
+            Identifier::Url(url) => match extract_asin(url) {
+                Some(asin) => format!("Amazon {}", asin),
+                None => name_from_url(url),
+            },
+        }
+    }
+
+    /// ASIN when known, so a bare ASIN and its product URL count as the same item
+    fn key(&self) -> String {
+        self.asin().unwrap_or_else(|| self.url().trim_end_matches('/').to_lowercase())
+    }
+}
+
+/// Parsed list entries and the non-empty lines that weren't identifiers.
+/// Blank lines and `#` comments are skipped.
+pub fn parse_list(text: &str) -> (Vec<Identifier>, Vec<String>) {
+    let mut identifiers = Vec::new();
+    let mut invalid = Vec::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        match Identifier::parse(line) {
+            Some(identifier) => identifiers.push(identifier),
+            None => invalid.push(line.to_string()),
+        }
+    }
+    (identifiers, invalid)
+}
+
+fn existing_product(conn: &Connection, identifier: &Identifier) -> rusqlite::Result<Option<i64>> {
+    conn.query_row(
+        "SELECT id FROM products WHERE product_url = ?1 OR (?2 IS NOT NULL AND amazon_asin = ?2) LIMIT 1",
+        params![identifier.url(), identifier.asin()],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Creates a stub product per new identifier in one transaction. Returns the
+/// enrichment items to queue and the identifiers skipped as duplicates.
+pub fn create_stubs(
+    conn: &mut Connection,
+    identifiers: &[Identifier],
+    editor: Option<&str>,
+) -> rusqlite::Result<(Vec<NewItem>, Vec<String>)> {
+    let tx = conn.transaction()?;
+    let mut seen = HashSet::new();
+    let mut items = Vec::new();
+    let mut duplicates = Vec::new();
+
+    for identifier in identifiers {
+        if !seen.insert(identifier.key()) || existing_product(&tx, identifier)?.is_some() {
+            duplicates.push(identifier.url());
+            continue;
+        }
+        let name = identifier.stub_name();
+        tx.execute(
+            "INSERT INTO products (name, category, amazon_asin, product_url, tags, updated_by)
+             VALUES (?1, 'Uncategorized', ?2, ?3, ?4, ?5)",
+            params![name, identifier.asin(), identifier.url(), format!("[\"{}\"]", IMPORT_TAG), editor],
+        )?;
+        items.push(NewItem { product_id: tx.last_insert_rowid(), product_name: name, skip_reason: None });
+    }
+
+    tx.commit()?;
+    Ok((items, duplicates))
+}
+
+/// Fills a stub from its scraped page, availability included. Fields the user
+/// has already set are kept; the name is replaced only while it is still the
+/// placeholder.
+pub fn apply_scrape(
+    conn: &Connection,
+    product_id: i64,
+    stub_name: &str,
+    scraped: &ScrapedProduct,
+) -> rusqlite::Result<usize> {
+    let updated = conn.execute(
+        "UPDATE products SET
+            name = CASE WHEN name = ?2 AND ?3 IS NOT NULL THEN ?3 ELSE name END,
+            description = COALESCE(NULLIF(description, ''), ?4),
+            image_url = COALESCE(NULLIF(image_url, ''), ?5),
+            price_range = COALESCE(NULLIF(price_range, ''), ?6),
+            amazon_asin = COALESCE(amazon_asin, ?7),
+            updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?1",
+        params![
+            product_id,
+            stub_name,
+            scraped.title.as_deref().filter(|t| !t.trim().is_empty()),
+            scraped.description,
+            scraped.image_url,
+            scraped.price.map(price_to_range),
+            scraped.amazon_asin,
+        ],
+    )?;
+    availability::record_scraped(conn, product_id, scraped.availability.as_deref())?;
+    Ok(updated)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_creates_stubs_once_and_enrichment_keeps_user_edits() {
+        let (identifiers, invalid) = parse_list(
+            "# bookmarks\n\
+             B08N5WRWNW\n\
+             https://www.amazon.com/Echo-Dot/dp/b08n5wrwnw?ref=x\n\
+             https://www.etsy.com/listing/123/handmade-candle\tgreat gift\n\
+             b07xyz1234, note\n\
+             \n\
+             not an identifier\n\
+             TOOTHBRUSH",
+        );
+        assert_eq!(invalid, ["not an identifier", "TOOTHBRUSH"]);
+        assert_eq!(identifiers.len(), 4);
+        assert_eq!(identifiers[3], Identifier::Asin("B07XYZ1234".to_string()));
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT, category TEXT, description TEXT,
+                price_range TEXT, image_url TEXT, amazon_asin TEXT, product_url TEXT, tags TEXT,
+                updated_by TEXT, updated_at TEXT, availability TEXT, availability_checked_at DATETIME);
+             INSERT INTO products (name, amazon_asin) VALUES ('Existing', 'B07XYZ1234');",
+        )
+        .unwrap();
+
+        let (items, duplicates) = create_stubs(&mut conn, &identifiers, None).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].product_name, "Amazon B08N5WRWNW");
+        assert_eq!(items[1].product_name, "handmade candle");
+        assert_eq!(duplicates.len(), 2);
+
+        let stub = &items[0];
+        conn.execute("UPDATE products SET description = 'Mine' WHERE id = ?1", params![stub.product_id]).unwrap();
+        let scraped = ScrapedProduct {
+            title: Some("Echo Dot".to_string()),
+            description: Some("Smart speaker".to_string()),
+            price: Some(49.99),
+            ..Default::default()
+        };
+        apply_scrape(&conn, stub.product_id, &stub.product_name, &scraped).unwrap();
+        let row: (String, String, String) = conn
+            .query_row(
+                "SELECT name, description, price_range FROM products WHERE id = ?1",
+                params![stub.product_id],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(row, ("Echo Dot".to_string(), "Mine".to_string(), "$40-$50".to_string()));
+    }
+}
@@ -0,0 +1,168 @@
+//! Data Integrity Checker
+//!
+//! SQLite only enforces foreign keys when the pragma is on, and older
+//! databases predate several of the constraints, so orphaned rows accumulate.
+//! This module finds them (ads and links pointing at deleted products,
+//! credentials for platforms the app doesn't know, misuse of the placeholder
+//! "Direct Product Ads" campaign) and can repair them.
+
+use crate::database::schema::ensure_default_campaign;
+use crate::models::affiliate_link::AffiliatePlatform;
+use crate::models::maintenance::IntegrityIssue;
+use crate::services::{ai_client, analytics_import, store_sync};
+use rusqlite::{params, Connection};
+
+/// Placeholder campaign that direct product ads are attached to
+const PLACEHOLDER_CAMPAIGN_ID: i64 = 1;
+
+/// Whether a credential platform is an affiliate platform, analytics provider, AI provider, or store
+pub fn is_known_credential_platform(platform: &str) -> bool {
+    let platform = platform.to_lowercase();
+    AffiliatePlatform::from_string(&platform).is_some()
+        || analytics_import::PROVIDERS.contains(&platform.as_str())
+        || ai_client::PROVIDERS.contains(&platform.as_str())
+        || store_sync::PROVIDERS.contains(&platform.as_str())
+}
+
+/// Runs every integrity check
+pub fn check(conn: &Connection) -> rusqlite::Result<Vec<IntegrityIssue>> {
+    let mut issues = Vec::new();
+
+    issues.extend(query_issues(
+        conn,
+        "ad_missing_product",
+        "ad_copies",
+        "SELECT id, 'Ad \"' || headline || '\" references missing product ' || product_id
+         FROM ad_copies
+         WHERE product_id IS NOT NULL AND product_id NOT IN (SELECT id FROM products)",
+        "Delete the ad",
+    )?);
+
+    issues.extend(query_issues(
+        conn,
+        "link_missing_product",
+        "affiliate_links",
+        "SELECT id, 'Link for \"' || product_name || '\" references deleted product ' || product_id
+         FROM affiliate_links WHERE product_id NOT IN (SELECT id FROM products)",
+        "Delete the link",
+    )?);
+
+    let mut stmt = conn.prepare("SELECT id, platform FROM affiliate_credentials")?;
+    let credentials = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (id, platform) in credentials {
+        if !is_known_credential_platform(&platform) {
+            issues.push(IntegrityIssue {
+                kind: "unknown_credential_platform".to_string(),
+                table: "affiliate_credentials".to_string(),
+                id: Some(id),
+                description: format!("Credentials stored for unknown platform '{}'", platform),
+                fix: "Delete the credentials".to_string(),
+            });
+        }
+    }
+
+    let placeholder_exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM campaigns WHERE id = ?1)",
+        params![PLACEHOLDER_CAMPAIGN_ID],
+        |row| row.get(0),
+    )?;
+    let placeholder_ads: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM ad_copies WHERE campaign_id = ?1",
+        params![PLACEHOLDER_CAMPAIGN_ID],
+        |row| row.get(0),
+    )?;
+    if !placeholder_exists && placeholder_ads > 0 {
+        issues.push(IntegrityIssue {
+            kind: "placeholder_campaign_missing".to_string(),
+            table: "campaigns".to_string(),
+            id: Some(PLACEHOLDER_CAMPAIGN_ID),
+            description: format!(
+                "{} ad(s) belong to campaign {} which does not exist",
+                placeholder_ads, PLACEHOLDER_CAMPAIGN_ID
+            ),
+            fix: "Recreate the \"Direct Product Ads\" campaign".to_string(),
+        });
+    }
+
+    // The placeholder campaign exists only to hold ads generated from a product
+    issues.extend(query_issues(
+        conn,
+        "placeholder_ad_without_product",
+        "ad_copies",
+        "SELECT id, 'Ad \"' || headline || '\" sits in the placeholder campaign without a product'
+         FROM ad_copies WHERE campaign_id = 1 AND product_id IS NULL AND bundle_id IS NULL",
+        "Delete the ad",
+    )?);
+
+    Ok(issues)
+}
+
+fn query_issues(
+    conn: &Connection,
+    kind: &str,
+    table: &str,
+    sql: &str,
+    fix: &str,
+) -> rusqlite::Result<Vec<IntegrityIssue>> {
+    let mut stmt = conn.prepare(sql)?;
+    let issues = stmt
+        .query_map([], |row| {
+            Ok(IntegrityIssue {
+                kind: kind.to_string(),
+                table: table.to_string(),
+                id: Some(row.get(0)?),
+                description: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                fix: fix.to_string(),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(issues)
+}
+
+/// Applies the fix for each issue inside one transaction. Returns the number fixed.
+pub fn apply_fixes(conn: &mut Connection, issues: &[IntegrityIssue]) -> rusqlite::Result<i64> {
+    let tx = conn.transaction()?;
+    let mut fixed = 0;
+
+    for issue in issues {
+        match (issue.kind.as_str(), issue.id) {
+            ("placeholder_campaign_missing", _) => {
+                ensure_default_campaign(&tx)?;
+                fixed += 1;
+            }
+            (
+                "ad_missing_product" | "link_missing_product" | "unknown_credential_platform"
+                | "placeholder_ad_without_product",
+                Some(id),
+            ) => {
+                // Table names come from the fixed set used in check()
+                fixed += tx.execute(&format!("DELETE FROM {} WHERE id = ?1", issue.table), params![id])? as i64;
+            }
+            _ => {}
+        }
+    }
+
+    tx.commit()?;
+    Ok(fixed)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_credential_platforms() {
+        assert!(is_known_credential_platform("amazon"));
+        assert!(is_known_credential_platform("TikTok"));
+        assert!(is_known_credential_platform("plausible"));
+        assert!(is_known_credential_platform("openai"));
+        assert!(is_known_credential_platform("shopify"));
+        assert!(!is_known_credential_platform("myspace"));
+    }
+}
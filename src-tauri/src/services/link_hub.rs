@@ -0,0 +1,229 @@
+//! Link Hub Site Export
+//!
+//! Renders a single-page, Linktree-style static site listing products with
+//! their image, a short blurb, and every active affiliate link. The page is
+//! self-contained (inline CSS, no scripts) so it can be dropped on any static
+//! host, and it is regenerated from the current catalog on every export.
+
+use crate::models::link_hub::{LinkHubEntry, LinkHubLink};
+use rusqlite::{params, Connection};
+
+pub const DEFAULT_TITLE: &str = "My Favorite Finds";
+const BLURB_CHARS: usize = 140;
+
+/// Loads hub entries for `product_ids` (or every product with an active link).
+/// Returns the entries plus the selected products skipped for having no active link.
+pub fn load_entries(
+    conn: &Connection,
+    product_ids: Option<&[i64]>,
+) -> rusqlite::Result<(Vec<LinkHubEntry>, Vec<i64>)> {
+    let ids: Vec<i64> = match product_ids {
+        Some(ids) => ids.to_vec(),
+        None => {
+            let mut stmt = conn.prepare(
+                "SELECT DISTINCT p.id FROM products p
+                 JOIN affiliate_links l ON l.product_id = p.id AND l.status = 'active'
+                 ORDER BY p.name",
+            )?;
+            let ids = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<Vec<i64>, _>>()?;
+            ids
+        }
+    };
+
+    let mut entries = Vec::new();
+    let mut skipped = Vec::new();
+    let mut links_stmt = conn.prepare(
+        "SELECT COALESCE(platform, ''), tracking_url FROM affiliate_links
+         WHERE product_id = ?1 AND status = 'active' ORDER BY created_at DESC",
+    )?;
+
+    for id in ids {
+        let product = conn.query_row(
+            "SELECT name, category, description, image_url FROM products WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            },
+        );
+        let (name, category, description, image_url) = match product {
+            Ok(product) => product,
+            Err(rusqlite::Error::QueryReturnedNoRows) => {
+                skipped.push(id);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        let links = links_stmt
+            .query_map(params![id], |row| {
+                let platform: String = row.get(0)?;
+                Ok(LinkHubLink {
+                    label: link_label(&platform),
+                    url: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if links.is_empty() {
+            skipped.push(id);
+            continue;
+        }
+
+        entries.push(LinkHubEntry {
+            product_id: id,
+            name,
+            image_url: image_url.filter(|u| u.starts_with("http")),
+            blurb: blurb(description.as_deref(), &category),
+            links,
+        });
+    }
+
+    Ok((entries, skipped))
+}
+
+fn link_label(platform: &str) -> String {
+    let store = match platform {
+        "tiktok" => "TikTok Shop",
+        "instagram" => "Instagram",
+        "amazon" => "Amazon",
+        "youtube" => "YouTube",
+        "pinterest" => "Pinterest",
+        "facebook" => "Facebook",
+        _ => return "Shop now".to_string(),
+    };
+    format!("Shop on {}", store)
+}
+
+/// First sentence-ish of the description, cut at a word boundary
+pub fn blurb(description: Option<&str>, category: &str) -> String {
+    let text = description.map(str::trim).filter(|d| !d.is_empty()).unwrap_or(category);
+    if text.chars().count() <= BLURB_CHARS {
+        return text.to_string();
+    }
+
+    let cut: String = text.chars().take(BLURB_CHARS).collect();
+    let cut = match cut.rfind(' ') {
+        Some(idx) => &cut[..idx],
+        None => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end_matches(|c: char| c.is_ascii_punctuation()))
+}
+
+/// Renders the complete index.html
+pub fn render_html(title: &str, entries: &[LinkHubEntry]) -> String {
+    let cards = entries
+        .iter()
+        .map(|entry| {
+            let image = entry
+                .image_url
+                .as_deref()
+                .map(|url| format!(r#"<img src="{}" alt="{}" loading="lazy">"#, escape_html(url), escape_html(&entry.name)))
+                .unwrap_or_default();
+            let links = entry
+                .links
+                .iter()
+                .map(|link| {
+                    format!(
+                        r#"<a class="link" href="{}" rel="sponsored noopener" target="_blank">{}</a>"#,
+                        escape_html(&link.url),
+                        escape_html(&link.label)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n        ");
+
+            format!(
+                "    <article class=\"card\">\n      {}\n      <h2>{}</h2>\n      <p>{}</p>\n      <div class=\"links\">\n        {}\n      </div>\n    </article>",
+                image,
+                escape_html(&entry.name),
+                escape_html(&entry.blurb),
+                links
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <meta name="viewport" content="width=device-width, initial-scale=1">
+  <title>{title}</title>
+  <style>
+    body {{ margin: 0; font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; background: #f6f6f8; color: #1d1d1f; }}
+    main {{ max-width: 560px; margin: 0 auto; padding: 32px 16px; }}
+    h1 {{ text-align: center; font-size: 1.6rem; }}
+    .card {{ background: #fff; border-radius: 16px; padding: 16px; margin-bottom: 16px; box-shadow: 0 1px 3px rgba(0,0,0,.08); }}
+    .card img {{ width: 100%; border-radius: 12px; object-fit: cover; max-height: 280px; }}
+    .card h2 {{ font-size: 1.1rem; margin: 12px 0 4px; }}
+    .card p {{ margin: 0 0 12px; color: #555; }}
+    .link {{ display: block; text-align: center; padding: 10px; margin-top: 8px; border-radius: 999px; background: #1d1d1f; color: #fff; text-decoration: none; }}
+    footer {{ text-align: center; font-size: .8rem; color: #888; }}
+  </style>
+</head>
+<body>
+  <main>
+    <h1>{title}</h1>
+{cards}
+    <footer>As an affiliate, I may earn from qualifying purchases.</footer>
+  </main>
+</body>
+</html>
+"#,
+        title = escape_html(title),
+        cards = cards
+    )
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blurb_cuts_at_word_boundary() {
+        let long = "Hydrating essence with snail mucin. ".repeat(10);
+        let text = blurb(Some(&long), "Beauty");
+        assert!(text.ends_with('…'));
+        assert!(text.chars().count() <= BLURB_CHARS + 1);
+        assert!(!text.contains("mu…"));
+        assert_eq!(blurb(None, "Beauty & Skincare"), "Beauty & Skincare");
+    }
+
+    #[test]
+    fn test_render_escapes_content() {
+        let entries = vec![LinkHubEntry {
+            product_id: 1,
+            name: "Mug <Limited>".to_string(),
+            image_url: None,
+            blurb: "Tea & coffee".to_string(),
+            links: vec![LinkHubLink {
+                label: link_label("amazon"),
+                url: "https://amazon.com/dp/X?a=1&b=2".to_string(),
+            }],
+        }];
+        let html = render_html("Picks", &entries);
+        assert!(html.contains("<h2>Mug &lt;Limited&gt;</h2>"));
+        assert!(html.contains(r#"href="https://amazon.com/dp/X?a=1&amp;b=2""#));
+        assert!(html.contains(">Shop on Amazon</a>"));
+    }
+}
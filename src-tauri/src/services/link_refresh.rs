@@ -0,0 +1,227 @@
+//! Link Refresh Diffs
+//!
+//! Refreshing a link re-runs program discovery and may move it to another
+//! network, rate, or tracking URL. Every refresh that changes something —
+//! one link at a time or as a bulk `refresh` operation — stores the link as it
+//! was and as it became in `link_refreshes`, so what the refresher did across
+//! the workspace can be audited afterwards. Refreshes that change nothing are
+//! not stored, and a link's history goes with it when it is deleted.
+
+use crate::models::link_refresh::{LinkRefreshDiff, LinkSnapshot, RefreshDiffReport, RefreshFieldCount};
+use chrono::{Duration, NaiveDateTime};
+use rusqlite::{params, Connection, Row};
+use std::collections::{HashMap, HashSet};
+
+/// Periods `diffs` accepts besides "all", with their length in days
+pub const PERIODS: [(&str, i64); 4] = [("24h", 1), ("7d", 7), ("30d", 30), ("90d", 90)];
+/// Diffs returned at most; the counts still cover the whole period
+const MAX_DIFFS: usize = 500;
+
+/// The link's refreshable fields as they are now
+pub fn snapshot(conn: &Connection, link_id: i64) -> rusqlite::Result<LinkSnapshot> {
+    conn.query_row(
+        "SELECT platform, program_name, commission_rate, cookie_duration, tracking_url, destination_url, status
+         FROM affiliate_links WHERE id = ?1",
+        params![link_id],
+        |row| {
+            Ok(LinkSnapshot {
+                platform: row.get(0)?,
+                program_name: row.get(1)?,
+                commission_rate: row.get(2)?,
+                cookie_duration: row.get(3)?,
+                tracking_url: row.get(4)?,
+                destination_url: row.get(5)?,
+                status: row.get(6)?,
+            })
+        },
+    )
+}
+
+/// Names of the fields that differ between two snapshots, in field order
+pub fn changed_fields(before: &LinkSnapshot, after: &LinkSnapshot) -> Vec<String> {
+    let fields = [
+        ("platform", before.platform != after.platform),
+        ("programName", before.program_name != after.program_name),
+        ("commissionRate", before.commission_rate != after.commission_rate),
+        ("cookieDuration", before.cookie_duration != after.cookie_duration),
+        ("trackingUrl", before.tracking_url != after.tracking_url),
+        ("destinationUrl", before.destination_url != after.destination_url),
+        ("status", before.status != after.status),
+    ];
+    fields.iter().filter(|(_, changed)| *changed).map(|(name, _)| name.to_string()).collect()
+}
+
+/// Stores a refresh of `link_id` when it changed anything; returns the new
+/// row's id, or None when the snapshots are equal
+pub fn record(
+    conn: &Connection,
+    link_id: i64,
+    product_id: i64,
+    operation_id: Option<i64>,
+    before: &LinkSnapshot,
+    after: &LinkSnapshot,
+) -> Result<Option<i64>, String> {
+    let changed = changed_fields(before, after);
+    if changed.is_empty() {
+        return Ok(None);
+    }
+    let before_json = serde_json::to_string(before).map_err(|e| e.to_string())?;
+    let after_json = serde_json::to_string(after).map_err(|e| e.to_string())?;
+    let changed_json = serde_json::to_string(&changed).map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT INTO link_refreshes (link_id, product_id, operation_id, before_json, after_json, changed_fields)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![link_id, product_id, operation_id, before_json, after_json, changed_json],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(Some(conn.last_insert_rowid()))
+}
+
+/// Start of `period` counted back from `now`; None for "all"
+pub fn period_start(period: &str, now: NaiveDateTime) -> Result<Option<NaiveDateTime>, String> {
+    let period = period.trim().to_lowercase();
+    if period == "all" {
+        return Ok(None);
+    }
+    PERIODS
+        .iter()
+        .find(|(name, _)| *name == period)
+        .map(|(_, days)| Some(now - Duration::days(*days)))
+        .ok_or_else(|| {
+            let names: Vec<&str> = PERIODS.iter().map(|(name, _)| *name).collect();
+            format!("Unknown period '{}'; use {} or all", period, names.join(", "))
+        })
+}
+
+/// Column `index` of `row` parsed from JSON
+fn json_column<T: serde::de::DeserializeOwned>(row: &Row, index: usize) -> rusqlite::Result<T> {
+    let text: String = row.get(index)?;
+    serde_json::from_str(&text)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+fn diff_from_row(row: &Row) -> rusqlite::Result<LinkRefreshDiff> {
+    Ok(LinkRefreshDiff {
+        id: row.get(0)?,
+        link_id: row.get(1)?,
+        product_id: row.get(2)?,
+        product_name: row.get(3)?,
+        operation_id: row.get(4)?,
+        before: json_column(row, 5)?,
+        after: json_column(row, 6)?,
+        changed_fields: json_column(row, 7)?,
+        refreshed_at: row.get(8)?,
+    })
+}
+
+/// Every stored refresh since the start of `period`, newest first, with how
+/// many links changed and how often each field did
+pub fn diffs(conn: &Connection, period: &str, now: NaiveDateTime) -> Result<RefreshDiffReport, String> {
+    let since = period_start(period, now)?.map(|start| start.format("%Y-%m-%d %H:%M:%S").to_string());
+    let mut stmt = conn
+        .prepare(
+            "SELECT r.id, r.link_id, r.product_id, l.product_name, r.operation_id,
+                    r.before_json, r.after_json, r.changed_fields, r.refreshed_at
+             FROM link_refreshes r JOIN affiliate_links l ON l.id = r.link_id
+             WHERE ?1 IS NULL OR r.refreshed_at >= ?1
+             ORDER BY r.refreshed_at DESC, r.id DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    let mut diffs = stmt
+        .query_map(params![since], diff_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let links: HashSet<i64> = diffs.iter().map(|d| d.link_id).collect();
+    let mut counts: HashMap<&str, i64> = HashMap::new();
+    for field in diffs.iter().flat_map(|d| &d.changed_fields) {
+        *counts.entry(field.as_str()).or_default() += 1;
+    }
+    let mut field_counts: Vec<RefreshFieldCount> =
+        counts.into_iter().map(|(field, count)| RefreshFieldCount { field: field.to_string(), count }).collect();
+    field_counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.field.cmp(&b.field)));
+    let links_changed = links.len() as i64;
+    diffs.truncate(MAX_DIFFS);
+
+    Ok(RefreshDiffReport {
+        period: period.trim().to_lowercase(),
+        since,
+        links_changed,
+        field_counts,
+        diffs,
+    })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE affiliate_links (id INTEGER PRIMARY KEY, product_name TEXT);
+             CREATE TABLE bulk_operations (id INTEGER PRIMARY KEY);
+             INSERT INTO affiliate_links VALUES (1, 'Kettlebell'), (2, 'Yoga Mat');
+             INSERT INTO bulk_operations VALUES (7);",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../../migrations/054_link_refreshes.sql")).unwrap();
+        conn
+    }
+
+    fn link(platform: &str, rate: f64, url: &str) -> LinkSnapshot {
+        LinkSnapshot {
+            platform: platform.to_string(),
+            program_name: format!("{} Associates", platform),
+            commission_rate: Some(rate),
+            cookie_duration: Some(24),
+            tracking_url: url.to_string(),
+            destination_url: "https://shop.example/p".to_string(),
+            status: "active".to_string(),
+        }
+    }
+
+    fn at(text: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_only_refreshes_that_change_a_link_are_recorded() {
+        let conn = setup();
+        let before = link("amazon", 0.04, "https://amzn.to/a");
+        assert_eq!(record(&conn, 1, 10, None, &before, &before).unwrap(), None);
+
+        let mut expired = before.clone();
+        expired.status = "expired".to_string();
+        let after = link("impact", 0.08, "https://imp.ct/a");
+        assert_eq!(
+            changed_fields(&expired, &after),
+            ["platform", "programName", "commissionRate", "trackingUrl", "status"]
+        );
+        record(&conn, 1, 10, None, &expired, &after).unwrap().unwrap();
+        let moved = link("amazon", 0.04, "https://amzn.to/b");
+        record(&conn, 2, 11, Some(7), &before, &moved).unwrap().unwrap();
+        conn.execute("UPDATE link_refreshes SET refreshed_at = '2026-03-01 09:00:00' WHERE link_id = 1", [])
+            .unwrap();
+        conn.execute("UPDATE link_refreshes SET refreshed_at = '2026-03-09 09:00:00' WHERE link_id = 2", [])
+            .unwrap();
+
+        let now = at("2026-03-10 12:00:00");
+        let all = diffs(&conn, "all", now).unwrap();
+        assert_eq!((all.links_changed, all.since.as_deref()), (2, None));
+        assert_eq!(all.diffs[0].operation_id, Some(7));
+        assert_eq!(all.diffs[1].before, expired);
+        assert_eq!(all.diffs[1].after, after);
+        assert_eq!(all.field_counts[0], RefreshFieldCount { field: "trackingUrl".to_string(), count: 2 });
+
+        let week = diffs(&conn, " 7D ", now).unwrap();
+        assert_eq!(week.since.as_deref(), Some("2026-03-03 12:00:00"));
+        assert_eq!(week.diffs.iter().map(|d| d.link_id).collect::<Vec<_>>(), [2]);
+        assert!(diffs(&conn, "fortnight", now).unwrap_err().contains("24h, 7d, 30d, 90d"));
+    }
+}
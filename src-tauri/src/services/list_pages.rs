@@ -0,0 +1,207 @@
+//! Windowed Lists
+//!
+//! Count and range-fetch for the product and link tables, so a virtualized
+//! table can ask for the total once and then only the rows it is showing
+//! instead of loading the whole catalog. Filters are bound as parameters and
+//! sort fields are checked against the table's column list.
+
+use crate::commands::affiliate_links::{link_from_row, LINK_COLUMNS};
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::models::entity_query::QuerySort;
+use crate::models::list_page::{LinkListFilter, LinkPage, ProductListFilter, ProductPage};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params_from_iter, Connection};
+
+const MAX_WINDOW: i64 = 500;
+
+/// A WHERE clause and its bound values
+#[derive(Default)]
+struct Conditions {
+    clauses: Vec<String>,
+    values: Vec<SqlValue>,
+}
+
+impl Conditions {
+    /// Adds `clause`, where `?` stands for `value`
+    fn push(&mut self, clause: &str, value: SqlValue) {
+        self.values.push(value);
+        self.clauses.push(clause.replace('?', &format!("?{}", self.values.len())));
+    }
+
+    fn text(&mut self, clause: &str, value: &Option<String>) {
+        if let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            self.push(clause, SqlValue::Text(value.to_string()));
+        }
+    }
+
+    /// The bound values followed by LIMIT and OFFSET, which are bound too so
+    /// the cached statement is reused while scrolling
+    fn with_window(&self, limit: i64, offset: i64) -> Vec<SqlValue> {
+        let mut values = self.values.clone();
+        values.extend([SqlValue::Integer(limit), SqlValue::Integer(offset)]);
+        values
+    }
+
+    fn sql(&self) -> String {
+        if self.clauses.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", self.clauses.join(" AND "))
+        }
+    }
+}
+
+fn contains(value: &Option<String>) -> Option<String> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(|v| format!("%{}%", v))
+}
+
+fn product_conditions(filter: &ProductListFilter) -> Conditions {
+    let mut conditions = Conditions::default();
+    conditions.text("(name LIKE ? OR category LIKE ? OR description LIKE ?)", &contains(&filter.search));
+    conditions.text("category = ?", &filter.category);
+    conditions.text("COALESCE(stage, 'researching') = ?", &filter.stage);
+    // json_each raises on malformed JSON, so invalid tag columns are skipped
+    conditions.text(
+        "EXISTS (SELECT 1 FROM json_each(CASE WHEN json_valid(products.tags) THEN products.tags END)
+         WHERE value = ?)",
+        &filter.tag,
+    );
+    conditions
+}
+
+/// ` WHERE ...` for `filter` against `products`, with its bound values
+pub(crate) fn product_filter_sql(filter: &ProductListFilter) -> (String, Vec<SqlValue>) {
+    let conditions = product_conditions(filter);
+    (conditions.sql(), conditions.values)
+}
+
+fn link_conditions(filter: &LinkListFilter) -> Conditions {
+    let mut conditions = Conditions::default();
+    conditions.text("(product_name LIKE ? OR program_name LIKE ?)", &contains(&filter.search));
+    if let Some(product_id) = filter.product_id {
+        conditions.push("product_id = ?", SqlValue::Integer(product_id));
+    }
+    conditions.text("status = ?", &filter.status);
+    conditions.text("platform = ?", &filter.platform);
+    conditions.text("program_name = ?", &filter.program_name);
+    conditions
+}
+
+/// ORDER BY for `sort`, or `default` when none is given; `id` breaks ties so
+/// windows never overlap
+fn order_by(columns: &str, sort: &Option<QuerySort>, default: &str) -> Result<String, String> {
+    let Some(sort) = sort else {
+        return Ok(format!("{}, id ASC", default));
+    };
+    if !columns.split(',').map(str::trim).any(|c| c == sort.field) {
+        return Err(format!("Unknown sort field '{}'", sort.field));
+    }
+    Ok(format!("{} {}, id ASC", sort.field, if sort.desc { "DESC" } else { "ASC" }))
+}
+
+fn count(conn: &Connection, table: &str, conditions: &Conditions) -> rusqlite::Result<i64> {
+    conn.prepare_cached(&format!("SELECT COUNT(*) FROM {}{}", table, conditions.sql()))?
+        .query_row(params_from_iter(&conditions.values), |row| row.get(0))
+}
+
+fn window(offset: i64, limit: i64) -> (i64, i64) {
+    (offset.max(0), limit.clamp(1, MAX_WINDOW))
+}
+
+pub fn count_products(conn: &Connection, filter: &ProductListFilter) -> rusqlite::Result<i64> {
+    count(conn, "products", &product_conditions(filter))
+}
+
+pub fn product_page(
+    conn: &Connection,
+    filter: &ProductListFilter,
+    sort: &Option<QuerySort>,
+    offset: i64,
+    limit: i64,
+) -> Result<ProductPage, String> {
+    let conditions = product_conditions(filter);
+    let order = order_by(PRODUCT_COLUMNS, sort, "trending_score DESC, name ASC")?;
+    let (offset, limit) = window(offset, limit);
+    let total = count(conn, "products", &conditions).map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare_cached(&format!(
+            "SELECT {} FROM products{} ORDER BY {} LIMIT ?{} OFFSET ?{}",
+            PRODUCT_COLUMNS,
+            conditions.sql(),
+            order,
+            conditions.values.len() + 1,
+            conditions.values.len() + 2
+        ))
+        .map_err(|e| e.to_string())?;
+    let items = stmt
+        .query_map(params_from_iter(conditions.with_window(limit, offset)), product_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(ProductPage { items, total, offset })
+}
+
+pub fn count_links(conn: &Connection, filter: &LinkListFilter) -> rusqlite::Result<i64> {
+    count(conn, "affiliate_links", &link_conditions(filter))
+}
+
+pub fn link_page(
+    conn: &Connection,
+    filter: &LinkListFilter,
+    sort: &Option<QuerySort>,
+    offset: i64,
+    limit: i64,
+) -> Result<LinkPage, String> {
+    let conditions = link_conditions(filter);
+    let order = order_by(LINK_COLUMNS, sort, "created_at DESC")?;
+    let (offset, limit) = window(offset, limit);
+    let total = count(conn, "affiliate_links", &conditions).map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare_cached(&format!(
+            "SELECT {} FROM affiliate_links{} ORDER BY {} LIMIT ?{} OFFSET ?{}",
+            LINK_COLUMNS,
+            conditions.sql(),
+            order,
+            conditions.values.len() + 1,
+            conditions.values.len() + 2
+        ))
+        .map_err(|e| e.to_string())?;
+    let items = stmt
+        .query_map(params_from_iter(conditions.with_window(limit, offset)), link_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(LinkPage { items, total, offset })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters_bind_values_and_sort_is_checked() {
+        let filter = ProductListFilter {
+            search: Some(" lamp ".to_string()),
+            stage: Some("approved".to_string()),
+            category: Some("  ".to_string()),
+            ..Default::default()
+        };
+        let conditions = product_conditions(&filter);
+        assert_eq!(
+            conditions.sql(),
+            " WHERE (name LIKE ?1 OR category LIKE ?1 OR description LIKE ?1) \
+             AND COALESCE(stage, 'researching') = ?2"
+        );
+        assert_eq!(conditions.values, [SqlValue::Text("%lamp%".into()), SqlValue::Text("approved".into())]);
+
+        let sort = Some(QuerySort { field: "name; DROP TABLE products".to_string(), desc: false });
+        assert!(order_by(PRODUCT_COLUMNS, &sort, "name ASC").is_err());
+        let sort = Some(QuerySort { field: "name".to_string(), desc: true });
+        assert_eq!(order_by(PRODUCT_COLUMNS, &sort, "id").unwrap(), "name DESC, id ASC");
+        assert_eq!(window(-5, 10_000), (0, MAX_WINDOW));
+    }
+}
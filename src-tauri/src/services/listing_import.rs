@@ -0,0 +1,344 @@
+//! Etsy and eBay Listing Import
+//!
+//! Builds a niche catalog quickly from a marketplace page: an Etsy shop, an
+//! eBay seller's store or listings, or an eBay search. Result pages are
+//! fetched like any product page and read in two ways — schema.org JSON-LD
+//! item lists when the page publishes them, otherwise the listing cards
+//! themselves — keeping each listing's title, price, image, and URL. Every
+//! listing becomes a product tagged with its platform; listings whose URL is
+//! already in the catalog are reported as duplicates instead.
+
+use crate::models::listing_import::{ListingImport, ListingSource};
+use crate::models::scraped_product::ScrapedProduct;
+use crate::services::scraper::{html_to_text, parse_price, price_to_range};
+use regex::Regex;
+use reqwest::Url;
+use rusqlite::{params, Connection};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Result pages read when the caller doesn't say
+pub const DEFAULT_PAGES: u32 = 1;
+pub const MAX_PAGES: u32 = 5;
+
+/// Identifies the shop, seller, or search a marketplace URL points at
+pub fn parse_source(raw: &str) -> Result<ListingSource, String> {
+    let raw = raw.trim();
+    let with_scheme = if raw.contains("://") { raw.to_string() } else { format!("https://{}", raw) };
+    let url = Url::parse(&with_scheme).map_err(|_| format!("'{}' is not a URL", raw))?;
+    let host = url.host_str().unwrap_or("").to_lowercase();
+    let segments: Vec<&str> =
+        url.path_segments().map(|s| s.filter(|s| !s.is_empty()).collect()).unwrap_or_default();
+    let query = |key: &str| {
+        url.query_pairs().find(|(k, v)| k == key && !v.trim().is_empty()).map(|(_, v)| v.trim().to_string())
+    };
+    let source = |platform: &str, kind: &str, name: String| ListingSource {
+        platform: platform.to_string(),
+        kind: kind.to_string(),
+        name,
+        url: with_scheme.clone(),
+    };
+
+    if host == "etsy.com" || host.ends_with(".etsy.com") {
+        // Shop pages may carry a language prefix: /de/shop/Name
+        if let Some(index) = segments.iter().position(|s| *s == "shop") {
+            if let Some(name) = segments.get(index + 1) {
+                return Ok(source("etsy", "shop", name.to_string()));
+            }
+        }
+        return Err("Use an Etsy shop URL, e.g. https://www.etsy.com/shop/ShopName".to_string());
+    }
+    if host.contains("ebay.") {
+        match segments.as_slice() {
+            ["str", name, ..] | ["usr", name, ..] => return Ok(source("ebay", "seller", name.to_string())),
+            ["sch", ..] => {
+                if let Some(seller) = query("_ssn") {
+                    return Ok(source("ebay", "seller", seller));
+                }
+                if let Some(terms) = query("_nkw") {
+                    return Ok(source("ebay", "search", terms));
+                }
+            }
+            _ => {}
+        }
+        return Err("Use an eBay store, seller, or search URL".to_string());
+    }
+    Err("Only Etsy shop and eBay seller or search URLs can be imported".to_string())
+}
+
+/// URL of result page `page` (1-based) of a source. eBay stores and sellers
+/// are read through their listings search, which pages reliably.
+pub fn page_url(source: &ListingSource, page: u32) -> Result<String, String> {
+    let mut url = Url::parse(&source.url).map_err(|e| e.to_string())?;
+    if source.platform == "ebay" && source.kind == "seller" && !url.path().starts_with("/sch/") {
+        let host = url.host_str().unwrap_or("www.ebay.com").to_string();
+        url = Url::parse(&format!("https://{}/sch/i.html", host)).map_err(|e| e.to_string())?;
+        url.query_pairs_mut().append_pair("_ssn", &source.name);
+    }
+    if page > 1 {
+        let key = if source.platform == "etsy" { "page" } else { "_pgn" };
+        let kept: Vec<(String, String)> =
+            url.query_pairs().filter(|(k, _)| k != key).map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        url.query_pairs_mut().clear().extend_pairs(kept).append_pair(key, &page.to_string());
+    }
+    Ok(url.to_string())
+}
+
+// =============================================================================
+// PARSING
+// =============================================================================
+
+/// Listing URL pattern per platform; the capture is the listing id
+fn listing_regex(platform: &str) -> Option<Regex> {
+    let pattern = match platform {
+        "etsy" => r#"https?://(?:www\.)?etsy\.com/(?:[a-z]{2}(?:-[a-z]{2})?/)?listing/(\d+)[^"'\s<>]*"#,
+        "ebay" => r#"https?://(?:www\.)?ebay\.[a-z.]+/itm/(?:[^/"'\s<>?]+/)?(\d{6,})[^"'\s<>]*"#,
+        _ => return None,
+    };
+    Regex::new(pattern).ok()
+}
+
+/// The listing's URL without tracking parameters
+fn canonical_url(platform: &str, id: &str) -> String {
+    match platform {
+        "etsy" => format!("https://www.etsy.com/listing/{}", id),
+        _ => format!("https://www.ebay.com/itm/{}", id),
+    }
+}
+
+fn new_listing(
+    platform: &str,
+    id: &str,
+    title: String,
+    price: Option<f64>,
+    image_url: Option<String>,
+) -> ScrapedProduct {
+    ScrapedProduct {
+        url: canonical_url(platform, id),
+        title: Some(title),
+        image_url,
+        price,
+        source_platform: Some(platform.to_string()),
+        ..Default::default()
+    }
+}
+
+fn json_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Array(items) => items.first().and_then(json_text),
+        Value::Object(map) => map.get("url").or_else(|| map.get("contentUrl")).and_then(json_text),
+        _ => None,
+    }
+}
+
+/// Collects every JSON-LD object whose `url` is a listing
+fn collect_json_ld(value: &Value, platform: &str, re: &Regex, found: &mut Vec<ScrapedProduct>) {
+    match value {
+        Value::Array(items) => items.iter().for_each(|item| collect_json_ld(item, platform, re, found)),
+        Value::Object(map) => {
+            let listing_id =
+                map.get("url").and_then(Value::as_str).and_then(|url| re.captures(url)).map(|c| c[1].to_string());
+            let name = map.get("name").and_then(json_text).map(|n| html_to_text(&n)).filter(|n| !n.is_empty());
+            match (listing_id, name) {
+                (Some(id), Some(name)) => {
+                    // A single offer, a list of offers, or an AggregateOffer with a lowPrice
+                    let offer = match map.get("offers") {
+                        Some(Value::Array(offers)) => offers.first(),
+                        offer => offer,
+                    };
+                    let price = offer
+                        .and_then(|o| o.get("price").or_else(|| o.get("lowPrice")))
+                        .and_then(json_text)
+                        .and_then(|p| parse_price(&p));
+                    found.push(new_listing(platform, &id, name, price, map.get("image").and_then(json_text)));
+                }
+                _ => map.values().for_each(|v| collect_json_ld(v, platform, re, found)),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Reads one listing card: the HTML from its first link to the next card's
+fn parse_card(platform: &str, id: &str, card: &str) -> Option<ScrapedProduct> {
+    // A title element, else the link's title or the image's alt text
+    let title_re = Regex::new(concat!(
+        r#"(?is)(?:class="[^"]*(?:s-item__title|s-card__title|listing-card-title|v2-listing-card__title)"#,
+        r#"[^"]*"[^>]*>(.*?)</(?:h3|div|span)>|\btitle="([^"]{3,})"|\balt="([^"]{3,})")"#,
+    ))
+    .ok()?;
+    let price_re = Regex::new(
+        r#"(?is)(?:currency-value[^>]*>\s*([\d.,]+)|(?:[$£€]|US \$|C \$|AU \$)\s?(\d[\d,]*(?:\.\d{1,2})?))"#,
+    )
+    .ok()?;
+    let image_re =
+        Regex::new(r#"(?i)(?:src|data-src)="(https://i\.(?:etsystatic\.com|ebayimg\.com)/[^"]+)""#).ok()?;
+
+    let title = title_re
+        .captures_iter(card)
+        .filter_map(|c| c.get(1).or_else(|| c.get(2)).or_else(|| c.get(3)))
+        .map(|m| html_to_text(m.as_str()))
+        .map(|t| t.trim_start_matches("New Listing").trim().to_string())
+        .find(|t| !t.is_empty() && !t.eq_ignore_ascii_case("Shop on eBay"))?;
+    let price =
+        price_re.captures(card).and_then(|c| c.get(1).or_else(|| c.get(2))).and_then(|m| parse_price(m.as_str()));
+    let image_url = image_re.captures(card).map(|c| c[1].to_string());
+    Some(new_listing(platform, id, title, price, image_url))
+}
+
+/// Listings on a result page, in page order and without repeats
+pub fn parse_listings(platform: &str, html: &str) -> Vec<ScrapedProduct> {
+    let Some(listing_re) = listing_regex(platform) else {
+        return Vec::new();
+    };
+
+    let mut found = Vec::new();
+    if let Ok(block_re) =
+        Regex::new(r#"(?is)<script[^>]*type\s*=\s*["']application/ld\+json["'][^>]*>(.*?)</script>"#)
+    {
+        for block in block_re.captures_iter(html) {
+            if let Ok(value) = serde_json::from_str::<Value>(block[1].trim()) {
+                collect_json_ld(&value, platform, &listing_re, &mut found);
+            }
+        }
+    }
+    if found.is_empty() {
+        // A card starts at the first link to a listing not seen yet
+        let mut starts: Vec<(usize, String)> = Vec::new();
+        for caps in listing_re.captures_iter(html) {
+            let id = caps[1].to_string();
+            if !starts.iter().any(|(_, seen)| *seen == id) {
+                let start = html[..caps.get(0).map_or(0, |m| m.start())].rfind('<').unwrap_or(0);
+                starts.push((start, id));
+            }
+        }
+        for (index, (start, id)) in starts.iter().enumerate() {
+            let end = starts.get(index + 1).map_or(html.len(), |(next, _)| *next);
+            found.extend(parse_card(platform, id, &html[*start..end]));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    found.retain(|listing| seen.insert(listing.url.clone()));
+    found
+}
+
+// =============================================================================
+// IMPORT
+// =============================================================================
+
+/// Creates a product per listing not already in the catalog, in one transaction
+pub fn import_listings(
+    conn: &mut Connection,
+    source: ListingSource,
+    pages_read: i64,
+    listings: &[ScrapedProduct],
+    editor: Option<&str>,
+) -> rusqlite::Result<ListingImport> {
+    let tx = conn.transaction()?;
+    let tags = serde_json::to_string(&[&source.platform]).unwrap_or_else(|_| "[]".to_string());
+    let mut product_ids = Vec::new();
+    let mut duplicates = Vec::new();
+    for listing in listings {
+        let exists: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM products WHERE product_url = ?1)",
+            params![listing.url],
+            |row| row.get(0),
+        )?;
+        if exists {
+            duplicates.push(listing.url.clone());
+            continue;
+        }
+        tx.execute(
+            "INSERT INTO products (name, category, price_range, image_url, product_url, tags, updated_by)
+             VALUES (?1, 'Uncategorized', ?2, ?3, ?4, ?5, ?6)",
+            params![
+                listing.title.as_deref().unwrap_or(&listing.url),
+                listing.price.map(price_to_range),
+                listing.image_url,
+                listing.url,
+                tags,
+                editor,
+            ],
+        )?;
+        product_ids.push(tx.last_insert_rowid());
+    }
+    tx.commit()?;
+    Ok(ListingImport { source, pages_read, found: listings.len() as i64, product_ids, duplicates })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sources_are_recognized_and_paged() {
+        let shop = parse_source("etsy.com/de/shop/CozyCandleCo?ref=profile").unwrap();
+        assert_eq!((shop.platform.as_str(), shop.kind.as_str(), shop.name.as_str()), ("etsy", "shop", "CozyCandleCo"));
+        assert!(page_url(&shop, 2).unwrap().ends_with("?ref=profile&page=2"));
+
+        let store = parse_source("https://www.ebay.com/str/retrocameras").unwrap();
+        assert_eq!((store.kind.as_str(), store.name.as_str()), ("seller", "retrocameras"));
+        assert_eq!(page_url(&store, 1).unwrap(), "https://www.ebay.com/sch/i.html?_ssn=retrocameras");
+
+        let search = parse_source("https://www.ebay.co.uk/sch/i.html?_nkw=film+camera&_pgn=3").unwrap();
+        assert_eq!((search.kind.as_str(), search.name.as_str()), ("search", "film camera"));
+        assert_eq!(page_url(&search, 2).unwrap(), "https://www.ebay.co.uk/sch/i.html?_nkw=film+camera&_pgn=2");
+
+        assert!(parse_source("https://www.etsy.com/listing/123/candle").is_err());
+        assert!(parse_source("https://www.amazon.com/s?k=candles").is_err());
+    }
+
+    #[test]
+    fn test_listings_are_read_from_json_ld_or_cards_and_imported_once() {
+        let etsy = r#"<script type="application/ld+json">{"@type":"ItemList","itemListElement":[
+            {"@type":"Product","name":"Soy Candle &amp; Matches",
+             "url":"https://www.etsy.com/listing/1111/soy-candle?ref=shop",
+             "image":["https://i.etsystatic.com/1/il_570.jpg"],"offers":{"@type":"Offer","price":"24.00"}},
+            {"@type":"Product","name":"Wax Melts","url":"https://www.etsy.com/listing/2222/wax-melts",
+             "offers":{"@type":"AggregateOffer","lowPrice":"9.50"}}]}</script>"#;
+        let listings = parse_listings("etsy", etsy);
+        assert_eq!(listings.len(), 2);
+        assert_eq!(listings[0].title.as_deref(), Some("Soy Candle & Matches"));
+        assert_eq!(listings[0].url, "https://www.etsy.com/listing/1111");
+        assert_eq!((listings[0].price, listings[1].price), (Some(24.0), Some(9.5)));
+
+        let ebay = r#"<ul>
+            <li class="s-item"><a href="https://www.ebay.com/itm/123456789?hash=x">
+              <div class="s-item__title"><span>Shop on eBay</span></div></a></li>
+            <li class="s-item"><div class="s-item__image">
+              <a href="https://www.ebay.com/itm/Canon-AE-1/223344556677?epid=1">
+              <img src="https://i.ebayimg.com/images/g/abc/s-l225.jpg" alt="Canon AE-1 Film Camera"></a></div>
+              <a href="https://www.ebay.com/itm/Canon-AE-1/223344556677?epid=1">
+              <div class="s-item__title"><span>New Listing</span>Canon AE-1 Film Camera</div></a>
+              <span class="s-item__price">$189.99</span></li>
+        </ul>"#;
+        let listings = parse_listings("ebay", ebay);
+        assert_eq!(listings.len(), 1);
+        assert_eq!(listings[0].url, "https://www.ebay.com/itm/223344556677");
+        assert_eq!(listings[0].title.as_deref(), Some("Canon AE-1 Film Camera"));
+        assert_eq!(listings[0].price, Some(189.99));
+        assert_eq!(listings[0].image_url.as_deref(), Some("https://i.ebayimg.com/images/g/abc/s-l225.jpg"));
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT NOT NULL, category TEXT NOT NULL,
+                price_range TEXT, image_url TEXT, product_url TEXT, tags TEXT, updated_by TEXT);",
+        )
+        .unwrap();
+        let source = parse_source("https://www.ebay.com/sch/i.html?_nkw=film+camera").unwrap();
+        let first = import_listings(&mut conn, source.clone(), 1, &listings, None).unwrap();
+        assert_eq!(first.product_ids.len(), 1);
+        let again = import_listings(&mut conn, source, 1, &listings, None).unwrap();
+        assert_eq!(again.duplicates, ["https://www.ebay.com/itm/223344556677"]);
+        let (tags, price_range): (String, String) =
+            conn.query_row("SELECT tags, price_range FROM products", [], |r| Ok((r.get(0)?, r.get(1)?))).unwrap();
+        assert_eq!((tags.as_str(), price_range.as_str()), (r#"["ebay"]"#, "$180-$190"));
+    }
+}
@@ -0,0 +1,243 @@
+//! Markdown Export
+//!
+//! Renders ads as Markdown notes for Notion, Obsidian, and similar content
+//! workflows: YAML front matter with product and platform metadata, then the
+//! headline as the title, the body, and the call to action. One note per ad
+//! keeps front matter valid for tools that index it.
+
+use crate::commands::ad_generation::{ad_copy_from_row, GeneratedAdCopy, AD_COPY_COLUMNS};
+use crate::models::ad_export::{AdExportFilter, MarkdownExport};
+use crate::services::ad_kit::slug;
+use crate::services::ad_links;
+use rusqlite::types::Value;
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+/// An ad with the product details its note needs
+#[derive(Debug, Clone)]
+pub struct AdNote {
+    pub ad: GeneratedAdCopy,
+    pub product_name: String,
+    pub category: String,
+    pub tracking_url: Option<String>, // Attached link, else the newest active one
+    pub product_url: Option<String>,
+}
+
+impl AdNote {
+    /// The affiliate link, falling back to the product page
+    pub fn link(&self) -> Option<&str> {
+        self.tracking_url.as_deref().or(self.product_url.as_deref())
+    }
+}
+
+/// Ads selected by `filter`, oldest first
+pub fn load_notes(conn: &Connection, filter: &AdExportFilter) -> Result<Vec<AdNote>, String> {
+    let mut conditions = vec!["COALESCE(ad_type, '') != 'email_sequence'".to_string()];
+    let mut values: Vec<Value> = Vec::new();
+
+    if !filter.ad_ids.is_empty() {
+        let placeholders = filter
+            .ad_ids
+            .iter()
+            .map(|id| {
+                values.push(Value::Integer(*id));
+                format!("?{}", values.len())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        conditions.push(format!("id IN ({})", placeholders));
+    }
+    if let Some(product_id) = filter.product_id {
+        values.push(Value::Integer(product_id));
+        conditions.push(format!("product_id = ?{}", values.len()));
+    }
+    if let Some(ad_type) = filter.ad_type.as_deref().filter(|t| !t.is_empty()) {
+        values.push(Value::Text(ad_type.to_string()));
+        conditions.push(format!("ad_type = ?{}", values.len()));
+    }
+    if filter.favorites_only {
+        conditions.push("is_favorite = 1".to_string());
+    }
+    if let Some(after) = filter.created_after.as_deref().filter(|d| !d.is_empty()) {
+        values.push(Value::Text(after.to_string()));
+        conditions.push(format!("date(created_at) >= date(?{})", values.len()));
+    }
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM ad_copies WHERE {} ORDER BY created_at, id",
+            AD_COPY_COLUMNS,
+            conditions.join(" AND ")
+        ))
+        .map_err(|e| e.to_string())?;
+    let ads = stmt
+        .query_map(rusqlite::params_from_iter(values), ad_copy_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut product_stmt = conn
+        .prepare(
+            "SELECT p.name, p.category, p.product_url FROM products p WHERE p.id = ?1",
+        )
+        .map_err(|e| e.to_string())?;
+    let mut notes = Vec::with_capacity(ads.len());
+    for ad in ads {
+        let (product_name, category, product_url) = product_stmt
+            .query_row(params![ad.product_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap_or_else(|_| ("Unknown product".to_string(), String::new(), None));
+        let tracking_url = ad_links::tracking_url(conn, ad.product_id, ad.link_id).map_err(|e| e.to_string())?;
+        notes.push(AdNote {
+            ad,
+            product_name,
+            category,
+            tracking_url,
+            product_url,
+        });
+    }
+    Ok(notes)
+}
+
+/// Double-quoted YAML scalar; JSON string escaping is valid YAML
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+/// Target platform recorded at generation time, if any
+fn platform(ad: &GeneratedAdCopy) -> Option<String> {
+    let data: serde_json::Value = serde_json::from_str(ad.platform_specific_data.as_deref()?).ok()?;
+    data["target_platform"]
+        .as_str()
+        .or_else(|| data["style_platform"].as_str())
+        .map(str::to_string)
+}
+
+/// Note file name: `<product>-<ad type>-<id>.md`
+pub fn file_name(note: &AdNote) -> String {
+    format!(
+        "{}-{}-{}.md",
+        slug(&note.product_name),
+        slug(note.ad.ad_type.as_deref().unwrap_or("ad")),
+        note.ad.id.unwrap_or_default()
+    )
+}
+
+/// Renders one ad as a Markdown note with front matter
+pub fn render_note(note: &AdNote) -> String {
+    let ad = &note.ad;
+    let mut front_matter = vec![
+        format!("title: {}", yaml_string(&ad.headline)),
+        format!("ad_id: {}", ad.id.unwrap_or_default()),
+        format!("product: {}", yaml_string(&note.product_name)),
+    ];
+    if let Some(product_id) = ad.product_id {
+        front_matter.push(format!("product_id: {}", product_id));
+    }
+    if !note.category.is_empty() {
+        front_matter.push(format!("category: {}", yaml_string(&note.category)));
+    }
+    if let Some(ad_type) = ad.ad_type.as_deref() {
+        front_matter.push(format!("ad_type: {}", yaml_string(ad_type)));
+    }
+    if let Some(platform) = platform(ad) {
+        front_matter.push(format!("platform: {}", yaml_string(&platform)));
+    }
+    if let Some(link) = note.link() {
+        front_matter.push(format!("link: {}", yaml_string(link)));
+    }
+    front_matter.push(format!("favorite: {}", ad.is_favorite));
+    if let Some(created_at) = ad.created_at.as_deref() {
+        front_matter.push(format!("created: {}", yaml_string(created_at)));
+    }
+
+    let mut md = format!("---\n{}\n---\n\n# {}\n\n", front_matter.join("\n"), ad.headline.trim());
+    if let Some(body) = ad.body_text.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+        match note.link() {
+            Some(link) => md.push_str(&body.replace("[LINK]", link)),
+            None => md.push_str(body),
+        }
+        md.push_str("\n\n");
+    }
+    if let Some(cta) = ad.cta.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        match note.link() {
+            Some(link) => md.push_str(&format!("**CTA:** [{}]({})\n", cta, link)),
+            None => md.push_str(&format!("**CTA:** {}\n", cta)),
+        }
+    }
+    if let Some(alt_text) = ad.alt_text.as_deref() {
+        md.push_str(&format!("\n**Image alt text:** {}\n", alt_text));
+    }
+    md
+}
+
+/// Writes one note per ad selected by `filter` into `dir`
+pub fn export_to_dir(conn: &Connection, filter: &AdExportFilter, dir: &Path) -> Result<MarkdownExport, String> {
+    let notes = load_notes(conn, filter)?;
+    if notes.is_empty() {
+        return Err("No ads match the export filter".to_string());
+    }
+
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let mut files = Vec::with_capacity(notes.len());
+    for note in &notes {
+        let file = dir.join(file_name(note));
+        std::fs::write(&file, render_note(note))
+            .map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+        files.push(file.display().to_string());
+    }
+
+    Ok(MarkdownExport {
+        path: dir.display().to_string(),
+        files,
+    })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_note_front_matter() {
+        let note = AdNote {
+            ad: GeneratedAdCopy {
+                id: Some(3),
+                product_id: Some(1),
+                campaign_id: 1,
+                variation_name: None,
+                headline: "Meet \"Glow\"".to_string(),
+                body_text: Some("Lightweight SPF.\n".to_string()),
+                cta: Some("Shop Now".to_string()),
+                ad_format: None,
+                ad_type: Some("social_post".to_string()),
+                platform_specific_data: Some(r#"{"target_platform": "instagram"}"#.to_string()),
+                performance_score: None,
+                created_at: None,
+                updated_at: None,
+                is_favorite: true,
+                parent_ad_id: None,
+                sequence_step: None,
+                send_delay_days: None,
+                alt_text: None,
+                link_id: None,
+                approval_state: "draft".to_string(),
+            },
+            product_name: "Glow SPF".to_string(),
+            category: "Beauty & Skincare".to_string(),
+            tracking_url: Some("https://go.example/glow".to_string()),
+            product_url: None,
+        };
+
+        assert_eq!(file_name(&note), "glow-spf-social-post-3.md");
+        assert_eq!(
+            render_note(&note),
+            "---\ntitle: \"Meet \\\"Glow\\\"\"\nad_id: 3\nproduct: \"Glow SPF\"\nproduct_id: 1\n\
+             category: \"Beauty & Skincare\"\nad_type: \"social_post\"\nplatform: \"instagram\"\n\
+             link: \"https://go.example/glow\"\nfavorite: true\n---\n\n# Meet \"Glow\"\n\n\
+             Lightweight SPF.\n\n**CTA:** [Shop Now](https://go.example/glow)\n"
+        );
+    }
+}
@@ -1,2 +1,93 @@
 pub mod ai_affiliate;
 pub mod analytics_service;
+pub mod postback;
+pub mod analytics_import;
+pub mod digest;
+pub mod scheduler;
+pub mod click_analytics;
+pub mod attribution;
+pub mod niche;
+pub mod scraper;
+pub mod clipboard_watcher;
+pub mod extension_api;
+pub mod cleanup;
+pub mod integrity;
+pub mod bulk_links;
+pub mod bulk_operations;
+pub mod rate_limiter;
+pub mod ai_client;
+pub mod ai_cache;
+pub mod ai_validation;
+pub mod ai_discovery;
+pub mod research;
+pub mod content_angles;
+pub mod reviews;
+pub mod link_hub;
+pub mod email_sequence;
+pub mod sms_compliance;
+pub mod pinterest;
+pub mod short_video;
+pub mod spreadsheet_import;
+pub mod store_sync;
+pub mod accessibility;
+pub mod style_rules;
+pub mod critique;
+pub mod headline_variants;
+pub mod quick_ad;
+pub mod ad_kit;
+pub mod markdown_export;
+pub mod paid_ads;
+pub mod ad_import;
+pub mod webhooks;
+pub mod rest_api;
+pub mod entity_query;
+pub mod data_events;
+pub mod demo_data;
+pub mod workspace;
+pub mod geoip;
+pub mod program_applications;
+pub mod network_terms;
+pub mod deep_links;
+pub mod pretty_links;
+pub mod ad_links;
+pub mod credential_checks;
+pub mod readiness;
+pub mod post_schedule;
+pub mod post_timing;
+pub mod posting_caps;
+pub mod retail_events;
+pub mod approvals;
+pub mod shared_workspace;
+pub mod roles;
+pub mod data_privacy;
+pub mod retention;
+pub mod daily_metrics;
+pub mod query_profiler;
+pub mod query_plans;
+pub mod list_pages;
+pub mod product_export;
+pub mod identifier_import;
+pub mod watchlist;
+pub mod availability;
+pub mod product_comparison;
+pub mod bundles;
+pub mod notes;
+pub mod attachments;
+pub mod promotion_queue;
+pub mod benchmarks;
+pub mod platform_mix;
+pub mod recommendation_trace;
+pub mod category_keywords;
+pub mod audience;
+pub mod trend_history;
+pub mod variation_bandit;
+pub mod experiments;
+pub mod cohorts;
+pub mod link_refresh;
+pub mod read_only;
+pub mod listing_import;
+pub mod tiktok_sync;
+pub mod catalog_feed;
+pub mod feed_sources;
+#[cfg(test)]
+pub mod scoring_fixtures;
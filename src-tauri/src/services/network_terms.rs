@@ -0,0 +1,165 @@
+//! Network Terms
+//!
+//! Reference data on what it is like to work with each affiliate network:
+//! payout threshold and schedule, cookie policy, and whether deep links are
+//! supported. Discovery results are matched to a network so a program is
+//! judged on these constraints as well as its commission rate.
+
+use crate::models::network_terms::{NetworkTerms, SaveNetworkTermsInput};
+use rusqlite::{params, Connection, Row};
+
+pub const NETWORK_TERMS_COLUMNS: &str = "id, network, platform, min_payout, payout_currency,
+     payment_schedule, cookie_days, cookie_policy, deep_link_support, notes, builtin, updated_at";
+
+/// Maps a row selected with `NETWORK_TERMS_COLUMNS` into `NetworkTerms`
+pub fn terms_from_row(row: &Row) -> rusqlite::Result<NetworkTerms> {
+    Ok(NetworkTerms {
+        id: row.get(0)?,
+        network: row.get(1)?,
+        platform: row.get(2)?,
+        min_payout: row.get(3)?,
+        payout_currency: row.get(4)?,
+        payment_schedule: row.get(5)?,
+        cookie_days: row.get(6)?,
+        cookie_policy: row.get(7)?,
+        deep_link_support: row.get(8)?,
+        notes: row.get(9)?,
+        builtin: row.get::<_, Option<bool>>(10)?.unwrap_or(false),
+        updated_at: row.get(11)?,
+    })
+}
+
+pub fn list(conn: &Connection) -> rusqlite::Result<Vec<NetworkTerms>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM network_terms ORDER BY network COLLATE NOCASE",
+        NETWORK_TERMS_COLUMNS
+    ))?;
+    let terms = stmt.query_map([], terms_from_row)?.collect();
+    terms
+}
+
+pub fn validate(input: &SaveNetworkTermsInput) -> Result<(), String> {
+    if input.network.trim().is_empty() {
+        return Err("Network name is required".to_string());
+    }
+    if input.min_payout.is_some_and(|p| p < 0.0) || input.cookie_days.is_some_and(|d| d < 0) {
+        return Err("Payout threshold and cookie days cannot be negative".to_string());
+    }
+    Ok(())
+}
+
+/// Creates or updates a network's terms and returns the saved row
+pub fn save(conn: &Connection, input: &SaveNetworkTermsInput) -> Result<NetworkTerms, String> {
+    let clean = |value: &Option<String>| value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(String::from);
+    let platform = clean(&input.platform).map(|p| p.to_lowercase());
+
+    let id = match input.id {
+        Some(id) => {
+            let updated = conn
+                .execute(
+                    "UPDATE network_terms SET network = ?1, platform = ?2, min_payout = ?3,
+                     payout_currency = ?4, payment_schedule = ?5, cookie_days = ?6, cookie_policy = ?7,
+                     deep_link_support = ?8, notes = ?9, updated_at = CURRENT_TIMESTAMP
+                     WHERE id = ?10",
+                    params![
+                        input.network.trim(),
+                        platform,
+                        input.min_payout,
+                        clean(&input.payout_currency),
+                        clean(&input.payment_schedule),
+                        input.cookie_days,
+                        clean(&input.cookie_policy),
+                        input.deep_link_support,
+                        clean(&input.notes),
+                        id
+                    ],
+                )
+                .map_err(|e| e.to_string())?;
+            if updated == 0 {
+                return Err(format!("Network terms {} not found", id));
+            }
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO network_terms (network, platform, min_payout, payout_currency, payment_schedule,
+                 cookie_days, cookie_policy, deep_link_support, notes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    input.network.trim(),
+                    platform,
+                    input.min_payout,
+                    clean(&input.payout_currency),
+                    clean(&input.payment_schedule),
+                    input.cookie_days,
+                    clean(&input.cookie_policy),
+                    input.deep_link_support,
+                    clean(&input.notes)
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+            conn.last_insert_rowid()
+        }
+    };
+
+    conn.query_row(
+        &format!("SELECT {} FROM network_terms WHERE id = ?1", NETWORK_TERMS_COLUMNS),
+        params![id],
+        terms_from_row,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Picks the terms for a discovered program: a network named in the program
+/// name (longest name wins, so "Amazon Associates" beats "Amazon"), otherwise
+/// the network registered for its platform
+pub fn match_terms<'a>(program_name: &str, platform: &str, terms: &'a [NetworkTerms]) -> Option<&'a NetworkTerms> {
+    let program_name = program_name.to_lowercase();
+    terms
+        .iter()
+        .filter(|t| program_name.contains(&t.network.to_lowercase()))
+        .max_by_key(|t| t.network.len())
+        .or_else(|| {
+            terms
+                .iter()
+                .find(|t| t.platform.as_deref().is_some_and(|p| p.eq_ignore_ascii_case(platform)))
+        })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(id: i64, network: &str, platform: Option<&str>) -> NetworkTerms {
+        NetworkTerms {
+            id,
+            network: network.to_string(),
+            platform: platform.map(String::from),
+            min_payout: None,
+            payout_currency: None,
+            payment_schedule: None,
+            cookie_days: None,
+            cookie_policy: None,
+            deep_link_support: None,
+            notes: None,
+            builtin: true,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_match_terms_prefers_named_network() {
+        let all = vec![
+            terms(1, "Amazon Associates", Some("amazon")),
+            terms(2, "Impact", None),
+            terms(3, "Impact Radius Pro", None),
+        ];
+        assert_eq!(match_terms("Walmart via Impact Radius Pro", "amazon", &all).map(|t| t.id), Some(3));
+        assert_eq!(match_terms("Amazon Influencer Storefront", "amazon", &all).map(|t| t.id), Some(1));
+        assert_eq!(match_terms("Brand Direct Program", "tiktok", &all), None);
+    }
+}
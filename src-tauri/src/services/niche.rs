@@ -0,0 +1,219 @@
+//! Niche Profile Service
+//!
+//! A niche profile captures workspace-wide knowledge about the vertical being
+//! promoted (preferred platforms, typical audience, claims that must never be
+//! made). Discovery scoring and ad generation consult the active profile in
+//! addition to per-product fields.
+
+use crate::models::affiliate_link::AffiliateProgramDiscovery;
+use crate::models::niche_profile::NicheProfile;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+/// Audience match boost applied to platforms the niche prefers
+pub const PREFERRED_PLATFORM_BOOST: f64 = 0.10;
+
+pub const NICHE_PROFILE_COLUMNS: &str = "id, name, description, preferred_platforms,
+     typical_audience, banned_claims, active, created_at, updated_at";
+
+/// Maps a row selected with `NICHE_PROFILE_COLUMNS` into a `NicheProfile`
+pub fn niche_profile_from_row(row: &Row) -> rusqlite::Result<NicheProfile> {
+    let preferred: Option<String> = row.get(3)?;
+    let banned: Option<String> = row.get(5)?;
+
+    Ok(NicheProfile {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        description: row.get(2)?,
+        preferred_platforms: parse_string_list(preferred.as_deref()),
+        typical_audience: row.get(4)?,
+        banned_claims: parse_string_list(banned.as_deref()),
+        active: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+fn parse_string_list(json: Option<&str>) -> Vec<String> {
+    json.and_then(|j| serde_json::from_str(j).ok())
+        .unwrap_or_default()
+}
+
+/// Returns the workspace's active niche profile, if one is set
+pub fn load_active_profile(conn: &Connection) -> rusqlite::Result<Option<NicheProfile>> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM niche_profiles WHERE active = 1 LIMIT 1",
+            NICHE_PROFILE_COLUMNS
+        ),
+        params![],
+        niche_profile_from_row,
+    )
+    .optional()
+}
+
+/// Picks the audience to score against: the product's own audience when set,
+/// otherwise the niche's typical audience.
+pub fn effective_audience(product_audience: &str, profile: Option<&NicheProfile>) -> String {
+    if !product_audience.trim().is_empty() {
+        return product_audience.to_string();
+    }
+
+    profile
+        .and_then(|p| p.typical_audience.clone())
+        .filter(|a| !a.trim().is_empty())
+        .unwrap_or_default()
+}
+
+/// Boosts platforms preferred by the niche and re-sorts by audience match
+pub fn apply_to_programs(
+    mut programs: Vec<AffiliateProgramDiscovery>,
+    profile: Option<&NicheProfile>,
+) -> Vec<AffiliateProgramDiscovery> {
+    let profile = match profile {
+        Some(p) if !p.preferred_platforms.is_empty() => p,
+        _ => return programs,
+    };
+
+    for program in programs.iter_mut() {
+        let platform = program.platform.to_string();
+        if profile
+            .preferred_platforms
+            .iter()
+            .any(|p| p.eq_ignore_ascii_case(&platform))
+        {
+            let boosted = (program.audience_match_score + PREFERRED_PLATFORM_BOOST).min(1.0);
+            if let Some(breakdown) = program.match_breakdown.as_mut() {
+                breakdown.niche_boost = boosted - program.audience_match_score;
+                breakdown.score = (breakdown.score + breakdown.niche_boost).min(1.0);
+            }
+            program.audience_match_score = boosted;
+            program.recommendation_reason = format!(
+                "{} (preferred for {} niche)",
+                program.recommendation_reason, profile.name
+            );
+        }
+    }
+
+    programs.sort_by(|a, b| b.audience_match_score.total_cmp(&a.audience_match_score));
+
+    programs
+}
+
+/// Whether `text` makes any claim the niche bans (case-insensitive phrase match)
+pub fn contains_banned_claim(text: &str, profile: Option<&NicheProfile>) -> bool {
+    let text_lower = text.to_lowercase();
+    profile
+        .map(|p| {
+            p.banned_claims
+                .iter()
+                .filter(|c| !c.trim().is_empty())
+                .any(|c| text_lower.contains(&c.to_lowercase()))
+        })
+        .unwrap_or(false)
+}
+
+/// Drops selling points that make banned claims
+pub fn filter_banned_claims(points: Vec<String>, profile: Option<&NicheProfile>) -> Vec<String> {
+    points
+        .into_iter()
+        .filter(|p| !contains_banned_claim(p, profile))
+        .collect()
+}
+
+/// Renders the niche as prompt context for AI providers
+pub fn prompt_context(profile: Option<&NicheProfile>) -> String {
+    let profile = match profile {
+        Some(p) => p,
+        None => return "- None configured".to_string(),
+    };
+
+    let mut lines = vec![format!("- Niche: {}", profile.name)];
+    if let Some(description) = profile.description.as_deref().filter(|d| !d.is_empty()) {
+        lines.push(format!("- Focus: {}", description));
+    }
+    if !profile.preferred_platforms.is_empty() {
+        lines.push(format!(
+            "- Preferred Platforms: {}",
+            profile.preferred_platforms.join(", ")
+        ));
+    }
+    if let Some(audience) = profile.typical_audience.as_deref().filter(|a| !a.is_empty()) {
+        lines.push(format!("- Typical Audience: {}", audience));
+    }
+    if !profile.banned_claims.is_empty() {
+        lines.push(format!(
+            "- Never claim: {}",
+            profile.banned_claims.join(", ")
+        ));
+    }
+
+    lines.join("\n")
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::ai_affiliate::mock_ai_discovery_with_platforms;
+    use crate::services::category_keywords::CategoryAffinities;
+
+    fn profile() -> NicheProfile {
+        NicheProfile {
+            id: Some(1),
+            name: "Home Fitness".to_string(),
+            description: None,
+            preferred_platforms: vec!["youtube".to_string()],
+            typical_audience: Some("Age 30-50".to_string()),
+            banned_claims: vec!["Clinically proven".to_string()],
+            active: true,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_preferred_platform_is_boosted() {
+        let programs = mock_ai_discovery_with_platforms(
+            "Adjustable Dumbbells",
+            "Fitness & Recovery",
+            70,
+            "Age 30-50",
+            "$200-300",
+            &CategoryAffinities::defaults(),
+        );
+        let before = programs
+            .iter()
+            .find(|p| p.platform.to_string() == "youtube")
+            .map(|p| p.audience_match_score)
+            .unwrap();
+        let boosted = apply_to_programs(programs, Some(&profile()));
+        let after = boosted
+            .iter()
+            .find(|p| p.platform.to_string() == "youtube")
+            .map(|p| p.audience_match_score)
+            .unwrap();
+        assert!(after > before);
+    }
+
+    #[test]
+    fn test_banned_claims_filtered() {
+        let points = vec![
+            "Clinically proven results".to_string(),
+            "Built to last".to_string(),
+        ];
+        assert_eq!(
+            filter_banned_claims(points, Some(&profile())),
+            vec!["Built to last".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_effective_audience_falls_back_to_niche() {
+        assert_eq!(effective_audience("", Some(&profile())), "Age 30-50");
+        assert_eq!(effective_audience("Age 18-25", Some(&profile())), "Age 18-25");
+        assert_eq!(effective_audience("", None), "");
+    }
+}
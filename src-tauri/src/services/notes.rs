@@ -0,0 +1,272 @@
+//! Notes
+//!
+//! Markdown notes that refer to products, links, ads, and campaigns. A note's
+//! references are its explicit list plus the backlinks written into its body
+//! as `[[product:12]]`, so a call note that mentions two products shows up on
+//! both. Notes are indexed with FTS5; product search includes products whose
+//! notes match.
+
+use crate::models::note::{Note, NoteRef, NoteSearchHit, SaveNoteInput};
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub const ENTITY_TYPES: [&str; 4] = ["product", "link", "ad", "campaign"];
+const SNIPPET_TOKENS: i64 = 16;
+
+/// `(entity_type, entity_id)` for each `[[type:id]]` backlink in a body, in order
+pub fn parse_backlinks(body: &str) -> Vec<(String, i64)> {
+    let mut links = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("[[") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("]]") else { break };
+        if let Some((entity_type, id)) = rest[..end].split_once(':') {
+            let entity_type = entity_type.trim().to_lowercase();
+            if let (true, Ok(id)) = (ENTITY_TYPES.contains(&entity_type.as_str()), id.trim().parse::<i64>()) {
+                if !links.contains(&(entity_type.clone(), id)) {
+                    links.push((entity_type, id));
+                }
+            }
+        }
+        rest = &rest[end + 2..];
+    }
+    links
+}
+
+/// An FTS5 query matching every word of `query` as a prefix; `None` when
+/// there are no words to search for
+pub fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| format!("\"{}\"*", t))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Display label of a referenced entity; `None` when it doesn't exist
+fn label(conn: &Connection, entity_type: &str, entity_id: i64) -> rusqlite::Result<Option<String>> {
+    let sql = match entity_type {
+        "product" => "SELECT name FROM products WHERE id = ?1",
+        "link" => "SELECT COALESCE(tracking_url, '') FROM affiliate_links WHERE id = ?1",
+        "ad" => "SELECT headline FROM ad_copies WHERE id = ?1",
+        "campaign" => "SELECT name FROM campaigns WHERE id = ?1",
+        _ => return Ok(None),
+    };
+    conn.query_row(sql, params![entity_id], |row| row.get(0)).optional()
+}
+
+fn refs(conn: &Connection, note_id: i64) -> rusqlite::Result<Vec<NoteRef>> {
+    let mut stmt = conn.prepare(
+        "SELECT entity_type, entity_id FROM note_refs WHERE note_id = ?1 ORDER BY entity_type, entity_id",
+    )?;
+    let keys = stmt
+        .query_map(params![note_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    keys.into_iter()
+        .map(|(entity_type, entity_id)| {
+            let label = label(conn, &entity_type, entity_id)?;
+            Ok(NoteRef { entity_type, entity_id, label })
+        })
+        .collect()
+}
+
+pub fn get(conn: &Connection, note_id: i64) -> Result<Note, String> {
+    let note = conn
+        .query_row(
+            "SELECT id, title, body, created_at, updated_at FROM notes WHERE id = ?1",
+            params![note_id],
+            |row| {
+                Ok(Note {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    body: row.get(2)?,
+                    refs: Vec::new(),
+                    created_at: row.get(3)?,
+                    updated_at: row.get(4)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Note {} not found", note_id))?;
+    let refs = refs(conn, note_id).map_err(|e| e.to_string())?;
+    Ok(Note { refs, ..note })
+}
+
+fn notes_by_id(conn: &Connection, sql: &str, params: impl rusqlite::Params) -> Result<Vec<Note>, String> {
+    let ids: Vec<i64> = conn
+        .prepare(sql)
+        .and_then(|mut stmt| stmt.query_map(params, |row| row.get(0))?.collect())
+        .map_err(|e| e.to_string())?;
+    ids.into_iter().map(|id| get(conn, id)).collect()
+}
+
+/// Most recently edited notes first
+pub fn list(conn: &Connection, limit: i64) -> Result<Vec<Note>, String> {
+    notes_by_id(conn, "SELECT id FROM notes ORDER BY updated_at DESC, id DESC LIMIT ?1", params![limit])
+}
+
+/// Notes referring to an entity (its backlinks), most recently edited first
+pub fn for_entity(conn: &Connection, entity_type: &str, entity_id: i64) -> Result<Vec<Note>, String> {
+    notes_by_id(
+        conn,
+        "SELECT n.id FROM notes n JOIN note_refs r ON r.note_id = n.id
+         WHERE r.entity_type = ?1 AND r.entity_id = ?2
+         ORDER BY n.updated_at DESC, n.id DESC",
+        params![entity_type, entity_id],
+    )
+}
+
+/// Creates or replaces a note and its references. Every reference, explicit
+/// or written as a backlink, must point at an existing entity.
+pub fn save(conn: &mut Connection, input: &SaveNoteInput) -> Result<Note, String> {
+    let body = input.body.trim();
+    if body.is_empty() {
+        return Err("Note body is required".to_string());
+    }
+    let title = input.title.as_deref().map(str::trim).filter(|t| !t.is_empty());
+
+    let mut keys: Vec<(String, i64)> = Vec::new();
+    let explicit = input.refs.iter().map(|r| (r.entity_type.trim().to_lowercase(), r.entity_id));
+    for key in explicit.chain(parse_backlinks(body)) {
+        if !ENTITY_TYPES.contains(&key.0.as_str()) {
+            return Err(format!("Notes can't refer to '{}' (use product, link, ad, or campaign)", key.0));
+        }
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    for (entity_type, entity_id) in &keys {
+        if label(&tx, entity_type, *entity_id).map_err(|e| e.to_string())?.is_none() {
+            return Err(format!("The note refers to {} {}, which doesn't exist", entity_type, entity_id));
+        }
+    }
+    let note_id = match input.id {
+        Some(id) => {
+            let updated = tx
+                .execute(
+                    "UPDATE notes SET title = ?2, body = ?3, updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+                    params![id, title, body],
+                )
+                .map_err(|e| e.to_string())?;
+            if updated == 0 {
+                return Err(format!("Note {} not found", id));
+            }
+            tx.execute("DELETE FROM note_refs WHERE note_id = ?1", params![id]).map_err(|e| e.to_string())?;
+            id
+        }
+        None => {
+            tx.execute("INSERT INTO notes (title, body) VALUES (?1, ?2)", params![title, body])
+                .map_err(|e| e.to_string())?;
+            tx.last_insert_rowid()
+        }
+    };
+    for (entity_type, entity_id) in &keys {
+        tx.execute(
+            "INSERT INTO note_refs (note_id, entity_type, entity_id) VALUES (?1, ?2, ?3)",
+            params![note_id, entity_type, entity_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    get(conn, note_id)
+}
+
+pub fn delete(conn: &Connection, note_id: i64) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM note_refs WHERE note_id = ?1", params![note_id])?;
+    conn.execute("DELETE FROM notes WHERE id = ?1", params![note_id])?;
+    Ok(())
+}
+
+/// Full-text search over titles and bodies, best match first
+pub fn search(conn: &Connection, query: &str, limit: i64) -> Result<Vec<NoteSearchHit>, String> {
+    let Some(fts) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+    let matches: Vec<(i64, String)> = conn
+        .prepare(
+            "SELECT rowid, snippet(notes_fts, 1, '**', '**', '…', ?3) FROM notes_fts
+             WHERE notes_fts MATCH ?1 ORDER BY rank LIMIT ?2",
+        )
+        .and_then(|mut stmt| {
+            stmt.query_map(params![fts, limit, SNIPPET_TOKENS], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect()
+        })
+        .map_err(|e| e.to_string())?;
+    matches
+        .into_iter()
+        .map(|(id, snippet)| Ok(NoteSearchHit { note: get(conn, id)?, snippet }))
+        .collect()
+}
+
+/// Products referred to by notes matching `query`, for product search
+pub fn matching_product_ids(conn: &Connection, query: &str) -> rusqlite::Result<Vec<i64>> {
+    let Some(fts) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT r.entity_id FROM notes_fts f JOIN note_refs r ON r.note_id = f.rowid
+         WHERE notes_fts MATCH ?1 AND r.entity_type = 'product'",
+    )?;
+    let ids = stmt.query_map(params![fts], |row| row.get(0))?.collect();
+    ids
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backlinks_are_referenced_and_searchable() {
+        let body = "Compare [[product:1]] with [[Product: 2]], not [[page:3]] or [[product:x]] [[product:1]]";
+        assert_eq!(
+            parse_backlinks(body),
+            vec![("product".to_string(), 1), ("product".to_string(), 2)]
+        );
+        assert_eq!(fts_query("rate \"card\" 2024").as_deref(), Some("\"rate\"* \"card\"* \"2024\"*"));
+        assert_eq!(fts_query(" - "), None);
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE affiliate_links (id INTEGER PRIMARY KEY, tracking_url TEXT);
+             CREATE TABLE ad_copies (id INTEGER PRIMARY KEY, headline TEXT);
+             CREATE TABLE campaigns (id INTEGER PRIMARY KEY, name TEXT);
+             INSERT INTO products VALUES (1, 'Kettlebell'), (2, 'Yoga Mat');
+             INSERT INTO campaigns VALUES (5, 'Spring');
+             {}",
+            include_str!("../../../migrations/047_notes.sql")
+        ))
+        .unwrap();
+
+        let input = SaveNoteInput {
+            id: None,
+            title: Some("Call with program manager".to_string()),
+            body: "Commission bump for [[product:1]] and [[product:2]] next month".to_string(),
+            refs: vec![NoteRef { entity_type: "campaign".to_string(), entity_id: 5, label: None }],
+        };
+        let note = save(&mut conn, &input).unwrap();
+        assert_eq!(note.refs.len(), 3);
+        assert_eq!(note.refs[0].label.as_deref(), Some("Spring"));
+        assert!(save(&mut conn, &SaveNoteInput { body: "See [[ad:9]]".to_string(), ..input.clone() }).is_err());
+
+        assert_eq!(for_entity(&conn, "product", 2).unwrap()[0].id, note.id);
+        let hits = search(&conn, "commiss", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].snippet.contains("**Commission**"));
+        assert_eq!(matching_product_ids(&conn, "bump").unwrap().len(), 2);
+
+        // Edits are re-indexed; deleting a product drops its references
+        let edit = SaveNoteInput { id: Some(note.id), body: "Rates are final".to_string(), ..input };
+        save(&mut conn, &edit).unwrap();
+        assert!(search(&conn, "commission", 10).unwrap().is_empty());
+        conn.execute("DELETE FROM campaigns WHERE id = 5", []).unwrap();
+        assert!(get(&conn, note.id).unwrap().refs.is_empty());
+    }
+}
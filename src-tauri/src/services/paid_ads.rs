@@ -0,0 +1,291 @@
+//! Paid Ads Bulk Sheets
+//!
+//! Maps generated ads into the bulk import layouts of Meta Ads Manager and
+//! Google Ads Editor so paid-ads users can upload creative directly. Each
+//! product becomes an ad set / ad group inside one campaign, final URLs are
+//! the products' tracking links, and everything is imported paused for
+//! review. Google responsive search ads have hard character limits, so copy
+//! is cut at word boundaries and every cut is reported.
+
+use crate::services::ad_kit::slug;
+use crate::services::markdown_export::AdNote;
+use crate::services::style_rules::is_emoji;
+
+pub const DEFAULT_CAMPAIGN_NAME: &str = "AffilAI Affiliate Ads";
+
+pub const META_HEADERS: [&str; 12] = [
+    "Campaign Name",
+    "Campaign Status",
+    "Campaign Objective",
+    "Buying Type",
+    "Ad Set Name",
+    "Ad Set Run Status",
+    "Ad Name",
+    "Ad Status",
+    "Title",
+    "Body",
+    "Link",
+    "Call to Action",
+];
+
+pub const GOOGLE_HEADERS: [&str; 12] = [
+    "Campaign",
+    "Ad Group",
+    "Ad type",
+    "Status",
+    "Headline 1",
+    "Headline 2",
+    "Headline 3",
+    "Description 1",
+    "Description 2",
+    "Final URL",
+    "Path 1",
+    "Path 2",
+];
+
+const GOOGLE_HEADLINE_MAX: usize = 30;
+const GOOGLE_DESCRIPTION_MAX: usize = 90;
+const GOOGLE_PATH_MAX: usize = 15;
+
+/// Bulk sheet layouts supported by `to_csv`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaidAdsFormat {
+    Meta,
+    Google,
+}
+
+impl PaidAdsFormat {
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "meta" | "facebook" => Some(PaidAdsFormat::Meta),
+            "google" | "google_ads" => Some(PaidAdsFormat::Google),
+            _ => None,
+        }
+    }
+}
+
+/// Meta's call-to-action button type for a free-text CTA
+pub fn meta_call_to_action(cta: &str) -> &'static str {
+    let cta = cta.to_lowercase();
+    let has = |words: &[&str]| words.iter().any(|w| cta.contains(w));
+    if has(&["shop"]) {
+        "SHOP_NOW"
+    } else if has(&["buy"]) {
+        "BUY_NOW"
+    } else if has(&["order"]) {
+        "ORDER_NOW"
+    } else if has(&["sign up", "join", "subscribe"]) {
+        "SIGN_UP"
+    } else if has(&["offer", "deal", "save", "claim"]) {
+        "GET_OFFER"
+    } else if has(&["download"]) {
+        "DOWNLOAD"
+    } else {
+        "LEARN_MORE"
+    }
+}
+
+/// Ad copy without link placeholders, hashtags, or emoji, whitespace collapsed
+fn clean(text: &str) -> String {
+    text.replace("[LINK]", "")
+        .split_whitespace()
+        .filter(|word| !word.starts_with('#'))
+        .map(|word| word.chars().filter(|&c| !is_emoji(c) && c != '\u{FE0F}').collect::<String>())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Cuts `text` to `max` characters at a word boundary, without an ellipsis
+/// (Google rejects decorative punctuation). Returns the text and whether it was cut.
+pub fn fit(text: &str, max: usize) -> (String, bool) {
+    let text = text.trim();
+    if text.chars().count() <= max {
+        return (text.to_string(), false);
+    }
+    let cut: String = text.chars().take(max).collect();
+    let cut = match text.chars().nth(max) {
+        Some(next) if !next.is_whitespace() => cut.rfind(' ').map(|idx| &cut[..idx]).unwrap_or(&cut),
+        _ => cut.as_str(),
+    };
+    (cut.trim_end_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace()).to_string(), true)
+}
+
+/// Splits cleaned body copy into sentences
+fn sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    for c in text.chars() {
+        current.push(c);
+        if matches!(c, '.' | '!' | '?') {
+            sentences.push(current.trim().to_string());
+            current.clear();
+        }
+    }
+    if !current.trim().is_empty() {
+        sentences.push(current.trim().to_string());
+    }
+    sentences
+}
+
+/// Packs whole sentences into descriptions of at most `max` characters
+fn descriptions(body: &str, max: usize, count: usize) -> Vec<String> {
+    let mut descriptions: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for sentence in sentences(body) {
+        let candidate = if current.is_empty() { sentence.clone() } else { format!("{} {}", current, sentence) };
+        if candidate.chars().count() <= max {
+            current = candidate;
+        } else {
+            if !current.is_empty() {
+                descriptions.push(std::mem::take(&mut current));
+            }
+            current = sentence;
+        }
+        if descriptions.len() == count {
+            break;
+        }
+    }
+    if !current.is_empty() && descriptions.len() < count {
+        descriptions.push(current);
+    }
+    descriptions
+}
+
+/// Renders the bulk sheet for ads that have a tracking link; returns the CSV,
+/// the number of exported rows, the skipped ad IDs, and fit warnings
+pub fn to_csv(
+    format: PaidAdsFormat,
+    notes: &[AdNote],
+    campaign: &str,
+) -> Result<(String, usize, Vec<i64>, Vec<String>), String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let headers = match format {
+        PaidAdsFormat::Meta => META_HEADERS,
+        PaidAdsFormat::Google => GOOGLE_HEADERS,
+    };
+    writer.write_record(headers).map_err(|e| e.to_string())?;
+
+    let mut exported = 0;
+    let mut skipped = Vec::new();
+    let mut warnings = Vec::new();
+    for note in notes {
+        let ad_id = note.ad.id.unwrap_or_default();
+        let final_url = match note.tracking_url.as_deref() {
+            Some(url) => url,
+            None => {
+                skipped.push(ad_id);
+                continue;
+            }
+        };
+        let ad_name = note
+            .ad
+            .variation_name
+            .clone()
+            .unwrap_or_else(|| format!("{} - Ad {}", note.product_name, ad_id));
+        let body = clean(note.ad.body_text.as_deref().unwrap_or(""));
+        let cta = note.ad.cta.as_deref().unwrap_or("");
+
+        let record = match format {
+            PaidAdsFormat::Meta => vec![
+                campaign.to_string(),
+                "PAUSED".to_string(),
+                "Outcome Traffic".to_string(),
+                "AUCTION".to_string(),
+                note.product_name.clone(),
+                "PAUSED".to_string(),
+                ad_name,
+                "PAUSED".to_string(),
+                clean(&note.ad.headline),
+                body,
+                final_url.to_string(),
+                meta_call_to_action(cta).to_string(),
+            ],
+            PaidAdsFormat::Google => {
+                let mut cut = Vec::new();
+                let mut headlines: Vec<String> = Vec::new();
+                let sources = [clean(&note.ad.headline), note.product_name.clone(), clean(cta), "Shop Now".to_string()];
+                for source in sources.iter().filter(|s| !s.is_empty()) {
+                    let (headline, was_cut) = fit(source, GOOGLE_HEADLINE_MAX);
+                    if was_cut {
+                        cut.push(format!("headline \"{}\"", source));
+                    }
+                    if headlines.len() < 3 && !headlines.iter().any(|h| h.eq_ignore_ascii_case(&headline)) {
+                        headlines.push(headline);
+                    }
+                }
+                headlines.resize(3, String::new());
+
+                let mut body_descriptions = descriptions(&body, GOOGLE_DESCRIPTION_MAX, 2);
+                if body_descriptions.is_empty() {
+                    body_descriptions.push(format!("Discover {}.", note.product_name));
+                }
+                let descriptions: Vec<String> = body_descriptions
+                    .iter()
+                    .map(|d| {
+                        let (description, was_cut) = fit(d, GOOGLE_DESCRIPTION_MAX);
+                        if was_cut {
+                            cut.push("description".to_string());
+                        }
+                        description
+                    })
+                    .chain(std::iter::repeat(String::new()))
+                    .take(2)
+                    .collect();
+                if !cut.is_empty() {
+                    warnings.push(format!("Ad {}: shortened {} to fit Google Ads limits", ad_id, cut.join(", ")));
+                }
+
+                let (path_1, _) = fit(&slug(&note.category), GOOGLE_PATH_MAX);
+                let (path_2, _) = fit(&slug(&note.product_name), GOOGLE_PATH_MAX);
+                vec![
+                    campaign.to_string(),
+                    note.product_name.clone(),
+                    "Responsive search ad".to_string(),
+                    "Paused".to_string(),
+                    headlines[0].clone(),
+                    headlines[1].clone(),
+                    headlines[2].clone(),
+                    descriptions[0].clone(),
+                    descriptions[1].clone(),
+                    final_url.to_string(),
+                    path_1.trim_matches('-').to_string(),
+                    path_2.trim_matches('-').to_string(),
+                ]
+            }
+        };
+        writer.write_record(&record).map_err(|e| e.to_string())?;
+        exported += 1;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    let csv = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+    Ok((csv, exported, skipped, warnings))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_cuts_at_word_boundary() {
+        assert_eq!(fit("Glow SPF 50 Mineral Sunscreen Stick", 30), ("Glow SPF 50 Mineral Sunscreen".to_string(), true));
+        assert_eq!(fit("Short headline", 30), ("Short headline".to_string(), false));
+    }
+
+    #[test]
+    fn test_descriptions_pack_whole_sentences() {
+        let body = clean("Blends clear in 10 seconds. 🔥 No white cast, no greasy finish. Reef-safe minerals only. #ad [LINK]");
+        assert_eq!(
+            descriptions(&body, 90, 2),
+            vec![
+                "Blends clear in 10 seconds. No white cast, no greasy finish. Reef-safe minerals only.".to_string()
+            ]
+        );
+        assert_eq!(meta_call_to_action("Shop the drop"), "SHOP_NOW");
+    }
+}
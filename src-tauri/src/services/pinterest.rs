@@ -0,0 +1,205 @@
+//! Pinterest Pins
+//!
+//! Builds the Pinterest-specific fields for `pinterest_pin` ads: a pin title,
+//! a keyword-rich description, alt text, and board suggestions, all within
+//! Pinterest's length limits. Pin ads export to the CSV layout accepted by
+//! Pinterest's bulk pin upload (Title, Media URL, Pinterest board, ...).
+
+use crate::models::pinterest::PinMetadata;
+use crate::models::product::Product;
+use crate::services::accessibility;
+
+pub const TITLE_MAX: usize = 100;
+pub const DESCRIPTION_MAX: usize = 500;
+pub const ALT_TEXT_MAX: usize = 500;
+const MAX_KEYWORDS: usize = 8;
+
+/// Column order of Pinterest's bulk upload template
+pub const BULK_UPLOAD_HEADERS: [&str; 8] = [
+    "Title",
+    "Media URL",
+    "Pinterest board",
+    "Thumbnail",
+    "Description",
+    "Link",
+    "Publish date",
+    "Keywords",
+];
+
+/// Search keywords for a pin: the product's tags, then category terms
+pub fn keywords(product: &Product) -> Vec<String> {
+    let category_terms: &[&str] = match product.category.as_str() {
+        "Beauty & Skincare" => &["skincare routine", "glowing skin", "beauty favorites"],
+        "Health & Wellness" => &["wellness routine", "healthy habits", "self care"],
+        "Fitness & Recovery" => &["home workout", "muscle recovery", "fitness gear"],
+        "Consumer Electronics" => &["tech gadgets", "smart home", "gift ideas for him"],
+        "Wearable Health Technology" => &["fitness tracker", "health tech", "wearable tech"],
+        "Fashion & Apparel" => &["outfit ideas", "capsule wardrobe", "style inspiration"],
+        "Home & Kitchen" => &["kitchen essentials", "home organization", "home decor ideas"],
+        _ => &["gift ideas", "must have products"],
+    };
+
+    let mut keywords: Vec<String> = Vec::new();
+    let candidates = product
+        .tags
+        .iter()
+        .map(|t| t.trim().to_lowercase())
+        .chain(category_terms.iter().map(|t| t.to_string()))
+        .chain(std::iter::once(product.category.to_lowercase()));
+    for keyword in candidates {
+        if !keyword.is_empty() && !keywords.contains(&keyword) {
+            keywords.push(keyword);
+        }
+    }
+    keywords.truncate(MAX_KEYWORDS);
+    keywords
+}
+
+/// Boards a pin would fit on, most specific first
+pub fn board_suggestions(product: &Product) -> Vec<String> {
+    let boards: &[&str] = match product.category.as_str() {
+        "Beauty & Skincare" => &["Skincare Routine", "Beauty Must-Haves", "Self Care Ideas"],
+        "Health & Wellness" => &["Wellness Finds", "Healthy Living", "Self Care Ideas"],
+        "Fitness & Recovery" => &["Home Gym Ideas", "Fitness Motivation", "Recovery Tools"],
+        "Consumer Electronics" | "Wearable Health Technology" => &["Tech Gadgets", "Gift Ideas", "Smart Living"],
+        "Fashion & Apparel" => &["Outfit Ideas", "Wardrobe Essentials", "Style Inspiration"],
+        "Home & Kitchen" => &["Kitchen Essentials", "Home Organization", "Home Finds"],
+        _ => &["Gift Ideas", "Favorite Finds"],
+    };
+    boards.iter().map(|b| b.to_string()).collect()
+}
+
+/// Pin metadata for an ad whose copy is already generated
+pub fn build_pin(product: &Product, headline: &str, body: &str) -> PinMetadata {
+    let keywords = keywords(product);
+
+    // Keywords close the description as a plain sentence so truncating the copy never drops them
+    let keyword_line = format!("Perfect for: {}.", keywords[..keywords.len().min(4)].join(", "));
+    let body_budget = DESCRIPTION_MAX - keyword_line.chars().count() - 1;
+    let description = format!("{} {}", truncate(body, body_budget), keyword_line);
+
+    let alt_text = truncate(&accessibility::alt_text(product), ALT_TEXT_MAX);
+
+    PinMetadata {
+        title: truncate(headline, TITLE_MAX),
+        description,
+        alt_text,
+        keywords,
+        board_suggestions: board_suggestions(product),
+    }
+}
+
+/// Cuts text to `max` characters at a word boundary, adding an ellipsis
+pub fn truncate(text: &str, max: usize) -> String {
+    let text = text.trim();
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max - 1).collect();
+    let cut = cut.rfind(' ').map(|idx| &cut[..idx]).unwrap_or(&cut);
+    format!("{}…", cut.trim_end_matches(|c: char| c.is_ascii_punctuation()))
+}
+
+/// One row of the bulk upload CSV
+pub struct BulkPinRow<'a> {
+    pub pin: &'a PinMetadata,
+    pub media_url: &'a str,
+    pub board: &'a str,
+    pub link: &'a str,
+    pub publish_date: Option<&'a str>,
+}
+
+/// Renders rows in Pinterest's bulk upload layout
+pub fn to_bulk_csv(rows: &[BulkPinRow]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(BULK_UPLOAD_HEADERS).map_err(|e| e.to_string())?;
+
+    for row in rows {
+        writer
+            .write_record([
+                row.pin.title.as_str(),
+                row.media_url,
+                row.board,
+                "",
+                row.pin.description.as_str(),
+                row.link,
+                row.publish_date.unwrap_or(""),
+                &row.pin.keywords.join(", "),
+            ])
+            .map_err(|e| e.to_string())?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product() -> Product {
+        Product {
+            id: Some(1),
+            name: "Glow Serum".to_string(),
+            category: "Beauty & Skincare".to_string(),
+            description: None,
+            price_range: None,
+            target_audience: None,
+            trending_score: None,
+            notes: None,
+            image_url: None,
+            amazon_asin: None,
+            tiktok_product_id: None,
+            instagram_product_id: None,
+            youtube_video_id: None,
+            pinterest_pin_id: None,
+            product_url: None,
+            created_at: None,
+            updated_at: None,
+            stage: None,
+            stage_changed_at: None,
+            tags: vec!["Vitamin C".to_string(), "skincare routine".to_string()],
+            updated_by: None,
+            availability: None,
+            availability_checked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_build_pin_respects_limits() {
+        let long_headline = "Glow ".repeat(40);
+        let long_body = "Brightens and hydrates. ".repeat(40);
+        let pin = build_pin(&product(), &long_headline, &long_body);
+
+        assert!(pin.title.chars().count() <= TITLE_MAX);
+        assert!(pin.description.chars().count() <= DESCRIPTION_MAX);
+        assert_eq!(pin.keywords[..2], ["vitamin c".to_string(), "skincare routine".to_string()]);
+        assert_eq!(pin.board_suggestions[0], "Skincare Routine");
+    }
+
+    #[test]
+    fn test_bulk_csv_layout() {
+        let pin = build_pin(&product(), "Glow Serum", "Brighter skin, fast.");
+        let rows = vec![BulkPinRow {
+            pin: &pin,
+            media_url: "https://img.example/serum.jpg",
+            board: "Skincare Routine",
+            link: "https://amzn.to/x",
+            publish_date: None,
+        }];
+        let csv = to_bulk_csv(&rows).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("Title,Media URL,Pinterest board,Thumbnail,Description,Link,Publish date,Keywords")
+        );
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("Glow Serum,https://img.example/serum.jpg,Skincare Routine,,"));
+    }
+}
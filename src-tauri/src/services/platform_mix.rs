@@ -0,0 +1,236 @@
+//! Platform Mix Simulator
+//!
+//! "What if" projections for splitting a product's reach across platforms.
+//! Each scenario's impressions are divided by its allocation shares, then
+//! clicks, conversions, and order value follow from the benchmark for each
+//! platform and the product's category, and earnings from the commission
+//! rate. Order value and commission come from the product unless the
+//! assumptions override them; when neither is known a fallback is used and
+//! reported as a warning. A single-platform estimate is the same projection
+//! with one scenario.
+
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::models::benchmark::Benchmark;
+use crate::models::platform_mix::{
+    MixScenario, PlatformAllocation, PlatformMixSimulation, PlatformProjection, ScenarioProjection,
+    SimulationAssumptions,
+};
+use crate::models::product::Product;
+use crate::services::{benchmarks, product_comparison};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Order value assumed when the product has no price range
+const FALLBACK_ORDER_VALUE: f64 = 30.0;
+/// Commission rate assumed when no program rate is known
+const FALLBACK_COMMISSION_RATE: f64 = 0.05;
+
+/// An even split across `platforms`, then all-in on each one
+pub fn default_scenarios(platforms: &[String]) -> Vec<MixScenario> {
+    let allocation = |platform: &str| PlatformAllocation { platform: platform.to_string(), share: 1.0 };
+    let mut scenarios = vec![MixScenario {
+        name: "Even split".to_string(),
+        allocations: platforms.iter().map(|p| allocation(p)).collect(),
+    }];
+    scenarios.extend(platforms.iter().map(|p| MixScenario {
+        name: format!("All on {}", p),
+        allocations: vec![allocation(p)],
+    }));
+    scenarios
+}
+
+/// Projects one scenario with the given benchmark lookup
+pub fn project(
+    scenario: &MixScenario,
+    benchmark: impl Fn(&str) -> Result<Benchmark, String>,
+    impressions: i64,
+    order_value: f64,
+    commission_rate: f64,
+) -> Result<ScenarioProjection, String> {
+    if scenario.allocations.iter().any(|a| a.share < 0.0) {
+        return Err(format!("Scenario '{}' has a negative share", scenario.name));
+    }
+    let total_share: f64 = scenario.allocations.iter().map(|a| a.share).sum();
+    if total_share <= 0.0 {
+        return Err(format!("Scenario '{}' allocates nothing", scenario.name));
+    }
+
+    let mut projection = ScenarioProjection {
+        name: scenario.name.clone(),
+        platforms: Vec::new(),
+        clicks: 0.0,
+        conversions: 0.0,
+        earnings: 0.0,
+    };
+    for allocation in &scenario.allocations {
+        let platform = allocation.platform.trim().to_lowercase();
+        let rates = benchmark(&platform)?;
+        let share = allocation.share / total_share;
+        let platform_impressions = (impressions as f64 * share).round() as i64;
+        let clicks = platform_impressions as f64 * rates.ctr;
+        let conversions = clicks * rates.conversion_rate;
+        let platform_order_value = order_value * rates.aov_multiplier;
+        let revenue = conversions * platform_order_value;
+        let earnings = revenue * commission_rate;
+        projection.clicks += clicks;
+        projection.conversions += conversions;
+        projection.earnings += earnings;
+        projection.platforms.push(PlatformProjection {
+            platform,
+            share,
+            impressions: platform_impressions,
+            ctr: rates.ctr,
+            conversion_rate: rates.conversion_rate,
+            order_value: platform_order_value,
+            clicks,
+            conversions,
+            revenue,
+            earnings,
+        });
+    }
+    Ok(projection)
+}
+
+/// Projects every scenario for a product, highest earnings first
+pub fn simulate(
+    conn: &Connection,
+    product_id: i64,
+    assumptions: &SimulationAssumptions,
+) -> Result<PlatformMixSimulation, String> {
+    if assumptions.impressions <= 0 {
+        return Err("Impressions must be greater than zero".to_string());
+    }
+    let product: Product = conn
+        .query_row(
+            &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
+            params![product_id],
+            product_from_row,
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Product {} not found", product_id))?;
+
+    let mut warnings = Vec::new();
+    let average_order_value = match assumptions.average_order_value {
+        Some(value) if value > 0.0 => value,
+        Some(_) => return Err("Average order value must be greater than zero".to_string()),
+        None => {
+            let range = product.price_range.as_deref().map(product_comparison::parse_price_range);
+            match range {
+                Some((Some(low), Some(high))) => (low + high) / 2.0,
+                _ => {
+                    warnings.push(format!("No price range; assuming a ${:.2} order", FALLBACK_ORDER_VALUE));
+                    FALLBACK_ORDER_VALUE
+                }
+            }
+        }
+    };
+    let commission_rate = match assumptions.commission_rate {
+        Some(rate) if (0.0..=1.0).contains(&rate) => rate,
+        Some(_) => return Err("Commission rate must be between 0 and 1 (0.08 = 8%)".to_string()),
+        None => {
+            let best = product_comparison::commission_options(conn, product_id)
+                .map_err(|e| e.to_string())?
+                .iter()
+                .filter_map(|o| o.commission_rate)
+                .reduce(f64::max);
+            best.unwrap_or_else(|| {
+                warnings.push(format!(
+                    "No known commission rate; assuming {:.0}%",
+                    FALLBACK_COMMISSION_RATE * 100.0
+                ));
+                FALLBACK_COMMISSION_RATE
+            })
+        }
+    };
+
+    let scenarios = if assumptions.scenarios.is_empty() {
+        default_scenarios(&benchmarks::platforms(conn).map_err(|e| e.to_string())?)
+    } else {
+        assumptions.scenarios.clone()
+    };
+    let benchmark = |platform: &str| benchmarks::lookup(conn, platform, &product.category);
+    let mut projections = scenarios
+        .iter()
+        .map(|s| project(s, benchmark, assumptions.impressions, average_order_value, commission_rate))
+        .collect::<Result<Vec<_>, _>>()?;
+    projections.sort_by(|a, b| b.earnings.total_cmp(&a.earnings));
+
+    Ok(PlatformMixSimulation {
+        product_id,
+        product_name: product.name,
+        impressions: assumptions.impressions,
+        average_order_value,
+        commission_rate,
+        scenarios: projections,
+        warnings,
+    })
+}
+
+/// Earnings estimate for putting all of `impressions` on one platform
+pub fn estimate(
+    conn: &Connection,
+    product_id: i64,
+    platform: &str,
+    impressions: i64,
+) -> Result<PlatformMixSimulation, String> {
+    let platform = platform.trim().to_lowercase();
+    let scenario = MixScenario {
+        name: platform.clone(),
+        allocations: vec![PlatformAllocation { platform, share: 1.0 }],
+    };
+    let assumptions = SimulationAssumptions {
+        impressions,
+        scenarios: vec![scenario],
+        average_order_value: None,
+        commission_rate: None,
+    };
+    simulate(conn, product_id, &assumptions)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenarios_split_impressions_by_share() {
+        let scenarios = default_scenarios(&["tiktok".to_string(), "email".to_string()]);
+        assert_eq!(scenarios.len(), 3);
+        assert_eq!(scenarios[2].name, "All on email");
+
+        let scenario = MixScenario {
+            name: "Mostly TikTok".to_string(),
+            allocations: vec![
+                PlatformAllocation { platform: "TikTok".to_string(), share: 3.0 },
+                PlatformAllocation { platform: "pinterest".to_string(), share: 1.0 },
+            ],
+        };
+        let benchmark = |platform: &str| {
+            Ok(Benchmark {
+                id: 1,
+                platform: platform.to_string(),
+                category: String::new(),
+                ctr: if platform == "tiktok" { 0.01 } else { 0.02 },
+                conversion_rate: 0.1,
+                aov_multiplier: if platform == "tiktok" { 1.0 } else { 1.5 },
+                builtin: true,
+                edited: false,
+            })
+        };
+        let projection = project(&scenario, benchmark, 100_000, 40.0, 0.05).unwrap();
+        assert_eq!(projection.platforms[0].platform, "tiktok");
+        assert_eq!(projection.platforms[0].impressions, 75_000);
+        assert!((projection.platforms[0].clicks - 750.0).abs() < 1e-9);
+        assert!((projection.clicks - 1250.0).abs() < 1e-9);
+        assert!((projection.conversions - 125.0).abs() < 1e-9);
+        // TikTok: 75 conversions × $40; Pinterest: 50 × $60 (1.5× order value)
+        assert_eq!(projection.platforms[1].order_value, 60.0);
+        assert!((projection.earnings - 300.0).abs() < 1e-9);
+
+        let empty = MixScenario { name: "Nothing".to_string(), allocations: Vec::new() };
+        assert!(project(&empty, benchmark, 1000, 40.0, 0.05).is_err());
+    }
+}
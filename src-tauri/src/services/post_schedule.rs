@@ -0,0 +1,382 @@
+//! Post Scheduling
+//!
+//! The posting calendar. A scheduled post can carry a recurrence rule
+//! ("re-promote every 21 days"), making it the head of a series. The
+//! scheduler expands each series into concrete calendar entries a few weeks
+//! ahead, rotating through the product's ad variations and nudging an
+//! occurrence to the next free day when its platform is already booked.
+//! Occurrences that break a posting cap are skipped when caps are enforced.
+
+use crate::database::settings::get_setting_parsed;
+use crate::database::{get_connection, parse_timestamp};
+use crate::models::scheduled_post::{SaveScheduledPostInput, ScheduledPost};
+use crate::services::posting_caps;
+use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::collections::HashSet;
+use tauri::AppHandle;
+
+pub const SCHEDULED: &str = "scheduled";
+pub const POSTED: &str = "posted";
+pub const SKIPPED: &str = "skipped";
+pub const CANCELLED: &str = "cancelled";
+
+pub const POST_STATUSES: [&str; 4] = [SCHEDULED, POSTED, SKIPPED, CANCELLED];
+
+/// How far ahead recurring series are expanded onto the calendar
+const DEFAULT_HORIZON_DAYS: i64 = 42;
+/// Furthest an occurrence is moved to avoid a day its platform is booked
+const MAX_SHIFT_DAYS: i64 = 6;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+pub const POST_COLUMNS: &str = "s.id, s.product_id, COALESCE(p.name, ''), s.ad_id, s.platform, s.scheduled_at,
+     s.status, s.notes, s.repeat_every_days, s.repeat_until, s.rotate_ads, s.series_id,
+     s.created_at, s.updated_at, s.approval_state";
+pub const POST_FROM: &str = "scheduled_posts s LEFT JOIN products p ON p.id = s.product_id";
+
+/// Maps a row selected with `POST_COLUMNS` into a `ScheduledPost`
+pub fn post_from_row(row: &Row) -> rusqlite::Result<ScheduledPost> {
+    Ok(ScheduledPost {
+        id: row.get(0)?,
+        product_id: row.get(1)?,
+        product_name: row.get(2)?,
+        ad_id: row.get(3)?,
+        platform: row.get(4)?,
+        scheduled_at: row.get(5)?,
+        status: row.get(6)?,
+        notes: row.get(7)?,
+        repeat_every_days: row.get(8)?,
+        repeat_until: row.get(9)?,
+        rotate_ads: row.get(10)?,
+        series_id: row.get(11)?,
+        created_at: row.get(12)?,
+        updated_at: row.get(13)?,
+        approval_state: row.get(14)?,
+    })
+}
+
+/// Parses a stored or frontend timestamp (including `datetime-local` values
+/// without seconds) into the stored format
+pub fn normalize_time(value: &str) -> Option<String> {
+    let value = value.trim();
+    let parsed = parse_timestamp(value)
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M").ok())
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").ok())
+        .or_else(|| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))?;
+    Some(parsed.format(TIMESTAMP_FORMAT).to_string())
+}
+
+pub fn validate(input: &SaveScheduledPostInput) -> Result<(), String> {
+    if input.platform.trim().is_empty() {
+        return Err("Platform is required".to_string());
+    }
+    if normalize_time(&input.scheduled_at).is_none() {
+        return Err(format!("Invalid schedule time '{}'", input.scheduled_at));
+    }
+    if let Some(until) = input.repeat_until.as_deref().filter(|u| !u.trim().is_empty()) {
+        if normalize_time(until).is_none() {
+            return Err(format!("Invalid repeat end '{}'", until));
+        }
+    }
+    if input.repeat_every_days.is_some_and(|days| days < 1) {
+        return Err("Repeat interval must be at least one day".to_string());
+    }
+    Ok(())
+}
+
+pub fn get(conn: &Connection, post_id: i64) -> rusqlite::Result<Option<ScheduledPost>> {
+    conn.query_row(
+        &format!("SELECT {} FROM {} WHERE s.id = ?1", POST_COLUMNS, POST_FROM),
+        params![post_id],
+        post_from_row,
+    )
+    .optional()
+}
+
+/// Calendar entries between `from` and `to` (inclusive, either open-ended), oldest first
+pub fn list(
+    conn: &Connection,
+    from: Option<&str>,
+    to: Option<&str>,
+    platform: Option<&str>,
+) -> rusqlite::Result<Vec<ScheduledPost>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM {}
+         WHERE (?1 IS NULL OR s.scheduled_at >= ?1) AND (?2 IS NULL OR s.scheduled_at <= ?2)
+           AND (?3 IS NULL OR s.platform = ?3 COLLATE NOCASE)
+         ORDER BY s.scheduled_at, s.id",
+        POST_COLUMNS, POST_FROM
+    ))?;
+    let posts = stmt.query_map(params![from, to, platform], post_from_row)?.collect();
+    posts
+}
+
+/// Inserts or updates a post. Changing a series head replaces its upcoming
+/// occurrences; the series is then expanded again.
+pub fn save(conn: &Connection, input: &SaveScheduledPostInput) -> rusqlite::Result<ScheduledPost> {
+    let scheduled_at = normalize_time(&input.scheduled_at).unwrap_or_else(|| input.scheduled_at.clone());
+    let repeat_until = input.repeat_until.as_deref().and_then(normalize_time);
+    let notes = input.notes.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    let rotate_ads = input.rotate_ads.unwrap_or(true);
+
+    let post_id = match input.id {
+        Some(id) => {
+            conn.execute(
+                "UPDATE scheduled_posts SET product_id = ?1, ad_id = ?2, platform = ?3, scheduled_at = ?4,
+                 notes = ?5, repeat_every_days = CASE WHEN series_id IS NULL THEN ?6 END,
+                 repeat_until = CASE WHEN series_id IS NULL THEN ?7 END, rotate_ads = ?8,
+                 updated_at = CURRENT_TIMESTAMP
+                 WHERE id = ?9",
+                params![
+                    input.product_id,
+                    input.ad_id,
+                    input.platform.trim().to_lowercase(),
+                    scheduled_at,
+                    notes,
+                    input.repeat_every_days,
+                    repeat_until,
+                    rotate_ads,
+                    id
+                ],
+            )?;
+            // Upcoming occurrences follow the edited rule
+            conn.execute(
+                "DELETE FROM scheduled_posts
+                 WHERE series_id = ?1 AND status = ?2 AND scheduled_at > datetime('now')",
+                params![id, SCHEDULED],
+            )?;
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO scheduled_posts (product_id, ad_id, platform, scheduled_at, notes,
+                 repeat_every_days, repeat_until, rotate_ads)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    input.product_id,
+                    input.ad_id,
+                    input.platform.trim().to_lowercase(),
+                    scheduled_at,
+                    notes,
+                    input.repeat_every_days,
+                    repeat_until,
+                    rotate_ads
+                ],
+            )?;
+            conn.last_insert_rowid()
+        }
+    };
+
+    let post = get(conn, post_id)?.ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+    if post.repeat_every_days.is_some() {
+        expand_series(conn, &post, Utc::now().naive_utc())?;
+    }
+    Ok(post)
+}
+
+pub fn set_status(conn: &Connection, post_id: i64, status: &str) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE scheduled_posts SET status = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        params![status, post_id],
+    )
+}
+
+/// Removes a post. A series head takes its occurrences with it; a single
+/// occurrence is cancelled instead so expansion doesn't bring it back.
+pub fn delete(conn: &Connection, post: &ScheduledPost, whole_series: bool) -> rusqlite::Result<usize> {
+    match post.series_id {
+        Some(series_id) if whole_series => {
+            conn.execute("DELETE FROM scheduled_posts WHERE id = ?1 OR series_id = ?1", params![series_id])
+        }
+        Some(_) => set_status(conn, post.id, CANCELLED),
+        None => conn.execute("DELETE FROM scheduled_posts WHERE id = ?1 OR series_id = ?1", params![post.id]),
+    }
+}
+
+/// A series' recurrence rule
+#[derive(Debug, Clone, Copy)]
+pub struct Recurrence {
+    pub start: NaiveDateTime, // The head's time; occurrences fall on start + k * every_days
+    pub every_days: i64,
+    pub until: Option<NaiveDateTime>,
+}
+
+/// Times of the occurrences after `after` (the series' latest entry) that fall
+/// between `now` and `horizon_end`. Each is shifted up to a few days (never a
+/// full interval) past days in `busy`, which is updated with the picks.
+pub fn plan_occurrences(
+    rule: &Recurrence,
+    after: NaiveDateTime,
+    now: NaiveDateTime,
+    horizon_end: NaiveDateTime,
+    busy: &mut HashSet<NaiveDate>,
+) -> Vec<NaiveDateTime> {
+    let every_days = rule.every_days.max(1);
+    let max_shift = MAX_SHIFT_DAYS.min(every_days - 1);
+    // Shifts stay within an interval, so this recovers the latest entry's slot
+    let mut k = (after - rule.start).num_days().max(0) / every_days + 1;
+
+    let mut times = Vec::new();
+    loop {
+        let nominal = rule.start + Duration::days(every_days * k);
+        k += 1;
+        if nominal > horizon_end || rule.until.is_some_and(|until| nominal > until) {
+            break;
+        }
+        if nominal < now {
+            continue;
+        }
+        let at = (0..=max_shift)
+            .map(|shift| nominal + Duration::days(shift))
+            .find(|at| !busy.contains(&at.date()))
+            .unwrap_or(nominal);
+        busy.insert(at.date());
+        times.push(at);
+    }
+    times
+}
+
+/// The ad after `previous` in `ads`, wrapping around; the first when unknown
+pub fn next_ad(ads: &[i64], previous: Option<i64>) -> Option<i64> {
+    let position = previous.and_then(|prev| ads.iter().position(|&id| id == prev));
+    match position {
+        Some(i) => ads.get((i + 1) % ads.len()).copied(),
+        None => ads.first().copied(),
+    }
+}
+
+/// The product's postable ads (sequence containers and messages excluded), oldest first
+fn product_ads(conn: &Connection, product_id: i64) -> rusqlite::Result<Vec<i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM ad_copies
+         WHERE product_id = ?1 AND parent_ad_id IS NULL AND COALESCE(ad_type, '') != 'email_sequence'
+         ORDER BY id",
+    )?;
+    let ads = stmt.query_map(params![product_id], |row| row.get(0))?.collect();
+    ads
+}
+
+/// Days from today on that already have a post on `platform`
+pub fn busy_days(conn: &Connection, platform: &str) -> rusqlite::Result<HashSet<NaiveDate>> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT date(scheduled_at) FROM scheduled_posts
+         WHERE platform = ?1 COLLATE NOCASE AND status != ?2 AND scheduled_at >= date('now')",
+    )?;
+    let days = stmt
+        .query_map(params![platform, CANCELLED], |row| row.get::<_, String>(0))?
+        .filter_map(|day| day.ok().and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()))
+        .collect();
+    Ok(days)
+}
+
+/// Adds a series' upcoming occurrences to the calendar; returns how many
+pub fn expand_series(conn: &Connection, head: &ScheduledPost, now: NaiveDateTime) -> rusqlite::Result<usize> {
+    let (Some(every_days), Some(start)) = (head.repeat_every_days, parse_timestamp(&head.scheduled_at)) else {
+        return Ok(0);
+    };
+    if head.status == CANCELLED {
+        return Ok(0);
+    }
+    let rule = Recurrence { start, every_days, until: head.repeat_until.as_deref().and_then(parse_timestamp) };
+
+    let latest: Option<(String, Option<i64>)> = conn
+        .query_row(
+            "SELECT scheduled_at, ad_id FROM scheduled_posts WHERE series_id = ?1
+             ORDER BY scheduled_at DESC, id DESC LIMIT 1",
+            params![head.id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let (after, mut previous_ad) = match latest {
+        Some((at, ad_id)) => (parse_timestamp(&at).unwrap_or(start), ad_id),
+        None => (start, head.ad_id),
+    };
+
+    let horizon_days = get_setting_parsed(conn, "schedule_horizon_days", DEFAULT_HORIZON_DAYS).max(1);
+    let mut busy = busy_days(conn, &head.platform)?;
+    let times = plan_occurrences(&rule, after, now, now + Duration::days(horizon_days), &mut busy);
+    let ads = if head.rotate_ads { product_ads(conn, head.product_id)? } else { Vec::new() };
+    let rules = posting_caps::load_rules(conn);
+
+    let mut added = 0;
+    for at in &times {
+        let stamp = at.format(TIMESTAMP_FORMAT).to_string();
+        let conflicts = posting_caps::check(conn, &rules, head.product_id, &head.platform, &stamp, None)?;
+        if let Some(conflict) = conflicts.first() {
+            eprintln!("Recurring post {} on {}: {}", head.id, stamp, conflict.message);
+            if rules.enforce {
+                continue;
+            }
+        }
+        let ad_id = if head.rotate_ads { next_ad(&ads, previous_ad).or(head.ad_id) } else { head.ad_id };
+        conn.execute(
+            "INSERT INTO scheduled_posts (product_id, ad_id, platform, scheduled_at, notes, rotate_ads, series_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                head.product_id,
+                ad_id,
+                head.platform,
+                stamp,
+                head.notes,
+                head.rotate_ads,
+                head.id
+            ],
+        )?;
+        previous_ad = ad_id;
+        added += 1;
+    }
+    Ok(added)
+}
+
+/// Expands every active recurring series; returns the occurrences added
+pub fn expand_all(conn: &Connection, now: NaiveDateTime) -> rusqlite::Result<usize> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM {}
+         WHERE s.repeat_every_days IS NOT NULL AND s.series_id IS NULL AND s.status != ?1
+         ORDER BY s.id",
+        POST_COLUMNS, POST_FROM
+    ))?;
+    let heads = stmt.query_map(params![CANCELLED], post_from_row)?.collect::<Result<Vec<_>, _>>()?;
+
+    let mut added = 0;
+    for head in &heads {
+        added += expand_series(conn, head, now)?;
+    }
+    Ok(added)
+}
+
+/// Scheduled job entry point: keeps recurring series filled in ahead
+pub fn run_expansion_job(app_handle: &AppHandle) -> Result<String, String> {
+    let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    let added = expand_all(&conn, Utc::now().naive_utc()).map_err(|e| e.to_string())?;
+    Ok(format!("{} recurring post(s) added to the calendar", added))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_occurrences_avoids_booked_days() {
+        let at = |s: &str| parse_timestamp(s).unwrap();
+        let rule = Recurrence { start: at("2026-01-05 18:00:00"), every_days: 21, until: None };
+        let mut busy = HashSet::from([NaiveDate::from_ymd_opt(2026, 2, 16).unwrap()]);
+
+        // The Jan 26 slot is already past; Feb 16 is booked and moves a day
+        let (now, horizon) = (at("2026-02-01 00:00:00"), at("2026-03-31 00:00:00"));
+        let times = plan_occurrences(&rule, rule.start, now, horizon, &mut busy);
+        assert_eq!(times, [at("2026-02-17 18:00:00"), at("2026-03-09 18:00:00"), at("2026-03-30 18:00:00")]);
+
+        // Expanding again after a shifted entry continues the series without drift
+        let more = plan_occurrences(&rule, times[0], now, at("2026-03-10 00:00:00"), &mut busy);
+        assert_eq!(more, [at("2026-03-10 18:00:00")]);
+
+        assert_eq!(next_ad(&[4, 9, 12], Some(12)), Some(4));
+        assert_eq!(next_ad(&[4, 9, 12], None), Some(4));
+    }
+}
@@ -0,0 +1,230 @@
+//! Best Time to Post
+//!
+//! Scores every weekday/hour for a product on a platform by blending three
+//! signals: the product's click heatmap (falling back to the whole workspace
+//! while the product has few clicks), per-platform engagement defaults, and
+//! the audience's age from the product or niche persona. The more clicks
+//! there are, the more the heatmap outweighs the defaults.
+
+use crate::models::scheduled_post::{PostTimeSuggestion, SuggestedSlot};
+use crate::services::click_analytics::build_heatmap;
+use crate::services::{audience, niche, post_schedule};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Utc};
+use rusqlite::{params, Connection};
+
+/// Audience time zone used when the caller doesn't pass one
+pub const UTC_OFFSET_SETTING: &str = "audience_utc_offset_minutes";
+
+/// Clicks at which the heatmap gets its full weight
+const FULL_CONFIDENCE_CLICKS: f64 = 50.0;
+/// Most weight the heatmap can carry against the defaults
+const MAX_CLICK_WEIGHT: f64 = 0.7;
+/// Below this many product clicks, workspace-wide clicks are used instead
+const MIN_PRODUCT_CLICKS: i64 = 20;
+const ALTERNATIVE_COUNT: usize = 3;
+
+/// Typical engagement peaks per platform as (good weekdays, peak hours), local time
+fn platform_peaks(platform: &str) -> (&'static [u32], &'static [u32]) {
+    match platform {
+        "tiktok" => (&[2, 4, 5], &[19, 20, 21]),
+        "instagram" => (&[1, 2, 3, 4, 5], &[11, 12, 19]),
+        "pinterest" => (&[5, 6, 0], &[20, 21, 22]),
+        "youtube" => (&[4, 5, 6], &[15, 16, 17]),
+        "facebook" => (&[3, 4], &[9, 13]),
+        "email" | "sms" => (&[2, 4], &[9, 10]),
+        _ => (&[1, 2, 3, 4, 5], &[12, 18]),
+    }
+}
+
+/// When an audience of this age tends to be on their phone
+fn persona_peaks(age_mid: i32) -> &'static [u32] {
+    match age_mid {
+        ..=24 => &[20, 21, 22],
+        25..=44 => &[12, 19, 20],
+        _ => &[8, 9, 10, 19],
+    }
+}
+
+/// 1 at a peak hour, 0.5 next to one, a small base otherwise
+fn peak_curve(hour: u32, peaks: &[u32]) -> f64 {
+    let distance = peaks
+        .iter()
+        .map(|&peak| (hour as i32 - peak as i32).rem_euclid(24).min((peak as i32 - hour as i32).rem_euclid(24)))
+        .min()
+        .unwrap_or(24);
+    match distance {
+        0 => 1.0,
+        1 => 0.5,
+        _ => 0.1,
+    }
+}
+
+/// Scores for each weekday (0 = Sunday) and hour, between 0 and 1
+pub fn score_slots(clicks: &[Vec<i64>], platform: &str, age_mid: i32) -> Vec<Vec<f64>> {
+    let total: i64 = clicks.iter().flatten().sum();
+    let max = clicks.iter().flatten().copied().max().unwrap_or(0).max(1) as f64;
+    let click_weight = MAX_CLICK_WEIGHT * (total as f64 / FULL_CONFIDENCE_CLICKS).min(1.0);
+    let (good_days, platform_hours) = platform_peaks(platform);
+    let persona_hours = persona_peaks(age_mid);
+
+    (0..7u32)
+        .map(|weekday| {
+            let day_factor = if good_days.contains(&weekday) { 1.0 } else { 0.7 };
+            (0..24u32)
+                .map(|hour| {
+                    let observed = clicks
+                        .get(weekday as usize)
+                        .and_then(|hours| hours.get(hour as usize))
+                        .copied()
+                        .unwrap_or(0) as f64
+                        / max;
+                    let prior = day_factor
+                        * (0.7 * peak_curve(hour, platform_hours) + 0.3 * peak_curve(hour, persona_hours));
+                    click_weight * observed + (1.0 - click_weight) * prior
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Next UTC time (at least an hour from `now`) that falls on `weekday`/`hour` in local time
+pub fn next_occurrence(now: NaiveDateTime, weekday: u32, hour: u32, utc_offset_minutes: i32) -> NaiveDateTime {
+    let offset = Duration::minutes(utc_offset_minutes as i64);
+    let earliest = now + offset + Duration::hours(1);
+    let start = earliest.date().and_hms_opt(hour, 0, 0).unwrap_or(earliest);
+    (0..8)
+        .map(|days| start + Duration::days(days))
+        .find(|at| at.weekday().num_days_from_sunday() == weekday && *at >= earliest)
+        .unwrap_or(start)
+        - offset
+}
+
+/// Suggests when to post `product_id` on `platform`. Slots whose next date is
+/// already booked on the platform are passed over when a free one scores close.
+pub fn suggest(
+    conn: &Connection,
+    product_id: i64,
+    platform: &str,
+    utc_offset_minutes: i32,
+) -> rusqlite::Result<PostTimeSuggestion> {
+    let platform = platform.trim().to_lowercase();
+    let mut reasons = Vec::new();
+
+    let mut heatmap = build_heatmap(conn, None, Some(product_id), utc_offset_minutes)?;
+    if heatmap.total_clicks < MIN_PRODUCT_CLICKS {
+        let workspace = build_heatmap(conn, None, None, utc_offset_minutes)?;
+        if workspace.total_clicks > heatmap.total_clicks {
+            reasons.push(format!(
+                "Only {} clicks on this product; using {} workspace clicks",
+                heatmap.total_clicks, workspace.total_clicks
+            ));
+            heatmap = workspace;
+        }
+    }
+    if heatmap.total_clicks > 0 {
+        if let Some(peak) = heatmap.peak_slots.first() {
+            reasons.push(format!("Clicks peak {} at {}:00", weekday_name(peak.weekday), peak.hour));
+        }
+    } else {
+        reasons.push("No clicks yet; using platform and audience defaults".to_string());
+    }
+
+    let product_audience: Option<String> = conn.query_row(
+        "SELECT target_audience FROM products WHERE id = ?1",
+        params![product_id],
+        |row| row.get(0),
+    )?;
+    let audience_text = match product_audience.filter(|a| !a.trim().is_empty()) {
+        Some(audience) => Some(audience),
+        None => niche::load_active_profile(conn)?.and_then(|profile| profile.typical_audience),
+    };
+    let profile = audience::parse(audience_text.as_deref().unwrap_or(""));
+    let (min_age, max_age) = (profile.age_min, profile.age_max);
+    let age_mid = (min_age + max_age) / 2;
+    reasons.push(format!("Audience aged {}-{}; {} defaults", min_age, max_age, platform));
+
+    let scores = score_slots(&heatmap.matrix, &platform, age_mid);
+    let now = Utc::now().naive_utc();
+    let busy = post_schedule::busy_days(conn, &platform)?;
+
+    let mut slots: Vec<SuggestedSlot> = scores
+        .iter()
+        .enumerate()
+        .flat_map(|(weekday, hours)| {
+            hours.iter().enumerate().map(move |(hour, &score)| (weekday as u32, hour as u32, score))
+        })
+        .map(|(weekday, hour, score)| SuggestedSlot {
+            weekday,
+            hour,
+            score: (score * 1000.0).round() / 1000.0,
+            scheduled_at: next_occurrence(now, weekday, hour, utc_offset_minutes)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+        })
+        .collect();
+    slots.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Prefer a free day among slots within 10% of the top score
+    let top_score = slots.first().map(|s| s.score).unwrap_or(0.0);
+    let is_free = |slot: &SuggestedSlot| {
+        NaiveDate::parse_from_str(&slot.scheduled_at[..10], "%Y-%m-%d").map_or(true, |day| !busy.contains(&day))
+    };
+    let best_index = slots
+        .iter()
+        .take_while(|slot| slot.score >= top_score * 0.9)
+        .position(is_free)
+        .unwrap_or(0);
+    if best_index > 0 {
+        reasons.push(format!("Skipped a slot on a day that already has a {} post", platform));
+    }
+    let best = slots.remove(best_index);
+    slots.truncate(ALTERNATIVE_COUNT);
+
+    Ok(PostTimeSuggestion {
+        product_id,
+        platform,
+        utc_offset_minutes,
+        best,
+        alternatives: slots,
+        clicks_considered: heatmap.total_clicks,
+        reasons,
+    })
+}
+
+fn weekday_name(weekday: u32) -> &'static str {
+    ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"][weekday as usize % 7]
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn best(scores: &[Vec<f64>]) -> (usize, usize) {
+        let mut best = (0, 0);
+        for (day, hours) in scores.iter().enumerate() {
+            for (hour, &score) in hours.iter().enumerate() {
+                if score > scores[best.0][best.1] {
+                    best = (day, hour);
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_clicks_outweigh_defaults_once_there_are_enough() {
+        let mut clicks = vec![vec![0i64; 24]; 7];
+        assert_eq!(best(&score_slots(&clicks, "tiktok", 30)), (2, 19));
+
+        clicks[0][7] = 120; // Sunday mornings
+        assert_eq!(best(&score_slots(&clicks, "tiktok", 30)), (0, 7));
+
+        let monday = NaiveDateTime::parse_from_str("2026-03-02 10:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let at = next_occurrence(monday, 0, 7, -300); // Sunday 07:00 in UTC-5
+        assert_eq!(at.format("%Y-%m-%d %H:%M").to_string(), "2026-03-08 12:00");
+    }
+}
@@ -0,0 +1,490 @@
+//! Postback Receiver for Network Conversion Pings
+//!
+//! Some affiliate networks can call a URL on every conversion ("server postback").
+//! This module runs an optional local HTTP listener that accepts those calls,
+//! validates them against the configured signature mode, matches the sub ID back
+//! to an affiliate link, and writes a `conversion_events` row automatically.
+
+use crate::database::get_connection;
+use crate::database::settings::{get_setting, get_setting_or, get_setting_parsed};
+use crate::models::conversion::PostbackConfig;
+use crate::services::{read_only, roles, webhooks};
+use hmac::{Hmac, Mac};
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tauri::AppHandle;
+
+pub const DEFAULT_POSTBACK_PORT: u16 = 8787;
+pub const POSTBACK_PATH: &str = "/postback";
+
+/// Parameters that carry signatures and must never be stored or signed
+const SIGNATURE_PARAMS: [&str; 2] = ["sig", "token"];
+
+// =============================================================================
+// CONFIGURATION
+// =============================================================================
+
+/// Loads the postback configuration from the settings table
+pub fn load_config(conn: &Connection) -> PostbackConfig {
+    PostbackConfig {
+        enabled: get_setting_or(conn, "postback_enabled", "false") == "true",
+        port: get_setting_parsed(conn, "postback_port", DEFAULT_POSTBACK_PORT),
+        // Unsigned postbacks must be opted into; until a secret is set, nothing is accepted
+        signature_mode: get_setting_or(conn, "postback_signature_mode", "token"),
+        secret: get_setting(conn, "postback_secret").ok().flatten(),
+        subid_param: get_setting_or(conn, "postback_subid_param", "subid"),
+        amount_param: get_setting_or(conn, "postback_amount_param", "amount"),
+        commission_param: get_setting_or(conn, "postback_commission_param", "commission"),
+        order_id_param: get_setting_or(conn, "postback_order_id_param", "order_id"),
+    }
+}
+
+// =============================================================================
+// REQUEST PARSING & SIGNATURE VALIDATION
+// =============================================================================
+
+/// Decodes a percent-encoded query component ('+' is treated as a space)
+pub fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex_pair = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex_pair.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(b) => {
+                        out.push(b);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Percent-encodes a query component, leaving only RFC 3986 unreserved characters
+pub fn percent_encode(input: &str) -> String {
+    input
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Splits a raw query string into decoded key/value pairs, preserving order
+pub fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((k, v)) => (percent_decode(k), percent_decode(v)),
+            None => (percent_decode(pair), String::new()),
+        })
+        .collect()
+}
+
+/// Returns the raw query string with signature parameters removed.
+/// This is both the canonical string that networks sign and what gets logged.
+pub fn strip_signature_params(query: &str) -> String {
+    query
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split_once('=').map(|(k, _)| k).unwrap_or(pair);
+            !pair.is_empty() && !SIGNATURE_PARAMS.contains(&key)
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Computes the hex-encoded HMAC-SHA256 of `message` with `secret`
+pub fn hmac_sha256_hex(secret: &str, message: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(message.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Validates a postback query string against the configured signature mode.
+///
+/// * `none` - every request is accepted
+/// * `token` - the `token` parameter must equal the shared secret
+/// * `hmac_sha256` - the `sig` parameter must be the hex HMAC-SHA256 of the
+///   query string (without `sig`/`token`) keyed by the shared secret
+pub fn verify_signature(config: &PostbackConfig, query: &str) -> Result<(), String> {
+    if config.signature_mode == "none" {
+        return Ok(());
+    }
+
+    let secret = config
+        .secret
+        .as_deref()
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| "Postback secret is not configured".to_string())?;
+
+    let params: HashMap<String, String> = parse_query(query).into_iter().collect();
+
+    match config.signature_mode.as_str() {
+        "token" => match params.get("token") {
+            Some(token) if roles::constant_time_eq(token, secret) => Ok(()),
+            Some(_) => Err("Invalid postback token".to_string()),
+            None => Err("Missing postback token".to_string()),
+        },
+        "hmac_sha256" => {
+            let sig = params
+                .get("sig")
+                .ok_or_else(|| "Missing postback signature".to_string())?;
+            let expected = hmac_sha256_hex(secret, &strip_signature_params(query));
+            if roles::constant_time_eq(&sig.to_ascii_lowercase(), &expected) {
+                Ok(())
+            } else {
+                Err("Invalid postback signature".to_string())
+            }
+        }
+        other => Err(format!("Unknown signature mode: {}", other)),
+    }
+}
+
+// =============================================================================
+// CONVERSION RECORDING
+// =============================================================================
+
+/// Resolves a network sub ID to an affiliate link.
+/// Numeric sub IDs are treated as link IDs; anything else must equal the `ref`
+/// tracking ID in the query string of the link's tracking URL.
+pub fn resolve_link_for_subid(conn: &Connection, subid: &str) -> rusqlite::Result<Option<i64>> {
+    if let Ok(link_id) = subid.parse::<i64>() {
+        let found = conn
+            .query_row(
+                "SELECT id FROM affiliate_links WHERE id = ?1",
+                params![link_id],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()?;
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT id, tracking_url FROM affiliate_links WHERE tracking_url LIKE '%ref=%'
+         ORDER BY created_at DESC, id DESC",
+    )?;
+    let links = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
+    for link in links {
+        let (id, tracking_url) = link?;
+        if tracking_ref(&tracking_url).as_deref() == Some(subid) {
+            return Ok(Some(id));
+        }
+    }
+    Ok(None)
+}
+
+/// The decoded `ref` query parameter of a tracking URL
+fn tracking_ref(url: &str) -> Option<String> {
+    let query = url.split_once('?')?.1;
+    let query = query.split_once('#').map_or(query, |(q, _)| q);
+    parse_query(query).into_iter().find(|(k, _)| k == "ref").map(|(_, v)| v)
+}
+
+/// Validates and records a single postback. Every hit is written to
+/// `postback_log`; accepted hits also create a `conversion_events` row.
+pub fn handle_postback(conn: &Connection, config: &PostbackConfig, query: &str) -> Result<i64, String> {
+    let logged_query = strip_signature_params(query);
+    let params: HashMap<String, String> = parse_query(query).into_iter().collect();
+    let subid = params.get(&config.subid_param).cloned();
+
+    let result = record_conversion(conn, config, query, &params, subid.as_deref());
+
+    let (accepted, conversion_id, error) = match &result {
+        Ok(id) => (true, Some(*id), None),
+        Err(e) => (false, None, Some(e.clone())),
+    };
+
+    conn.execute(
+        "INSERT INTO postback_log (query_string, subid, accepted, conversion_id, error)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![logged_query, subid, accepted, conversion_id, error],
+    )
+    .map_err(|e| e.to_string())?;
+
+    result
+}
+
+fn record_conversion(
+    conn: &Connection,
+    config: &PostbackConfig,
+    query: &str,
+    params: &HashMap<String, String>,
+    subid: Option<&str>,
+) -> Result<i64, String> {
+    verify_signature(config, query)?;
+
+    let subid = subid
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| format!("Missing '{}' parameter", config.subid_param))?;
+
+    let link_id = resolve_link_for_subid(conn, subid)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("No affiliate link matches subid '{}'", subid))?;
+
+    let order_value = params
+        .get(&config.amount_param)
+        .and_then(|v| v.parse::<f64>().ok());
+    let commission = params
+        .get(&config.commission_param)
+        .and_then(|v| v.parse::<f64>().ok());
+    let order_id = params.get(&config.order_id_param).filter(|v| !v.is_empty());
+
+    // Networks retry postbacks; the same order must only be counted once
+    if let Some(order_id) = order_id {
+        let duplicate: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM conversion_events WHERE link_id = ?1 AND order_id = ?2",
+                params![link_id, order_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        if duplicate {
+            return Err(format!("Duplicate postback for order '{}'", order_id));
+        }
+    }
+
+    conn.execute(
+        "INSERT INTO conversion_events (link_id, order_value, commission,
+         status, order_id, source, subid)
+         VALUES (?1, ?2, ?3, 'pending', ?4, 'postback', ?5)",
+        params![link_id, order_value, commission, order_id, subid],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+// =============================================================================
+// LOCAL HTTP LISTENER
+// =============================================================================
+
+struct ListenerHandle {
+    port: u16,
+    stop: Arc<AtomicBool>,
+}
+
+fn listener_slot() -> &'static Mutex<Option<ListenerHandle>> {
+    static SLOT: OnceLock<Mutex<Option<ListenerHandle>>> = OnceLock::new();
+    SLOT.get_or_init(|| Mutex::new(None))
+}
+
+/// Returns the port of the running listener, if any
+pub fn running_port() -> Option<u16> {
+    listener_slot().lock().ok()?.as_ref().map(|h| h.port)
+}
+
+/// Starts the postback listener on 127.0.0.1:`port`. Restarts it if already
+/// running on a different port.
+pub fn start_listener(app_handle: AppHandle, port: u16) -> Result<(), String> {
+    let mut slot = listener_slot().lock().map_err(|e| e.to_string())?;
+
+    if let Some(handle) = slot.as_ref() {
+        if handle.port == port {
+            return Ok(());
+        }
+        handle.stop.store(true, Ordering::SeqCst);
+        *slot = None;
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .map_err(|e| format!("Failed to bind postback listener on port {}: {}", port, e))?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = stop.clone();
+
+    std::thread::spawn(move || {
+        while !stop_flag.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    if let Err(e) = serve_connection(&app_handle, stream) {
+                        eprintln!("Postback request failed: {}", e);
+                    }
+                }
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+                Err(e) => eprintln!("Postback listener error: {}", e),
+            }
+        }
+    });
+
+    *slot = Some(ListenerHandle { port, stop });
+    println!("✓ Postback listener started on port {}", port);
+    Ok(())
+}
+
+/// Stops the postback listener if it is running
+pub fn stop_listener() {
+    if let Ok(mut slot) = listener_slot().lock() {
+        if let Some(handle) = slot.take() {
+            handle.stop.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+fn serve_connection(app_handle: &AppHandle, stream: TcpStream) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Request line: "GET /postback?subid=...&amount=... HTTP/1.1"
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let target = parts.next().unwrap_or("");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let (status, body) = if !(method == "GET" || method == "POST") || path != POSTBACK_PATH {
+        ("404 Not Found", "not found".to_string())
+    } else {
+        match get_connection(app_handle) {
+            // Networks retry on 5xx, so conversions arrive once read-only is off
+            Ok(conn) if read_only::is_enabled(&conn) => {
+                ("503 Service Unavailable", "workspace is read-only".to_string())
+            }
+            Ok(conn) => {
+                let config = load_config(&conn);
+                match handle_postback(&conn, &config, query) {
+                    Ok(conversion_id) => {
+                        if let Ok(data) = webhooks::conversion_data(&conn, conversion_id) {
+                            webhooks::emit(app_handle, webhooks::CONVERSION_RECORDED, data);
+                        }
+                        ("200 OK", "ok".to_string())
+                    }
+                    Err(e) => ("400 Bad Request", e),
+                }
+            }
+            Err(e) => ("500 Internal Server Error", e.to_string()),
+        }
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(mode: &str, secret: Option<&str>) -> PostbackConfig {
+        PostbackConfig {
+            enabled: true,
+            port: DEFAULT_POSTBACK_PORT,
+            signature_mode: mode.to_string(),
+            secret: secret.map(String::from),
+            subid_param: "subid".to_string(),
+            amount_param: "amount".to_string(),
+            commission_param: "commission".to_string(),
+            order_id_param: "order_id".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_query_decodes_values() {
+        let params = parse_query("subid=afl_123&note=hello+world%21&empty");
+        assert_eq!(params[0], ("subid".to_string(), "afl_123".to_string()));
+        assert_eq!(params[1], ("note".to_string(), "hello world!".to_string()));
+        assert_eq!(params[2], ("empty".to_string(), String::new()));
+    }
+
+    #[test]
+    fn test_strip_signature_params() {
+        assert_eq!(
+            strip_signature_params("subid=1&sig=abc&amount=10&token=x"),
+            "subid=1&amount=10"
+        );
+    }
+
+    #[test]
+    fn test_token_signature() {
+        let cfg = config("token", Some("s3cret"));
+        assert!(verify_signature(&cfg, "subid=1&token=s3cret").is_ok());
+        assert!(verify_signature(&cfg, "subid=1&token=wrong").is_err());
+        assert!(verify_signature(&cfg, "subid=1").is_err());
+    }
+
+    #[test]
+    fn test_hmac_signature() {
+        let cfg = config("hmac_sha256", Some("s3cret"));
+        let sig = hmac_sha256_hex("s3cret", "subid=1&amount=25.00");
+        let query = format!("subid=1&amount=25.00&sig={}", sig);
+        assert!(verify_signature(&cfg, &query).is_ok());
+        assert!(verify_signature(&cfg, "subid=1&amount=99.00&sig=deadbeef").is_err());
+        let upper = format!("subid=1&amount=25.00&sig={}", sig.to_uppercase());
+        assert!(verify_signature(&cfg, &upper).is_ok());
+    }
+
+    #[test]
+    fn test_signature_requires_secret() {
+        let cfg = config("hmac_sha256", None);
+        assert!(verify_signature(&cfg, "subid=1&sig=abc").is_err());
+        assert!(verify_signature(&config("none", None), "subid=1").is_ok());
+    }
+
+    #[test]
+    fn test_unsigned_postbacks_are_opt_in() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE settings (key TEXT PRIMARY KEY, value TEXT, updated_at DATETIME);")
+            .unwrap();
+        let cfg = load_config(&conn);
+        assert_eq!(cfg.signature_mode, "token");
+        assert!(verify_signature(&cfg, "subid=1&token=").is_err());
+    }
+
+    #[test]
+    fn test_subid_matches_the_whole_ref() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE affiliate_links (id INTEGER PRIMARY KEY, tracking_url TEXT, created_at DATETIME);
+             INSERT INTO affiliate_links VALUES
+                (1, 'https://shop.example.com/p?ref=afl_123&utm_campaign=spring', '2026-01-01'),
+                (2, 'https://shop.example.com/p?utm_source=tiktok&ref=afl_12', '2026-01-02'),
+                (3, 'https://shop.example.com/p?ref=afl%2B7#top', '2026-01-03');",
+        )
+        .unwrap();
+
+        assert_eq!(resolve_link_for_subid(&conn, "afl_123").unwrap(), Some(1));
+        assert_eq!(resolve_link_for_subid(&conn, "afl_12").unwrap(), Some(2));
+        assert_eq!(resolve_link_for_subid(&conn, "afl+7").unwrap(), Some(3));
+        assert_eq!(resolve_link_for_subid(&conn, "3").unwrap(), Some(3));
+        for near_miss in ["afl_1", "afl%", "afl_12_", "afl_1_3"] {
+            assert_eq!(resolve_link_for_subid(&conn, near_miss).unwrap(), None, "{}", near_miss);
+        }
+    }
+}
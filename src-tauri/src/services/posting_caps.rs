@@ -0,0 +1,261 @@
+//! Posting Caps
+//!
+//! Per-platform daily and weekly posting limits, plus double-booking
+//! detection: the same product on the same platform within a configurable
+//! window. By default conflicts are reported as warnings; with enforcement on
+//! the calendar rejects them and recurring expansion skips them.
+
+use crate::database::parse_timestamp;
+use crate::database::settings::{get_setting_or, get_setting_parsed, set_setting};
+use crate::models::scheduled_post::{PlatformPostingCap, PostingRules, ScheduleConflict, ScheduledPost};
+use crate::services::post_schedule::CANCELLED;
+use chrono::{Datelike, Duration, NaiveDateTime};
+use rusqlite::{params, Connection};
+use std::collections::BTreeMap;
+
+pub const DAILY_CAP: &str = "daily_cap";
+pub const WEEKLY_CAP: &str = "weekly_cap";
+pub const DUPLICATE: &str = "duplicate";
+
+const DEFAULT_DUPLICATE_WINDOW_HOURS: i64 = 48;
+
+/// Platforms with caps and their defaults as (platform, daily, weekly); 0 is no cap
+const DEFAULT_CAPS: [(&str, i64, i64); 7] = [
+    ("tiktok", 3, 0),
+    ("instagram", 2, 0),
+    ("youtube", 1, 0),
+    ("pinterest", 10, 0),
+    ("facebook", 2, 0),
+    ("email", 1, 3),
+    ("sms", 1, 2),
+];
+
+fn cap_value(value: i64) -> Option<i64> {
+    (value > 0).then_some(value)
+}
+
+fn daily_key(platform: &str) -> String {
+    format!("posting_cap_daily:{}", platform)
+}
+
+fn weekly_key(platform: &str) -> String {
+    format!("posting_cap_weekly:{}", platform)
+}
+
+pub fn load_rules(conn: &Connection) -> PostingRules {
+    PostingRules {
+        caps: DEFAULT_CAPS
+            .iter()
+            .map(|&(platform, daily, weekly)| PlatformPostingCap {
+                platform: platform.to_string(),
+                daily_cap: cap_value(get_setting_parsed(conn, &daily_key(platform), daily)),
+                weekly_cap: cap_value(get_setting_parsed(conn, &weekly_key(platform), weekly)),
+            })
+            .collect(),
+        duplicate_window_hours: get_setting_parsed(
+            conn,
+            "posting_duplicate_window_hours",
+            DEFAULT_DUPLICATE_WINDOW_HOURS,
+        )
+        .max(0),
+        enforce: get_setting_or(conn, "posting_caps_enforce", "false") == "true",
+    }
+}
+
+pub fn save_rules(conn: &Connection, rules: &PostingRules) -> rusqlite::Result<()> {
+    for cap in &rules.caps {
+        let platform = cap.platform.trim().to_lowercase();
+        let value = |cap: Option<i64>| cap.unwrap_or(0).max(0).to_string();
+        set_setting(conn, &daily_key(&platform), &value(cap.daily_cap))?;
+        set_setting(conn, &weekly_key(&platform), &value(cap.weekly_cap))?;
+    }
+    set_setting(conn, "posting_duplicate_window_hours", &rules.duplicate_window_hours.max(0).to_string())?;
+    set_setting(conn, "posting_caps_enforce", &rules.enforce.to_string())
+}
+
+fn cap_for<'a>(rules: &'a PostingRules, platform: &str) -> Option<&'a PlatformPostingCap> {
+    rules.caps.iter().find(|cap| cap.platform.eq_ignore_ascii_case(platform))
+}
+
+/// A post already on the calendar, as far as the checks are concerned
+#[derive(Debug, Clone, Copy)]
+pub struct BookedPost {
+    pub id: i64,
+    pub product_id: i64,
+    pub at: NaiveDateTime,
+}
+
+/// Conflicts a new post of `product_id` at `at` would have with `booked`,
+/// the other live posts on the same platform
+pub fn detect(
+    rules: &PostingRules,
+    platform: &str,
+    product_id: i64,
+    at: NaiveDateTime,
+    booked: &[BookedPost],
+) -> Vec<ScheduleConflict> {
+    let mut conflicts = Vec::new();
+    let conflict = |kind: &str, message: String, posts: Vec<&BookedPost>| ScheduleConflict {
+        kind: kind.to_string(),
+        platform: platform.to_string(),
+        message,
+        post_ids: posts.iter().map(|p| p.id).collect(),
+    };
+
+    if let Some(cap) = cap_for(rules, platform) {
+        if let Some(daily) = cap.daily_cap {
+            let same_day: Vec<&BookedPost> = booked.iter().filter(|p| p.at.date() == at.date()).collect();
+            if same_day.len() as i64 >= daily {
+                let message = format!(
+                    "{} already has {} post(s) on {} (cap {} a day)",
+                    platform,
+                    same_day.len(),
+                    at.date(),
+                    daily
+                );
+                conflicts.push(conflict(DAILY_CAP, message, same_day));
+            }
+        }
+        if let Some(weekly) = cap.weekly_cap {
+            let week = at.iso_week();
+            let same_week: Vec<&BookedPost> = booked.iter().filter(|p| p.at.iso_week() == week).collect();
+            if same_week.len() as i64 >= weekly {
+                let message = format!(
+                    "{} already has {} post(s) in the week of {} (cap {} a week)",
+                    platform,
+                    same_week.len(),
+                    at.date() - Duration::days(at.weekday().num_days_from_monday() as i64),
+                    weekly
+                );
+                conflicts.push(conflict(WEEKLY_CAP, message, same_week));
+            }
+        }
+    }
+
+    if rules.duplicate_window_hours > 0 {
+        let window = Duration::hours(rules.duplicate_window_hours);
+        let nearby: Vec<&BookedPost> = booked
+            .iter()
+            .filter(|p| p.product_id == product_id && (p.at - at).abs() < window)
+            .collect();
+        if !nearby.is_empty() {
+            let message = format!(
+                "This product is already scheduled on {} within {} hours",
+                platform, rules.duplicate_window_hours
+            );
+            conflicts.push(conflict(DUPLICATE, message, nearby));
+        }
+    }
+    conflicts
+}
+
+/// Live posts on `platform` within a week either side of `at`, except `exclude_id`
+fn booked_near(
+    conn: &Connection,
+    platform: &str,
+    at: NaiveDateTime,
+    exclude_id: Option<i64>,
+) -> rusqlite::Result<Vec<BookedPost>> {
+    let format = |t: NaiveDateTime| t.format("%Y-%m-%d %H:%M:%S").to_string();
+    let mut stmt = conn.prepare(
+        "SELECT id, product_id, scheduled_at FROM scheduled_posts
+         WHERE platform = ?1 COLLATE NOCASE AND status != ?2 AND scheduled_at BETWEEN ?3 AND ?4
+           AND (?5 IS NULL OR id != ?5)",
+    )?;
+    let rows = stmt
+        .query_map(
+            params![
+                platform,
+                CANCELLED,
+                format(at - Duration::days(8)),
+                format(at + Duration::days(8)),
+                exclude_id
+            ],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, String>(2)?)),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(id, product_id, at)| Some(BookedPost { id, product_id, at: parse_timestamp(&at)? }))
+        .collect())
+}
+
+/// Conflicts for placing `product_id` on `platform` at `at` (a stored timestamp)
+pub fn check(
+    conn: &Connection,
+    rules: &PostingRules,
+    product_id: i64,
+    platform: &str,
+    at: &str,
+    exclude_id: Option<i64>,
+) -> rusqlite::Result<Vec<ScheduleConflict>> {
+    let Some(at) = parse_timestamp(at) else {
+        return Ok(Vec::new());
+    };
+    let platform = platform.trim().to_lowercase();
+    let booked = booked_near(conn, &platform, at, exclude_id)?;
+    Ok(detect(rules, &platform, product_id, at, &booked))
+}
+
+/// Every conflict among the live posts in `posts` (one calendar range)
+pub fn calendar_conflicts(rules: &PostingRules, posts: &[ScheduledPost]) -> Vec<ScheduleConflict> {
+    let mut by_platform: BTreeMap<String, Vec<BookedPost>> = BTreeMap::new();
+    for post in posts.iter().filter(|p| p.status != CANCELLED) {
+        if let Some(at) = parse_timestamp(&post.scheduled_at) {
+            by_platform
+                .entry(post.platform.to_lowercase())
+                .or_default()
+                .push(BookedPost { id: post.id, product_id: post.product_id, at });
+        }
+    }
+
+    let mut conflicts: Vec<ScheduleConflict> = Vec::new();
+    for (platform, booked) in &by_platform {
+        // Each post is checked against those booked before it, so a cap
+        // reports the post that went over it
+        let mut sorted = booked.clone();
+        sorted.sort_by_key(|p| (p.at, p.id));
+        for (i, post) in sorted.iter().enumerate() {
+            for mut conflict in detect(rules, platform, post.product_id, post.at, &sorted[..i]) {
+                conflict.post_ids.push(post.id);
+                conflicts.push(conflict);
+            }
+        }
+    }
+    conflicts
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_caps_and_double_booking() {
+        let rules = PostingRules {
+            caps: vec![PlatformPostingCap {
+                platform: "tiktok".to_string(),
+                daily_cap: Some(2),
+                weekly_cap: Some(3),
+            }],
+            duplicate_window_hours: 48,
+            enforce: false,
+        };
+        let at = |s: &str| parse_timestamp(s).unwrap();
+        let booked = [
+            BookedPost { id: 1, product_id: 10, at: at("2026-03-02 09:00:00") }, // Monday
+            BookedPost { id: 2, product_id: 11, at: at("2026-03-04 19:00:00") },
+            BookedPost { id: 3, product_id: 12, at: at("2026-03-04 21:00:00") },
+        ];
+
+        let kinds = |conflicts: Vec<ScheduleConflict>| conflicts.into_iter().map(|c| c.kind).collect::<Vec<_>>();
+        let busy_day = detect(&rules, "tiktok", 13, at("2026-03-04 12:00:00"), &booked);
+        assert_eq!(kinds(busy_day), [DAILY_CAP, WEEKLY_CAP]);
+        assert_eq!(kinds(detect(&rules, "tiktok", 10, at("2026-03-09 08:00:00"), &booked)), Vec::<String>::new());
+        let duplicate = detect(&rules, "tiktok", 10, at("2026-03-01 10:00:00"), &booked);
+        assert_eq!((duplicate[0].kind.as_str(), duplicate[0].post_ids.as_slice()), (DUPLICATE, &[1][..]));
+    }
+}
@@ -0,0 +1,206 @@
+//! Pretty Link Export
+//!
+//! Lets bloggers mirror their affiliate links as `/go/<product>` paths on
+//! their own site. Active links get stable, readable slugs and are written as
+//! a server redirects file (Apache `.htaccess`, or `_redirects` for Netlify
+//! and Cloudflare Pages) or as an import CSV for the Pretty Links and
+//! ThirstyAffiliates WordPress plugins.
+
+use crate::models::pretty_link::PrettyLink;
+use crate::services::ad_kit::slug;
+use rusqlite::{params_from_iter, Connection};
+use std::collections::{HashMap, HashSet};
+
+pub const DEFAULT_PREFIX: &str = "go";
+
+/// Output layouts supported by `render`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrettyLinksFormat {
+    Htaccess,
+    Redirects,
+    PrettyLinks,
+    ThirstyAffiliates,
+}
+
+impl PrettyLinksFormat {
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "htaccess" | "apache" => Some(PrettyLinksFormat::Htaccess),
+            "redirects" | "netlify" | "cloudflare" => Some(PrettyLinksFormat::Redirects),
+            "pretty_links" | "prettylinks" => Some(PrettyLinksFormat::PrettyLinks),
+            "thirsty_affiliates" | "thirstyaffiliates" => Some(PrettyLinksFormat::ThirstyAffiliates),
+            _ => None,
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            PrettyLinksFormat::Htaccess => ".htaccess",
+            PrettyLinksFormat::Redirects => "_redirects",
+            PrettyLinksFormat::PrettyLinks => "pretty-links-import.csv",
+            PrettyLinksFormat::ThirstyAffiliates => "thirstyaffiliates-import.csv",
+        }
+    }
+}
+
+/// A link row before slugs are assigned
+struct LinkRow {
+    link_id: i64,
+    product_id: i64,
+    product_name: String,
+    category: String,
+    platform: String,
+    tracking_url: String,
+}
+
+/// Active links for `product_ids` (or all products), with unique slugs under
+/// `/<prefix>/`. A product with several links gets the platform in each slug.
+pub fn load_links(
+    conn: &Connection,
+    product_ids: Option<&[i64]>,
+    prefix: &str,
+) -> rusqlite::Result<Vec<(PrettyLink, String)>> {
+    let filter = match product_ids {
+        Some(ids) => format!("AND l.product_id IN ({})", vec!["?"; ids.len().max(1)].join(", ")),
+        None => String::new(),
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT l.id, l.product_id, COALESCE(p.name, l.product_name), COALESCE(p.category, ''),
+         COALESCE(l.platform, l.program_name), l.tracking_url
+         FROM affiliate_links l LEFT JOIN products p ON p.id = l.product_id
+         WHERE l.status = 'active' {}
+         ORDER BY COALESCE(p.name, l.product_name) COLLATE NOCASE, l.id",
+        filter
+    ))?;
+    let rows = stmt
+        .query_map(params_from_iter(product_ids.unwrap_or(&[]).iter()), |row| {
+            Ok(LinkRow {
+                link_id: row.get(0)?,
+                product_id: row.get(1)?,
+                product_name: row.get(2)?,
+                category: row.get(3)?,
+                platform: row.get(4)?,
+                tracking_url: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(assign_slugs(rows, prefix))
+}
+
+/// Gives every link a unique slug; returns each link with its product category
+fn assign_slugs(rows: Vec<LinkRow>, prefix: &str) -> Vec<(PrettyLink, String)> {
+    let mut links_per_product: HashMap<i64, usize> = HashMap::new();
+    for row in &rows {
+        *links_per_product.entry(row.product_id).or_default() += 1;
+    }
+
+    let prefix = prefix.trim_matches('/');
+    let mut taken = HashSet::new();
+    rows.into_iter()
+        .map(|row| {
+            let base = if links_per_product[&row.product_id] > 1 {
+                slug(&format!("{} {}", row.product_name, row.platform))
+            } else {
+                slug(&row.product_name)
+            };
+            let mut candidate = base.clone();
+            let mut n = 2;
+            while !taken.insert(candidate.clone()) {
+                candidate = format!("{}-{}", base, n);
+                n += 1;
+            }
+            let path = if prefix.is_empty() { format!("/{}", candidate) } else { format!("/{}/{}", prefix, candidate) };
+            let link = PrettyLink {
+                link_id: row.link_id,
+                product_id: row.product_id,
+                name: row.product_name,
+                slug: candidate,
+                path,
+                tracking_url: row.tracking_url,
+            };
+            (link, row.category)
+        })
+        .collect()
+}
+
+/// Renders the links in `format`
+pub fn render(format: PrettyLinksFormat, links: &[(PrettyLink, String)]) -> Result<String, String> {
+    match format {
+        PrettyLinksFormat::Htaccess => {
+            let mut out = String::from("# Affiliate redirects exported from AffilAI\n");
+            for (link, _) in links {
+                out.push_str(&format!("Redirect 302 {} {}\n", link.path, link.tracking_url));
+            }
+            Ok(out)
+        }
+        PrettyLinksFormat::Redirects => {
+            let mut out = String::from("# Affiliate redirects exported from AffilAI\n");
+            for (link, _) in links {
+                out.push_str(&format!("{}  {}  302\n", link.path, link.tracking_url));
+            }
+            Ok(out)
+        }
+        PrettyLinksFormat::PrettyLinks | PrettyLinksFormat::ThirstyAffiliates => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            let headers: &[&str] = if format == PrettyLinksFormat::PrettyLinks {
+                &["url", "slug", "name", "redirect_type", "nofollow", "sponsored", "track_me"]
+            } else {
+                &["Link Name", "Destination URL", "Link Slug", "Link Categories", "Redirect Type", "No Follow"]
+            };
+            writer.write_record(headers).map_err(|e| e.to_string())?;
+
+            for (link, category) in links {
+                let record: Vec<&str> = if format == PrettyLinksFormat::PrettyLinks {
+                    // The plugin adds its own base path, so the slug keeps the prefix
+                    vec![&link.tracking_url, link.path.trim_start_matches('/'), &link.name, "302", "1", "1", "1"]
+                } else {
+                    vec![&link.name, &link.tracking_url, &link.slug, category, "302", "yes"]
+                };
+                writer.write_record(record).map_err(|e| e.to_string())?;
+            }
+            let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+            String::from_utf8(bytes).map_err(|e| e.to_string())
+        }
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(link_id: i64, product_id: i64, name: &str, platform: &str) -> LinkRow {
+        LinkRow {
+            link_id,
+            product_id,
+            product_name: name.to_string(),
+            category: "Kitchen".to_string(),
+            platform: platform.to_string(),
+            tracking_url: format!("https://aff.example/{}", link_id),
+        }
+    }
+
+    #[test]
+    fn test_slugs_are_unique_and_readable() {
+        let links = assign_slugs(
+            vec![
+                row(1, 1, "Bamboo Cutting Board", "amazon"),
+                row(2, 1, "Bamboo Cutting Board", "tiktok"),
+                row(3, 2, "Air Fryer!", "amazon"),
+                row(4, 3, "Air Fryer", "amazon"),
+            ],
+            "/go/",
+        );
+        let paths: Vec<&str> = links.iter().map(|(l, _)| l.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["/go/bamboo-cutting-board-amazon", "/go/bamboo-cutting-board-tiktok", "/go/air-fryer", "/go/air-fryer-2"]
+        );
+
+        let htaccess = render(PrettyLinksFormat::Htaccess, &links[2..3]).unwrap();
+        assert!(htaccess.ends_with("Redirect 302 /go/air-fryer https://aff.example/3\n"));
+    }
+}
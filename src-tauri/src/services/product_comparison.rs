@@ -0,0 +1,346 @@
+//! Product Comparison
+//!
+//! Lines up a handful of products side by side: price, the programs each can
+//! be promoted through, how clicks have trended over recent weeks, and how
+//! they performed over the last month. The same data drives the comparison
+//! view (one row per attribute, with the leader marked) and is rendered as a
+//! Markdown table to ground comparison-article ad copy.
+
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::models::product::Product;
+use crate::models::product_comparison::{CommissionOption, ComparedProduct, ComparisonRow, ProductComparison};
+use crate::services::daily_metrics;
+use crate::services::scraper::parse_price;
+use chrono::{Duration, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub const MAX_PRODUCTS: usize = 6;
+pub const PERIOD_DAYS: i64 = 30;
+const TREND_WEEKS: usize = 8;
+/// Change between the two halves of the trend window that counts as a trend
+const TREND_THRESHOLD: f64 = 0.1;
+
+/// Low and high end of a price range such as "$30-$40" or "$25"
+pub fn parse_price_range(range: &str) -> (Option<f64>, Option<f64>) {
+    let prices: Vec<f64> = range.split(['-', '–']).filter_map(parse_price).collect();
+    (prices.first().copied(), prices.last().copied())
+}
+
+/// Compares the second half of `weekly` with the first
+pub fn trend(weekly: &[i64]) -> &'static str {
+    let (earlier, recent) = weekly.split_at(weekly.len() / 2);
+    let (earlier, recent) = (earlier.iter().sum::<i64>() as f64, recent.iter().sum::<i64>() as f64);
+    if recent > earlier * (1.0 + TREND_THRESHOLD) && recent > 0.0 {
+        "rising"
+    } else if recent < earlier * (1.0 - TREND_THRESHOLD) {
+        "falling"
+    } else {
+        "flat"
+    }
+}
+
+/// Active links first, then programs the product is associated with but has
+/// no link for yet
+pub(crate) fn commission_options(conn: &Connection, product_id: i64) -> rusqlite::Result<Vec<CommissionOption>> {
+    let mut stmt = conn.prepare(
+        "SELECT program_name, platform, MAX(commission_rate), MAX(cookie_duration)
+         FROM affiliate_links WHERE product_id = ?1 AND status = 'active'
+         GROUP BY program_name, platform
+         ORDER BY MAX(commission_rate) DESC",
+    )?;
+    let mut options = stmt
+        .query_map(params![product_id], |row| {
+            Ok(CommissionOption {
+                program_name: row.get(0)?,
+                platform: row.get(1)?,
+                commission_rate: row.get(2)?,
+                cookie_days: row.get(3)?,
+                has_link: true,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT ap.name, COALESCE(pp.commission_rate, ap.default_commission_rate)
+         FROM product_programs pp JOIN affiliate_programs ap ON ap.id = pp.program_id
+         WHERE pp.product_id = ?1
+         ORDER BY 2 DESC",
+    )?;
+    let programs = stmt
+        .query_map(params![product_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<f64>>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (program_name, commission_rate) in programs {
+        if !options.iter().any(|o| o.program_name.eq_ignore_ascii_case(&program_name)) {
+            options.push(CommissionOption {
+                program_name,
+                platform: None,
+                commission_rate,
+                cookie_days: None,
+                has_link: false,
+            });
+        }
+    }
+    Ok(options)
+}
+
+fn compare_one(conn: &Connection, product: Product, today: NaiveDate) -> rusqlite::Result<ComparedProduct> {
+    let product_id = product.id.unwrap_or_default();
+
+    let trend_start = today - Duration::days(TREND_WEEKS as i64 * 7 - 1);
+    let mut weekly_clicks = vec![0; TREND_WEEKS];
+    for day in daily_metrics::series(conn, trend_start, today, Some(product_id), None)? {
+        if let Ok(date) = NaiveDate::parse_from_str(&day.day, "%Y-%m-%d") {
+            let weeks_ago = ((today - date).num_days() / 7) as usize;
+            if weeks_ago < TREND_WEEKS {
+                weekly_clicks[TREND_WEEKS - 1 - weeks_ago] += day.clicks;
+            }
+        }
+    }
+
+    let period_start = today - Duration::days(PERIOD_DAYS - 1);
+    let (mut clicks, mut conversions, mut revenue, mut commission) = (0, 0, 0.0, 0.0);
+    for day in daily_metrics::series(conn, period_start, today, Some(product_id), None)? {
+        clicks += day.clicks;
+        conversions += day.conversions;
+        revenue += day.revenue;
+        commission += day.commission;
+    }
+
+    let commission_options = commission_options(conn, product_id)?;
+    let best_commission_rate = commission_options.iter().filter_map(|o| o.commission_rate).reduce(f64::max);
+    let (price_low, price_high) = product.price_range.as_deref().map(parse_price_range).unwrap_or_default();
+    let per_click = |value: f64| (clicks > 0).then(|| value / clicks as f64);
+
+    Ok(ComparedProduct {
+        product_id,
+        trend: trend(&weekly_clicks).to_string(),
+        name: product.name,
+        category: product.category,
+        stage: product.stage,
+        availability: product.availability,
+        price_range: product.price_range,
+        price_low,
+        price_high,
+        trending_score: product.trending_score.unwrap_or(0),
+        weekly_clicks,
+        commission_options,
+        best_commission_rate,
+        clicks,
+        conversions,
+        revenue,
+        commission,
+        conversion_rate: per_click(conversions as f64),
+        earnings_per_click: per_click(commission),
+    })
+}
+
+/// Index of the product with the highest (or, with `lowest`, the lowest)
+/// value; `None` when fewer than two products have one or they tie
+fn leader(values: &[Option<f64>], lowest: bool) -> Option<usize> {
+    let present: Vec<(usize, f64)> = values.iter().enumerate().filter_map(|(i, v)| v.map(|v| (i, v))).collect();
+    if present.len() < 2 {
+        return None;
+    }
+    let pick = |a: &(usize, f64), b: &(usize, f64)| if lowest { b.1.total_cmp(&a.1) } else { a.1.total_cmp(&b.1) };
+    let best = present.iter().max_by(|a, b| pick(a, b))?;
+    let ties = present.iter().filter(|(_, v)| (v - best.1).abs() < 1e-9).count();
+    (ties == 1).then_some(best.0)
+}
+
+fn percent(value: f64) -> String {
+    format!("{:.1}%", value * 100.0)
+}
+
+fn money(value: f64) -> String {
+    format!("${:.2}", value)
+}
+
+fn rows(products: &[ComparedProduct]) -> Vec<ComparisonRow> {
+    let row = |key: &str, label: &str, values: Vec<Option<String>>, best: Option<usize>| ComparisonRow {
+        key: key.to_string(),
+        label: label.to_string(),
+        values,
+        best,
+    };
+    let column = |f: &dyn Fn(&ComparedProduct) -> Option<String>| products.iter().map(f).collect::<Vec<_>>();
+    let numbers = |f: &dyn Fn(&ComparedProduct) -> Option<f64>| products.iter().map(f).collect::<Vec<_>>();
+
+    vec![
+        row(
+            "price",
+            "Price",
+            column(&|p| p.price_range.clone()),
+            leader(&numbers(&|p| p.price_low), true),
+        ),
+        row("availability", "Availability", column(&|p| p.availability.clone()), None),
+        row(
+            "trending_score",
+            "Trending score",
+            column(&|p| Some(p.trending_score.to_string())),
+            leader(&numbers(&|p| Some(p.trending_score as f64)), false),
+        ),
+        row("trend", "Click trend (8 weeks)", column(&|p| Some(p.trend.clone())), None),
+        row(
+            "best_commission",
+            "Best commission",
+            column(&|p| p.best_commission_rate.map(percent)),
+            leader(&numbers(&|p| p.best_commission_rate), false),
+        ),
+        row(
+            "programs",
+            "Programs",
+            column(&|p| {
+                let names: Vec<&str> = p.commission_options.iter().map(|o| o.program_name.as_str()).collect();
+                (!names.is_empty()).then(|| names.join(", "))
+            }),
+            None,
+        ),
+        row(
+            "clicks",
+            "Clicks (30 days)",
+            column(&|p| Some(p.clicks.to_string())),
+            leader(&numbers(&|p| Some(p.clicks as f64)), false),
+        ),
+        row(
+            "conversion_rate",
+            "Conversion rate",
+            column(&|p| p.conversion_rate.map(percent)),
+            leader(&numbers(&|p| p.conversion_rate), false),
+        ),
+        row(
+            "earnings_per_click",
+            "Earnings per click",
+            column(&|p| p.earnings_per_click.map(money)),
+            leader(&numbers(&|p| p.earnings_per_click), false),
+        ),
+        row(
+            "commission",
+            "Commission (30 days)",
+            column(&|p| Some(money(p.commission))),
+            leader(&numbers(&|p| Some(p.commission)), false),
+        ),
+    ]
+}
+
+/// Markdown table of the comparison; cells are stripped of pipes and newlines
+pub fn markdown_context(products: &[ComparedProduct], rows: &[ComparisonRow]) -> String {
+    let cell = |value: &str| value.replace('|', "/").replace('\n', " ");
+    let names: Vec<String> = products.iter().map(|p| cell(&p.name)).collect();
+    let mut lines = vec![
+        format!("Comparison of {}:", names.join(" vs ")),
+        String::new(),
+        format!("| | {} |", names.join(" | ")),
+        format!("|---|{}", "---|".repeat(names.len())),
+    ];
+    for row in rows {
+        let values: Vec<String> = row
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let value = cell(v.as_deref().unwrap_or("-"));
+                if row.best == Some(i) {
+                    format!("**{}**", value)
+                } else {
+                    value
+                }
+            })
+            .collect();
+        lines.push(format!("| {} | {} |", row.label, values.join(" | ")));
+    }
+    lines.join("\n")
+}
+
+pub fn compare(conn: &Connection, product_ids: &[i64], today: NaiveDate) -> Result<ProductComparison, String> {
+    let mut ids: Vec<i64> = Vec::new();
+    for id in product_ids {
+        if !ids.contains(id) {
+            ids.push(*id);
+        }
+    }
+    if ids.len() < 2 {
+        return Err("Pick at least two products to compare".to_string());
+    }
+    if ids.len() > MAX_PRODUCTS {
+        return Err(format!("At most {} products can be compared at once", MAX_PRODUCTS));
+    }
+
+    let mut products = Vec::new();
+    for id in ids {
+        let product = conn
+            .query_row(
+                &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
+                params![id],
+                product_from_row,
+            )
+            .optional()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Product {} not found", id))?;
+        products.push(compare_one(conn, product, today).map_err(|e| e.to_string())?);
+    }
+
+    let rows = rows(&products);
+    let context = markdown_context(&products, &rows);
+    Ok(ProductComparison { products, rows, period_days: PERIOD_DAYS, context })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helpers_pick_leaders_and_trends() {
+        assert_eq!(parse_price_range("$30-$40"), (Some(30.0), Some(40.0)));
+        assert_eq!(parse_price_range("$25"), (Some(25.0), Some(25.0)));
+        assert_eq!(parse_price_range("ask"), (None, None));
+
+        assert_eq!(trend(&[1, 1, 1, 1, 3, 3, 3, 3]), "rising");
+        assert_eq!(trend(&[5, 5, 5, 5, 1, 0, 0, 0]), "falling");
+        assert_eq!(trend(&[0; 8]), "flat");
+
+        assert_eq!(leader(&[Some(30.0), Some(20.0), None], true), Some(1));
+        assert_eq!(leader(&[Some(0.08), Some(0.12)], false), Some(1));
+        assert_eq!(leader(&[Some(5.0), Some(5.0)], false), None);
+        assert_eq!(leader(&[Some(5.0), None], false), None);
+
+        let row = ComparisonRow {
+            key: "price".to_string(),
+            label: "Price".to_string(),
+            values: vec![Some("$30-$40".to_string()), None],
+            best: Some(0),
+        };
+        let products: Vec<ComparedProduct> = ["Lamp | XL", "Mug"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| ComparedProduct {
+                product_id: i as i64,
+                name: name.to_string(),
+                category: String::new(),
+                stage: None,
+                availability: None,
+                price_range: None,
+                price_low: None,
+                price_high: None,
+                trending_score: 0,
+                weekly_clicks: Vec::new(),
+                trend: "flat".to_string(),
+                commission_options: Vec::new(),
+                best_commission_rate: None,
+                clicks: 0,
+                conversions: 0,
+                revenue: 0.0,
+                commission: 0.0,
+                conversion_rate: None,
+                earnings_per_click: None,
+            })
+            .collect();
+        assert_eq!(
+            markdown_context(&products, &[row]),
+            "Comparison of Lamp / XL vs Mug:\n\n| | Lamp / XL | Mug |\n|---|---|---|\n| Price | **$30-$40** | - |"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Product CSV Export
+//!
+//! Writes the catalog (or a filtered part of it) as a spreadsheet for review
+//! or sharing. Any product column can be chosen, plus computed columns: the
+//! active link URL for a platform (`link_url:<platform>`), the latest ad
+//! headline, and all-time human clicks. Without a column list the export has
+//! the main product fields, one link column per platform in use, and the
+//! computed columns.
+
+use crate::commands::products::PRODUCT_COLUMNS;
+use crate::models::list_page::ProductListFilter;
+use crate::services::list_pages::product_filter_sql;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params_from_iter, Connection};
+
+const LINK_URL_PREFIX: &str = "link_url:";
+
+const DEFAULT_FIELDS: [&str; 8] =
+    ["id", "name", "category", "stage", "price_range", "trending_score", "product_url", "tags"];
+const COMPUTED_COLUMNS: [&str; 3] = ["latest_ad_headline", "total_clicks", "active_links"];
+
+/// The SQL expression for an export column, evaluated per `products` row
+fn column_sql(column: &str) -> Result<String, String> {
+    if let Some(platform) = column.strip_prefix(LINK_URL_PREFIX) {
+        let platform = platform.trim().to_lowercase();
+        if platform.is_empty() || !platform.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(format!("Invalid platform in column '{}'", column));
+        }
+        return Ok(format!(
+            "(SELECT tracking_url FROM affiliate_links l
+              WHERE l.product_id = products.id AND l.status = 'active' AND lower(l.platform) = '{}'
+              ORDER BY l.created_at DESC, l.id DESC LIMIT 1)",
+            platform
+        ));
+    }
+    match column {
+        "latest_ad_headline" => Ok("(SELECT headline FROM ad_copies a WHERE a.product_id = products.id
+              ORDER BY a.created_at DESC, a.id DESC LIMIT 1)"
+            .to_string()),
+        // Clicks that retention folded into daily rollups still count
+        "total_clicks" => Ok("((SELECT COUNT(*) FROM click_events c JOIN affiliate_links l ON l.id = c.link_id
+               WHERE l.product_id = products.id AND c.bot_reason IS NULL)
+             + (SELECT COALESCE(SUM(r.clicks), 0) FROM click_rollups r JOIN affiliate_links l ON l.id = r.link_id
+               WHERE l.product_id = products.id))"
+            .to_string()),
+        "active_links" => Ok("(SELECT COUNT(*) FROM affiliate_links l
+              WHERE l.product_id = products.id AND l.status = 'active')"
+            .to_string()),
+        _ if PRODUCT_COLUMNS.split(',').map(str::trim).any(|c| c == column) => Ok(column.to_string()),
+        _ => Err(format!("Unknown export column '{}'", column)),
+    }
+}
+
+/// Default columns: main fields, a link column per platform with an active
+/// link, then the computed columns
+pub fn default_columns(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT lower(platform) FROM affiliate_links
+         WHERE status = 'active' AND platform IS NOT NULL AND platform != '' ORDER BY 1",
+    )?;
+    let platforms = stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<Result<Vec<_>, _>>()?;
+
+    let mut columns: Vec<String> = DEFAULT_FIELDS.iter().map(|c| c.to_string()).collect();
+    columns.extend(platforms.into_iter().map(|p| format!("{}{}", LINK_URL_PREFIX, p)));
+    columns.extend(COMPUTED_COLUMNS.iter().map(|c| c.to_string()));
+    Ok(columns)
+}
+
+fn cell(column: &str, value: SqlValue) -> String {
+    match value {
+        SqlValue::Null => String::new(),
+        SqlValue::Integer(i) => i.to_string(),
+        SqlValue::Real(f) => f.to_string(),
+        // Tags are stored as a JSON array; a spreadsheet wants "a, b"
+        SqlValue::Text(text) if column == "tags" => serde_json::from_str::<Vec<String>>(&text)
+            .map(|tags| tags.join(", "))
+            .unwrap_or(text),
+        SqlValue::Text(text) => text,
+        SqlValue::Blob(_) => String::new(),
+    }
+}
+
+/// Renders the CSV; returns it with the number of product rows
+pub fn to_csv(
+    conn: &Connection,
+    filter: &ProductListFilter,
+    columns: &[String],
+) -> Result<(String, usize), String> {
+    if columns.is_empty() {
+        return Err("Choose at least one column to export".to_string());
+    }
+    let expressions = columns.iter().map(|c| column_sql(c.trim())).collect::<Result<Vec<_>, _>>()?;
+    let (where_sql, values) = product_filter_sql(filter);
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM products{} ORDER BY trending_score DESC, name ASC, id ASC",
+            expressions.join(", "),
+            where_sql
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params_from_iter(values), |row| {
+            (0..columns.len())
+                .map(|i| row.get::<_, SqlValue>(i).map(|v| cell(columns[i].trim(), v)))
+                .collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(columns.iter().map(|c| c.trim())).map_err(|e| e.to_string())?;
+    for row in &rows {
+        writer.write_record(row).map_err(|e| e.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    let csv = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+    Ok((csv, rows.len()))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_includes_computed_columns() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT, category TEXT, description TEXT,
+                trending_score INTEGER, stage TEXT, tags TEXT, created_at TEXT);
+             CREATE TABLE affiliate_links (id INTEGER PRIMARY KEY, product_id INTEGER, platform TEXT,
+                tracking_url TEXT, status TEXT, created_at TEXT);
+             CREATE TABLE ad_copies (id INTEGER PRIMARY KEY, product_id INTEGER, headline TEXT, created_at TEXT);
+             CREATE TABLE click_events (id INTEGER PRIMARY KEY, link_id INTEGER, bot_reason TEXT);
+             {}
+             INSERT INTO products VALUES (1, 'Lamp', 'Home', '', 90, 'promoting', '[\"cozy\",\"gift\"]', '');
+             INSERT INTO products VALUES (2, 'Mug', 'Kitchen', '', 50, 'researching', NULL, '');
+             INSERT INTO affiliate_links VALUES (10, 1, 'Amazon', 'https://amzn.to/x', 'active', '2026-01-01'),
+                (11, 1, 'tiktok', 'https://old', 'expired', '2026-01-02');
+             INSERT INTO ad_copies VALUES (1, 1, 'Old headline', '2026-01-01'), (2, 1, 'Glow up', '2026-02-01');
+             INSERT INTO click_events VALUES (1, 10, NULL), (2, 10, 'crawler');
+             INSERT INTO click_rollups VALUES ('2025-01-01', 10, 4, 0, 3);",
+            include_str!("../../../migrations/041_data_retention.sql")
+        ))
+        .unwrap();
+
+        let columns = default_columns(&conn).unwrap();
+        assert!(columns.contains(&"link_url:amazon".to_string()));
+        assert!(!columns.contains(&"link_url:tiktok".to_string()));
+
+        let filter = ProductListFilter { category: Some("Home".to_string()), ..Default::default() };
+        let wanted: Vec<String> =
+            ["name", "tags", "link_url:amazon", "latest_ad_headline", "total_clicks"].map(String::from).to_vec();
+        let (csv, rows) = to_csv(&conn, &filter, &wanted).unwrap();
+        assert_eq!(rows, 1);
+        assert_eq!(
+            csv,
+            "name,tags,link_url:amazon,latest_ad_headline,total_clicks\n\
+             Lamp,\"cozy, gift\",https://amzn.to/x,Glow up,5\n"
+        );
+
+        assert!(to_csv(&conn, &filter, &["price; DROP TABLE products".to_string()]).is_err());
+        assert!(to_csv(&conn, &filter, &["link_url:x' OR 1".to_string()]).is_err());
+    }
+}
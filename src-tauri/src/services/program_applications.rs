@@ -0,0 +1,275 @@
+//! Program Applications
+//!
+//! Tracks signups with affiliate programs and networks: whether the user has
+//! applied, when, who their contact is, and how it was decided. Applications
+//! are keyed by program name so programs that only appear in discovery
+//! results can be tracked too. Pending applications get a follow-up
+//! reminder, shown as an OS notification by the scheduler.
+
+use crate::database::settings::get_setting_or;
+use crate::database::{get_connection, parse_timestamp};
+use crate::models::program_application::{ProgramApplication, SaveProgramApplicationInput};
+use chrono::{Duration, NaiveDateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::collections::HashMap;
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+pub const NOT_APPLIED: &str = "not_applied";
+pub const APPLIED: &str = "applied";
+pub const APPROVED: &str = "approved";
+pub const REJECTED: &str = "rejected";
+
+pub const APPLICATION_STATUSES: [&str; 4] = [NOT_APPLIED, APPLIED, APPROVED, REJECTED];
+
+/// First follow-up after applying, and the gap between repeat reminders
+const REMIND_AFTER_DAYS: i64 = 14;
+const REMIND_AGAIN_DAYS: i64 = 7;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+pub const APPLICATION_COLUMNS: &str = "id, program_name, platform, status, signup_url, applied_at,
+     decided_at, contact_name, contact_email, notes, remind_at, created_at, updated_at";
+
+/// Maps a row selected with `APPLICATION_COLUMNS` into a `ProgramApplication`
+pub fn application_from_row(row: &Row) -> rusqlite::Result<ProgramApplication> {
+    Ok(ProgramApplication {
+        id: row.get(0)?,
+        program_name: row.get(1)?,
+        platform: row.get(2)?,
+        status: row.get(3)?,
+        signup_url: row.get(4)?,
+        applied_at: row.get(5)?,
+        decided_at: row.get(6)?,
+        contact_name: row.get(7)?,
+        contact_email: row.get(8)?,
+        notes: row.get(9)?,
+        remind_at: row.get(10)?,
+        created_at: row.get(11)?,
+        updated_at: row.get(12)?,
+    })
+}
+
+pub fn validate(input: &SaveProgramApplicationInput) -> Result<(), String> {
+    if input.program_name.trim().is_empty() {
+        return Err("Program name is required".to_string());
+    }
+    if !APPLICATION_STATUSES.contains(&input.status.as_str()) {
+        return Err(format!(
+            "Unknown application status '{}'; expected one of {}",
+            input.status,
+            APPLICATION_STATUSES.join(", ")
+        ));
+    }
+    for date in [&input.applied_at, &input.decided_at, &input.remind_at] {
+        if let Some(value) = date.as_deref().filter(|v| !v.trim().is_empty()) {
+            if normalize_date(date).is_none() {
+                return Err(format!("Invalid date '{}'; expected YYYY-MM-DD or YYYY-MM-DD HH:MM:SS", value));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Accepts a date or a timestamp and returns it as a stored timestamp
+fn normalize_date(value: &Option<String>) -> Option<String> {
+    let value = value.as_deref().map(str::trim).filter(|v| !v.is_empty())?;
+    let parsed = parse_timestamp(value).or_else(|| {
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0)
+    })?;
+    Some(parsed.format(TIMESTAMP_FORMAT).to_string())
+}
+
+/// Works out `(applied_at, decided_at, remind_at)` for a save. Explicit input
+/// wins, then what was already stored, then a date implied by the status.
+pub fn resolve_dates(
+    existing: Option<&ProgramApplication>,
+    input: &SaveProgramApplicationInput,
+    now: NaiveDateTime,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let stamp = |at: NaiveDateTime| at.format(TIMESTAMP_FORMAT).to_string();
+    let status_unchanged = existing.is_some_and(|e| e.status == input.status);
+
+    let applied_at = match input.status.as_str() {
+        NOT_APPLIED => None,
+        _ => normalize_date(&input.applied_at)
+            .or_else(|| existing.and_then(|e| e.applied_at.clone()))
+            .or_else(|| Some(stamp(now))),
+    };
+
+    let decided_at = match input.status.as_str() {
+        APPROVED | REJECTED => normalize_date(&input.decided_at)
+            .or_else(|| existing.filter(|_| status_unchanged).and_then(|e| e.decided_at.clone()))
+            .or_else(|| Some(stamp(now))),
+        _ => None,
+    };
+
+    let remind_at = match input.status.as_str() {
+        APPLIED => normalize_date(&input.remind_at)
+            .or_else(|| existing.filter(|_| status_unchanged).and_then(|e| e.remind_at.clone()))
+            .or_else(|| {
+                let applied = applied_at.as_deref().and_then(parse_timestamp).unwrap_or(now);
+                Some(stamp(applied + Duration::days(REMIND_AFTER_DAYS)))
+            }),
+        _ => None,
+    };
+
+    (applied_at, decided_at, remind_at)
+}
+
+pub fn get_by_name(conn: &Connection, program_name: &str) -> rusqlite::Result<Option<ProgramApplication>> {
+    conn.query_row(
+        &format!("SELECT {} FROM program_applications WHERE program_name = ?1", APPLICATION_COLUMNS),
+        params![program_name.trim()],
+        application_from_row,
+    )
+    .optional()
+}
+
+/// Applications, optionally with one status, most recently updated first
+pub fn list(conn: &Connection, status: Option<&str>) -> rusqlite::Result<Vec<ProgramApplication>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM program_applications WHERE (?1 IS NULL OR status = ?1)
+         ORDER BY updated_at DESC, id DESC",
+        APPLICATION_COLUMNS
+    ))?;
+    let applications = stmt.query_map(params![status], application_from_row)?.collect();
+    applications
+}
+
+/// Inserts or updates the application for the input's program name
+pub fn save(conn: &Connection, input: &SaveProgramApplicationInput) -> rusqlite::Result<ProgramApplication> {
+    let existing = get_by_name(conn, &input.program_name)?;
+    let (applied_at, decided_at, remind_at) = resolve_dates(existing.as_ref(), input, Utc::now().naive_utc());
+    let clean = |value: &Option<String>| value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(String::from);
+
+    conn.execute(
+        "INSERT INTO program_applications (program_name, platform, status, signup_url, applied_at,
+         decided_at, contact_name, contact_email, notes, remind_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+         ON CONFLICT(program_name) DO UPDATE SET
+            platform = COALESCE(excluded.platform, platform), status = excluded.status,
+            signup_url = excluded.signup_url, applied_at = excluded.applied_at,
+            decided_at = excluded.decided_at, contact_name = excluded.contact_name,
+            contact_email = excluded.contact_email, notes = excluded.notes,
+            remind_at = excluded.remind_at, updated_at = CURRENT_TIMESTAMP",
+        params![
+            input.program_name.trim(),
+            clean(&input.platform),
+            input.status,
+            clean(&input.signup_url),
+            applied_at,
+            decided_at,
+            clean(&input.contact_name),
+            clean(&input.contact_email),
+            clean(&input.notes),
+            remind_at,
+        ],
+    )?;
+
+    get_by_name(conn, &input.program_name)?.ok_or(rusqlite::Error::QueryReturnedNoRows)
+}
+
+/// Pending applications whose follow-up reminder has come due
+pub fn due_reminders(conn: &Connection) -> rusqlite::Result<Vec<ProgramApplication>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM program_applications
+         WHERE status = ?1 AND remind_at IS NOT NULL AND remind_at <= datetime('now')
+         ORDER BY remind_at",
+        APPLICATION_COLUMNS
+    ))?;
+    let applications = stmt.query_map(params![APPLIED], application_from_row)?.collect();
+    applications
+}
+
+/// Application status per program name (lowercased), for annotating discovery results
+pub fn statuses_by_name(conn: &Connection) -> rusqlite::Result<HashMap<String, String>> {
+    let mut stmt = conn.prepare("SELECT program_name, status FROM program_applications")?;
+    let statuses = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?.to_lowercase(), row.get::<_, String>(1)?)))?
+        .collect();
+    statuses
+}
+
+/// Scheduled job entry point: notifies about pending applications that are
+/// due a follow-up and pushes their next reminder out a week
+pub fn run_reminder_job(app_handle: &AppHandle) -> Result<String, String> {
+    let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    let due = due_reminders(&conn).map_err(|e| e.to_string())?;
+    if due.is_empty() {
+        return Ok("No application reminders due".to_string());
+    }
+
+    if get_setting_or(&conn, "application_reminders_enabled", "true") == "true" {
+        let names: Vec<&str> = due.iter().map(|a| a.program_name.as_str()).collect();
+        let body = format!("Still waiting to hear back from {}. Time to follow up?", names.join(", "));
+        if let Err(e) = app_handle.notification().builder().title("Affiliate applications").body(&body).show() {
+            eprintln!("Failed to show application reminder: {}", e);
+        }
+    }
+
+    for application in &due {
+        conn.execute(
+            "UPDATE program_applications SET remind_at = datetime('now', ?1) WHERE id = ?2",
+            params![format!("+{} days", REMIND_AGAIN_DAYS), application.id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(format!("{} application reminder(s) sent", due.len()))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(status: &str) -> SaveProgramApplicationInput {
+        SaveProgramApplicationInput {
+            program_name: "ShareASale".to_string(),
+            platform: None,
+            status: status.to_string(),
+            signup_url: None,
+            applied_at: None,
+            decided_at: None,
+            contact_name: None,
+            contact_email: None,
+            notes: None,
+            remind_at: None,
+        }
+    }
+
+    #[test]
+    fn test_resolve_dates_follows_status() {
+        let now = parse_timestamp("2026-03-01 09:00:00").unwrap();
+        let (applied, decided, remind) = resolve_dates(None, &input(APPLIED), now);
+        assert_eq!(applied.as_deref(), Some("2026-03-01 09:00:00"));
+        assert_eq!(decided, None);
+        assert_eq!(remind.as_deref(), Some("2026-03-15 09:00:00"));
+
+        let existing = ProgramApplication {
+            id: 1,
+            program_name: "ShareASale".to_string(),
+            platform: None,
+            status: APPLIED.to_string(),
+            signup_url: None,
+            applied_at: applied,
+            decided_at: None,
+            contact_name: None,
+            contact_email: None,
+            notes: None,
+            remind_at: remind,
+            created_at: None,
+            updated_at: None,
+        };
+        let later = parse_timestamp("2026-03-12 12:30:00").unwrap();
+        let mut approved = input(APPROVED);
+        approved.decided_at = Some("2026-03-10".to_string());
+        let (applied, decided, remind) = resolve_dates(Some(&existing), &approved, later);
+        assert_eq!(applied.as_deref(), Some("2026-03-01 09:00:00"));
+        assert_eq!(decided.as_deref(), Some("2026-03-10 00:00:00"));
+        assert_eq!(remind, None);
+    }
+}
@@ -0,0 +1,197 @@
+//! Promotion Queue
+//!
+//! Ranks products by what to promote next. Each product gets an opportunity
+//! score from 0 to 100, a weighted sum of five factors scaled to 0-1:
+//! trending score, the best commission rate available, how crowded its
+//! category is, earnings per click so far (relative to the best product),
+//! and how close a matching retail event is. Retired and out-of-stock
+//! products are left out. Each entry carries its factors and the strongest
+//! ones as plain-language reasons.
+
+use crate::commands::ad_generation::competition_level;
+use crate::models::product::Product;
+use crate::models::promotion_queue::{PromotionCandidate, ScoreFactor};
+use crate::models::retail_event::{RetailEvent, UpcomingEvent};
+use crate::services::{availability, daily_metrics, product_comparison, retail_events};
+use chrono::{Duration, NaiveDate};
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+const TRENDING_WEIGHT: f64 = 0.25;
+const COMMISSION_WEIGHT: f64 = 0.25;
+const COMPETITION_WEIGHT: f64 = 0.15;
+const EPC_WEIGHT: f64 = 0.2;
+const SEASONALITY_WEIGHT: f64 = 0.15;
+
+/// Commission rate that earns the full commission factor
+const FULL_COMMISSION_RATE: f64 = 0.2;
+/// Days of click history behind earnings per click
+pub const EPC_DAYS: i64 = 90;
+/// Clicks needed before earnings per click is trusted
+const MIN_EPC_CLICKS: i64 = 20;
+/// Factor value given when there is nothing to go on
+const NEUTRAL: f64 = 0.5;
+/// Factors at or above this are named as reasons
+const REASON_THRESHOLD: f64 = 0.6;
+const MAX_REASONS: usize = 3;
+
+/// What a product is scored on
+#[derive(Debug, Clone, Default)]
+pub struct ScoreInputs {
+    pub trending_score: Option<i32>,
+    pub best_commission_rate: Option<f64>,
+    pub competition: String, // "high", "medium", "low"
+    pub clicks: i64,
+    pub commission: f64,
+    pub best_epc: f64, // Highest earnings per click among the products ranked
+    pub seasonality: Option<(f64, String, i64)>, // (0-1, event name, days until)
+}
+
+fn factor(key: &str, label: &str, value: f64, weight: f64, detail: String) -> ScoreFactor {
+    ScoreFactor { key: key.to_string(), label: label.to_string(), value: value.clamp(0.0, 1.0), weight, detail }
+}
+
+pub fn factors(inputs: &ScoreInputs) -> Vec<ScoreFactor> {
+    let trending = inputs.trending_score.unwrap_or(0);
+    let commission = match inputs.best_commission_rate {
+        Some(rate) => (rate / FULL_COMMISSION_RATE, format!("Up to {:.1}% commission", rate * 100.0)),
+        None => (0.0, "No program with a known commission rate".to_string()),
+    };
+    let competition = match inputs.competition.as_str() {
+        "high" => (0.3, "Crowded category".to_string()),
+        "medium" => (0.6, "Moderately competitive category".to_string()),
+        _ => (1.0, "Little competition in the category".to_string()),
+    };
+    let epc = if inputs.clicks < MIN_EPC_CLICKS {
+        (NEUTRAL, format!("Too few clicks to judge earnings ({})", inputs.clicks))
+    } else {
+        let epc = inputs.commission / inputs.clicks as f64;
+        let value = if inputs.best_epc > 0.0 { epc / inputs.best_epc } else { 0.0 };
+        (value, format!("${:.2} earned per click over {} days", epc, EPC_DAYS))
+    };
+    let seasonality = match &inputs.seasonality {
+        Some((score, name, 0)) => (*score, format!("{} is today", name)),
+        Some((score, name, days)) => (*score, format!("{} in {} days", name, days)),
+        None => (0.0, "No matching retail event coming up".to_string()),
+    };
+
+    vec![
+        factor(
+            "trending",
+            "Trending",
+            trending as f64 / 100.0,
+            TRENDING_WEIGHT,
+            format!("Trending score {}", trending),
+        ),
+        factor("commission", "Commission", commission.0, COMMISSION_WEIGHT, commission.1),
+        factor("competition", "Competition", competition.0, COMPETITION_WEIGHT, competition.1),
+        factor("epc", "Earnings per click", epc.0, EPC_WEIGHT, epc.1),
+        factor("seasonality", "Seasonality", seasonality.0, SEASONALITY_WEIGHT, seasonality.1),
+    ]
+}
+
+/// Weighted sum of the factors, 0-100
+pub fn score(factors: &[ScoreFactor]) -> f64 {
+    let total: f64 = factors.iter().map(|f| f.value * f.weight).sum();
+    (total * 1000.0).round() / 10.0
+}
+
+/// Details of the strong factors, biggest contribution first
+pub fn reasons(factors: &[ScoreFactor]) -> Vec<String> {
+    let mut strong: Vec<&ScoreFactor> = factors.iter().filter(|f| f.value >= REASON_THRESHOLD).collect();
+    strong.sort_by(|a, b| (b.value * b.weight).total_cmp(&(a.value * a.weight)));
+    strong.into_iter().take(MAX_REASONS).map(|f| f.detail.clone()).collect()
+}
+
+fn seasonality(events: &[RetailEvent], product: &Product, today: NaiveDate) -> Option<(f64, String, i64)> {
+    retail_events::seasonality(events, product, today)
+        .map(|(score, UpcomingEvent { event, days_until, .. })| (score, event.name, days_until))
+}
+
+/// Products ranked by opportunity score, best first
+pub fn queue(conn: &Connection, today: NaiveDate, limit: usize) -> rusqlite::Result<Vec<PromotionCandidate>> {
+    let events = retail_events::list(conn)?;
+    let products: Vec<Product> = retail_events::active_products(conn)?
+        .into_iter()
+        .filter(|p| p.availability.as_deref() != Some(availability::OUT_OF_STOCK))
+        .collect();
+
+    let start = today - Duration::days(EPC_DAYS - 1);
+    let history: HashMap<i64, (i64, f64)> = daily_metrics::top_products(conn, start, today, usize::MAX)?
+        .into_iter()
+        .map(|m| (m.product_id, (m.clicks, m.commission)))
+        .collect();
+    let best_epc = history
+        .values()
+        .filter(|(clicks, _)| *clicks >= MIN_EPC_CLICKS)
+        .map(|(clicks, commission)| commission / *clicks as f64)
+        .fold(0.0, f64::max);
+
+    let mut candidates = Vec::new();
+    for product in products {
+        let Some(product_id) = product.id else { continue };
+        let best_commission_rate = product_comparison::commission_options(conn, product_id)?
+            .iter()
+            .filter_map(|o| o.commission_rate)
+            .reduce(f64::max);
+        let (clicks, commission) = history.get(&product_id).copied().unwrap_or_default();
+        let inputs = ScoreInputs {
+            trending_score: product.trending_score,
+            best_commission_rate,
+            competition: competition_level(&product.category).to_string(),
+            clicks,
+            commission,
+            best_epc,
+            seasonality: seasonality(&events, &product, today),
+        };
+        let factors = factors(&inputs);
+        candidates.push(PromotionCandidate {
+            product_id,
+            product_name: product.name,
+            category: product.category,
+            score: score(&factors),
+            reasons: reasons(&factors),
+            factors,
+        });
+    }
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.product_name.cmp(&b.product_name)));
+    candidates.truncate(limit);
+    Ok(candidates)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_factors_weigh_into_score_and_reasons() {
+        let strong = ScoreInputs {
+            trending_score: Some(90),
+            best_commission_rate: Some(0.3),
+            competition: "low".to_string(),
+            clicks: 200,
+            commission: 100.0,
+            best_epc: 0.5,
+            seasonality: Some((1.0, "Black Friday".to_string(), 10)),
+        };
+        let strong = factors(&strong);
+        assert!((strong.iter().map(|f| f.weight).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(strong[1].value, 1.0); // Capped at the full rate
+        assert_eq!(score(&strong), 97.5);
+        assert_eq!(
+            reasons(&strong),
+            vec!["Up to 30.0% commission", "Trending score 90", "$0.50 earned per click over 90 days"]
+        );
+
+        // Thin click history is neutral rather than a penalty
+        let weak = ScoreInputs { competition: "high".to_string(), clicks: 3, ..Default::default() };
+        let weak = factors(&weak);
+        assert_eq!(weak[3].value, NEUTRAL);
+        assert_eq!(score(&weak), 14.5);
+        assert!(reasons(&weak).is_empty());
+    }
+}
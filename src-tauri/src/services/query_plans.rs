@@ -0,0 +1,103 @@
+//! Query Plan Review
+//!
+//! Runs `EXPLAIN QUERY PLAN` over the hot list and lookup queries so a
+//! missing or unused index shows up as a full scan or a temporary sort.
+//! Parameters are bound to NULL; SQLite plans the query the same way.
+
+use crate::commands::ad_generation::AD_COPY_COLUMNS;
+use crate::commands::affiliate_links::LINK_COLUMNS;
+use crate::commands::products::PRODUCT_COLUMNS;
+use crate::models::diagnostics::QueryPlan;
+use rusqlite::types::Null;
+use rusqlite::{params_from_iter, Connection};
+
+/// The queries behind the main lists, as `(name, sql)`
+pub fn hot_queries() -> Vec<(&'static str, String)> {
+    vec![
+        (
+            "all_products",
+            format!("SELECT {} FROM products ORDER BY trending_score DESC, name ASC", PRODUCT_COLUMNS),
+        ),
+        (
+            "products_by_stage",
+            format!(
+                "SELECT {} FROM products WHERE COALESCE(stage, 'researching') = ?1
+                 ORDER BY stage_changed_at DESC, trending_score DESC, name ASC",
+                PRODUCT_COLUMNS
+            ),
+        ),
+        ("all_links", format!("SELECT {} FROM affiliate_links ORDER BY created_at DESC", LINK_COLUMNS)),
+        (
+            "links_for_product",
+            format!("SELECT {} FROM affiliate_links WHERE product_id = ?1 ORDER BY created_at DESC", LINK_COLUMNS),
+        ),
+        (
+            "ads_for_product",
+            format!("SELECT {} FROM ad_copies WHERE product_id = ?1 ORDER BY created_at DESC", AD_COPY_COLUMNS),
+        ),
+        (
+            "clicks_for_link",
+            "SELECT clicked_at FROM click_events WHERE link_id = ?1 AND clicked_at >= ?2".to_string(),
+        ),
+        (
+            "conversions_for_link",
+            "SELECT converted_at, commission FROM conversion_events WHERE link_id = ?1 AND converted_at >= ?2"
+                .to_string(),
+        ),
+    ]
+}
+
+pub fn explain(conn: &Connection, name: &str, sql: &str) -> rusqlite::Result<QueryPlan> {
+    let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", sql))?;
+    let nulls = std::iter::repeat_n(Null, stmt.parameter_count());
+    let steps = stmt
+        .query_map(params_from_iter(nulls), |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, String>(3)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Depth of each step follows its parent chain
+    let mut depths: std::collections::HashMap<i64, usize> = std::collections::HashMap::new();
+    let mut plan = Vec::new();
+    for (id, parent, detail) in &steps {
+        let depth = depths.get(parent).map_or(0, |d| d + 1);
+        depths.insert(*id, depth);
+        plan.push(format!("{}{}", "  ".repeat(depth), detail));
+    }
+
+    let full_scan = steps.iter().any(|(_, _, d)| d.starts_with("SCAN ") && !d.contains("USING"));
+    let temp_sort = steps.iter().any(|(_, _, d)| d.contains("USE TEMP B-TREE"));
+    Ok(QueryPlan { name: name.to_string(), sql: sql.to_string(), plan, full_scan, temp_sort })
+}
+
+pub fn explain_hot_queries(conn: &Connection) -> rusqlite::Result<Vec<QueryPlan>> {
+    hot_queries().iter().map(|(name, sql)| explain(conn, name, sql)).collect()
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_flags_scans_and_sorts() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE ad_copies (id INTEGER PRIMARY KEY, product_id INTEGER, created_at TEXT);",
+        )
+        .unwrap();
+        let sql = "SELECT id FROM ad_copies WHERE product_id = ?1 ORDER BY created_at DESC";
+
+        let before = explain(&conn, "ads", sql).unwrap();
+        assert!(before.full_scan && before.temp_sort);
+
+        conn.execute_batch("CREATE INDEX idx_ad_copies_product_created ON ad_copies(product_id, created_at);")
+            .unwrap();
+        let after = explain(&conn, "ads", sql).unwrap();
+        assert!(!after.full_scan && !after.temp_sort);
+        assert!(after.plan[0].contains("idx_ad_copies_product_created"));
+    }
+}
@@ -0,0 +1,140 @@
+//! Slow Query Log
+//!
+//! Every connection from `get_connection` reports each statement's run time
+//! here through SQLite's profile hook. Statements slower than the threshold
+//! are logged and kept in memory, grouped by SQL text, so a list or report
+//! command that regresses shows up in `get_slow_queries`. SQLite hands the
+//! hook the statement as written, so bound parameters never reach the log;
+//! string literals built into the SQL are redacted as well.
+
+use crate::database::settings::{get_setting_parsed, set_setting};
+use crate::models::diagnostics::{SlowQuery, SlowQueryReport};
+use chrono::Utc;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+const THRESHOLD_SETTING: &str = "slow_query_threshold_ms";
+const DEFAULT_THRESHOLD_MS: i64 = 100;
+/// Distinct statements kept; the least recently seen is dropped first
+const MAX_ENTRIES: usize = 200;
+const MAX_SQL_CHARS: usize = 1000;
+
+static THRESHOLD_MS: AtomicI64 = AtomicI64::new(DEFAULT_THRESHOLD_MS);
+
+fn log() -> &'static Mutex<HashMap<String, SlowQuery>> {
+    static LOG: OnceLock<Mutex<HashMap<String, SlowQuery>>> = OnceLock::new();
+    LOG.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Reads the configured threshold; called once at startup
+pub fn load_threshold(conn: &Connection) {
+    THRESHOLD_MS.store(get_setting_parsed(conn, THRESHOLD_SETTING, DEFAULT_THRESHOLD_MS), Ordering::Relaxed);
+}
+
+pub fn set_threshold(conn: &Connection, threshold_ms: i64) -> Result<(), String> {
+    if threshold_ms < 1 {
+        return Err("Slow query threshold must be at least 1 ms".to_string());
+    }
+    set_setting(conn, THRESHOLD_SETTING, &threshold_ms.to_string()).map_err(|e| e.to_string())?;
+    THRESHOLD_MS.store(threshold_ms, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Replaces string literals with `?` and collapses whitespace
+pub fn redact(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut last_space = true;
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            // '' inside a literal is an escaped quote
+            loop {
+                match chars.next() {
+                    Some('\'') if chars.peek() == Some(&'\'') => {
+                        chars.next();
+                    }
+                    Some('\'') | None => break,
+                    Some(_) => {}
+                }
+            }
+            out.push('?');
+            last_space = false;
+        } else if c.is_whitespace() {
+            if !last_space {
+                out.push(' ');
+                last_space = true;
+            }
+        } else {
+            out.push(c);
+            last_space = false;
+        }
+    }
+    let out = out.trim_end();
+    match out.char_indices().nth(MAX_SQL_CHARS) {
+        Some((end, _)) => format!("{}…", &out[..end]),
+        None => out.to_string(),
+    }
+}
+
+/// Profile hook installed on each connection
+pub fn record(sql: &str, elapsed: Duration) {
+    let ms = elapsed.as_secs_f64() * 1000.0;
+    if ms < THRESHOLD_MS.load(Ordering::Relaxed) as f64 {
+        return;
+    }
+    let sql = redact(sql);
+    eprintln!("Slow query ({:.1} ms): {}", ms, sql);
+
+    let Ok(mut log) = log().lock() else { return };
+    let now = Utc::now().to_rfc3339();
+    if !log.contains_key(&sql) && log.len() >= MAX_ENTRIES {
+        let oldest = log.values().min_by(|a, b| a.last_seen.cmp(&b.last_seen)).map(|q| q.sql.clone());
+        if let Some(oldest) = oldest {
+            log.remove(&oldest);
+        }
+    }
+    let entry = log.entry(sql.clone()).or_insert_with(|| SlowQuery {
+        sql,
+        count: 0,
+        total_ms: 0.0,
+        max_ms: 0.0,
+        last_ms: 0.0,
+        last_seen: now.clone(),
+    });
+    entry.count += 1;
+    entry.total_ms += ms;
+    entry.max_ms = entry.max_ms.max(ms);
+    entry.last_ms = ms;
+    entry.last_seen = now;
+}
+
+pub fn report() -> SlowQueryReport {
+    let mut queries: Vec<SlowQuery> = log().lock().map(|log| log.values().cloned().collect()).unwrap_or_default();
+    queries.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+    SlowQueryReport { threshold_ms: THRESHOLD_MS.load(Ordering::Relaxed), queries }
+}
+
+pub fn clear() {
+    if let Ok(mut log) = log().lock() {
+        log.clear();
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_hides_literals_and_collapses_whitespace() {
+        let sql = "SELECT *\n   FROM products\n   WHERE name LIKE '%o''brien%' AND id = ?1 LIMIT 50";
+        assert_eq!(redact(sql), "SELECT * FROM products WHERE name LIKE ? AND id = ?1 LIMIT 50");
+        assert_eq!(redact("SELECT 'unterminated"), "SELECT ?");
+    }
+}
@@ -0,0 +1,213 @@
+//! Quick Ads from Templates
+//!
+//! A deterministic fast path beside AI generation: user-authored templates
+//! whose `{{merge_tags}}` are filled from the product, its active affiliate
+//! link, and its top review. No provider call is made, so the same product
+//! and template always produce the same ad, instantly and at no cost.
+
+use crate::models::ad_template::{AdTemplate, SaveAdTemplateInput};
+use crate::models::product::Product;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::collections::HashMap;
+
+/// Tags a template may use, e.g. `{{product_name}}`
+pub const MERGE_TAGS: [&str; 8] = [
+    "product_name",
+    "category",
+    "description",
+    "price",
+    "audience",
+    "link",
+    "selling_point",
+    "review_quote",
+];
+
+/// Stand-in for `{{link}}` when the product has no active link or URL,
+/// matching the placeholder generated copy uses
+const LINK_PLACEHOLDER: &str = "[LINK]";
+
+pub const TEMPLATE_COLUMNS: &str = "id, name, ad_type, headline, body, cta, created_at, updated_at";
+
+/// Maps a row selected with `TEMPLATE_COLUMNS` into an `AdTemplate`
+pub fn template_from_row(row: &Row) -> rusqlite::Result<AdTemplate> {
+    Ok(AdTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        ad_type: row.get(2)?,
+        headline: row.get(3)?,
+        body: row.get(4)?,
+        cta: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+/// Tag names used in `text`, in order of appearance (whitespace inside the braces is ignored)
+pub fn merge_tags(text: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                tags.push(after[..end].trim().to_string());
+                rest = &after[end + 2..];
+            }
+            None => break,
+        }
+    }
+    tags
+}
+
+/// Rejects templates with missing fields, unknown ad types, or unknown merge tags
+pub fn validate(input: &SaveAdTemplateInput, valid_ad_type: bool) -> Result<(), String> {
+    if input.name.trim().is_empty() {
+        return Err("Template name is required".to_string());
+    }
+    if input.headline.trim().is_empty() {
+        return Err("Template headline is required".to_string());
+    }
+    if !valid_ad_type {
+        return Err(format!("Unknown ad type '{}'", input.ad_type));
+    }
+
+    let text = format!("{}\n{}\n{}", input.headline, input.body, input.cta.as_deref().unwrap_or(""));
+    let mut unknown: Vec<String> = Vec::new();
+    for tag in merge_tags(&text) {
+        if !MERGE_TAGS.contains(&tag.as_str()) && !unknown.contains(&tag) {
+            unknown.push(tag);
+        }
+    }
+    if !unknown.is_empty() {
+        return Err(format!(
+            "Unknown merge tags: {}; available: {}",
+            unknown.join(", "),
+            MERGE_TAGS.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Values for every merge tag; missing product fields merge as empty text
+pub fn merge_values(
+    product: &Product,
+    link: Option<&str>,
+    selling_point: Option<&str>,
+    review_quote: Option<&str>,
+) -> HashMap<&'static str, String> {
+    let link = link
+        .or(product.product_url.as_deref())
+        .unwrap_or(LINK_PLACEHOLDER);
+    HashMap::from([
+        ("product_name", product.name.clone()),
+        ("category", product.category.clone()),
+        ("description", product.description.clone().unwrap_or_default()),
+        ("price", product.price_range.clone().unwrap_or_default()),
+        ("audience", product.target_audience.clone().unwrap_or_default()),
+        ("link", link.to_string()),
+        ("selling_point", selling_point.unwrap_or_default().to_string()),
+        ("review_quote", review_quote.unwrap_or_default().to_string()),
+    ])
+}
+
+/// Fills every merge tag in `text`; empty values leave no stray spaces behind
+pub fn render(text: &str, values: &HashMap<&'static str, String>) -> String {
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else { break };
+        rendered.push_str(&rest[..start]);
+        if let Some(value) = values.get(after[..end].trim()) {
+            rendered.push_str(value);
+        }
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    rendered
+        .lines()
+        .map(|line| line.split(' ').filter(|w| !w.is_empty()).collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+pub fn list(conn: &Connection) -> rusqlite::Result<Vec<AdTemplate>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM ad_templates ORDER BY name COLLATE NOCASE",
+        TEMPLATE_COLUMNS
+    ))?;
+    let templates = stmt
+        .query_map([], template_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(templates)
+}
+
+pub fn get(conn: &Connection, template_id: i64) -> rusqlite::Result<Option<AdTemplate>> {
+    conn.query_row(
+        &format!("SELECT {} FROM ad_templates WHERE id = ?1", TEMPLATE_COLUMNS),
+        params![template_id],
+        template_from_row,
+    )
+    .optional()
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product() -> Product {
+        Product {
+            id: Some(1),
+            name: "Glow Serum".to_string(),
+            category: "Beauty & Skincare".to_string(),
+            description: None,
+            price_range: Some("$20-$30".to_string()),
+            target_audience: None,
+            trending_score: None,
+            notes: None,
+            image_url: None,
+            amazon_asin: None,
+            tiktok_product_id: None,
+            instagram_product_id: None,
+            youtube_video_id: None,
+            pinterest_pin_id: None,
+            product_url: None,
+            created_at: None,
+            updated_at: None,
+            stage: None,
+            stage_changed_at: None,
+            tags: vec![],
+            updated_by: None,
+            availability: None,
+            availability_checked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_render_fills_tags_and_collapses_empty_values() {
+        let values = merge_values(&product(), None, Some("Lightweight"), None);
+        let rendered = render("{{ product_name }} for {{price}}. {{description}} {{selling_point}}!\n{{link}}", &values);
+        assert_eq!(rendered, "Glow Serum for $20-$30. Lightweight!\n[LINK]");
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_tags() {
+        let input = SaveAdTemplateInput {
+            id: None,
+            name: "Launch".to_string(),
+            ad_type: "social_post".to_string(),
+            headline: "New: {{product_name}}".to_string(),
+            body: "{{discount_code}} {{product}}".to_string(),
+            cta: None,
+        };
+        let err = validate(&input, true).unwrap_err();
+        assert!(err.starts_with("Unknown merge tags: discount_code, product;"));
+    }
+}
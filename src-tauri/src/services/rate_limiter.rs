@@ -0,0 +1,246 @@
+//! Rate Limiting for AI Provider Calls
+//!
+//! Each provider gets a token bucket sized from settings
+//! (`ai_rate_limit_rpm:<provider>`, `ai_rate_limit_burst:<provider>`).
+//! Callers queue on the provider's bucket in arrival order, so a bulk run
+//! with many concurrent tasks drains at the provider's pace instead of
+//! tripping its limits. When a provider still answers 429, the bucket is
+//! paused for the `Retry-After` period and the call is retried.
+
+use crate::database::settings::get_setting_parsed;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::time::Instant;
+
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 60;
+pub const DEFAULT_BURST: u32 = 5;
+/// Retries after a rate-limit response before giving up
+pub const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimits {
+    pub requests_per_minute: u32,
+    pub burst: u32,
+}
+
+/// Loads the configured limits for a provider
+pub fn load_limits(conn: &Connection, provider: &str) -> RateLimits {
+    RateLimits {
+        requests_per_minute: get_setting_parsed(
+            conn,
+            &format!("ai_rate_limit_rpm:{}", provider),
+            DEFAULT_REQUESTS_PER_MINUTE,
+        )
+        .max(1),
+        burst: get_setting_parsed(conn, &format!("ai_rate_limit_burst:{}", provider), DEFAULT_BURST).max(1),
+    }
+}
+
+/// Outcome of a provider call as seen by the limiter
+#[derive(Debug, Clone)]
+pub enum CallError {
+    /// The provider rejected the call for exceeding its limits (HTTP 429)
+    RateLimited { retry_after: Option<Duration> },
+    Failed(String),
+}
+
+// =============================================================================
+// TOKEN BUCKET
+// =============================================================================
+
+#[derive(Debug)]
+pub struct TokenBucket {
+    limits: RateLimits,
+    tokens: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    pub fn new(limits: RateLimits, now: Instant) -> Self {
+        TokenBucket {
+            limits,
+            tokens: limits.burst as f64,
+            last_refill: now,
+            paused_until: None,
+        }
+    }
+
+    fn refill_per_sec(&self) -> f64 {
+        self.limits.requests_per_minute as f64 / 60.0
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec()).min(self.limits.burst as f64);
+        self.last_refill = now;
+    }
+
+    /// Takes a token when one is available, otherwise returns how long to wait
+    pub fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(until) = self.paused_until {
+            if now < until {
+                return Err(until - now);
+            }
+            self.paused_until = None;
+        }
+
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - self.tokens;
+            Err(Duration::from_secs_f64(missing / self.refill_per_sec()))
+        }
+    }
+
+    /// Stops handing out tokens until `now + wait` (provider asked us to back off)
+    pub fn pause(&mut self, wait: Duration, now: Instant) {
+        let until = now + wait;
+        self.paused_until = Some(self.paused_until.map_or(until, |current| current.max(until)));
+        self.tokens = 0.0;
+    }
+}
+
+// =============================================================================
+// PER-PROVIDER QUEUE
+// =============================================================================
+
+/// A provider's bucket behind an async mutex; tokio's mutex is fair, so
+/// waiting callers are served in the order they queued
+pub struct ProviderLimiter {
+    bucket: tokio::sync::Mutex<TokenBucket>,
+}
+
+impl ProviderLimiter {
+    /// Waits for the caller's turn and a free token
+    pub async fn acquire(&self) {
+        let mut bucket = self.bucket.lock().await;
+        loop {
+            match bucket.try_acquire(Instant::now()) {
+                Ok(()) => return,
+                Err(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    pub async fn pause(&self, wait: Duration) {
+        self.bucket.lock().await.pause(wait, Instant::now());
+    }
+
+    async fn update_limits(&self, limits: RateLimits) {
+        let mut bucket = self.bucket.lock().await;
+        if bucket.limits != limits {
+            *bucket = TokenBucket::new(limits, Instant::now());
+        }
+    }
+}
+
+fn registry() -> &'static Mutex<HashMap<String, Arc<ProviderLimiter>>> {
+    static LIMITERS: OnceLock<Mutex<HashMap<String, Arc<ProviderLimiter>>>> = OnceLock::new();
+    LIMITERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Returns the shared limiter for `provider`, applying changed limits
+pub async fn limiter_for(provider: &str, limits: RateLimits) -> Arc<ProviderLimiter> {
+    let limiter = {
+        let mut limiters = registry().lock().unwrap_or_else(|e| e.into_inner());
+        limiters
+            .entry(provider.to_string())
+            .or_insert_with(|| {
+                Arc::new(ProviderLimiter {
+                    bucket: tokio::sync::Mutex::new(TokenBucket::new(limits, Instant::now())),
+                })
+            })
+            .clone()
+    };
+    limiter.update_limits(limits).await;
+    limiter
+}
+
+/// Runs `call` under the provider's rate limit, retrying rate-limited
+/// attempts after the provider's `Retry-After` (or an exponential backoff)
+pub async fn call_with_rate_limit<T, F, Fut>(provider: &str, limits: RateLimits, mut call: F) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, CallError>>,
+{
+    let limiter = limiter_for(provider, limits).await;
+    let mut attempt = 0;
+
+    loop {
+        limiter.acquire().await;
+
+        match call().await {
+            Ok(value) => return Ok(value),
+            Err(CallError::Failed(e)) => return Err(e),
+            Err(CallError::RateLimited { retry_after }) => {
+                if attempt >= MAX_RATE_LIMIT_RETRIES {
+                    return Err(format!("{} rate limit exceeded after {} retries", provider, attempt));
+                }
+                limiter.pause(retry_after.unwrap_or_else(|| backoff(attempt))).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Exponential backoff used when the provider gives no Retry-After: 2s, 4s, 8s, ...
+pub fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(attempt + 1).min(60))
+}
+
+/// Parses a `Retry-After` header given in seconds (HTTP-date values are ignored)
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<f64>().ok().filter(|s| *s >= 0.0).map(Duration::from_secs_f64)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: RateLimits = RateLimits {
+        requests_per_minute: 60,
+        burst: 2,
+    };
+
+    #[test]
+    fn test_bucket_allows_burst_then_waits() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(LIMITS, now);
+        assert!(bucket.try_acquire(now).is_ok());
+        assert!(bucket.try_acquire(now).is_ok());
+
+        let wait = bucket.try_acquire(now).unwrap_err();
+        assert!(wait <= Duration::from_secs(1));
+
+        // One token per second at 60 rpm
+        assert!(bucket.try_acquire(now + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_pause_blocks_until_retry_after() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(LIMITS, now);
+        bucket.pause(Duration::from_secs(10), now);
+        assert_eq!(bucket.try_acquire(now).unwrap_err(), Duration::from_secs(10));
+        assert!(bucket.try_acquire(now + Duration::from_secs(11)).is_ok());
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("30"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_retry_after("1.5"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+        assert_eq!(backoff(0), Duration::from_secs(2));
+        assert_eq!(backoff(2), Duration::from_secs(8));
+    }
+}
@@ -0,0 +1,158 @@
+//! Read-only Workspace
+//!
+//! A workspace can be switched to read-only, e.g. while presenting it to a
+//! client or after opening a backup, so nothing in it changes by accident.
+//! The flag is stored in the workspace's own settings. Enforcement happens
+//! once, in the app's invoke handler: every command not known to only read
+//! is refused with a JSON `ReadOnlyError` before it runs. Commands are
+//! treated as changing data unless their name says otherwise, so a new
+//! command is blocked until it is recognized as a read.
+
+use crate::database::get_connection;
+use crate::database::settings::{get_setting, set_setting};
+use crate::models::read_only::{ReadOnlyError, ReadOnlyStatus};
+use chrono::Utc;
+use rusqlite::Connection;
+use tauri::AppHandle;
+
+pub const READ_ONLY: &str = "read_only";
+
+const SETTING: &str = "workspace_read_only";
+
+/// Command name prefixes that only read the workspace. Exports write files
+/// outside it, which is what presenting and backups need.
+const READ_PREFIXES: [&str; 14] = [
+    "get_", "count_", "search_", "query_", "filter_", "explain_", "compare_", "analyze_", "estimate_",
+    "simulate_", "suggest_", "check_", "build_", "export_",
+];
+
+/// Read-named commands that write: `check_watchlist_now` stores what it
+/// finds and `build_deep_link` saves the link it builds.
+/// `check_data_integrity` only reads unless asked to apply fixes, so it
+/// refuses those itself with `require_writable`.
+const WRITING_READS: [&str; 2] = ["check_watchlist_now", "build_deep_link"];
+
+/// Commands that change the session rather than the workspace, and the
+/// toggle itself so read-only can be turned off again
+const ALWAYS_ALLOWED: [&str; 5] =
+    ["set_workspace_read_only", "unlock_owner", "lock_owner", "release_workspace_lock", "open_attachment"];
+
+/// Whether `command` can run while the workspace is read-only
+pub fn allows(command: &str) -> bool {
+    ALWAYS_ALLOWED.contains(&command)
+        || (READ_PREFIXES.iter().any(|prefix| command.starts_with(prefix)) && !WRITING_READS.contains(&command))
+}
+
+pub fn status(conn: &Connection) -> ReadOnlyStatus {
+    get_setting(conn, SETTING)
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn is_enabled(conn: &Connection) -> bool {
+    status(conn).enabled
+}
+
+/// Turns read-only on (keeping the time it was first turned on) or off
+pub fn set(conn: &Connection, enabled: bool, reason: Option<&str>) -> Result<ReadOnlyStatus, String> {
+    let current = status(conn);
+    let status = if enabled {
+        ReadOnlyStatus {
+            enabled,
+            reason: reason.map(str::trim).filter(|r| !r.is_empty()).map(String::from),
+            since: current
+                .since
+                .filter(|_| current.enabled)
+                .or_else(|| Some(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string())),
+        }
+    } else {
+        ReadOnlyStatus::default()
+    };
+    let json = serde_json::to_string(&status).map_err(|e| e.to_string())?;
+    set_setting(conn, SETTING, &json).map_err(|e| e.to_string())?;
+    Ok(status)
+}
+
+/// Gate for `command`; the error is a JSON `ReadOnlyError`
+pub fn check(conn: &Connection, command: &str) -> Result<(), String> {
+    if allows(command) {
+        return Ok(());
+    }
+    require_writable(conn, command)
+}
+
+/// Refuses `action` while the workspace is read-only, whatever its name. For
+/// writes the invoke handler can't see, e.g. an option that turns a read
+/// into a write.
+pub fn require_writable(conn: &Connection, action: &str) -> Result<(), String> {
+    let status = status(conn);
+    if !status.enabled {
+        return Ok(());
+    }
+    let message = match status.reason {
+        Some(reason) => format!("The workspace is read-only ({}). Turn read-only off to make changes.", reason),
+        None => "The workspace is read-only. Turn read-only off to make changes.".to_string(),
+    };
+    let error = ReadOnlyError { code: READ_ONLY.to_string(), command: action.to_string(), message };
+    Err(serde_json::to_string(&error).unwrap_or_else(|_| error.message.clone()))
+}
+
+/// `check` for an invoked command. Reads never touch the database here; a
+/// workspace that can't be opened lets the command report that itself.
+pub fn guard(app_handle: &AppHandle, command: &str) -> Result<(), String> {
+    if allows(command) {
+        return Ok(());
+    }
+    match get_connection(app_handle) {
+        Ok(conn) => check(&conn, command),
+        Err(_) => Ok(()),
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_refuses_everything_but_reads() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE settings (key TEXT PRIMARY KEY, value TEXT, updated_at DATETIME DEFAULT CURRENT_TIMESTAMP);",
+        )
+        .unwrap();
+        assert!(check(&conn, "update_product").is_ok());
+
+        let on = set(&conn, true, Some(" Presenting ")).unwrap();
+        assert_eq!(on.reason.as_deref(), Some("Presenting"));
+        let error: ReadOnlyError = serde_json::from_str(&check(&conn, "update_product").unwrap_err()).unwrap();
+        assert_eq!((error.code.as_str(), error.command.as_str()), (READ_ONLY, "update_product"));
+        assert!(error.message.contains("(Presenting)"));
+        let refused =
+            ["delete_product", "check_watchlist_now", "build_deep_link", "generate_quick_ad", "brand_new_command"];
+        for command in refused {
+            assert!(check(&conn, command).is_err(), "{} should be refused", command);
+        }
+        for command in ["get_all_products", "count_links", "export_products_csv", "set_workspace_read_only"] {
+            assert!(check(&conn, command).is_ok(), "{} should run", command);
+        }
+        assert!(check(&conn, "check_data_integrity").is_ok());
+        assert!(require_writable(&conn, "check_data_integrity").is_err());
+
+        // Changing the reason keeps when read-only started
+        conn.execute(
+            "UPDATE settings SET value = json_set(value, '$.since', '2026-01-01 09:00:00') WHERE key = ?1",
+            [SETTING],
+        )
+        .unwrap();
+        assert_eq!(set(&conn, true, None).unwrap().since.as_deref(), Some("2026-01-01 09:00:00"));
+
+        assert!(!set(&conn, false, None).unwrap().enabled);
+        assert!(check(&conn, "update_product").is_ok());
+    }
+}
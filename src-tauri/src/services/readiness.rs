@@ -0,0 +1,200 @@
+//! Product Readiness
+//!
+//! A configurable checklist a product has to pass before it is promoted: a
+//! product image, an active affiliate link, enough ads, a workspace affiliate
+//! disclosure, and a recently verified price. Bulk promotion can be limited
+//! to products that are ready.
+
+use crate::database::parse_timestamp;
+use crate::database::settings::{get_setting, get_setting_or, get_setting_parsed, set_setting};
+use crate::models::readiness::{ProductReadiness, ReadinessCheck, ReadinessConfig};
+use chrono::{Duration, NaiveDateTime, Utc};
+use rusqlite::{params, Connection};
+
+pub const DISCLOSURE_SETTING: &str = "affiliate_disclosure";
+
+const DEFAULT_MIN_ADS: i64 = 2;
+const DEFAULT_PRICE_MAX_AGE_DAYS: i64 = 30;
+
+pub fn load_config(conn: &Connection) -> ReadinessConfig {
+    ReadinessConfig {
+        require_image: get_setting_or(conn, "readiness_require_image", "true") == "true",
+        require_link: get_setting_or(conn, "readiness_require_link", "true") == "true",
+        min_ads: get_setting_parsed(conn, "readiness_min_ads", DEFAULT_MIN_ADS).max(0),
+        require_disclosure: get_setting_or(conn, "readiness_require_disclosure", "true") == "true",
+        require_price_verified: get_setting_or(conn, "readiness_require_price_verified", "true") == "true",
+        price_max_age_days: get_setting_parsed(conn, "readiness_price_max_age_days", DEFAULT_PRICE_MAX_AGE_DAYS)
+            .max(1),
+        disclosure_text: get_setting(conn, DISCLOSURE_SETTING).ok().flatten().filter(|t| !t.trim().is_empty()),
+    }
+}
+
+pub fn save_config(conn: &Connection, config: &ReadinessConfig) -> rusqlite::Result<()> {
+    let values = [
+        ("readiness_require_image", config.require_image.to_string()),
+        ("readiness_require_link", config.require_link.to_string()),
+        ("readiness_min_ads", config.min_ads.max(0).to_string()),
+        ("readiness_require_disclosure", config.require_disclosure.to_string()),
+        ("readiness_require_price_verified", config.require_price_verified.to_string()),
+        ("readiness_price_max_age_days", config.price_max_age_days.max(1).to_string()),
+        (DISCLOSURE_SETTING, config.disclosure_text.as_deref().unwrap_or("").trim().to_string()),
+    ];
+    for (key, value) in values.iter() {
+        set_setting(conn, key, value)?;
+    }
+    Ok(())
+}
+
+/// What the checklist looks at for one product
+#[derive(Debug, Clone, Default)]
+pub struct ProductFacts {
+    pub has_image: bool,
+    pub active_links: i64,
+    pub ads: i64,
+    pub price_verified_at: Option<String>,
+}
+
+fn load_facts(conn: &Connection, product_id: i64) -> rusqlite::Result<(String, ProductFacts)> {
+    conn.query_row(
+        "SELECT p.name, TRIM(COALESCE(p.image_url, '')) != '', p.price_verified_at,
+                (SELECT COUNT(*) FROM affiliate_links WHERE product_id = p.id AND status = 'active'),
+                (SELECT COUNT(*) FROM ad_copies WHERE product_id = p.id AND parent_ad_id IS NULL
+                   AND COALESCE(ad_type, '') != 'email_sequence')
+         FROM products p WHERE p.id = ?1",
+        params![product_id],
+        |row| {
+            Ok((
+                row.get(0)?,
+                ProductFacts {
+                    has_image: row.get(1)?,
+                    price_verified_at: row.get(2)?,
+                    active_links: row.get(3)?,
+                    ads: row.get(4)?,
+                },
+            ))
+        },
+    )
+}
+
+/// Runs the checklist against a product's facts
+pub fn evaluate(config: &ReadinessConfig, facts: &ProductFacts, now: NaiveDateTime) -> Vec<ReadinessCheck> {
+    let check = |key: &str, label: &str, required: bool, passed: bool, detail: Option<String>| ReadinessCheck {
+        key: key.to_string(),
+        label: label.to_string(),
+        required,
+        passed,
+        detail: detail.filter(|_| !passed),
+    };
+
+    let verified_at = facts.price_verified_at.as_deref().and_then(parse_timestamp);
+    let price_fresh = verified_at.is_some_and(|at| now - at <= Duration::days(config.price_max_age_days));
+    let price_detail = match verified_at {
+        Some(at) => format!("Price last verified {} days ago", (now - at).num_days()),
+        None => "Price has not been verified".to_string(),
+    };
+
+    vec![
+        check(
+            "image",
+            "Has a product image",
+            config.require_image,
+            facts.has_image,
+            Some("No product image".to_string()),
+        ),
+        check(
+            "link",
+            "Has an active affiliate link",
+            config.require_link,
+            facts.active_links > 0,
+            Some("No active affiliate link".to_string()),
+        ),
+        check(
+            "ads",
+            &format!("Has {}+ ads", config.min_ads.max(1)),
+            config.min_ads > 0,
+            facts.ads >= config.min_ads.max(1),
+            Some(format!("{} of {} ads", facts.ads, config.min_ads.max(1))),
+        ),
+        check(
+            "disclosure",
+            "Affiliate disclosure configured",
+            config.require_disclosure,
+            config.disclosure_text.is_some(),
+            Some("Set an affiliate disclosure in the readiness settings".to_string()),
+        ),
+        check("price_verified", "Price verified", config.require_price_verified, price_fresh, Some(price_detail)),
+    ]
+}
+
+pub fn product_readiness(
+    conn: &Connection,
+    config: &ReadinessConfig,
+    product_id: i64,
+) -> rusqlite::Result<ProductReadiness> {
+    let (product_name, facts) = load_facts(conn, product_id)?;
+    let checks = evaluate(config, &facts, Utc::now().naive_utc());
+    Ok(ProductReadiness {
+        product_id,
+        product_name,
+        ready: checks.iter().all(|c| c.passed || !c.required),
+        checks,
+    })
+}
+
+/// The subset of `product_ids` that passes every required check, in order
+pub fn ready_product_ids(conn: &Connection, product_ids: &[i64]) -> rusqlite::Result<Vec<i64>> {
+    let config = load_config(conn);
+    let mut ready = Vec::new();
+    for &product_id in product_ids {
+        match product_readiness(conn, &config, product_id) {
+            Ok(readiness) if readiness.ready => ready.push(product_id),
+            Ok(_) | Err(rusqlite::Error::QueryReturnedNoRows) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(ready)
+}
+
+/// Records that the product's price was just checked, or clears it
+pub fn set_price_verified(conn: &Connection, product_id: i64, verified: bool) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE products SET price_verified_at = CASE WHEN ?2 THEN CURRENT_TIMESTAMP ELSE NULL END
+         WHERE id = ?1",
+        params![product_id, verified],
+    )
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_checks() {
+        let config = ReadinessConfig {
+            require_image: true,
+            require_link: true,
+            min_ads: 2,
+            require_disclosure: false,
+            require_price_verified: true,
+            price_max_age_days: 30,
+            disclosure_text: None,
+        };
+        let facts = ProductFacts {
+            has_image: true,
+            active_links: 1,
+            ads: 1,
+            price_verified_at: Some("2026-01-01 00:00:00".to_string()),
+        };
+        let now = parse_timestamp("2026-02-15 00:00:00").unwrap();
+        let checks = evaluate(&config, &facts, now);
+        let failed: Vec<&str> = checks.iter().filter(|c| !c.passed).map(|c| c.key.as_str()).collect();
+        assert_eq!(failed, ["ads", "disclosure", "price_verified"]);
+        assert_eq!(checks[2].detail.as_deref(), Some("1 of 2 ads"));
+        assert_eq!(checks[4].detail.as_deref(), Some("Price last verified 45 days ago"));
+        assert!(!checks[3].required);
+    }
+}
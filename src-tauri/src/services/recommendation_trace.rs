@@ -0,0 +1,220 @@
+//! Recommendation Trace
+//!
+//! Explain mode for product recommendations. Runs the same discovery and
+//! ad type scoring that ad generation uses and keeps every intermediate
+//! value: the resolved inputs, each platform's match breakdown (including
+//! ones below the cut-off), the platform-to-ad-type rule, the engagement
+//! estimate and the analytics service's per-ad-type factors.
+//!
+//! Scoring uses no randomness or clock, and ties keep candidate order, so
+//! identical inputs give an identical trace. The fingerprint hashes the
+//! whole trace, which makes recommendation changes between versions easy
+//! to spot and lets traces be used as test snapshots.
+
+use crate::models::niche_profile::NicheProfile;
+use crate::models::product::Product;
+use crate::models::recommendation_trace::{EngagementTrace, PlatformTrace, RecommendationTrace, TraceInputs};
+use crate::services::ai_affiliate::{
+    mock_ai_discovery_with_platforms, price_tier_label, score_platforms, MIN_PLATFORM_SCORE,
+};
+use crate::services::analytics_service::ScoringSignals;
+use crate::services::category_keywords::CategoryAffinities;
+use crate::services::{analytics_service, audience, niche};
+use sha2::{Digest, Sha256};
+
+/// Audience scored against when neither the product nor the niche has one
+pub const DEFAULT_AUDIENCE: &str = "Age 25-45";
+const DEFAULT_PRICE_RANGE: &str = "$50-$100";
+const DEFAULT_TRENDING_SCORE: i32 = 50;
+/// Used when no platform clears the discovery cut-off
+const FALLBACK_PLATFORM: &str = "instagram";
+/// Audience match assumed when there is no ranked platform
+const FALLBACK_PLATFORM_BOOST: f64 = 0.5;
+
+/// The ad type recommended for a platform, and the rule that picked it
+pub fn ad_type_for_platform(platform: &str, category: &str) -> (&'static str, &'static str) {
+    match platform {
+        "tiktok" => ("video_script", "TikTok favors short video"),
+        "instagram" if category.contains("Fashion") || category.contains("Beauty") => {
+            ("carousel", "Instagram fashion and beauty favor carousels")
+        }
+        "instagram" => ("story", "Instagram favors stories outside fashion and beauty"),
+        "youtube" => ("video_script", "YouTube favors video"),
+        "pinterest" => ("pinterest_pin", "Pinterest favors pins"),
+        "facebook" => ("social_post", "Facebook favors social posts"),
+        _ => ("social_post", "Social posts are the default for other platforms"),
+    }
+}
+
+/// Engagement estimate: 60% trending score, 40% the top platform's audience match
+pub fn engagement(trending_score: i32, platform_boost: f64) -> EngagementTrace {
+    let base_engagement = (trending_score as f64) / 100.0;
+    EngagementTrace {
+        base_engagement,
+        platform_boost,
+        score: (base_engagement * 0.6 + platform_boost * 0.4).min(1.0),
+    }
+}
+
+/// Traces the recommendation for a product in the context of a niche profile
+pub fn explain(
+    product: &Product,
+    niche_profile: Option<&NicheProfile>,
+    affinities: &CategoryAffinities,
+    signals: &ScoringSignals,
+) -> RecommendationTrace {
+    let niche_audience =
+        niche::effective_audience(product.target_audience.as_deref().unwrap_or(""), niche_profile);
+    let target_audience = if niche_audience.is_empty() {
+        DEFAULT_AUDIENCE.to_string()
+    } else {
+        niche_audience
+    };
+    let trending_score = product.trending_score.unwrap_or(DEFAULT_TRENDING_SCORE);
+    let price_range = product.price_range.clone().unwrap_or_else(|| DEFAULT_PRICE_RANGE.to_string());
+    let category = product.category.as_str();
+
+    // The discovery ranking, biased toward the niche's preferred platforms
+    let programs = niche::apply_to_programs(
+        mock_ai_discovery_with_platforms(
+            &product.name,
+            category,
+            trending_score,
+            &target_audience,
+            &price_range,
+            affinities,
+        ),
+        niche_profile,
+    );
+
+    let mut platforms: Vec<PlatformTrace> = programs
+        .iter()
+        .enumerate()
+        .map(|(i, program)| PlatformTrace {
+            platform: program.platform.to_string(),
+            breakdown: program.match_breakdown.clone().unwrap_or_default(),
+            included: true,
+            rank: Some(i as i32 + 1),
+        })
+        .collect();
+    let profile = audience::parse(&target_audience);
+    let scored = score_platforms(category, trending_score, &profile, &price_range, affinities);
+    for (platform, _, breakdown) in scored {
+        if breakdown.score <= MIN_PLATFORM_SCORE {
+            platforms.push(PlatformTrace {
+                platform: platform.to_string(),
+                breakdown,
+                included: false,
+                rank: None,
+            });
+        }
+    }
+
+    let recommended_platform = programs
+        .first()
+        .map(|p| p.platform.to_string())
+        .unwrap_or_else(|| FALLBACK_PLATFORM.to_string());
+    let (recommended_ad_type, ad_type_rule) = ad_type_for_platform(&recommended_platform, category);
+    let platform_boost = programs
+        .first()
+        .map(|p| p.audience_match_score)
+        .unwrap_or(FALLBACK_PLATFORM_BOOST);
+
+    let mut trace = RecommendationTrace {
+        inputs: TraceInputs {
+            category: category.to_string(),
+            target_audience,
+            audience: profile,
+            price_tier: price_tier_label(&price_range).to_string(),
+            price_range,
+            trending_score,
+            trend_momentum: signals.momentum_per_week,
+            best_commission_rate: signals.best_commission_rate,
+            cookie_days: signals.cookie_days,
+            niche: niche_profile.map(|p| p.name.clone()),
+            preferred_platforms: niche_profile.map(|p| p.preferred_platforms.clone()).unwrap_or_default(),
+        },
+        platforms,
+        recommended_platform,
+        recommended_ad_type: recommended_ad_type.to_string(),
+        ad_type_rule: ad_type_rule.to_string(),
+        engagement: engagement(trending_score, platform_boost),
+        ad_type_factors: analytics_service::ad_type_factors(product, affinities, signals),
+        fingerprint: String::new(),
+    };
+    trace.fingerprint = fingerprint(&trace);
+    trace
+}
+
+/// First 16 hex digits of the SHA-256 of the trace (fingerprint left blank)
+fn fingerprint(trace: &RecommendationTrace) -> String {
+    let json = serde_json::to_string(trace).unwrap_or_default();
+    hex::encode(Sha256::digest(json.as_bytes()))[..16].to_string()
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product(category: &str, audience: Option<&str>, trending: Option<i32>) -> Product {
+        Product {
+            id: Some(1),
+            name: "Test Product".to_string(),
+            category: category.to_string(),
+            description: None,
+            price_range: Some("$20-$40".to_string()),
+            target_audience: audience.map(String::from),
+            trending_score: trending,
+            notes: None,
+            image_url: None,
+            amazon_asin: None,
+            tiktok_product_id: None,
+            instagram_product_id: None,
+            youtube_video_id: None,
+            pinterest_pin_id: None,
+            product_url: None,
+            created_at: None,
+            updated_at: None,
+            stage: None,
+            stage_changed_at: None,
+            tags: Vec::new(),
+            updated_by: None,
+            availability: None,
+            availability_checked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_trace_is_deterministic_and_complete() {
+        let p = product("Beauty & Skincare", Some("Ages 18-25"), Some(90));
+        let affinities = CategoryAffinities::defaults();
+        let signals = ScoringSignals::default();
+        let first = explain(&p, None, &affinities, &signals);
+        let second = explain(&p, None, &affinities, &signals);
+        assert_eq!(serde_json::to_string(&first).unwrap(), serde_json::to_string(&second).unwrap());
+        assert_eq!(first.fingerprint.len(), 16);
+
+        // Every candidate platform is accounted for, ranked ones first
+        assert_eq!(first.platforms.len(), 5);
+        assert_eq!(first.platforms[0].rank, Some(1));
+        assert_eq!(first.recommended_platform, first.platforms[0].platform);
+        assert_eq!(first.recommended_platform, "tiktok");
+        assert_eq!(first.recommended_ad_type, "video_script");
+        assert_eq!(first.ad_type_factors.len(), 6);
+        assert_eq!((first.inputs.audience.age_min, first.inputs.audience.age_max), (18, 25));
+        assert_eq!(first.inputs.price_tier, "low");
+
+        // Any change to the inputs shows up in the fingerprint
+        let older_product = product("Beauty & Skincare", Some("Ages 45-60"), Some(90));
+        let older = explain(&older_product, None, &affinities, &signals);
+        assert_ne!(first.fingerprint, older.fingerprint);
+        let rising_signals = ScoringSignals { momentum_per_week: Some(10.0), ..Default::default() };
+        let rising = explain(&p, None, &affinities, &rising_signals);
+        assert_eq!(rising.inputs.trend_momentum, Some(10.0));
+        assert_ne!(first.fingerprint, rising.fingerprint);
+    }
+}
@@ -0,0 +1,234 @@
+//! Product Research Briefs
+//!
+//! Asks the AI provider for a structured brief on a product (pain points,
+//! objections, key benefits, comparison targets, content angles). The latest
+//! brief per product is stored in `product_research` and used as context by
+//! ad generation. With the mock provider, or when every provider in the
+//! fallback chain fails, a category-based heuristic brief is stored instead.
+
+use crate::commands::ad_generation::generate_selling_points;
+use crate::models::ai_discovery::ParseDiagnostic;
+use crate::models::product::Product;
+use crate::models::research::{ProductResearch, ResearchBrief};
+use crate::services::ai_validation::validate_object;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+pub const RESEARCH_PROMPT: &str = r#"You are an expert product marketer. Research the following product and write a brief for an affiliate marketer creating ads for it.
+
+Product Information:
+- Name: {product_name}
+- Category: {category}
+- Description: {description}
+- Price Range: {price_range}
+- Target Audience: {target_audience}
+
+Niche Profile:
+{niche_context}
+
+Return ONLY a valid JSON object with this structure:
+{
+  "pain_points": ["Problems the target audience has that this product solves"],
+  "objections": ["Reasons a buyer might hesitate"],
+  "key_benefits": ["Concrete benefits, phrased for ad copy"],
+  "comparison_targets": ["Competing products or alternatives buyers compare against"],
+  "content_angles": ["Hooks or creative angles for short-form content"]
+}
+
+Rules:
+- 3 to 6 short entries per list
+- Never make claims the niche profile bans
+- NO explanatory text, ONLY the JSON object"#;
+
+/// Schema for the object requested by `RESEARCH_PROMPT`
+pub const RESEARCH_SCHEMA: &str = r##"{
+  "type": "object",
+  "required": ["pain_points", "objections", "key_benefits", "comparison_targets", "content_angles"],
+  "properties": {
+    "pain_points": { "$ref": "#/$defs/list" },
+    "objections": { "$ref": "#/$defs/list" },
+    "key_benefits": { "$ref": "#/$defs/list" },
+    "comparison_targets": { "$ref": "#/$defs/list" },
+    "content_angles": { "$ref": "#/$defs/list" }
+  },
+  "$defs": {
+    "list": {
+      "type": "array",
+      "minItems": 1,
+      "maxItems": 10,
+      "items": { "type": "string", "minLength": 1 }
+    }
+  }
+}"##;
+
+pub const RESEARCH_COLUMNS: &str = "id, product_id, pain_points, objections, key_benefits,
+     comparison_targets, content_angles, served_by, created_at";
+
+/// Maps a row selected with `RESEARCH_COLUMNS` into a `ProductResearch`
+pub fn research_from_row(row: &Row) -> rusqlite::Result<ProductResearch> {
+    let list = |idx: usize| -> rusqlite::Result<Vec<String>> {
+        let json: Option<String> = row.get(idx)?;
+        Ok(json.and_then(|j| serde_json::from_str(&j).ok()).unwrap_or_default())
+    };
+
+    Ok(ProductResearch {
+        id: row.get(0)?,
+        product_id: row.get(1)?,
+        brief: ResearchBrief {
+            pain_points: list(2)?,
+            objections: list(3)?,
+            key_benefits: list(4)?,
+            comparison_targets: list(5)?,
+            content_angles: list(6)?,
+        },
+        served_by: row.get(7)?,
+        created_at: row.get(8)?,
+    })
+}
+
+pub fn build_research_prompt(product: &Product, target_audience: &str, niche_context: &str) -> String {
+    RESEARCH_PROMPT
+        .replace("{product_name}", &product.name)
+        .replace("{category}", &product.category)
+        .replace("{description}", product.description.as_deref().unwrap_or(""))
+        .replace("{price_range}", product.price_range.as_deref().unwrap_or(""))
+        .replace("{target_audience}", target_audience)
+        .replace("{niche_context}", niche_context)
+}
+
+/// Extracts and schema-validates a research brief response
+pub fn validate_brief(response: &str) -> Result<ResearchBrief, ParseDiagnostic> {
+    validate_object(response, RESEARCH_SCHEMA)
+}
+
+/// Heuristic brief used with the mock provider and as the last fallback
+pub fn mock_research(product: &Product) -> ResearchBrief {
+    let name = &product.name;
+    let category = product.category.to_lowercase();
+
+    let pain_points = match product.category.as_str() {
+        "Beauty & Skincare" => vec![
+            "Dull, dehydrated skin that makeup can't hide".to_string(),
+            "Overwhelmed by 10-step routines".to_string(),
+            "Products that irritate sensitive skin".to_string(),
+        ],
+        "Health & Wellness" => vec![
+            "Low energy through the afternoon".to_string(),
+            "Hard to stay consistent with healthy habits".to_string(),
+            "Unsure which supplements actually help".to_string(),
+        ],
+        "Fitness & Recovery" => vec![
+            "Sore muscles slowing down training".to_string(),
+            "No time or space for a gym".to_string(),
+            "Plateaued progress".to_string(),
+        ],
+        "Consumer Electronics" | "Wearable Health Technology" => vec![
+            "Gadgets that don't work together".to_string(),
+            "Poor battery life".to_string(),
+            "No clear picture of personal health data".to_string(),
+        ],
+        "Fashion & Apparel" => vec![
+            "Nothing to wear despite a full closet".to_string(),
+            "Pieces that don't last past a season".to_string(),
+            "Hard to find a flattering fit".to_string(),
+        ],
+        "Home & Kitchen" => vec![
+            "Cluttered, hard-to-clean spaces".to_string(),
+            "Weeknight cooking takes too long".to_string(),
+            "Cheap tools that break".to_string(),
+        ],
+        _ => vec![
+            format!("Frustration with existing {} options", category),
+            "Not enough time to research the best choice".to_string(),
+        ],
+    };
+
+    ResearchBrief {
+        pain_points: pain_points.clone(),
+        objections: vec![
+            "Is it worth the price?".to_string(),
+            "Will it actually work for me?".to_string(),
+            "What if I don't like it?".to_string(),
+        ],
+        key_benefits: generate_selling_points(&product.category, name),
+        comparison_targets: vec![
+            format!("Budget {} alternatives", category),
+            format!("Premium brand-name {}", category),
+        ],
+        content_angles: vec![
+            format!(
+                "Problem-agitate-solve: {}",
+                pain_points.first().cloned().unwrap_or_default()
+            ),
+            format!("Social proof: why people switch to {}", name),
+            format!("Comparison: {} vs. the alternatives", name),
+            format!("Unboxing and first impressions of {}", name),
+        ],
+    }
+}
+
+/// Stores a brief for a product and returns the saved record
+pub fn save(
+    conn: &Connection,
+    product_id: i64,
+    brief: &ResearchBrief,
+    served_by: &str,
+) -> rusqlite::Result<ProductResearch> {
+    let json = |list: &Vec<String>| serde_json::to_string(list).unwrap_or_else(|_| "[]".to_string());
+
+    conn.execute(
+        "INSERT INTO product_research
+         (product_id, pain_points, objections, key_benefits, comparison_targets, content_angles, served_by)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            product_id,
+            json(&brief.pain_points),
+            json(&brief.objections),
+            json(&brief.key_benefits),
+            json(&brief.comparison_targets),
+            json(&brief.content_angles),
+            served_by,
+        ],
+    )?;
+
+    conn.query_row(
+        &format!("SELECT {} FROM product_research WHERE id = ?1", RESEARCH_COLUMNS),
+        params![conn.last_insert_rowid()],
+        research_from_row,
+    )
+}
+
+/// The most recent brief for a product, if it has been researched
+pub fn latest_for_product(conn: &Connection, product_id: i64) -> rusqlite::Result<Option<ProductResearch>> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM product_research WHERE product_id = ?1
+             ORDER BY created_at DESC, id DESC LIMIT 1",
+            RESEARCH_COLUMNS
+        ),
+        params![product_id],
+        research_from_row,
+    )
+    .optional()
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_brief() {
+        let response = r#"Sure! {"pain_points": ["Dry skin"], "objections": ["Price"],
+            "key_benefits": ["Hydrates all day"], "comparison_targets": ["CeraVe"],
+            "content_angles": ["Morning routine"]}"#;
+        let brief = validate_brief(response).unwrap();
+        assert_eq!(brief.comparison_targets, vec!["CeraVe".to_string()]);
+
+        let diagnostic = validate_brief(r#"{"pain_points": []}"#).unwrap_err();
+        assert_eq!(diagnostic.stage, "schema");
+        assert!(diagnostic.errors.iter().any(|e| e.starts_with("/pain_points")));
+    }
+}
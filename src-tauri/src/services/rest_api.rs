@@ -0,0 +1,529 @@
+//! Local REST API
+//!
+//! An opt-in, read-only HTTP API for power users: scripts, dashboards, or a
+//! phone on the LAN can query the workspace while the desktop app runs. It
+//! listens on 127.0.0.1 unless LAN access is enabled, and every request must
+//! carry the REST API token (separate from the browser extension's) as
+//! `Authorization: Bearer <token>` or an `X-AffilAI-Token` header.
+//!
+//! Endpoints (all `GET`, JSON responses):
+//! * `/v1/products?stage=&category=&limit=&offset=` - catalog products
+//! * `/v1/products/<id>` - one product with its active links and ad count
+//! * `/v1/links?product_id=&status=` - affiliate links
+//! * `/v1/ads?product_id=&ad_type=&limit=` - ad copy, newest first
+//! * `/v1/stats?days=` - workspace totals, clicks and conversions over `days`
+//!
+//! `/r/<link_id>?utm_source=` is the one public route: it logs a click (with
+//! its referrer and source) and redirects to the link's tracking URL, so
+//! shared links can point at this server to be counted.
+
+use crate::commands::ad_generation::{ad_copy_from_row, AD_COPY_COLUMNS};
+use crate::commands::affiliate_links::{link_from_row, LINK_COLUMNS};
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::database::get_connection;
+use crate::database::settings::{get_setting, get_setting_or, get_setting_parsed, set_setting};
+use crate::models::rest_api::RestApiStats;
+use crate::services::click_analytics::{record_click, ClickRecord};
+use crate::services::extension_api::{read_request, request_token, ApiRequest};
+use crate::services::{daily_metrics, geoip, read_only, roles};
+use crate::services::postback::parse_query;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::{IpAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tauri::AppHandle;
+
+pub const DEFAULT_REST_API_PORT: u16 = 8789;
+pub const ENABLED_SETTING: &str = "rest_api_enabled";
+pub const PORT_SETTING: &str = "rest_api_port";
+pub const TOKEN_SETTING: &str = "rest_api_token";
+pub const ALLOW_LAN_SETTING: &str = "rest_api_allow_lan";
+
+const DEFAULT_PAGE_SIZE: i64 = 100;
+const MAX_PAGE_SIZE: i64 = 500;
+const DEFAULT_STATS_DAYS: i64 = 30;
+
+// =============================================================================
+// CONFIGURATION
+// =============================================================================
+
+pub fn is_enabled(conn: &Connection) -> bool {
+    get_setting_or(conn, ENABLED_SETTING, "false") == "true"
+}
+
+pub fn allows_lan(conn: &Connection) -> bool {
+    get_setting_or(conn, ALLOW_LAN_SETTING, "false") == "true"
+}
+
+pub fn configured_port(conn: &Connection) -> u16 {
+    get_setting_parsed(conn, PORT_SETTING, DEFAULT_REST_API_PORT)
+}
+
+/// Returns the API token, generating and storing one on first use
+pub fn ensure_token(conn: &Connection) -> rusqlite::Result<String> {
+    match get_setting(conn, TOKEN_SETTING)? {
+        Some(token) if !token.is_empty() => Ok(token),
+        _ => regenerate_token(conn),
+    }
+}
+
+/// Replaces the API token, invalidating every client using the old one
+pub fn regenerate_token(conn: &Connection) -> rusqlite::Result<String> {
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    set_setting(conn, TOKEN_SETTING, &token)?;
+    Ok(token)
+}
+
+/// This machine's address on the local network, for LAN clients. Connecting a
+/// UDP socket only picks the outgoing interface; nothing is sent.
+pub fn lan_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind(("0.0.0.0", 0)).ok()?;
+    socket.connect(("8.8.8.8", 80)).ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip()).filter(|ip| !ip.is_loopback())
+}
+
+// =============================================================================
+// REQUEST HANDLING
+// =============================================================================
+
+/// Routes an authenticated request. Returns the HTTP status and JSON body.
+pub fn handle_request(conn: &Connection, request: &ApiRequest) -> (&'static str, Value) {
+    let expected = match ensure_token(conn) {
+        Ok(token) => token,
+        Err(e) => return ("500 Internal Server Error", json!({ "error": e.to_string() })),
+    };
+    if !request_token(request).is_some_and(|token| roles::constant_time_eq(token, &expected)) {
+        return ("401 Unauthorized", json!({ "error": "Invalid or missing token" }));
+    }
+    if request.method != "GET" {
+        return ("405 Method Not Allowed", json!({ "error": "The REST API is read-only" }));
+    }
+
+    let params: HashMap<String, String> = parse_query(&request.query).into_iter().collect();
+    let path = request.path.trim_end_matches('/');
+
+    let result = match path {
+        "/v1/products" => products(conn, &params),
+        "/v1/links" => links(conn, &params),
+        "/v1/ads" => ads(conn, &params),
+        "/v1/stats" => stats(conn, &params),
+        _ => match path.strip_prefix("/v1/products/") {
+            Some(id) => match id.parse::<i64>() {
+                Ok(id) => match product_detail(conn, id) {
+                    Ok(Some(body)) => Ok(body),
+                    Ok(None) => return ("404 Not Found", json!({ "error": "Product not found" })),
+                    Err(e) => Err(e),
+                },
+                Err(_) => Err(format!("Invalid product id: {}", id)),
+            },
+            None => return ("404 Not Found", json!({ "error": "Not found" })),
+        },
+    };
+
+    match result {
+        Ok(body) => ("200 OK", body),
+        Err(e) => ("400 Bad Request", json!({ "error": e })),
+    }
+}
+
+/// Logs a click on `/r/<link_id>` and returns the URL to redirect to, or
+/// `None` for an unknown link. Visitor IPs are stored hashed, after an
+/// optional GeoIP lookup. Nothing is logged while the workspace is read-only.
+pub fn track_redirect(
+    conn: &Connection,
+    request: &ApiRequest,
+    link_id: &str,
+    peer: Option<IpAddr>,
+) -> Result<Option<String>, String> {
+    let link_id: i64 = link_id.parse().map_err(|_| format!("Invalid link id: {}", link_id))?;
+    let target: Option<String> = conn
+        .query_row(
+            "SELECT tracking_url FROM affiliate_links WHERE id = ?1",
+            params![link_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let Some(target) = target else {
+        return Ok(None);
+    };
+    // Visitors still get where they're going; the click just isn't counted
+    if read_only::is_enabled(conn) {
+        return Ok(Some(target));
+    }
+
+    let params: HashMap<String, String> = parse_query(&request.query).into_iter().collect();
+    let visitor = visitor_ip(request, peer);
+    let location = visitor
+        .as_deref()
+        .and_then(|ip| ip.parse::<IpAddr>().ok())
+        .and_then(|ip| geoip::lookup(conn, ip));
+    let ip_hash = visitor.map(|ip| hex::encode(Sha256::digest(ip.as_bytes()))[..16].to_string());
+
+    record_click(
+        conn,
+        &ClickRecord {
+            link_id,
+            campaign_id: int_param(&params, "campaign_id")?,
+            ip_hash: ip_hash.as_deref(),
+            user_agent: request.headers.get("user-agent").map(String::as_str),
+            referrer: request.headers.get("referer").map(String::as_str),
+            utm_source: params.get("utm_source").map(String::as_str),
+            country: location.as_ref().map(|l| l.country.as_str()),
+            region: location.as_ref().and_then(|l| l.region.as_deref()),
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(Some(target))
+}
+
+/// The visitor's address. Behind a tunnel, connections arrive from loopback and
+/// the visitor is the first X-Forwarded-For hop; anyone else could set that
+/// header to pick their own address, so it's ignored for other peers.
+fn visitor_ip(request: &ApiRequest, peer: Option<IpAddr>) -> Option<String> {
+    request
+        .headers
+        .get("x-forwarded-for")
+        .filter(|_| peer.is_some_and(|ip| ip.is_loopback()))
+        .and_then(|v| v.split(',').next())
+        .map(|v| v.trim().to_string())
+        .or_else(|| peer.map(|ip| ip.to_string()))
+}
+
+fn int_param(params: &HashMap<String, String>, name: &str) -> Result<Option<i64>, String> {
+    match params.get(name).filter(|v| !v.is_empty()) {
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("Invalid {}: {}", name, value)),
+        None => Ok(None),
+    }
+}
+
+fn text_param<'a>(params: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    params.get(name).map(|v| v.trim()).filter(|v| !v.is_empty())
+}
+
+/// `limit` clamped to 1..=MAX_PAGE_SIZE
+fn page_size(params: &HashMap<String, String>) -> Result<i64, String> {
+    Ok(int_param(params, "limit")?
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE))
+}
+
+fn products(conn: &Connection, params: &HashMap<String, String>) -> Result<Value, String> {
+    let mut conditions = vec!["1 = 1".to_string()];
+    let mut values: Vec<SqlValue> = Vec::new();
+    if let Some(stage) = text_param(params, "stage") {
+        values.push(SqlValue::Text(stage.to_string()));
+        conditions.push(format!("stage = ?{}", values.len()));
+    }
+    if let Some(category) = text_param(params, "category") {
+        values.push(SqlValue::Text(category.to_string()));
+        conditions.push(format!("category = ?{} COLLATE NOCASE", values.len()));
+    }
+    let limit = page_size(params)?;
+    let offset = int_param(params, "offset")?.unwrap_or(0).max(0);
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM products WHERE {} ORDER BY id LIMIT {} OFFSET {}",
+            PRODUCT_COLUMNS,
+            conditions.join(" AND "),
+            limit,
+            offset
+        ))
+        .map_err(|e| e.to_string())?;
+    let products = stmt
+        .query_map(params_from_iter(values), product_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(json!({ "products": products, "limit": limit, "offset": offset }))
+}
+
+fn product_detail(conn: &Connection, product_id: i64) -> Result<Option<Value>, String> {
+    let product = conn
+        .query_row(
+            &format!("SELECT {} FROM products WHERE id = ?1", PRODUCT_COLUMNS),
+            params![product_id],
+            product_from_row,
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let product = match product {
+        Some(product) => product,
+        None => return Ok(None),
+    };
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM affiliate_links WHERE product_id = ?1 AND status = 'active'
+             ORDER BY created_at DESC",
+            LINK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let links = stmt
+        .query_map(params![product_id], link_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    let ad_count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM ad_copies WHERE product_id = ?1",
+            params![product_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(Some(json!({ "product": product, "links": links, "adCount": ad_count })))
+}
+
+fn links(conn: &Connection, params: &HashMap<String, String>) -> Result<Value, String> {
+    let product_id = int_param(params, "product_id")?;
+    let status = text_param(params, "status");
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM affiliate_links
+             WHERE (?1 IS NULL OR product_id = ?1) AND (?2 IS NULL OR status = ?2)
+             ORDER BY created_at DESC",
+            LINK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let links = stmt
+        .query_map(params![product_id, status], link_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(json!({ "links": links }))
+}
+
+fn ads(conn: &Connection, params: &HashMap<String, String>) -> Result<Value, String> {
+    let product_id = int_param(params, "product_id")?;
+    let ad_type = text_param(params, "ad_type");
+    let limit = page_size(params)?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM ad_copies
+             WHERE (?1 IS NULL OR product_id = ?1) AND (?2 IS NULL OR ad_type = ?2)
+             ORDER BY created_at DESC, id DESC LIMIT ?3",
+            AD_COPY_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let ads = stmt
+        .query_map(params![product_id, ad_type, limit], ad_copy_from_row)
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(json!({ "ads": ads, "limit": limit }))
+}
+
+fn stats(conn: &Connection, params: &HashMap<String, String>) -> Result<Value, String> {
+    let days = int_param(params, "days")?.unwrap_or(DEFAULT_STATS_DAYS).max(1);
+    let stats = workspace_stats(conn, days).map_err(|e| e.to_string())?;
+    serde_json::to_value(stats).map_err(|e| e.to_string())
+}
+
+/// Catalog totals plus clicks and conversions over the last `days` days
+/// (today included)
+pub fn workspace_stats(conn: &Connection, days: i64) -> rusqlite::Result<RestApiStats> {
+    let today = chrono::Utc::now().date_naive();
+    let metrics = daily_metrics::totals(conn, today - chrono::Duration::days(days - 1), today)?;
+    conn.query_row(
+        "SELECT
+            (SELECT COUNT(*) FROM products),
+            (SELECT COUNT(*) FROM affiliate_links WHERE status = 'active'),
+            (SELECT COUNT(*) FROM ad_copies)",
+        [],
+        |row| {
+            Ok(RestApiStats {
+                days,
+                products: row.get(0)?,
+                active_links: row.get(1)?,
+                ads: row.get(2)?,
+                clicks: metrics.clicks,
+                conversions: metrics.conversions,
+                revenue: metrics.revenue,
+                commission: metrics.commission,
+            })
+        },
+    )
+}
+
+// =============================================================================
+// HTTP LISTENER
+// =============================================================================
+
+struct ServerHandle {
+    port: u16,
+    allow_lan: bool,
+    stop: Arc<AtomicBool>,
+}
+
+fn server_slot() -> &'static Mutex<Option<ServerHandle>> {
+    static SLOT: OnceLock<Mutex<Option<ServerHandle>>> = OnceLock::new();
+    SLOT.get_or_init(|| Mutex::new(None))
+}
+
+/// Returns the port of the running REST server, if any
+pub fn running_port() -> Option<u16> {
+    server_slot().lock().ok()?.as_ref().map(|h| h.port)
+}
+
+/// Starts the REST server on `port`, on all interfaces when `allow_lan` is
+/// set and on 127.0.0.1 otherwise. Restarts it if the binding changed.
+pub fn start_server(app_handle: AppHandle, port: u16, allow_lan: bool) -> Result<(), String> {
+    let mut slot = server_slot().lock().map_err(|e| e.to_string())?;
+
+    if let Some(handle) = slot.as_ref() {
+        if handle.port == port && handle.allow_lan == allow_lan {
+            return Ok(());
+        }
+        handle.stop.store(true, Ordering::SeqCst);
+        *slot = None;
+    }
+
+    let host = if allow_lan { "0.0.0.0" } else { "127.0.0.1" };
+    let listener = TcpListener::bind((host, port))
+        .map_err(|e| format!("Failed to bind REST API on {}:{}: {}", host, port, e))?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = stop.clone();
+
+    std::thread::spawn(move || {
+        while !stop_flag.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    if let Err(e) = serve_connection(&app_handle, stream) {
+                        eprintln!("REST API request failed: {}", e);
+                    }
+                }
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+                Err(e) => eprintln!("REST API listener error: {}", e),
+            }
+        }
+    });
+
+    *slot = Some(ServerHandle { port, allow_lan, stop });
+    println!("✓ REST API started on {}:{}", host, port);
+    Ok(())
+}
+
+/// Stops the REST server if it is running
+pub fn stop_server() {
+    if let Ok(mut slot) = server_slot().lock() {
+        if let Some(handle) = slot.take() {
+            handle.stop.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+fn serve_connection(app_handle: &AppHandle, stream: TcpStream) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    let request = read_request(&stream)?;
+    let mut stream = stream;
+
+    if let Some(link_id) = request.path.strip_prefix("/r/").filter(|_| request.method == "GET") {
+        let peer = stream.peer_addr().ok().map(|addr| addr.ip());
+        let outcome = get_connection(app_handle)
+            .map_err(|e| e.to_string())
+            .and_then(|conn| track_redirect(&conn, &request, link_id.trim_end_matches('/'), peer));
+        return match outcome {
+            Ok(Some(location)) => {
+                write!(
+                    stream,
+                    "HTTP/1.1 302 Found\r\nLocation: {}\r\nCache-Control: no-store\r\n\
+                     Content-Length: 0\r\nConnection: close\r\n\r\n",
+                    location
+                )?;
+                stream.flush()
+            }
+            Ok(None) => write_plain(&mut stream, "404 Not Found", "Link not found"),
+            Err(e) => write_plain(&mut stream, "400 Bad Request", &e),
+        };
+    }
+
+    let (status, body) = if request.method == "OPTIONS" {
+        // CORS preflight from browser dashboards
+        ("204 No Content", String::new())
+    } else {
+        let (status, value) = match get_connection(app_handle) {
+            Ok(conn) => handle_request(&conn, &request),
+            Err(e) => ("500 Internal Server Error", json!({ "error": e.to_string() })),
+        };
+        (status, value.to_string())
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Headers: Authorization, X-AffilAI-Token\r\n\
+         Access-Control-Allow-Methods: GET, OPTIONS\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn write_plain(stream: &mut TcpStream, status: &str, body: &str) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_size_is_clamped() {
+        let params = |limit: &str| -> HashMap<String, String> {
+            [("limit".to_string(), limit.to_string())].into_iter().collect()
+        };
+        assert_eq!(page_size(&HashMap::new()), Ok(DEFAULT_PAGE_SIZE));
+        assert_eq!(page_size(&params("10000")), Ok(MAX_PAGE_SIZE));
+        assert_eq!(page_size(&params("0")), Ok(1));
+        assert!(page_size(&params("ten")).is_err());
+    }
+
+    #[test]
+    fn test_forwarded_for_is_only_trusted_from_the_tunnel() {
+        let request = ApiRequest {
+            headers: [("x-forwarded-for".to_string(), "203.0.113.7, 10.0.0.1".to_string())].into_iter().collect(),
+            ..Default::default()
+        };
+        let tunnel: IpAddr = "127.0.0.1".parse().unwrap();
+        let lan: IpAddr = "192.168.1.20".parse().unwrap();
+        assert_eq!(visitor_ip(&request, Some(tunnel)).as_deref(), Some("203.0.113.7"));
+        assert_eq!(visitor_ip(&request, Some(lan)).as_deref(), Some("192.168.1.20"));
+        assert_eq!(visitor_ip(&request, None), None);
+    }
+}
@@ -0,0 +1,370 @@
+//! Retail Events
+//!
+//! Holidays and shopping events (Valentine's Day, Prime Day, Black Friday,
+//! ...) with the product keywords they sell. Upcoming events are shown on the
+//! posting calendar, turned into campaign suggestions for matching products,
+//! and give products a seasonality boost while their event's promotion window
+//! is open.
+
+use crate::commands::products::{product_from_row, PRODUCT_COLUMNS};
+use crate::models::product::Product;
+use crate::models::retail_event::{CampaignSuggestion, RetailEvent, SaveRetailEventInput, UpcomingEvent};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use rusqlite::{params, Connection, Row};
+
+pub const EVENT_COLUMNS: &str =
+    "id, name, month, day, weekday, week_of_month, offset_days, lead_days, categories, enabled, builtin";
+
+/// Products suggested per campaign
+pub const MAX_CAMPAIGN_PRODUCTS: usize = 10;
+
+const WEEKDAYS: [Weekday; 7] =
+    [Weekday::Sun, Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat];
+
+/// Maps a row selected with `EVENT_COLUMNS` into a `RetailEvent`
+pub fn event_from_row(row: &Row) -> rusqlite::Result<RetailEvent> {
+    Ok(RetailEvent {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        month: row.get(2)?,
+        day: row.get(3)?,
+        weekday: row.get(4)?,
+        week_of_month: row.get(5)?,
+        offset_days: row.get(6)?,
+        lead_days: row.get(7)?,
+        categories: row
+            .get::<_, Option<String>>(8)?
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default(),
+        enabled: row.get::<_, Option<bool>>(9)?.unwrap_or(true),
+        builtin: row.get::<_, Option<bool>>(10)?.unwrap_or(false),
+    })
+}
+
+pub fn list(conn: &Connection) -> rusqlite::Result<Vec<RetailEvent>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM retail_events ORDER BY month, COALESCE(day, 0), id",
+        EVENT_COLUMNS
+    ))?;
+    let events = stmt.query_map([], event_from_row)?.collect();
+    events
+}
+
+pub fn get(conn: &Connection, event_id: i64) -> rusqlite::Result<RetailEvent> {
+    conn.query_row(
+        &format!("SELECT {} FROM retail_events WHERE id = ?1", EVENT_COLUMNS),
+        params![event_id],
+        event_from_row,
+    )
+}
+
+pub fn validate(input: &SaveRetailEventInput) -> Result<(), String> {
+    if input.name.trim().is_empty() {
+        return Err("Event name is required".to_string());
+    }
+    if !(1..=12).contains(&input.month) {
+        return Err(format!("Invalid month {}; expected 1-12", input.month));
+    }
+    match (input.day, input.weekday, input.week_of_month) {
+        // Checked against a leap year so Feb 29 is allowed
+        (Some(day), _, _) if NaiveDate::from_ymd_opt(2028, input.month, day).is_none() => {
+            Err(format!("Month {} has no day {}", input.month, day))
+        }
+        (Some(_), _, _) => Ok(()),
+        (None, Some(weekday), Some(week)) if weekday <= 6 && ((1..=5).contains(&week) || week == -1) => Ok(()),
+        _ => Err(
+            "Set a day of the month, or a weekday (0 = Sunday) and week of the month (1-5, or -1 for the last)"
+                .to_string(),
+        ),
+    }?;
+    if input.lead_days.is_some_and(|d| d < 0) {
+        return Err("Lead days cannot be negative".to_string());
+    }
+    Ok(())
+}
+
+/// Creates or updates an event and returns the saved row
+pub fn save(conn: &Connection, input: &SaveRetailEventInput) -> Result<RetailEvent, String> {
+    let categories: Vec<String> = input
+        .categories
+        .iter()
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty())
+        .collect();
+    let categories = serde_json::to_string(&categories).map_err(|e| e.to_string())?;
+    // A fixed day takes precedence over the weekday rule
+    let (weekday, week_of_month) =
+        if input.day.is_some() { (None, None) } else { (input.weekday, input.week_of_month) };
+    let (offset_days, lead_days) = (input.offset_days.unwrap_or(0), input.lead_days.unwrap_or(21));
+    let enabled = input.enabled.unwrap_or(true);
+
+    let id = match input.id {
+        Some(id) => {
+            let updated = conn
+                .execute(
+                    "UPDATE retail_events SET name = ?1, month = ?2, day = ?3, weekday = ?4, week_of_month = ?5,
+                     offset_days = ?6, lead_days = ?7, categories = ?8, enabled = ?9,
+                     updated_at = CURRENT_TIMESTAMP
+                     WHERE id = ?10",
+                    params![
+                        input.name.trim(),
+                        input.month,
+                        input.day,
+                        weekday,
+                        week_of_month,
+                        offset_days,
+                        lead_days,
+                        categories,
+                        enabled,
+                        id
+                    ],
+                )
+                .map_err(|e| e.to_string())?;
+            if updated == 0 {
+                return Err(format!("Retail event {} not found", id));
+            }
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO retail_events (name, month, day, weekday, week_of_month, offset_days, lead_days,
+                 categories, enabled)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    input.name.trim(),
+                    input.month,
+                    input.day,
+                    weekday,
+                    week_of_month,
+                    offset_days,
+                    lead_days,
+                    categories,
+                    enabled
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+            conn.last_insert_rowid()
+        }
+    };
+    get(conn, id).map_err(|e| e.to_string())
+}
+
+/// The date `event` falls on in `year`
+pub fn occurrence(event: &RetailEvent, year: i32) -> Option<NaiveDate> {
+    let date = match (event.day, event.weekday, event.week_of_month) {
+        (Some(day), _, _) => NaiveDate::from_ymd_opt(year, event.month, day)?,
+        (None, Some(weekday), Some(-1)) => {
+            let weekday = *WEEKDAYS.get(weekday as usize)?;
+            (1..=5).rev().find_map(|n| NaiveDate::from_weekday_of_month_opt(year, event.month, weekday, n))?
+        }
+        (None, Some(weekday), Some(week)) => {
+            let week = u8::try_from(week).ok()?;
+            NaiveDate::from_weekday_of_month_opt(year, event.month, *WEEKDAYS.get(weekday as usize)?, week)?
+        }
+        _ => return None,
+    };
+    Some(date + Duration::days(event.offset_days))
+}
+
+/// The event's next date on or after `today`, with its promotion window
+pub fn next_occurrence(event: &RetailEvent, today: NaiveDate) -> Option<UpcomingEvent> {
+    let date = (today.year()..=today.year() + 1)
+        .filter_map(|year| occurrence(event, year))
+        .find(|date| *date >= today)?;
+    let promote_from = (date - Duration::days(event.lead_days)).max(today);
+    Some(UpcomingEvent {
+        event: event.clone(),
+        date: date.format("%Y-%m-%d").to_string(),
+        days_until: (date - today).num_days(),
+        promote_from: promote_from.format("%Y-%m-%d").to_string(),
+    })
+}
+
+/// Enabled events in the next `days` days, soonest first
+pub fn upcoming(events: &[RetailEvent], today: NaiveDate, days: i64) -> Vec<UpcomingEvent> {
+    let mut upcoming: Vec<UpcomingEvent> = events
+        .iter()
+        .filter(|e| e.enabled)
+        .filter_map(|e| next_occurrence(e, today))
+        .filter(|u| u.days_until <= days)
+        .collect();
+    upcoming.sort_by_key(|u| u.days_until);
+    upcoming
+}
+
+/// Whether one of the event's keywords appears in the product's category or tags
+pub fn matches(event: &RetailEvent, product: &Product) -> bool {
+    let category = product.category.to_lowercase();
+    event.categories.iter().any(|keyword| {
+        category.contains(keyword.as_str()) || product.tags.iter().any(|t| t.contains(keyword.as_str()))
+    })
+}
+
+/// How seasonal a product is right now (0-1) and the event responsible: full
+/// strength inside an event's promotion window, fading out over the same
+/// number of days before it opens
+pub fn seasonality(events: &[RetailEvent], product: &Product, today: NaiveDate) -> Option<(f64, UpcomingEvent)> {
+    events
+        .iter()
+        .filter(|e| e.enabled && matches(e, product))
+        .filter_map(|e| next_occurrence(e, today))
+        .filter_map(|u| {
+            let lead = u.event.lead_days.max(1);
+            let score = if u.days_until <= lead {
+                1.0
+            } else {
+                1.0 - (u.days_until - lead) as f64 / lead as f64
+            };
+            (score > 0.0).then_some((score, u))
+        })
+        .max_by(|a, b| {
+            let by_score = a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal);
+            by_score.then(b.1.days_until.cmp(&a.1.days_until)) // Ties go to the sooner event
+        })
+}
+
+/// Products that aren't retired, highest trending first
+pub fn active_products(conn: &Connection) -> rusqlite::Result<Vec<Product>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM products WHERE COALESCE(stage, '') != 'retired'
+         ORDER BY COALESCE(trending_score, 0) DESC, id",
+        PRODUCT_COLUMNS
+    ))?;
+    let products = stmt.query_map([], product_from_row)?.collect();
+    products
+}
+
+/// One suggestion per event in the next `days` days that has matching products
+pub fn campaign_suggestions(
+    conn: &Connection,
+    today: NaiveDate,
+    days: i64,
+) -> rusqlite::Result<Vec<CampaignSuggestion>> {
+    let events = list(conn)?;
+    let products = active_products(conn)?;
+
+    let mut suggestions = Vec::new();
+    for event in upcoming(&events, today, days) {
+        let matched: Vec<&Product> = products
+            .iter()
+            .filter(|p| matches(&event.event, p))
+            .take(MAX_CAMPAIGN_PRODUCTS)
+            .collect();
+        if matched.is_empty() {
+            continue;
+        }
+
+        let mut categories: Vec<String> = Vec::new();
+        for product in &matched {
+            if !categories.iter().any(|c| c.eq_ignore_ascii_case(&product.category)) {
+                categories.push(product.category.clone());
+            }
+        }
+        let when = match event.days_until {
+            0 => "today".to_string(),
+            1 => "tomorrow".to_string(),
+            n => format!("in {} days", n),
+        };
+        let message = format!(
+            "{} {} — {} products to promote",
+            event.event.name,
+            when,
+            categories.iter().take(3).cloned().collect::<Vec<_>>().join(" & ")
+        );
+
+        suggestions.push(CampaignSuggestion {
+            categories,
+            product_ids: matched.iter().filter_map(|p| p.id).collect(),
+            product_names: matched.iter().map(|p| p.name.clone()).collect(),
+            message,
+            event,
+        });
+    }
+    Ok(suggestions)
+}
+
+/// Days to post a campaign of `count` posts on: spread evenly from
+/// `promote_from` up to the day before the event (or the event day itself)
+pub fn campaign_days(event: &UpcomingEvent, count: usize) -> Vec<NaiveDate> {
+    let (Ok(start), Ok(date)) = (
+        NaiveDate::parse_from_str(&event.promote_from, "%Y-%m-%d"),
+        NaiveDate::parse_from_str(&event.date, "%Y-%m-%d"),
+    ) else {
+        return Vec::new();
+    };
+    let last = (date - Duration::days(1)).max(start);
+    let span = (last - start).num_days();
+    (0..count)
+        .map(|i| if count > 1 { start + Duration::days(span * i as i64 / (count as i64 - 1)) } else { last })
+        .collect()
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &str, month: u32, day: Option<u32>, rule: Option<(u32, i32)>, offset_days: i64) -> RetailEvent {
+        RetailEvent {
+            id: 1,
+            name: name.to_string(),
+            month,
+            day,
+            weekday: rule.map(|r| r.0),
+            week_of_month: rule.map(|r| r.1),
+            offset_days,
+            lead_days: 30,
+            categories: vec!["beauty".to_string()],
+            enabled: true,
+            builtin: true,
+        }
+    }
+
+    #[test]
+    fn test_event_dates_and_seasonality() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let black_friday = event("Black Friday", 11, None, Some((4, 4)), 1);
+        let memorial_day = event("Memorial Day", 5, None, Some((1, -1)), 0);
+        assert_eq!(occurrence(&black_friday, 2026), Some(date("2026-11-27")));
+        assert_eq!(occurrence(&memorial_day, 2026), Some(date("2026-05-25")));
+
+        let valentines = event("Valentine's Day", 2, Some(14), None, 0);
+        let next = next_occurrence(&valentines, date("2026-03-01")).unwrap();
+        assert_eq!((next.date.as_str(), next.promote_from.as_str()), ("2027-02-14", "2027-01-15"));
+
+        let mut product = Product {
+            id: Some(1),
+            name: "Serum".to_string(),
+            category: "Beauty & Skincare".to_string(),
+            description: None,
+            price_range: None,
+            target_audience: None,
+            trending_score: None,
+            notes: None,
+            image_url: None,
+            amazon_asin: None,
+            tiktok_product_id: None,
+            instagram_product_id: None,
+            youtube_video_id: None,
+            pinterest_pin_id: None,
+            product_url: None,
+            created_at: None,
+            updated_at: None,
+            stage: None,
+            stage_changed_at: None,
+            tags: Vec::new(),
+            updated_by: None,
+            availability: None,
+            availability_checked_at: None,
+        };
+        let events = [valentines];
+        assert_eq!(seasonality(&events, &product, date("2026-01-20")).unwrap().0, 1.0);
+        assert_eq!(seasonality(&events, &product, date("2025-12-31")).unwrap().0, 0.5);
+        assert!(seasonality(&events, &product, date("2025-11-01")).is_none());
+        product.category = "Kitchen".to_string();
+        assert!(seasonality(&events, &product, date("2026-01-20")).is_none());
+    }
+}
@@ -0,0 +1,162 @@
+//! Data Retention
+//!
+//! Keeps the click and log tables from growing without bound. Raw clicks
+//! older than the retention window are folded into `click_rollups` (one row
+//! per link per day) and deleted; postback and webhook delivery logs past
+//! their window are deleted outright, as are expired AI cache entries. Runs
+//! daily from the scheduler; thresholds live in settings.
+
+use crate::database::get_connection;
+use crate::database::settings::{get_setting_parsed, set_setting};
+use crate::models::maintenance::{RetentionPolicy, RetentionReport};
+use chrono::{Duration, NaiveDateTime, Utc};
+use rusqlite::{params, Connection};
+use tauri::AppHandle;
+
+const CLICK_DAYS_SETTING: &str = "retention_click_days";
+const LOG_DAYS_SETTING: &str = "retention_log_days";
+const DEFAULT_CLICK_DAYS: i64 = 365;
+const DEFAULT_LOG_DAYS: i64 = 90;
+
+pub fn load_policy(conn: &Connection) -> RetentionPolicy {
+    RetentionPolicy {
+        click_days: get_setting_parsed(conn, CLICK_DAYS_SETTING, DEFAULT_CLICK_DAYS),
+        log_days: get_setting_parsed(conn, LOG_DAYS_SETTING, DEFAULT_LOG_DAYS),
+    }
+}
+
+pub fn save_policy(conn: &Connection, policy: &RetentionPolicy) -> Result<RetentionPolicy, String> {
+    if policy.click_days < 0 || policy.log_days < 0 {
+        return Err("Retention days can't be negative (use 0 to keep data forever)".to_string());
+    }
+    set_setting(conn, CLICK_DAYS_SETTING, &policy.click_days.to_string()).map_err(|e| e.to_string())?;
+    set_setting(conn, LOG_DAYS_SETTING, &policy.log_days.to_string()).map_err(|e| e.to_string())?;
+    Ok(load_policy(conn))
+}
+
+/// Start of the oldest day still kept. Cutting on a day boundary means a
+/// day is always rolled up whole, so its unique visitor count stays exact.
+fn cutoff(now: NaiveDateTime, days: i64) -> String {
+    (now - Duration::days(days)).format("%Y-%m-%d 00:00:00").to_string()
+}
+
+/// Applies the policy in one transaction
+pub fn run(
+    conn: &mut Connection,
+    policy: &RetentionPolicy,
+    now: NaiveDateTime,
+) -> rusqlite::Result<RetentionReport> {
+    let tx = conn.transaction()?;
+    let mut report = RetentionReport::default();
+
+    if policy.click_days > 0 {
+        let before = cutoff(now, policy.click_days);
+        report.rollup_days = tx.execute(
+            "INSERT INTO click_rollups (day, link_id, clicks, bot_clicks, unique_visitors)
+             SELECT date(clicked_at), link_id,
+                    SUM(bot_reason IS NULL), SUM(bot_reason IS NOT NULL), COUNT(DISTINCT ip_hash)
+             FROM click_events WHERE clicked_at < ?1
+             GROUP BY date(clicked_at), link_id
+             ON CONFLICT(day, link_id) DO UPDATE SET
+                clicks = clicks + excluded.clicks,
+                bot_clicks = bot_clicks + excluded.bot_clicks,
+                unique_visitors = unique_visitors + excluded.unique_visitors",
+            params![before],
+        )? as i64;
+        report.clicks_rolled_up =
+            tx.execute("DELETE FROM click_events WHERE clicked_at < ?1", params![before])? as i64;
+    }
+
+    if policy.log_days > 0 {
+        let before = cutoff(now, policy.log_days);
+        report.postbacks_deleted =
+            tx.execute("DELETE FROM postback_log WHERE received_at < ?1", params![before])? as i64;
+        report.webhook_deliveries_deleted =
+            tx.execute("DELETE FROM webhook_deliveries WHERE delivered_at < ?1", params![before])? as i64;
+    }
+
+    report.ai_cache_expired = tx.execute(
+        "DELETE FROM ai_cache WHERE expires_at < ?1",
+        params![now.format("%Y-%m-%d %H:%M:%S").to_string()],
+    )? as i64;
+
+    tx.commit()?;
+    Ok(report)
+}
+
+/// Scheduler entry point
+pub fn run_retention_job(app_handle: &AppHandle) -> Result<String, String> {
+    let mut conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    let policy = load_policy(&conn);
+    let report = run(&mut conn, &policy, Utc::now().naive_utc()).map_err(|e| e.to_string())?;
+    Ok(format!(
+        "{} click(s) rolled up, {} log row(s) and {} expired AI response(s) removed",
+        report.clicks_rolled_up,
+        report.postbacks_deleted + report.webhook_deliveries_deleted,
+        report.ai_cache_expired
+    ))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::parse_timestamp;
+
+    #[test]
+    fn test_old_clicks_fold_into_daily_rollups() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE affiliate_links (id INTEGER PRIMARY KEY);
+             CREATE TABLE click_events (id INTEGER PRIMARY KEY, link_id INTEGER, clicked_at TEXT,
+                ip_hash TEXT, bot_reason TEXT);
+             CREATE TABLE postback_log (id INTEGER PRIMARY KEY, received_at TEXT);
+             CREATE TABLE webhook_deliveries (id INTEGER PRIMARY KEY, delivered_at TEXT);
+             CREATE TABLE ai_cache (cache_key TEXT PRIMARY KEY, expires_at TEXT);
+             INSERT INTO affiliate_links VALUES (1), (2);
+             INSERT INTO click_events (link_id, clicked_at, ip_hash, bot_reason) VALUES
+                (1, '2025-01-10 09:00:00', 'a', NULL),
+                (1, '2025-01-10 17:00:00', 'a', NULL),
+                (1, '2025-01-10 18:00:00', 'b', 'datacenter'),
+                (2, '2025-01-11 08:00:00', 'c', NULL),
+                (1, '2026-02-01 08:00:00', 'a', NULL);
+             INSERT INTO postback_log (received_at) VALUES ('2025-06-01 00:00:00'), ('2026-02-01 00:00:00');
+             INSERT INTO ai_cache VALUES ('old', '2026-01-01 00:00:00'), ('fresh', '2026-03-01 00:00:00');",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../../migrations/041_data_retention.sql")).unwrap();
+        conn.execute(
+            "INSERT INTO click_rollups (day, link_id, clicks, bot_clicks, unique_visitors)
+             VALUES ('2025-01-10', 1, 5, 0, 2)",
+            [],
+        )
+        .unwrap();
+
+        let now = parse_timestamp("2026-02-15 12:00:00").unwrap();
+        let policy = RetentionPolicy { click_days: 365, log_days: 90 };
+        let report = run(&mut conn, &policy, now).unwrap();
+
+        assert_eq!(report.clicks_rolled_up, 4);
+        assert_eq!(report.rollup_days, 2);
+        assert_eq!(report.postbacks_deleted, 1);
+        assert_eq!(report.ai_cache_expired, 1);
+
+        let day: (i64, i64, i64) = conn
+            .query_row(
+                "SELECT clicks, bot_clicks, unique_visitors FROM click_rollups WHERE day = '2025-01-10'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(day, (7, 1, 4));
+        let remaining: i64 = conn.query_row("SELECT COUNT(*) FROM click_events", [], |r| r.get(0)).unwrap();
+        assert_eq!(remaining, 1);
+
+        // Zero keeps everything
+        let keep = RetentionPolicy { click_days: 0, log_days: 0 };
+        assert_eq!(run(&mut conn, &keep, now).unwrap().clicks_rolled_up, 0);
+    }
+}
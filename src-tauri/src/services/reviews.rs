@@ -0,0 +1,341 @@
+//! Review Summarization
+//!
+//! Distills customer reviews (pasted by the user or scraped from the product
+//! page's JSON-LD) into quotable social-proof snippets and the objections
+//! buyers raise most often. Snippets must appear verbatim in one of the
+//! reviews, so a model can't put invented praise in a customer's mouth. Ads
+//! quote a snippet with an attribution placeholder the user fills in.
+
+use crate::models::ai_discovery::ParseDiagnostic;
+use crate::models::review::{ObjectionDraft, ReviewDigest, ReviewInsight, ReviewSummary, SnippetDraft};
+use crate::services::ai_validation::validate_object;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::cmp::Reverse;
+
+pub const MAX_REVIEWS: usize = 50;
+const MAX_REVIEW_CHARS: usize = 1000;
+pub const MAX_SNIPPETS: usize = 5;
+const MAX_SNIPPET_CHARS: usize = 160;
+const MIN_SNIPPET_CHARS: usize = 20;
+
+/// Stands in for the reviewer's name until the user confirms attribution
+pub const ATTRIBUTION_PLACEHOLDER: &str = "[CUSTOMER NAME], verified buyer";
+
+pub const REVIEWS_PROMPT: &str = r#"You are an expert at turning customer reviews into ad-ready social proof. Read the reviews of the following product.
+
+Product: {product_name}
+
+Reviews:
+{reviews}
+
+Return ONLY a valid JSON object with this structure:
+{
+  "snippets": [
+    { "quote": "Exact sentence copied from a review", "theme": "results" }
+  ],
+  "objections": [
+    { "objection": "Short statement of the concern", "theme": "price", "mentions": 3 }
+  ]
+}
+
+Rules:
+- Up to 5 snippets; every quote must be copied word for word from a single review
+- Prefer short, specific, enthusiastic quotes over generic praise
+- Objections are the most common concerns, with how many reviews raise each
+- NO explanatory text, ONLY the JSON object"#;
+
+/// Schema for the object requested by `REVIEWS_PROMPT`
+pub const REVIEWS_SCHEMA: &str = r#"{
+  "type": "object",
+  "required": ["snippets", "objections"],
+  "properties": {
+    "snippets": {
+      "type": "array",
+      "items": {
+        "type": "object",
+        "required": ["quote"],
+        "properties": {
+          "quote": { "type": "string", "minLength": 1 },
+          "theme": { "type": ["string", "null"] }
+        }
+      }
+    },
+    "objections": {
+      "type": "array",
+      "items": {
+        "type": "object",
+        "required": ["objection", "mentions"],
+        "properties": {
+          "objection": { "type": "string", "minLength": 1 },
+          "theme": { "type": ["string", "null"] },
+          "mentions": { "type": "integer", "minimum": 1 }
+        }
+      }
+    }
+  }
+}"#;
+
+/// Objection themes the heuristics recognize, with the phrases that signal them
+const OBJECTION_THEMES: [(&str, &str, &[&str]); 5] = [
+    ("price", "Feels expensive for what it is", &["expensive", "pricey", "overpriced", "cost", "price"]),
+    ("quality", "Durability and build quality concerns", &["broke", "flimsy", "cheap", "fell apart", "stopped working"]),
+    ("size", "Sizing or fit doesn't match expectations", &["too small", "too big", "too large", "fit", "sizing"]),
+    ("shipping", "Slow or damaged delivery", &["shipping", "arrived late", "took forever", "damaged", "delivery"]),
+    ("results", "Didn't see the promised results", &["didn't work", "no difference", "didn't notice", "waste", "disappoint"]),
+];
+
+const POSITIVE_CUES: [&str; 10] = [
+    "love", "amazing", "best", "great", "recommend", "perfect", "obsessed", "game changer", "works", "worth",
+];
+
+pub const INSIGHT_COLUMNS: &str = "id, product_id, kind, text, theme, mentions, served_by, created_at";
+
+/// Maps a row selected with `INSIGHT_COLUMNS` into a `ReviewInsight`
+pub fn insight_from_row(row: &Row) -> rusqlite::Result<ReviewInsight> {
+    Ok(ReviewInsight {
+        id: row.get(0)?,
+        product_id: row.get(1)?,
+        kind: row.get(2)?,
+        text: row.get(3)?,
+        theme: row.get(4)?,
+        mentions: row.get::<_, Option<i32>>(5)?.unwrap_or(1),
+        served_by: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+/// Trims, dedupes, and caps the reviews sent for summarization
+pub fn clean_reviews(reviews: Vec<String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for review in reviews {
+        let review: String = review.trim().chars().take(MAX_REVIEW_CHARS).collect();
+        if !review.is_empty() && !cleaned.contains(&review) {
+            cleaned.push(review);
+        }
+        if cleaned.len() == MAX_REVIEWS {
+            break;
+        }
+    }
+    cleaned
+}
+
+pub fn build_reviews_prompt(product_name: &str, reviews: &[String]) -> String {
+    let numbered = reviews
+        .iter()
+        .enumerate()
+        .map(|(i, r)| format!("{}. {}", i + 1, r.replace('\n', " ")))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    REVIEWS_PROMPT
+        .replace("{product_name}", product_name)
+        .replace("{reviews}", &numbered)
+}
+
+/// Extracts and schema-validates a review summary response
+pub fn validate_digest(response: &str) -> Result<ReviewDigest, ParseDiagnostic> {
+    validate_object(response, REVIEWS_SCHEMA)
+}
+
+/// Drops snippets that don't appear verbatim in any review and caps the count
+pub fn keep_verbatim(mut digest: ReviewDigest, reviews: &[String]) -> ReviewDigest {
+    let haystacks: Vec<String> = reviews.iter().map(|r| normalize(r)).collect();
+    digest.snippets.retain(|s| {
+        let quote = normalize(s.quote.trim_matches(|c| c == '"' || c == '\u{201c}' || c == '\u{201d}'));
+        !quote.is_empty() && haystacks.iter().any(|h| h.contains(&quote))
+    });
+    digest.snippets.truncate(MAX_SNIPPETS);
+    digest
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Heuristic summary used with the mock provider and as the last fallback:
+/// the most enthusiastic short sentences become snippets, and objections are
+/// counted by theme keywords.
+pub fn mock_digest(reviews: &[String]) -> ReviewDigest {
+    let mut candidates: Vec<(usize, String)> = reviews
+        .iter()
+        .flat_map(|r| split_sentences(r))
+        .filter(|s| (MIN_SNIPPET_CHARS..=MAX_SNIPPET_CHARS).contains(&s.chars().count()))
+        .filter_map(|s| {
+            let lower = s.to_lowercase();
+            let score = POSITIVE_CUES.iter().filter(|cue| lower.contains(*cue)).count();
+            let negative = OBJECTION_THEMES
+                .iter()
+                .any(|(_, _, cues)| cues.iter().any(|cue| lower.contains(cue)));
+            (score > 0 && !negative).then_some((score, s))
+        })
+        .collect();
+    // Stable sort keeps review order among equally enthusiastic sentences
+    candidates.sort_by_key(|c| Reverse(c.0));
+
+    let mut snippets: Vec<SnippetDraft> = Vec::new();
+    for (_, quote) in candidates {
+        if !snippets.iter().any(|s| s.quote.eq_ignore_ascii_case(&quote)) {
+            snippets.push(SnippetDraft { quote, theme: None });
+        }
+    }
+    snippets.truncate(MAX_SNIPPETS);
+
+    let mut objections: Vec<ObjectionDraft> = OBJECTION_THEMES
+        .iter()
+        .filter_map(|(theme, statement, cues)| {
+            let mentions = reviews
+                .iter()
+                .filter(|r| {
+                    let lower = r.to_lowercase();
+                    cues.iter().any(|cue| lower.contains(cue))
+                })
+                .count() as i32;
+            (mentions > 0).then(|| ObjectionDraft {
+                objection: statement.to_string(),
+                theme: Some(theme.to_string()),
+                mentions,
+            })
+        })
+        .collect();
+    objections.sort_by_key(|o| Reverse(o.mentions));
+
+    ReviewDigest { snippets, objections }
+}
+
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    for c in text.chars() {
+        current.push(c);
+        if matches!(c, '.' | '!' | '?' | '\n') {
+            let sentence = current.trim().to_string();
+            if !sentence.is_empty() {
+                sentences.push(sentence);
+            }
+            current.clear();
+        }
+    }
+    let rest = current.trim();
+    if !rest.is_empty() {
+        sentences.push(rest.to_string());
+    }
+    sentences
+}
+
+/// Replaces a product's stored insights with a fresh summary
+pub fn replace_insights(
+    conn: &mut Connection,
+    product_id: i64,
+    digest: &ReviewDigest,
+    served_by: &str,
+) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM review_insights WHERE product_id = ?1", params![product_id])?;
+
+    for snippet in &digest.snippets {
+        tx.execute(
+            "INSERT INTO review_insights (product_id, kind, text, theme, mentions, served_by)
+             VALUES (?1, 'snippet', ?2, ?3, 1, ?4)",
+            params![product_id, snippet.quote, snippet.theme, served_by],
+        )?;
+    }
+    for objection in &digest.objections {
+        tx.execute(
+            "INSERT INTO review_insights (product_id, kind, text, theme, mentions, served_by)
+             VALUES (?1, 'objection', ?2, ?3, ?4, ?5)",
+            params![product_id, objection.objection, objection.theme, objection.mentions, served_by],
+        )?;
+    }
+
+    tx.commit()
+}
+
+/// Stored insights for a product; snippets in summary order, objections by mentions
+pub fn load_summary(conn: &Connection, product_id: i64, review_count: usize) -> rusqlite::Result<ReviewSummary> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM review_insights WHERE product_id = ?1 ORDER BY mentions DESC, id",
+        INSIGHT_COLUMNS
+    ))?;
+    let (snippets, objections): (Vec<ReviewInsight>, Vec<ReviewInsight>) = stmt
+        .query_map(params![product_id], insight_from_row)?
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .partition(|i| i.kind == "snippet");
+
+    Ok(ReviewSummary {
+        product_id,
+        review_count,
+        snippets,
+        objections,
+    })
+}
+
+/// The snippet ads quote by default: the first one from the latest summary
+pub fn top_snippet(conn: &Connection, product_id: i64) -> rusqlite::Result<Option<ReviewInsight>> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM review_insights WHERE product_id = ?1 AND kind = 'snippet' ORDER BY id LIMIT 1",
+            INSIGHT_COLUMNS
+        ),
+        params![product_id],
+        insight_from_row,
+    )
+    .optional()
+}
+
+/// Renders a snippet as an attributed quote for ad copy
+pub fn attributed_quote(snippet: &ReviewInsight) -> String {
+    format!("\"{}\" - {}", snippet.text, ATTRIBUTION_PLACEHOLDER)
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reviews() -> Vec<String> {
+        vec![
+            "I love this serum, my skin has never looked better! Shipping took forever though.".to_string(),
+            "Honestly the best purchase I've made this year. A bit pricey.".to_string(),
+            "Way too expensive for such a small bottle.".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_mock_digest() {
+        let digest = mock_digest(&reviews());
+        assert_eq!(
+            digest.snippets.first().map(|s| s.quote.as_str()),
+            Some("I love this serum, my skin has never looked better!")
+        );
+        // Sentences raising a concern never become snippets
+        assert!(digest.snippets.iter().all(|s| !s.quote.contains("pricey")));
+
+        let price = digest.objections.first().unwrap();
+        assert_eq!(price.theme.as_deref(), Some("price"));
+        assert_eq!(price.mentions, 2);
+    }
+
+    #[test]
+    fn test_keep_verbatim_drops_invented_quotes() {
+        let digest = ReviewDigest {
+            snippets: vec![
+                SnippetDraft { quote: "\"The BEST purchase   I've made this year.\"".to_string(), theme: None },
+                SnippetDraft { quote: "Cured my acne overnight!".to_string(), theme: None },
+            ],
+            objections: Vec::new(),
+        };
+        let kept = keep_verbatim(digest, &reviews());
+        assert_eq!(kept.snippets.len(), 1);
+        assert!(kept.snippets[0].quote.contains("BEST purchase"));
+    }
+
+    #[test]
+    fn test_clean_reviews() {
+        let cleaned = clean_reviews(vec![" Great ".to_string(), "Great".to_string(), "".to_string()]);
+        assert_eq!(cleaned, vec!["Great".to_string()]);
+    }
+}
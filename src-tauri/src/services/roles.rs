@@ -0,0 +1,180 @@
+//! Roles
+//!
+//! Local owner/contributor roles for shared machines and workspaces. Once an
+//! owner password is set, each app session starts as a contributor;
+//! destructive commands (deleting products, purging data, reading or changing
+//! credentials) need the session to be unlocked with the owner password,
+//! which lasts 30 minutes. This keeps a VA from wiping the catalog by
+//! accident; it is not protection against someone with access to the
+//! database file.
+
+use crate::database::settings::{get_setting, set_setting};
+use crate::models::role::{OwnerRequiredError, RoleStatus};
+use chrono::{Duration, Utc};
+use hmac::{Hmac, Mac};
+use rusqlite::Connection;
+use sha2::Sha256;
+use std::sync::{Mutex, OnceLock};
+
+pub const OWNER: &str = "owner";
+pub const CONTRIBUTOR: &str = "contributor";
+pub const OWNER_REQUIRED: &str = "owner_required";
+
+const HASH_SETTING: &str = "owner_password_hash";
+const SALT_SETTING: &str = "owner_password_salt";
+
+const PBKDF2_ROUNDS: u32 = 100_000;
+const MIN_PASSWORD_LENGTH: usize = 6;
+const UNLOCK_MINUTES: i64 = 30;
+
+/// When this session's owner unlock expires
+fn unlock_slot() -> &'static Mutex<Option<chrono::DateTime<Utc>>> {
+    static SLOT: OnceLock<Mutex<Option<chrono::DateTime<Utc>>>> = OnceLock::new();
+    SLOT.get_or_init(|| Mutex::new(None))
+}
+
+/// PBKDF2-HMAC-SHA256, one 32-byte block, hex encoded
+pub fn hash_password(password: &str, salt: &str, rounds: u32) -> String {
+    let mac = || Hmac::<Sha256>::new_from_slice(password.as_bytes()).expect("HMAC accepts any key length");
+    let mut block = mac();
+    block.update(salt.as_bytes());
+    block.update(&1u32.to_be_bytes());
+    let mut u = block.finalize().into_bytes();
+    let mut result = u;
+    for _ in 1..rounds {
+        let mut next = mac();
+        next.update(&u);
+        u = next.finalize().into_bytes();
+        result.iter_mut().zip(u.iter()).for_each(|(r, b)| *r ^= b);
+    }
+    hex::encode(result)
+}
+
+/// Compares without stopping at the first difference
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn stored_hash(conn: &Connection) -> Option<(String, String)> {
+    let hash = get_setting(conn, HASH_SETTING).ok().flatten().filter(|h| !h.is_empty())?;
+    let salt = get_setting(conn, SALT_SETTING).ok().flatten().unwrap_or_default();
+    Some((hash, salt))
+}
+
+pub fn password_set(conn: &Connection) -> bool {
+    stored_hash(conn).is_some()
+}
+
+fn verify(conn: &Connection, password: &str) -> bool {
+    stored_hash(conn)
+        .is_some_and(|(hash, salt)| constant_time_eq(&hash_password(password, &salt, PBKDF2_ROUNDS), &hash))
+}
+
+fn unlocked_until() -> Option<chrono::DateTime<Utc>> {
+    unlock_slot().lock().ok().and_then(|slot| *slot).filter(|until| *until > Utc::now())
+}
+
+pub fn current_role(conn: &Connection) -> &'static str {
+    if !password_set(conn) || unlocked_until().is_some() {
+        OWNER
+    } else {
+        CONTRIBUTOR
+    }
+}
+
+pub fn status(conn: &Connection) -> RoleStatus {
+    let password_set = password_set(conn);
+    RoleStatus {
+        password_set,
+        role: current_role(conn).to_string(),
+        unlocked_until: unlocked_until().filter(|_| password_set).map(|until| until.to_rfc3339()),
+    }
+}
+
+/// Gate for owner-only commands; the error is a JSON `OwnerRequiredError`
+pub fn require_owner(conn: &Connection, action: &str) -> Result<(), String> {
+    if current_role(conn) == OWNER {
+        return Ok(());
+    }
+    let error = OwnerRequiredError {
+        code: OWNER_REQUIRED.to_string(),
+        action: action.to_string(),
+        message: format!("Only the owner can {}. Unlock with the owner password first.", action.replace('_', " ")),
+    };
+    Err(serde_json::to_string(&error).unwrap_or_else(|_| error.message.clone()))
+}
+
+pub fn unlock(conn: &Connection, password: &str) -> Result<RoleStatus, String> {
+    if !password_set(conn) {
+        return Ok(status(conn));
+    }
+    if !verify(conn, password) {
+        return Err("Incorrect owner password".to_string());
+    }
+    if let Ok(mut slot) = unlock_slot().lock() {
+        *slot = Some(Utc::now() + Duration::minutes(UNLOCK_MINUTES));
+    }
+    Ok(status(conn))
+}
+
+/// Drops this session back to contributor
+pub fn lock() {
+    if let Ok(mut slot) = unlock_slot().lock() {
+        *slot = None;
+    }
+}
+
+/// Sets, changes, or (with an empty `new_password`) removes the owner
+/// password. Changing or removing it needs the current one.
+pub fn set_password(
+    conn: &Connection,
+    current: Option<&str>,
+    new_password: Option<&str>,
+) -> Result<RoleStatus, String> {
+    if password_set(conn) && !current.is_some_and(|c| verify(conn, c)) {
+        return Err("Incorrect owner password".to_string());
+    }
+
+    match new_password.filter(|p| !p.is_empty()) {
+        Some(password) if password.chars().count() < MIN_PASSWORD_LENGTH => {
+            return Err(format!("Use at least {} characters for the owner password", MIN_PASSWORD_LENGTH));
+        }
+        Some(password) => {
+            let salt = uuid::Uuid::new_v4().simple().to_string();
+            set_setting(conn, SALT_SETTING, &salt).map_err(|e| e.to_string())?;
+            set_setting(conn, HASH_SETTING, &hash_password(password, &salt, PBKDF2_ROUNDS))
+                .map_err(|e| e.to_string())?;
+            // Whoever set the password is the owner for this session
+            unlock(conn, password)?;
+        }
+        None => {
+            set_setting(conn, HASH_SETTING, "").map_err(|e| e.to_string())?;
+            set_setting(conn, SALT_SETTING, "").map_err(|e| e.to_string())?;
+            lock();
+        }
+    }
+    Ok(status(conn))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_password_matches_pbkdf2_vectors() {
+        assert_eq!(
+            hash_password("password", "salt", 1),
+            "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"
+        );
+        assert_eq!(
+            hash_password("password", "salt", 2),
+            "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"
+        );
+        assert!(constant_time_eq("abc", "abc"));
+        assert!(!constant_time_eq("abc", "abd"));
+    }
+}
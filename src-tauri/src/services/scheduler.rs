@@ -0,0 +1,170 @@
+//! Background Job Scheduler
+//!
+//! Runs periodic maintenance jobs (digests, rollups, ...) on a background
+//! thread. Each job records its last run time in the settings table under
+//! `job_last_run:<name>` so intervals survive app restarts.
+
+use crate::database::get_connection;
+use crate::database::settings::{get_setting, get_setting_or, set_setting};
+use crate::services::read_only;
+use chrono::{DateTime, Duration, Utc};
+use std::time::Duration as StdDuration;
+use tauri::AppHandle;
+
+/// How often the scheduler wakes up to look for due jobs
+const TICK_INTERVAL: StdDuration = StdDuration::from_secs(15 * 60);
+
+/// A periodic background job
+pub struct Job {
+    pub name: &'static str,
+    /// Settings key that enables/disables the job ("true"/"false")
+    pub enabled_setting: &'static str,
+    pub enabled_by_default: bool,
+    pub interval: Duration,
+    pub run: fn(&AppHandle) -> Result<String, String>,
+}
+
+/// All registered background jobs
+pub fn jobs() -> Vec<Job> {
+    vec![
+        Job {
+            name: "weekly_digest",
+            enabled_setting: "weekly_digest_enabled",
+            enabled_by_default: true,
+            interval: Duration::days(7),
+            run: crate::services::digest::run_weekly_digest_job,
+        },
+        Job {
+            name: "application_reminders",
+            enabled_setting: "application_reminders_enabled",
+            enabled_by_default: true,
+            interval: Duration::days(1),
+            run: crate::services::program_applications::run_reminder_job,
+        },
+        Job {
+            name: "recurring_posts",
+            enabled_setting: "recurring_posts_enabled",
+            enabled_by_default: true,
+            interval: Duration::days(1),
+            run: crate::services::post_schedule::run_expansion_job,
+        },
+        Job {
+            name: "data_retention",
+            enabled_setting: "data_retention_enabled",
+            enabled_by_default: true,
+            interval: Duration::days(1),
+            run: crate::services::retention::run_retention_job,
+        },
+        Job {
+            name: "daily_metrics",
+            enabled_setting: "daily_metrics_enabled",
+            enabled_by_default: true,
+            interval: Duration::days(1),
+            run: crate::services::daily_metrics::run_rollup_job,
+        },
+        Job {
+            name: "product_watchlist",
+            enabled_setting: "product_watchlist_enabled",
+            enabled_by_default: true,
+            interval: Duration::hours(12),
+            run: crate::services::watchlist::run_watch_job,
+        },
+        Job {
+            name: "store_sync",
+            enabled_setting: "store_sync_enabled",
+            enabled_by_default: true,
+            interval: Duration::hours(6),
+            run: crate::services::store_sync::run_store_sync_job,
+        },
+        Job {
+            name: "feed_sources",
+            enabled_setting: "feed_sources_enabled",
+            enabled_by_default: true,
+            interval: Duration::hours(3),
+            run: crate::services::feed_sources::run_feed_job,
+        },
+    ]
+}
+
+/// Spawns the scheduler thread
+pub fn start(app_handle: AppHandle) {
+    std::thread::spawn(move || loop {
+        run_due_jobs(&app_handle);
+        std::thread::sleep(TICK_INTERVAL);
+    });
+}
+
+/// Runs every enabled job whose interval has elapsed since its last run
+pub fn run_due_jobs(app_handle: &AppHandle) {
+    let now = Utc::now();
+
+    for job in jobs() {
+        let due = match get_connection(app_handle) {
+            Ok(conn) => {
+                let default = if job.enabled_by_default { "true" } else { "false" };
+                let enabled = get_setting_or(&conn, job.enabled_setting, default) == "true";
+                // Every job writes, so none run while the workspace is read-only;
+                // the last run isn't recorded either, so due jobs run once it's off
+                enabled && !read_only::is_enabled(&conn) && is_due(last_run(&conn, job.name), job.interval, now)
+            }
+            Err(e) => {
+                eprintln!("Scheduler could not open database: {}", e);
+                return;
+            }
+        };
+
+        if !due {
+            continue;
+        }
+
+        match (job.run)(app_handle) {
+            Ok(message) => println!("✓ Job {} completed: {}", job.name, message),
+            Err(e) => eprintln!("Job {} failed: {}", job.name, e),
+        }
+
+        // Record the attempt even on failure so a broken job doesn't retry every tick
+        if let Ok(conn) = get_connection(app_handle) {
+            let key = format!("job_last_run:{}", job.name);
+            if let Err(e) = set_setting(&conn, &key, &now.to_rfc3339()) {
+                eprintln!("Failed to record last run for job {}: {}", job.name, e);
+            }
+        }
+    }
+}
+
+fn last_run(conn: &rusqlite::Connection, name: &str) -> Option<DateTime<Utc>> {
+    get_setting(conn, &format!("job_last_run:{}", name))
+        .ok()
+        .flatten()
+        .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// A job is due when it has never run or its interval has elapsed
+pub fn is_due(last_run: Option<DateTime<Utc>>, interval: Duration, now: DateTime<Utc>) -> bool {
+    match last_run {
+        Some(last) => now - last >= interval,
+        None => true,
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_due_when_never_run() {
+        assert!(is_due(None, Duration::days(7), Utc::now()));
+    }
+
+    #[test]
+    fn test_job_due_after_interval() {
+        let now = Utc::now();
+        assert!(!is_due(Some(now - Duration::days(3)), Duration::days(7), now));
+        assert!(is_due(Some(now - Duration::days(7)), Duration::days(7), now));
+    }
+}
@@ -0,0 +1,142 @@
+//! Scoring Fixtures
+//!
+//! Representative products, one per category and audience combination, and
+//! golden-file tests pinning the platform and ad types each one is
+//! recommended. A change to weights or category matching that flips a
+//! recommendation fails here instead of shipping silently.
+//!
+//! When a change is intended, regenerate the golden file and review its diff:
+//! `UPDATE_GOLDEN=1 cargo test scoring_fixtures`
+
+use crate::models::product::Product;
+use serde::{Deserialize, Serialize};
+
+/// Categories with a typical price range and trending score for each
+pub const CATEGORIES: [(&str, &str, &str, i32); 8] = [
+    ("beauty", "Beauty & Skincare", "$20-$40", 85),
+    ("wellness", "Health & Wellness", "$30-$60", 60),
+    ("fitness", "Fitness & Recovery", "$80-$150", 70),
+    ("electronics", "Consumer Electronics", "$150-$400", 75),
+    ("wearables", "Wearable Health Technology", "$200-$350", 65),
+    ("fashion", "Fashion & Apparel", "$40-$90", 80),
+    ("home", "Home & Kitchen", "$50-$120", 55),
+    ("other", "Pet Supplies", "$15-$35", 40),
+];
+
+/// Audiences by generation
+pub const AUDIENCES: [(&str, &str); 4] = [
+    ("gen_z", "Ages 18-25"),
+    ("millennial", "Ages 26-40"),
+    ("gen_x", "Ages 41-55"),
+    ("boomer", "Ages 56-70"),
+];
+
+/// A named fixture product
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    pub key: String, // "<category>/<audience>"
+    pub product: Product,
+}
+
+/// What a fixture is expected to be recommended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoldenRecommendation {
+    pub fixture: String,
+    pub platform: String,
+    pub ad_type: String,
+    pub analytics_ad_type: String, // Analytics service's top-scored ad type
+}
+
+/// A product with only the fields scoring reads set
+pub fn product(name: &str, category: &str, audience: &str, trending_score: i32, price_range: &str) -> Product {
+    Product {
+        id: None,
+        name: name.to_string(),
+        category: category.to_string(),
+        description: None,
+        price_range: Some(price_range.to_string()),
+        target_audience: Some(audience.to_string()),
+        trending_score: Some(trending_score),
+        notes: None,
+        image_url: None,
+        amazon_asin: None,
+        tiktok_product_id: None,
+        instagram_product_id: None,
+        youtube_video_id: None,
+        pinterest_pin_id: None,
+        product_url: None,
+        created_at: None,
+        updated_at: None,
+        stage: None,
+        stage_changed_at: None,
+        tags: Vec::new(),
+        updated_by: None,
+        availability: None,
+        availability_checked_at: None,
+    }
+}
+
+/// Every category and audience combination, in a fixed order
+pub fn all() -> Vec<Fixture> {
+    CATEGORIES
+        .iter()
+        .flat_map(|&(category_key, category, price_range, trending_score)| {
+            AUDIENCES.iter().map(move |&(audience_key, audience)| Fixture {
+                key: format!("{}/{}", category_key, audience_key),
+                product: product(
+                    &format!("{} fixture", category),
+                    category,
+                    audience,
+                    trending_score,
+                    price_range,
+                ),
+            })
+        })
+        .collect()
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::category_keywords::CategoryAffinities;
+    use crate::services::recommendation_trace;
+
+    const GOLDEN_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/recommendations.json");
+
+    fn recommend(fixture: &Fixture, affinities: &CategoryAffinities) -> GoldenRecommendation {
+        let trace = recommendation_trace::explain(&fixture.product, None, affinities, &Default::default());
+        GoldenRecommendation {
+            fixture: fixture.key.clone(),
+            platform: trace.recommended_platform,
+            ad_type: trace.recommended_ad_type,
+            analytics_ad_type: trace.ad_type_factors[0].ad_type.clone(),
+        }
+    }
+
+    #[test]
+    fn test_recommendations_match_golden_file() {
+        let affinities = CategoryAffinities::defaults();
+        let actual: Vec<GoldenRecommendation> = all().iter().map(|f| recommend(f, &affinities)).collect();
+        if std::env::var("UPDATE_GOLDEN").is_ok() {
+            let json = serde_json::to_string_pretty(&actual).unwrap();
+            std::fs::write(GOLDEN_PATH, json + "\n").unwrap();
+            return;
+        }
+
+        let golden: Vec<GoldenRecommendation> =
+            serde_json::from_str(&std::fs::read_to_string(GOLDEN_PATH).unwrap()).unwrap();
+        assert_eq!(golden.len(), actual.len(), "fixture set changed; regenerate the golden file");
+        let flipped: Vec<String> = golden
+            .iter()
+            .zip(&actual)
+            .filter(|(expected, got)| expected != got)
+            .map(|(expected, got)| format!("{:?} -> {:?}", expected, got))
+            .collect();
+        assert!(flipped.is_empty(), "recommendations changed:\n{}", flipped.join("\n"));
+    }
+}
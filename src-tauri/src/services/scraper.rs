@@ -0,0 +1,294 @@
+//! Product Page Scraper
+//!
+//! Fetches a product URL and extracts the details needed to create a catalog
+//! entry: title, description, image, price, availability, and platform
+//! identifiers. Extraction relies on OpenGraph / product meta tags and
+//! schema.org JSON-LD, which every major storefront publishes.
+
+use crate::models::scraped_product::ScrapedProduct;
+use regex::Regex;
+use std::time::Duration;
+
+const USER_AGENT: &str =
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0 Safari/537.36";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Fetches and parses a product page
+pub async fn scrape_product(url: &str) -> Result<ScrapedProduct, String> {
+    let html = fetch_html(url).await?;
+    Ok(parse_product_html(url, &html))
+}
+
+/// Fetches a product page and returns the customer reviews it publishes
+pub async fn scrape_reviews(url: &str) -> Result<Vec<String>, String> {
+    let html = fetch_html(url).await?;
+    Ok(parse_review_bodies(&html))
+}
+
+/// Fetches a page with a browser user agent
+pub async fn fetch_html(url: &str) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let html = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?
+        .error_for_status()
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", url, e))?;
+
+    Ok(html)
+}
+
+/// Extracts product details from raw HTML
+pub fn parse_product_html(url: &str, html: &str) -> ScrapedProduct {
+    let title = meta_content(html, &["og:title", "twitter:title"])
+        .or_else(|| title_tag(html))
+        .map(|t| clean_title(&t));
+
+    let price = meta_content(html, &["product:price:amount", "og:price:amount"])
+        .and_then(|p| parse_price(&p))
+        .or_else(|| json_ld_value(html, "price").and_then(|p| parse_price(&p)));
+
+    let currency = meta_content(html, &["product:price:currency", "og:price:currency"])
+        .or_else(|| json_ld_value(html, "priceCurrency"));
+
+    let availability = meta_content(html, &["product:availability", "og:availability"])
+        .or_else(|| json_ld_value(html, "availability"));
+
+    ScrapedProduct {
+        url: url.to_string(),
+        title,
+        description: meta_content(html, &["og:description", "description", "twitter:description"]),
+        image_url: meta_content(html, &["og:image", "twitter:image"]),
+        price,
+        currency,
+        availability,
+        amazon_asin: extract_asin(url),
+        source_platform: detect_platform(url).map(String::from),
+    }
+}
+
+/// Returns the `content` of the first meta tag whose property/name matches any key
+pub fn meta_content(html: &str, keys: &[&str]) -> Option<String> {
+    let tag_re = Regex::new(r"(?is)<meta\s[^>]*>").ok()?;
+    let attr_re = Regex::new(r#"(?is)([a-z:_-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).ok()?;
+
+    for key in keys {
+        for tag in tag_re.find_iter(html) {
+            let mut name = None;
+            let mut content = None;
+
+            for caps in attr_re.captures_iter(tag.as_str()) {
+                let attr = caps[1].to_lowercase();
+                let value = caps.get(2).or_else(|| caps.get(3)).map(|m| m.as_str());
+                match attr.as_str() {
+                    "property" | "name" | "itemprop" => name = value,
+                    "content" => content = value,
+                    _ => {}
+                }
+            }
+
+            if name.map(|n| n.eq_ignore_ascii_case(key)).unwrap_or(false) {
+                if let Some(content) = content.map(decode_entities).filter(|c| !c.trim().is_empty()) {
+                    return Some(content.trim().to_string());
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn title_tag(html: &str) -> Option<String> {
+    let re = Regex::new(r"(?is)<title[^>]*>(.*?)</title>").ok()?;
+    re.captures(html)
+        .map(|c| decode_entities(c[1].trim()))
+        .filter(|t| !t.is_empty())
+}
+
+/// Finds the first `"key": value` pair in any JSON-LD block
+fn json_ld_value(html: &str, key: &str) -> Option<String> {
+    let block_re =
+        Regex::new(r#"(?is)<script[^>]*type\s*=\s*["']application/ld\+json["'][^>]*>(.*?)</script>"#).ok()?;
+    let value_re = Regex::new(&format!(
+        r#""{}"\s*:\s*(?:"([^"]*)"|([0-9.]+))"#,
+        regex::escape(key)
+    ))
+    .ok()?;
+
+    let value = block_re.captures_iter(html).find_map(|block| {
+        value_re
+            .captures(&block[1])
+            .and_then(|c| c.get(1).or_else(|| c.get(2)))
+            .map(|m| m.as_str().to_string())
+    });
+    value
+}
+
+/// Collects every schema.org `reviewBody` from the page's JSON-LD blocks
+pub fn parse_review_bodies(html: &str) -> Vec<String> {
+    let block_re = match Regex::new(
+        r#"(?is)<script[^>]*type\s*=\s*["']application/ld\+json["'][^>]*>(.*?)</script>"#,
+    ) {
+        Ok(re) => re,
+        Err(_) => return Vec::new(),
+    };
+    let body_re = match Regex::new(r#""reviewBody"\s*:\s*("(?:[^"\\]|\\.)*")"#) {
+        Ok(re) => re,
+        Err(_) => return Vec::new(),
+    };
+
+    block_re
+        .captures_iter(html)
+        .flat_map(|block| {
+            body_re
+                .captures_iter(&block[1])
+                // The capture is a JSON string literal; let serde handle the escapes
+                .filter_map(|c| serde_json::from_str::<String>(&c[1]).ok())
+                .collect::<Vec<_>>()
+        })
+        .map(|body| body.trim().to_string())
+        .filter(|body| !body.is_empty())
+        .collect()
+}
+
+/// Strips storefront suffixes like "Amazon.com: " or " | Etsy"
+fn clean_title(title: &str) -> String {
+    let mut t = title.trim();
+    for prefix in ["Amazon.com: ", "Amazon.com : "] {
+        if let Some(stripped) = t.strip_prefix(prefix) {
+            t = stripped;
+        }
+    }
+    for suffix in [" | Etsy", " - Etsy", " : Amazon.com", " | eBay", " - TikTok Shop"] {
+        if let Some(stripped) = t.strip_suffix(suffix) {
+            t = stripped;
+        }
+    }
+    t.trim().to_string()
+}
+
+/// Decodes the HTML entities pages and feeds commonly use
+pub fn decode_entities(s: &str) -> String {
+    s.replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+}
+
+/// Plain text of an HTML fragment, e.g. a product description from a store API
+pub fn html_to_text(html: &str) -> String {
+    let without_tags = match Regex::new(r"<[^>]*>") {
+        Ok(re) => re.replace_all(html, " ").to_string(),
+        Err(_) => html.to_string(),
+    };
+    decode_entities(&without_tags).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses "$1,299.99" / "29.95" / "29,95 €" into a number
+pub fn parse_price(raw: &str) -> Option<f64> {
+    let re = Regex::new(r"\d[\d,]*(?:\.\d+)?").ok()?;
+    let m = re.find(raw)?;
+    m.as_str().replace(',', "").parse().ok()
+}
+
+/// Formats a single scraped price into the catalog's price_range style ("$30-$40")
+pub fn price_to_range(price: f64) -> String {
+    let low = (price / 10.0).floor() * 10.0;
+    format!("${}-${}", low as i64, low as i64 + 10)
+}
+
+/// Extracts an Amazon ASIN from a product URL
+pub fn extract_asin(url: &str) -> Option<String> {
+    let re = Regex::new(r"(?i)amazon\.[a-z.]+/(?:.*/)?(?:dp|gp/product|gp/aw/d)/([A-Z0-9]{10})").ok()?;
+    re.captures(url).map(|c| c[1].to_uppercase())
+}
+
+/// Identifies the storefront a product URL belongs to
+pub fn detect_platform(url: &str) -> Option<&'static str> {
+    let lower = url.to_lowercase();
+    if lower.contains("amazon.") || lower.contains("amzn.to") || lower.contains("a.co/") {
+        Some("amazon")
+    } else if lower.contains("shop.tiktok.com") || lower.contains("tiktok.com/view/product") {
+        Some("tiktok")
+    } else if lower.contains("etsy.com") {
+        Some("etsy")
+    } else if lower.contains("ebay.") {
+        Some("ebay")
+    } else if lower.contains("walmart.com") {
+        Some("walmart")
+    } else {
+        None
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><head>
+        <title>Ignored Title</title>
+        <meta content="Amazon.com: Snail Mucin Essence &amp; Serum" property="og:title">
+        <meta name="description" content="Hydrating essence">
+        <meta property="og:image" content='https://img.example.com/p.jpg'>
+        <script type="application/ld+json">{"@type":"Product","offers":{"price":"24.99","priceCurrency":"USD","availability":"https://schema.org/InStock"}}</script>
+        </head></html>"#;
+
+    #[test]
+    fn test_parse_product_html() {
+        let product = parse_product_html("https://www.amazon.com/dp/B00PBX3L7K?th=1", PAGE);
+        assert_eq!(product.title.as_deref(), Some("Snail Mucin Essence & Serum"));
+        assert_eq!(product.description.as_deref(), Some("Hydrating essence"));
+        assert_eq!(product.image_url.as_deref(), Some("https://img.example.com/p.jpg"));
+        assert_eq!(product.price, Some(24.99));
+        assert_eq!(product.currency.as_deref(), Some("USD"));
+        assert_eq!(product.availability.as_deref(), Some("https://schema.org/InStock"));
+        assert_eq!(product.amazon_asin.as_deref(), Some("B00PBX3L7K"));
+        assert_eq!(product.source_platform.as_deref(), Some("amazon"));
+    }
+
+    #[test]
+    fn test_title_tag_fallback() {
+        let product = parse_product_html("https://example.com/p", "<title> Handmade Mug | Etsy </title>");
+        assert_eq!(product.title.as_deref(), Some("Handmade Mug"));
+    }
+
+    #[test]
+    fn test_parse_price() {
+        assert_eq!(parse_price("$1,299.99"), Some(1299.99));
+        assert_eq!(parse_price("29"), Some(29.0));
+        assert_eq!(parse_price("n/a"), None);
+        assert_eq!(price_to_range(34.5), "$30-$40");
+    }
+
+    #[test]
+    fn test_parse_review_bodies() {
+        let html = r#"<script type="application/ld+json">{"@type":"Product","review":[
+            {"@type":"Review","reviewBody":"Love it, \"best\" purchase"},
+            {"@type":"Review","reviewBody":"  "}]}</script>"#;
+        assert_eq!(parse_review_bodies(html), vec!["Love it, \"best\" purchase".to_string()]);
+    }
+
+    #[test]
+    fn test_detect_platform() {
+        assert_eq!(detect_platform("https://www.etsy.com/listing/123/mug"), Some("etsy"));
+        assert_eq!(detect_platform("https://shop.tiktok.com/view/product/1729"), Some("tiktok"));
+        assert_eq!(detect_platform("https://example.com"), None);
+    }
+}
@@ -0,0 +1,243 @@
+//! Shared Workspace
+//!
+//! Lets a small team share one catalog by pointing the app at a database on
+//! a shared drive, without a server. SQLite's own locking keeps each write
+//! atomic (with a rollback journal, since WAL needs shared memory on one
+//! machine); on top of that a cooperative lock file next to the database says
+//! who is editing, so a teammate sees "Sam is editing" instead of silently
+//! racing them. Product edits record who made them and are rejected when the
+//! row changed since it was loaded.
+
+use crate::models::shared_workspace::{SharedWorkspaceStatus, WorkspaceLocation, WorkspaceLock};
+use chrono::{DateTime, Duration, Utc};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::AppHandle;
+
+const LOCATION_FILE: &str = "workspace.json";
+const LOCAL_DATABASE: &str = "affilai.db";
+
+/// A lock whose heartbeat is older than this is treated as abandoned
+const STALE_AFTER_SECS: i64 = 180;
+const HEARTBEAT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+fn location_slot() -> &'static Mutex<Option<WorkspaceLocation>> {
+    static SLOT: OnceLock<Mutex<Option<WorkspaceLocation>>> = OnceLock::new();
+    SLOT.get_or_init(|| Mutex::new(None))
+}
+
+/// The configured location, read from `workspace.json` once and cached
+pub fn load_location(app_dir: &Path) -> WorkspaceLocation {
+    if let Ok(slot) = location_slot().lock() {
+        if let Some(location) = slot.as_ref() {
+            return location.clone();
+        }
+    }
+    let location: WorkspaceLocation = fs::read_to_string(app_dir.join(LOCATION_FILE))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    if let Ok(mut slot) = location_slot().lock() {
+        *slot = Some(location.clone());
+    }
+    location
+}
+
+pub fn save_location(app_dir: &Path, location: &WorkspaceLocation) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(location).map_err(|e| e.to_string())?;
+    fs::write(app_dir.join(LOCATION_FILE), json).map_err(|e| e.to_string())?;
+    if let Ok(mut slot) = location_slot().lock() {
+        *slot = Some(location.clone());
+    }
+    Ok(())
+}
+
+pub fn is_shared(location: &WorkspaceLocation) -> bool {
+    location.database_path.as_deref().is_some_and(|p| !p.trim().is_empty())
+}
+
+pub fn database_path(app_dir: &Path, location: &WorkspaceLocation) -> PathBuf {
+    match location.database_path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => app_dir.join(LOCAL_DATABASE),
+    }
+}
+
+/// The name changes are attributed to: the configured editor, else the OS user
+pub fn editor_name(location: &WorkspaceLocation) -> String {
+    location
+        .editor_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(String::from)
+        .or_else(|| std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok())
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+/// The editor name for this install, for attributing changes
+pub fn current_editor(app_handle: &AppHandle) -> String {
+    editor_name(&load_location(&crate::database::app_dir(app_handle)))
+}
+
+fn host_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok().map(|h| h.trim().to_string()))
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "unknown-host".to_string())
+}
+
+fn lock_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.as_os_str().to_owned();
+    name.push(".lock");
+    PathBuf::from(name)
+}
+
+pub fn read_lock(db_path: &Path) -> Option<WorkspaceLock> {
+    fs::read_to_string(lock_path(db_path)).ok().and_then(|json| serde_json::from_str(&json).ok())
+}
+
+pub fn is_stale(lock: &WorkspaceLock, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(&lock.heartbeat_at)
+        .map_or(true, |at| now - at.with_timezone(&Utc) > Duration::seconds(STALE_AFTER_SECS))
+}
+
+pub fn is_mine(lock: &WorkspaceLock) -> bool {
+    lock.host == host_name() && lock.pid == std::process::id()
+}
+
+fn write_lock(db_path: &Path, lock: &WorkspaceLock, create_new: bool) -> std::io::Result<()> {
+    let json = serde_json::to_string(lock).map_err(std::io::Error::other)?;
+    if create_new {
+        // Fails if a teammate created the lock first
+        let mut file = OpenOptions::new().write(true).create_new(true).open(lock_path(db_path))?;
+        return file.write_all(json.as_bytes());
+    }
+    // Replace rather than rewrite in place so a reader never sees half a file
+    let tmp = lock_path(db_path).with_extension(format!("lock.{}", std::process::id()));
+    fs::write(&tmp, json)?;
+    fs::rename(&tmp, lock_path(db_path))
+}
+
+fn describe(lock: &WorkspaceLock) -> String {
+    let since = DateTime::parse_from_rfc3339(&lock.acquired_at)
+        .map(|at| at.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|_| lock.acquired_at.clone());
+    format!("{} is editing on {} (since {})", lock.editor, lock.host, since)
+}
+
+/// Takes the edit lock. Fails while a teammate holds a live lock unless
+/// `force` is set; abandoned locks are taken over.
+pub fn acquire(db_path: &Path, editor: &str, force: bool) -> Result<WorkspaceLock, String> {
+    let now = Utc::now();
+    let existing = read_lock(db_path);
+    if let Some(lock) = existing.as_ref() {
+        if !is_mine(lock) && !is_stale(lock, now) && !force {
+            return Err(format!("{}. Open read-only or take over the lock.", describe(lock)));
+        }
+    }
+
+    let lock = WorkspaceLock {
+        editor: editor.to_string(),
+        host: host_name(),
+        pid: std::process::id(),
+        acquired_at: existing
+            .as_ref()
+            .filter(|l| is_mine(l))
+            .map(|l| l.acquired_at.clone())
+            .unwrap_or_else(|| now.to_rfc3339()),
+        heartbeat_at: now.to_rfc3339(),
+    };
+    write_lock(db_path, &lock, existing.is_none()).map_err(|e| match read_lock(db_path) {
+        Some(other) if !is_mine(&other) => describe(&other),
+        _ => format!("Failed to write the workspace lock: {}", e),
+    })?;
+
+    // Two instances can take over a stale lock at once; the last write wins
+    match read_lock(db_path) {
+        Some(held) if is_mine(&held) => Ok(held),
+        Some(other) => Err(describe(&other)),
+        None => Err("The workspace lock disappeared while it was being taken".to_string()),
+    }
+}
+
+/// Drops the lock if this instance holds it
+pub fn release(db_path: &Path) -> Result<(), String> {
+    match read_lock(db_path) {
+        Some(lock) if is_mine(&lock) => fs::remove_file(lock_path(db_path)).map_err(|e| e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Keeps this instance's lock fresh; a lock taken over by a teammate is left alone
+pub fn heartbeat(db_path: &Path) {
+    if let Some(mut lock) = read_lock(db_path).filter(is_mine) {
+        lock.heartbeat_at = Utc::now().to_rfc3339();
+        if let Err(e) = write_lock(db_path, &lock, false) {
+            eprintln!("Failed to refresh workspace lock: {}", e);
+        }
+    }
+}
+
+/// On startup: takes the lock on a shared database if it's free and keeps it
+/// fresh while the app runs
+pub fn start(app_handle: AppHandle) {
+    let app_dir = crate::database::app_dir(&app_handle);
+    let location = load_location(&app_dir);
+    if is_shared(&location) {
+        if let Err(e) = acquire(&database_path(&app_dir, &location), &editor_name(&location), false) {
+            eprintln!("Workspace lock not taken: {}", e);
+        }
+    }
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(HEARTBEAT_INTERVAL);
+        let location = load_location(&app_dir);
+        if is_shared(&location) {
+            heartbeat(&database_path(&app_dir, &location));
+        }
+    });
+}
+
+pub fn status(app_dir: &Path) -> SharedWorkspaceStatus {
+    let location = load_location(app_dir);
+    let db_path = database_path(app_dir, &location);
+    let lock = read_lock(&db_path);
+    SharedWorkspaceStatus {
+        database_path: db_path.to_string_lossy().to_string(),
+        shared: is_shared(&location),
+        editor_name: editor_name(&location),
+        held_by_me: lock.as_ref().is_some_and(is_mine),
+        lock_stale: lock.as_ref().is_some_and(|l| is_stale(l, Utc::now())),
+        lock,
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_goes_stale_without_heartbeat() {
+        let now = Utc::now();
+        let lock = |heartbeat: DateTime<Utc>| WorkspaceLock {
+            editor: "Sam".to_string(),
+            host: "other-laptop".to_string(),
+            pid: 1,
+            acquired_at: (now - Duration::hours(2)).to_rfc3339(),
+            heartbeat_at: heartbeat.to_rfc3339(),
+        };
+        assert!(!is_stale(&lock(now - Duration::seconds(30)), now));
+        assert!(is_stale(&lock(now - Duration::minutes(10)), now));
+        assert!(!is_mine(&lock(now)));
+        assert_eq!(lock_path(Path::new("/mnt/team/affilai.db")), PathBuf::from("/mnt/team/affilai.db.lock"));
+    }
+}
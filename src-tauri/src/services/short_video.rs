@@ -0,0 +1,168 @@
+//! Short-Video Companion Output
+//!
+//! Turns a `video_script` ad into the pieces needed to assemble a TikTok or
+//! Reels post: a caption with hashtags, on-screen text overlays per scene with
+//! rough timings, and a placeholder note for picking a trending sound. Scenes
+//! come from the script's `[HOOK]`, `[PROBLEM]`, `[SOLUTION]`, `[BENEFITS]`,
+//! and `[CTA]` sections.
+
+use crate::models::product::Product;
+use crate::models::short_video::{SceneOverlay, ShortVideoCompanion};
+
+pub const CAPTION_MAX: usize = 150;
+const MAX_HASHTAGS: usize = 5;
+const OVERLAY_MAX_WORDS: usize = 7;
+/// Voiceover pace used to estimate how long each scene stays on screen
+const WORDS_PER_SECOND: f64 = 2.5;
+
+/// Splits a script into `(section, text)` pairs; each benefit bullet becomes
+/// its own `BENEFIT` scene and `[NOTE]` sections are dropped
+pub fn parse_scenes(script: &str) -> Vec<(String, String)> {
+    let mut scenes = Vec::new();
+    for block in script.split("\n\n") {
+        let block = block.trim();
+        let Some(rest) = block.strip_prefix('[') else { continue };
+        let Some((tag, text)) = rest.split_once(']') else { continue };
+        let tag = tag.trim().to_uppercase();
+        let text = text.trim();
+
+        match tag.as_str() {
+            "NOTE" => {}
+            "BENEFITS" => scenes.extend(
+                text.lines()
+                    .map(|line| line.trim().trim_start_matches('-').trim())
+                    .filter(|line| !line.is_empty())
+                    .map(|line| ("BENEFIT".to_string(), line.to_string())),
+            ),
+            _ => scenes.push((tag, text.to_string())),
+        }
+    }
+    scenes
+}
+
+/// First sentence of `text`, capped at a few words so it reads at a glance
+pub fn overlay_text(text: &str) -> String {
+    let sentence = text
+        .split_inclusive(['.', '!', '?'])
+        .next()
+        .unwrap_or(text)
+        .trim();
+    let words: Vec<&str> = sentence.split_whitespace().collect();
+    if words.len() <= OVERLAY_MAX_WORDS {
+        sentence.to_string()
+    } else {
+        words[..OVERLAY_MAX_WORDS].join(" ")
+    }
+}
+
+fn scene_seconds(spoken: &str) -> u32 {
+    let words = spoken.split_whitespace().count() as f64;
+    ((words / WORDS_PER_SECOND).ceil() as u32).clamp(2, 6)
+}
+
+pub fn hashtags(product: &Product) -> Vec<String> {
+    let category_tags: &[&str] = match product.category.as_str() {
+        "Beauty & Skincare" => &["#skincare", "#beautytok"],
+        "Health & Wellness" => &["#wellness", "#healthtok"],
+        "Fitness & Recovery" => &["#fitness", "#gymtok"],
+        "Consumer Electronics" | "Wearable Health Technology" => &["#tech", "#gadgets"],
+        "Fashion & Apparel" => &["#ootd", "#fashiontok"],
+        "Home & Kitchen" => &["#homefinds", "#kitchenhacks"],
+        _ => &["#musthave"],
+    };
+    let product_tag: String = product
+        .name
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+
+    let mut tags: Vec<String> = category_tags.iter().map(|t| t.to_string()).collect();
+    if !product_tag.is_empty() {
+        tags.push(format!("#{}", product_tag));
+    }
+    tags.extend(["#tiktokmademebuyit".to_string(), "#fyp".to_string()]);
+    tags.truncate(MAX_HASHTAGS);
+    tags
+}
+
+/// Builds the companion output for a generated video script
+pub fn build_companion(
+    product: &Product,
+    headline: &str,
+    script: &str,
+    selling_points: &[String],
+) -> ShortVideoCompanion {
+    let mut start_seconds = 0;
+    let overlays = parse_scenes(script)
+        .into_iter()
+        .map(|(scene, spoken)| {
+            // The hook overlay is the headline; the solution scene just names the product
+            let text = match scene.as_str() {
+                "HOOK" => overlay_text(headline),
+                "SOLUTION" => product.name.clone(),
+                "CTA" => "Link in bio".to_string(),
+                _ => overlay_text(&spoken),
+            };
+            let duration_seconds = scene_seconds(&spoken);
+            let overlay = SceneOverlay {
+                scene,
+                text,
+                start_seconds,
+                duration_seconds,
+            };
+            start_seconds += duration_seconds;
+            overlay
+        })
+        .collect();
+
+    let benefit = selling_points
+        .first()
+        .map(|b| b.trim_end_matches('.').to_string())
+        .unwrap_or_else(|| "worth the hype".to_string());
+    let caption: String = format!("{}: {}. Link in bio!", product.name, benefit);
+    let caption = if caption.chars().count() > CAPTION_MAX {
+        format!("{}…", caption.chars().take(CAPTION_MAX - 1).collect::<String>().trim_end())
+    } else {
+        caption
+    };
+
+    ShortVideoCompanion {
+        caption,
+        hashtags: hashtags(product),
+        overlays,
+        sound_note: format!(
+            "[TRENDING SOUND] Pick a sound trending in {} content on the day you post; keep it under the voiceover.",
+            product.category.to_lowercase()
+        ),
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scenes_splits_benefits() {
+        let script = "[HOOK] Wait, you don't know about X yet?\n\n\
+                      [PROBLEM] Dry skin all winter.\n\n\
+                      [BENEFITS]\n- Hydrates\n- Brightens\n\n\
+                      [CTA] Link in bio!\n\n\
+                      [NOTE] Keep it upbeat";
+        let tags: Vec<String> = parse_scenes(script).into_iter().map(|(tag, _)| tag).collect();
+        assert_eq!(tags, vec!["HOOK", "PROBLEM", "BENEFIT", "BENEFIT", "CTA"]);
+    }
+
+    #[test]
+    fn test_overlay_text_is_short() {
+        assert_eq!(overlay_text("Dry skin all winter. Nothing helps."), "Dry skin all winter.");
+        assert_eq!(
+            overlay_text("This serum keeps your skin hydrated for a full twenty four hours"),
+            "This serum keeps your skin hydrated for"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! SMS Compliance
+//!
+//! Checks SMS ads before they leave the app: every body must carry opt-out
+//! text, the segment count is computed the way carriers bill it (GSM-7:
+//! 160 characters, 306 for two segments, 153 per segment after that; UCS-2:
+//! 70/67), and send times are validated against quiet hours in the configured
+//! timezone. Campaigns export per contact list in Twilio or EZTexting CSV layout.
+
+use crate::database::settings::{get_setting_or, get_setting_parsed};
+use crate::models::sms::{SmsContact, SmsSegments};
+use chrono::{DateTime, Timelike, Utc};
+use chrono_tz::Tz;
+use regex::Regex;
+use rusqlite::Connection;
+
+/// Appended to SMS bodies that don't already tell recipients how to opt out
+pub const OPT_OUT_TEXT: &str = "Reply STOP to unsubscribe";
+
+/// Default quiet hours (local time): no messages from 21:00 until 08:00
+pub const DEFAULT_QUIET_START_HOUR: u32 = 21;
+pub const DEFAULT_QUIET_END_HOUR: u32 = 8;
+
+const GSM7_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞ\u{1b}ÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+                          ¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+const GSM7_EXTENDED: &str = "^{}\\[~]|€\u{c}";
+
+/// True when the body already contains an opt-out keyword
+pub fn has_opt_out(body: &str) -> bool {
+    Regex::new(r"(?i)\b(STOP|UNSUBSCRIBE|OPT[ -]?OUT)\b")
+        .map(|re| re.is_match(body))
+        .unwrap_or(false)
+}
+
+/// Returns the body with opt-out text appended when missing, and whether it was added
+pub fn ensure_opt_out(body: &str) -> (String, bool) {
+    if has_opt_out(body) {
+        return (body.to_string(), false);
+    }
+    let body = body.trim_end();
+    let separator = if body.ends_with(['.', '!', '?']) || body.is_empty() { " " } else { ". " };
+    (format!("{}{}{}", body, separator, OPT_OUT_TEXT).trim_start().to_string(), true)
+}
+
+/// Counts billable characters and segments for a message body
+pub fn segment_info(body: &str) -> SmsSegments {
+    let is_gsm7 = body
+        .chars()
+        .all(|c| GSM7_BASIC.contains(c) || GSM7_EXTENDED.contains(c));
+
+    if is_gsm7 {
+        let characters = body
+            .chars()
+            .map(|c| if GSM7_EXTENDED.contains(c) { 2 } else { 1 })
+            .sum();
+        SmsSegments {
+            encoding: "gsm7".to_string(),
+            characters,
+            segments: segments(characters, 160, 153),
+        }
+    } else {
+        let characters = body.encode_utf16().count();
+        SmsSegments {
+            encoding: "ucs2".to_string(),
+            characters,
+            segments: segments(characters, 70, 67),
+        }
+    }
+}
+
+fn segments(characters: usize, single: usize, per_part: usize) -> usize {
+    match characters {
+        0 => 0,
+        n if n <= single => 1,
+        n => n.div_ceil(per_part),
+    }
+}
+
+/// Quiet-hours window in a configured timezone
+#[derive(Debug, Clone, Copy)]
+pub struct QuietHours {
+    pub timezone: Tz,
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+impl QuietHours {
+    /// True when `send_at` falls inside the window (which may wrap past midnight)
+    pub fn contains(&self, send_at: DateTime<Utc>) -> bool {
+        let hour = send_at.with_timezone(&self.timezone).hour();
+        if self.start_hour == self.end_hour {
+            false
+        } else if self.start_hour > self.end_hour {
+            hour >= self.start_hour || hour < self.end_hour
+        } else {
+            hour >= self.start_hour && hour < self.end_hour
+        }
+    }
+}
+
+/// Reads `sms_timezone` (IANA name, default UTC) and `sms_quiet_start_hour` /
+/// `sms_quiet_end_hour` from settings
+pub fn load_quiet_hours(conn: &Connection) -> Result<QuietHours, String> {
+    let name = get_setting_or(conn, "sms_timezone", "UTC");
+    let timezone = name
+        .parse::<Tz>()
+        .map_err(|_| format!("Invalid SMS timezone setting: {}", name))?;
+
+    Ok(QuietHours {
+        timezone,
+        start_hour: get_setting_parsed(conn, "sms_quiet_start_hour", DEFAULT_QUIET_START_HOUR) % 24,
+        end_hour: get_setting_parsed(conn, "sms_quiet_end_hour", DEFAULT_QUIET_END_HOUR) % 24,
+    })
+}
+
+/// Parses an RFC 3339 send time
+pub fn parse_send_at(send_at: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(send_at)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid send time '{}': {}", send_at, e))
+}
+
+/// Normalizes a phone number to E.164; bare 10-digit numbers are treated as US
+pub fn normalize_phone(raw: &str) -> Option<String> {
+    let digits: String = raw.chars().filter(|c| c.is_ascii_digit()).collect();
+    let international = raw.trim_start().starts_with('+');
+
+    match digits.len() {
+        8..=15 if international => Some(format!("+{}", digits)),
+        10 => Some(format!("+1{}", digits)),
+        11 if digits.starts_with('1') => Some(format!("+{}", digits)),
+        _ => None,
+    }
+}
+
+/// SMS provider CSV layouts supported by `to_csv`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SmsExportFormat {
+    Twilio,
+    EzTexting,
+}
+
+impl SmsExportFormat {
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "twilio" => Some(SmsExportFormat::Twilio),
+            "eztexting" | "ez_texting" | "ez-texting" => Some(SmsExportFormat::EzTexting),
+            _ => None,
+        }
+    }
+}
+
+/// Renders one row per valid contact. Twilio takes E.164 numbers and an
+/// ISO 8601 send time; EZTexting takes US numbers without the country code
+/// and a send date in the account's local `timezone`.
+/// Returns the CSV, the number of exported rows, and the skipped phone numbers.
+pub fn to_csv(
+    format: SmsExportFormat,
+    contacts: &[SmsContact],
+    body: &str,
+    send_at: Option<DateTime<Utc>>,
+    timezone: Tz,
+) -> Result<(String, usize, Vec<String>), String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let headers: &[&str] = match format {
+        SmsExportFormat::Twilio => &["To", "Body", "SendAt"],
+        SmsExportFormat::EzTexting => &["PhoneNumber", "FirstName", "Message", "SendDate"],
+    };
+    writer.write_record(headers).map_err(|e| e.to_string())?;
+
+    let mut exported = 0;
+    let mut skipped = Vec::new();
+    for contact in contacts {
+        let phone = match normalize_phone(&contact.phone) {
+            Some(phone) if format == SmsExportFormat::Twilio => phone,
+            Some(phone) if phone.starts_with("+1") && phone.len() == 12 => phone[2..].to_string(),
+            _ => {
+                skipped.push(contact.phone.clone());
+                continue;
+            }
+        };
+
+        let record = match format {
+            SmsExportFormat::Twilio => vec![
+                phone,
+                body.to_string(),
+                send_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
+            ],
+            SmsExportFormat::EzTexting => vec![
+                phone,
+                contact.first_name.clone().unwrap_or_default(),
+                body.to_string(),
+                send_at
+                    .map(|dt| dt.with_timezone(&timezone).format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default(),
+            ],
+        };
+        writer.write_record(&record).map_err(|e| e.to_string())?;
+        exported += 1;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    let csv = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+    Ok((csv, exported, skipped))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_segment_info() {
+        assert_eq!(segment_info(&"a".repeat(160)).segments, 1);
+        assert_eq!(segment_info(&"a".repeat(161)).segments, 2);
+        assert_eq!(segment_info(&"a".repeat(306)).segments, 2);
+        assert_eq!(segment_info(&"a".repeat(307)).segments, 3);
+
+        let extended = segment_info("Save 20€ [today]");
+        assert_eq!((extended.encoding.as_str(), extended.characters), ("gsm7", 19));
+
+        let emoji = segment_info(&format!("{} 🔥", "a".repeat(68)));
+        assert_eq!((emoji.encoding.as_str(), emoji.characters, emoji.segments), ("ucs2", 71, 2));
+    }
+
+    #[test]
+    fn test_ensure_opt_out() {
+        let (body, added) = ensure_opt_out("Back in stock! Get yours: https://x.co/a");
+        assert!(added);
+        assert!(body.ends_with(". Reply STOP to unsubscribe"));
+        assert!(!ensure_opt_out("Deal today. Text STOP to quit").1);
+    }
+
+    #[test]
+    fn test_quiet_hours_use_local_time() {
+        let quiet = QuietHours {
+            timezone: "America/New_York".parse().unwrap(),
+            start_hour: 21,
+            end_hour: 8,
+        };
+        // 01:30 UTC in July is 21:30 in New York
+        assert!(quiet.contains(Utc.with_ymd_and_hms(2024, 7, 2, 1, 30, 0).unwrap()));
+        assert!(!quiet.contains(Utc.with_ymd_and_hms(2024, 7, 2, 16, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_eztexting_csv_skips_non_us_numbers() {
+        let contacts = vec![
+            SmsContact { phone: "(555) 123-4567".to_string(), first_name: Some("Ana".to_string()) },
+            SmsContact { phone: "+44 7700 900123".to_string(), first_name: None },
+        ];
+        let (csv, exported, skipped) = to_csv(SmsExportFormat::EzTexting, &contacts, "Hi", None, Tz::UTC).unwrap();
+        assert_eq!(exported, 1);
+        assert_eq!(skipped, vec!["+44 7700 900123".to_string()]);
+        assert!(csv.contains("5551234567,Ana,Hi,"));
+    }
+}
@@ -0,0 +1,798 @@
+//! Spreadsheet Import Assistant
+//!
+//! Moves a catalog kept in spreadsheets into the app in two steps. Analysis
+//! reads a CSV/TSV export, detects its delimiter and columns, and suggests
+//! which column fills which field for each kind of import — products, links,
+//! or historical earnings — from the headers alone. The user reviews the
+//! mapping, and the plan is executed in one transaction: rows that are
+//! missing values, don't match a product or link, or are already in the
+//! workspace are skipped and reported, so a file can be imported again after
+//! fixing it. Back-dated earnings mark their days for re-materializing.
+
+use crate::database::parse_timestamp;
+use crate::models::spreadsheet_import::{
+    ColumnMapping, ImportAnalysis, ImportField, ImportPlan, ImportResult, ImportRowSkip, ImportTargetSuggestion,
+};
+use crate::services::daily_metrics;
+use crate::services::scraper::detect_platform;
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub const TARGETS: [&str; 3] = ["products", "links", "earnings"];
+
+/// Rows returned with an analysis for previewing
+const SAMPLE_ROWS: usize = 5;
+const DELIMITERS: [u8; 3] = [b',', b';', b'\t'];
+
+/// A field and the normalized headers that suggest it
+struct FieldSpec {
+    name: &'static str,
+    label: &'static str,
+    aliases: &'static [&'static str],
+}
+
+const PRODUCT_FIELDS: [FieldSpec; 11] = [
+    FieldSpec { name: "name", label: "Name", aliases: &["name", "product", "product_name", "title", "item"] },
+    FieldSpec { name: "category", label: "Category", aliases: &["category", "niche", "department", "type"] },
+    FieldSpec { name: "description", label: "Description", aliases: &["description", "details", "summary"] },
+    FieldSpec { name: "price_range", label: "Price", aliases: &["price", "price_range", "cost", "msrp"] },
+    FieldSpec { name: "target_audience", label: "Target audience", aliases: &["audience", "target_audience"] },
+    FieldSpec { name: "product_url", label: "Product URL", aliases: &["product_url", "url", "link", "page"] },
+    FieldSpec { name: "image_url", label: "Image URL", aliases: &["image", "image_url", "photo", "thumbnail"] },
+    FieldSpec { name: "amazon_asin", label: "ASIN", aliases: &["asin", "amazon_asin"] },
+    FieldSpec { name: "tags", label: "Tags", aliases: &["tags", "labels", "keywords"] },
+    FieldSpec { name: "notes", label: "Notes", aliases: &["notes", "comments", "note"] },
+    FieldSpec { name: "created_at", label: "Date added", aliases: &["date_added", "added", "created", "created_at"] },
+];
+
+const LINK_FIELDS: [FieldSpec; 9] = [
+    FieldSpec { name: "product", label: "Product", aliases: &["product", "product_name", "name", "item", "title"] },
+    FieldSpec { name: "product_id", label: "Product ID", aliases: &["product_id"] },
+    FieldSpec {
+        name: "tracking_url",
+        label: "Tracking URL",
+        aliases: &["tracking_url", "affiliate_link", "affiliate_url", "tracking_link", "short_link", "link", "url"],
+    },
+    FieldSpec {
+        name: "destination_url",
+        label: "Destination URL",
+        aliases: &["destination_url", "destination", "product_url", "landing_page"],
+    },
+    FieldSpec { name: "platform", label: "Platform", aliases: &["platform", "network", "store", "marketplace"] },
+    FieldSpec { name: "program_name", label: "Program", aliases: &["program", "program_name", "affiliate_program"] },
+    FieldSpec {
+        name: "commission_rate",
+        label: "Commission rate",
+        aliases: &["commission_rate", "commission", "rate", "commission_percent"],
+    },
+    FieldSpec { name: "cookie_duration", label: "Cookie days", aliases: &["cookie", "cookie_days", "cookie_duration"] },
+    FieldSpec { name: "status", label: "Status", aliases: &["status", "link_status"] },
+];
+
+const EARNING_FIELDS: [FieldSpec; 10] = [
+    FieldSpec {
+        name: "date",
+        label: "Date",
+        aliases: &["date", "day", "order_date", "transaction_date", "converted_at", "sale_date"],
+    },
+    FieldSpec {
+        name: "commission",
+        label: "Commission",
+        aliases: &["commission", "earnings", "payout", "commission_amount", "amount", "earned"],
+    },
+    FieldSpec {
+        name: "order_value",
+        label: "Order value",
+        aliases: &["order_value", "sale_amount", "revenue", "sales", "order_total", "total"],
+    },
+    FieldSpec {
+        name: "order_id",
+        label: "Order ID",
+        aliases: &["order_id", "order", "transaction_id", "order_number"],
+    },
+    FieldSpec { name: "status", label: "Status", aliases: &["status", "state"] },
+    FieldSpec { name: "link_id", label: "Link ID", aliases: &["link_id"] },
+    FieldSpec {
+        name: "tracking_url",
+        label: "Tracking URL",
+        aliases: &["tracking_url", "affiliate_link", "affiliate_url", "link", "url"],
+    },
+    FieldSpec { name: "product", label: "Product", aliases: &["product", "product_name", "item", "title", "name"] },
+    FieldSpec { name: "product_id", label: "Product ID", aliases: &["product_id"] },
+    FieldSpec { name: "platform", label: "Platform", aliases: &["platform", "network", "store", "marketplace"] },
+];
+
+fn field_specs(target: &str) -> Result<&'static [FieldSpec], String> {
+    match target {
+        "products" => Ok(&PRODUCT_FIELDS),
+        "links" => Ok(&LINK_FIELDS),
+        "earnings" => Ok(&EARNING_FIELDS),
+        other => Err(format!("Unknown import target '{}'; use {}", other, TARGETS.join(", "))),
+    }
+}
+
+/// Fields a row can't be imported without; each entry lists alternatives
+fn requirements(target: &str) -> &'static [&'static [&'static str]] {
+    match target {
+        "products" => &[&["name"]],
+        "links" => &[&["tracking_url"], &["product", "product_id"]],
+        "earnings" => &[&["date"], &["commission"], &["link_id", "tracking_url", "product", "product_id"]],
+        _ => &[],
+    }
+}
+
+fn label(specs: &[FieldSpec], name: &str) -> String {
+    specs.iter().find(|spec| spec.name == name).map(|spec| spec.label).unwrap_or(name).to_string()
+}
+
+/// Requirements none of whose fields are mapped, as "A or B" labels
+fn missing_requirements(target: &str, mapped: &[&str]) -> Vec<String> {
+    let specs = field_specs(target).unwrap_or(&[]);
+    requirements(target)
+        .iter()
+        .filter(|options| !options.iter().any(|field| mapped.contains(field)))
+        .map(|options| options.iter().map(|field| label(specs, field)).collect::<Vec<_>>().join(" or "))
+        .collect()
+}
+
+// =============================================================================
+// READING
+// =============================================================================
+
+/// A parsed spreadsheet: headers plus rows padded or cut to their length
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sheet {
+    pub delimiter: u8,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// The delimiter used most often on the header line, outside quotes
+pub fn detect_delimiter(data: &str) -> u8 {
+    let mut counts = [0usize; DELIMITERS.len()];
+    let mut quoted = false;
+    for byte in data.lines().next().unwrap_or("").bytes() {
+        if byte == b'"' {
+            quoted = !quoted;
+        } else if !quoted {
+            if let Some(index) = DELIMITERS.iter().position(|d| *d == byte) {
+                counts[index] += 1;
+            }
+        }
+    }
+    // Ties go to the comma, the first entry
+    let best = (0..DELIMITERS.len()).rev().max_by_key(|&i| counts[i]).unwrap_or(0);
+    DELIMITERS[best]
+}
+
+/// Parses CSV text, skipping blank rows
+pub fn parse_sheet(data: &str) -> Result<Sheet, String> {
+    let delimiter = detect_delimiter(data);
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(data.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Invalid header row: {}", e))?
+        .iter()
+        .map(|h| h.trim_start_matches('\u{FEFF}').to_string())
+        .collect();
+    if headers.iter().all(|h| h.is_empty()) {
+        return Err("The file has no header row".to_string());
+    }
+
+    let mut rows = Vec::new();
+    for (idx, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| format!("Row {}: {}", idx + 1, e))?;
+        if record.iter().all(|value| value.is_empty()) {
+            continue;
+        }
+        let mut row: Vec<String> = record.iter().take(headers.len()).map(String::from).collect();
+        row.resize(headers.len(), String::new());
+        rows.push(row);
+    }
+    Ok(Sheet { delimiter, headers, rows })
+}
+
+/// Reads a .csv, .tsv, or .txt export
+pub fn read_sheet(path: &Path) -> Result<Sheet, String> {
+    match path.extension().and_then(|e| e.to_str()).map(str::to_lowercase).as_deref() {
+        Some("csv" | "tsv" | "txt") => {}
+        Some("xlsx" | "xls" | "ods" | "numbers") => {
+            return Err("Workbooks aren't read directly; save the sheet as CSV and import that".to_string());
+        }
+        _ => return Err("Unsupported file type; expected .csv, .tsv, or .txt".to_string()),
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_sheet(&String::from_utf8_lossy(&bytes))
+}
+
+// =============================================================================
+// ANALYSIS
+// =============================================================================
+
+/// Lowercase words joined by underscores: "Commission ($)" -> "commission"
+fn normalize_header(header: &str) -> String {
+    header
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Columns recognized for `target`: exact alias matches first, then headers
+/// that start or end with an alias ("Affiliate Link URL"). Each field and
+/// each column is used once.
+pub fn suggest_mappings(headers: &[String], target: &str) -> Result<Vec<ColumnMapping>, String> {
+    let specs = field_specs(target)?;
+    let normalized: Vec<String> = headers.iter().map(|h| normalize_header(h)).collect();
+    let mut mappings: Vec<ColumnMapping> = Vec::new();
+
+    let matchers: [fn(&str, &str) -> bool; 2] = [
+        |header, alias| header == alias,
+        |header, alias| {
+            header.starts_with(&format!("{}_", alias)) || header.ends_with(&format!("_{}", alias))
+        },
+    ];
+    for matches in matchers {
+        for spec in specs {
+            if mappings.iter().any(|m| m.field == spec.name) {
+                continue;
+            }
+            let found = spec.aliases.iter().find_map(|alias| {
+                normalized.iter().enumerate().position(|(column, header)| {
+                    !mappings.iter().any(|m| m.column == column) && matches(header, alias)
+                })
+            });
+            if let Some(column) = found {
+                mappings.push(ColumnMapping { column, field: spec.name.to_string() });
+            }
+        }
+    }
+    mappings.sort_by_key(|m| m.column);
+    Ok(mappings)
+}
+
+/// Columns, sample rows, and a suggested mapping for every kind of import
+pub fn analyze(sheet: &Sheet, path: &str) -> ImportAnalysis {
+    let mut targets = Vec::new();
+    let mut suggested: Option<(usize, &str)> = None;
+    for target in TARGETS {
+        let specs = field_specs(target).unwrap_or(&[]);
+        let mappings = suggest_mappings(&sheet.headers, target).unwrap_or_default();
+        let mapped: Vec<&str> = mappings.iter().map(|m| m.field.as_str()).collect();
+        let missing_required = missing_requirements(target, &mapped);
+        // The complete target recognizing the most columns; ties keep the earlier one
+        if missing_required.is_empty() && suggested.is_none_or(|(count, _)| mappings.len() > count) {
+            suggested = Some((mappings.len(), target));
+        }
+        let fields = specs
+            .iter()
+            .map(|spec| ImportField {
+                name: spec.name.to_string(),
+                label: spec.label.to_string(),
+                required: requirements(target).contains(&[spec.name].as_slice()),
+            })
+            .collect();
+        targets.push(ImportTargetSuggestion { target: target.to_string(), fields, mappings, missing_required });
+    }
+
+    ImportAnalysis {
+        path: path.to_string(),
+        delimiter: (sheet.delimiter as char).to_string(),
+        columns: sheet.headers.clone(),
+        row_count: sheet.rows.len(),
+        sample_rows: sheet.rows.iter().take(SAMPLE_ROWS).cloned().collect(),
+        suggested_target: suggested.map(|(_, target)| target.to_string()),
+        targets,
+    }
+}
+
+// =============================================================================
+// VALUE PARSING
+// =============================================================================
+
+/// A number written with currency symbols, thousands separators, or a
+/// decimal comma: "$1,234.50", "1.234,50 €", "12,5"
+pub fn parse_number(value: &str) -> Option<f64> {
+    let negative = value.contains('-') || (value.starts_with('(') && value.ends_with(')'));
+    let digits: String = value.chars().filter(|c| c.is_ascii_digit() || *c == '.' || *c == ',').collect();
+    let normalized = match (digits.rfind('.'), digits.rfind(',')) {
+        (Some(dot), Some(comma)) if comma > dot => digits.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => digits.replace(',', ""),
+        (None, Some(comma)) if digits.len() - comma - 1 != 3 => digits.replace(',', "."),
+        _ => digits.replace(',', ""),
+    };
+    let number: f64 = normalized.parse().ok()?;
+    Some(if negative { -number } else { number })
+}
+
+/// A commission rate as a fraction; "8%", "8", and "0.08" are all 0.08
+pub fn parse_rate(value: &str) -> Option<f64> {
+    let number = parse_number(value)?;
+    Some(if value.contains('%') || number > 1.0 { number / 100.0 } else { number })
+}
+
+/// A date or timestamp in the formats spreadsheets export; month-first
+/// wins when a slashed date could be either
+pub fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    if let Some(timestamp) = parse_timestamp(value) {
+        return Some(timestamp);
+    }
+    const TIMESTAMP_FORMATS: [&str; 3] = ["%Y-%m-%d %H:%M", "%m/%d/%Y %H:%M:%S", "%m/%d/%Y %H:%M"];
+    const DATE_FORMATS: [&str; 7] =
+        ["%Y-%m-%d", "%Y/%m/%d", "%m/%d/%Y", "%d/%m/%Y", "%d.%m.%Y", "%b %d, %Y", "%d %b %Y"];
+    TIMESTAMP_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+/// Network statuses mapped onto 'pending', 'approved', or 'rejected'
+fn conversion_status(value: Option<&str>) -> Option<&'static str> {
+    match value.map(str::to_lowercase).as_deref() {
+        None => Some("approved"),
+        Some("approved" | "paid" | "locked" | "confirmed" | "closed" | "complete" | "completed") => Some("approved"),
+        Some("pending" | "open" | "unconfirmed" | "new") => Some("pending"),
+        Some("rejected" | "declined" | "reversed" | "returned" | "cancelled" | "canceled" | "void") => {
+            Some("rejected")
+        }
+        Some(_) => None,
+    }
+}
+
+fn link_status(value: Option<&str>) -> Option<&'static str> {
+    match value.map(str::to_lowercase).as_deref() {
+        None | Some("active" | "live" | "enabled") => Some("active"),
+        Some("expired" | "paused" | "inactive" | "disabled") => Some("expired"),
+        Some("invalid" | "broken") => Some("invalid"),
+        Some(_) => None,
+    }
+}
+
+fn timestamp(value: NaiveDateTime) -> String {
+    value.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+// =============================================================================
+// EXECUTION
+// =============================================================================
+
+/// A row's non-empty values by field
+type Values<'a> = HashMap<&'a str, &'a str>;
+
+/// Checks the plan against the file and the target's fields
+fn validate(sheet: &Sheet, plan: &ImportPlan) -> Result<(), String> {
+    let specs = field_specs(&plan.target)?;
+    let mut mapped: Vec<&str> = Vec::new();
+    for mapping in &plan.mappings {
+        if mapping.column >= sheet.headers.len() {
+            return Err(format!("Column {} is not in the file", mapping.column + 1));
+        }
+        if !specs.iter().any(|spec| spec.name == mapping.field) {
+            return Err(format!("'{}' is not a field of {} imports", mapping.field, plan.target));
+        }
+        if mapped.contains(&mapping.field.as_str()) {
+            return Err(format!("{} is mapped to more than one column", label(specs, &mapping.field)));
+        }
+        mapped.push(&mapping.field);
+    }
+    let missing = missing_requirements(&plan.target, &mapped);
+    if !missing.is_empty() {
+        return Err(format!("Map a column to {}", missing.join(", ")));
+    }
+    Ok(())
+}
+
+/// Runs a plan against an already read sheet in one transaction
+pub fn execute(
+    conn: &mut Connection,
+    sheet: &Sheet,
+    plan: &ImportPlan,
+    editor: Option<&str>,
+) -> Result<ImportResult, String> {
+    validate(sheet, plan)?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let mut result = ImportResult { target: plan.target.clone(), ..Default::default() };
+    let mut earliest_day: Option<NaiveDate> = None;
+
+    for (idx, row) in sheet.rows.iter().enumerate() {
+        let values: Values = plan
+            .mappings
+            .iter()
+            .filter_map(|m| Some((m.field.as_str(), row.get(m.column).map(|v| v.trim()).filter(|v| !v.is_empty())?)))
+            .collect();
+        let outcome = match plan.target.as_str() {
+            "products" => import_product(&tx, &values, editor),
+            "links" => import_link(&tx, &values, plan.create_missing_products, editor, &mut result.created_product_ids),
+            _ => import_earning(&tx, &values).map(|outcome| {
+                outcome.map(|(id, day)| {
+                    earliest_day = Some(earliest_day.map_or(day, |earliest| earliest.min(day)));
+                    id
+                })
+            }),
+        }
+        .map_err(|e| format!("Failed to import row {}: {}", idx + 1, e))?;
+
+        match outcome {
+            Ok(id) => {
+                result.imported += 1;
+                result.imported_ids.push(id);
+            }
+            Err(reason) => result.skipped.push(ImportRowSkip { row: idx + 1, reason }),
+        }
+    }
+
+    if let Some(day) = earliest_day {
+        daily_metrics::mark_stale(&tx, day).map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+/// The row's id, or the reason it was skipped
+type RowOutcome<T = i64> = rusqlite::Result<Result<T, String>>;
+
+fn import_product(tx: &Transaction, values: &Values, editor: Option<&str>) -> RowOutcome {
+    let Some(name) = values.get("name").copied() else {
+        return Ok(Err("Missing name".to_string()));
+    };
+    let exists: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM products WHERE name = ?1 COLLATE NOCASE)",
+        params![name],
+        |row| row.get(0),
+    )?;
+    if exists {
+        return Ok(Err(format!("{} is already in the catalog", name)));
+    }
+    let created_at = match values.get("created_at") {
+        Some(value) => match parse_date(value) {
+            Some(date) => Some(timestamp(date)),
+            None => return Ok(Err(format!("Unrecognized date '{}'", value))),
+        },
+        None => None,
+    };
+    let mut tags: Vec<String> = Vec::new();
+    for tag in values.get("tags").map(|t| t.split([',', ';', '|'])).into_iter().flatten() {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    tx.execute(
+        "INSERT INTO products (name, category, description, price_range, target_audience, product_url,
+         image_url, amazon_asin, tags, notes, updated_by, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, COALESCE(?12, CURRENT_TIMESTAMP))",
+        params![
+            name,
+            values.get("category").copied().unwrap_or("Uncategorized"),
+            values.get("description"),
+            values.get("price_range"),
+            values.get("target_audience"),
+            values.get("product_url"),
+            values.get("image_url"),
+            values.get("amazon_asin").map(|asin| asin.to_uppercase()),
+            serde_json::to_string(&tags).unwrap_or_else(|_| "[]".to_string()),
+            values.get("notes"),
+            editor,
+            created_at,
+        ],
+    )?;
+    Ok(Ok(tx.last_insert_rowid()))
+}
+
+/// Product for a row: by ID when it exists, otherwise by exact name
+fn resolve_product(tx: &Transaction, values: &Values) -> rusqlite::Result<Option<(i64, String)>> {
+    if let Some(product_id) = values.get("product_id").and_then(|id| id.parse::<i64>().ok()) {
+        let found = tx
+            .query_row("SELECT id, name FROM products WHERE id = ?1", params![product_id], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .optional()?;
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+    match values.get("product") {
+        Some(name) => tx
+            .query_row(
+                "SELECT id, name FROM products WHERE name = ?1 COLLATE NOCASE ORDER BY id LIMIT 1",
+                params![name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional(),
+        None => Ok(None),
+    }
+}
+
+fn import_link(
+    tx: &Transaction,
+    values: &Values,
+    create_missing_products: bool,
+    editor: Option<&str>,
+    created_product_ids: &mut Vec<i64>,
+) -> RowOutcome {
+    let Some(tracking_url) = values.get("tracking_url").copied() else {
+        return Ok(Err("Missing tracking URL".to_string()));
+    };
+    let exists: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM affiliate_links WHERE tracking_url = ?1)",
+        params![tracking_url],
+        |row| row.get(0),
+    )?;
+    if exists {
+        return Ok(Err(format!("{} is already a link", tracking_url)));
+    }
+    let commission_rate = match values.get("commission_rate") {
+        Some(value) => match parse_rate(value) {
+            Some(rate) => Some(rate),
+            None => return Ok(Err(format!("Unrecognized commission rate '{}'", value))),
+        },
+        None => None,
+    };
+    let cookie_duration = values.get("cookie_duration").and_then(|v| parse_number(v)).map(|days| days.round() as i64);
+    let Some(status) = link_status(values.get("status").copied()) else {
+        return Ok(Err(format!("Unrecognized link status '{}'", values["status"])));
+    };
+
+    let (product_id, product_name) = match resolve_product(tx, values)? {
+        Some(product) => product,
+        None => match values.get("product").copied() {
+            Some(name) if create_missing_products => {
+                tx.execute(
+                    "INSERT INTO products (name, category, product_url, tags, updated_by)
+                     VALUES (?1, 'Uncategorized', ?2, '[]', ?3)",
+                    params![name, values.get("destination_url"), editor],
+                )?;
+                created_product_ids.push(tx.last_insert_rowid());
+                (tx.last_insert_rowid(), name.to_string())
+            }
+            Some(name) => return Ok(Err(format!("No product matches {}", name))),
+            None => return Ok(Err(format!("No product matches #{}", values.get("product_id").unwrap_or(&"")))),
+        },
+    };
+
+    let destination_url = values.get("destination_url").copied().unwrap_or(tracking_url);
+    let platform = values
+        .get("platform")
+        .map(|p| p.to_lowercase())
+        .or_else(|| detect_platform(destination_url).or_else(|| detect_platform(tracking_url)).map(String::from))
+        .unwrap_or_else(|| "other".to_string());
+    tx.execute(
+        "INSERT INTO affiliate_links (product_id, product_name, platform, program_name, commission_rate,
+         cookie_duration, tracking_url, destination_url, status)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            product_id,
+            product_name,
+            platform,
+            values.get("program_name").copied().unwrap_or("Imported"),
+            commission_rate,
+            cookie_duration,
+            tracking_url,
+            destination_url,
+            status,
+        ],
+    )?;
+    Ok(Ok(tx.last_insert_rowid()))
+}
+
+/// Link for an earnings row: by ID, then tracking URL, then the product's
+/// link on the row's platform, preferring active ones
+fn resolve_link(tx: &Transaction, values: &Values) -> rusqlite::Result<Option<i64>> {
+    if let Some(link_id) = values.get("link_id").and_then(|id| id.parse::<i64>().ok()) {
+        let found = tx
+            .query_row("SELECT id FROM affiliate_links WHERE id = ?1", params![link_id], |row| row.get(0))
+            .optional()?;
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+    if let Some(url) = values.get("tracking_url") {
+        let found = tx
+            .query_row(
+                "SELECT id FROM affiliate_links WHERE tracking_url = ?1 ORDER BY id LIMIT 1",
+                params![url],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+    let Some((product_id, _)) = resolve_product(tx, values)? else {
+        return Ok(None);
+    };
+    tx.query_row(
+        "SELECT id FROM affiliate_links WHERE product_id = ?1 AND (?2 IS NULL OR platform = ?2 COLLATE NOCASE)
+         ORDER BY status = 'active' DESC, id LIMIT 1",
+        params![product_id, values.get("platform")],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+fn import_earning(tx: &Transaction, values: &Values) -> RowOutcome<(i64, NaiveDate)> {
+    let Some(converted_at) = values.get("date").map(|d| parse_date(d)) else {
+        return Ok(Err("Missing date".to_string()));
+    };
+    let Some(converted_at) = converted_at else {
+        return Ok(Err(format!("Unrecognized date '{}'", values["date"])));
+    };
+    let Some(commission) = values.get("commission").and_then(|c| parse_number(c)) else {
+        return Ok(Err("Missing or unrecognized commission".to_string()));
+    };
+    let order_value = values.get("order_value").and_then(|v| parse_number(v));
+    let Some(status) = conversion_status(values.get("status").copied()) else {
+        return Ok(Err(format!("Unrecognized status '{}'", values["status"])));
+    };
+    let Some(link_id) = resolve_link(tx, values)? else {
+        let wanted = ["tracking_url", "product", "product_id", "link_id"]
+            .iter()
+            .find_map(|field| values.get(field))
+            .unwrap_or(&"(none)");
+        return Ok(Err(format!("No link matches {}; import links first", wanted)));
+    };
+
+    let order_id = values.get("order_id").copied();
+    let converted_at_text = timestamp(converted_at);
+    let duplicate: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM conversion_events WHERE link_id = ?1 AND
+         CASE WHEN ?2 IS NOT NULL THEN order_id = ?2 ELSE converted_at = ?3 AND commission = ?4 END)",
+        params![link_id, order_id, converted_at_text, commission],
+        |row| row.get(0),
+    )?;
+    if duplicate {
+        return Ok(Err("Already imported".to_string()));
+    }
+
+    tx.execute(
+        "INSERT INTO conversion_events (link_id, converted_at, order_value, commission, status, order_id, source)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'import')",
+        params![link_id, converted_at_text, order_value, commission, status, order_id],
+    )?;
+    Ok(Ok((tx.last_insert_rowid(), converted_at.date())))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT NOT NULL, category TEXT NOT NULL,
+                description TEXT, price_range TEXT, target_audience TEXT, product_url TEXT, image_url TEXT,
+                amazon_asin TEXT, tags TEXT, notes TEXT, updated_by TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP);
+             CREATE TABLE affiliate_links (id INTEGER PRIMARY KEY, product_id INTEGER, product_name TEXT,
+                platform TEXT, program_name TEXT, commission_rate REAL, cookie_duration INTEGER,
+                tracking_url TEXT, destination_url TEXT, status TEXT);
+             CREATE TABLE conversion_events (id INTEGER PRIMARY KEY, link_id INTEGER, converted_at DATETIME,
+                order_value REAL, commission REAL, status TEXT, order_id TEXT, source TEXT);
+             CREATE TABLE settings (key TEXT PRIMARY KEY, value TEXT, updated_at DATETIME);
+             INSERT INTO settings (key, value) VALUES ('daily_metrics_through', '2026-03-31');",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn plan(sheet: &Sheet, target: &str) -> ImportPlan {
+        ImportPlan {
+            path: String::new(),
+            target: target.to_string(),
+            mappings: suggest_mappings(&sheet.headers, target).unwrap(),
+            create_missing_products: false,
+        }
+    }
+
+    #[test]
+    fn test_analysis_detects_delimiter_and_suggests_the_matching_target() {
+        let sheet = parse_sheet("\u{FEFF}Order Date;Product;Commission (€);Sale Amount\n\
+                                 03/02/2026;Yoga Mat;4,50;45,00\n;;;\n")
+            .unwrap();
+        assert_eq!((sheet.delimiter, sheet.rows.len()), (b';', 1));
+
+        let analysis = analyze(&sheet, "earnings.csv");
+        assert_eq!(analysis.suggested_target.as_deref(), Some("earnings"));
+        let earnings = &analysis.targets[2];
+        let fields: Vec<&str> = earnings.mappings.iter().map(|m| m.field.as_str()).collect();
+        assert_eq!(fields, ["date", "product", "commission", "order_value"]);
+        assert!(earnings.fields.iter().any(|f| f.name == "date" && f.required));
+        assert_eq!(analysis.targets[1].missing_required, ["Tracking URL"]);
+
+        let links = parse_sheet("Product,Affiliate Link URL,Commission %\nMat,https://amzn.to/x,8%\n").unwrap();
+        let analysis = analyze(&links, "links.csv");
+        assert_eq!(analysis.suggested_target.as_deref(), Some("links"));
+        assert_eq!(analysis.targets[1].mappings[1], ColumnMapping { column: 1, field: "tracking_url".to_string() });
+
+        assert_eq!(parse_number("$1,234.50"), Some(1234.5));
+        assert_eq!(parse_number("1.234,50 €"), Some(1234.5));
+        assert_eq!(parse_number("12,5"), Some(12.5));
+        assert_eq!(parse_rate("8"), parse_rate("0.08"));
+        assert_eq!(parse_date("Mar 2, 2026"), parse_date("2026-03-02"));
+        assert!(read_sheet(Path::new("catalog.xlsx")).unwrap_err().contains("save the sheet as CSV"));
+    }
+
+    #[test]
+    fn test_execute_imports_products_links_and_earnings() {
+        let mut conn = setup();
+        let products = parse_sheet(
+            "Name,Category,Tags,Date Added\n\
+             Yoga Mat,Fitness,\"Home, Yoga\",2025-11-04\n\
+             yoga mat,Fitness,,\n\
+             Kettlebell,,,someday\n",
+        )
+        .unwrap();
+        let result = execute(&mut conn, &products, &plan(&products, "products"), Some("Sam")).unwrap();
+        assert_eq!(result.imported, 1);
+        assert_eq!(result.skipped.iter().map(|s| s.row).collect::<Vec<_>>(), [2, 3]);
+        let (tags, created_at): (String, String) = conn
+            .query_row("SELECT tags, created_at FROM products WHERE name = 'Yoga Mat'", [], |r| {
+                Ok((r.get(0)?, r.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((tags.as_str(), created_at.as_str()), (r#"["home","yoga"]"#, "2025-11-04 00:00:00"));
+
+        let links = parse_sheet(
+            "Product,Tracking URL,Product URL,Commission Rate\n\
+             Yoga Mat,https://amzn.to/mat,https://www.amazon.com/dp/B000000001,4%\n\
+             Foam Roller,https://ex.co/roll,,10\n",
+        )
+        .unwrap();
+        let mut link_plan = plan(&links, "links");
+        let result = execute(&mut conn, &links, &link_plan, None).unwrap();
+        assert_eq!((result.imported, result.skipped[0].reason.as_str()), (1, "No product matches Foam Roller"));
+        link_plan.create_missing_products = true;
+        let result = execute(&mut conn, &links, &link_plan, None).unwrap();
+        assert_eq!((result.imported, result.created_product_ids.len()), (1, 1));
+        let (platform, rate): (String, f64) = conn
+            .query_row("SELECT platform, commission_rate FROM affiliate_links WHERE id = 1", [], |r| {
+                Ok((r.get(0)?, r.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((platform.as_str(), rate), ("amazon", 0.04));
+
+        let earnings = parse_sheet(
+            "Date,Product,Commission,Status\n\
+             2026-03-02,Yoga Mat,$4.50,Paid\n\
+             2026-03-02,Yoga Mat,$4.50,Paid\n\
+             2026-03-05,Foam Roller,2.00,Reversed\n\
+             2026-03-06,Jump Rope,1.00,\n",
+        )
+        .unwrap();
+        let result = execute(&mut conn, &earnings, &plan(&earnings, "earnings"), None).unwrap();
+        assert_eq!(result.imported, 2);
+        let reasons: Vec<&str> = result.skipped.iter().map(|s| s.reason.as_str()).collect();
+        assert_eq!(reasons, ["Already imported", "No link matches Jump Rope; import links first"]);
+        let statuses: Vec<String> = conn
+            .prepare("SELECT status FROM conversion_events ORDER BY id")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(statuses, ["approved", "rejected"]);
+        // Back-dated earnings push the materialized range back before their first day
+        assert_eq!(daily_metrics::materialized_through(&conn), NaiveDate::from_ymd_opt(2026, 3, 1));
+
+        let bad = ImportPlan { mappings: vec![], ..plan(&earnings, "earnings") };
+        assert!(execute(&mut conn, &earnings, &bad, None).unwrap_err().contains("Date, Commission"));
+    }
+}
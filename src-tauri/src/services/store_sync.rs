@@ -0,0 +1,679 @@
+//! Store Product Sync (Shopify / WooCommerce)
+//!
+//! Affiliates who also run a storefront can pull its products into the
+//! catalog. Every store item (each variant, for Shopify) is keyed by its SKU
+//! in `store_products`; an import maps unmapped SKUs to the product with the
+//! same name or creates one. After that, the scheduled sync only follows the
+//! store: mapped products get the store's current price and image, changes are
+//! logged to `product_changes` next to the watchlist's, and items that aren't
+//! mapped are counted but never imported behind the user's back.
+//!
+//! Credentials are stored in `affiliate_credentials` under the platform names
+//! `shopify` (shop_id = store domain, api_key = Admin API access token) and
+//! `woocommerce` (shop_id = site URL, api_key = consumer key, api_secret =
+//! consumer secret).
+
+use crate::database::get_connection;
+use crate::models::store_sync::{StoreSkuMapping, StoreSyncReport};
+use crate::services::data_events;
+use crate::services::scraper::{html_to_text, price_to_range};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde_json::Value;
+use tauri::AppHandle;
+
+/// Stores whose credentials live in `affiliate_credentials` alongside affiliate platforms
+pub const PROVIDERS: [&str; 2] = ["shopify", "woocommerce"];
+
+const SHOPIFY_API_VERSION: &str = "2024-10";
+const SHOPIFY_PAGE_SIZE: usize = 250;
+const WOOCOMMERCE_PAGE_SIZE: usize = 100;
+/// Pages read per fetch, so a runaway pagination loop stops
+const MAX_PAGES: usize = 40;
+
+/// One purchasable store item as the catalog needs it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StoreItem {
+    pub external_id: String,
+    pub sku: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: Option<f64>,
+    pub image_url: Option<String>,
+    pub product_url: Option<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl StoreItem {
+    /// Mapping key: the SKU, or the store's id for items without one
+    pub fn sku_key(&self) -> String {
+        match &self.sku {
+            Some(sku) => sku.clone(),
+            None => format!("id:{}", self.external_id),
+        }
+    }
+}
+
+/// Connection details read from the store's credential
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreCredentials {
+    pub store: String,
+    pub base_url: String,
+    pub api_key: String,
+    pub api_secret: Option<String>,
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value.map(str::trim).filter(|v| !v.is_empty()).map(String::from)
+}
+
+/// "https://<host>" for a store domain or URL; bare Shopify shop names get
+/// their myshopify.com domain
+pub fn normalize_store_url(store: &str, raw: &str) -> Result<String, String> {
+    let raw = raw.trim().trim_end_matches('/');
+    let host = raw.strip_prefix("https://").or_else(|| raw.strip_prefix("http://")).unwrap_or(raw);
+    if host.is_empty() || host.contains(char::is_whitespace) {
+        return Err(format!("'{}' is not a store address", raw));
+    }
+    if store == "shopify" && !host.contains('.') {
+        return Ok(format!("https://{}.myshopify.com", host));
+    }
+    Ok(format!("https://{}", host))
+}
+
+/// The store's active credential, unmasked
+pub fn credentials(conn: &Connection, store: &str) -> Result<StoreCredentials, String> {
+    if !PROVIDERS.contains(&store) {
+        return Err(format!("Unsupported store: {}", store));
+    }
+    let row: Option<(Option<String>, Option<String>, Option<String>)> = conn
+        .query_row(
+            "SELECT shop_id, api_key, api_secret FROM affiliate_credentials WHERE platform = ?1 AND active = 1",
+            params![store],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let (shop_id, api_key, api_secret) = row.ok_or_else(|| format!("No active credentials saved for {}", store))?;
+    let shop_id = non_empty(shop_id.as_deref()).ok_or_else(|| format!("{} store address is not configured", store))?;
+    let api_key = non_empty(api_key.as_deref()).ok_or_else(|| format!("{} API key is not configured", store))?;
+    let api_secret = non_empty(api_secret.as_deref());
+    if store == "woocommerce" && api_secret.is_none() {
+        return Err("WooCommerce consumer secret is not configured".to_string());
+    }
+    let base_url = normalize_store_url(store, &shop_id)?;
+    Ok(StoreCredentials { store: store.to_string(), base_url, api_key, api_secret })
+}
+
+// =============================================================================
+// STORE FETCHING
+// =============================================================================
+
+/// Every published item in the store
+pub async fn fetch_items(credentials: &StoreCredentials) -> Result<Vec<StoreItem>, String> {
+    match credentials.store.as_str() {
+        "shopify" => fetch_shopify(credentials).await,
+        "woocommerce" => fetch_woocommerce(credentials).await,
+        other => Err(format!("Unsupported store: {}", other)),
+    }
+}
+
+async fn fetch_shopify(credentials: &StoreCredentials) -> Result<Vec<StoreItem>, String> {
+    let client = reqwest::Client::new();
+    let mut url = Some(format!(
+        "{}/admin/api/{}/products.json?status=active&limit={}",
+        credentials.base_url, SHOPIFY_API_VERSION, SHOPIFY_PAGE_SIZE
+    ));
+    let mut items = Vec::new();
+    for _ in 0..MAX_PAGES {
+        let Some(page_url) = url.take() else {
+            break;
+        };
+        let response = client
+            .get(&page_url)
+            .header("X-Shopify-Access-Token", &credentials.api_key)
+            .send()
+            .await
+            .map_err(|e| format!("Shopify request failed: {}", e))?
+            .error_for_status()
+            .map_err(|e| format!("Shopify request failed: {}", e))?;
+        url = response
+            .headers()
+            .get(reqwest::header::LINK)
+            .and_then(|link| link.to_str().ok())
+            .and_then(next_page_link);
+        let body: Value = response.json().await.map_err(|e| format!("Invalid Shopify response: {}", e))?;
+        items.extend(parse_shopify_products(&body, &credentials.base_url)?);
+    }
+    Ok(items)
+}
+
+async fn fetch_woocommerce(credentials: &StoreCredentials) -> Result<Vec<StoreItem>, String> {
+    let client = reqwest::Client::new();
+    let mut items = Vec::new();
+    for page in 1..=MAX_PAGES {
+        let body: Value = client
+            .get(format!(
+                "{}/wp-json/wc/v3/products?status=publish&per_page={}&page={}",
+                credentials.base_url, WOOCOMMERCE_PAGE_SIZE, page
+            ))
+            .basic_auth(&credentials.api_key, credentials.api_secret.as_deref())
+            .send()
+            .await
+            .map_err(|e| format!("WooCommerce request failed: {}", e))?
+            .error_for_status()
+            .map_err(|e| format!("WooCommerce request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid WooCommerce response: {}", e))?;
+        let page_items = parse_woocommerce_products(&body)?;
+        let last_page = body.as_array().is_none_or(|products| products.len() < WOOCOMMERCE_PAGE_SIZE);
+        items.extend(page_items);
+        if last_page {
+            break;
+        }
+    }
+    Ok(items)
+}
+
+// =============================================================================
+// RESPONSE PARSING
+// =============================================================================
+
+/// The `rel="next"` URL of a Link header
+pub fn next_page_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|part| {
+        let (url, rel) = part.split_once(';')?;
+        rel.contains("rel=\"next\"").then(|| url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
+
+/// A price sent as a string or a number; empty means unpriced
+fn price_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .filter(|price| *price > 0.0)
+}
+
+fn id_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(n) => Some(n.to_string()),
+        Value::String(s) => non_empty(Some(s)),
+        _ => None,
+    }
+}
+
+/// Parses a Shopify Admin API `products.json` page into one item per
+/// variant. Variants are named "Product - Variant" unless the product only
+/// has its default variant.
+pub fn parse_shopify_products(body: &Value, base_url: &str) -> Result<Vec<StoreItem>, String> {
+    let products = body["products"].as_array().ok_or("Shopify response has no products")?;
+    let mut items = Vec::new();
+    for product in products {
+        let Some(title) = non_empty(product["title"].as_str()) else {
+            continue;
+        };
+        let description = product["body_html"].as_str().map(html_to_text).filter(|d| !d.is_empty());
+        let product_url = product["handle"].as_str().map(|handle| format!("{}/products/{}", base_url, handle));
+        let tags: Vec<String> = product["tags"]
+            .as_str()
+            .unwrap_or("")
+            .split(',')
+            .map(|tag| tag.trim().to_lowercase())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        let default_image = product["image"]["src"].as_str().map(String::from);
+        let variants = product["variants"].as_array().cloned().unwrap_or_default();
+        let single = variants.len() <= 1;
+
+        for variant in &variants {
+            let Some(external_id) = id_value(&variant["id"]) else {
+                continue;
+            };
+            let variant_title = non_empty(variant["title"].as_str()).filter(|t| t != "Default Title");
+            let name = match variant_title {
+                Some(variant_title) if !single => format!("{} - {}", title, variant_title),
+                _ => title.clone(),
+            };
+            let image_url = product["images"]
+                .as_array()
+                .and_then(|images| {
+                    images.iter().find(|image| !image["id"].is_null() && image["id"] == variant["image_id"])
+                })
+                .and_then(|image| image["src"].as_str().map(String::from))
+                .or_else(|| default_image.clone());
+            items.push(StoreItem {
+                external_id,
+                sku: non_empty(variant["sku"].as_str()),
+                name,
+                description: description.clone(),
+                price: price_value(&variant["price"]),
+                image_url,
+                product_url: product_url.clone(),
+                category: non_empty(product["product_type"].as_str()),
+                tags: tags.clone(),
+            });
+        }
+    }
+    Ok(items)
+}
+
+/// Parses a WooCommerce REST API `products` page. Variable products are read
+/// as one item priced from their cheapest variation.
+pub fn parse_woocommerce_products(body: &Value) -> Result<Vec<StoreItem>, String> {
+    let products = body.as_array().ok_or("WooCommerce response is not a product list")?;
+    let names = |value: &Value| -> Vec<String> {
+        value
+            .as_array()
+            .map(|entries| entries.iter().filter_map(|e| non_empty(e["name"].as_str())).collect())
+            .unwrap_or_default()
+    };
+    Ok(products
+        .iter()
+        .filter_map(|product| {
+            let description = [&product["short_description"], &product["description"]]
+                .iter()
+                .filter_map(|html| html.as_str().map(html_to_text))
+                .find(|text| !text.is_empty());
+            Some(StoreItem {
+                external_id: id_value(&product["id"])?,
+                sku: non_empty(product["sku"].as_str()),
+                name: html_to_text(product["name"].as_str()?),
+                description,
+                price: price_value(&product["price"]),
+                image_url: product["images"][0]["src"].as_str().map(String::from),
+                product_url: non_empty(product["permalink"].as_str()),
+                category: names(&product["categories"]).into_iter().next(),
+                tags: names(&product["tags"]).into_iter().map(|tag| tag.to_lowercase()).collect(),
+            })
+        })
+        .filter(|item| !item.name.is_empty())
+        .collect())
+}
+
+// =============================================================================
+// APPLYING
+// =============================================================================
+
+/// The stored mapping of an item's SKU: (id, product_id, last_price, last_image_url)
+type StoredMapping = (i64, i64, Option<f64>, Option<String>);
+
+fn price_changed(old: Option<f64>, new: f64) -> bool {
+    old.is_none_or(|old| (old - new).abs() >= 0.005)
+}
+
+/// Writes the item's price and image to its mapped product when they
+/// differ from what the last sync saw. Returns whether the product changed.
+fn sync_mapped(tx: &Transaction, item: &StoreItem, mapping: &StoredMapping) -> rusqlite::Result<bool> {
+    let (id, product_id, last_price, last_image_url) = mapping;
+    let new_price = item.price.filter(|price| price_changed(*last_price, *price));
+    let new_image = item.image_url.as_ref().filter(|url| last_image_url.as_ref() != Some(*url));
+    if let Some(price) = new_price {
+        tx.execute(
+            "INSERT INTO product_changes (product_id, field, old_value, new_value) VALUES (?1, 'price', ?2, ?3)",
+            params![product_id, last_price.map(|p| format!("{:.2}", p)), format!("{:.2}", price)],
+        )?;
+    }
+    if let Some(image_url) = new_image {
+        tx.execute(
+            "INSERT INTO product_changes (product_id, field, old_value, new_value) VALUES (?1, 'image', ?2, ?3)",
+            params![product_id, last_image_url, image_url],
+        )?;
+    }
+    let changed = new_price.is_some() || new_image.is_some();
+    if changed {
+        tx.execute(
+            "UPDATE products SET
+                price_range = COALESCE(?2, price_range),
+                image_url = COALESCE(?3, image_url),
+                updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?1",
+            params![product_id, new_price.map(price_to_range), new_image],
+        )?;
+    }
+    tx.execute(
+        "UPDATE store_products SET external_id = ?2, last_price = COALESCE(?3, last_price),
+            last_image_url = COALESCE(?4, last_image_url), last_synced_at = CURRENT_TIMESTAMP
+         WHERE id = ?1",
+        params![id, item.external_id, item.price, item.image_url],
+    )?;
+    Ok(changed)
+}
+
+/// Maps an unmapped item to the product of the same name that no other SKU
+/// of this store is mapped to, filling its empty fields, or creates one
+fn import_item(tx: &Transaction, store: &str, item: &StoreItem, editor: Option<&str>) -> rusqlite::Result<(i64, bool)> {
+    let existing: Option<i64> = tx
+        .query_row(
+            "SELECT id FROM products WHERE name = ?1 COLLATE NOCASE
+             AND id NOT IN (SELECT product_id FROM store_products WHERE store = ?2)
+             ORDER BY id LIMIT 1",
+            params![item.name, store],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let price_range = item.price.map(price_to_range);
+    let (product_id, created) = match existing {
+        Some(product_id) => {
+            tx.execute(
+                "UPDATE products SET
+                    description = COALESCE(NULLIF(description, ''), ?2),
+                    price_range = COALESCE(NULLIF(price_range, ''), ?3),
+                    image_url = COALESCE(NULLIF(image_url, ''), ?4),
+                    product_url = COALESCE(NULLIF(product_url, ''), ?5),
+                    updated_at = CURRENT_TIMESTAMP
+                 WHERE id = ?1",
+                params![product_id, item.description, price_range, item.image_url, item.product_url],
+            )?;
+            (product_id, false)
+        }
+        None => {
+            let mut tags = vec![store.to_string()];
+            for tag in &item.tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+            tx.execute(
+                "INSERT INTO products (name, category, description, price_range, image_url, product_url, tags,
+                 updated_by) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    item.name,
+                    item.category.as_deref().unwrap_or("Uncategorized"),
+                    item.description,
+                    price_range,
+                    item.image_url,
+                    item.product_url,
+                    serde_json::to_string(&tags).unwrap_or_else(|_| "[]".to_string()),
+                    editor,
+                ],
+            )?;
+            (tx.last_insert_rowid(), true)
+        }
+    };
+    tx.execute(
+        "INSERT INTO store_products (store, sku, external_id, product_id, last_price, last_image_url, last_synced_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, CURRENT_TIMESTAMP)",
+        params![store, item.sku_key(), item.external_id, product_id, item.price, item.image_url],
+    )?;
+    Ok((product_id, created))
+}
+
+/// Applies fetched items in one transaction. Mapped SKUs sync their price
+/// and image; unmapped ones are imported when `import_new` is set and only
+/// counted otherwise.
+pub fn apply(
+    conn: &mut Connection,
+    store: &str,
+    items: &[StoreItem],
+    import_new: bool,
+    editor: Option<&str>,
+) -> rusqlite::Result<StoreSyncReport> {
+    let tx = conn.transaction()?;
+    let mut report = StoreSyncReport { store: store.to_string(), fetched: items.len() as i64, ..Default::default() };
+    for item in items {
+        let mapping: Option<StoredMapping> = tx
+            .query_row(
+                "SELECT id, product_id, last_price, last_image_url FROM store_products WHERE store = ?1 AND sku = ?2",
+                params![store, item.sku_key()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()?;
+        match mapping {
+            Some(mapping) => {
+                if sync_mapped(&tx, item, &mapping)? && !report.updated_product_ids.contains(&mapping.1) {
+                    report.updated_product_ids.push(mapping.1);
+                }
+            }
+            None if import_new => match import_item(&tx, store, item, editor)? {
+                (product_id, true) => report.created_product_ids.push(product_id),
+                (product_id, false) => report.matched_product_ids.push(product_id),
+            },
+            None => report.unmapped += 1,
+        }
+    }
+    tx.commit()?;
+    Ok(report)
+}
+
+// =============================================================================
+// SKU MAPPINGS
+// =============================================================================
+
+pub fn mappings(conn: &Connection, store: Option<&str>) -> rusqlite::Result<Vec<StoreSkuMapping>> {
+    let mut stmt = conn.prepare(
+        "SELECT s.id, s.store, s.sku, s.external_id, s.product_id, p.name, s.last_price, s.last_image_url,
+                s.last_synced_at
+         FROM store_products s JOIN products p ON p.id = s.product_id
+         WHERE ?1 IS NULL OR s.store = ?1
+         ORDER BY s.store, s.sku",
+    )?;
+    let mappings = stmt
+        .query_map(params![store], |row| {
+            Ok(StoreSkuMapping {
+                id: row.get(0)?,
+                store: row.get(1)?,
+                sku: row.get(2)?,
+                external_id: row.get(3)?,
+                product_id: row.get(4)?,
+                product_name: row.get(5)?,
+                last_price: row.get(6)?,
+                last_image_url: row.get(7)?,
+                last_synced_at: row.get(8)?,
+            })
+        })?
+        .collect();
+    mappings
+}
+
+/// Points a SKU at a product, replacing its current mapping. The next sync
+/// writes the store's price and image to the new product.
+pub fn map_sku(conn: &Connection, store: &str, sku: &str, product_id: i64) -> Result<(), String> {
+    if !PROVIDERS.contains(&store) {
+        return Err(format!("Unsupported store: {}", store));
+    }
+    let sku = sku.trim();
+    if sku.is_empty() {
+        return Err("SKU is required".to_string());
+    }
+    let exists: bool = conn
+        .query_row("SELECT EXISTS(SELECT 1 FROM products WHERE id = ?1)", params![product_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if !exists {
+        return Err(format!("Product {} not found", product_id));
+    }
+    conn.execute(
+        "INSERT INTO store_products (store, sku, product_id) VALUES (?1, ?2, ?3)
+         ON CONFLICT(store, sku) DO UPDATE SET product_id = excluded.product_id,
+            last_price = NULL, last_image_url = NULL, last_synced_at = NULL",
+        params![store, sku, product_id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn unmap_sku(conn: &Connection, mapping_id: i64) -> rusqlite::Result<usize> {
+    conn.execute("DELETE FROM store_products WHERE id = ?1", params![mapping_id])
+}
+
+// =============================================================================
+// SCHEDULED SYNC
+// =============================================================================
+
+/// Fetches one store and applies it
+pub async fn sync_store(
+    app_handle: &AppHandle,
+    store: &str,
+    import_new: bool,
+    editor: Option<&str>,
+) -> Result<StoreSyncReport, String> {
+    let credentials = {
+        let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        credentials(&conn, store)?
+    };
+    let items = fetch_items(&credentials).await?;
+    let mut conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    let report = apply(&mut conn, store, &items, import_new, editor).map_err(|e| e.to_string())?;
+    let created = report.created_product_ids.clone();
+    let updated = report.matched_product_ids.iter().chain(&report.updated_product_ids).copied().collect();
+    data_events::emit(app_handle, data_events::PRODUCTS_CHANGED, data_events::CREATED, created);
+    data_events::emit(app_handle, data_events::PRODUCTS_CHANGED, data_events::UPDATED, updated);
+    Ok(report)
+}
+
+/// Scheduler entry point: syncs prices and images of every connected store
+pub fn run_store_sync_job(app_handle: &AppHandle) -> Result<String, String> {
+    let connected: Vec<&str> = {
+        let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        PROVIDERS.into_iter().filter(|store| credentials(&conn, store).is_ok()).collect()
+    };
+    if connected.is_empty() {
+        return Ok("No stores connected".to_string());
+    }
+
+    let mut summaries = Vec::new();
+    let mut errors = Vec::new();
+    for store in connected {
+        match tauri::async_runtime::block_on(sync_store(app_handle, store, false, None)) {
+            Ok(report) => summaries.push(format!(
+                "{}: {} item(s), {} product(s) updated, {} unmapped",
+                store,
+                report.fetched,
+                report.updated_product_ids.len(),
+                report.unmapped
+            )),
+            Err(e) => errors.push(format!("{}: {}", store, e)),
+        }
+    }
+    if summaries.is_empty() {
+        return Err(errors.join("; "));
+    }
+    summaries.extend(errors);
+    Ok(summaries.join("; "))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_store_responses_parse_into_one_item_per_variant() {
+        let shopify = json!({"products": [{
+            "id": 1, "title": "Linen Shirt", "handle": "linen-shirt", "body_html": "<p>Breezy &amp; light</p>",
+            "product_type": "Apparel", "tags": "Summer, Linen",
+            "image": {"src": "https://cdn.shopify.com/shirt.jpg"},
+            "images": [
+                {"id": 11, "src": "https://cdn.shopify.com/shirt.jpg"},
+                {"id": 12, "src": "https://cdn.shopify.com/blue.jpg"}
+            ],
+            "variants": [
+                {"id": 101, "title": "White", "sku": "LS-W", "price": "48.00", "image_id": null},
+                {"id": 102, "title": "Blue", "sku": "", "price": "52.50", "image_id": 12}
+            ]
+        }, {
+            "id": 2, "title": "Gift Card", "handle": "gift-card",
+            "variants": [{"id": 201, "title": "Default Title", "sku": "GC", "price": "25.00"}]
+        }]});
+        let items = parse_shopify_products(&shopify, "https://shop.example").unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].name, "Linen Shirt - White");
+        assert_eq!(items[0].description.as_deref(), Some("Breezy & light"));
+        assert_eq!(items[0].tags, ["summer", "linen"]);
+        assert_eq!(items[1].sku_key(), "id:102");
+        assert_eq!(items[1].image_url.as_deref(), Some("https://cdn.shopify.com/blue.jpg"));
+        assert_eq!(items[2].name, "Gift Card");
+        assert_eq!(items[2].product_url.as_deref(), Some("https://shop.example/products/gift-card"));
+
+        let woo = json!([{
+            "id": 7, "name": "Ceramic Mug", "sku": "MUG-1", "price": "18", "permalink": "https://shop.example/mug",
+            "short_description": "", "description": "<p>Holds 12oz</p>",
+            "images": [{"src": "https://shop.example/mug.jpg"}],
+            "categories": [{"name": "Kitchen"}], "tags": [{"name": "Gifts"}]
+        }, {"id": 8, "name": "Draft", "price": ""}]);
+        let items = parse_woocommerce_products(&woo).unwrap();
+        assert_eq!(items[0].description.as_deref(), Some("Holds 12oz"));
+        assert_eq!((items[0].category.as_deref(), items[0].price), (Some("Kitchen"), Some(18.0)));
+        assert_eq!(items[1].price, None);
+
+        let link = r#"<https://s.myshopify.com/a?page_info=p1>; rel="previous",
+                      <https://s.myshopify.com/a?page_info=p2>; rel="next""#;
+        assert_eq!(next_page_link(link).as_deref(), Some("https://s.myshopify.com/a?page_info=p2"));
+        assert_eq!(normalize_store_url("shopify", "my-shop").unwrap(), "https://my-shop.myshopify.com");
+        assert_eq!(normalize_store_url("woocommerce", "http://shop.example/").unwrap(), "https://shop.example");
+    }
+
+    #[test]
+    fn test_import_maps_skus_and_sync_only_follows_mapped_products() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT NOT NULL, category TEXT NOT NULL,
+                description TEXT, price_range TEXT, image_url TEXT, product_url TEXT, tags TEXT, updated_by TEXT,
+                updated_at DATETIME);
+             INSERT INTO products (id, name, category, price_range) VALUES (1, 'ceramic mug', 'Kitchen', '$10-$20');
+             {}
+             {}",
+            include_str!("../../../migrations/044_product_watchlist.sql"),
+            include_str!("../../../migrations/055_store_products.sql")
+        ))
+        .unwrap();
+        let mug = StoreItem {
+            external_id: "7".to_string(),
+            sku: Some("MUG-1".to_string()),
+            name: "Ceramic Mug".to_string(),
+            price: Some(18.0),
+            image_url: Some("https://shop.example/mug.jpg".to_string()),
+            ..Default::default()
+        };
+        let tray = StoreItem {
+            external_id: "9".to_string(),
+            name: "Tray".to_string(),
+            price: Some(30.0),
+            tags: vec!["gifts".to_string()],
+            ..Default::default()
+        };
+
+        // Syncing before an import leaves the catalog alone
+        let report = apply(&mut conn, "woocommerce", &[mug.clone(), tray.clone()], false, None).unwrap();
+        assert_eq!((report.unmapped, report.created_product_ids.len()), (2, 0));
+
+        let report = apply(&mut conn, "woocommerce", &[mug.clone(), tray.clone()], true, Some("Sam")).unwrap();
+        assert_eq!(report.matched_product_ids, [1]);
+        assert_eq!(report.created_product_ids.len(), 1);
+        let (image, price_range): (String, String) = conn
+            .query_row("SELECT image_url, price_range FROM products WHERE id = 1", [], |r| Ok((r.get(0)?, r.get(1)?)))
+            .unwrap();
+        assert_eq!((image.as_str(), price_range.as_str()), ("https://shop.example/mug.jpg", "$10-$20"));
+        let tags: String = conn.query_row("SELECT tags FROM products WHERE name = 'Tray'", [], |r| r.get(0)).unwrap();
+        assert_eq!(tags, r#"["woocommerce","gifts"]"#);
+
+        let repriced = StoreItem { price: Some(24.0), ..mug.clone() };
+        let report = apply(&mut conn, "woocommerce", &[repriced, tray.clone()], false, None).unwrap();
+        assert_eq!((report.updated_product_ids.as_slice(), report.unmapped), ([1].as_slice(), 0));
+        let price_range: String =
+            conn.query_row("SELECT price_range FROM products WHERE id = 1", [], |r| r.get(0)).unwrap();
+        assert_eq!(price_range, "$20-$30");
+        let change: (String, String, String) = conn
+            .query_row("SELECT field, old_value, new_value FROM product_changes", [], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?))
+            })
+            .unwrap();
+        assert_eq!(change, ("price".to_string(), "18.00".to_string(), "24.00".to_string()));
+
+        // Remapping the SKU moves the sync to the other product
+        map_sku(&conn, "woocommerce", "MUG-1", 2).unwrap();
+        let report = apply(&mut conn, "woocommerce", &[mug], false, None).unwrap();
+        assert_eq!(report.updated_product_ids, [2]);
+        let mapped = mappings(&conn, Some("woocommerce")).unwrap();
+        let skus: Vec<(&str, i64)> = mapped.iter().map(|m| (m.sku.as_str(), m.product_id)).collect();
+        assert_eq!(skus, [("MUG-1", 2), ("id:9", 2)]);
+        assert!(map_sku(&conn, "woocommerce", "MUG-1", 99).is_err());
+    }
+}
@@ -0,0 +1,258 @@
+//! Per-Platform Style Rules
+//!
+//! Each platform has formatting conventions: emoji density, headline
+//! capitalization, line break style, and where the link goes. Built-in
+//! defaults can be overridden per platform in the settings table under
+//! `style_rules:<platform>` and are applied to generated headlines and bodies.
+//! `[LINK]` is the placeholder generated copy uses for the affiliate link.
+
+use crate::database::settings::{get_setting, set_setting};
+use crate::models::style_rules::StyleRules;
+use rusqlite::Connection;
+
+/// Platforms with built-in defaults; email and SMS are treated as platforms here
+pub const STYLE_PLATFORMS: [&str; 7] = ["tiktok", "instagram", "facebook", "pinterest", "youtube", "email", "sms"];
+
+const EMOJI_DENSITIES: [&str; 3] = ["none", "light", "heavy"];
+const CAPITALIZATIONS: [&str; 4] = ["as_is", "sentence", "title", "upper"];
+const LINE_BREAKS: [&str; 4] = ["as_is", "airy", "compact", "single_line"];
+const LINK_PLACEMENTS: [&str; 4] = ["as_is", "start", "end", "bio"];
+
+const LINK_PLACEHOLDER: &str = "[LINK]";
+const ACCENT_EMOJI: [&str; 3] = ["✨", "🔥", "👉"];
+
+fn settings_key(platform: &str) -> String {
+    format!("style_rules:{}", platform)
+}
+
+/// Built-in rules for a platform
+pub fn default_rules(platform: &str) -> StyleRules {
+    let (emoji_density, capitalization, line_breaks, link_placement) = match platform {
+        "tiktok" => ("heavy", "as_is", "airy", "bio"),
+        "instagram" => ("light", "sentence", "airy", "bio"),
+        "facebook" => ("light", "sentence", "compact", "end"),
+        "pinterest" => ("none", "title", "compact", "as_is"),
+        "youtube" => ("none", "title", "airy", "end"),
+        "sms" => ("none", "as_is", "single_line", "end"),
+        _ => ("none", "as_is", "as_is", "as_is"),
+    };
+    StyleRules {
+        platform: platform.to_string(),
+        emoji_density: emoji_density.to_string(),
+        capitalization: capitalization.to_string(),
+        line_breaks: line_breaks.to_string(),
+        link_placement: link_placement.to_string(),
+    }
+}
+
+/// Saved rules for a platform, or its defaults
+pub fn load_rules(conn: &Connection, platform: &str) -> StyleRules {
+    get_setting(conn, &settings_key(platform))
+        .ok()
+        .flatten()
+        .and_then(|json| serde_json::from_str::<StyleRules>(&json).ok())
+        .unwrap_or_else(|| default_rules(platform))
+}
+
+/// Validates and saves rules for their platform
+pub fn save_rules(conn: &Connection, rules: &StyleRules) -> Result<(), String> {
+    let checks = [
+        ("emoji_density", &rules.emoji_density, &EMOJI_DENSITIES[..]),
+        ("capitalization", &rules.capitalization, &CAPITALIZATIONS[..]),
+        ("line_breaks", &rules.line_breaks, &LINE_BREAKS[..]),
+        ("link_placement", &rules.link_placement, &LINK_PLACEMENTS[..]),
+    ];
+    for (field, value, allowed) in checks {
+        if !allowed.contains(&value.as_str()) {
+            return Err(format!("Invalid {} '{}'; expected one of {}", field, value, allowed.join(", ")));
+        }
+    }
+    if rules.platform.trim().is_empty() {
+        return Err("Platform is required".to_string());
+    }
+
+    let json = serde_json::to_string(rules).map_err(|e| e.to_string())?;
+    set_setting(conn, &settings_key(&rules.platform), &json).map_err(|e| e.to_string())
+}
+
+/// Style platform for an ad: channel-like ad types use their own rules,
+/// everything else follows the target platform
+pub fn platform_for(ad_type: &str, target_platform: &str) -> String {
+    match ad_type {
+        "email" | "email_sequence" => "email".to_string(),
+        "sms" => "sms".to_string(),
+        "pinterest_pin" => "pinterest".to_string(),
+        _ => target_platform.to_lowercase(),
+    }
+}
+
+/// Applies the rules to a generated headline and body
+pub fn apply(rules: &StyleRules, headline: &str, body: &str) -> (String, String) {
+    let headline = capitalize(&strip_emoji_if(headline, rules.emoji_density == "none"), &rules.capitalization);
+    let body = place_link(body, &rules.link_placement);
+    let body = apply_emoji(&body, &rules.emoji_density);
+    let body = apply_line_breaks(&body, &rules.line_breaks);
+    (headline, body)
+}
+
+/// Emoji and pictograph code points (not variation selectors or joiners)
+pub fn is_emoji(c: char) -> bool {
+    matches!(c as u32, 0x1F300..=0x1FAFF | 0x2600..=0x27BF | 0x1F1E6..=0x1F1FF)
+}
+
+fn strip_emoji_if(text: &str, strip: bool) -> String {
+    if !strip {
+        return text.to_string();
+    }
+    let stripped: String = text
+        .chars()
+        .filter(|&c| !is_emoji(c) && c != '\u{FE0F}' && c != '\u{200D}')
+        .collect();
+    stripped.split(' ').filter(|w| !w.is_empty()).collect::<Vec<_>>().join(" ")
+}
+
+fn apply_emoji(body: &str, density: &str) -> String {
+    match density {
+        "none" => body
+            .lines()
+            .map(|line| strip_emoji_if(line, true))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "light" if !body.chars().any(is_emoji) => {
+            // One accent on the first paragraph
+            match body.split_once('\n') {
+                Some((first, rest)) => format!("{} {}\n{}", first, ACCENT_EMOJI[0], rest),
+                None => format!("{} {}", body, ACCENT_EMOJI[0]),
+            }
+        }
+        "heavy" => {
+            let mut accent = ACCENT_EMOJI.iter().cycle();
+            body.split("\n\n")
+                .map(|paragraph| {
+                    let skip = paragraph.trim().is_empty()
+                        || paragraph.trim() == LINK_PLACEHOLDER
+                        || paragraph.chars().any(is_emoji);
+                    if skip {
+                        paragraph.to_string()
+                    } else {
+                        // Trailing, so script section markers like [HOOK] stay first
+                        format!("{} {}", paragraph, accent.next().unwrap_or(&ACCENT_EMOJI[0]))
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        }
+        _ => body.to_string(),
+    }
+}
+
+fn capitalize(headline: &str, style: &str) -> String {
+    let upper_first = |word: &str| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+            None => String::new(),
+        }
+    };
+    // Shouted words are lowered; short acronyms (POV, SPF) and mixed-case
+    // words such as product names keep their casing
+    let unshout = |word: &str| {
+        let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+        if letters > 3 && !word.chars().any(|c| c.is_lowercase()) {
+            word.to_lowercase()
+        } else {
+            word.to_string()
+        }
+    };
+
+    match style {
+        "upper" => headline.to_uppercase(),
+        "sentence" => headline
+            .split(' ')
+            .enumerate()
+            .map(|(idx, word)| if idx == 0 { upper_first(&unshout(word)) } else { unshout(word) })
+            .collect::<Vec<_>>()
+            .join(" "),
+        "title" => headline
+            .split(' ')
+            .enumerate()
+            .map(|(idx, word)| {
+                let minor = ["a", "an", "and", "the", "of", "for", "to", "in", "on", "or", "vs."];
+                let lower = word.to_lowercase();
+                if idx > 0 && minor.contains(&lower.as_str()) {
+                    lower
+                } else {
+                    upper_first(&unshout(word))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => headline.to_string(),
+    }
+}
+
+fn apply_line_breaks(body: &str, style: &str) -> String {
+    let paragraphs = || {
+        body.split("\n\n")
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+    };
+    match style {
+        "airy" => paragraphs().collect::<Vec<_>>().join("\n\n"),
+        "compact" => paragraphs().collect::<Vec<_>>().join("\n"),
+        "single_line" => body.split_whitespace().collect::<Vec<_>>().join(" "),
+        _ => body.to_string(),
+    }
+}
+
+fn place_link(body: &str, placement: &str) -> String {
+    let without_link = || {
+        body.replace(&format!(": {}", LINK_PLACEHOLDER), "")
+            .replace(&format!(" {}", LINK_PLACEHOLDER), "")
+            .replace(LINK_PLACEHOLDER, "")
+            .trim_end()
+            .to_string()
+    };
+    match placement {
+        "start" => format!("{}\n\n{}", LINK_PLACEHOLDER, without_link()),
+        "end" if body.trim_end().ends_with(LINK_PLACEHOLDER) => body.to_string(),
+        "end" => format!("{}\n\n{}", without_link(), LINK_PLACEHOLDER),
+        "bio" => {
+            let body = without_link();
+            if body.to_lowercase().contains("link in bio") {
+                body
+            } else {
+                format!("{}\n\nLink in bio.", body)
+            }
+        }
+        _ => body.to_string(),
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sms_rules_flatten_and_end_with_link() {
+        let (_, body) = apply(&default_rules("sms"), "Deal", "Back in stock 🔥\n\nGet yours: [LINK] before it's gone");
+        assert_eq!(body, "Back in stock Get yours before it's gone [LINK]");
+    }
+
+    #[test]
+    fn test_instagram_rules() {
+        let rules = default_rules("instagram");
+        let (headline, body) = apply(&rules, "TRANSFORM your routine", "Meet the serum.\n\n\n\nShop: [LINK]");
+        assert_eq!(headline, "Transform your routine");
+        assert_eq!(body, "Meet the serum. ✨\n\nShop\n\nLink in bio.");
+    }
+
+    #[test]
+    fn test_title_case_keeps_minor_words_lowercase() {
+        assert_eq!(capitalize("the best serum for dry skin", "title"), "The Best Serum for Dry Skin");
+    }
+}
@@ -0,0 +1,340 @@
+//! TikTok Shop Product Sync
+//!
+//! Pulls the products a creator promotes on TikTok — the shop's own catalog
+//! when a shop is connected, otherwise the creator's showcase — through the
+//! TikTok Shop Open API and ties them to the catalog by `tiktok_product_id`.
+//! Items whose id a product already carries are left alone; otherwise the
+//! product with the same name gets the id, and items nothing matches become
+//! new products.
+//!
+//! Credentials are the `tiktok` entry in `affiliate_credentials`: api_key =
+//! app key, api_secret = app secret, shop_id = shop cipher (optional), with
+//! affiliate_id still the creator ID used for links. The access token from
+//! authorizing the app expires, so it is kept in the `tiktok_access_token`
+//! setting where it can be replaced without touching the credential.
+
+use crate::database::get_connection;
+use crate::database::settings::get_setting;
+use crate::models::tiktok_sync::TikTokSyncReport;
+use crate::services::postback::hmac_sha256_hex;
+use crate::services::scraper::price_to_range;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+pub const ACCESS_TOKEN_SETTING: &str = "tiktok_access_token";
+
+const API_BASE_URL: &str = "https://open-api.tiktokglobalshop.com";
+const SHOP_PRODUCTS_PATH: &str = "/product/202309/products/search";
+const SHOWCASE_PRODUCTS_PATH: &str = "/affiliate_creator/202405/showcases/products";
+const PAGE_SIZE: usize = 50;
+/// Pages read per sync, so a runaway pagination loop stops
+const MAX_PAGES: usize = 40;
+
+/// A product as TikTok lists it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TikTokProduct {
+    pub id: String,
+    pub title: String,
+    pub price: Option<f64>,
+    pub image_url: Option<String>,
+}
+
+impl TikTokProduct {
+    pub fn product_url(&self) -> String {
+        format!("https://shop.tiktok.com/view/product/{}", self.id)
+    }
+}
+
+/// What signing and calling the API needs
+#[derive(Debug, Clone, PartialEq)]
+pub struct TikTokApiConfig {
+    pub app_key: String,
+    pub app_secret: String,
+    pub access_token: String,
+    pub shop_cipher: Option<String>,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// The API configuration, or what is missing from it
+pub fn config(conn: &Connection) -> Result<TikTokApiConfig, String> {
+    let row: Option<(Option<String>, Option<String>, Option<String>)> = conn
+        .query_row(
+            "SELECT api_key, api_secret, shop_id FROM affiliate_credentials WHERE platform = 'tiktok' AND active = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let (app_key, app_secret, shop_cipher) = row.ok_or("No active TikTok credentials saved")?;
+    let app_key = non_empty(app_key).ok_or("Add the TikTok Shop app key to the TikTok credentials")?;
+    let app_secret = non_empty(app_secret).ok_or("Add the TikTok Shop app secret to the TikTok credentials")?;
+    let access_token = non_empty(get_setting(conn, ACCESS_TOKEN_SETTING).map_err(|e| e.to_string())?)
+        .ok_or("Save a TikTok Shop access token before syncing")?;
+    Ok(TikTokApiConfig { app_key, app_secret, access_token, shop_cipher: non_empty(shop_cipher) })
+}
+
+/// TikTok Shop request signature: the HMAC-SHA256 (keyed by the app secret)
+/// of the path, the sorted query parameters other than `sign` and
+/// `access_token`, and the body, wrapped in the app secret
+pub fn sign(path: &str, query: &[(String, String)], body: &str, app_secret: &str) -> String {
+    let mut pairs: Vec<&(String, String)> =
+        query.iter().filter(|(key, _)| key != "sign" && key != "access_token").collect();
+    pairs.sort_by(|a, b| a.0.cmp(&b.0));
+    let params: String = pairs.iter().map(|(key, value)| format!("{}{}", key, value)).collect();
+    hmac_sha256_hex(app_secret, &format!("{}{}{}{}{}", app_secret, path, params, body, app_secret))
+}
+
+// =============================================================================
+// FETCHING
+// =============================================================================
+
+/// Every product of the connected shop, or of the creator's showcase
+pub async fn fetch_products(config: &TikTokApiConfig) -> Result<Vec<TikTokProduct>, String> {
+    let client = reqwest::Client::new();
+    let (path, body) = match config.shop_cipher {
+        Some(_) => (SHOP_PRODUCTS_PATH, json!({ "status": "ACTIVATE" }).to_string()),
+        None => (SHOWCASE_PRODUCTS_PATH, String::new()),
+    };
+
+    let mut products = Vec::new();
+    let mut page_token: Option<String> = None;
+    for _ in 0..MAX_PAGES {
+        let mut query = vec![
+            ("app_key".to_string(), config.app_key.clone()),
+            ("timestamp".to_string(), Utc::now().timestamp().to_string()),
+            ("page_size".to_string(), PAGE_SIZE.to_string()),
+        ];
+        if let Some(cipher) = &config.shop_cipher {
+            query.push(("shop_cipher".to_string(), cipher.clone()));
+        }
+        if let Some(token) = &page_token {
+            query.push(("page_token".to_string(), token.clone()));
+        }
+        query.push(("sign".to_string(), sign(path, &query, &body, &config.app_secret)));
+
+        let request = match config.shop_cipher {
+            Some(_) => client.post(format!("{}{}", API_BASE_URL, path)).body(body.clone()),
+            None => client.get(format!("{}{}", API_BASE_URL, path)),
+        };
+        let response: Value = request
+            .query(&query)
+            .header("x-tts-access-token", &config.access_token)
+            .header("content-type", "application/json")
+            .send()
+            .await
+            .map_err(|e| format!("TikTok request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid TikTok response: {}", e))?;
+
+        let (page, next) = parse_products(&response)?;
+        products.extend(page);
+        match next {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+    Ok(products)
+}
+
+/// A price sent as a string or a number
+fn price_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .filter(|price| *price > 0.0)
+}
+
+/// Parses a products search or showcase response into its products and the
+/// next page token. Shop products are priced from their cheapest SKU.
+pub fn parse_products(response: &Value) -> Result<(Vec<TikTokProduct>, Option<String>), String> {
+    if response["code"].as_i64().unwrap_or(0) != 0 {
+        let message = response["message"].as_str().unwrap_or("unknown error");
+        return Err(format!("TikTok API error {}: {}", response["code"], message));
+    }
+    let data = &response["data"];
+    let items = data["products"].as_array().or_else(|| data["showcase_products"].as_array());
+
+    let products = items
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let id = match &item["id"] {
+                        Value::String(s) if !s.trim().is_empty() => s.trim().to_string(),
+                        Value::Number(n) => n.to_string(),
+                        _ => return None,
+                    };
+                    let title = item["title"].as_str().map(str::trim).filter(|t| !t.is_empty())?.to_string();
+                    let sku_prices = item["skus"].as_array().into_iter().flatten().filter_map(|sku| {
+                        let price = &sku["price"];
+                        price_value(&price["sale_price"]).or_else(|| price_value(&price["tax_exclusive_price"]))
+                    });
+                    let price = sku_prices
+                        .fold(None, |low: Option<f64>, price| Some(low.map_or(price, |low| low.min(price))))
+                        .or_else(|| price_value(&item["price"]["sale_price"]))
+                        .or_else(|| price_value(&item["price"]));
+                    let image_url = item["main_image_url"]
+                        .as_str()
+                        .or_else(|| item["main_images"][0]["urls"][0].as_str())
+                        .map(String::from);
+                    Some(TikTokProduct { id, title, price, image_url })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let next = data["next_page_token"].as_str().filter(|t| !t.is_empty()).map(String::from);
+    Ok((products, next))
+}
+
+// =============================================================================
+// LINKING
+// =============================================================================
+
+/// Links or creates the product for one TikTok item; None when a product
+/// already has its id
+fn link_product(tx: &Transaction, item: &TikTokProduct, editor: Option<&str>) -> rusqlite::Result<Option<(i64, bool)>> {
+    let linked: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM products WHERE tiktok_product_id = ?1)",
+        params![item.id],
+        |row| row.get(0),
+    )?;
+    if linked {
+        return Ok(None);
+    }
+
+    let matched: Option<i64> = tx
+        .query_row(
+            "SELECT id FROM products
+             WHERE TRIM(name) = ?1 COLLATE NOCASE AND COALESCE(tiktok_product_id, '') = ''
+             ORDER BY id LIMIT 1",
+            params![item.title],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if let Some(product_id) = matched {
+        tx.execute(
+            "UPDATE products SET tiktok_product_id = ?2,
+                image_url = COALESCE(NULLIF(image_url, ''), ?3),
+                price_range = COALESCE(NULLIF(price_range, ''), ?4),
+                updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?1",
+            params![product_id, item.id, item.image_url, item.price.map(price_to_range)],
+        )?;
+        return Ok(Some((product_id, false)));
+    }
+
+    tx.execute(
+        "INSERT INTO products (name, category, price_range, image_url, product_url, tiktok_product_id, tags, updated_by)
+         VALUES (?1, 'Uncategorized', ?2, ?3, ?4, ?5, '[\"tiktok\"]', ?6)",
+        params![item.title, item.price.map(price_to_range), item.image_url, item.product_url(), item.id, editor],
+    )?;
+    Ok(Some((tx.last_insert_rowid(), true)))
+}
+
+/// Links every fetched item in one transaction
+pub fn apply(
+    conn: &mut Connection,
+    source: &str,
+    items: &[TikTokProduct],
+    editor: Option<&str>,
+) -> rusqlite::Result<TikTokSyncReport> {
+    let tx = conn.transaction()?;
+    let mut report = TikTokSyncReport { source: source.to_string(), fetched: items.len() as i64, ..Default::default() };
+    for item in items {
+        match link_product(&tx, item, editor)? {
+            Some((product_id, true)) => report.created_product_ids.push(product_id),
+            Some((product_id, false)) => report.linked_product_ids.push(product_id),
+            None => report.already_linked += 1,
+        }
+    }
+    tx.commit()?;
+    Ok(report)
+}
+
+/// Fetches the products and links them
+pub async fn sync(app_handle: &AppHandle, editor: Option<&str>) -> Result<TikTokSyncReport, String> {
+    let config = {
+        let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        config(&conn)?
+    };
+    let source = if config.shop_cipher.is_some() { "shop" } else { "showcase" };
+    let items = fetch_products(&config).await?;
+    let mut conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    apply(&mut conn, source, &items, editor).map_err(|e| e.to_string())
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_are_signed_over_sorted_params_and_body() {
+        let query = vec![
+            ("timestamp".to_string(), "1700000000".to_string()),
+            ("app_key".to_string(), "key".to_string()),
+            ("access_token".to_string(), "ignored".to_string()),
+        ];
+        let expected = hmac_sha256_hex("secret", "secret/path/xapp_keykeytimestamp1700000000{}secret");
+        assert_eq!(sign("/path/x", &query, "{}", "secret"), expected);
+
+        let response = json!({"code": 0, "data": {"next_page_token": "p2", "products": [
+            {"id": "1729", "title": " Lip Oil ", "skus": [
+                {"price": {"sale_price": "14.00"}}, {"price": {"sale_price": "11.50"}}]},
+            {"id": "1730", "title": "Brow Gel", "price": {"sale_price": "9.99"},
+             "main_images": [{"urls": ["https://p16.tiktokcdn.com/brow.jpg"]}]},
+            {"id": "", "title": "No id"}
+        ]}});
+        let (products, next) = parse_products(&response).unwrap();
+        assert_eq!(next.as_deref(), Some("p2"));
+        assert_eq!((products[0].title.as_str(), products[0].price), ("Lip Oil", Some(11.5)));
+        assert_eq!(products[1].image_url.as_deref(), Some("https://p16.tiktokcdn.com/brow.jpg"));
+        assert_eq!(products.len(), 2);
+        let error = parse_products(&json!({"code": 105001, "message": "Access token is expired"})).unwrap_err();
+        assert!(error.contains("Access token is expired"));
+    }
+
+    #[test]
+    fn test_items_link_by_name_or_become_products() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT NOT NULL, category TEXT NOT NULL,
+                price_range TEXT, image_url TEXT, product_url TEXT, tiktok_product_id TEXT, tags TEXT,
+                updated_by TEXT, updated_at DATETIME);
+             INSERT INTO products (id, name, category, tiktok_product_id) VALUES
+                (1, 'lip oil', 'Beauty', NULL), (2, 'Serum', 'Beauty', '1700');",
+        )
+        .unwrap();
+        let item = |id: &str, title: &str| TikTokProduct {
+            id: id.to_string(),
+            title: title.to_string(),
+            ..Default::default()
+        };
+        let items = [item("1729", "Lip Oil"), item("1700", "Serum v2"), item("1730", "Brow Gel")];
+
+        let report = apply(&mut conn, "showcase", &items, None).unwrap();
+        assert_eq!(report.linked_product_ids, [1]);
+        assert_eq!((report.created_product_ids.len(), report.already_linked), (1, 1));
+        let (id, url): (String, String) = conn
+            .query_row("SELECT tiktok_product_id, product_url FROM products WHERE name = 'Brow Gel'", [], |r| {
+                Ok((r.get(0)?, r.get(1)?))
+            })
+            .unwrap();
+        assert_eq!((id.as_str(), url.as_str()), ("1730", "https://shop.tiktok.com/view/product/1730"));
+
+        // A second sync finds everything linked
+        let again = apply(&mut conn, "showcase", &items, None).unwrap();
+        assert_eq!(again.already_linked, 3);
+    }
+}
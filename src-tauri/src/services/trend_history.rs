@@ -0,0 +1,113 @@
+//! Trend History
+//!
+//! Trending scores are recorded by triggers (migration 051) every time a
+//! product's score changes. Momentum is the least-squares slope of the scores
+//! in the recent window, in points per week; scoring prefers viral formats
+//! for rising products and evergreen ones for declining products.
+
+use crate::models::trend::{TrendHistory, TrendMomentum, TrendPoint};
+use chrono::NaiveDateTime;
+use rusqlite::{params, Connection};
+
+/// Days of history momentum is computed over
+pub const MOMENTUM_DAYS: i64 = 60;
+/// Weekly change beyond which a product counts as rising or declining
+pub const SIGNIFICANT_CHANGE_PER_WEEK: f64 = 3.0;
+
+/// Scores recorded for a product in the last `days` days, oldest first
+pub fn points(conn: &Connection, product_id: i64, days: i64) -> rusqlite::Result<Vec<TrendPoint>> {
+    let mut stmt = conn.prepare(
+        "SELECT trending_score, recorded_at FROM trend_history
+         WHERE product_id = ?1 AND recorded_at >= datetime('now', ?2)
+         ORDER BY recorded_at, id",
+    )?;
+    let points = stmt
+        .query_map(params![product_id, format!("-{} days", days)], |row| {
+            Ok(TrendPoint { trending_score: row.get(0)?, recorded_at: row.get(1)? })
+        })?
+        .collect();
+    points
+}
+
+/// Momentum of a series of points; None without two points at least a day apart
+pub fn momentum(points: &[TrendPoint]) -> Option<TrendMomentum> {
+    let parsed: Vec<(f64, f64)> = points
+        .iter()
+        .filter_map(|p| {
+            let at = NaiveDateTime::parse_from_str(&p.recorded_at, "%Y-%m-%d %H:%M:%S").ok()?;
+            Some((at.and_utc().timestamp() as f64 / 86_400.0, p.trending_score as f64))
+        })
+        .collect();
+    let (first_day, last_day) = (parsed.first()?.0, parsed.last()?.0);
+    if parsed.len() < 2 || last_day - first_day < 1.0 {
+        return None;
+    }
+
+    // Least-squares slope of score over days
+    let n = parsed.len() as f64;
+    let mean_x = parsed.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = parsed.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = parsed.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = parsed.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let change_per_week = (covariance / variance * 7.0 * 100.0).round() / 100.0;
+
+    let direction = if change_per_week >= SIGNIFICANT_CHANGE_PER_WEEK {
+        "rising"
+    } else if change_per_week <= -SIGNIFICANT_CHANGE_PER_WEEK {
+        "declining"
+    } else {
+        "steady"
+    };
+    Some(TrendMomentum {
+        direction: direction.to_string(),
+        change_per_week,
+        first_score: points.first()?.trending_score,
+        latest_score: points.last()?.trending_score,
+        points: points.len() as i64,
+        days: (last_day - first_day).round() as i64,
+    })
+}
+
+/// A product's momentum over the last `MOMENTUM_DAYS` days
+pub fn product_momentum(conn: &Connection, product_id: i64) -> rusqlite::Result<Option<TrendMomentum>> {
+    Ok(momentum(&points(conn, product_id, MOMENTUM_DAYS)?))
+}
+
+pub fn history(conn: &Connection, product_id: i64, days: i64) -> rusqlite::Result<TrendHistory> {
+    let points = points(conn, product_id, days)?;
+    Ok(TrendHistory { product_id, momentum: momentum(&points), points })
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(score: i32, at: &str) -> TrendPoint {
+        TrendPoint { trending_score: score, recorded_at: at.to_string() }
+    }
+
+    #[test]
+    fn test_momentum_direction_from_slope() {
+        let rising = [
+            point(40, "2026-05-01 09:00:00"),
+            point(55, "2026-05-08 09:00:00"),
+            point(70, "2026-05-15 09:00:00"),
+        ];
+        let m = momentum(&rising).unwrap();
+        assert_eq!((m.direction.as_str(), m.change_per_week), ("rising", 15.0));
+        assert_eq!((m.first_score, m.latest_score, m.points, m.days), (40, 70, 3, 14));
+
+        let declining = [point(80, "2026-05-01 09:00:00"), point(60, "2026-05-29 09:00:00")];
+        assert_eq!(momentum(&declining).unwrap().direction, "declining");
+        let steady = [point(60, "2026-05-01 09:00:00"), point(62, "2026-05-29 09:00:00")];
+        assert_eq!(momentum(&steady).unwrap().direction, "steady");
+
+        // A single point, or points within a day, say nothing about direction
+        assert!(momentum(&rising[..1]).is_none());
+        assert!(momentum(&[point(40, "2026-05-01 09:00:00"), point(90, "2026-05-01 18:00:00")]).is_none());
+    }
+}
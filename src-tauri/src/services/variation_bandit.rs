@@ -0,0 +1,292 @@
+//! Variation Bandit
+//!
+//! Picks which of a product's ads to post next on a platform by Thompson
+//! sampling. Each ad's click-through rate has a Beta posterior: the platform
+//! benchmark for the product's category stands in for `PRIOR_IMPRESSIONS`
+//! impressions, and the impressions and clicks recorded for the ad on that
+//! platform are added on top. One draw is taken per ad and the highest wins,
+//! so proven ads are posted most while untested ones still get their turn.
+//!
+//! A product's variations for a platform are its postable ads targeting that
+//! platform, or all of its postable ads when none do.
+
+use crate::models::variation::{NextVariation, RecordVariationPerformanceInput, VariationArm};
+use crate::services::benchmarks;
+use chrono::{NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Impressions' worth of weight the benchmark CTR carries in each posterior
+pub const PRIOR_IMPRESSIONS: f64 = 200.0;
+
+/// Recorded totals for one ad on a platform
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArmStats {
+    pub ad_id: i64,
+    pub variation_name: Option<String>,
+    pub headline: String,
+    pub impressions: i64,
+    pub clicks: i64,
+}
+
+/// Validates and stores one day of an ad's platform figures
+pub fn record(conn: &Connection, input: &RecordVariationPerformanceInput) -> Result<(), String> {
+    let platform = input.platform.trim().to_lowercase();
+    if platform.is_empty() {
+        return Err("Platform is required".to_string());
+    }
+    if input.impressions < 0 || input.clicks < 0 {
+        return Err("Impressions and clicks can't be negative".to_string());
+    }
+    if input.clicks > input.impressions {
+        return Err("Clicks can't exceed impressions".to_string());
+    }
+    let day = match input.day.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        Some(day) => NaiveDate::parse_from_str(day, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid day '{}', expected YYYY-MM-DD", day))?,
+        None => Utc::now().date_naive(),
+    };
+    let exists: bool = conn
+        .query_row("SELECT EXISTS(SELECT 1 FROM ad_copies WHERE id = ?1)", params![input.ad_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+    if !exists {
+        return Err(format!("Ad {} not found", input.ad_id));
+    }
+
+    conn.execute(
+        "INSERT INTO variation_performance (ad_id, platform, day, impressions, clicks)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT (ad_id, platform, day) DO UPDATE SET
+            impressions = excluded.impressions, clicks = excluded.clicks, updated_at = CURRENT_TIMESTAMP",
+        params![input.ad_id, platform, day.format("%Y-%m-%d").to_string(), input.impressions, input.clicks],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// The product's variations for `platform` with their recorded totals, oldest first
+pub fn arm_stats(conn: &Connection, product_id: i64, platform: &str) -> rusqlite::Result<Vec<ArmStats>> {
+    let platform = platform.trim().to_lowercase();
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.variation_name, a.headline,
+                COALESCE(SUM(v.impressions), 0), COALESCE(SUM(v.clicks), 0),
+                LOWER(COALESCE(json_extract(a.platform_specific_data, '$.target_platform'), '')) = ?2
+         FROM ad_copies a
+         LEFT JOIN variation_performance v ON v.ad_id = a.id AND v.platform = ?2
+         WHERE a.product_id = ?1 AND a.parent_ad_id IS NULL AND COALESCE(a.ad_type, '') != 'email_sequence'
+         GROUP BY a.id
+         ORDER BY a.id",
+    )?;
+    let rows = stmt
+        .query_map(params![product_id, platform], |row| {
+            let stats = ArmStats {
+                ad_id: row.get(0)?,
+                variation_name: row.get(1)?,
+                headline: row.get(2)?,
+                impressions: row.get(3)?,
+                clicks: row.get(4)?,
+            };
+            Ok((stats, row.get::<_, bool>(5)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let targeted = rows.iter().any(|(_, targets)| *targets);
+    Ok(rows
+        .into_iter()
+        .filter(|(_, targets)| *targets || !targeted)
+        .map(|(stats, _)| stats)
+        .collect())
+}
+
+/// The ad to post next on `platform`
+pub fn next_variation(conn: &Connection, product_id: i64, platform: &str) -> Result<NextVariation, String> {
+    let category: String = conn
+        .query_row("SELECT category FROM products WHERE id = ?1", params![product_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Product {} not found", product_id))?;
+    let arms = arm_stats(conn, product_id, platform).map_err(|e| e.to_string())?;
+    if arms.is_empty() {
+        return Err("This product has no ads to choose from yet".to_string());
+    }
+    let prior_ctr = benchmarks::lookup(conn, platform, &category)?.ctr;
+
+    let mut rng = Rng(uuid::Uuid::new_v4().as_u64_pair().0 | 1);
+    let (arms, exploring) = choose(&arms, prior_ctr, &mut rng);
+    Ok(NextVariation {
+        product_id,
+        platform: platform.trim().to_lowercase(),
+        ad_id: arms[0].ad_id,
+        prior_ctr,
+        exploring,
+        arms,
+    })
+}
+
+/// Draws a CTR for every arm, highest first; the flag is set when the winner's
+/// posterior mean is below the best arm's
+pub fn choose(stats: &[ArmStats], prior_ctr: f64, rng: &mut Rng) -> (Vec<VariationArm>, bool) {
+    let prior_ctr = prior_ctr.clamp(0.0001, 0.9999);
+    let (prior_clicks, prior_misses) = (prior_ctr * PRIOR_IMPRESSIONS, (1.0 - prior_ctr) * PRIOR_IMPRESSIONS);
+
+    let mut draws: Vec<(VariationArm, f64)> = stats
+        .iter()
+        .map(|s| {
+            let alpha = prior_clicks + s.clicks.max(0) as f64;
+            let beta = prior_misses + (s.impressions - s.clicks).max(0) as f64;
+            let arm = VariationArm {
+                ad_id: s.ad_id,
+                variation_name: s.variation_name.clone(),
+                headline: s.headline.clone(),
+                impressions: s.impressions,
+                clicks: s.clicks,
+                observed_ctr: (s.impressions > 0).then(|| s.clicks as f64 / s.impressions as f64),
+                sampled_ctr: rng.beta(alpha, beta),
+            };
+            (arm, alpha / (alpha + beta))
+        })
+        .collect();
+    draws.sort_by(|a, b| b.0.sampled_ctr.total_cmp(&a.0.sampled_ctr));
+
+    let best_mean = draws.iter().map(|(_, mean)| *mean).fold(0.0, f64::max);
+    let exploring = draws.first().is_some_and(|(_, mean)| *mean < best_mean);
+    (draws.into_iter().map(|(arm, _)| arm).collect(), exploring)
+}
+
+/// Small xorshift generator with the distributions Thompson sampling needs
+pub struct Rng(pub u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform in (0, 1)
+    fn unit(&mut self) -> f64 {
+        ((self.next() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /// Standard normal (Box-Muller)
+    fn normal(&mut self) -> f64 {
+        (-2.0 * self.unit().ln()).sqrt() * (2.0 * std::f64::consts::PI * self.unit()).cos()
+    }
+
+    /// Gamma(shape, 1) by Marsaglia and Tsang; shapes below 1 are boosted
+    fn gamma(&mut self, shape: f64) -> f64 {
+        if shape < 1.0 {
+            return self.gamma(shape + 1.0) * self.unit().powf(1.0 / shape);
+        }
+        let d = shape - 1.0 / 3.0;
+        let c = 1.0 / (9.0 * d).sqrt();
+        loop {
+            let x = self.normal();
+            let v = (1.0 + c * x).powi(3);
+            if v <= 0.0 {
+                continue;
+            }
+            if self.unit().ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+                return d * v;
+            }
+        }
+    }
+
+    /// Beta(alpha, beta) as the ratio of two gamma draws
+    pub fn beta(&mut self, alpha: f64, beta: f64) -> f64 {
+        let x = self.gamma(alpha);
+        let y = self.gamma(beta);
+        x / (x + y)
+    }
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arm(ad_id: i64, impressions: i64, clicks: i64) -> ArmStats {
+        ArmStats { ad_id, variation_name: None, headline: format!("Ad {}", ad_id), impressions, clicks }
+    }
+
+    #[test]
+    fn test_beta_draws_center_on_the_mean() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        let draws: Vec<f64> = (0..4000).map(|_| rng.beta(2.0, 198.0)).collect();
+        let mean = draws.iter().sum::<f64>() / draws.len() as f64;
+        assert!((mean - 0.01).abs() < 0.001, "mean {}", mean);
+        assert!(draws.iter().all(|d| (0.0..1.0).contains(d)));
+        assert!((0..100).map(|_| rng.beta(0.5, 0.5)).all(|d| (0.0..=1.0).contains(&d)));
+    }
+
+    #[test]
+    fn test_choose_favors_the_proven_ad_and_still_explores() {
+        let mut rng = Rng(42);
+        let stats = [arm(1, 20_000, 100), arm(2, 20_000, 300), arm(3, 0, 0)];
+        let mut wins = [0; 4];
+        let mut explored = 0;
+        for _ in 0..1000 {
+            let (arms, exploring) = choose(&stats, 0.01, &mut rng);
+            assert_eq!(arms.len(), 3);
+            assert!(arms.windows(2).all(|w| w[0].sampled_ctr >= w[1].sampled_ctr));
+            wins[arms[0].ad_id as usize] += 1;
+            explored += exploring as i32;
+        }
+
+        // 1.5% beats 0.5% almost every time; the untested ad still gets turns
+        assert!(wins[2] > wins[3] * 2, "wins {:?}", wins);
+        assert!(wins[1] < 10 && wins[3] > 0, "wins {:?}", wins);
+        assert_eq!(explored, 1000 - wins[2]);
+
+        let (arms, _) = choose(&stats, 0.01, &mut rng);
+        let untested = arms.iter().find(|a| a.ad_id == 3).unwrap();
+        assert_eq!(untested.observed_ctr, None);
+        assert_eq!(arms.iter().find(|a| a.ad_id == 2).unwrap().observed_ctr, Some(0.015));
+    }
+
+    #[test]
+    fn test_arms_are_the_ads_targeting_the_platform() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE ad_copies (id INTEGER PRIMARY KEY, product_id INTEGER, variation_name TEXT,
+                 headline TEXT NOT NULL, ad_type TEXT, platform_specific_data TEXT, parent_ad_id INTEGER);
+             INSERT INTO ad_copies VALUES
+                 (1, 7, 'Hook A', 'A', 'story', '{\"target_platform\":\"TikTok\"}', NULL),
+                 (2, 7, 'Hook B', 'B', 'story', '{\"target_platform\":\"tiktok\"}', NULL),
+                 (3, 7, 'Pin', 'C', 'carousel', '{\"target_platform\":\"pinterest\"}', NULL),
+                 (4, 7, 'Sequence', 'D', 'email_sequence', NULL, NULL),
+                 (5, 8, 'Other product', 'E', 'story', NULL, NULL);",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../../migrations/052_variation_performance.sql")).unwrap();
+
+        let input = |ad_id: i64, platform: &str, day: &str, impressions: i64, clicks: i64| {
+            RecordVariationPerformanceInput {
+                ad_id,
+                platform: platform.to_string(),
+                day: Some(day.to_string()),
+                impressions,
+                clicks,
+            }
+        };
+        record(&conn, &input(1, "TikTok", "2026-05-01", 500, 5)).unwrap();
+        record(&conn, &input(1, "tiktok", "2026-05-02", 800, 9)).unwrap();
+        // Re-recording a day replaces it
+        record(&conn, &input(1, "tiktok", "2026-05-02", 1000, 12)).unwrap();
+        record(&conn, &input(2, "instagram", "2026-05-02", 400, 4)).unwrap();
+        assert!(record(&conn, &input(1, "tiktok", "2026-05-03", 10, 11)).is_err());
+        assert!(record(&conn, &input(1, "tiktok", "May 3", 10, 1)).is_err());
+        assert!(record(&conn, &input(99, "tiktok", "2026-05-03", 10, 1)).is_err());
+
+        let tiktok = arm_stats(&conn, 7, "TIKTOK").unwrap();
+        let totals: Vec<(i64, i64, i64)> = tiktok.iter().map(|a| (a.ad_id, a.impressions, a.clicks)).collect();
+        assert_eq!(totals, vec![(1, 1500, 17), (2, 0, 0)]);
+
+        // No ad targets YouTube, so every postable ad competes
+        let youtube: Vec<i64> = arm_stats(&conn, 7, "youtube").unwrap().iter().map(|a| a.ad_id).collect();
+        assert_eq!(youtube, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,327 @@
+//! Product Watchlist
+//!
+//! Watched products are re-scraped by a scheduled job. Each check compares the
+//! page's title, price, and availability with what the previous check saw and
+//! logs every difference to `product_changes`; the first check only records a
+//! baseline. The product's own availability is updated along the way. When a
+//! run finds changes an OS notification lists them, so a stockout or price
+//! hike on a product being promoted doesn't go unnoticed.
+
+use crate::database::get_connection;
+use crate::database::settings::get_setting_or;
+use crate::models::scraped_product::ScrapedProduct;
+use crate::models::watchlist::{ProductChange, WatchCheckReport, WatchedProduct};
+use crate::services::availability;
+use crate::services::scraper::scrape_product;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+const NOTIFICATIONS_SETTING: &str = "watchlist_notifications_enabled";
+/// Changes named in a notification before it switches to "and N more"
+const NOTIFICATION_CHANGES: usize = 5;
+
+/// The fields compared between checks
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot {
+    pub title: Option<String>,
+    pub price: Option<f64>,
+    pub availability: Option<String>,
+}
+
+impl From<&ScrapedProduct> for Snapshot {
+    fn from(scraped: &ScrapedProduct) -> Self {
+        Snapshot {
+            title: scraped.title.as_deref().map(str::trim).filter(|t| !t.is_empty()).map(String::from),
+            price: scraped.price,
+            availability: scraped.availability.as_deref().and_then(availability::normalize),
+        }
+    }
+}
+
+fn format_price(price: f64) -> String {
+    format!("{:.2}", price)
+}
+
+/// `(field, old, new)` for every field that differs. A field the page didn't
+/// publish this time is not treated as a change.
+pub fn diff(previous: &Snapshot, current: &Snapshot) -> Vec<(&'static str, Option<String>, Option<String>)> {
+    let mut changes = Vec::new();
+    if let Some(title) = &current.title {
+        if previous.title.as_ref() != Some(title) {
+            changes.push(("title", previous.title.clone(), Some(title.clone())));
+        }
+    }
+    if let Some(price) = current.price {
+        if previous.price.is_none_or(|old| (old - price).abs() >= 0.005) {
+            changes.push(("price", previous.price.map(format_price), Some(format_price(price))));
+        }
+    }
+    if let Some(availability) = &current.availability {
+        if previous.availability.as_ref() != Some(availability) {
+            changes.push(("availability", previous.availability.clone(), Some(availability.clone())));
+        }
+    }
+    changes
+}
+
+// =============================================================================
+// WATCHLIST
+// =============================================================================
+
+pub fn watch(conn: &Connection, product_id: i64) -> Result<(), String> {
+    let url: Option<Option<String>> = conn
+        .query_row("SELECT product_url FROM products WHERE id = ?1", params![product_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    match url {
+        None => return Err(format!("Product {} not found", product_id)),
+        Some(url) if url.as_deref().is_none_or(|u| u.trim().is_empty()) => {
+            return Err("Add a product URL before watching this product".to_string())
+        }
+        Some(_) => {}
+    }
+    conn.execute("INSERT OR IGNORE INTO product_watches (product_id) VALUES (?1)", params![product_id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn unwatch(conn: &Connection, product_id: i64) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM product_watches WHERE product_id = ?1", params![product_id])?;
+    Ok(())
+}
+
+pub fn list(conn: &Connection) -> rusqlite::Result<Vec<WatchedProduct>> {
+    let mut stmt = conn.prepare(
+        "SELECT w.product_id, p.name, p.product_url, w.last_title, w.last_price, w.last_availability,
+                w.last_checked_at, w.last_error
+         FROM product_watches w JOIN products p ON p.id = w.product_id
+         ORDER BY p.name",
+    )?;
+    let watched = stmt
+        .query_map([], |row| {
+            Ok(WatchedProduct {
+                product_id: row.get(0)?,
+                product_name: row.get(1)?,
+                product_url: row.get(2)?,
+                last_title: row.get(3)?,
+                last_price: row.get(4)?,
+                last_availability: row.get(5)?,
+                last_checked_at: row.get(6)?,
+                last_error: row.get(7)?,
+            })
+        })?
+        .collect();
+    watched
+}
+
+fn change_from_row(row: &Row) -> rusqlite::Result<ProductChange> {
+    Ok(ProductChange {
+        id: row.get(0)?,
+        product_id: row.get(1)?,
+        product_name: row.get(2)?,
+        field: row.get(3)?,
+        old_value: row.get(4)?,
+        new_value: row.get(5)?,
+        detected_at: row.get(6)?,
+    })
+}
+
+/// Logged changes, newest first; `after_id` limits to changes logged since
+pub fn changes(
+    conn: &Connection,
+    product_id: Option<i64>,
+    after_id: i64,
+    limit: i64,
+) -> rusqlite::Result<Vec<ProductChange>> {
+    let mut stmt = conn.prepare(
+        "SELECT c.id, c.product_id, p.name, c.field, c.old_value, c.new_value, c.detected_at
+         FROM product_changes c JOIN products p ON p.id = c.product_id
+         WHERE (?1 IS NULL OR c.product_id = ?1) AND c.id > ?2
+         ORDER BY c.id DESC LIMIT ?3",
+    )?;
+    let changes = stmt.query_map(params![product_id, after_id, limit], change_from_row)?.collect();
+    changes
+}
+
+/// Compares `current` with the stored state, logs the differences, and stores
+/// `current` as the new state. Returns how many changes were logged.
+pub fn record_check(conn: &mut Connection, product_id: i64, current: &Snapshot) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    let stored: Option<(Snapshot, Option<String>)> = tx
+        .query_row(
+            "SELECT last_title, last_price, last_availability, last_checked_at
+             FROM product_watches WHERE product_id = ?1",
+            params![product_id],
+            |row| {
+                let snapshot = Snapshot { title: row.get(0)?, price: row.get(1)?, availability: row.get(2)? };
+                Ok((snapshot, row.get(3)?))
+            },
+        )
+        .optional()?;
+    let Some((previous, last_checked_at)) = stored else {
+        return Ok(0);
+    };
+
+    let mut logged = 0;
+    // The first check only records the baseline
+    if last_checked_at.is_some() {
+        for (field, old, new) in diff(&previous, current) {
+            tx.execute(
+                "INSERT INTO product_changes (product_id, field, old_value, new_value) VALUES (?1, ?2, ?3, ?4)",
+                params![product_id, field, old, new],
+            )?;
+            logged += 1;
+        }
+    }
+
+    tx.execute(
+        "UPDATE product_watches SET
+            last_title = COALESCE(?2, last_title),
+            last_price = COALESCE(?3, last_price),
+            last_availability = COALESCE(?4, last_availability),
+            last_checked_at = CURRENT_TIMESTAMP,
+            last_error = NULL
+         WHERE product_id = ?1",
+        params![product_id, current.title, current.price, current.availability],
+    )?;
+    availability::record_scraped(&tx, product_id, current.availability.as_deref())?;
+    tx.commit()?;
+    Ok(logged)
+}
+
+fn record_error(conn: &Connection, product_id: i64, error: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE product_watches SET last_error = ?2 WHERE product_id = ?1",
+        params![product_id, error],
+    )?;
+    Ok(())
+}
+
+/// Re-scrapes every watched product and logs what changed
+pub async fn check_all(app_handle: &AppHandle) -> Result<WatchCheckReport, String> {
+    let (targets, last_change_id) = {
+        let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        let targets: Vec<(i64, String)> = list(&conn)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .filter_map(|w| w.product_url.filter(|u| !u.trim().is_empty()).map(|url| (w.product_id, url)))
+            .collect();
+        let last_change_id: i64 = conn
+            .query_row("SELECT COALESCE(MAX(id), 0) FROM product_changes", [], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+        (targets, last_change_id)
+    };
+
+    let mut report = WatchCheckReport::default();
+    for (product_id, url) in targets {
+        let scraped = scrape_product(&url).await;
+        let mut conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+        match scraped {
+            Ok(scraped) => {
+                record_check(&mut conn, product_id, &Snapshot::from(&scraped)).map_err(|e| e.to_string())?;
+            }
+            Err(e) => {
+                record_error(&conn, product_id, &e).map_err(|e| e.to_string())?;
+                report.failed += 1;
+            }
+        }
+        report.checked += 1;
+    }
+
+    let conn = get_connection(app_handle).map_err(|e| e.to_string())?;
+    report.changes = changes(&conn, None, last_change_id, i64::MAX).map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+/// "Lamp: price 19.99 → 24.99" style lines for a notification body
+pub fn notification_body(changes: &[ProductChange]) -> String {
+    let mut lines: Vec<String> = changes
+        .iter()
+        .take(NOTIFICATION_CHANGES)
+        .map(|c| {
+            format!(
+                "{}: {} {} → {}",
+                c.product_name,
+                c.field,
+                c.old_value.as_deref().unwrap_or("?"),
+                c.new_value.as_deref().unwrap_or("?")
+            )
+        })
+        .collect();
+    if changes.len() > NOTIFICATION_CHANGES {
+        lines.push(format!("and {} more", changes.len() - NOTIFICATION_CHANGES));
+    }
+    lines.join("\n")
+}
+
+/// Scheduler entry point
+pub fn run_watch_job(app_handle: &AppHandle) -> Result<String, String> {
+    let report = tauri::async_runtime::block_on(check_all(app_handle))?;
+
+    let notify = get_connection(app_handle)
+        .map(|conn| get_setting_or(&conn, NOTIFICATIONS_SETTING, "true") == "true")
+        .unwrap_or(false);
+    if notify && !report.changes.is_empty() {
+        let body = notification_body(&report.changes);
+        if let Err(e) = app_handle.notification().builder().title("Watched products changed").body(&body).show() {
+            eprintln!("Failed to show watchlist notification: {}", e);
+        }
+    }
+
+    Ok(format!(
+        "{} watched product(s) checked, {} failed, {} change(s)",
+        report.checked,
+        report.failed,
+        report.changes.len()
+    ))
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changes_are_logged_after_the_baseline_check() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(&format!(
+            "CREATE TABLE products (id INTEGER PRIMARY KEY, name TEXT, product_url TEXT, availability TEXT,
+                availability_checked_at DATETIME);
+             INSERT INTO products (id, name, product_url)
+                VALUES (1, 'Lamp', 'https://shop.example/lamp'), (2, 'Mug', NULL);
+             {}",
+            include_str!("../../../migrations/044_product_watchlist.sql")
+        ))
+        .unwrap();
+
+        assert!(watch(&conn, 2).is_err());
+        watch(&conn, 1).unwrap();
+
+        let baseline = Snapshot {
+            title: Some("Lamp".to_string()),
+            price: Some(19.99),
+            availability: availability::normalize("https://schema.org/InStock"),
+        };
+        assert_eq!(record_check(&mut conn, 1, &baseline).unwrap(), 0);
+
+        // Missing fields are not changes; a price within a cent is unchanged
+        let same = Snapshot { title: None, price: Some(19.991), availability: Some("in_stock".to_string()) };
+        assert_eq!(record_check(&mut conn, 1, &same).unwrap(), 0);
+
+        let changed =
+            Snapshot { price: Some(24.5), availability: availability::normalize("OutOfStock"), ..baseline };
+        assert_eq!(record_check(&mut conn, 1, &changed).unwrap(), 2);
+
+        let logged = changes(&conn, Some(1), 0, 10).unwrap();
+        assert_eq!(logged[0].field, "availability");
+        assert_eq!(logged[0].new_value.as_deref(), Some("out_of_stock"));
+        assert_eq!(logged[1].old_value.as_deref(), Some("19.99"));
+        assert_eq!(notification_body(&logged[1..]), "Lamp: price 19.99 → 24.50");
+        assert_eq!(list(&conn).unwrap()[0].last_price, Some(24.5));
+        assert!(availability::is_out_of_stock(&conn, 1).unwrap());
+    }
+}
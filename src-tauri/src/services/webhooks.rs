@@ -0,0 +1,253 @@
+//! Outgoing Webhooks
+//!
+//! Notifies user-configured endpoints (Zapier, Make, n8n, ...) when something
+//! happens in the workspace. Each delivery is a JSON POST:
+//!
+//! ```json
+//! { "event": "ad_created", "occurred_at": "2024-05-01T12:00:00Z", "data": { ... } }
+//! ```
+//!
+//! with an `X-AffilAI-Event` header and, when the webhook has a secret, an
+//! `X-AffilAI-Signature: sha256=<hex>` HMAC of the raw body. Deliveries run
+//! in the background so the action that fired them never waits on the
+//! network; every attempt is logged in `webhook_deliveries`.
+
+use crate::commands::ad_generation::GeneratedAdCopy;
+use crate::database::get_connection;
+use crate::models::webhook::{SaveWebhookInput, Webhook, WebhookDelivery};
+use crate::services::postback::hmac_sha256_hex;
+use rusqlite::{params, Connection, Row};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::AppHandle;
+
+pub const AD_CREATED: &str = "ad_created";
+pub const LINK_CREATED: &str = "link_created";
+pub const CONVERSION_RECORDED: &str = "conversion_recorded";
+/// Sent only by "Send test" in settings
+pub const TEST_EVENT: &str = "test";
+
+pub const WEBHOOK_EVENTS: [&str; 3] = [AD_CREATED, LINK_CREATED, CONVERSION_RECORDED];
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const WEBHOOK_COLUMNS: &str = "id, name, url, secret, events, active, created_at, updated_at";
+
+/// Maps a row selected with `WEBHOOK_COLUMNS` into a `Webhook`
+pub fn webhook_from_row(row: &Row) -> rusqlite::Result<Webhook> {
+    let events: Option<String> = row.get(4)?;
+    Ok(Webhook {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        url: row.get(2)?,
+        secret: row.get(3)?,
+        events: events
+            .and_then(|j| serde_json::from_str(&j).ok())
+            .unwrap_or_default(),
+        active: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+/// Whether the webhook wants `event`; an empty event list means every event
+pub fn subscribes(webhook: &Webhook, event: &str) -> bool {
+    event == TEST_EVENT || webhook.events.is_empty() || webhook.events.iter().any(|e| e == event)
+}
+
+/// Rejects webhooks without a name, with a non-HTTP URL, or with unknown events
+pub fn validate(input: &SaveWebhookInput) -> Result<(), String> {
+    if input.name.trim().is_empty() {
+        return Err("Webhook name is required".to_string());
+    }
+    let url = input.url.trim();
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err("Webhook URL must start with http:// or https://".to_string());
+    }
+    if let Some(unknown) = input.events.iter().find(|e| !WEBHOOK_EVENTS.contains(&e.as_str())) {
+        return Err(format!(
+            "Unknown webhook event '{}'; expected one of {}",
+            unknown,
+            WEBHOOK_EVENTS.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+pub fn list(conn: &Connection) -> rusqlite::Result<Vec<Webhook>> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM webhooks ORDER BY name", WEBHOOK_COLUMNS))?;
+    let webhooks = stmt.query_map([], webhook_from_row)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(webhooks)
+}
+
+/// The request body for an event
+pub fn payload(event: &str, data: &Value) -> String {
+    json!({
+        "event": event,
+        "occurred_at": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        "data": data,
+    })
+    .to_string()
+}
+
+/// Posts one event to one webhook; returns the HTTP status or the failure
+pub async fn deliver(webhook: &Webhook, event: &str, body: &str) -> Result<u16, (Option<u16>, String)> {
+    let client = reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .build()
+        .map_err(|e| (None, e.to_string()))?;
+
+    let mut request = client
+        .post(&webhook.url)
+        .header("Content-Type", "application/json")
+        .header("X-AffilAI-Event", event)
+        .body(body.to_string());
+    if let Some(secret) = webhook.secret.as_deref().filter(|s| !s.is_empty()) {
+        request = request.header("X-AffilAI-Signature", format!("sha256={}", hmac_sha256_hex(secret, body)));
+    }
+
+    let response = request.send().await.map_err(|e| (None, e.to_string()))?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(status.as_u16())
+    } else {
+        Err((Some(status.as_u16()), format!("Endpoint responded {}", status)))
+    }
+}
+
+/// Records a delivery attempt
+pub fn log_delivery(
+    conn: &Connection,
+    webhook_id: i64,
+    event: &str,
+    result: &Result<u16, (Option<u16>, String)>,
+) -> rusqlite::Result<WebhookDelivery> {
+    let (status_code, error) = match result {
+        Ok(status) => (Some(*status), None),
+        Err((status, error)) => (*status, Some(error.as_str())),
+    };
+    conn.execute(
+        "INSERT INTO webhook_deliveries (webhook_id, event, status_code, success, error)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![webhook_id, event, status_code, result.is_ok(), error],
+    )?;
+    conn.query_row(
+        "SELECT id, webhook_id, event, status_code, success, error, delivered_at
+         FROM webhook_deliveries WHERE id = ?1",
+        params![conn.last_insert_rowid()],
+        delivery_from_row,
+    )
+}
+
+pub fn delivery_from_row(row: &Row) -> rusqlite::Result<WebhookDelivery> {
+    Ok(WebhookDelivery {
+        id: row.get(0)?,
+        webhook_id: row.get(1)?,
+        event: row.get(2)?,
+        status_code: row.get(3)?,
+        success: row.get(4)?,
+        error: row.get(5)?,
+        delivered_at: row.get(6)?,
+    })
+}
+
+/// Event data for a new ad
+pub fn ad_data(ad: &GeneratedAdCopy) -> Value {
+    json!({
+        "ad_id": ad.id,
+        "product_id": ad.product_id,
+        "ad_type": ad.ad_type,
+        "headline": ad.headline,
+        "body_text": ad.body_text,
+        "cta": ad.cta,
+    })
+}
+
+/// Event data for a recorded conversion
+pub fn conversion_data(conn: &Connection, conversion_id: i64) -> rusqlite::Result<Value> {
+    conn.query_row(
+        "SELECT c.link_id, l.product_id, c.order_id, c.order_value, c.commission, c.status, c.converted_at
+         FROM conversion_events c LEFT JOIN affiliate_links l ON l.id = c.link_id
+         WHERE c.id = ?1",
+        params![conversion_id],
+        |row| {
+            Ok(json!({
+                "conversion_id": conversion_id,
+                "link_id": row.get::<_, i64>(0)?,
+                "product_id": row.get::<_, Option<i64>>(1)?,
+                "order_id": row.get::<_, Option<String>>(2)?,
+                "order_value": row.get::<_, Option<f64>>(3)?,
+                "commission": row.get::<_, Option<f64>>(4)?,
+                "status": row.get::<_, Option<String>>(5)?,
+                "converted_at": row.get::<_, Option<String>>(6)?,
+            }))
+        },
+    )
+}
+
+/// Fires `event` to every active webhook subscribed to it, in the background.
+/// Failures are logged, never surfaced to the action that emitted the event.
+pub fn emit(app_handle: &AppHandle, event: &'static str, data: Value) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let webhooks: Vec<Webhook> = match get_connection(&app_handle).map(|conn| list(&conn)) {
+            Ok(Ok(webhooks)) => webhooks
+                .into_iter()
+                .filter(|w| w.active && subscribes(w, event))
+                .collect(),
+            _ => return,
+        };
+        if webhooks.is_empty() {
+            return;
+        }
+
+        let body = payload(event, &data);
+        for webhook in webhooks {
+            let result = deliver(&webhook, event, &body).await;
+            if let Ok(conn) = get_connection(&app_handle) {
+                if let Err(e) = log_delivery(&conn, webhook.id, event, &result) {
+                    eprintln!("Failed to log webhook delivery: {}", e);
+                }
+            }
+        }
+    });
+}
+
+// =============================================================================
+// UNIT TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscriptions_and_validation() {
+        let mut input = SaveWebhookInput {
+            id: None,
+            name: "Zapier".to_string(),
+            url: "https://hooks.zapier.com/abc".to_string(),
+            secret: None,
+            events: vec!["ad_created".to_string()],
+            active: true,
+        };
+        assert!(validate(&input).is_ok());
+
+        let webhook = Webhook {
+            id: 1,
+            name: input.name.clone(),
+            url: input.url.clone(),
+            secret: None,
+            events: input.events.clone(),
+            active: true,
+            created_at: None,
+            updated_at: None,
+        };
+        assert!(subscribes(&webhook, AD_CREATED));
+        assert!(!subscribes(&webhook, LINK_CREATED));
+        assert!(subscribes(&webhook, TEST_EVENT));
+
+        input.events.push("product_deleted".to_string());
+        assert!(validate(&input).unwrap_err().contains("product_deleted"));
+    }
+}
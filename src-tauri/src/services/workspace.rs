@@ -0,0 +1,85 @@
+//! Workspace Reset
+//!
+//! Clears everything the user has built up (products, links, ads, traffic,
+//! and the AI output attached to them) while keeping configuration: settings,
+//! credentials, affiliate programs, niche profiles, templates, and webhooks.
+
+use crate::models::workspace::WorkspaceReset;
+use rusqlite::{params_from_iter, Connection};
+
+/// Data tables, children before parents. Campaign 1 is the system campaign
+/// for direct product ads and survives a reset.
+const WORKSPACE_TABLES: [&str; 37] = [
+    "store_products",
+    "link_refreshes",
+    "experiment_variants",
+    "experiments",
+    "trend_history",
+    "note_refs",
+    "notes",
+    "bundle_products",
+    "product_changes",
+    "product_watches",
+    "webhook_deliveries",
+    "headline_variants",
+    "variation_performance",
+    "approval_transitions",
+    "ad_critiques",
+    "review_insights",
+    "content_angles",
+    "product_research",
+    "ai_discoveries",
+    "bulk_operation_items",
+    "bulk_operations",
+    "ideas",
+    "digests",
+    "postback_log",
+    "traffic_sessions",
+    "conversion_events",
+    "daily_metrics",
+    "click_rollups",
+    "click_events",
+    "performance_records",
+    "campaign_links",
+    "creative_assets",
+    "ad_copies",
+    "affiliate_links",
+    "product_programs",
+    "bundles",
+    "products",
+];
+
+/// Product, link, and ad rows, as `(table, id)`, for change events
+pub fn entity_rows(conn: &Connection) -> rusqlite::Result<Vec<(&'static str, i64)>> {
+    let mut rows = Vec::new();
+    for table in ["products", "affiliate_links", "ad_copies"] {
+        let mut stmt = conn.prepare(&format!("SELECT id FROM {}", table))?;
+        let ids = stmt.query_map([], |row| row.get::<_, i64>(0))?.collect::<Result<Vec<_>, _>>()?;
+        rows.extend(ids.into_iter().map(|id| (table, id)));
+    }
+    Ok(rows)
+}
+
+/// Deletes all workspace data in one transaction and restarts row IDs at 1
+pub fn reset(conn: &mut Connection) -> rusqlite::Result<WorkspaceReset> {
+    let tx = conn.transaction()?;
+    let mut rows_deleted = 0;
+    for table in WORKSPACE_TABLES {
+        rows_deleted += tx.execute(&format!("DELETE FROM {}", table), [])? as i64;
+    }
+    rows_deleted += tx.execute("DELETE FROM campaigns WHERE id != 1", [])? as i64;
+    // Credentials survive a reset, and so do their attachments
+    rows_deleted += tx.execute("DELETE FROM attachments WHERE entity_type != 'credential'", [])? as i64;
+
+    let placeholders = (1..=WORKSPACE_TABLES.len()).map(|i| format!("?{}", i)).collect::<Vec<_>>().join(", ");
+    tx.execute(
+        &format!("DELETE FROM sqlite_sequence WHERE name IN ({})", placeholders),
+        params_from_iter(WORKSPACE_TABLES),
+    )?;
+    tx.commit()?;
+
+    let mut tables_cleared: Vec<String> = WORKSPACE_TABLES.iter().map(|t| t.to_string()).collect();
+    tables_cleared.push("campaigns".to_string());
+    tables_cleared.push("attachments".to_string());
+    Ok(WorkspaceReset { tables_cleared, rows_deleted })
+}
@@ -35,6 +35,7 @@ const AD_TYPES = [
   { value: "carousel", label: "Carousel" },
   { value: "email", label: "Email" },
   { value: "sms", label: "SMS" },
+  { value: "pinterest_pin", label: "Pinterest Pin" },
 ] as const;
 
 type AdType = ApiAdType;
@@ -114,12 +115,12 @@ export function AdGenerationModal({
 
       // Transform backend analysis to our format
       const analysis: MarketAnalysis = {
-        recommendedAdType: result.market_analysis.recommended_ad_type,
-        confidence: Math.round(result.market_analysis.estimated_engagement_score * 100),
-        reasoning: `${result.market_analysis.suggested_tone} tone recommended for ${result.market_analysis.target_demographic}. Competition level: ${result.market_analysis.competition_level}. Key selling points: ${result.market_analysis.key_selling_points.slice(0, 2).join(", ")}.`,
+        recommendedAdType: result.marketAnalysis.recommendedAdType,
+        confidence: Math.round(result.marketAnalysis.estimatedEngagementScore * 100),
+        reasoning: `${result.marketAnalysis.suggestedTone} tone recommended for ${result.marketAnalysis.targetDemographic}. Competition level: ${result.marketAnalysis.competitionLevel}. Key selling points: ${result.marketAnalysis.keySellingPoints.slice(0, 2).join(", ")}.`,
       };
       setMarketAnalysis(analysis);
-      setSelectedAdType(result.market_analysis.recommended_ad_type as AdType);
+      setSelectedAdType(result.marketAnalysis.recommendedAdType as AdType);
       // Don't auto-generate ad - wait for user to click "Generate Ad" button
     } catch (error) {
       console.error("Error analyzing market:", error);
@@ -183,10 +184,10 @@ export function AdGenerationModal({
       setApiResult(result);
 
       setGeneratedAd({
-        headline: result.ad_copy.headline,
-        body: result.ad_copy.body_text || "",
-        cta: result.ad_copy.cta || "Shop Now",
-        adType: result.ad_copy.ad_type || selectedAdType,
+        headline: result.adCopy.headline,
+        body: result.adCopy.bodyText || "",
+        cta: result.adCopy.cta || "Shop Now",
+        adType: result.adCopy.adType || selectedAdType,
       });
       toast.success("Ad generated & saved!", {
         description: "Your ad has been saved automatically.",
@@ -441,17 +442,17 @@ export function AdGenerationModal({
                           {ad.headline}
                         </span>
                         <Badge variant="outline" className="ml-2 text-xs">
-                          {getAdTypeLabel(ad.ad_type || "social_post")}
+                          {getAdTypeLabel(ad.adType || "social_post")}
                         </Badge>
                       </div>
                       <p className="text-xs text-muted-foreground line-clamp-2">
-                        {ad.body_text}
+                        {ad.bodyText}
                       </p>
                       <div className="flex items-center justify-between text-xs text-muted-foreground">
                         <span>CTA: {ad.cta}</span>
                         <span>
                           {ad.created_at
-                            ? new Date(ad.created_at).toLocaleDateString()
+                            ? new Date(ad.createdAt).toLocaleDateString()
                             : ""}
                         </span>
                       </div>
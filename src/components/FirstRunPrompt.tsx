@@ -0,0 +1,57 @@
+import { useEffect, useState } from "react";
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
+import { Button } from "@/components/ui/button";
+import { workspaceApi } from "@/services/api";
+import { toast } from "sonner";
+
+// Asks once whether to start blank or with the 10 starter products
+export function FirstRunPrompt() {
+  const [visible, setVisible] = useState(false);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    workspaceApi
+      .getSeedStatus()
+      .then((status) => setVisible(!status.preference))
+      .catch((err) => console.error("Error loading seed status:", err));
+  }, []);
+
+  const choose = async (seeded: boolean) => {
+    try {
+      setSaving(true);
+      const result = await workspaceApi.chooseStartingWorkspace(seeded);
+      setVisible(false);
+      if (seeded) {
+        toast.success(`Added ${result.productIds.length} starter products`);
+      }
+    } catch (err) {
+      toast.error(err instanceof Error ? err.message : "Failed to set up workspace");
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  if (!visible) {
+    return null;
+  }
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>How would you like to start?</CardTitle>
+        <CardDescription>
+          Begin with 10 researched trending products, or with an empty workspace. The starter
+          products can still be added later.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="flex gap-3">
+        <Button disabled={saving} onClick={() => choose(true)}>
+          Add starter products
+        </Button>
+        <Button variant="outline" disabled={saving} onClick={() => choose(false)}>
+          Start blank
+        </Button>
+      </CardContent>
+    </Card>
+  );
+}
@@ -24,19 +24,19 @@ export function ProductForm({ open, onOpenChange, product, onSuccess }: ProductF
     name: product?.name || "",
     category: product?.category || "",
     description: product?.description || "",
-    price_range: product?.price_range || "",
-    target_audience: product?.target_audience || "",
-    trending_score: product?.trending_score || 50,
+    priceRange: product?.priceRange || "",
+    targetAudience: product?.targetAudience || "",
+    trendingScore: product?.trendingScore || 50,
     notes: product?.notes || "",
-    image_url: product?.image_url || "",
+    imageUrl: product?.imageUrl || "",
 
     // Affiliate platform identifiers
-    amazon_asin: product?.amazon_asin || "",
-    tiktok_product_id: product?.tiktok_product_id || "",
-    instagram_product_id: product?.instagram_product_id || "",
-    youtube_video_id: product?.youtube_video_id || "",
-    pinterest_pin_id: product?.pinterest_pin_id || "",
-    product_url: product?.product_url || "",
+    amazonAsin: product?.amazonAsin || "",
+    tiktokProductId: product?.tiktokProductId || "",
+    instagramProductId: product?.instagramProductId || "",
+    youtubeVideoId: product?.youtubeVideoId || "",
+    pinterestPinId: product?.pinterestPinId || "",
+    productUrl: product?.productUrl || "",
   });
 
   const handleSubmit = async (e: React.FormEvent) => {
@@ -57,17 +57,17 @@ export function ProductForm({ open, onOpenChange, product, onSuccess }: ProductF
         name: "",
         category: "",
         description: "",
-        price_range: "",
-        target_audience: "",
-        trending_score: 50,
+        priceRange: "",
+        targetAudience: "",
+        trendingScore: 50,
         notes: "",
-        image_url: "",
-        amazon_asin: "",
-        tiktok_product_id: "",
-        instagram_product_id: "",
-        youtube_video_id: "",
-        pinterest_pin_id: "",
-        product_url: "",
+        imageUrl: "",
+        amazonAsin: "",
+        tiktokProductId: "",
+        instagramProductId: "",
+        youtubeVideoId: "",
+        pinterestPinId: "",
+        productUrl: "",
       });
     } catch (err) {
       setError(err instanceof Error ? err.message : "Failed to save product");
@@ -130,35 +130,35 @@ export function ProductForm({ open, onOpenChange, product, onSuccess }: ProductF
 
             <div className="grid grid-cols-2 gap-4">
               <div>
-                <Label htmlFor="price_range">Price Range</Label>
+                <Label htmlFor="priceRange">Price Range</Label>
                 <Input
-                  id="price_range"
+                  id="priceRange"
                   placeholder="e.g., $20-$30"
-                  value={formData.price_range}
-                  onChange={(e) => updateField("price_range", e.target.value)}
+                  value={formData.priceRange}
+                  onChange={(e) => updateField("priceRange", e.target.value)}
                 />
               </div>
 
               <div>
-                <Label htmlFor="trending_score">Trending Score (0-100)</Label>
+                <Label htmlFor="trendingScore">Trending Score (0-100)</Label>
                 <Input
-                  id="trending_score"
+                  id="trendingScore"
                   type="number"
                   min="0"
                   max="100"
-                  value={formData.trending_score}
-                  onChange={(e) => updateField("trending_score", parseInt(e.target.value) || 0)}
+                  value={formData.trendingScore}
+                  onChange={(e) => updateField("trendingScore", parseInt(e.target.value) || 0)}
                 />
               </div>
             </div>
 
             <div>
-              <Label htmlFor="target_audience">Target Audience</Label>
+              <Label htmlFor="targetAudience">Target Audience</Label>
               <Input
-                id="target_audience"
+                id="targetAudience"
                 placeholder="e.g., Age 20-35, female, skincare enthusiasts"
-                value={formData.target_audience}
-                onChange={(e) => updateField("target_audience", e.target.value)}
+                value={formData.targetAudience}
+                onChange={(e) => updateField("targetAudience", e.target.value)}
               />
               <p className="text-xs text-muted-foreground mt-1">
                 Demographics help optimize platform selection (age, gender, interests)
@@ -166,12 +166,12 @@ export function ProductForm({ open, onOpenChange, product, onSuccess }: ProductF
             </div>
 
             <div>
-              <Label htmlFor="image_url">Image URL</Label>
+              <Label htmlFor="imageUrl">Image URL</Label>
               <Input
-                id="image_url"
+                id="imageUrl"
                 placeholder="https://example.com/product-image.jpg"
-                value={formData.image_url}
-                onChange={(e) => updateField("image_url", e.target.value)}
+                value={formData.imageUrl}
+                onChange={(e) => updateField("imageUrl", e.target.value)}
               />
             </div>
 
@@ -207,7 +207,7 @@ export function ProductForm({ open, onOpenChange, product, onSuccess }: ProductF
 
                   {/* Amazon ASIN */}
                   <div>
-                    <Label htmlFor="amazon_asin" className="flex items-center gap-2">
+                    <Label htmlFor="amazonAsin" className="flex items-center gap-2">
                       Amazon ASIN
                       <a
                         href="https://www.amazon.com/gp/help/customer/display.html?nodeId=G200141420"
@@ -219,10 +219,10 @@ export function ProductForm({ open, onOpenChange, product, onSuccess }: ProductF
                       </a>
                     </Label>
                     <Input
-                      id="amazon_asin"
+                      id="amazonAsin"
                       placeholder="B08N5WRWNW"
-                      value={formData.amazon_asin}
-                      onChange={(e) => updateField("amazon_asin", e.target.value)}
+                      value={formData.amazonAsin}
+                      onChange={(e) => updateField("amazonAsin", e.target.value)}
                       maxLength={10}
                     />
                     <p className="text-xs text-muted-foreground mt-1">
@@ -232,12 +232,12 @@ export function ProductForm({ open, onOpenChange, product, onSuccess }: ProductF
 
                   {/* Product URL - Universal fallback */}
                   <div>
-                    <Label htmlFor="product_url">Product URL (All Platforms)</Label>
+                    <Label htmlFor="productUrl">Product URL (All Platforms)</Label>
                     <Input
-                      id="product_url"
+                      id="productUrl"
                       placeholder="https://example.com/product-page"
-                      value={formData.product_url}
-                      onChange={(e) => updateField("product_url", e.target.value)}
+                      value={formData.productUrl}
+                      onChange={(e) => updateField("productUrl", e.target.value)}
                     />
                     <p className="text-xs text-muted-foreground mt-1">
                       Generic product page URL - used as fallback for platforms without specific IDs
@@ -246,12 +246,12 @@ export function ProductForm({ open, onOpenChange, product, onSuccess }: ProductF
 
                   {/* TikTok Product ID */}
                   <div>
-                    <Label htmlFor="tiktok_product_id">TikTok Product ID</Label>
+                    <Label htmlFor="tiktokProductId">TikTok Product ID</Label>
                     <Input
-                      id="tiktok_product_id"
+                      id="tiktokProductId"
                       placeholder="1234567890"
-                      value={formData.tiktok_product_id}
-                      onChange={(e) => updateField("tiktok_product_id", e.target.value)}
+                      value={formData.tiktokProductId}
+                      onChange={(e) => updateField("tiktokProductId", e.target.value)}
                     />
                     <p className="text-xs text-muted-foreground mt-1">
                       Product ID from your TikTok Shop catalog
@@ -260,12 +260,12 @@ export function ProductForm({ open, onOpenChange, product, onSuccess }: ProductF
 
                   {/* Instagram Product ID */}
                   <div>
-                    <Label htmlFor="instagram_product_id">Instagram Product ID</Label>
+                    <Label htmlFor="instagramProductId">Instagram Product ID</Label>
                     <Input
-                      id="instagram_product_id"
+                      id="instagramProductId"
                       placeholder="1234567890"
-                      value={formData.instagram_product_id}
-                      onChange={(e) => updateField("instagram_product_id", e.target.value)}
+                      value={formData.instagramProductId}
+                      onChange={(e) => updateField("instagramProductId", e.target.value)}
                     />
                     <p className="text-xs text-muted-foreground mt-1">
                       Product ID from Instagram Shopping catalog (via Meta Commerce Manager)
@@ -274,12 +274,12 @@ export function ProductForm({ open, onOpenChange, product, onSuccess }: ProductF
 
                   {/* YouTube Video ID */}
                   <div>
-                    <Label htmlFor="youtube_video_id">YouTube Video ID</Label>
+                    <Label htmlFor="youtubeVideoId">YouTube Video ID</Label>
                     <Input
-                      id="youtube_video_id"
+                      id="youtubeVideoId"
                       placeholder="dQw4w9WgXcQ"
-                      value={formData.youtube_video_id}
-                      onChange={(e) => updateField("youtube_video_id", e.target.value)}
+                      value={formData.youtubeVideoId}
+                      onChange={(e) => updateField("youtubeVideoId", e.target.value)}
                     />
                     <p className="text-xs text-muted-foreground mt-1">
                       YouTube video showcasing this product (11-character ID from video URL)
@@ -288,12 +288,12 @@ export function ProductForm({ open, onOpenChange, product, onSuccess }: ProductF
 
                   {/* Pinterest Pin ID */}
                   <div>
-                    <Label htmlFor="pinterest_pin_id">Pinterest Pin ID</Label>
+                    <Label htmlFor="pinterestPinId">Pinterest Pin ID</Label>
                     <Input
-                      id="pinterest_pin_id"
+                      id="pinterestPinId"
                       placeholder="1234567890"
-                      value={formData.pinterest_pin_id}
-                      onChange={(e) => updateField("pinterest_pin_id", e.target.value)}
+                      value={formData.pinterestPinId}
+                      onChange={(e) => updateField("pinterestPinId", e.target.value)}
                     />
                     <p className="text-xs text-muted-foreground mt-1">
                       Pin ID for this product on Pinterest
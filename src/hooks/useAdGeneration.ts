@@ -38,9 +38,9 @@ export function useAdGeneration(): UseAdGenerationReturn {
 
       try {
         const request: GenerateAdRequest = {
-          product_id: productId,
-          ad_type: adType,
-          custom_instructions: customInstructions,
+          productId,
+          adType,
+          customInstructions,
         };
 
         const result = await invoke<AdGenerationResult>("generate_ad_for_product", {
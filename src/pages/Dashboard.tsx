@@ -1,6 +1,7 @@
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
 import { Package, Link as LinkIcon, Megaphone, TrendingUp, CheckCircle2, ArrowUpRight } from "lucide-react";
 import { Badge } from "@/components/ui/badge";
+import { FirstRunPrompt } from "@/components/FirstRunPrompt";
 
 export function Dashboard() {
   return (
@@ -13,6 +14,8 @@ export function Dashboard() {
         </p>
       </div>
 
+      <FirstRunPrompt />
+
       {/* Stats Grid */}
       <div className="grid gap-6 md:grid-cols-2 lg:grid-cols-4 stagger-children">
         {/* Total Products Card */}
@@ -1,5 +1,5 @@
 import { useEffect, useState } from "react";
-import { affiliateLinkApi, productApi } from "@/services/api";
+import { affiliateLinkApi, parseMissingCredentialError, productApi } from "@/services/api";
 import type { AffiliateLink, Product } from "@/types";
 import {
   Card,
@@ -73,10 +73,27 @@ export function Links() {
     }
   };
 
+  // Runs a link action; without a credential for the platform, asks before
+  // retrying with a placeholder affiliate ID
+  const withCredentialCheck = async (
+    action: (allowMissingCredential: boolean) => Promise<unknown>
+  ) => {
+    try {
+      await action(false);
+    } catch (err) {
+      const missing = parseMissingCredentialError(err);
+      if (!missing) throw err;
+      if (!confirm(`${missing.message}\n\nGenerate with a placeholder ID anyway?`)) return;
+      await action(true);
+    }
+  };
+
   const handleGenerateSingle = async (productId: number) => {
     try {
       setGeneratingId(productId);
-      await affiliateLinkApi.generateLink({ product_id: productId });
+      await withCredentialCheck((allowMissingCredential) =>
+        affiliateLinkApi.generateLink({ productId, allowMissingCredential })
+      );
       await loadData();
     } catch (err) {
       setError(
@@ -90,7 +107,9 @@ export function Links() {
   const handleRefresh = async (linkId: number) => {
     try {
       setRefreshingId(linkId);
-      await affiliateLinkApi.refreshLink(linkId);
+      await withCredentialCheck((allowMissingCredential) =>
+        affiliateLinkApi.refreshLink(linkId, allowMissingCredential)
+      );
       await loadData();
     } catch (err) {
       setError(err instanceof Error ? err.message : "Failed to refresh link");
@@ -146,7 +165,7 @@ export function Links() {
   };
 
   const productsWithoutLinks = products.filter(
-    (product) => !links.some((link) => link.product_id === product.id)
+    (product) => !links.some((link) => link.productId === product.id)
   );
 
   return (
@@ -240,12 +259,12 @@ export function Links() {
                   <div className="flex items-start justify-between gap-4">
                     <div className="flex-1 min-w-0">
                       <CardTitle className="text-xl mb-2 truncate">
-                        {link.product_name}
+                        {link.productName}
                       </CardTitle>
                       <div className="flex flex-wrap items-center gap-2">
                         {getPlatformBadge(link.platform)}
                         <span className="text-muted-foreground">•</span>
-                        <span className="text-sm font-medium text-muted-foreground">{link.program_name}</span>
+                        <span className="text-sm font-medium text-muted-foreground">{link.programName}</span>
                       </div>
                     </div>
                     <div className="shrink-0">
@@ -255,21 +274,21 @@ export function Links() {
                 </CardHeader>
                 <CardContent>
                   <div className="space-y-4">
-                    {(link.commission_rate || link.cookie_duration) && (
+                    {(link.commissionRate || link.cookieDuration) && (
                       <div className="grid grid-cols-2 gap-6 pb-4 border-b">
-                        {link.commission_rate && (
+                        {link.commissionRate && (
                           <div className="space-y-1">
                             <p className="text-xs text-muted-foreground uppercase tracking-wide">Commission Rate</p>
                             <p className="text-lg font-bold text-success">
-                              {(link.commission_rate * 100).toFixed(1)}%
+                              {(link.commissionRate * 100).toFixed(1)}%
                             </p>
                           </div>
                         )}
-                        {link.cookie_duration && (
+                        {link.cookieDuration && (
                           <div className="space-y-1">
                             <p className="text-xs text-muted-foreground uppercase tracking-wide">Cookie Duration</p>
                             <p className="text-lg font-bold">
-                              {link.cookie_duration} <span className="text-sm font-normal text-muted-foreground">days</span>
+                              {link.cookieDuration} <span className="text-sm font-normal text-muted-foreground">days</span>
                             </p>
                           </div>
                         )}
@@ -281,14 +300,14 @@ export function Links() {
                         <div className="flex-1 min-w-0">
                           <p className="text-xs text-muted-foreground uppercase tracking-wide mb-2">Tracking URL</p>
                           <code className="block truncate text-sm font-mono">
-                            {link.tracking_url}
+                            {link.trackingUrl}
                           </code>
                         </div>
                         <Button
                           size="sm"
                           variant={copiedId === link.id ? "success" : "outline"}
                           onClick={() =>
-                            link.id && handleCopy(link.tracking_url, link.id)
+                            link.id && handleCopy(link.trackingUrl, link.id)
                           }
                           className="shrink-0"
                         >
@@ -312,7 +331,7 @@ export function Links() {
                         size="sm"
                         variant="outline"
                         onClick={() =>
-                          window.open(link.destination_url, "_blank")
+                          window.open(link.destinationUrl, "_blank")
                         }
                         className="hover:bg-primary hover:text-primary-foreground transition-colors"
                       >
@@ -343,10 +362,10 @@ export function Links() {
                       </Button>
                     </div>
 
-                    {link.created_at && (
+                    {link.createdAt && (
                       <div className="flex items-center gap-2 text-xs text-muted-foreground pt-2 border-t">
                         <Clock className="h-3 w-3" />
-                        <span>Created {new Date(link.created_at).toLocaleDateString()} at {new Date(link.created_at).toLocaleTimeString()}</span>
+                        <span>Created {new Date(link.createdAt).toLocaleDateString()} at {new Date(link.createdAt).toLocaleTimeString()}</span>
                       </div>
                     )}
                   </div>
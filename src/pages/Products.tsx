@@ -170,10 +170,10 @@ export function Products() {
                     <CardTitle className="text-lg mb-1.5 truncate">{product.name}</CardTitle>
                     <CardDescription className="text-sm">{product.category}</CardDescription>
                   </div>
-                  {product.trending_score && product.trending_score > 0 && (
+                  {product.trendingScore && product.trendingScore > 0 && (
                     <Badge variant="warning" className="shrink-0 gap-1">
                       <TrendingUp className="h-3 w-3" />
-                      {product.trending_score}
+                      {product.trendingScore}
                     </Badge>
                   )}
                 </div>
@@ -187,26 +187,26 @@ export function Products() {
                   )}
 
                   <div className="space-y-2">
-                    {product.price_range && (
+                    {product.priceRange && (
                       <div className="flex items-center justify-between text-sm">
                         <span className="text-muted-foreground">Price Range</span>
-                        <span className="font-semibold">{product.price_range}</span>
+                        <span className="font-semibold">{product.priceRange}</span>
                       </div>
                     )}
-                    {product.target_audience && (
+                    {product.targetAudience && (
                       <div className="flex items-center justify-between text-sm">
                         <span className="text-muted-foreground">Target</span>
-                        <span className="font-medium text-xs">{product.target_audience}</span>
+                        <span className="font-medium text-xs">{product.targetAudience}</span>
                       </div>
                     )}
                   </div>
 
                   {/* Platform Badges */}
-                  {(product.amazon_asin || product.tiktok_product_id || product.instagram_product_id) && (
+                  {(product.amazonAsin || product.tiktokProductId || product.instagramProductId) && (
                     <div className="flex flex-wrap gap-2 pt-2 border-t">
-                      {product.amazon_asin && <Badge variant="amazon">Amazon</Badge>}
-                      {product.tiktok_product_id && <Badge variant="tiktok">TikTok</Badge>}
-                      {product.instagram_product_id && <Badge variant="instagram">Instagram</Badge>}
+                      {product.amazonAsin && <Badge variant="amazon">Amazon</Badge>}
+                      {product.tiktokProductId && <Badge variant="tiktok">TikTok</Badge>}
+                      {product.instagramProductId && <Badge variant="instagram">Instagram</Badge>}
                     </div>
                   )}
 
@@ -14,7 +14,7 @@ import { CheckCircle2, ExternalLink, Info } from "lucide-react";
 
 // Platform configuration types
 interface PlatformField {
-  key: "affiliate_id" | "shop_id" | "account_name" | "notes";
+  key: "affiliateId" | "shopId" | "accountName" | "notes";
   label: string;
   placeholder: string;
   helpText?: string;
@@ -33,7 +33,7 @@ interface PlatformConfig {
   icon: string;
   description: string;
   tabClassName: string;
-  configuredField: "affiliate_id" | "shop_id";
+  configuredField: "affiliateId" | "shopId";
   fields: PlatformField[];
   setupTitle: string;
   setupSteps: SetupStep[];
@@ -48,17 +48,17 @@ const PLATFORM_CONFIGS: PlatformConfig[] = [
     icon: "📦",
     description: "Configure your Amazon Associates affiliate credentials",
     tabClassName: "data-[state=active]:bg-orange-100 data-[state=active]:text-orange-700 dark:data-[state=active]:bg-orange-900/20 dark:data-[state=active]:text-orange-300",
-    configuredField: "affiliate_id",
+    configuredField: "affiliateId",
     fields: [
       {
-        key: "affiliate_id",
+        key: "affiliateId",
         label: "Associate Tag (Tracking ID)",
         placeholder: "yourname-20",
         helpText: "Found in your Amazon Associates dashboard (format: yourname-20)",
         required: true,
       },
       {
-        key: "account_name",
+        key: "accountName",
         label: "Account Name",
         placeholder: "My Amazon Associates Account",
       },
@@ -86,17 +86,17 @@ const PLATFORM_CONFIGS: PlatformConfig[] = [
     icon: "🎵",
     description: "Configure your TikTok Shop Creator/Affiliate credentials",
     tabClassName: "data-[state=active]:bg-pink-100 data-[state=active]:text-pink-700 dark:data-[state=active]:bg-pink-900/20 dark:data-[state=active]:text-pink-300",
-    configuredField: "shop_id",
+    configuredField: "shopId",
     fields: [
       {
-        key: "shop_id",
+        key: "shopId",
         label: "Shop ID",
         placeholder: "12345678",
         helpText: "Found in TikTok Seller Center under Account Settings",
         required: true,
       },
       {
-        key: "affiliate_id",
+        key: "affiliateId",
         label: "Creator/Affiliate ID (Optional)",
         placeholder: "@yourcreatorname",
       },
@@ -123,10 +123,10 @@ const PLATFORM_CONFIGS: PlatformConfig[] = [
     icon: "📸",
     description: "Configure your Instagram Shopping credentials",
     tabClassName: "data-[state=active]:bg-purple-100 data-[state=active]:text-purple-700 dark:data-[state=active]:bg-purple-900/20 dark:data-[state=active]:text-purple-300",
-    configuredField: "shop_id",
+    configuredField: "shopId",
     fields: [
       {
-        key: "shop_id",
+        key: "shopId",
         label: "Shop/Business ID",
         placeholder: "1234567890",
         required: true,
@@ -153,10 +153,10 @@ const PLATFORM_CONFIGS: PlatformConfig[] = [
     icon: "▶️",
     description: "Configure your YouTube affiliate credentials",
     tabClassName: "data-[state=active]:bg-red-100 data-[state=active]:text-red-700 dark:data-[state=active]:bg-red-900/20 dark:data-[state=active]:text-red-300",
-    configuredField: "affiliate_id",
+    configuredField: "affiliateId",
     fields: [
       {
-        key: "affiliate_id",
+        key: "affiliateId",
         label: "Channel ID",
         placeholder: "UCxxxxxxxxxxxxxxxxx",
         helpText: "Found in YouTube Studio under Customization → Basic info",
@@ -183,10 +183,10 @@ const PLATFORM_CONFIGS: PlatformConfig[] = [
     icon: "📌",
     description: "Configure your Pinterest affiliate credentials",
     tabClassName: "data-[state=active]:bg-red-100 data-[state=active]:text-red-700 dark:data-[state=active]:bg-red-900/20 dark:data-[state=active]:text-red-300",
-    configuredField: "affiliate_id",
+    configuredField: "affiliateId",
     fields: [
       {
-        key: "affiliate_id",
+        key: "affiliateId",
         label: "Account ID",
         placeholder: "1234567890",
         required: true,
@@ -206,6 +206,122 @@ const PLATFORM_CONFIGS: PlatformConfig[] = [
       { text: "Find your Account ID in Pinterest Business Hub" },
     ],
   },
+  {
+    id: "impact",
+    name: "Impact",
+    icon: "🔗",
+    description: "Publisher ID used to build Impact deep links",
+    tabClassName: "data-[state=active]:bg-blue-100 data-[state=active]:text-blue-700 dark:data-[state=active]:bg-blue-900/20 dark:data-[state=active]:text-blue-300",
+    configuredField: "affiliateId",
+    fields: [
+      {
+        key: "affiliateId",
+        label: "Partner ID",
+        placeholder: "1234567",
+        helpText: "Shown in your Impact account settings",
+        required: true,
+      },
+      {
+        key: "notes",
+        label: "Notes (Optional)",
+        placeholder: "Personal notes about this account",
+        type: "textarea",
+      },
+    ],
+    setupTitle: "Using Impact deep links:",
+    setupSteps: [
+      { text: "Save your Partner ID here" },
+      { text: "Add each brand's tracking domain, ad ID, and campaign ID under its program" },
+      { text: "Build deep links for any page on the brand's site" },
+    ],
+  },
+  {
+    id: "cj",
+    name: "CJ Affiliate",
+    icon: "🔗",
+    description: "Publisher ID used to build CJ Affiliate deep links",
+    tabClassName: "data-[state=active]:bg-green-100 data-[state=active]:text-green-700 dark:data-[state=active]:bg-green-900/20 dark:data-[state=active]:text-green-300",
+    configuredField: "affiliateId",
+    fields: [
+      {
+        key: "affiliateId",
+        label: "Website ID (PID)",
+        placeholder: "9876543",
+        helpText: "Account → Websites in the CJ dashboard",
+        required: true,
+      },
+      {
+        key: "notes",
+        label: "Notes (Optional)",
+        placeholder: "Personal notes about this account",
+        type: "textarea",
+      },
+    ],
+    setupTitle: "Using CJ Affiliate deep links:",
+    setupSteps: [
+      { text: "Save your website PID here" },
+      { text: "Set the program's network to CJ" },
+      { text: "Build deep links for any advertiser page" },
+    ],
+  },
+  {
+    id: "shareasale",
+    name: "ShareASale",
+    icon: "🔗",
+    description: "Publisher ID used to build ShareASale deep links",
+    tabClassName: "data-[state=active]:bg-orange-100 data-[state=active]:text-orange-700 dark:data-[state=active]:bg-orange-900/20 dark:data-[state=active]:text-orange-300",
+    configuredField: "affiliateId",
+    fields: [
+      {
+        key: "affiliateId",
+        label: "Affiliate ID",
+        placeholder: "1234567",
+        helpText: "Top of the ShareASale dashboard",
+        required: true,
+      },
+      {
+        key: "notes",
+        label: "Notes (Optional)",
+        placeholder: "Personal notes about this account",
+        type: "textarea",
+      },
+    ],
+    setupTitle: "Using ShareASale deep links:",
+    setupSteps: [
+      { text: "Save your affiliate ID here" },
+      { text: "Add each merchant's ID and a banner ID under its program" },
+      { text: "Build deep links for any merchant page" },
+    ],
+  },
+  {
+    id: "awin",
+    name: "Awin",
+    icon: "🔗",
+    description: "Publisher ID used to build Awin deep links",
+    tabClassName: "data-[state=active]:bg-teal-100 data-[state=active]:text-teal-700 dark:data-[state=active]:bg-teal-900/20 dark:data-[state=active]:text-teal-300",
+    configuredField: "affiliateId",
+    fields: [
+      {
+        key: "affiliateId",
+        label: "Publisher ID",
+        placeholder: "123456",
+        helpText: "Top of the Awin dashboard",
+        required: true,
+      },
+      {
+        key: "notes",
+        label: "Notes (Optional)",
+        placeholder: "Personal notes about this account",
+        type: "textarea",
+      },
+    ],
+    setupTitle: "Using Awin deep links:",
+    setupSteps: [
+      { text: "Save your publisher ID here" },
+      { text: "Add each advertiser's ID under its program" },
+      { text: "Build deep links for any advertiser page" },
+    ],
+  },
 ];
 
 // Reusable component for platform credentials card
@@ -401,9 +517,9 @@ export function Settings() {
     try {
       await credentialsApi.save({
         platform,
-        affiliate_id: data.affiliate_id,
-        shop_id: data.shop_id,
-        account_name: data.account_name,
+        affiliateId: data.affiliateId,
+        shopId: data.shopId,
+        accountName: data.accountName,
         notes: data.notes,
       });
       setSaveStatus({ platform, message: "Saved successfully!" });
@@ -449,7 +565,7 @@ export function Settings() {
       </Alert>
 
       <Tabs defaultValue="amazon" className="w-full">
-        <TabsList className="grid w-full grid-cols-5 h-auto p-1 gap-1">
+        <TabsList className="grid w-full grid-cols-3 md:grid-cols-5 h-auto p-1 gap-1">
           {PLATFORM_CONFIGS.map((config) => (
             <TabsTrigger
               key={config.id}
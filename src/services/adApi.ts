@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import type { AffiliateLink, ApprovalState, RecommendationTrace } from "@/types";
 
 // Ad type options for generation
 export type AdType =
@@ -7,60 +8,300 @@ export type AdType =
   | "video_script"
   | "carousel"
   | "email"
-  | "sms";
+  | "sms"
+  | "pinterest_pin";
 
 // Market analysis result from analyzing a product
 export interface MarketAnalysis {
-  recommended_ad_type: string;
-  recommended_platform: string;
-  target_demographic: string;
-  key_selling_points: string[];
-  suggested_tone: string;
-  competition_level: string;
-  estimated_engagement_score: number;
+  recommendedAdType: string;
+  recommendedPlatform: string;
+  targetDemographic: string;
+  keySellingPoints: string[];
+  suggestedTone: string;
+  competitionLevel: string;
+  estimatedEngagementScore: number;
 }
 
 // A generated ad copy record
 export interface GeneratedAdCopy {
   id?: number;
-  product_id?: number;
-  campaign_id: number;
-  variation_name?: string;
+  productId?: number;
+  campaignId: number;
+  variationName?: string;
   headline: string;
-  body_text?: string;
+  bodyText?: string;
   cta?: string;
-  ad_format?: string;
-  ad_type?: string;
-  platform_specific_data?: string;
-  performance_score?: number;
-  created_at?: string;
-  updated_at?: string;
+  adFormat?: string;
+  adType?: string;
+  platformSpecificData?: string;
+  performanceScore?: number;
+  createdAt?: string;
+  updatedAt?: string;
+  isFavorite?: boolean;
+  parentAdId?: number; // the email_sequence ad this message belongs to
+  sequenceStep?: number;
+  sendDelayDays?: number; // days after the previous message
+  altText?: string; // product image description for visual ad types
+  linkId?: number | null; // affiliate link [LINK] resolves to on export
+  approvalState?: ApprovalState;
 }
 
 // Result containing both the generated ad and market analysis
 export interface AdGenerationResult {
-  ad_copy: GeneratedAdCopy;
-  market_analysis: MarketAnalysis;
+  adCopy: GeneratedAdCopy;
+  marketAnalysis: MarketAnalysis;
 }
 
+// Reusable creative angle for a product
+export type AngleType =
+  | "problem_agitate_solve"
+  | "social_proof"
+  | "comparison"
+  | "unboxing"
+  | "before_after"
+  | "myth_busting";
+
+export interface ContentAngle {
+  id: number;
+  productId: number;
+  angleType: AngleType;
+  hook: string;
+  direction: string;
+  servedBy: string;
+  createdAt?: string;
+}
+
+// Social-proof snippet or common objection distilled from reviews
+export interface ReviewInsight {
+  id: number;
+  productId: number;
+  kind: "snippet" | "objection";
+  text: string;
+  theme?: string;
+  mentions: number;
+  servedBy: string;
+  createdAt?: string;
+}
+
+export interface ReviewSummary {
+  productId: number;
+  reviewCount: number;
+  snippets: ReviewInsight[];
+  objections: ReviewInsight[];
+}
+
+// Email nurture sequence: the sequence ad plus its messages in send order
+export interface EmailSequence {
+  sequence: GeneratedAdCopy;
+  messages: GeneratedAdCopy[];
+}
+
+export type EspFormat = "generic" | "mailchimp" | "klaviyo" | "convertkit";
+
+// SMS billing: GSM-7 fits 160 characters per message (153 per segment when split), UCS-2 fits 70 (67)
+export interface SmsSegments {
+  encoding: "gsm7" | "ucs2";
+  characters: number;
+  segments: number;
+}
+
+export interface SmsComplianceReport {
+  adId: number;
+  body: string; // as sent: link filled in, opt-out text enforced
+  optOutAdded: boolean;
+  segments: SmsSegments;
+  timezone: string;
+  sendAt?: string;
+  withinQuietHours: boolean;
+  issues: string[];
+}
+
+export interface SmsContact {
+  phone: string;
+  firstName?: string;
+}
+
+export interface SmsExport {
+  path: string;
+  exported: number;
+  skippedPhones: string[];
+  segments: SmsSegments;
+}
+
+// Stored under "pinterest" in a pinterest_pin ad's platform_specific_data
+export interface PinMetadata {
+  title: string; // max 100 characters
+  description: string; // max 500 characters
+  altText: string;
+  keywords: string[];
+  boardSuggestions: string[];
+}
+
+export interface PinterestExport {
+  path: string;
+  exported: number;
+  skippedAdIds: number[];
+  warnings: string[]; // e.g. pins exported without alt text
+}
+
+// Stored under "short_video" in a video_script ad's platform_specific_data
+export interface SceneOverlay {
+  scene: string; // HOOK, PROBLEM, SOLUTION, BENEFIT, CTA
+  text: string;
+  startSeconds: number;
+  durationSeconds: number;
+}
+
+export interface ShortVideoCompanion {
+  caption: string;
+  hashtags: string[];
+  overlays: SceneOverlay[];
+  soundNote: string;
+}
+
+// Per-platform formatting applied to generated copy
+export interface StyleRules {
+  platform: string; // tiktok, instagram, facebook, pinterest, youtube, email, sms
+  emojiDensity: "none" | "light" | "heavy";
+  capitalization: "as_is" | "sentence" | "title" | "upper";
+  lineBreaks: "as_is" | "airy" | "compact" | "single_line";
+  linkPlacement: "as_is" | "start" | "end" | "bio";
+}
+
+// Copy rubric scores, 1 (weak) to 10 (strong)
+export interface RubricScores {
+  hookStrength: number;
+  clarity: number;
+  cta: number;
+  specificity: number;
+  compliance: number;
+}
+
+export interface AdCritique extends RubricScores {
+  id: number;
+  adId: number;
+  overall: number; // mean of the five criteria
+  suggestions: string[];
+  servedBy: string;
+  createdAt?: string;
+}
+
+export interface CritiquedAd {
+  ad: GeneratedAdCopy;
+  critique: AdCritique;
+}
+
+// Breakdown of a headline's heuristic CTR score
+export interface CtrSignals {
+  lengthPoints: number;
+  hasNumber: boolean;
+  powerWords: string[];
+  curiosity: boolean;
+  directAddress: boolean;
+  penalties: string[];
+}
+
+export interface HeadlineVariant {
+  id: number;
+  adId: number;
+  headline: string;
+  ctrScore: number; // 0-100
+  signals: CtrSignals;
+  isSelected: boolean;
+  servedBy: string; // "current" for the ad's own headline
+  createdAt?: string;
+}
+
+// User-authored template; text may contain {{merge_tags}} such as
+// {{product_name}}, {{category}}, {{description}}, {{price}}, {{audience}},
+// {{link}}, {{selling_point}}, {{review_quote}}
+export interface AdTemplate {
+  id: number;
+  name: string;
+  adType: string;
+  headline: string;
+  body: string;
+  cta?: string;
+  createdAt?: string;
+  updatedAt?: string;
+}
+
+export interface SaveAdTemplateInput {
+  id?: number; // update when set, create otherwise
+  name: string;
+  adType: string;
+  headline: string;
+  body: string;
+  cta?: string;
+}
+
+export interface AdKitExport {
+  path: string; // kit folder, or the .zip file
+  files: string[]; // paths inside the kit
+  adCount: number;
+  warnings: string[];
+}
+
+// Selects ads for export; an empty filter selects every ad
+export interface AdExportFilter {
+  adIds?: number[];
+  productId?: number;
+  adType?: string;
+  favoritesOnly?: boolean;
+  createdAfter?: string; // YYYY-MM-DD
+}
+
+export interface MarkdownExport {
+  path: string; // folder the notes were written to
+  files: string[];
+}
+
+export type PaidAdsFormat = "meta" | "google";
+
+export interface PaidAdsExport {
+  path: string;
+  exported: number;
+  skippedAdIds: number[]; // no active affiliate link for the final URL
+  warnings: string[];
+}
+
+export interface AdImportSkip {
+  row: number; // 1-based, excluding the CSV header
+  reason: string;
+}
+
+export interface AdImportSummary {
+  imported: number;
+  importedAdIds: number[];
+  skipped: AdImportSkip[];
+}
+
+export type SmsExportFormat = "twilio" | "eztexting";
+
 // Ad Generation API
 export const adApi = {
   /**
    * Generate an ad for a specific product
    * @param productId - The ID of the product to generate an ad for
-   * @param adType - Optional ad type (social_post, story, video_script, carousel, email, sms)
+   * @param adType - Optional ad type (social_post, story, video_script, carousel, email, sms, pinterest_pin)
    * @param customInstructions - Optional custom instructions to influence ad generation
+   * @param angleId - Optional content angle to use as the creative direction
+   * @param includeReviewSnippet - Append the top review snippet with an attribution placeholder
    * @returns The generated ad copy and market analysis
    */
   generateForProduct: (
     productId: number,
     adType?: AdType,
-    customInstructions?: string
+    customInstructions?: string,
+    angleId?: number,
+    includeReviewSnippet?: boolean
   ): Promise<AdGenerationResult> =>
     invoke<AdGenerationResult>("generate_ad_for_product", {
       productId,
       adType,
       customInstructions,
+      angleId,
+      includeReviewSnippet,
     }),
 
   /**
@@ -70,4 +311,303 @@ export const adApi = {
    */
   getForProduct: (productId: number): Promise<GeneratedAdCopy[]> =>
     invoke<GeneratedAdCopy[]>("get_ads_for_product", { productId }),
+
+  /**
+   * Mark or unmark an ad as a favorite (favorites are kept by cleanup)
+   * @param adId - The ID of the ad copy
+   * @param favorite - Whether the ad is a favorite
+   * @returns The updated ad copy
+   */
+  setFavorite: (adId: number, favorite: boolean): Promise<GeneratedAdCopy> =>
+    invoke<GeneratedAdCopy>("set_ad_favorite", { adId, favorite }),
+
+  /**
+   * Set an ad's image alt text
+   * @param adId - The ID of the ad copy
+   * @param altText - New alt text; omit to regenerate it from the product
+   * @returns The updated ad copy
+   */
+  setAltText: (adId: number, altText?: string): Promise<GeneratedAdCopy> =>
+    invoke<GeneratedAdCopy>("set_ad_alt_text", { adId, altText }),
+
+  /**
+   * Explain mode: every intermediate value behind a product's recommendation
+   * @param productId - The ID of the product
+   * @returns The trace; identical inputs give an identical trace and fingerprint
+   */
+  explainRecommendation: (productId: number): Promise<RecommendationTrace> =>
+    invoke<RecommendationTrace>("explain_recommendation", { productId }),
+
+  /**
+   * Generate distinct content angles for a product and store them for reuse
+   * @param productId - The ID of the product
+   * @param count - Number of angles (default 4, max 8)
+   * @returns The stored angles
+   */
+  generateAngles: (productId: number, count?: number): Promise<ContentAngle[]> =>
+    invoke<ContentAngle[]>("generate_content_angles", { productId, count }),
+
+  /**
+   * Get stored content angles for a product
+   * @param productId - The ID of the product
+   * @returns Angles, newest first
+   */
+  getAngles: (productId: number): Promise<ContentAngle[]> =>
+    invoke<ContentAngle[]>("get_content_angles", { productId }),
+
+  /**
+   * Delete a stored content angle
+   * @param angleId - The ID of the angle
+   */
+  deleteAngle: (angleId: number): Promise<void> =>
+    invoke<void>("delete_content_angle", { angleId }),
+
+  /**
+   * Summarize customer reviews into social-proof snippets and objections
+   * @param productId - The ID of the product
+   * @param reviews - Pasted reviews; omit to scrape the product page
+   * @returns The stored snippets and objections
+   */
+  summarizeReviews: (productId: number, reviews?: string[]): Promise<ReviewSummary> =>
+    invoke<ReviewSummary>("summarize_reviews", { productId, reviews }),
+
+  /**
+   * Get stored review snippets and objections for a product
+   * @param productId - The ID of the product
+   */
+  getReviewInsights: (productId: number): Promise<ReviewSummary> =>
+    invoke<ReviewSummary>("get_review_insights", { productId }),
+
+  /**
+   * Generate a 3-5 message email nurture sequence (intro, value, objection handling, urgency)
+   * @param productId - The ID of the product
+   * @param length - Number of messages (default 4, clamped to 3-5)
+   * @param customInstructions - Optional note added as a P.S. to every message
+   * @param includeReviewSnippet - Quote the top review snippet in the objection email
+   * @returns The sequence ad and its ordered messages
+   */
+  generateEmailSequence: (
+    productId: number,
+    length?: number,
+    customInstructions?: string,
+    includeReviewSnippet?: boolean
+  ): Promise<EmailSequence> =>
+    invoke<EmailSequence>("generate_email_sequence", {
+      productId,
+      length,
+      customInstructions,
+      includeReviewSnippet,
+    }),
+
+  /**
+   * Get an email sequence and its messages
+   * @param sequenceId - The ID of the email_sequence ad
+   */
+  getEmailSequence: (sequenceId: number): Promise<EmailSequence> =>
+    invoke<EmailSequence>("get_email_sequence", { sequenceId }),
+
+  /**
+   * Export an email sequence as CSV for an ESP
+   * @param sequenceId - The ID of the email_sequence ad
+   * @param format - ESP layout
+   * @param path - Destination file or directory
+   * @returns The written file path
+   */
+  exportEmailSequence: (sequenceId: number, format: EspFormat, path: string): Promise<string> =>
+    invoke<string>("export_email_sequence", { sequenceId, format, path }),
+
+  /**
+   * Check an SMS ad for opt-out text, segment count, and quiet hours
+   * @param adId - The ID of the SMS ad
+   * @param sendAt - Optional planned send time (RFC 3339)
+   */
+  checkSmsCompliance: (adId: number, sendAt?: string): Promise<SmsComplianceReport> =>
+    invoke<SmsComplianceReport>("check_sms_compliance", { adId, sendAt }),
+
+  /**
+   * Export an SMS ad for a contact list (fails if it isn't compliant)
+   * @param adId - The ID of the SMS ad
+   * @param contacts - Recipients; invalid numbers are skipped
+   * @param format - Provider CSV layout
+   * @param path - Destination file or directory
+   * @param sendAt - Optional scheduled send time (RFC 3339)
+   */
+  exportSmsCampaign: (
+    adId: number,
+    contacts: SmsContact[],
+    format: SmsExportFormat,
+    path: string,
+    sendAt?: string
+  ): Promise<SmsExport> =>
+    invoke<SmsExport>("export_sms_campaign", { adId, contacts, format, path, sendAt }),
+
+  /**
+   * Export Pinterest pin ads as a bulk pin upload CSV
+   * @param adIds - pinterest_pin ads to export; others are skipped
+   * @param path - Destination file or directory
+   * @param board - Board for every pin; defaults to each pin's first suggestion
+   * @param publishDate - Optional scheduled publish date
+   */
+  exportPinterestPins: (
+    adIds: number[],
+    path: string,
+    board?: string,
+    publishDate?: string
+  ): Promise<PinterestExport> =>
+    invoke<PinterestExport>("export_pinterest_pins", { adIds, path, board, publishDate }),
+
+  /**
+   * Rebuild the caption, on-screen text overlays, and sound note for a video script ad
+   * @param adId - The ID of the video_script ad
+   * @returns The updated ad copy
+   */
+  generateVideoCompanion: (adId: number): Promise<GeneratedAdCopy> =>
+    invoke<GeneratedAdCopy>("generate_video_companion", { adId }),
+
+  /**
+   * Get the effective style rules for every platform
+   */
+  getStyleRules: (): Promise<StyleRules[]> => invoke<StyleRules[]>("get_style_rules"),
+
+  /**
+   * Save style rules for a platform
+   * @param rules - The platform's rules
+   */
+  saveStyleRules: (rules: StyleRules): Promise<StyleRules> =>
+    invoke<StyleRules>("save_style_rules", { rules }),
+
+  /**
+   * Restore a platform's default style rules
+   * @param platform - The platform key
+   */
+  resetStyleRules: (platform: string): Promise<StyleRules> =>
+    invoke<StyleRules>("reset_style_rules", { platform }),
+
+  /**
+   * Score an ad on hook strength, clarity, CTA, specificity, and compliance
+   * @param adId - The ID of the ad
+   * @returns The stored critique
+   */
+  critiqueAd: (adId: number): Promise<AdCritique> => invoke<AdCritique>("critique_ad", { adId }),
+
+  /**
+   * Get the latest critique for an ad
+   * @param adId - The ID of the ad
+   */
+  getCritique: (adId: number): Promise<AdCritique | null> =>
+    invoke<AdCritique | null>("get_ad_critique", { adId }),
+
+  /**
+   * List ads whose latest critique scores below a threshold, weakest first
+   * @param maxOverall - Overall score cutoff (default 6.0)
+   * @param productId - Optional product filter
+   */
+  getWeakAds: (maxOverall?: number, productId?: number): Promise<CritiquedAd[]> =>
+    invoke<CritiquedAd[]>("get_weak_ads", { maxOverall, productId }),
+
+  /**
+   * Generate alternative headlines for an ad, ranked by CTR heuristics
+   * @param adId - The ID of the ad
+   * @param count - Number of variants (default 5, max 10)
+   * @returns The ad's current headline plus the ranked variants
+   */
+  generateHeadlineVariants: (adId: number, count?: number): Promise<HeadlineVariant[]> =>
+    invoke<HeadlineVariant[]>("generate_headline_variants", { adId, count }),
+
+  /**
+   * Get an ad's headline variants, best score first
+   * @param adId - The ID of the ad
+   */
+  getHeadlineVariants: (adId: number): Promise<HeadlineVariant[]> =>
+    invoke<HeadlineVariant[]>("get_headline_variants", { adId }),
+
+  /**
+   * Use a headline variant as the ad's headline
+   * @param variantId - The ID of the variant
+   * @returns The updated ad copy
+   */
+  selectHeadlineVariant: (variantId: number): Promise<GeneratedAdCopy> =>
+    invoke<GeneratedAdCopy>("select_headline_variant", { variantId }),
+
+  /**
+   * List the user's quick ad templates
+   */
+  getTemplates: (): Promise<AdTemplate[]> => invoke<AdTemplate[]>("get_ad_templates"),
+
+  /**
+   * Create or update a quick ad template; unknown merge tags are rejected
+   * @param input - The template
+   */
+  saveTemplate: (input: SaveAdTemplateInput): Promise<AdTemplate> =>
+    invoke<AdTemplate>("save_ad_template", { input }),
+
+  /**
+   * Delete a quick ad template
+   * @param templateId - The ID of the template
+   */
+  deleteTemplate: (templateId: number): Promise<void> =>
+    invoke<void>("delete_ad_template", { templateId }),
+
+  /**
+   * Generate an ad instantly from a template, without calling an AI provider
+   * @param productId - The ID of the product
+   * @param templateId - The ID of the template
+   * @returns The saved ad copy
+   */
+  generateQuickAd: (productId: number, templateId: number): Promise<GeneratedAdCopy> =>
+    invoke<GeneratedAdCopy>("generate_quick_ad", { productId, templateId }),
+
+  /**
+   * Bundle copy (.txt/.md), tracking link, QR code, and product image for posting
+   * @param target - A single ad or every ad for a product
+   * @param path - Directory to write the kit into
+   * @param asZip - Write a single .zip instead of a folder
+   */
+  exportAdKit: (
+    target: { adId: number } | { productId: number },
+    path: string,
+    asZip?: boolean
+  ): Promise<AdKitExport> => invoke<AdKitExport>("export_ad_kit", { ...target, path, asZip }),
+
+  /**
+   * Export ads as Markdown notes with front matter (Notion/Obsidian friendly)
+   * @param path - Folder to write one note per ad into
+   * @param filter - Which ads to export (default: all)
+   */
+  exportMarkdown: (path: string, filter?: AdExportFilter): Promise<MarkdownExport> =>
+    invoke<MarkdownExport>("export_ads_markdown", { filter, path }),
+
+  /**
+   * Export ads as a Meta Ads Manager bulk import or Google Ads Editor CSV
+   * @param format - "meta" or "google"
+   * @param path - Directory or .csv file path
+   * @param filter - Which ads to export (default: all)
+   * @param campaignName - Campaign to create the ads under
+   */
+  exportPaidAds: (
+    format: PaidAdsFormat,
+    path: string,
+    filter?: AdExportFilter,
+    campaignName?: string
+  ): Promise<PaidAdsExport> =>
+    invoke<PaidAdsExport>("export_paid_ads", { format, filter, path, campaignName }),
+
+  /**
+   * Import an existing ad copy library (CSV or JSON), matched to products by ID or name
+   * @param filePath - Path to the .csv or .json file
+   */
+  importAds: (filePath: string): Promise<AdImportSummary> =>
+    invoke<AdImportSummary>("import_ads", { filePath }),
+
+  /**
+   * Ads without an active affiliate link attached, newest first
+   */
+  getUnlinkedAds: (): Promise<GeneratedAdCopy[]> =>
+    invoke<GeneratedAdCopy[]>("get_unlinked_ads"),
+
+  /**
+   * Active affiliate links that no ad uses yet, newest first
+   */
+  getUnadvertisedLinks: (): Promise<AffiliateLink[]> =>
+    invoke<AffiliateLink[]>("get_unadvertised_links"),
 };
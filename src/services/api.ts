@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type {
   Product,
   CreateProductInput,
@@ -7,12 +8,120 @@ import type {
   AffiliateProgramDiscovery,
   GenerateLinkRequest,
   GenerateLinkForPlatformRequest,
+  BulkLinkFilter,
+  BulkLinkGenerationResult,
+  AiDiscoveryRecord,
   AffiliateCredential,
+  CredentialCheck,
+  MissingCredentialError,
   SaveCredentialInput,
+  ProductResearch,
+  LinkHubExport,
+  Webhook,
+  SaveWebhookInput,
+  FeedSource,
+  SaveFeedSourceInput,
+  FeedPollReport,
+  WebhookDelivery,
+  RestApiStatus,
+  EntityQuery,
+  DataChanged,
+  DemoDataSummary,
+  WorkspaceReset,
+  SeedStatus,
+  SeedResult,
+  ProgramApplication,
+  SaveProgramApplicationInput,
+  ApplicationStatus,
+  NetworkTerms,
+  SaveNetworkTermsInput,
+  ProgramNetworkIds,
+  BuildDeepLinkRequest,
+  PrettyLinksFormat,
+  PrettyLinksExport,
+  CatalogFeedFormat,
+  CatalogFeedExport,
+  ReadinessConfig,
+  ProductReadiness,
+  ScheduledPost,
+  ScheduledPostStatus,
+  SaveScheduledPostInput,
+  PostTimeSuggestion,
+  PostingRules,
+  ScheduleConflict,
+  SavedScheduledPost,
+  RetailEvent,
+  SaveRetailEventInput,
+  UpcomingEvent,
+  CampaignSuggestion,
+  ProductSeasonality,
+  ApprovalState,
+  ApprovalEntityType,
+  ApprovalTransition,
+  WorkspaceLock,
+  SharedWorkspaceStatus,
+  RoleStatus,
+  OwnerRequiredError,
+  ReadOnlyStatus,
+  ReadOnlyError,
+  DataExportSummary,
+  DataWipeSummary,
+  RetentionPolicy,
+  RetentionReport,
+  DailyMetric,
+  DailyMetricsStatus,
+  SlowQueryReport,
+  QueryPlan,
+  ProductListFilter,
+  LinkListFilter,
+  ListSort,
+  ListPage,
+  ProductCsvExport,
+  IdentifierImport,
+  ListingImport,
+  WatchedProduct,
+  ProductChange,
+  WatchCheckReport,
+  ProductComparison,
+  Bundle,
+  SaveBundleInput,
+  BundlePerformance,
+  Note,
+  NoteEntityType,
+  NoteSearchHit,
+  SaveNoteInput,
+  Attachment,
+  AttachmentEntityType,
+  PromotionCandidate,
+  TrendHistory,
+  Benchmark,
+  SaveBenchmarkInput,
+  PlatformMixSimulation,
+  SimulationAssumptions,
+  CategoryKeyword,
+  SaveCategoryKeywordInput,
+  RecordVariationPerformanceInput,
+  NextVariation,
+  Experiment,
+  StartExperimentInput,
+  ExperimentAnalysis,
+  ProductCohortReport,
+  BulkOperation,
+  RefreshDiffReport,
+  RefreshPeriod,
+  ImportAnalysis,
+  ImportPlan,
+  ImportResult,
+  StoreProvider,
+  StoreSkuMapping,
+  StoreSyncReport,
+  TikTokSyncReport,
 } from "@/types";
+import type { GeneratedAdCopy } from "@/services/adApi";
 
 // Product API
 export const productApi = {
+  /** Loads the whole catalog; large lists should page with count/getRange */
   getAll: async (): Promise<Product[]> => {
     return await invoke("get_all_products");
   },
@@ -36,10 +145,51 @@ export const productApi = {
   search: async (query: string): Promise<Product[]> => {
     return await invoke("search_products", { query });
   },
+
+  /** Total for a virtualized table */
+  count: async (filter?: ProductListFilter): Promise<number> => {
+    return await invoke("count_products", { filter: filter ?? null });
+  },
+
+  /** Only rows offset..offset+limit (limit at most 500), plus the total */
+  getRange: async (
+    offset: number,
+    limit: number,
+    filter?: ProductListFilter,
+    sort?: ListSort
+  ): Promise<ListPage<Product>> => {
+    return await invoke("get_products_range", { filter: filter ?? null, sort: sort ?? null, offset, limit });
+  },
+
+  /** Creates stub products from a file of ASINs / URLs and starts enriching them in the background */
+  importIdentifiers: async (filePath: string): Promise<IdentifierImport> => {
+    return await invoke("import_identifiers", { filePath });
+  },
+
+  /** Creates products from an Etsy shop or eBay seller/search URL, reading up to `pages` result pages (max 5) */
+  importMarketplaceListings: async (url: string, pages?: number): Promise<ListingImport> => {
+    return await invoke("import_marketplace_listings", { url, pages: pages ?? null });
+  },
+
+  /** 2-6 products side by side; `context` is a Markdown table for comparison-article prompts */
+  compare: async (productIds: number[]): Promise<ProductComparison> => {
+    return await invoke("compare_products", { productIds });
+  },
+
+  /** What to promote next, best opportunity first, with the reasons */
+  getPromotionQueue: async (limit?: number): Promise<PromotionCandidate[]> => {
+    return await invoke("get_promotion_queue", { limit: limit ?? null });
+  },
+
+  /** Recorded trending scores (default: the last 60 days) with the current momentum */
+  getTrendHistory: async (productId: number, days?: number): Promise<TrendHistory> => {
+    return await invoke("get_trend_history", { productId, days: days ?? null });
+  },
 };
 
 // Affiliate Link API
 export const affiliateLinkApi = {
+  /** Loads every link; large lists should page with count/getRange */
   getAll: async (): Promise<AffiliateLink[]> => {
     return await invoke("get_all_affiliate_links");
   },
@@ -48,6 +198,21 @@ export const affiliateLinkApi = {
     return await invoke("get_links_by_product", { productId });
   },
 
+  /** Total for a virtualized table */
+  count: async (filter?: LinkListFilter): Promise<number> => {
+    return await invoke("count_links", { filter: filter ?? null });
+  },
+
+  /** Only rows offset..offset+limit (limit at most 500), plus the total */
+  getRange: async (
+    offset: number,
+    limit: number,
+    filter?: LinkListFilter,
+    sort?: ListSort
+  ): Promise<ListPage<AffiliateLink>> => {
+    return await invoke("get_links_range", { filter: filter ?? null, sort: sort ?? null, offset, limit });
+  },
+
   discoverPrograms: async (
     productId: number
   ): Promise<AffiliateProgramDiscovery[]> => {
@@ -60,8 +225,25 @@ export const affiliateLinkApi = {
     return await invoke("generate_affiliate_link", { request });
   },
 
-  refreshLink: async (linkId: number): Promise<AffiliateLink> => {
-    return await invoke("refresh_affiliate_link", { linkId });
+  refreshLink: async (
+    linkId: number,
+    allowMissingCredential?: boolean
+  ): Promise<AffiliateLink> => {
+    return await invoke("refresh_affiliate_link", { linkId, allowMissingCredential });
+  },
+
+  refreshForProducts: async (
+    productIds: number[],
+    allowMissingCredential?: boolean
+  ): Promise<BulkOperation> => {
+    return await invoke("refresh_links_for_products", {
+      productIds,
+      allowMissingCredential: allowMissingCredential ?? null,
+    });
+  },
+
+  getRefreshDiffs: async (period: RefreshPeriod): Promise<RefreshDiffReport> => {
+    return await invoke("get_refresh_diffs", { period });
   },
 
   deleteLink: async (id: number): Promise<void> => {
@@ -72,11 +254,24 @@ export const affiliateLinkApi = {
     return await invoke("generate_links_for_all_products");
   },
 
+  generateForProducts: async (
+    filter: BulkLinkFilter
+  ): Promise<BulkLinkGenerationResult> => {
+    return await invoke("generate_links_for_products", { filter });
+  },
+
   generateForPlatform: async (
     request: GenerateLinkForPlatformRequest
   ): Promise<AffiliateLink> => {
     return await invoke("generate_link_for_platform", { request });
   },
+
+  getDiscoveryDiagnostics: async (
+    productId: number,
+    limit?: number
+  ): Promise<AiDiscoveryRecord[]> => {
+    return await invoke("get_discovery_diagnostics", { productId, limit });
+  },
 };
 
 // Credentials API
@@ -96,4 +291,840 @@ export const credentialsApi = {
   delete: async (platform: string): Promise<void> => {
     return await invoke("delete_credential", { platform });
   },
+
+  /** Whether each platform has an active credential for real tracking IDs */
+  check: async (platforms: string[]): Promise<CredentialCheck[]> => {
+    return await invoke("check_platform_credentials", { platforms });
+  },
+};
+
+/** The missing-credential error thrown by link generation, if that's what `error` is */
+export function parseMissingCredentialError(error: unknown): MissingCredentialError | null {
+  if (typeof error !== "string") return null;
+  try {
+    const parsed = JSON.parse(error);
+    return parsed?.code === "missing_credential" ? (parsed as MissingCredentialError) : null;
+  } catch {
+    return null;
+  }
+}
+
+// Product research API
+export const researchApi = {
+  research: async (productId: number): Promise<ProductResearch> => {
+    return await invoke("research_product", { productId });
+  },
+
+  getLatest: async (productId: number): Promise<ProductResearch | null> => {
+    return await invoke("get_product_research", { productId });
+  },
+};
+
+// Export API
+export const exportApi = {
+  linkHub: async (
+    path: string,
+    productIds?: number[],
+    title?: string
+  ): Promise<LinkHubExport> => {
+    return await invoke("export_link_hub", { path, productIds, title });
+  },
+};
+
+// Outgoing webhooks API
+export const webhookApi = {
+  getAll: async (): Promise<Webhook[]> => {
+    return await invoke("get_webhooks");
+  },
+
+  save: async (input: SaveWebhookInput): Promise<Webhook> => {
+    return await invoke("save_webhook", { input });
+  },
+
+  delete: async (webhookId: number): Promise<void> => {
+    return await invoke("delete_webhook", { webhookId });
+  },
+
+  /** Sends a test event and returns the logged delivery */
+  test: async (webhookId: number): Promise<WebhookDelivery> => {
+    return await invoke("test_webhook", { webhookId });
+  },
+
+  getDeliveries: async (webhookId?: number, limit?: number): Promise<WebhookDelivery[]> => {
+    return await invoke("get_webhook_deliveries", { webhookId, limit });
+  },
+};
+
+// RSS/Atom feed sources API
+export const feedSourceApi = {
+  getAll: async (): Promise<FeedSource[]> => {
+    return await invoke("get_feed_sources");
+  },
+
+  save: async (input: SaveFeedSourceInput): Promise<FeedSource> => {
+    return await invoke("save_feed_source", { input });
+  },
+
+  delete: async (sourceId: number): Promise<void> => {
+    return await invoke("delete_feed_source", { sourceId });
+  },
+
+  /** Polls one feed (or all active feeds) now; feeds are also polled every 3 hours */
+  pollNow: async (sourceId?: number | null): Promise<FeedPollReport[]> => {
+    return await invoke("poll_feed_sources_now", { sourceId: sourceId ?? null });
+  },
+};
+
+// Local REST API
+export const restApi = {
+  getStatus: async (): Promise<RestApiStatus> => {
+    return await invoke("get_rest_api_status");
+  },
+
+  saveConfig: async (
+    enabled: boolean,
+    port?: number,
+    allowLan?: boolean
+  ): Promise<RestApiStatus> => {
+    return await invoke("save_rest_api_config", { enabled, port, allowLan });
+  },
+
+  /** Invalidates the current token; clients must be updated */
+  regenerateToken: async (): Promise<RestApiStatus> => {
+    return await invoke("regenerate_rest_api_token");
+  },
+};
+
+// Flexible query API
+export const queryApi = {
+  /**
+   * Fetches rows with related records embedded, e.g.
+   * `{ entity: "products", include: ["active_links", "latest_ad"] }`
+   */
+  query: async <T = Record<string, unknown>>(spec: EntityQuery): Promise<T[]> => {
+    return await invoke("query_entities", { spec });
+  },
+};
+
+// Data change events emitted by mutating commands
+export type DataChangeEvent = "products://changed" | "links://changed" | "ads://changed";
+
+export const dataEvents = {
+  /** Subscribes to changes; call the returned function to unsubscribe */
+  onChange: async (
+    event: DataChangeEvent,
+    handler: (change: DataChanged) => void
+  ): Promise<UnlistenFn> => {
+    return await listen<DataChanged>(event, (e) => handler(e.payload));
+  },
+};
+
+// Workspace API
+export const workspaceApi = {
+  /** Adds 50 demo products with links, ads, and 90 days of traffic */
+  loadDemoData: async (): Promise<DemoDataSummary> => {
+    return await invoke("load_demo_data");
+  },
+
+  /** Deletes all workspace data; settings and credentials are kept */
+  reset: async (): Promise<WorkspaceReset> => {
+    return await invoke("reset_workspace");
+  },
+
+  getSeedStatus: async (): Promise<SeedStatus> => {
+    return await invoke("get_seed_status");
+  },
+
+  /** Answers the first-run prompt */
+  chooseStartingWorkspace: async (seeded: boolean): Promise<SeedResult> => {
+    return await invoke("choose_starting_workspace", { seeded });
+  },
+
+  /** Adds missing starter products; `force` also restores deleted ones */
+  reseedProducts: async (force?: boolean): Promise<SeedResult> => {
+    return await invoke("reseed_products", { force });
+  },
+};
+
+// Program Applications API
+export const programApplicationApi = {
+  getAll: async (status?: ApplicationStatus): Promise<ProgramApplication[]> => {
+    return await invoke("get_program_applications", { status });
+  },
+
+  /** Creates or updates the application for a program, matched by name */
+  save: async (input: SaveProgramApplicationInput): Promise<ProgramApplication> => {
+    return await invoke("save_program_application", { input });
+  },
+
+  delete: async (applicationId: number): Promise<void> => {
+    return await invoke("delete_program_application", { applicationId });
+  },
+
+  /** Pending applications due a follow-up */
+  getReminders: async (): Promise<ProgramApplication[]> => {
+    return await invoke("get_application_reminders");
+  },
+};
+
+// Network Terms API
+export const networkTermsApi = {
+  getAll: async (): Promise<NetworkTerms[]> => {
+    return await invoke("get_network_terms");
+  },
+
+  save: async (input: SaveNetworkTermsInput): Promise<NetworkTerms> => {
+    return await invoke("save_network_terms", { input });
+  },
+
+  /** Only user-added networks can be deleted */
+  delete: async (termsId: number): Promise<void> => {
+    return await invoke("delete_network_terms", { termsId });
+  },
+};
+
+// Deep Links API
+export const deepLinkApi = {
+  getPrograms: async (): Promise<ProgramNetworkIds[]> => {
+    return await invoke("get_program_network_ids");
+  },
+
+  saveProgram: async (input: ProgramNetworkIds): Promise<ProgramNetworkIds> => {
+    return await invoke("save_program_network_ids", { input });
+  },
+
+  /** Wraps a merchant URL in the program's network deep link and saves it as a link */
+  build: async (request: BuildDeepLinkRequest): Promise<AffiliateLink> => {
+    return await invoke("build_deep_link", { request });
+  },
+};
+
+// Pretty Links API
+export const prettyLinksApi = {
+  /** Writes active links as /go/<slug> redirects for the user's own site */
+  export: async (
+    format: PrettyLinksFormat,
+    path: string,
+    productIds?: number[] | null,
+    prefix?: string | null,
+  ): Promise<PrettyLinksExport> => {
+    return await invoke("export_pretty_links", { format, path, productIds, prefix });
+  },
+};
+
+// Catalog Feed API
+export const catalogFeedApi = {
+  /** Writes a Meta Commerce catalog feed for Instagram/Facebook Shopping; items link to affiliate URLs */
+  exportMeta: async (
+    productIds: number[],
+    format: CatalogFeedFormat,
+    path: string,
+    brand: string,
+    currency?: string | null,
+  ): Promise<CatalogFeedExport> => {
+    return await invoke("export_meta_catalog", { productIds, format, path, brand, currency: currency ?? null });
+  },
+
+  /** Writes a Google Merchant Center feed for Shopping and Performance Max; failing products list every problem */
+  exportGoogle: async (
+    productIds: number[],
+    format: CatalogFeedFormat,
+    path: string,
+    brand: string,
+    currency?: string | null,
+  ): Promise<CatalogFeedExport> => {
+    return await invoke("export_google_merchant_feed", {
+      productIds,
+      format,
+      path,
+      brand,
+      currency: currency ?? null,
+    });
+  },
+};
+
+// Product CSV Export API
+export const productExportApi = {
+  /** Writes the filtered catalog as a CSV; columns default to the main fields plus link, ad and click columns */
+  exportCsv: async (
+    path: string,
+    filter?: ProductListFilter | null,
+    columns?: string[] | null,
+  ): Promise<ProductCsvExport> => {
+    return await invoke("export_products_csv", { filter, path, columns });
+  },
+};
+
+// Product Readiness API
+export const readinessApi = {
+  getConfig: async (): Promise<ReadinessConfig> => {
+    return await invoke("get_readiness_config");
+  },
+
+  saveConfig: async (config: ReadinessConfig): Promise<ReadinessConfig> => {
+    return await invoke("save_readiness_config", { config });
+  },
+
+  getProductReadiness: async (productId: number): Promise<ProductReadiness> => {
+    return await invoke("get_product_readiness", { productId });
+  },
+
+  /** The products among `productIds` that pass every required check */
+  filterReady: async (productIds: number[]): Promise<number[]> => {
+    return await invoke("filter_ready_products", { productIds });
+  },
+
+  setPriceVerified: async (productId: number, verified: boolean): Promise<ProductReadiness> => {
+    return await invoke("set_price_verified", { productId, verified });
+  },
+};
+
+// Post Schedule API
+export const postScheduleApi = {
+  getPosts: async (
+    from?: string | null,
+    to?: string | null,
+    platform?: string | null,
+  ): Promise<ScheduledPost[]> => {
+    return await invoke("get_scheduled_posts", { from, to, platform });
+  },
+
+  /** Best time to post from clicks, platform defaults, and audience */
+  suggestTime: async (
+    productId: number,
+    platform: string,
+    utcOffsetMinutes?: number | null,
+  ): Promise<PostTimeSuggestion> => {
+    return await invoke("suggest_post_time", { productId, platform, utcOffsetMinutes });
+  },
+
+  /**
+   * Recurring posts (repeatEveryDays set) are expanded onto the calendar right
+   * away; an empty scheduledAt uses the suggested time. Cap conflicts come
+   * back as warnings, or reject the save when caps are enforced.
+   */
+  save: async (input: SaveScheduledPostInput): Promise<SavedScheduledPost> => {
+    return await invoke("save_scheduled_post", { input });
+  },
+
+  getConflicts: async (from?: string | null, to?: string | null): Promise<ScheduleConflict[]> => {
+    return await invoke("get_schedule_conflicts", { from, to });
+  },
+
+  getPostingRules: async (): Promise<PostingRules> => {
+    return await invoke("get_posting_rules");
+  },
+
+  savePostingRules: async (rules: PostingRules): Promise<PostingRules> => {
+    return await invoke("save_posting_rules", { rules });
+  },
+
+  setStatus: async (postId: number, status: ScheduledPostStatus): Promise<ScheduledPost> => {
+    return await invoke("set_scheduled_post_status", { postId, status });
+  },
+
+  /** Single occurrences of a series are cancelled; pass wholeSeries to remove it all */
+  delete: async (postId: number, wholeSeries?: boolean): Promise<void> => {
+    return await invoke("delete_scheduled_post", { postId, wholeSeries });
+  },
+
+  expandRecurring: async (): Promise<number> => {
+    return await invoke("expand_recurring_posts");
+  },
+};
+
+export const retailEventsApi = {
+  getAll: async (): Promise<RetailEvent[]> => {
+    return await invoke("get_retail_events");
+  },
+
+  save: async (input: SaveRetailEventInput): Promise<RetailEvent> => {
+    return await invoke("save_retail_event", { input });
+  },
+
+  /** Shipped events can only be edited or disabled */
+  delete: async (eventId: number): Promise<void> => {
+    return await invoke("delete_retail_event", { eventId });
+  },
+
+  getUpcoming: async (days?: number | null): Promise<UpcomingEvent[]> => {
+    return await invoke("get_upcoming_events", { days });
+  },
+
+  getCampaignSuggestions: async (days?: number | null): Promise<CampaignSuggestion[]> => {
+    return await invoke("get_campaign_suggestions", { days });
+  },
+
+  getProductSeasonality: async (productId: number): Promise<ProductSeasonality> => {
+    return await invoke("get_product_seasonality", { productId });
+  },
+
+  /** Spreads one post per product across the event's promotion window */
+  scheduleCampaign: async (
+    eventId: number,
+    platform: string,
+    productIds?: number[] | null,
+  ): Promise<SavedScheduledPost[]> => {
+    return await invoke("schedule_event_campaign", { eventId, platform, productIds });
+  },
+};
+
+export interface ApprovalQueue {
+  state: ApprovalState;
+  ads: GeneratedAdCopy[];
+  posts: ScheduledPost[];
+}
+
+export const approvalsApi = {
+  /** Sending something back from review to draft needs a comment */
+  transition: async (
+    entityType: ApprovalEntityType,
+    entityId: number,
+    toState: ApprovalState,
+    reviewer?: string | null,
+    comment?: string | null,
+  ): Promise<ApprovalTransition> => {
+    return await invoke("transition_approval", { entityType, entityId, toState, reviewer, comment });
+  },
+
+  getHistory: async (entityType: ApprovalEntityType, entityId: number): Promise<ApprovalTransition[]> => {
+    return await invoke("get_approval_history", { entityType, entityId });
+  },
+
+  getQueue: async (state: ApprovalState): Promise<ApprovalQueue> => {
+    return await invoke("get_approval_queue", { state });
+  },
+
+  getRequired: async (): Promise<boolean> => {
+    return await invoke("get_approval_required");
+  },
+
+  setRequired: async (required: boolean): Promise<boolean> => {
+    return await invoke("set_approval_required", { required });
+  },
+};
+
+export const sharedWorkspaceApi = {
+  getStatus: async (): Promise<SharedWorkspaceStatus> => {
+    return await invoke("get_shared_workspace_status");
+  },
+
+  /**
+   * Points the workspace at a database on a shared drive (null for the local
+   * one); copyCurrent starts a new shared database from the current data
+   */
+  setLocation: async (
+    databasePath: string | null,
+    editorName?: string | null,
+    copyCurrent?: boolean,
+  ): Promise<SharedWorkspaceStatus> => {
+    return await invoke("set_shared_workspace", { databasePath, editorName, copyCurrent });
+  },
+
+  /** force takes the lock over from a teammate */
+  acquireLock: async (force?: boolean): Promise<WorkspaceLock> => {
+    return await invoke("acquire_workspace_lock", { force });
+  },
+
+  releaseLock: async (): Promise<SharedWorkspaceStatus> => {
+    return await invoke("release_workspace_lock");
+  },
+};
+
+/** The owner-required error thrown by gated commands, if that's what `error` is */
+export function parseOwnerRequiredError(error: unknown): OwnerRequiredError | null {
+  if (typeof error !== "string") return null;
+  try {
+    const parsed = JSON.parse(error);
+    return parsed?.code === "owner_required" ? (parsed as OwnerRequiredError) : null;
+  } catch {
+    return null;
+  }
+}
+
+/** The read-only error thrown while the workspace is read-only, if that's what `error` is */
+export function parseReadOnlyError(error: unknown): ReadOnlyError | null {
+  if (typeof error !== "string") return null;
+  try {
+    const parsed = JSON.parse(error);
+    return parsed?.code === "read_only" ? (parsed as ReadOnlyError) : null;
+  } catch {
+    return null;
+  }
+}
+
+export const readOnlyApi = {
+  getStatus: async (): Promise<ReadOnlyStatus> => {
+    return await invoke("get_read_only_status");
+  },
+
+  /** Turning read-only off is owner-only */
+  setEnabled: async (enabled: boolean, reason?: string): Promise<ReadOnlyStatus> => {
+    return await invoke("set_workspace_read_only", { enabled, reason: reason ?? null });
+  },
+};
+
+export const rolesApi = {
+  getStatus: async (): Promise<RoleStatus> => {
+    return await invoke("get_role_status");
+  },
+
+  /**
+   * Owner-only commands work for 30 minutes after unlocking. Reload
+   * credentials afterwards; their secrets are hidden from contributors.
+   */
+  unlock: async (password: string): Promise<RoleStatus> => {
+    return await invoke("unlock_owner", { password });
+  },
+
+  lock: async (): Promise<RoleStatus> => {
+    return await invoke("lock_owner");
+  },
+
+  /** An empty newPassword removes the password and turns gating off */
+  setPassword: async (currentPassword: string | null, newPassword: string | null): Promise<RoleStatus> => {
+    return await invoke("set_owner_password", { currentPassword, newPassword });
+  },
+};
+
+/** Typed by the user to confirm `dataPrivacyApi.wipeAll` */
+export const WIPE_CONFIRM_PHRASE = "DELETE ALL MY DATA";
+
+export const dataPrivacyApi = {
+  /** JSON dump of every table, credentials included (owner only) */
+  exportAll: async (path: string): Promise<DataExportSummary> => {
+    return await invoke("export_all_data", { path });
+  },
+
+  /** Erases all data and media; the app returns to its first-run state */
+  wipeAll: async (confirmPhrase: string): Promise<DataWipeSummary> => {
+    return await invoke("wipe_all_data", { confirmPhrase });
+  },
+};
+
+export const retentionApi = {
+  getPolicy: async (): Promise<RetentionPolicy> => {
+    return await invoke("get_retention_policy");
+  },
+
+  savePolicy: async (policy: RetentionPolicy): Promise<RetentionPolicy> => {
+    return await invoke("save_retention_policy", { policy });
+  },
+
+  /** Runs the daily retention pass immediately */
+  applyNow: async (): Promise<RetentionReport> => {
+    return await invoke("apply_retention_policy");
+  },
+};
+
+export const dailyMetricsApi = {
+  /** Days without activity are omitted; dates are YYYY-MM-DD */
+  get: async (
+    start: string,
+    end: string,
+    filter?: { productId?: number; linkId?: number }
+  ): Promise<DailyMetric[]> => {
+    return await invoke("get_daily_metrics", {
+      start,
+      end,
+      productId: filter?.productId ?? null,
+      linkId: filter?.linkId ?? null,
+    });
+  },
+
+  getStatus: async (): Promise<DailyMetricsStatus> => {
+    return await invoke("get_daily_metrics_status");
+  },
+
+  /** Recomputes all days from raw events */
+  rebuild: async (): Promise<DailyMetricsStatus> => {
+    return await invoke("rebuild_daily_metrics");
+  },
+
+  /** Products grouped by month added, followed month by month (default: the last 12 cohorts) */
+  getProductCohorts: async (months?: number): Promise<ProductCohortReport> => {
+    return await invoke("get_product_cohorts", { months: months ?? null });
+  },
+};
+
+export const diagnosticsApi = {
+  /** Slow statements since the app started */
+  getSlowQueries: async (): Promise<SlowQueryReport> => {
+    return await invoke("get_slow_queries");
+  },
+
+  setSlowQueryThreshold: async (thresholdMs: number): Promise<SlowQueryReport> => {
+    return await invoke("set_slow_query_threshold", { thresholdMs });
+  },
+
+  clearSlowQueries: async (): Promise<SlowQueryReport> => {
+    return await invoke("clear_slow_queries");
+  },
+
+  /** Query plans for the main lists; flags full scans and temp sorts */
+  explainHotQueries: async (): Promise<QueryPlan[]> => {
+    return await invoke("explain_hot_queries");
+  },
+};
+
+// Product Watchlist API
+export const watchlistApi = {
+  getAll: async (): Promise<WatchedProduct[]> => {
+    return await invoke("get_watchlist");
+  },
+
+  /** The product needs a URL; it is re-scraped twice a day */
+  watch: async (productId: number): Promise<WatchedProduct[]> => {
+    return await invoke("watch_product", { productId });
+  },
+
+  unwatch: async (productId: number): Promise<WatchedProduct[]> => {
+    return await invoke("unwatch_product", { productId });
+  },
+
+  getChanges: async (productId?: number, limit?: number): Promise<ProductChange[]> => {
+    return await invoke("get_product_changes", { productId: productId ?? null, limit: limit ?? null });
+  },
+
+  checkNow: async (): Promise<WatchCheckReport> => {
+    return await invoke("check_watchlist_now");
+  },
+};
+
+// Product Bundles API
+export const bundlesApi = {
+  getAll: async (): Promise<Bundle[]> => {
+    return await invoke("get_bundles");
+  },
+
+  /** Creates the bundle when input.id is unset, otherwise replaces it */
+  save: async (input: SaveBundleInput): Promise<Bundle> => {
+    return await invoke("save_bundle", { input });
+  },
+
+  /** Ads generated for the bundle are kept */
+  delete: async (bundleId: number): Promise<void> => {
+    return await invoke("delete_bundle", { bundleId });
+  },
+
+  /** A carousel card or email line per product, each with its own link */
+  generateAd: async (bundleId: number, adType: "carousel" | "email"): Promise<GeneratedAdCopy> => {
+    return await invoke("generate_bundle_ad", { bundleId, adType });
+  },
+
+  getAds: async (bundleId: number): Promise<GeneratedAdCopy[]> => {
+    return await invoke("get_bundle_ads", { bundleId });
+  },
+
+  getPerformance: async (bundleId: number, days?: number): Promise<BundlePerformance> => {
+    return await invoke("get_bundle_performance", { bundleId, days: days ?? null });
+  },
+};
+
+// Notes API
+export const notesApi = {
+  getAll: async (limit?: number): Promise<Note[]> => {
+    return await invoke("get_notes", { limit: limit ?? null });
+  },
+
+  /** Notes referring to the entity, explicitly or via a [[type:id]] backlink */
+  getForEntity: async (entityType: NoteEntityType, entityId: number): Promise<Note[]> => {
+    return await invoke("get_notes_for_entity", { entityType, entityId });
+  },
+
+  save: async (input: SaveNoteInput): Promise<Note> => {
+    return await invoke("save_note", { input: { ...input, refs: input.refs ?? [] } });
+  },
+
+  delete: async (noteId: number): Promise<void> => {
+    return await invoke("delete_note", { noteId });
+  },
+
+  search: async (query: string, limit?: number): Promise<NoteSearchHit[]> => {
+    return await invoke("search_notes", { query, limit: limit ?? null });
+  },
+};
+
+// Attachments API
+export const attachmentsApi = {
+  /** Copies the file into the workspace; files over 25 MB are refused */
+  add: async (
+    entityType: AttachmentEntityType,
+    entityId: number,
+    filePath: string,
+    description?: string
+  ): Promise<Attachment> => {
+    return await invoke("add_attachment", { entityType, entityId, filePath, description: description ?? null });
+  },
+
+  getForEntity: async (entityType: AttachmentEntityType, entityId: number): Promise<Attachment[]> => {
+    return await invoke("get_attachments", { entityType, entityId });
+  },
+
+  /** Opens the file with the system's default app */
+  open: async (attachmentId: number): Promise<void> => {
+    return await invoke("open_attachment", { attachmentId });
+  },
+
+  delete: async (attachmentId: number): Promise<void> => {
+    return await invoke("delete_attachment", { attachmentId });
+  },
+};
+
+// Platform Mix Simulator API
+export const platformMixApi = {
+  simulate: async (productId: number, assumptions: SimulationAssumptions): Promise<PlatformMixSimulation> => {
+    return await invoke("simulate_platform_mix", {
+      productId,
+      assumptions: {
+        impressions: assumptions.impressions,
+        scenarios: assumptions.scenarios ?? [],
+        averageOrderValue: assumptions.averageOrderValue ?? null,
+        commissionRate: assumptions.commissionRate ?? null,
+      },
+    });
+  },
+
+  /** All impressions on one platform */
+  estimateEarnings: async (productId: number, platform: string, impressions: number): Promise<PlatformMixSimulation> => {
+    return await invoke("estimate_earnings", { productId, platform, impressions });
+  },
+
+  getBenchmarks: async (): Promise<Benchmark[]> => {
+    return await invoke("get_benchmarks");
+  },
+
+  saveBenchmark: async (input: SaveBenchmarkInput): Promise<Benchmark> => {
+    return await invoke("save_benchmark", {
+      input: { ...input, id: input.id ?? null, category: input.category ?? null, aovMultiplier: input.aovMultiplier ?? null },
+    });
+  },
+
+  /** Restores a shipped benchmark's values */
+  resetBenchmark: async (benchmarkId: number): Promise<Benchmark> => {
+    return await invoke("reset_benchmark", { benchmarkId });
+  },
+
+  deleteBenchmark: async (benchmarkId: number): Promise<void> => {
+    return await invoke("delete_benchmark", { benchmarkId });
+  },
+};
+
+// Category Keywords API
+export const categoryKeywordsApi = {
+  getAll: async (): Promise<CategoryKeyword[]> => {
+    return await invoke("get_category_keywords");
+  },
+
+  /** Edits a keyword's score, or teaches a new keyword to an ad type or platform */
+  save: async (input: SaveCategoryKeywordInput): Promise<CategoryKeyword> => {
+    return await invoke("save_category_keyword", {
+      input: { ...input, id: input.id ?? null, keyword: input.keyword ?? null },
+    });
+  },
+
+  /** Restores a shipped keyword's score */
+  reset: async (keywordId: number): Promise<CategoryKeyword> => {
+    return await invoke("reset_category_keyword", { keywordId });
+  },
+
+  delete: async (keywordId: number): Promise<void> => {
+    return await invoke("delete_category_keyword", { keywordId });
+  },
+};
+
+// Ad Variation Bandit API
+export const variationsApi = {
+  /** Impressions and clicks for one ad, platform and day (default today); re-recording a day replaces it */
+  recordPerformance: async (input: RecordVariationPerformanceInput): Promise<void> => {
+    return await invoke("record_variation_performance", { input: { ...input, day: input.day ?? null } });
+  },
+
+  /** The ad to post next on a platform, by Thompson sampling over recorded CTR */
+  getNext: async (productId: number, platform: string): Promise<NextVariation> => {
+    return await invoke("get_next_variation", { productId, platform });
+  },
+};
+
+// Experiments API
+export const experimentsApi = {
+  getAll: async (): Promise<Experiment[]> => {
+    return await invoke("get_experiments");
+  },
+
+  /** Starts an A/B test now; the first variant is the control */
+  start: async (input: StartExperimentInput): Promise<Experiment> => {
+    return await invoke("start_experiment", {
+      input: {
+        ...input,
+        hypothesis: input.hypothesis ?? null,
+        variants: input.variants.map((v) => ({ name: v.name ?? "", adId: v.adId ?? null, linkId: v.linkId ?? null })),
+      },
+    });
+  },
+
+  stop: async (experimentId: number): Promise<Experiment> => {
+    return await invoke("stop_experiment", { experimentId });
+  },
+
+  delete: async (experimentId: number): Promise<void> => {
+    return await invoke("delete_experiment", { experimentId });
+  },
+
+  /** Lift and significance of each variant over the control */
+  analyze: async (experimentId: number): Promise<ExperimentAnalysis> => {
+    return await invoke("analyze_experiment", { experimentId });
+  },
+};
+
+// Spreadsheet import API
+export const spreadsheetImportApi = {
+  /** Detects columns and suggests a mapping for products, links, and earnings */
+  analyzeFile: async (path: string): Promise<ImportAnalysis> => {
+    return await invoke("analyze_import_file", { path });
+  },
+
+  /** Runs a reviewed mapping; unimportable rows come back in `skipped` */
+  executeImport: async (plan: ImportPlan): Promise<ImportResult> => {
+    return await invoke("execute_import", {
+      plan: { ...plan, createMissingProducts: plan.createMissingProducts ?? false },
+    });
+  },
+};
+
+// Store Sync API (credentials are saved with credentialsApi under "shopify" / "woocommerce")
+export const storeSyncApi = {
+  /** Maps unmapped SKUs to same-named products or creates products for them */
+  importProducts: async (store: StoreProvider): Promise<StoreSyncReport> => {
+    return await invoke("import_store_products", { store });
+  },
+
+  /** Prices and images of mapped SKUs also sync every 6 hours */
+  syncNow: async (store: StoreProvider): Promise<StoreSyncReport> => {
+    return await invoke("sync_store_now", { store });
+  },
+
+  getMappings: async (store?: StoreProvider): Promise<StoreSkuMapping[]> => {
+    return await invoke("get_store_sku_mappings", { store: store ?? null });
+  },
+
+  mapSku: async (store: StoreProvider, sku: string, productId: number): Promise<StoreSkuMapping[]> => {
+    return await invoke("map_store_sku", { store, sku, productId });
+  },
+
+  unmapSku: async (mappingId: number): Promise<StoreSkuMapping[]> => {
+    return await invoke("unmap_store_sku", { mappingId });
+  },
+};
+
+export const tiktokSyncApi = {
+  /** Pulls shop or showcase products and fills in tiktokProductId */
+  syncProducts: async (): Promise<TikTokSyncReport> => {
+    return await invoke("sync_tiktok_products");
+  },
+
+  /** Owner only */
+  saveAccessToken: async (token: string): Promise<void> => {
+    return await invoke("save_tiktok_access_token", { token });
+  },
 };
@@ -4,33 +4,33 @@ export type AdType = 'social_post' | 'story' | 'video_script' | 'carousel' | 'em
 // Generated ad copy result
 export interface GeneratedAdCopy {
   id?: number;
-  product_id: number;
-  ad_type: AdType;
+  productId: number;
+  adType: AdType;
   headline: string;
-  body_text: string;
+  bodyText: string;
   cta: string;
-  platform_specific_data?: Record<string, unknown>;
-  performance_score?: number;
-  created_at?: string;
+  platformSpecificData?: Record<string, unknown>;
+  performanceScore?: number;
+  createdAt?: string;
 }
 
 // Request to generate ad
 export interface GenerateAdRequest {
-  product_id: number;
-  ad_type?: AdType; // Optional - will auto-select if not provided
-  custom_instructions?: string;
+  productId: number;
+  adType?: AdType; // Optional - will auto-select if not provided
+  customInstructions?: string;
 }
 
 // Market analysis result
 export interface MarketAnalysis {
-  recommended_ad_type: AdType;
-  confidence_score: number;
+  recommendedAdType: AdType;
+  confidenceScore: number;
   reasoning: string;
-  alternative_types: AdType[];
+  alternativeTypes: AdType[];
 }
 
 // Generation result with analysis
 export interface AdGenerationResult {
-  ad_copy: GeneratedAdCopy;
-  market_analysis: MarketAnalysis;
+  adCopy: GeneratedAdCopy;
+  marketAnalysis: MarketAnalysis;
 }
@@ -4,41 +4,88 @@ export interface Product {
   name: string;
   category: string;
   description?: string;
-  price_range?: string;
-  target_audience?: string;
-  trending_score?: number;
+  priceRange?: string;
+  targetAudience?: string;
+  trendingScore?: number;
   notes?: string;
-  image_url?: string;
+  imageUrl?: string;
 
   // Platform-specific product identifiers
-  amazon_asin?: string;
-  tiktok_product_id?: string;
-  instagram_product_id?: string;
-  youtube_video_id?: string;
-  pinterest_pin_id?: string;
-  product_url?: string;
+  amazonAsin?: string;
+  tiktokProductId?: string;
+  instagramProductId?: string;
+  youtubeVideoId?: string;
+  pinterestPinId?: string;
+  productUrl?: string;
 
-  created_at?: string;
-  updated_at?: string;
+  createdAt?: string;
+  updatedAt?: string;
+
+  // Lifecycle pipeline
+  stage?: ProductStage;
+  stageChangedAt?: string;
+  tags: string[];
+
+  updatedBy?: string | null; // Who last changed it (shared workspaces)
+
+  availability?: ProductAvailability; // Read from the product page when it is scraped
+  availabilityCheckedAt?: string;
+}
+
+export type ProductAvailability = "in_stock" | "out_of_stock" | "unknown";
+
+export type ProductStage = "researching" | "approved" | "promoting" | "retired";
+
+export interface StageCount {
+  stage: ProductStage;
+  count: number;
+}
+
+export type IdeaStatus = "new" | "promoted" | "dismissed";
+
+export interface Idea {
+  id?: number;
+  url: string;
+  note?: string;
+  source?: string;
+  status: IdeaStatus;
+  productId?: number;
+  capturedAt?: string;
+}
+
+// Payload of the "clipboard-product-url" event
+export interface ClipboardProductUrl {
+  url: string;
+  platform: string;
+}
+
+export interface ClipboardWatcherStatus {
+  enabled: boolean;
+  running: boolean;
 }
 
 export interface CreateProductInput {
   name: string;
   category: string;
   description?: string;
-  price_range?: string;
-  target_audience?: string;
-  trending_score?: number;
+  priceRange?: string;
+  targetAudience?: string;
+  trendingScore?: number;
   notes?: string;
-  image_url?: string;
+  imageUrl?: string;
 
   // Platform-specific product identifiers
-  amazon_asin?: string;
-  tiktok_product_id?: string;
-  instagram_product_id?: string;
-  youtube_video_id?: string;
-  pinterest_pin_id?: string;
-  product_url?: string;
+  amazonAsin?: string;
+  tiktokProductId?: string;
+  instagramProductId?: string;
+  youtubeVideoId?: string;
+  pinterestPinId?: string;
+  productUrl?: string;
+
+  tags?: string[];
+
+  /** updatedAt the edit is based on; the save fails if a teammate changed it since */
+  expectedUpdatedAt?: string | null;
 }
 
 export interface UpdateProductInput {
@@ -46,19 +93,22 @@ export interface UpdateProductInput {
   name?: string;
   category?: string;
   description?: string;
-  price_range?: string;
-  target_audience?: string;
-  trending_score?: number;
+  priceRange?: string;
+  targetAudience?: string;
+  trendingScore?: number;
   notes?: string;
-  image_url?: string;
+  imageUrl?: string;
 
   // Platform-specific product identifiers
-  amazon_asin?: string;
-  tiktok_product_id?: string;
-  instagram_product_id?: string;
-  youtube_video_id?: string;
-  pinterest_pin_id?: string;
-  product_url?: string;
+  amazonAsin?: string;
+  tiktokProductId?: string;
+  instagramProductId?: string;
+  youtubeVideoId?: string;
+  pinterestPinId?: string;
+  productUrl?: string;
+
+  tags?: string[];
+  availability?: ProductAvailability; // Set by hand
 }
 
 // Product categories
@@ -83,62 +133,1369 @@ export type AffiliatePlatform =
 
 export interface AffiliateLink {
   id?: number;
-  product_id: number;
-  product_name: string;
-  platform: string;
-  program_name: string;
-  commission_rate?: number;
-  cookie_duration?: number;
-  tracking_url: string;
-  destination_url: string;
+  productId: number;
+  productName: string;
+  platform: string;
+  programName: string;
+  commissionRate?: number;
+  cookieDuration?: number;
+  trackingUrl: string;
+  destinationUrl: string;
   status: "active" | "expired" | "invalid";
-  created_at?: string;
-  updated_at?: string;
+  createdAt?: string;
+  updatedAt?: string;
+}
+
+// Scopes bulk link generation
+export interface BulkLinkFilter {
+  category?: string;
+  tags?: string[];
+  minTrendingScore?: number;
+  missingPlatformOnly?: boolean;
+  targetPlatform?: AffiliatePlatform;
+  allowMissingCredential?: boolean; // Generate with placeholder IDs where no credential exists
+  includeOutOfStock?: boolean; // Out-of-stock products are skipped otherwise
+}
+
+export interface BulkLinkSkip {
+  productId: number;
+  productName: string;
+  reason: string;
+}
+
+export interface BulkLinkGenerationResult {
+  operationId: number;
+  generated: AffiliateLink[];
+  skipped: BulkLinkSkip[];
+  failed: BulkLinkSkip[];
+}
+
+// Why a provider response was rejected during AI discovery
+export interface ParseDiagnostic {
+  provider: string;
+  attempt: number;
+  stage: "extract" | "json" | "schema";
+  errors: string[];
+}
+
+// Logged provider-backed discovery run
+export interface AiDiscoveryRecord {
+  id: number;
+  productId: number;
+  provider: string; // Primary provider requested
+  model: string;
+  servedBy?: string; // Provider whose output was used ("mock" after full fallback)
+  fallbackErrors: string[];
+  valid: boolean;
+  attempts: number;
+  rawResponse?: string;
+  diagnostics: ParseDiagnostic[];
+  createdAt?: string;
+}
+
+// Persisted bulk link/ad generation or product enrichment run
+export type BulkOperationStatus = "running" | "completed" | "cancelled" | "interrupted";
+
+export interface BulkOperation {
+  id: number;
+  kind: "links" | "ads" | "enrich" | "refresh";
+  status: BulkOperationStatus;
+  paramsJson: string;
+  total: number;
+  pending: number;
+  succeeded: number;
+  failed: number;
+  skipped: number;
+  createdAt?: string;
+  updatedAt?: string;
+}
+
+export interface BulkOperationItem {
+  id: number;
+  operationId: number;
+  productId: number;
+  productName: string;
+  status: "pending" | "succeeded" | "failed" | "skipped";
+  message?: string;
+  resultId?: number;
+}
+
+// Payload of the "bulk-operation-progress" event
+export interface BulkProgress {
+  operationId: number;
+  kind: "links" | "ads" | "enrich" | "refresh";
+  status: BulkOperationStatus;
+  productId?: number;
+  processed: number;
+  total: number;
+  succeeded: number;
+  failed: number;
+  skipped: number;
+}
+
+// Result of importing a list of ASINs / product URLs
+export interface IdentifierImport {
+  productIds: number[]; // stub products created
+  duplicates: string[]; // already in the catalog or repeated in the file
+  invalid: string[]; // lines that are neither an ASIN nor a URL
+  operation?: BulkOperation; // the "enrich" run scraping the new stubs
+}
+
+export interface ListingSource {
+  platform: "etsy" | "ebay";
+  kind: "shop" | "seller" | "search";
+  name: string; // shop or seller name, or the search terms
+  url: string;
+}
+
+// Result of importing marketplace listings
+export interface ListingImport {
+  source: ListingSource;
+  pagesRead: number;
+  found: number; // distinct listings on the pages read
+  productIds: number[]; // products created, tagged with the platform
+  duplicates: string[]; // listing URLs already in the catalog
+}
+
+/**
+ * Audience match factors (each 0-1); score = 50% age + 25% category + 15% trending + 10% price
+ * + audience adjustment + niche boost, or 0 when the platform doesn't serve the audience's country
+ */
+export interface MatchBreakdown {
+  ageAlignment: number;
+  categoryFit: number;
+  trendingFit: number;
+  priceFit: number;
+  audienceAdjustment: number; // gender skew and interest overlap, may be negative
+  localeUnsupported: boolean;
+  nicheBoost: number;
+  score: number;
 }
 
 export interface AffiliateProgramDiscovery {
-  program_name: string;
+  programName: string;
   platform: string;
-  commission_rate: number;
-  cookie_duration: number;
-  affiliate_url: string;
-  is_official: boolean;
-  confidence_score: number;
-  audience_match_score: number;
-  recommendation_reason: string;
+  commissionRate: number;
+  cookieDuration: number;
+  affiliateUrl: string;
+  isOfficial: boolean;
+  confidenceScore: number;
+  audienceMatchScore: number;
+  recommendationReason: string;
+  matchBreakdown?: MatchBreakdown | null; // AI results get the local heuristic's factors
+  applicationStatus?: ApplicationStatus | null; // Set when the program's signup is being tracked
+  networkTerms?: NetworkTerms | null; // Payout and cookie constraints of the matched network
 }
 
 export interface GenerateLinkRequest {
-  product_id: number;
+  productId: number;
+  allowMissingCredential?: boolean;
 }
 
 export interface GenerateLinkForPlatformRequest {
-  product_id: number;
+  productId: number;
   platform: string;
+  allowMissingCredential?: boolean;
 }
 
 // Affiliate Credentials types
 export interface AffiliateCredential {
   id?: number;
   platform: string;
-  affiliate_id?: string;
-  shop_id?: string;
-  account_name?: string;
-  api_key?: string;
-  api_secret?: string;
+  affiliateId?: string;
+  shopId?: string;
+  accountName?: string;
+  apiKey?: string;
+  apiSecret?: string;
   active: boolean;
   verified: boolean;
   notes?: string;
-  created_at?: string;
-  updated_at?: string;
+  createdAt?: string;
+  updatedAt?: string;
+}
+
+export interface CredentialCheck {
+  platform: string;
+  ready: boolean;
+  message?: string | null;
+}
+
+// Thrown (as a JSON string) when generating a link for a platform without a credential
+export interface MissingCredentialError {
+  code: "missing_credential";
+  platform: string;
+  message: string;
 }
 
 export interface SaveCredentialInput {
   platform: string;
-  affiliate_id?: string;
-  shop_id?: string;
-  account_name?: string;
-  api_key?: string;
-  api_secret?: string;
+  affiliateId?: string;
+  shopId?: string;
+  accountName?: string;
+  apiKey?: string;
+  apiSecret?: string;
   notes?: string;
 }
+
+// AI research brief for a product; the latest one feeds ad generation
+export interface ResearchBrief {
+  painPoints: string[];
+  objections: string[];
+  keyBenefits: string[];
+  comparisonTargets: string[];
+  contentAngles: string[];
+}
+
+export interface ProductResearch extends ResearchBrief {
+  id: number;
+  productId: number;
+  servedBy: string; // "mock" when produced by local heuristics
+  createdAt?: string;
+}
+
+// Link hub static site export
+export interface LinkHubExport {
+  path: string; // the written index.html
+  productCount: number;
+  linkCount: number;
+  skippedProductIds: number[]; // selected products without an active link
+}
+
+// Outgoing webhooks (Zapier, Make, n8n, ...)
+export type WebhookEvent = "ad_created" | "link_created" | "conversion_recorded";
+
+export interface Webhook {
+  id: number;
+  name: string;
+  url: string;
+  secret?: string; // signs deliveries as X-AffilAI-Signature: sha256=<hmac>
+  events: WebhookEvent[]; // empty = every event
+  active: boolean;
+  createdAt?: string;
+  updatedAt?: string;
+}
+
+export interface SaveWebhookInput {
+  id?: number;
+  name: string;
+  url: string;
+  secret?: string;
+  events: WebhookEvent[];
+  active: boolean;
+}
+
+export interface WebhookDelivery {
+  id: number;
+  webhookId: number;
+  event: string;
+  statusCode?: number;
+  success: boolean;
+  error?: string;
+  deliveredAt?: string;
+}
+
+// RSS/Atom feeds polled for the ideas inbox
+export interface FeedSource {
+  id: number;
+  name: string;
+  url: string;
+  active: boolean;
+  lastPolledAt?: string;
+  lastError?: string; // why the most recent poll failed, if it did
+  createdAt?: string;
+}
+
+export interface SaveFeedSourceInput {
+  id?: number;
+  name: string;
+  url: string;
+  active: boolean;
+}
+
+export interface FeedPollReport {
+  sourceId: number;
+  name: string;
+  itemsRead: number;
+  newItems: number; // items not seen on an earlier poll
+  ideaIds: number[];
+  error?: string;
+}
+
+// Opt-in local REST API (read-only, token auth)
+export interface RestApiStatus {
+  enabled: boolean;
+  running: boolean;
+  port: number;
+  allowLan: boolean; // listen on all interfaces, not just 127.0.0.1
+  baseUrl: string;
+  lanUrl?: string; // for phones and other devices on the LAN
+  token: string;
+}
+
+// Structured read query for query_entities (one IPC round-trip)
+export type QueryEntity = "products" | "links" | "ads";
+export type QueryFilterOp =
+  | "eq"
+  | "ne"
+  | "lt"
+  | "lte"
+  | "gt"
+  | "gte"
+  | "like"
+  | "in"
+  | "is_null"
+  | "not_null";
+
+export interface QueryFilter {
+  field: string;
+  op: QueryFilterOp;
+  value?: unknown; // array for "in"; omitted for is_null / not_null
+}
+
+export interface EntityQuery {
+  entity: QueryEntity;
+  fields?: string[]; // column names, e.g. "trending_score"; empty = all; "id" is always returned
+  filters?: QueryFilter[];
+  sort?: { field: string; desc?: boolean }[];
+  // products: links, active_links, ads, latest_ad; links / ads: product
+  include?: string[];
+  limit?: number; // default 100, max 1000
+  offset?: number;
+}
+
+// Payload of products://changed, links://changed, ads://changed
+export interface DataChanged {
+  action: "created" | "updated" | "deleted";
+  ids: number[];
+}
+
+// Counts from load_demo_data
+export interface DemoDataSummary {
+  products: number;
+  links: number;
+  ads: number;
+  clicks: number;
+  conversions: number;
+  days: number; // history spans this many days back
+}
+
+export interface WorkspaceReset {
+  tablesCleared: string[];
+  rowsDeleted: number;
+}
+
+// First-run choice and starter product pack
+export interface SeedStatus {
+  preference?: "seeded" | "blank"; // unset until the first-run prompt is answered
+  appliedVersion: number;
+  latestVersion: number;
+  updateAvailable: boolean;
+}
+
+export interface SeedResult {
+  productIds: number[]; // starter products added
+  skipped: number; // already in the workspace
+  seedVersion: number;
+}
+
+// Affiliate program applications
+export type ApplicationStatus = "not_applied" | "applied" | "approved" | "rejected";
+
+export interface ProgramApplication {
+  id: number;
+  programName: string;
+  platform?: string | null;
+  status: ApplicationStatus;
+  signupUrl?: string | null;
+  appliedAt?: string | null;
+  decidedAt?: string | null;
+  contactName?: string | null;
+  contactEmail?: string | null;
+  notes?: string | null;
+  remindAt?: string | null; // Next follow-up while the application is pending
+  createdAt?: string | null;
+  updatedAt?: string | null;
+}
+
+export interface SaveProgramApplicationInput {
+  programName: string;
+  platform?: string | null;
+  status: ApplicationStatus;
+  signupUrl?: string | null;
+  appliedAt?: string | null; // YYYY-MM-DD or YYYY-MM-DD HH:MM:SS; filled in from the status when empty
+  decidedAt?: string | null;
+  contactName?: string | null;
+  contactEmail?: string | null;
+  notes?: string | null;
+  remindAt?: string | null;
+}
+
+// Affiliate network terms (reference data, editable)
+export interface NetworkTerms {
+  id: number;
+  network: string;
+  platform?: string | null;
+  minPayout?: number | null;
+  payoutCurrency?: string | null;
+  paymentSchedule?: string | null;
+  cookieDays?: number | null; // null when each merchant sets its own
+  cookiePolicy?: string | null;
+  deepLinkSupport?: boolean | null;
+  notes?: string | null;
+  builtin: boolean; // Shipped with the app; editable but not deletable
+  updatedAt?: string | null;
+}
+
+export interface SaveNetworkTermsInput {
+  id?: number | null;
+  network: string;
+  platform?: string | null;
+  minPayout?: number | null;
+  payoutCurrency?: string | null;
+  paymentSchedule?: string | null;
+  cookieDays?: number | null;
+  cookiePolicy?: string | null;
+  deepLinkSupport?: boolean | null;
+  notes?: string | null;
+}
+
+// Deep links through affiliate networks
+export type DeepLinkNetwork = "impact" | "cj" | "shareasale" | "awin";
+
+export interface ProgramNetworkIds {
+  programName: string;
+  network: DeepLinkNetwork;
+  merchantId?: string | null; // Impact campaign ID, ShareASale merchant ID, Awin advertiser ID
+  adId?: string | null; // Impact ad ID, ShareASale banner ID
+  trackingDomain?: string | null; // Impact brand tracking domain
+}
+
+export interface BuildDeepLinkRequest {
+  productId: number;
+  program: string;
+  destinationUrl: string;
+}
+
+// Pretty link export for WordPress and static sites
+export type PrettyLinksFormat = "htaccess" | "redirects" | "pretty_links" | "thirsty_affiliates";
+
+export interface PrettyLink {
+  linkId: number;
+  productId: number;
+  name: string;
+  slug: string;
+  path: string; // e.g. "/go/bamboo-cutting-board"
+  trackingUrl: string;
+}
+
+export interface PrettyLinksExport {
+  path: string;
+  format: PrettyLinksFormat;
+  links: PrettyLink[];
+}
+
+// Meta Commerce / Google Merchant Center catalog feeds
+export type CatalogFeedFormat = "csv" | "xml";
+
+export interface FeedSkip {
+  productId: number;
+  name: string;
+  reason: string; // every failed required attribute, e.g. "link: no active affiliate link"
+}
+
+export interface CatalogFeedExport {
+  path: string;
+  format: CatalogFeedFormat;
+  exported: number;
+  skipped: FeedSkip[];
+}
+
+// Product readiness checklist
+export interface ReadinessConfig {
+  requireImage: boolean;
+  requireLink: boolean;
+  minAds: number; // 0 disables the ad count check
+  requireDisclosure: boolean;
+  requirePriceVerified: boolean;
+  priceMaxAgeDays: number;
+  disclosureText?: string | null;
+}
+
+export type ReadinessCheckKey = "image" | "link" | "ads" | "disclosure" | "price_verified";
+
+export interface ReadinessCheck {
+  key: ReadinessCheckKey;
+  label: string;
+  required: boolean;
+  passed: boolean;
+  detail?: string | null;
+}
+
+export interface ProductReadiness {
+  productId: number;
+  productName: string;
+  ready: boolean;
+  checks: ReadinessCheck[];
+}
+
+// Posting calendar
+export type ScheduledPostStatus = "scheduled" | "posted" | "skipped" | "cancelled";
+
+export interface ScheduledPost {
+  id: number;
+  productId: number;
+  productName: string;
+  adId?: number | null;
+  platform: string;
+  scheduledAt: string; // UTC
+  status: ScheduledPostStatus;
+  notes?: string | null;
+  repeatEveryDays?: number | null; // Set on the head of a recurring series
+  repeatUntil?: string | null;
+  rotateAds: boolean;
+  seriesId?: number | null; // The series head this occurrence was expanded from
+  approvalState: ApprovalState;
+  createdAt?: string | null;
+  updatedAt?: string | null;
+}
+
+export interface SaveScheduledPostInput {
+  id?: number | null;
+  productId: number;
+  adId?: number | null;
+  platform: string;
+  scheduledAt: string;
+  notes?: string | null;
+  repeatEveryDays?: number | null;
+  repeatUntil?: string | null;
+  rotateAds?: boolean | null;
+  allowConflicts?: boolean | null; // Save despite posting cap conflicts when caps are enforced
+}
+
+export interface SuggestedSlot {
+  weekday: number; // 0 = Sunday, audience local time
+  hour: number;
+  score: number; // 0-1
+  scheduledAt: string; // Next occurrence, UTC
+}
+
+export interface PostTimeSuggestion {
+  productId: number;
+  platform: string;
+  utcOffsetMinutes: number;
+  best: SuggestedSlot;
+  alternatives: SuggestedSlot[];
+  clicksConsidered: number;
+  reasons: string[];
+}
+
+export interface PlatformPostingCap {
+  platform: string;
+  dailyCap: number | null; // null = no cap
+  weeklyCap: number | null; // Per calendar week, Monday to Sunday
+}
+
+export interface PostingRules {
+  caps: PlatformPostingCap[];
+  duplicateWindowHours: number;
+  enforce: boolean; // Reject conflicting posts instead of warning
+}
+
+export type ScheduleConflictKind = "daily_cap" | "weekly_cap" | "duplicate" | "out_of_stock" | "missing_alt_text";
+
+export interface ScheduleConflict {
+  kind: ScheduleConflictKind;
+  platform: string;
+  message: string;
+  postIds: number[];
+}
+
+export interface SavedScheduledPost {
+  post: ScheduledPost;
+  conflicts: ScheduleConflict[];
+}
+
+export interface RetailEvent {
+  id: number;
+  name: string;
+  month: number; // 1-12
+  day: number | null; // Fixed day of the month
+  weekday: number | null; // 0 = Sunday; used with weekOfMonth when day is null
+  weekOfMonth: number | null; // 1-5, or -1 for the last one
+  offsetDays: number;
+  leadDays: number; // How far ahead promotion should start
+  categories: string[]; // Keywords matched against product categories and tags
+  enabled: boolean;
+  builtin: boolean;
+}
+
+export interface SaveRetailEventInput {
+  id?: number | null;
+  name: string;
+  month: number;
+  day?: number | null;
+  weekday?: number | null;
+  weekOfMonth?: number | null;
+  offsetDays?: number | null;
+  leadDays?: number | null;
+  categories: string[];
+  enabled?: boolean | null;
+}
+
+export interface UpcomingEvent {
+  event: RetailEvent;
+  date: string; // YYYY-MM-DD
+  daysUntil: number;
+  promoteFrom: string;
+}
+
+export interface CampaignSuggestion {
+  event: UpcomingEvent;
+  categories: string[];
+  productIds: number[];
+  productNames: string[];
+  message: string;
+}
+
+export interface ProductSeasonality {
+  productId: number;
+  score: number; // 0-1
+  event: UpcomingEvent | null;
+}
+
+export type ApprovalState = "draft" | "in_review" | "approved" | "published";
+
+export type ApprovalEntityType = "ad" | "scheduled_post";
+
+export interface ApprovalTransition {
+  id: number;
+  entityType: ApprovalEntityType;
+  entityId: number;
+  fromState: ApprovalState;
+  toState: ApprovalState;
+  reviewer: string | null;
+  comment: string | null;
+  createdAt: string | null;
+}
+
+export interface WorkspaceLock {
+  editor: string;
+  host: string;
+  pid: number;
+  acquiredAt: string;
+  heartbeatAt: string;
+}
+
+export interface SharedWorkspaceStatus {
+  databasePath: string;
+  shared: boolean;
+  editorName: string;
+  lock: WorkspaceLock | null;
+  heldByMe: boolean;
+  lockStale: boolean; // The holder stopped refreshing it
+}
+
+export type Role = "owner" | "contributor";
+
+export interface RoleStatus {
+  passwordSet: boolean;
+  role: Role;
+  unlockedUntil: string | null;
+}
+
+/** Thrown (JSON-encoded) by owner-only commands while the session is a contributor */
+export interface OwnerRequiredError {
+  code: "owner_required";
+  action: string;
+  message: string;
+}
+
+export interface ReadOnlyStatus {
+  enabled: boolean;
+  reason: string | null; // e.g. "Presenting" or "Opened from a backup"
+  since: string | null; // UTC
+}
+
+/** Thrown (JSON-encoded) by any command that would change a read-only workspace */
+export interface ReadOnlyError {
+  code: "read_only";
+  command: string;
+  message: string;
+}
+
+export interface DataExportSummary {
+  path: string;
+  tables: number;
+  rows: number;
+}
+
+export interface DataWipeSummary {
+  tablesCleared: string[];
+  rowsDeleted: number;
+  filesDeleted: number;
+}
+
+/** Days to keep raw data; 0 keeps it forever */
+export interface RetentionPolicy {
+  clickDays: number; // Older clicks are folded into daily rollups
+  logDays: number;
+}
+
+export interface RetentionReport {
+  clicksRolledUp: number;
+  rollupDays: number;
+  postbacksDeleted: number;
+  webhookDeliveriesDeleted: number;
+  aiCacheExpired: number;
+}
+
+/** Clicks and conversions for one day */
+export interface DailyMetric {
+  day: string; // YYYY-MM-DD
+  clicks: number; // Human clicks
+  botClicks: number;
+  uniqueVisitors: number;
+  conversions: number;
+  revenue: number;
+  commission: number;
+}
+
+export interface DailyMetricsStatus {
+  through: string | null; // Last day the nightly rollup materialized
+  rows: number;
+}
+
+/** A cohort's activity in one month of its life */
+export interface CohortMonth {
+  offset: number; // months since the cohort month
+  month: string; // YYYY-MM
+  clicks: number;
+  conversions: number;
+  commission: number;
+  activeProducts: number; // with at least one click
+  cumulativeCommissionPerProduct: number;
+}
+
+/** Products added in the same month */
+export interface ProductCohort {
+  cohort: string; // YYYY-MM
+  productCount: number;
+  clicks: number;
+  commission: number;
+  months: CohortMonth[]; // cohort month through now
+}
+
+export interface ProductCohortReport {
+  cohorts: ProductCohort[]; // oldest first
+  through: string; // YYYY-MM-DD
+}
+
+/** A statement slower than the threshold, grouped by SQL (literals redacted) */
+export interface SlowQuery {
+  sql: string;
+  count: number;
+  totalMs: number;
+  maxMs: number;
+  lastMs: number;
+  lastSeen: string;
+}
+
+export interface SlowQueryReport {
+  thresholdMs: number;
+  queries: SlowQuery[]; // Slowest total time first
+}
+
+/** EXPLAIN QUERY PLAN for one of the main list queries */
+export interface QueryPlan {
+  name: string;
+  sql: string;
+  plan: string[]; // Indented by depth
+  fullScan: boolean;
+  tempSort: boolean;
+}
+
+export interface ProductListFilter {
+  search?: string; // name, category, or description contains
+  category?: string;
+  stage?: ProductStage;
+  tag?: string;
+}
+
+export interface LinkListFilter {
+  search?: string; // product or program name contains
+  productId?: number;
+  status?: string;
+  platform?: string;
+  programName?: string;
+}
+
+export interface ListSort {
+  field: string; // column name, e.g. "trending_score"
+  desc?: boolean;
+}
+
+/** One window of a filtered list and the total it was cut from */
+export interface ListPage<T> {
+  items: T[];
+  total: number;
+  offset: number;
+}
+
+export interface ProductCsvExport {
+  path: string;
+  rows: number;
+  columns: string[]; // in file order, e.g. "name", "link_url:amazon", "total_clicks"
+}
+
+export interface WatchedProduct {
+  productId: number;
+  productName: string;
+  productUrl?: string;
+  lastTitle?: string;
+  lastPrice?: number;
+  lastAvailability?: ProductAvailability;
+  lastCheckedAt?: string; // unset until the first check records a baseline
+  lastError?: string;
+}
+
+export interface ProductChange {
+  id: number;
+  productId: number;
+  productName: string;
+  field: "title" | "price" | "availability" | "image"; // "image" comes from store syncs
+  oldValue?: string;
+  newValue?: string;
+  detectedAt?: string;
+}
+
+export interface WatchCheckReport {
+  checked: number;
+  failed: number;
+  changes: ProductChange[];
+}
+
+export interface CommissionOption {
+  programName: string;
+  platform?: string;
+  commissionRate?: number; // fraction, 0.08 = 8%
+  cookieDays?: number;
+  hasLink: boolean; // an active affiliate link exists for it
+}
+
+export interface ComparedProduct {
+  productId: number;
+  name: string;
+  category: string;
+  stage?: ProductStage;
+  availability?: ProductAvailability;
+  priceRange?: string;
+  priceLow?: number;
+  priceHigh?: number;
+  trendingScore: number;
+  weeklyClicks: number[]; // oldest week first
+  trend: "rising" | "falling" | "flat";
+  commissionOptions: CommissionOption[];
+  bestCommissionRate?: number;
+  // Performance over the comparison period
+  clicks: number;
+  conversions: number;
+  revenue: number;
+  commission: number;
+  conversionRate?: number;
+  earningsPerClick?: number;
+}
+
+export interface ComparisonRow {
+  key: string; // e.g. "price", "best_commission", "clicks"
+  label: string;
+  values: (string | null)[]; // formatted, in products order
+  best?: number; // index of the leading product
+}
+
+export interface ProductComparison {
+  products: ComparedProduct[];
+  rows: ComparisonRow[];
+  periodDays: number;
+  context: string; // Markdown table for comparison-article prompts
+}
+
+// Product Bundles
+export interface BundleMember {
+  productId: number;
+  productName: string;
+  priceRange?: string;
+  imageUrl?: string;
+  position: number;
+}
+
+export interface Bundle {
+  id: number;
+  name: string;
+  description?: string;
+  members: BundleMember[]; // in ad order
+  createdAt?: string;
+  updatedAt?: string;
+}
+
+export interface SaveBundleInput {
+  id?: number; // unset to create
+  name: string;
+  description?: string;
+  productIds: number[]; // at least two, in display order
+}
+
+export interface BundleMemberMetrics {
+  productId: number;
+  productName: string;
+  clicks: number;
+  commission: number;
+}
+
+export interface BundlePerformance {
+  bundleId: number;
+  start: string; // YYYY-MM-DD
+  end: string;
+  clicks: number;
+  conversions: number;
+  revenue: number;
+  commission: number;
+  members: BundleMemberMetrics[];
+}
+
+// Notes
+export type NoteEntityType = "product" | "link" | "ad" | "campaign";
+
+export interface NoteRef {
+  entityType: NoteEntityType;
+  entityId: number;
+  label?: string; // product or campaign name, link URL, ad headline
+}
+
+export interface Note {
+  id: number;
+  title?: string;
+  body: string; // Markdown; [[product:12]] links a product
+  refs: NoteRef[];
+  createdAt?: string;
+  updatedAt?: string;
+}
+
+export interface SaveNoteInput {
+  id?: number; // unset to create
+  title?: string;
+  body: string;
+  refs?: NoteRef[]; // in addition to backlinks in the body
+}
+
+export interface NoteSearchHit {
+  note: Note;
+  snippet: string; // matches wrapped in **
+}
+
+// Attachments
+export type AttachmentEntityType = "product" | "campaign" | "credential";
+
+export interface Attachment {
+  id: number;
+  entityType: AttachmentEntityType;
+  entityId: number;
+  fileName: string;
+  mimeType: string;
+  sizeBytes: number;
+  description?: string;
+  createdAt?: string;
+}
+
+// Promotion Queue
+export interface ScoreFactor {
+  key: "trending" | "commission" | "competition" | "epc" | "seasonality";
+  label: string;
+  value: number; // 0-1, higher is better
+  weight: number; // weights sum to 1
+  detail: string;
+}
+
+export interface PromotionCandidate {
+  productId: number;
+  productName: string;
+  category: string;
+  score: number; // 0-100
+  factors: ScoreFactor[];
+  reasons: string[]; // strongest factors first
+}
+
+// Platform Mix Simulator
+export interface Benchmark {
+  id: number;
+  platform: string; // "other" covers platforms without a row
+  category: string; // "" = any category
+  ctr: number; // clicks per impression, 0.01 = 1%
+  conversionRate: number; // conversions per click
+  aovMultiplier: number; // applied to the product's order value
+  builtin: boolean; // shipped; can be reset but not deleted
+  edited: boolean;
+}
+
+export interface SaveBenchmarkInput {
+  id?: number; // unset to add
+  platform: string;
+  category?: string;
+  ctr: number;
+  conversionRate: number;
+  aovMultiplier?: number;
+}
+
+export interface PlatformAllocation {
+  platform: string;
+  share: number; // relative weight, normalized per scenario
+}
+
+export interface MixScenario {
+  name: string;
+  allocations: PlatformAllocation[];
+}
+
+export interface SimulationAssumptions {
+  impressions: number;
+  scenarios?: MixScenario[]; // empty: even split plus all-in on each platform
+  averageOrderValue?: number;
+  commissionRate?: number;
+}
+
+export interface PlatformProjection {
+  platform: string;
+  share: number;
+  impressions: number;
+  ctr: number;
+  conversionRate: number;
+  orderValue: number; // after the benchmark's multiplier
+  clicks: number;
+  conversions: number;
+  revenue: number;
+  earnings: number;
+}
+
+export interface ScenarioProjection {
+  name: string;
+  platforms: PlatformProjection[];
+  clicks: number;
+  conversions: number;
+  earnings: number;
+}
+
+export interface PlatformMixSimulation {
+  productId: number;
+  productName: string;
+  impressions: number;
+  averageOrderValue: number; // before each benchmark's multiplier
+  commissionRate: number;
+  scenarios: ScenarioProjection[]; // highest earnings first
+  warnings: string[];
+}
+
+/** What a free-text target audience says */
+export interface AudienceProfile {
+  ageMin: number;
+  ageMax: number;
+  gender: "female" | "male" | "mixed" | "unspecified";
+  femaleShare?: number | null; // 0-1
+  interests: string[]; // "home_decor", "gaming", ...
+  locale?: string | null; // ISO country code
+}
+
+export interface TraceInputs {
+  category: string;
+  targetAudience: string; // product audience, else the niche's, else "Age 25-45"
+  audience: AudienceProfile;
+  priceRange: string;
+  priceTier: "low" | "medium" | "high" | "premium";
+  trendingScore: number;
+  trendMomentum?: number | null; // trending change per week, null without history
+  bestCommissionRate?: number | null; // fraction, 0.08 = 8%
+  cookieDays?: number | null; // of the best-paying program
+  niche?: string | null;
+  preferredPlatforms: string[];
+}
+
+export interface PlatformTrace {
+  platform: string;
+  breakdown: MatchBreakdown; // includes the niche boost when one applied
+  included: boolean; // false when at or below the discovery cut-off
+  rank?: number | null; // 1-based among included platforms
+}
+
+export interface AdTypeFactors {
+  adType: string;
+  categoryScore: number;
+  audienceScore: number;
+  trendingScore: number;
+  platformScore: number;
+  commissionAdjustment: number; // nurture vs volume shift from commission and price
+  totalScore: number;
+}
+
+export interface EngagementTrace {
+  baseEngagement: number;
+  platformBoost: number;
+  score: number;
+}
+
+export interface RecommendationTrace {
+  inputs: TraceInputs;
+  platforms: PlatformTrace[]; // ranked platforms first, then excluded ones
+  recommendedPlatform: string;
+  recommendedAdType: string;
+  adTypeRule: string;
+  engagement: EngagementTrace;
+  adTypeFactors: AdTypeFactors[]; // best first
+  fingerprint: string; // equal for identical inputs
+}
+
+// Category Keywords
+export type CategoryKeywordKind = "ad_type" | "platform";
+
+export interface CategoryKeyword {
+  id: number;
+  kind: CategoryKeywordKind;
+  target: string; // ad type key ("video_script", ...) or platform ("tiktok", ...)
+  keyword: string; // "" = any category no other keyword matches
+  score: number; // 0-1; the highest matching keyword wins
+  builtin: boolean; // shipped; can be reset but not deleted
+  edited: boolean;
+}
+
+export interface SaveCategoryKeywordInput {
+  id?: number; // unset to add
+  kind: CategoryKeywordKind;
+  target: string;
+  keyword?: string;
+  score: number;
+}
+
+/** One recorded trending score */
+export interface TrendPoint {
+  trendingScore: number;
+  recordedAt: string;
+}
+
+export interface TrendMomentum {
+  direction: "rising" | "steady" | "declining";
+  changePerWeek: number; // least-squares slope over the window
+  firstScore: number;
+  latestScore: number;
+  points: number;
+  days: number; // span from the first to the latest point
+}
+
+export interface TrendHistory {
+  productId: number;
+  points: TrendPoint[]; // oldest first
+  momentum?: TrendMomentum | null; // null with fewer than two points a day apart
+}
+
+// Ad Variation Bandit
+export interface RecordVariationPerformanceInput {
+  adId: number;
+  platform: string;
+  day?: string; // YYYY-MM-DD; default today
+  impressions: number;
+  clicks: number;
+}
+
+/** One of a product's ads competing for the next post */
+export interface VariationArm {
+  adId: number;
+  variationName?: string | null;
+  headline: string;
+  impressions: number;
+  clicks: number;
+  observedCtr?: number | null; // null before any impressions
+  sampledCtr: number; // this draw from the ad's posterior
+}
+
+export interface NextVariation {
+  productId: number;
+  platform: string;
+  adId: number; // the ad to post next
+  priorCtr: number; // platform benchmark for the product's category
+  exploring: boolean; // the chosen ad isn't the best one observed so far
+  arms: VariationArm[]; // highest sampled CTR first
+}
+
+// Experiments
+export type ExperimentMetric = "ctr" | "conversion_rate";
+
+export interface ExperimentVariant {
+  id: number;
+  name: string;
+  adId?: number | null;
+  adHeadline?: string | null;
+  linkId?: number | null;
+  linkName?: string | null;
+  position: number; // 0 = control
+}
+
+export interface Experiment {
+  id: number;
+  name: string;
+  hypothesis?: string | null;
+  successMetric: ExperimentMetric;
+  status: "running" | "stopped";
+  startedAt: string;
+  endedAt?: string | null;
+  variants: ExperimentVariant[]; // control first
+  createdAt?: string | null;
+}
+
+export interface ExperimentVariantInput {
+  name?: string; // default "Variant A", "Variant B", ...
+  adId?: number; // required for ctr
+  linkId?: number; // default: the ad's attached link; required for conversion_rate
+}
+
+export interface StartExperimentInput {
+  name: string;
+  hypothesis?: string;
+  successMetric: ExperimentMetric;
+  variants: ExperimentVariantInput[]; // first is the control
+}
+
+export interface VariantResult {
+  variantId: number;
+  name: string;
+  isControl: boolean;
+  trials: number; // impressions for ctr, clicks for conversion_rate
+  successes: number;
+  rate?: number | null;
+  lift?: number | null; // vs control, 0.25 = +25%
+  zScore?: number | null;
+  pValue?: number | null; // two-sided
+  significant: boolean;
+}
+
+export interface ExperimentAnalysis {
+  experiment: Experiment;
+  start: string;
+  end: string; // stop time, or now while running
+  confidenceLevel: number;
+  variants: VariantResult[]; // control first
+  winnerVariantId?: number | null;
+}
+
+export type RefreshPeriod = "24h" | "7d" | "30d" | "90d" | "all";
+
+export interface LinkSnapshot {
+  platform: string;
+  programName: string;
+  commissionRate?: number | null;
+  cookieDuration?: number | null;
+  trackingUrl: string;
+  destinationUrl: string;
+  status: AffiliateLink["status"];
+}
+
+export interface LinkRefreshDiff {
+  id: number;
+  linkId: number;
+  productId: number;
+  productName: string;
+  operationId?: number | null; // bulk refresh run
+  before: LinkSnapshot;
+  after: LinkSnapshot;
+  changedFields: (keyof LinkSnapshot)[];
+  refreshedAt?: string | null;
+}
+
+export interface RefreshFieldCount {
+  field: keyof LinkSnapshot;
+  count: number;
+}
+
+export interface RefreshDiffReport {
+  period: RefreshPeriod;
+  since?: string | null; // null for "all"
+  linksChanged: number;
+  fieldCounts: RefreshFieldCount[]; // most changed first
+  diffs: LinkRefreshDiff[]; // newest first
+}
+
+export type ImportTarget = "products" | "links" | "earnings";
+
+export interface ImportField {
+  name: string; // e.g. "tracking_url"
+  label: string;
+  required: boolean;
+}
+
+export interface ColumnMapping {
+  column: number; // 0-based index into ImportAnalysis.columns
+  field: string;
+}
+
+export interface ImportTargetSuggestion {
+  target: ImportTarget;
+  fields: ImportField[];
+  mappings: ColumnMapping[]; // recognized from the headers
+  missingRequired: string[]; // e.g. "Date", "Product or Product ID"
+}
+
+export interface ImportAnalysis {
+  path: string;
+  delimiter: string;
+  columns: string[];
+  rowCount: number;
+  sampleRows: string[][];
+  suggestedTarget?: ImportTarget | null; // null when no target has its required columns
+  targets: ImportTargetSuggestion[];
+}
+
+export interface ImportPlan {
+  path: string;
+  target: ImportTarget;
+  mappings: ColumnMapping[];
+  createMissingProducts?: boolean; // links only
+}
+
+export interface ImportRowSkip {
+  row: number; // 1-based, excluding the header
+  reason: string;
+}
+
+export interface ImportResult {
+  target: ImportTarget;
+  imported: number;
+  importedIds: number[]; // product, link, or conversion ids
+  createdProductIds: number[];
+  skipped: ImportRowSkip[];
+}
+
+export type StoreProvider = "shopify" | "woocommerce";
+
+export interface StoreSkuMapping {
+  id: number;
+  store: StoreProvider;
+  sku: string; // "id:<external id>" for store items without a SKU
+  externalId?: string | null;
+  productId: number;
+  productName: string;
+  lastPrice?: number | null;
+  lastImageUrl?: string | null;
+  lastSyncedAt?: string | null; // null until the SKU is seen in the store
+}
+
+export interface StoreSyncReport {
+  store: StoreProvider;
+  fetched: number; // store items read, one per variant
+  createdProductIds: number[]; // imports only
+  matchedProductIds: number[]; // imports only: existing products mapped by name
+  updatedProductIds: number[]; // price or image changed
+  unmapped: number; // syncs only
+}
+
+export interface TikTokSyncReport {
+  source: "shop" | "showcase"; // "shop" when a shop cipher is configured
+  fetched: number;
+  linkedProductIds: number[]; // existing products matched by name
+  createdProductIds: number[];
+  alreadyLinked: number; // items a product already carries the TikTok id of
+}